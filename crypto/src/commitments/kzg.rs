@@ -268,6 +268,7 @@ mod tests {
             traits::{IsEllipticCurve, IsPairing},
         },
         field::element::FieldElement,
+        msm::fixed_base::{optimum_window_size, FixedBaseTable},
         polynomial::Polynomial,
         traits::{AsBytes, Deserializable},
         unsigned_integer::element::U256,
@@ -298,10 +299,9 @@ mod tests {
         });
         let g1 = BLS12381Curve::generator();
         let g2 = BLS12381TwistCurve::generator();
+        let g1_table = FixedBaseTable::new(&g1, 255, optimum_window_size(255, 100));
         let powers_main_group: Vec<G1> = (0..100)
-            .map(|exponent| {
-                g1.operate_with_self(toxic_waste.pow(exponent as u128).representative())
-            })
+            .map(|exponent| g1_table.mul(&toxic_waste.pow(exponent as u128).representative()))
             .collect();
         let powers_secondary_group = [
             g2.clone(),
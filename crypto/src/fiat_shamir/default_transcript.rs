@@ -62,8 +62,23 @@ where
         self.hasher.clone().finalize().into()
     }
 
+    /// Decodes a sample of 32 bytes, which fields with large enough encodings reduce modulo
+    /// their order. Fields that only decode canonical encodings of fewer bytes take instead
+    /// the first bytes of new samples, until they are a canonical encoding.
     fn sample_field_element(&mut self) -> FieldElement<F> {
-        FieldElement::from_bytes_be(&self.sample()).unwrap()
+        if let Ok(element) = FieldElement::from_bytes_be(&self.sample()) {
+            return element;
+        }
+        let encoding_len = FieldElement::<F>::zero().to_bytes_be().len();
+        // Each attempt decodes fresh hash output, so it is accepted independently with the
+        // probability that a random encoding is canonical. This is about 1/16 at worst, for
+        // the degree 4 extension of Mersenne31, so the loop ends after 16 attempts on average
+        // and runs n attempts only with probability below (16/17)^n.
+        loop {
+            if let Ok(element) = FieldElement::from_bytes_be(&self.sample()[..encoding_len]) {
+                return element;
+            }
+        }
     }
 
     fn sample_u64(&mut self, upper_bound: u64) -> u64 {
@@ -116,4 +131,51 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn field_elements_with_canonical_encodings_are_sampled() {
+        use lambdaworks_math::field::fields::u64_goldilocks_field::Goldilocks64ExtensionField;
+
+        let mut transcript = DefaultTranscript::<Goldilocks64ExtensionField>::new(b"seed");
+        let element = transcript.sample_field_element();
+        let mut replay = DefaultTranscript::<Goldilocks64ExtensionField>::new(b"seed");
+        assert_eq!(replay.sample_field_element(), element);
+
+        // A sample of 32 bytes isn't an encoding, so the first 16 bytes of the next one are used.
+        let mut samples = DefaultTranscript::<Goldilocks64ExtensionField>::new(b"seed");
+        samples.sample();
+        assert_eq!(
+            FieldElement::from_bytes_be(&samples.sample()[..16]).ok(),
+            Some(element)
+        );
+    }
+
+    #[test]
+    fn non_canonical_candidates_are_rejected_until_a_canonical_one_is_sampled() {
+        use lambdaworks_math::field::fields::mersenne31::extension::Mersenne31ComplexQuadraticExtensionField as QM31;
+
+        // Find a transcript state whose first 16 byte candidate has a coordinate not below
+        // the order of Mersenne31, which happens for roughly 15 out of 16 states.
+        let seed = (0_u32..)
+            .map(u32::to_be_bytes)
+            .find(|seed| {
+                let mut samples = DefaultTranscript::<QM31>::new(seed);
+                samples.sample();
+                FieldElement::<QM31>::from_bytes_be(&samples.sample()[..16]).is_err()
+            })
+            .unwrap();
+
+        let mut samples = DefaultTranscript::<QM31>::new(&seed);
+        samples.sample();
+        samples.sample();
+        let expected = core::iter::repeat_with(|| {
+            FieldElement::<QM31>::from_bytes_be(&samples.sample()[..16])
+        })
+        .find_map(Result::ok)
+        .unwrap();
+
+        let mut transcript = DefaultTranscript::<QM31>::new(&seed);
+        assert_eq!(transcript.sample_field_element(), expected);
+        assert_eq!(transcript.state(), samples.state());
+    }
 }
@@ -0,0 +1,103 @@
+//! The suites BLS12381G1_XMD:SHA-256_SSWU_RO_ and BLS12381G2_XMD:SHA-256_SSWU_RO_ of RFC 9380
+//! (section 8.8), which hash to the subgroups of order r of G1 and G2.
+
+use lambdaworks_math::{
+    cyclic_group::IsGroup,
+    elliptic_curve::short_weierstrass::curves::bls12_381::{
+        curve::{BLS12381FieldElement, BLS12381TwistCurveFieldElement},
+        map_to_curve::{map_to_curve_g1, map_to_curve_g2, G1Point, G2Point},
+    },
+    traits::ByteConversion,
+    unsigned_integer::element::U384,
+};
+
+use super::expand_message_xmd;
+
+/// Bytes hashed into each element of Fp: L = ceil((ceil(log2(p)) + k) / 8) for k = 128.
+const L: usize = 64;
+
+/// Hashes a message to a point of G1 of order r.
+pub fn hash_to_g1(msg: &[u8], dst: &[u8]) -> G1Point {
+    let [u0, u1] = hash_to_field::<2>(msg, dst);
+    map_to_curve_g1(&u0)
+        .operate_with(&map_to_curve_g1(&u1))
+        .clear_cofactor()
+}
+
+/// Hashes a message to a point of G2 of order r.
+pub fn hash_to_g2(msg: &[u8], dst: &[u8]) -> G2Point {
+    let [u0_c0, u0_c1, u1_c0, u1_c1] = hash_to_field::<4>(msg, dst);
+    let u0 = BLS12381TwistCurveFieldElement::new([u0_c0, u0_c1]);
+    let u1 = BLS12381TwistCurveFieldElement::new([u1_c0, u1_c1]);
+    map_to_curve_g2(&u0)
+        .operate_with(&map_to_curve_g2(&u1))
+        .clear_cofactor()
+}
+
+/// Hashes a message to `N` elements of Fp, which are the coordinates of `N / m` elements of an
+/// extension of degree m.
+/// https://www.rfc-editor.org/rfc/rfc9380#section-5.2
+fn hash_to_field<const N: usize>(msg: &[u8], dst: &[u8]) -> [BLS12381FieldElement; N] {
+    let uniform_bytes =
+        expand_message_xmd(msg, dst, N * L).expect("the length of the output is small");
+
+    // each chunk is the integer hi * 2^256 + lo, with both halves smaller than p
+    let two_to_the_256 = BLS12381FieldElement::from_hex_unchecked(
+        "10000000000000000000000000000000000000000000000000000000000000000",
+    );
+    core::array::from_fn(|i| {
+        let chunk = &uniform_bytes[i * L..(i + 1) * L];
+        let (hi, lo) = chunk.split_at(L / 2);
+        &two_to_the_256 * from_32_bytes(hi) + from_32_bytes(lo)
+    })
+}
+
+fn from_32_bytes(bytes: &[u8]) -> BLS12381FieldElement {
+    let mut padded = [0; 48];
+    padded[16..].copy_from_slice(bytes);
+    BLS12381FieldElement::new(U384::from_bytes_be(&padded).expect("48 bytes fit in a U384"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use lambdaworks_math::elliptic_curve::short_weierstrass::curves::bls12_381::compression::{
+        compress_g1_point, compress_g2_point,
+    };
+
+    fn hex(value: &str) -> Vec<u8> {
+        (0..value.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&value[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    // Test vectors of RFC 9380 (appendix J.9.1), compressed
+    #[test]
+    fn hash_to_g1_matches_rfc9380() {
+        let dst = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+        for (msg, expected) in [
+            (&b""[..], "852926add2207b76ca4fa57a8734416c8dc95e24501772c814278700eed6d1e4e8cf62d9c09db0fac349612b759e79a1"),
+            (b"abc", "83567bc5ef9c690c2ab2ecdf6a96ef1c139cc0b2f284dca0a9a7943388a49a3aee664ba5379a7655d3c68900be2f6903"),
+            (b"abcdef0123456789", "91e0b079dea29a68f0383ee94fed1b940995272407e3bb916bbf268c263ddd57a6a27200a784cbc248e84f357ce82d98"),
+            (&[b'a'; 512][..], "ab3802a797ceb88ddaf88e5063b50effd853bbb1d6dc4a949e01415e3f064261353f4c05a5187ca8708f74396e3cbfbb"),
+        ] {
+            assert_eq!(compress_g1_point(&hash_to_g1(msg, dst)), hex(expected));
+        }
+    }
+
+    // Test vectors of RFC 9380 (appendix J.10.1), compressed
+    #[test]
+    fn hash_to_g2_matches_rfc9380() {
+        let dst = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+        for (msg, expected) in [
+            (&b""[..], "a5cb8437535e20ecffaef7752baddf98034139c38452458baeefab379ba13dff5bf5dd71b72418717047f5b0f37da03d0141ebfbdca40eb85b87142e130ab689c673cf60f1a3e98d69335266f30d9b8d4ac44c1038e9dcdd5393faf5c41fb78a"),
+            (b"abc", "939cddbccdc5e91b9623efd38c49f81a6f83f175e80b06fc374de9eb4b41dfe4ca3a230ed250fbe3a2acf73a41177fd802c2d18e033b960562aae3cab37a27ce00d80ccd5ba4b7fe0e7a210245129dbec7780ccc7954725f4168aff2787776e6"),
+            (b"abcdef0123456789", "990d119345b94fbd15497bcba94ecf7db2cbfd1e1fe7da034d26cbba169fb3968288b3fafb265f9ebd380512a71c3f2c121982811d2491fde9ba7ed31ef9ca474f0e1501297f68c298e9f4c0028add35aea8bb83d53c08cfc007c1e005723cd0"),
+            (&[b'a'; 512][..], "a737c8cf44c709c52d31f7257fdf19c87083c6386f423c78f6778892777a652326701f6319c65e75cfeeacf3a8a86bea0bf02512dcb3848efb3dfcc5a7a5c650d674b370483fcef725fc87df2ce0e046c145df2637ceff50626c107c3ee16e52"),
+        ] {
+            assert_eq!(compress_g2_point(&hash_to_g2(msg, dst)), hex(expected));
+        }
+    }
+}
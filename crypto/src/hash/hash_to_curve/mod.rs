@@ -0,0 +1,120 @@
+//! Hashing to elliptic curves, as specified in RFC 9380.
+
+use alloc::vec::Vec;
+use sha2::{Digest, Sha256};
+
+pub mod bls12_381;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpandMessageError {
+    /// More than 255 blocks of output, or more than 65535 bytes, were requested.
+    InvalidLength,
+}
+
+/// Expands a message into `len_in_bytes` uniformly random bytes with SHA-256, as in
+/// https://www.rfc-editor.org/rfc/rfc9380#section-5.3.1
+/// Domain separation tags longer than 255 bytes are hashed first, as in section 5.3.3.
+pub fn expand_message_xmd(
+    msg: &[u8],
+    dst: &[u8],
+    len_in_bytes: usize,
+) -> Result<Vec<u8>, ExpandMessageError> {
+    const B_IN_BYTES: usize = 32;
+    const S_IN_BYTES: usize = 64;
+
+    let ell = len_in_bytes.div_ceil(B_IN_BYTES);
+    if ell > 255 || len_in_bytes > 65535 {
+        return Err(ExpandMessageError::InvalidLength);
+    }
+
+    let long_dst;
+    let dst = if dst.len() > 255 {
+        long_dst = Sha256::new()
+            .chain_update(b"H2C-OVERSIZE-DST-")
+            .chain_update(dst)
+            .finalize();
+        long_dst.as_slice()
+    } else {
+        dst
+    };
+    let dst_len = [dst.len() as u8];
+
+    let b_0 = Sha256::new()
+        .chain_update([0; S_IN_BYTES])
+        .chain_update(msg)
+        .chain_update((len_in_bytes as u16).to_be_bytes())
+        .chain_update([0])
+        .chain_update(dst)
+        .chain_update(dst_len)
+        .finalize();
+
+    let mut uniform_bytes = Vec::with_capacity(ell * B_IN_BYTES);
+    let mut b_i = Sha256::new()
+        .chain_update(b_0)
+        .chain_update([1])
+        .chain_update(dst)
+        .chain_update(dst_len)
+        .finalize();
+    uniform_bytes.extend_from_slice(&b_i);
+    for i in 2..=ell {
+        let mut block = b_0;
+        for (byte, previous) in block.iter_mut().zip(b_i) {
+            *byte ^= previous;
+        }
+        b_i = Sha256::new()
+            .chain_update(block)
+            .chain_update([i as u8])
+            .chain_update(dst)
+            .chain_update(dst_len)
+            .finalize();
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+    uniform_bytes.truncate(len_in_bytes);
+    Ok(uniform_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(value: &str) -> Vec<u8> {
+        (0..value.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&value[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    // Test vectors of RFC 9380 (appendix K.1)
+    #[test]
+    fn expand_message_xmd_matches_rfc9380() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        assert_eq!(
+            expand_message_xmd(b"", dst, 0x20),
+            Ok(hex(
+                "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"
+            ))
+        );
+        assert_eq!(
+            expand_message_xmd(b"abc", dst, 0x20),
+            Ok(hex(
+                "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"
+            ))
+        );
+        assert_eq!(
+            expand_message_xmd(b"abc", dst, 0x80),
+            Ok(hex("abba86a6129e366fc877aab32fc4ffc70120d8996c88aee2fe4b32d6c7b6437a647e6c3163d40b76a73cf6a5674ef1d890f95b664ee0afa5359a5c4e07985635bbecbac65d747d3d2da7ec2b8221b17b0ca9dc8a1ac1c07ea6a1e60583e2cb00058e77b7b72a298425cd1b941ad4ec65e8afc50303a22c0f99b0509b4c895f40"))
+        );
+    }
+
+    #[test]
+    fn expand_message_xmd_rejects_long_outputs() {
+        assert_eq!(
+            expand_message_xmd(b"", b"DST", 255 * 32 + 1),
+            Err(ExpandMessageError::InvalidLength)
+        );
+        assert_eq!(
+            expand_message_xmd(b"", b"DST", 255 * 32).unwrap().len(),
+            255 * 32
+        );
+    }
+}
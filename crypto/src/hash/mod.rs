@@ -1,3 +1,4 @@
+pub mod hash_to_curve;
 pub mod hash_to_field;
 pub mod monolith;
 pub mod pedersen;
@@ -0,0 +1,174 @@
+//! X25519 Diffie-Hellman key agreement, as specified in RFC 7748 (sections 5 and 6.1).
+//!
+//! Scalars and `u` coordinates of points of curve25519 are 32 little-endian bytes.
+
+use lambdaworks_math::{
+    elliptic_curve::{
+        edwards::curves::ed25519::field::{Curve25519FieldElement, CURVE25519_PRIME_FIELD_ORDER},
+        montgomery::{curves::curve25519::Curve25519, traits::IsMontgomery},
+    },
+    traits::ByteConversion,
+    unsigned_integer::element::U256,
+};
+
+/// `u` coordinate of the generator of curve25519.
+pub const BASE_POINT: [u8; 32] = {
+    let mut u = [0; 32];
+    u[0] = 9;
+    u
+};
+
+/// Multiplies the point of curve25519 with the given `u` coordinate by the clamped scalar, with
+/// the Montgomery ladder, and returns the `u` coordinate of the result.
+pub fn x25519(scalar: &[u8; 32], u: &[u8; 32]) -> [u8; 32] {
+    let mut k = *scalar;
+    k[0] &= 0b1111_1000;
+    k[31] &= 0b0111_1111;
+    k[31] |= 0b0100_0000;
+
+    let x1 = decode_u(u);
+    let mut x2 = Curve25519FieldElement::one();
+    let mut z2 = Curve25519FieldElement::zero();
+    let mut x3 = x1.clone();
+    let mut z3 = Curve25519FieldElement::one();
+    // (A - 2) / 4
+    let a24 = (Curve25519::a() - Curve25519FieldElement::from(2)) / Curve25519FieldElement::from(4);
+
+    let mut swap = 0;
+    for t in (0..255).rev() {
+        let k_t = u64::from((k[t / 8] >> (t % 8)) & 1);
+        swap ^= k_t;
+        cswap(swap, &mut x2, &mut x3);
+        cswap(swap, &mut z2, &mut z3);
+        swap = k_t;
+
+        let a = &x2 + &z2;
+        let aa = a.square();
+        let b = &x2 - &z2;
+        let bb = b.square();
+        let e = &aa - &bb;
+        let c = &x3 + &z3;
+        let d = &x3 - &z3;
+        let da = d * a;
+        let cb = c * b;
+        x3 = (&da + &cb).square();
+        z3 = &x1 * (da - cb).square();
+        z2 = &e * (&aa + &a24 * &e);
+        x2 = aa * bb;
+    }
+    cswap(swap, &mut x2, &mut x3);
+    cswap(swap, &mut z2, &mut z3);
+
+    // z2 is zero for points of small order, whose multiples are encoded as u = 0
+    let u = match z2.inv() {
+        Ok(z2_inv) => x2 * z2_inv,
+        Err(_) => Curve25519FieldElement::zero(),
+    };
+    u.to_bytes_le()
+        .try_into()
+        .expect("elements of the field of curve25519 have 32 bytes")
+}
+
+/// Public key of a secret scalar: the `u` coordinate of the scalar times the generator.
+pub fn public_key(secret: &[u8; 32]) -> [u8; 32] {
+    x25519(secret, &BASE_POINT)
+}
+
+/// Secret shared with the owner of the public key. Returns `None` when the public key has small
+/// order, since the shared secret is then zero and does not depend on the secret key.
+pub fn shared_secret(secret: &[u8; 32], public_key: &[u8; 32]) -> Option<[u8; 32]> {
+    let shared = x25519(secret, public_key);
+    (shared != [0; 32]).then_some(shared)
+}
+
+/// Swaps `a` and `b` when `swap` is 1 and leaves them as they are when it is 0, masking their
+/// limbs instead of branching so that the bits of the scalar don't change the operations done.
+fn cswap(swap: u64, a: &mut Curve25519FieldElement, b: &mut Curve25519FieldElement) {
+    let mask = core::hint::black_box(0u64.wrapping_sub(swap));
+    let mut a_limbs = a.value().limbs;
+    let mut b_limbs = b.value().limbs;
+    for (a_limb, b_limb) in a_limbs.iter_mut().zip(b_limbs.iter_mut()) {
+        let t = mask & (*a_limb ^ *b_limb);
+        *a_limb ^= t;
+        *b_limb ^= t;
+    }
+    *a = Curve25519FieldElement::from_raw(U256::from_limbs(a_limbs));
+    *b = Curve25519FieldElement::from_raw(U256::from_limbs(b_limbs));
+}
+
+/// Decodes a `u` coordinate, ignoring the most significant bit and reducing it modulo p.
+fn decode_u(bytes: &[u8; 32]) -> Curve25519FieldElement {
+    let mut bytes = *bytes;
+    bytes[31] &= 0b0111_1111;
+    let mut u = U256::from_bytes_le(&bytes).expect("32 bytes fit in a U256");
+    if u >= CURVE25519_PRIME_FIELD_ORDER {
+        u = u - CURVE25519_PRIME_FIELD_ORDER;
+    }
+    Curve25519FieldElement::new(u)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(value: &str) -> [u8; 32] {
+        let mut bytes = [0; 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&value[2 * i..2 * i + 2], 16).unwrap();
+        }
+        bytes
+    }
+
+    #[test]
+    fn cswap_swaps_only_when_the_bit_is_set() {
+        let (two, three) = (
+            Curve25519FieldElement::from(2),
+            Curve25519FieldElement::from(3),
+        );
+        let (mut a, mut b) = (two.clone(), three.clone());
+        cswap(0, &mut a, &mut b);
+        assert_eq!((&a, &b), (&two, &three));
+        cswap(1, &mut a, &mut b);
+        assert_eq!((a, b), (three, two));
+    }
+
+    #[test]
+    fn x25519_matches_rfc7748() {
+        let scalar = hex("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4");
+        let u = hex("e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c");
+        assert_eq!(
+            x25519(&scalar, &u),
+            hex("c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552")
+        );
+    }
+
+    #[test]
+    fn diffie_hellman_matches_rfc7748() {
+        let alice = hex("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+        let bob = hex("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb");
+        let alice_public = public_key(&alice);
+        let bob_public = public_key(&bob);
+        assert_eq!(
+            alice_public,
+            hex("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a")
+        );
+        assert_eq!(
+            bob_public,
+            hex("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f")
+        );
+
+        let shared = hex("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+        assert_eq!(shared_secret(&alice, &bob_public), Some(shared));
+        assert_eq!(shared_secret(&bob, &alice_public), Some(shared));
+    }
+
+    #[test]
+    fn small_order_public_keys_are_rejected() {
+        let secret = [42; 32];
+        // u = 0 and u = 1 have order 4 and 2
+        assert_eq!(shared_secret(&secret, &[0; 32]), None);
+        let mut one = [0; 32];
+        one[0] = 1;
+        assert_eq!(shared_secret(&secret, &one), None);
+    }
+}
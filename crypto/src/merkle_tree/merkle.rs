@@ -31,8 +31,12 @@ where
     B: IsMerkleTreeBackend,
 {
     pub fn build(unhashed_leaves: &[B::Data]) -> Self {
-        let mut hashed_leaves: Vec<B::Node> = B::hash_leaves(unhashed_leaves);
+        Self::build_from_hashed_leaves(B::hash_leaves(unhashed_leaves))
+    }
 
+    /// Builds a Merkle tree from the already hashed leaves. This allows hashing the data
+    /// in batches, without keeping all of it in memory at the same time.
+    pub fn build_from_hashed_leaves(mut hashed_leaves: Vec<B::Node>) -> Self {
         // If there is only one node, handle it specially
         if hashed_leaves.len() == 1 {
             hashed_leaves.push(hashed_leaves[0].clone());
@@ -116,6 +120,16 @@ mod tests {
         assert_eq!(merkle_tree.root, FE::new(8)); // Adjusted expected value
     }
 
+    #[test]
+    fn build_merkle_tree_from_hashed_leaves_matches_build() {
+        let values: Vec<FE> = (1..6).map(FE::new).collect();
+        let merkle_tree = MerkleTree::<TestBackend<U64PF>>::build(&values);
+        let hashed_leaves = TestBackend::<U64PF>::hash_leaves(&values);
+        let merkle_tree_from_hashes =
+            MerkleTree::<TestBackend<U64PF>>::build_from_hashed_leaves(hashed_leaves);
+        assert_eq!(merkle_tree.root, merkle_tree_from_hashes.root);
+    }
+
     #[test]
     fn build_merkle_tree_from_a_single_value() {
         const MODULUS: u64 = 13;
@@ -0,0 +1,470 @@
+//! BLS signatures over BLS12-381 with proofs of possession, as specified in
+//! draft-irtf-cfrg-bls-signature-05 and used by the Ethereum consensus layer.
+//!
+//! Both variants are supported: [`MinPk`], with public keys in G1 and signatures in G2, and
+//! [`MinSig`], with public keys in G2 and signatures in G1. Messages are hashed to the curve with
+//! the suites of RFC 9380. Signatures of the same message aggregate into a multi-signature,
+//! which is safe against rogue key attacks only if the proofs of possession of the keys were
+//! checked.
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use hmac::{Hmac, Mac};
+use lambdaworks_math::{
+    cyclic_group::IsGroup,
+    elliptic_curve::short_weierstrass::curves::bls12_381::default_types::{FrConfig, FrElement},
+    field::fields::montgomery_backed_prime_fields::IsModulus,
+    traits::ByteConversion,
+    unsigned_integer::element::U256,
+};
+use sha2::{Digest, Sha256};
+
+mod variants;
+
+pub use variants::{BlsVariant, MinPk, MinSig};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlsError {
+    /// The secret key is not in `[1, r)`, or the key material is shorter than 32 bytes.
+    InvalidSecretKey,
+    /// The public key is not a compressed point of order r.
+    InvalidPublicKey,
+    /// The signature is not a compressed point of the subgroup of order r.
+    InvalidSignature,
+    /// There is nothing to aggregate.
+    EmptyAggregation,
+}
+
+/// Secret key, a scalar in `[1, r)`. Points are multiplied by it with the same group
+/// operations for every key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SigningKey<V: BlsVariant> {
+    secret: FrElement,
+    variant: PhantomData<V>,
+}
+
+impl<V: BlsVariant> SigningKey<V> {
+    /// Derives a secret key from at least 32 bytes of secret key material, with `KeyGen`.
+    /// https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-2.3
+    pub fn key_gen(ikm: &[u8], key_info: &[u8]) -> Result<Self, BlsError> {
+        if ikm.len() < 32 {
+            return Err(BlsError::InvalidSecretKey);
+        }
+
+        let mut salt = Sha256::digest(b"BLS-SIG-KEYGEN-SALT-");
+        loop {
+            // HKDF-Extract and HKDF-Expand of RFC 5869, with an output of 48 bytes
+            let prk = hmac(&salt, &[ikm, &[0]]);
+            let length = 48_u16.to_be_bytes();
+            let t1 = hmac(&prk, &[key_info, &length, &[1]]);
+            let t2 = hmac(&prk, &[&t1, key_info, &length, &[2]]);
+
+            // the integer t1 || t2[..16] modulo r, in chunks of 128 bits which are smaller than r
+            let shift = FrElement::from_hex_unchecked("100000000000000000000000000000000");
+            let secret = [&t1[..16], &t1[16..], &t2[..16]]
+                .iter()
+                .fold(FrElement::zero(), |acc, chunk| {
+                    acc * &shift + scalar_from_be(chunk)
+                });
+            if secret != FrElement::zero() {
+                return Ok(Self::from_scalar(secret));
+            }
+            salt = Sha256::digest(salt);
+        }
+    }
+
+    /// Reads a big-endian secret key of 32 bytes.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self, BlsError> {
+        let secret = U256::from_bytes_be(bytes).map_err(|_| BlsError::InvalidSecretKey)?;
+        if secret == U256::from_u64(0) || secret >= FrConfig::MODULUS {
+            return Err(BlsError::InvalidSecretKey);
+        }
+        Ok(Self::from_scalar(FrElement::new(secret)))
+    }
+
+    fn from_scalar(secret: FrElement) -> Self {
+        Self {
+            secret,
+            variant: PhantomData,
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.secret
+            .to_bytes_be()
+            .try_into()
+            .expect("scalars of BLS12-381 have 32 bytes")
+    }
+
+    /// Public key `PK = SK * G`.
+    pub fn verifying_key(&self) -> VerifyingKey<V> {
+        VerifyingKey {
+            point: V::generator()
+                .operate_with_self_constant_time(&self.secret.representative(), &FrConfig::MODULUS),
+        }
+    }
+
+    /// Signs a message: `SK * H(message)`.
+    pub fn sign(&self, message: &[u8]) -> Signature<V> {
+        self.sign_with_dst(message, V::SIGNATURE_DST)
+    }
+
+    /// Proof of possession of the secret key: a signature of the public key, with the domain
+    /// separation tag of proofs of possession.
+    pub fn prove_possession(&self) -> Signature<V> {
+        self.sign_with_dst(&self.verifying_key().to_bytes(), V::POP_DST)
+    }
+
+    fn sign_with_dst(&self, message: &[u8], dst: &[u8]) -> Signature<V> {
+        Signature {
+            point: V::hash_to_point(message, dst)
+                .operate_with_self_constant_time(&self.secret.representative(), &FrConfig::MODULUS),
+        }
+    }
+}
+
+/// Public key, a point of order r.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyingKey<V: BlsVariant> {
+    point: V::PublicKeyPoint,
+}
+
+impl<V: BlsVariant> VerifyingKey<V> {
+    /// Reads a compressed public key, rejecting the point at infinity and points that are not in
+    /// the subgroup of order r, as `KeyValidate` does.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BlsError> {
+        V::public_key_from_bytes(bytes)
+            .filter(|point| !point.is_neutral_element())
+            .map(|point| Self { point })
+            .ok_or(BlsError::InvalidPublicKey)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        V::public_key_to_bytes(&self.point)
+    }
+
+    pub fn point(&self) -> &V::PublicKeyPoint {
+        &self.point
+    }
+
+    /// Aggregates public keys into the key that verifies the aggregate of their signatures of a
+    /// message.
+    pub fn aggregate(keys: &[Self]) -> Result<Self, BlsError> {
+        let (first, rest) = keys.split_first().ok_or(BlsError::EmptyAggregation)?;
+        let point = rest
+            .iter()
+            .fold(first.point.clone(), |acc, key| acc.operate_with(&key.point));
+        Ok(Self { point })
+    }
+
+    /// Verifies a signature of a message: `e(PK, H(message)) = e(G, signature)`.
+    pub fn verify(&self, message: &[u8], signature: &Signature<V>) -> bool {
+        self.verify_with_dst(message, signature, V::SIGNATURE_DST)
+    }
+
+    /// Verifies a proof of possession of the secret key.
+    pub fn verify_possession(&self, proof: &Signature<V>) -> bool {
+        self.verify_with_dst(&self.to_bytes(), proof, V::POP_DST)
+    }
+
+    fn verify_with_dst(&self, message: &[u8], signature: &Signature<V>, dst: &[u8]) -> bool {
+        // aggregated keys may add up to the point at infinity
+        if self.point.is_neutral_element() {
+            return false;
+        }
+        let hash = V::hash_to_point(message, dst);
+        V::pairing_product_is_one(&[
+            (&self.point, &hash),
+            (&V::generator().neg(), &signature.point),
+        ])
+    }
+}
+
+/// A signature, a point of the subgroup of order r.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature<V: BlsVariant> {
+    point: V::SignaturePoint,
+}
+
+impl<V: BlsVariant> Signature<V> {
+    /// Reads a compressed signature, rejecting points that are not in the subgroup of order r.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BlsError> {
+        V::signature_from_bytes(bytes)
+            .map(|point| Self { point })
+            .ok_or(BlsError::InvalidSignature)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        V::signature_to_bytes(&self.point)
+    }
+
+    pub fn point(&self) -> &V::SignaturePoint {
+        &self.point
+    }
+
+    /// Aggregates signatures, of the same or of different messages.
+    pub fn aggregate(signatures: &[Self]) -> Result<Self, BlsError> {
+        let (first, rest) = signatures.split_first().ok_or(BlsError::EmptyAggregation)?;
+        let point = rest.iter().fold(first.point.clone(), |acc, signature| {
+            acc.operate_with(&signature.point)
+        });
+        Ok(Self { point })
+    }
+}
+
+/// Verifies an aggregate of the signatures of each message by the key in the same position:
+/// `e(PK_1, H(message_1)) ... e(PK_n, H(message_n)) = e(G, signature)`. The messages need not be
+/// distinct, since the keys have proofs of possession.
+pub fn aggregate_verify<V: BlsVariant>(
+    keys: &[VerifyingKey<V>],
+    messages: &[&[u8]],
+    signature: &Signature<V>,
+) -> bool {
+    if keys.is_empty() || keys.len() != messages.len() {
+        return false;
+    }
+
+    let hashes: Vec<_> = messages
+        .iter()
+        .map(|message| V::hash_to_point(message, V::SIGNATURE_DST))
+        .collect();
+    let minus_generator = V::generator().neg();
+    let mut pairs: Vec<_> = keys.iter().map(|key| &key.point).zip(&hashes).collect();
+    pairs.push((&minus_generator, &signature.point));
+    V::pairing_product_is_one(&pairs)
+}
+
+/// Verifies a multi-signature: an aggregate of the signatures of the same message by all the
+/// keys, which must have proofs of possession.
+pub fn fast_aggregate_verify<V: BlsVariant>(
+    keys: &[VerifyingKey<V>],
+    message: &[u8],
+    signature: &Signature<V>,
+) -> bool {
+    VerifyingKey::aggregate(keys).is_ok_and(|key| key.verify(message, signature))
+}
+
+fn hmac(key: &[u8], parts: &[&[u8]]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes keys of any length");
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+/// Scalar of at most 32 big-endian bytes, smaller than r.
+fn scalar_from_be(bytes: &[u8]) -> FrElement {
+    let mut padded = [0; 32];
+    padded[32 - bytes.len()..].copy_from_slice(bytes);
+    FrElement::new(U256::from_bytes_be(&padded).expect("32 bytes fit in a U256"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(value: &str) -> Vec<u8> {
+        (0..value.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&value[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn signing_key<V: BlsVariant>(value: &str) -> SigningKey<V> {
+        SigningKey::from_bytes(&hex(value).try_into().unwrap()).unwrap()
+    }
+
+    // Keys, messages and signatures of the BLS tests of the Ethereum consensus specs
+    const SECRET_KEYS: [&str; 3] = [
+        "263dbd792f5b1be47ed85f8938c0f29586af0d3ac7b977f21c278fe1462040e3",
+        "47b8192d77bf871b62e87859d653922725724a5c031afeabc60bcef5ff665138",
+        "328388aff0d4a5b7dc9205abd374e7e98f3cd9f3418edb4eafda5fb16473d216",
+    ];
+    const PUBLIC_KEYS: [&str; 3] = [
+        "a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20fd6e10c1b77654d067c0618f6e5a7f79a",
+        "b301803f8b5ac4a1133581fc676dfedc60d891dd5fa99028805e5ea5b08d3491af75d0707adab3b70c6a6a580217bf81",
+        "b53d21a4cfd562c469cc81514d4ce5a6b577d8403d32a394dc265dd190b47fa9f829fdd7963afdf972e5e77854051f6f",
+    ];
+    const MESSAGES: [[u8; 32]; 3] = [[0x00; 32], [0x56; 32], [0xab; 32]];
+
+    fn keys() -> Vec<VerifyingKey<MinPk>> {
+        PUBLIC_KEYS
+            .iter()
+            .map(|key| VerifyingKey::from_bytes(&hex(key)).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn sign_matches_ethereum() {
+        let signatures = [
+            [
+                "b6ed936746e01f8ecf281f020953fbf1f01debd5657c4a383940b020b26507f6076334f91e2366c96e9ab279fb5158090352ea1c5b0c9274504f4f0e7053af24802e51e4568d164fe986834f41e55c8e850ce1f98458c0cfc9ab380b55285a55",
+                "882730e5d03f6b42c3abc26d3372625034e1d871b65a8a6b900a56dae22da98abbe1b68f85e49fe7652a55ec3d0591c20767677e33e5cbb1207315c41a9ac03be39c2e7668edc043d6cb1d9fd93033caa8a1c5b0e84bedaeb6c64972503a43eb",
+                "91347bccf740d859038fcdcaf233eeceb2a436bcaaee9b2aa3bfb70efe29dfb2677562ccbea1c8e061fb9971b0753c240622fab78489ce96768259fc01360346da5b9f579e5da0d941e4c6ba18a0e64906082375394f337fa1af2b7127b0d121",
+            ],
+            [
+                "b23c46be3a001c63ca711f87a005c200cc550b9429d5f4eb38d74322144f1b63926da3388979e5321012fb1a0526bcd100b5ef5fe72628ce4cd5e904aeaa3279527843fae5ca9ca675f4f51ed8f83bbf7155da9ecc9663100a885d5dc6df96d9",
+                "af1390c3c47acdb37131a51216da683c509fce0e954328a59f93aebda7e4ff974ba208d9a4a2a2389f892a9d418d618418dd7f7a6bc7aa0da999a9d3a5b815bc085e14fd001f6a1948768a3f4afefc8b8240dda329f984cb345c6363272ba4fe",
+                "9674e2228034527f4c083206032b020310face156d4a4685e2fcaec2f6f3665aa635d90347b6ce124eb879266b1e801d185de36a0a289b85e9039662634f2eea1e02e670bc7ab849d006a70b2f93b84597558a05b879c8d445f387a5d5b653df",
+            ],
+            [
+                "948a7cb99f76d616c2c564ce9bf4a519f1bea6b0a624a02276443c245854219fabb8d4ce061d255af5330b078d5380681751aa7053da2c98bae898edc218c75f07e24d8802a17cd1f6833b71e58f5eb5b94208b4d0bb3848cecb075ea21be115",
+                "a4efa926610b8bd1c8330c918b7a5e9bf374e53435ef8b7ec186abf62e1b1f65aeaaeb365677ac1d1172a1f5b44b4e6d022c252c58486c0a759fbdc7de15a756acc4d343064035667a594b4c2a6f0b0b421975977f297dba63ee2f63ffe47bb6",
+                "ae82747ddeefe4fd64cf9cedb9b04ae3e8a43420cd255e3c7cd06a8d88b7c7f8638543719981c5d16fa3527c468c25f0026704a6951bde891360c7e8d12ddee0559004ccdbe6046b55bae1b257ee97f7cdb955773d7cf29adf3ccbb9975e4eb9",
+            ],
+        ];
+
+        for ((secret_key, public_key), signatures) in SECRET_KEYS.iter().zip(keys()).zip(signatures)
+        {
+            let secret_key = signing_key::<MinPk>(secret_key);
+            assert_eq!(secret_key.verifying_key(), public_key);
+            for (message, expected) in MESSAGES.iter().zip(signatures) {
+                let signature = secret_key.sign(message);
+                assert_eq!(signature.to_bytes(), hex(expected));
+                assert_eq!(Signature::from_bytes(&hex(expected)), Ok(signature.clone()));
+                assert!(public_key.verify(message, &signature));
+            }
+        }
+    }
+
+    #[test]
+    fn wrong_signatures_do_not_verify() {
+        let keys = keys();
+        let signature = signing_key::<MinPk>(SECRET_KEYS[0]).sign(&MESSAGES[0]);
+
+        assert!(!keys[0].verify(&MESSAGES[1], &signature));
+        assert!(!keys[1].verify(&MESSAGES[0], &signature));
+        let infinity = Signature::<MinPk>::from_bytes(&hex(&format!("c0{}", "00".repeat(95))));
+        assert!(!keys[0].verify(&MESSAGES[0], &infinity.unwrap()));
+    }
+
+    #[test]
+    fn invalid_keys_and_signatures_are_rejected() {
+        let r = "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001";
+        for secret_key in ["00".repeat(32).as_str(), r] {
+            assert_eq!(
+                SigningKey::<MinPk>::from_bytes(&hex(secret_key).try_into().unwrap()),
+                Err(BlsError::InvalidSecretKey)
+            );
+        }
+
+        // the point at infinity, and a point of the curve that is not in the subgroup
+        for public_key in [
+            format!("c0{}", "00".repeat(47)),
+            format!("80{}01", "00".repeat(46)),
+        ] {
+            assert_eq!(
+                VerifyingKey::<MinPk>::from_bytes(&hex(&public_key)),
+                Err(BlsError::InvalidPublicKey)
+            );
+        }
+        assert_eq!(
+            VerifyingKey::<MinPk>::from_bytes(&hex(PUBLIC_KEYS[0])[..47]),
+            Err(BlsError::InvalidPublicKey)
+        );
+        assert_eq!(
+            Signature::<MinPk>::from_bytes(&hex(PUBLIC_KEYS[0])),
+            Err(BlsError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn fast_aggregate_verify_matches_ethereum() {
+        let keys = keys();
+        let signature = Signature::<MinPk>::from_bytes(&hex("9712c3edd73a209c742b8250759db12549b3eaf43b5ca61376d9f30e2747dbcf842d8b2ac0901d2a093713e20284a7670fcf6954e9ab93de991bb9b313e664785a075fc285806fa5224c82bde146561b446ccfc706a64b8579513cfc4ff1d930")).unwrap();
+        let signatures: Vec<_> = SECRET_KEYS
+            .iter()
+            .map(|key| signing_key::<MinPk>(key).sign(&MESSAGES[2]))
+            .collect();
+
+        assert_eq!(Signature::aggregate(&signatures), Ok(signature.clone()));
+        assert!(fast_aggregate_verify(&keys, &MESSAGES[2], &signature));
+        assert!(!fast_aggregate_verify(&keys[..2], &MESSAGES[2], &signature));
+        assert!(!fast_aggregate_verify(&keys, &MESSAGES[1], &signature));
+        assert!(!fast_aggregate_verify(&[], &MESSAGES[2], &signature));
+        assert_eq!(
+            Signature::<MinPk>::aggregate(&[]),
+            Err(BlsError::EmptyAggregation)
+        );
+    }
+
+    #[test]
+    fn aggregate_verify_matches_ethereum() {
+        let keys = keys();
+        let messages: Vec<&[u8]> = MESSAGES.iter().map(|message| &message[..]).collect();
+        let signature = Signature::<MinPk>::from_bytes(&hex("9104e74b9dfd3ad502f25d6a5ef57db0ed7d9a0e00f3500586d8ce44231212542fcfaf87840539b398bf07626705cf1105d246ca1062c6c2e1a53029a0f790ed5e3cb1f52f8234dc5144c45fc847c0cd37a92d68e7c5ba7c648a8a339f171244")).unwrap();
+
+        assert!(aggregate_verify(&keys, &messages, &signature));
+        assert!(!aggregate_verify(&keys[..2], &messages[..2], &signature));
+        assert!(!aggregate_verify(
+            &keys,
+            &[messages[0], messages[2], messages[1]],
+            &signature
+        ));
+        assert!(!aggregate_verify(&[], &[], &signature));
+    }
+
+    #[test]
+    fn key_gen_matches_eip2333() {
+        let seed = hex("c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04");
+        // 6083874454709270928345386274498605044986640685124978867557563392430687146096
+        let secret_key = SigningKey::<MinPk>::key_gen(&seed, b"").unwrap();
+        assert_eq!(
+            secret_key.to_bytes().to_vec(),
+            hex("0d7359d57963ab8fbbde1852dcf553fedbc31f464d80ee7d40ae683122b45070")
+        );
+        assert_eq!(
+            SigningKey::<MinPk>::key_gen(&seed[..31], b""),
+            Err(BlsError::InvalidSecretKey)
+        );
+    }
+
+    fn proofs_of_possession_verify<V: BlsVariant>() {
+        let secret_key = SigningKey::<V>::key_gen(&[7; 32], b"").unwrap();
+        let public_key = secret_key.verifying_key();
+        let proof = secret_key.prove_possession();
+
+        assert!(public_key.verify_possession(&proof));
+        // a proof of possession is not a signature of the public key, nor the converse
+        assert!(!public_key.verify(&public_key.to_bytes(), &proof));
+        assert!(!public_key.verify_possession(&secret_key.sign(&public_key.to_bytes())));
+        let other_key = SigningKey::<V>::key_gen(&[8; 32], b"").unwrap();
+        assert!(!other_key.verifying_key().verify_possession(&proof));
+    }
+
+    #[test]
+    fn proofs_of_possession_verify_min_pk() {
+        proofs_of_possession_verify::<MinPk>();
+    }
+
+    #[test]
+    fn proofs_of_possession_verify_min_sig() {
+        proofs_of_possession_verify::<MinSig>();
+    }
+
+    #[test]
+    fn min_sig_signatures_verify() {
+        let secret_keys: Vec<_> = SECRET_KEYS
+            .iter()
+            .map(|key| signing_key::<MinSig>(key))
+            .collect();
+        let keys: Vec<_> = secret_keys.iter().map(SigningKey::verifying_key).collect();
+        let signatures: Vec<_> = secret_keys.iter().map(|key| key.sign(b"message")).collect();
+
+        for (key, signature) in keys.iter().zip(&signatures) {
+            assert_eq!(key.to_bytes().len(), 96);
+            assert_eq!(signature.to_bytes().len(), 48);
+            assert_eq!(VerifyingKey::from_bytes(&key.to_bytes()), Ok(key.clone()));
+            assert_eq!(
+                Signature::from_bytes(&signature.to_bytes()),
+                Ok(signature.clone())
+            );
+            assert!(key.verify(b"message", signature));
+            assert!(!key.verify(b"other message", signature));
+        }
+
+        let signature = Signature::aggregate(&signatures).unwrap();
+        assert!(fast_aggregate_verify(&keys, b"message", &signature));
+        assert!(aggregate_verify(&keys, &[&b"message"[..]; 3], &signature));
+    }
+}
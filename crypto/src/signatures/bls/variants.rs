@@ -0,0 +1,132 @@
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use lambdaworks_math::{
+    cyclic_group::IsGroup,
+    elliptic_curve::{
+        short_weierstrass::curves::bls12_381::{
+            compression::{
+                compress_g1_point, compress_g2_point, decompress_g1_point, decompress_g2_point,
+            },
+            curve::BLS12381Curve,
+            map_to_curve::{G1Point, G2Point},
+            pairing::BLS12381AtePairing,
+            twist::BLS12381TwistCurve,
+        },
+        traits::{IsEllipticCurve, IsPairing},
+    },
+    field::element::FieldElement,
+    traits::ConditionalSwap,
+};
+
+use crate::hash::hash_to_curve::bls12_381::{hash_to_g1, hash_to_g2};
+
+/// Groups of the keys and signatures of a BLS ciphersuite with proofs of possession.
+/// https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-4.2.3
+pub trait BlsVariant: Clone + Debug + PartialEq + Eq {
+    type PublicKeyPoint: IsGroup + ConditionalSwap + Debug;
+    type SignaturePoint: IsGroup + ConditionalSwap + Debug;
+
+    /// Domain separation tag of the hash to the curve of messages.
+    const SIGNATURE_DST: &'static [u8];
+    /// Domain separation tag of the hash to the curve of public keys in proofs of possession.
+    const POP_DST: &'static [u8];
+
+    fn generator() -> Self::PublicKeyPoint;
+
+    fn hash_to_point(message: &[u8], dst: &[u8]) -> Self::SignaturePoint;
+
+    fn public_key_to_bytes(point: &Self::PublicKeyPoint) -> Vec<u8>;
+
+    /// Reads a compressed point of the subgroup of order r.
+    fn public_key_from_bytes(bytes: &[u8]) -> Option<Self::PublicKeyPoint>;
+
+    fn signature_to_bytes(point: &Self::SignaturePoint) -> Vec<u8>;
+
+    /// Reads a compressed point of the subgroup of order r.
+    fn signature_from_bytes(bytes: &[u8]) -> Option<Self::SignaturePoint>;
+
+    /// Whether the product of the pairings of the given public key and signature group points
+    /// is one.
+    fn pairing_product_is_one(pairs: &[(&Self::PublicKeyPoint, &Self::SignaturePoint)]) -> bool;
+}
+
+/// Public keys of 48 bytes in G1 and signatures of 96 bytes in G2, as in Ethereum.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MinPk;
+
+impl BlsVariant for MinPk {
+    type PublicKeyPoint = G1Point;
+    type SignaturePoint = G2Point;
+
+    const SIGNATURE_DST: &'static [u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+    const POP_DST: &'static [u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+    fn generator() -> G1Point {
+        BLS12381Curve::generator()
+    }
+
+    fn hash_to_point(message: &[u8], dst: &[u8]) -> G2Point {
+        hash_to_g2(message, dst)
+    }
+
+    fn public_key_to_bytes(point: &G1Point) -> Vec<u8> {
+        compress_g1_point(point)
+    }
+
+    fn public_key_from_bytes(bytes: &[u8]) -> Option<G1Point> {
+        decompress_g1_point(&mut bytes.try_into().ok()?).ok()
+    }
+
+    fn signature_to_bytes(point: &G2Point) -> Vec<u8> {
+        compress_g2_point(point)
+    }
+
+    fn signature_from_bytes(bytes: &[u8]) -> Option<G2Point> {
+        decompress_g2_point(&mut bytes.try_into().ok()?).ok()
+    }
+
+    fn pairing_product_is_one(pairs: &[(&G1Point, &G2Point)]) -> bool {
+        BLS12381AtePairing::compute_batch(pairs).is_ok_and(|result| result == FieldElement::one())
+    }
+}
+
+/// Public keys of 96 bytes in G2 and signatures of 48 bytes in G1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MinSig;
+
+impl BlsVariant for MinSig {
+    type PublicKeyPoint = G2Point;
+    type SignaturePoint = G1Point;
+
+    const SIGNATURE_DST: &'static [u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+    const POP_DST: &'static [u8] = b"BLS_POP_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+
+    fn generator() -> G2Point {
+        BLS12381TwistCurve::generator()
+    }
+
+    fn hash_to_point(message: &[u8], dst: &[u8]) -> G1Point {
+        hash_to_g1(message, dst)
+    }
+
+    fn public_key_to_bytes(point: &G2Point) -> Vec<u8> {
+        compress_g2_point(point)
+    }
+
+    fn public_key_from_bytes(bytes: &[u8]) -> Option<G2Point> {
+        decompress_g2_point(&mut bytes.try_into().ok()?).ok()
+    }
+
+    fn signature_to_bytes(point: &G1Point) -> Vec<u8> {
+        compress_g1_point(point)
+    }
+
+    fn signature_from_bytes(bytes: &[u8]) -> Option<G1Point> {
+        decompress_g1_point(&mut bytes.try_into().ok()?).ok()
+    }
+
+    fn pairing_product_is_one(pairs: &[(&G2Point, &G1Point)]) -> bool {
+        let pairs: Vec<_> = pairs.iter().map(|(q, p)| (*p, *q)).collect();
+        BLS12381AtePairing::compute_batch(&pairs).is_ok_and(|result| result == FieldElement::one())
+    }
+}
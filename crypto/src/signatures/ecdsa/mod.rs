@@ -0,0 +1,471 @@
+//! ECDSA over secp256k1 and P-256, as specified in SEC 1 (section 4.1), with the deterministic
+//! nonces of RFC 6979.
+//!
+//! Messages are hashed with SHA-256 by `sign` and `verify`. Signatures over other hashes, such as
+//! the Keccak-256 of Ethereum transactions, are made with `sign_prehash` and `verify_prehash`.
+
+use alloc::vec::Vec;
+use lambdaworks_math::{
+    cyclic_group::IsGroup,
+    elliptic_curve::{
+        short_weierstrass::{point::ShortWeierstrassProjectivePoint, sec1},
+        traits::IsEllipticCurve,
+    },
+    field::{
+        element::FieldElement, fields::montgomery_backed_prime_fields::IsModulus,
+        fields::montgomery_backed_prime_fields::MontgomeryBackendPrimeField,
+    },
+    traits::ByteConversion,
+    unsigned_integer::element::U256,
+};
+use sha2::{Digest, Sha256};
+
+mod parameters;
+
+use super::rfc6979::NonceGenerator;
+pub use parameters::{EcdsaP256, EcdsaParameters, EcdsaSecp256k1};
+
+type Scalar<P> =
+    FieldElement<MontgomeryBackendPrimeField<<P as EcdsaParameters>::ScalarModulus, 4>>;
+type BaseElement<P> =
+    FieldElement<MontgomeryBackendPrimeField<<P as EcdsaParameters>::BaseModulus, 4>>;
+type Point<P> = ShortWeierstrassProjectivePoint<<P as EcdsaParameters>::EC>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EcdsaError {
+    /// The secret key is not in `[1, n)`.
+    InvalidSecretKey,
+    /// The public key is not a SEC 1 encoded point of the curve other than the point at infinity.
+    InvalidPublicKey,
+    /// `r` or `s` is not in `[1, n)`.
+    InvalidSignature,
+    /// No public key is recovered from the signature with this recovery id.
+    InvalidRecoveryId,
+}
+
+/// An ECDSA signature `(r, s)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature<P: EcdsaParameters> {
+    pub r: Scalar<P>,
+    pub s: Scalar<P>,
+}
+
+impl<P: EcdsaParameters> Signature<P> {
+    /// Reads a signature from the 64 bytes `r || s`, both big-endian.
+    pub fn from_bytes(bytes: &[u8; 64]) -> Result<Self, EcdsaError> {
+        let r = nonzero_scalar::<P>(&bytes[..32]).ok_or(EcdsaError::InvalidSignature)?;
+        let s = nonzero_scalar::<P>(&bytes[32..]).ok_or(EcdsaError::InvalidSignature)?;
+        Ok(Self { r, s })
+    }
+
+    /// Writes the signature as the 64 bytes `r || s`, both big-endian.
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0; 64];
+        bytes[..32].copy_from_slice(&self.r.to_bytes_be());
+        bytes[32..].copy_from_slice(&self.s.to_bytes_be());
+        bytes
+    }
+}
+
+/// Secret key `d`, a scalar in `[1, n)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SigningKey<P: EcdsaParameters> {
+    secret: Scalar<P>,
+}
+
+impl<P: EcdsaParameters> SigningKey<P> {
+    /// Reads a big-endian secret key of 32 bytes.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self, EcdsaError> {
+        nonzero_scalar::<P>(bytes)
+            .map(|secret| Self { secret })
+            .ok_or(EcdsaError::InvalidSecretKey)
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        scalar_to_bytes::<P>(&self.secret)
+    }
+
+    /// Public key `Q = d * G`.
+    pub fn verifying_key(&self) -> VerifyingKey<P> {
+        VerifyingKey {
+            point: secret_mul::<P>(&self.secret),
+        }
+    }
+
+    /// Signs the SHA-256 hash of the message.
+    pub fn sign(&self, message: &[u8]) -> Signature<P> {
+        self.sign_prehash(&Sha256::digest(message))
+    }
+
+    /// Signs the hash of a message.
+    pub fn sign_prehash(&self, hash: &[u8]) -> Signature<P> {
+        self.sign_prehash_recoverable(hash).0
+    }
+
+    /// Signs the hash of a message, also returning the recovery id of the signature. Its low bit
+    /// is the parity of the `y` coordinate of `R = k * G` and its high bit is set when the `x`
+    /// coordinate of `R` is at least `n`.
+    pub fn sign_prehash_recoverable(&self, hash: &[u8]) -> (Signature<P>, u8) {
+        let z = hash_to_scalar::<P>(hash);
+        let mut nonces = NonceGenerator::new(
+            &scalar_to_bytes::<P>(&self.secret),
+            &scalar_to_bytes::<P>(&z),
+            P::ScalarModulus::MODULUS,
+        );
+
+        loop {
+            let k = Scalar::<P>::new(nonces.next_nonce());
+            let big_r = secret_mul::<P>(&k).to_affine();
+
+            let x = big_r.x().representative();
+            let r = Scalar::<P>::new(reduce::<P>(x));
+            let s = (&z + &r * &self.secret) * k.inv().expect("nonces are nonzero");
+            if r == Scalar::<P>::zero() || s == Scalar::<P>::zero() {
+                continue;
+            }
+
+            // limbs are big-endian
+            let y_is_odd = big_r.y().representative().limbs[3] & 1 == 1;
+            let mut recovery_id =
+                u8::from(y_is_odd) | (u8::from(x >= P::ScalarModulus::MODULUS) << 1);
+            let s = if P::LOW_S && is_high::<P>(&s) {
+                recovery_id ^= 1;
+                -s
+            } else {
+                s
+            };
+
+            return (Signature { r, s }, recovery_id);
+        }
+    }
+}
+
+/// Public key `Q`, a point of the curve other than the point at infinity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyingKey<P: EcdsaParameters> {
+    point: Point<P>,
+}
+
+impl<P: EcdsaParameters> VerifyingKey<P> {
+    pub fn from_point(point: Point<P>) -> Result<Self, EcdsaError> {
+        if point.is_neutral_element() {
+            Err(EcdsaError::InvalidPublicKey)
+        } else {
+            Ok(Self { point })
+        }
+    }
+
+    /// Reads a SEC 1 encoded public key, compressed or uncompressed.
+    pub fn from_sec1_bytes(bytes: &[u8]) -> Result<Self, EcdsaError> {
+        let point = sec1::decode_point::<P::EC>(bytes).map_err(|_| EcdsaError::InvalidPublicKey)?;
+        Self::from_point(point)
+    }
+
+    pub fn to_sec1_bytes(&self, compressed: bool) -> Vec<u8> {
+        sec1::encode_point(&self.point, compressed)
+    }
+
+    pub fn point(&self) -> &Point<P> {
+        &self.point
+    }
+
+    /// Verifies a signature of the SHA-256 hash of the message.
+    pub fn verify(&self, message: &[u8], signature: &Signature<P>) -> bool {
+        self.verify_prehash(&Sha256::digest(message), signature)
+    }
+
+    /// Verifies a signature of the hash of a message. Signatures with a high `s` are accepted
+    /// on every curve.
+    pub fn verify_prehash(&self, hash: &[u8], signature: &Signature<P>) -> bool {
+        let Signature { r, s } = signature;
+        let Ok(s_inv) = s.inv() else {
+            return false;
+        };
+        if r == &Scalar::<P>::zero() {
+            return false;
+        }
+
+        let u1 = hash_to_scalar::<P>(hash) * &s_inv;
+        let u2 = r * s_inv;
+        let big_r = P::EC::generator()
+            .glv_mul(u1.representative())
+            .operate_with(&self.point.glv_mul(u2.representative()));
+        if big_r.is_neutral_element() {
+            return false;
+        }
+
+        Scalar::<P>::new(reduce::<P>(big_r.to_affine().x().representative())) == *r
+    }
+}
+
+impl VerifyingKey<EcdsaSecp256k1> {
+    /// Recovers the public key that made a signature of the hash of a message, given the
+    /// recovery id returned when signing, as Ethereum does with the `v` of its signatures.
+    pub fn recover_from_prehash(
+        hash: &[u8],
+        signature: &Signature<EcdsaSecp256k1>,
+        recovery_id: u8,
+    ) -> Result<Self, EcdsaError> {
+        type P = EcdsaSecp256k1;
+
+        if recovery_id > 3 {
+            return Err(EcdsaError::InvalidRecoveryId);
+        }
+        let Signature { r, s } = signature;
+        let r_inv = r.inv().map_err(|_| EcdsaError::InvalidSignature)?;
+
+        // x coordinate of R, which is r or r + n
+        let mut x = r.representative();
+        if recovery_id & 2 != 0 {
+            let (sum, overflow) = U256::add(&x, &<P as EcdsaParameters>::ScalarModulus::MODULUS);
+            if overflow || sum >= <P as EcdsaParameters>::BaseModulus::MODULUS {
+                return Err(EcdsaError::InvalidRecoveryId);
+            }
+            x = sum;
+        }
+        let x = BaseElement::<P>::new(x);
+        let y = sec1::lift_x::<<P as EcdsaParameters>::EC>(&x, recovery_id & 1 == 1)
+            .ok_or(EcdsaError::InvalidRecoveryId)?;
+        let big_r = Point::<P>::new([x, y, FieldElement::one()]);
+
+        // Q = r^-1 (s * R - z * G)
+        let u1 = -hash_to_scalar::<P>(hash) * &r_inv;
+        let u2 = s * r_inv;
+        let point = <P as EcdsaParameters>::EC::generator()
+            .glv_mul(u1.representative())
+            .operate_with(&big_r.glv_mul(u2.representative()));
+
+        Self::from_point(point).map_err(|_| EcdsaError::InvalidRecoveryId)
+    }
+}
+
+/// Multiplies the generator by a secret scalar, the secret key or a nonce, doing the same group
+/// operations for every scalar.
+fn secret_mul<P: EcdsaParameters>(scalar: &Scalar<P>) -> Point<P> {
+    P::EC::generator()
+        .operate_with_self_constant_time(&scalar.representative(), &P::ScalarModulus::MODULUS)
+}
+
+/// Converts a hash to a scalar with the leftmost 256 bits of the hash, reduced modulo `n`.
+fn hash_to_scalar<P: EcdsaParameters>(hash: &[u8]) -> Scalar<P> {
+    let mut bytes = [0; 32];
+    let len = hash.len().min(32);
+    bytes[32 - len..].copy_from_slice(&hash[..len]);
+    let value = U256::from_bytes_be(&bytes).expect("32 bytes fit in a U256");
+    Scalar::<P>::new(reduce::<P>(value))
+}
+
+/// Reduces a 256-bit integer modulo `n`, which is greater than `2^255`.
+fn reduce<P: EcdsaParameters>(value: U256) -> U256 {
+    if value >= P::ScalarModulus::MODULUS {
+        value - P::ScalarModulus::MODULUS
+    } else {
+        value
+    }
+}
+
+/// Reads a big-endian scalar in `[1, n)` from 32 bytes.
+fn nonzero_scalar<P: EcdsaParameters>(bytes: &[u8]) -> Option<Scalar<P>> {
+    let value = U256::from_bytes_be(bytes).ok()?;
+    (value != U256::from_u64(0) && value < P::ScalarModulus::MODULUS)
+        .then(|| Scalar::<P>::new(value))
+}
+
+fn scalar_to_bytes<P: EcdsaParameters>(scalar: &Scalar<P>) -> [u8; 32] {
+    scalar
+        .to_bytes_be()
+        .try_into()
+        .expect("scalars of 256-bit fields have 32 bytes")
+}
+
+/// Whether the scalar is greater than `n / 2`.
+fn is_high<P: EcdsaParameters>(scalar: &Scalar<P>) -> bool {
+    scalar.representative() > P::ScalarModulus::MODULUS >> 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex<const N: usize>(value: &str) -> [u8; N] {
+        let mut bytes = [0; N];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&value[2 * i..2 * i + 2], 16).unwrap();
+        }
+        bytes
+    }
+
+    fn signature_from_hex<P: EcdsaParameters>(r: &str, s: &str) -> Signature<P> {
+        Signature::from_bytes(&hex(&[r, s].concat())).unwrap()
+    }
+
+    /// Private key of the P-256 examples of RFC 6979 (appendix A.2.5)
+    fn rfc6979_p256_key() -> SigningKey<EcdsaP256> {
+        SigningKey::from_bytes(&hex(
+            "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721",
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn p256_public_key_matches_rfc6979() {
+        let public_key = rfc6979_p256_key().verifying_key().to_sec1_bytes(false);
+        let expected: [u8; 65] = hex(concat!(
+            "04",
+            "60fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6",
+            "7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299",
+        ));
+        assert_eq!(public_key, expected);
+    }
+
+    #[test]
+    fn p256_signatures_match_rfc6979() {
+        let key = rfc6979_p256_key();
+        let vectors = [
+            (
+                "sample",
+                "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716",
+                "f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8",
+            ),
+            (
+                "test",
+                "f1abb023518351cd71d881567b1ea663ed3efcf6c5132b354f28d3b0b7d38367",
+                "019f4113742a2b14bd25926b49c649155f267e60d3814b4c0cc84250e46f0083",
+            ),
+        ];
+
+        for (message, r, s) in vectors {
+            let signature = key.sign(message.as_bytes());
+            assert_eq!(signature, signature_from_hex(r, s));
+            assert!(key.verifying_key().verify(message.as_bytes(), &signature));
+        }
+    }
+
+    #[test]
+    fn rfc6979_nonce_matches_p256_example() {
+        let key = rfc6979_p256_key();
+        let z = hash_to_scalar::<EcdsaP256>(&Sha256::digest(b"sample"));
+        let mut nonces = NonceGenerator::new(
+            &key.to_bytes(),
+            &scalar_to_bytes::<EcdsaP256>(&z),
+            <EcdsaP256 as EcdsaParameters>::ScalarModulus::MODULUS,
+        );
+        assert_eq!(
+            nonces.next_nonce(),
+            U256::from_hex_unchecked(
+                "a6e3c57dd01abe90086538398355dd4c3b17aa873382b0f24d6129493d8aad60"
+            )
+        );
+    }
+
+    #[test]
+    fn secp256k1_signatures_are_low_s_and_recoverable() {
+        let mut secret = [0; 32];
+        secret[31] = 1;
+        let key = SigningKey::<EcdsaSecp256k1>::from_bytes(&secret).unwrap();
+        let vectors = [
+            (
+                "Satoshi Nakamoto",
+                "934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8",
+                "2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5",
+                1,
+            ),
+            (
+                "All those moments will be lost in time, like tears in rain. Time to die...",
+                "8600dbd41e348fe5c9465ab92d23e3db8b98b873beecd930736488696438cb6b",
+                "547fe64427496db33bf66019dacbf0039c04199abb0122918601db38a72cfc21",
+                0,
+            ),
+        ];
+
+        for (message, r, s, expected_recovery_id) in vectors {
+            let hash = Sha256::digest(message.as_bytes());
+            let (signature, recovery_id) = key.sign_prehash_recoverable(&hash);
+            assert_eq!(signature, signature_from_hex(r, s));
+            assert_eq!(recovery_id, expected_recovery_id);
+            assert!(!is_high::<EcdsaSecp256k1>(&signature.s));
+
+            assert_eq!(
+                VerifyingKey::recover_from_prehash(&hash, &signature, recovery_id),
+                Ok(key.verifying_key())
+            );
+            assert_ne!(
+                VerifyingKey::recover_from_prehash(&hash, &signature, recovery_id ^ 1),
+                Ok(key.verifying_key())
+            );
+        }
+    }
+
+    #[test]
+    fn tampered_signatures_are_rejected() {
+        let key = SigningKey::<EcdsaSecp256k1>::from_bytes(&[7; 32]).unwrap();
+        let verifying_key = key.verifying_key();
+        let signature = key.sign(b"message");
+        assert!(verifying_key.verify(b"message", &signature));
+
+        assert!(!verifying_key.verify(b"massage", &signature));
+        let other_key = SigningKey::<EcdsaSecp256k1>::from_bytes(&[8; 32]).unwrap();
+        assert!(!other_key.verifying_key().verify(b"message", &signature));
+        let flipped = Signature {
+            r: signature.s.clone(),
+            s: signature.r.clone(),
+        };
+        assert!(!verifying_key.verify(b"message", &flipped));
+    }
+
+    #[test]
+    fn high_s_signatures_are_verified() {
+        let key = SigningKey::<EcdsaSecp256k1>::from_bytes(&[7; 32]).unwrap();
+        let signature = key.sign(b"message");
+        let high_s = Signature {
+            r: signature.r.clone(),
+            s: -&signature.s,
+        };
+        assert!(key.verifying_key().verify(b"message", &high_s));
+    }
+
+    #[test]
+    fn keys_and_signatures_round_trip_through_bytes() {
+        let key = SigningKey::<EcdsaP256>::from_bytes(&[42; 32]).unwrap();
+        let verifying_key = key.verifying_key();
+        let signature = key.sign(b"message");
+
+        assert_eq!(SigningKey::from_bytes(&key.to_bytes()), Ok(key));
+        for compressed in [true, false] {
+            let bytes = verifying_key.to_sec1_bytes(compressed);
+            assert_eq!(
+                VerifyingKey::from_sec1_bytes(&bytes),
+                Ok(verifying_key.clone())
+            );
+        }
+        assert_eq!(Signature::from_bytes(&signature.to_bytes()), Ok(signature));
+    }
+
+    #[test]
+    fn out_of_range_values_are_rejected() {
+        let n: [u8; 32] = hex("ffffffffffffffffffffffffffffffffbaaedce6af48a03bbfd25e8cd0364141");
+        assert_eq!(
+            SigningKey::<EcdsaSecp256k1>::from_bytes(&[0; 32]),
+            Err(EcdsaError::InvalidSecretKey)
+        );
+        assert_eq!(
+            SigningKey::<EcdsaSecp256k1>::from_bytes(&n),
+            Err(EcdsaError::InvalidSecretKey)
+        );
+        assert_eq!(
+            Signature::<EcdsaSecp256k1>::from_bytes(&[0; 64]),
+            Err(EcdsaError::InvalidSignature)
+        );
+        assert_eq!(
+            VerifyingKey::<EcdsaSecp256k1>::from_sec1_bytes(&[0]),
+            Err(EcdsaError::InvalidPublicKey)
+        );
+
+        let key = SigningKey::<EcdsaSecp256k1>::from_bytes(&[7; 32]).unwrap();
+        let (signature, _) = key.sign_prehash_recoverable(&[1; 32]);
+        assert_eq!(
+            VerifyingKey::recover_from_prehash(&[1; 32], &signature, 4),
+            Err(EcdsaError::InvalidRecoveryId)
+        );
+    }
+}
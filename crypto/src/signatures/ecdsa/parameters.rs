@@ -0,0 +1,51 @@
+use core::fmt::Debug;
+use lambdaworks_math::{
+    elliptic_curve::short_weierstrass::{
+        curves::{
+            secp256k1::curve::{self as secp256k1, Secp256k1Curve, Secp256k1FieldModulus},
+            secp256r1::curve::{self as secp256r1, Secp256r1Curve, Secp256r1FieldModulus},
+        },
+        point::ShortWeierstrassProjectivePoint,
+        traits::IsShortWeierstrass,
+    },
+    field::fields::montgomery_backed_prime_fields::{IsModulus, MontgomeryBackendPrimeField},
+    unsigned_integer::element::U256,
+};
+
+/// Curve and conventions of an ECDSA instance over 256-bit prime fields.
+pub trait EcdsaParameters: Clone + Debug + PartialEq + Eq {
+    type BaseModulus: IsModulus<U256> + Clone + Debug;
+    type ScalarModulus: IsModulus<U256> + Clone + Debug;
+    type EC: IsShortWeierstrass<
+        BaseField = MontgomeryBackendPrimeField<Self::BaseModulus, 4>,
+        PointRepresentation = ShortWeierstrassProjectivePoint<Self::EC>,
+    >;
+
+    /// Whether signing normalizes `s` to the lower half of the scalar field, as Bitcoin and
+    /// Ethereum require to make signatures non-malleable.
+    const LOW_S: bool;
+}
+
+/// ECDSA over secp256k1, with low-S signatures.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EcdsaSecp256k1;
+
+impl EcdsaParameters for EcdsaSecp256k1 {
+    type BaseModulus = Secp256k1FieldModulus;
+    type ScalarModulus = secp256k1::FrConfig;
+    type EC = Secp256k1Curve;
+
+    const LOW_S: bool = true;
+}
+
+/// ECDSA over P-256, as in FIPS 186-4.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EcdsaP256;
+
+impl EcdsaParameters for EcdsaP256 {
+    type BaseModulus = Secp256r1FieldModulus;
+    type ScalarModulus = secp256r1::FrConfig;
+    type EC = Secp256r1Curve;
+
+    const LOW_S: bool = false;
+}
@@ -0,0 +1,351 @@
+//! Ed25519 signatures, as specified in RFC 8032 (section 5.1).
+//!
+//! Verification is cofactored, `[8][S]B = [8]R + [8][k]A`, so that a signature is accepted by
+//! `verify` if and only if it is accepted by `verify_batch`.
+
+use alloc::vec::Vec;
+use lambdaworks_math::{
+    cyclic_group::IsGroup,
+    elliptic_curve::{
+        edwards::{
+            compression::{compress_point, decompress_point},
+            curves::ed25519::curve::{Ed25519, FrConfig, FrElement},
+            point::EdwardsProjectivePoint,
+        },
+        traits::IsEllipticCurve,
+    },
+    field::fields::montgomery_backed_prime_fields::IsModulus,
+    msm::pippenger,
+    traits::ByteConversion,
+    unsigned_integer::element::U256,
+};
+use sha2::{Digest, Sha512};
+
+type Point = EdwardsProjectivePoint<Ed25519>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ed25519Error {
+    /// The public key is not the encoding of a point of edwards25519.
+    InvalidPublicKey,
+    /// `S` is not reduced modulo the order of the group.
+    InvalidSignature,
+}
+
+/// An Ed25519 signature: the encoding of the point `R` and the scalar `S`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    pub r: [u8; 32],
+    pub s: FrElement,
+}
+
+impl Signature {
+    /// Reads a signature from the 64 bytes `R || S`, with `S` little-endian.
+    pub fn from_bytes(bytes: &[u8; 64]) -> Result<Self, Ed25519Error> {
+        let s = U256::from_bytes_le(&bytes[32..]).map_err(|_| Ed25519Error::InvalidSignature)?;
+        if s >= FrConfig::MODULUS {
+            return Err(Ed25519Error::InvalidSignature);
+        }
+
+        let mut r = [0; 32];
+        r.copy_from_slice(&bytes[..32]);
+        Ok(Self {
+            r,
+            s: FrElement::new(s),
+        })
+    }
+
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0; 64];
+        bytes[..32].copy_from_slice(&self.r);
+        bytes[32..].copy_from_slice(&self.s.to_bytes_le());
+        bytes
+    }
+}
+
+/// Secret key, a seed of 32 bytes from which the secret scalar and the prefix of the nonces are
+/// derived.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SigningKey {
+    seed: [u8; 32],
+    scalar: FrElement,
+    prefix: [u8; 32],
+    verifying_key: VerifyingKey,
+}
+
+impl SigningKey {
+    pub fn from_bytes(seed: &[u8; 32]) -> Self {
+        let hash = Sha512::digest(seed);
+
+        // The scalar is the integer of the first half of the hash, clamped to a multiple of the
+        // cofactor below 2^255. Since B has order l, [s]B = [s mod l]B.
+        let mut scalar_bytes = [0; 32];
+        scalar_bytes.copy_from_slice(&hash[..32]);
+        scalar_bytes[0] &= 0b1111_1000;
+        scalar_bytes[31] &= 0b0111_1111;
+        scalar_bytes[31] |= 0b0100_0000;
+        let scalar = scalar_from_bytes_le(&scalar_bytes);
+
+        let mut prefix = [0; 32];
+        prefix.copy_from_slice(&hash[32..]);
+
+        let point = secret_mul(&scalar);
+        Self {
+            seed: *seed,
+            scalar,
+            prefix,
+            verifying_key: VerifyingKey::from_point(point),
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.seed
+    }
+
+    pub fn verifying_key(&self) -> &VerifyingKey {
+        &self.verifying_key
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        let r = hash_to_scalar(&[&self.prefix, message]);
+        let big_r = compress(&secret_mul(&r));
+        let k = hash_to_scalar(&[&big_r, &self.verifying_key.bytes, message]);
+
+        Signature {
+            r: big_r,
+            s: r + k * &self.scalar,
+        }
+    }
+}
+
+/// Computes `[s]B` for a secret scalar, the secret scalar of a key or the nonce of a signature,
+/// doing the same group operations for every scalar.
+fn secret_mul(scalar: &FrElement) -> Point {
+    Ed25519::generator()
+        .operate_with_self_constant_time(&scalar.representative(), &FrConfig::MODULUS)
+}
+
+/// Public key, the point `A = [s]B`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyingKey {
+    point: Point,
+    bytes: [u8; 32],
+}
+
+impl VerifyingKey {
+    fn from_point(point: Point) -> Self {
+        let bytes = compress(&point);
+        Self { point, bytes }
+    }
+
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self, Ed25519Error> {
+        let point = decompress_point(bytes).map_err(|_| Ed25519Error::InvalidPublicKey)?;
+        Ok(Self {
+            point,
+            bytes: *bytes,
+        })
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.bytes
+    }
+
+    pub fn point(&self) -> &Point {
+        &self.point
+    }
+
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> bool {
+        let Ok(big_r) = decompress_point::<Ed25519>(&signature.r) else {
+            return false;
+        };
+        let k = hash_to_scalar(&[&signature.r, &self.bytes, message]);
+
+        let expected = Ed25519::generator().operate_with_self(signature.s.representative());
+        let actual = big_r.operate_with(&self.point.operate_with_self(k.representative()));
+        expected
+            .operate_with(&actual.neg())
+            .operate_with_self(8_u64)
+            .is_neutral_element()
+    }
+}
+
+/// Verifies many signatures at once, checking a random linear combination of their
+/// verification equations: `[8]([sum z_i S_i]B - sum [z_i]R_i - sum [z_i k_i]A_i) = 0`.
+///
+/// The 128-bit coefficients `z_i` are derived from a hash of every signature, key and message,
+/// so that they cannot be known when choosing them. Returns `false` if the slices have different
+/// lengths.
+pub fn verify_batch(messages: &[&[u8]], signatures: &[Signature], keys: &[VerifyingKey]) -> bool {
+    if messages.len() != signatures.len() || messages.len() != keys.len() {
+        return false;
+    }
+
+    let Ok(rs) = signatures
+        .iter()
+        .map(|signature| decompress_point::<Ed25519>(&signature.r))
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return false;
+    };
+
+    let mut transcript = Sha512::new();
+    for ((message, signature), key) in messages.iter().zip(signatures).zip(keys) {
+        transcript.update(signature.to_bytes());
+        transcript.update(key.bytes);
+        transcript.update((message.len() as u64).to_le_bytes());
+        transcript.update(message);
+    }
+    let seed = transcript.finalize();
+
+    let mut b_coefficient = FrElement::zero();
+    let mut r_coefficients = Vec::with_capacity(signatures.len());
+    let mut a_coefficients = Vec::with_capacity(signatures.len());
+    for (i, ((message, signature), key)) in messages.iter().zip(signatures).zip(keys).enumerate() {
+        let z_bytes = Sha512::new()
+            .chain_update(seed)
+            .chain_update((i as u64).to_le_bytes())
+            .finalize();
+        let mut z = [0; 16];
+        z.copy_from_slice(&z_bytes[..16]);
+        let z = FrElement::new(U256::from_u128(u128::from_le_bytes(z)));
+
+        let k = hash_to_scalar(&[&signature.r, &key.bytes, message]);
+        b_coefficient += &z * &signature.s;
+        r_coefficients.push((-&z).representative());
+        a_coefficients.push((-(z * k)).representative());
+    }
+
+    let coefficients = [r_coefficients, a_coefficients].concat();
+    let points: Vec<_> = rs
+        .into_iter()
+        .chain(keys.iter().map(|key| key.point.clone()))
+        .collect();
+    let sum = pippenger::msm(&coefficients, &points).expect("as many coefficients as points");
+    Ed25519::generator()
+        .operate_with_self(b_coefficient.representative())
+        .operate_with(&sum)
+        .operate_with_self(8_u64)
+        .is_neutral_element()
+}
+
+fn compress(point: &Point) -> [u8; 32] {
+    compress_point(point)
+        .try_into()
+        .expect("points of edwards25519 are encoded in 32 bytes")
+}
+
+/// Reduces the little-endian SHA-512 hash of the concatenation of the parts modulo `l`.
+fn hash_to_scalar(parts: &[&[u8]]) -> FrElement {
+    let mut hasher = Sha512::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    scalar_from_bytes_le(&hasher.finalize())
+}
+
+/// Reduces a little-endian integer modulo `l`, 128 bits at a time since `l > 2^252`.
+fn scalar_from_bytes_le(bytes: &[u8]) -> FrElement {
+    let two_to_128 = FrElement::new(U256::from_limbs([0, 1, 0, 0]));
+    bytes
+        .chunks(16)
+        .rev()
+        .fold(FrElement::zero(), |acc, chunk| {
+            let mut limb = [0; 16];
+            limb[..chunk.len()].copy_from_slice(chunk);
+            acc * &two_to_128 + FrElement::new(U256::from_u128(u128::from_le_bytes(limb)))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex<const N: usize>(value: &str) -> [u8; N] {
+        let mut bytes = [0; N];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&value[2 * i..2 * i + 2], 16).unwrap();
+        }
+        bytes
+    }
+
+    /// Tests 1, 2 and 3 of RFC 8032 (section 7.1): secret key, public key, message, signature.
+    const RFC8032_VECTORS: [(&str, &str, &[u8], &str); 3] = [
+        (
+            "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+            &[],
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+        ),
+        (
+            "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+            "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+            &[0x72],
+            "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+        ),
+        (
+            "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+            "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+            &[0xaf, 0x82],
+            "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+        ),
+    ];
+
+    #[test]
+    fn signatures_match_rfc8032() {
+        for (secret, public, message, signature) in RFC8032_VECTORS {
+            let key = SigningKey::from_bytes(&hex(secret));
+            assert_eq!(key.verifying_key().to_bytes(), hex::<32>(public));
+
+            let signature = Signature::from_bytes(&hex(signature)).unwrap();
+            assert_eq!(key.sign(message), signature);
+            assert!(key.verifying_key().verify(message, &signature));
+        }
+    }
+
+    #[test]
+    fn tampered_signatures_are_rejected() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let signature = key.sign(b"message");
+
+        assert!(!key.verifying_key().verify(b"massage", &signature));
+        assert!(!SigningKey::from_bytes(&[8; 32])
+            .verifying_key()
+            .verify(b"message", &signature));
+
+        let mut other_s = signature.clone();
+        other_s.s += FrElement::one();
+        assert!(!key.verifying_key().verify(b"message", &other_s));
+
+        let mut other_r = signature;
+        other_r.r[0] ^= 1;
+        assert!(!key.verifying_key().verify(b"message", &other_r));
+    }
+
+    #[test]
+    fn non_canonical_s_is_rejected() {
+        let mut bytes = SigningKey::from_bytes(&[7; 32]).sign(b"message").to_bytes();
+        bytes[32..].copy_from_slice(&FrConfig::MODULUS.to_bytes_le());
+        assert_eq!(
+            Signature::from_bytes(&bytes),
+            Err(Ed25519Error::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn batch_verification_agrees_with_verification() {
+        let keys: Vec<_> = (0..4).map(|i| SigningKey::from_bytes(&[i; 32])).collect();
+        let messages: Vec<&[u8]> = vec![b"", b"a", b"message", b"another message"];
+        let verifying_keys: Vec<_> = keys.iter().map(|key| key.verifying_key().clone()).collect();
+        let mut signatures: Vec<_> = keys
+            .iter()
+            .zip(&messages)
+            .map(|(key, message)| key.sign(message))
+            .collect();
+
+        assert!(verify_batch(&messages, &signatures, &verifying_keys));
+        assert!(verify_batch(&[], &[], &[]));
+        assert!(!verify_batch(&messages[..3], &signatures, &verifying_keys));
+
+        signatures.swap(1, 2);
+        assert!(!verify_batch(&messages, &signatures, &verifying_keys));
+    }
+}
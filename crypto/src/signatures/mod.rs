@@ -0,0 +1,5 @@
+pub mod bls;
+pub mod ecdsa;
+pub mod ed25519;
+mod rfc6979;
+pub mod starknet;
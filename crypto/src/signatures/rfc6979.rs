@@ -0,0 +1,78 @@
+use hmac::{Hmac, Mac};
+use lambdaworks_math::{traits::ByteConversion, unsigned_integer::element::U256};
+use sha2::Sha256;
+
+/// Deterministic generation of nonces of RFC 6979 (section 3.2) with HMAC-SHA256, for groups
+/// of order of at most 256 bits.
+pub struct NonceGenerator {
+    key: [u8; 32],
+    value: [u8; 32],
+    order: U256,
+    started: bool,
+}
+
+impl NonceGenerator {
+    /// Seeds the generator with the big-endian secret key and the hash of the message reduced
+    /// modulo the order of the group.
+    pub fn new(secret: &[u8; 32], hash: &[u8; 32], order: U256) -> Self {
+        Self::with_additional_data(secret, hash, &[], order)
+    }
+
+    /// Seeds the generator as `new` does, also feeding it the additional data `k'` of
+    /// section 3.6.
+    pub fn with_additional_data(
+        secret: &[u8; 32],
+        hash: &[u8; 32],
+        additional_data: &[u8],
+        order: U256,
+    ) -> Self {
+        let mut key = [0; 32];
+        let mut value = [1; 32];
+
+        key = hmac(&key, &[&value, &[0x00], secret, hash, additional_data]);
+        value = hmac(&key, &[&value]);
+        key = hmac(&key, &[&value, &[0x01], secret, hash, additional_data]);
+        value = hmac(&key, &[&value]);
+
+        Self {
+            key,
+            value,
+            order,
+            started: false,
+        }
+    }
+
+    /// Returns the next nonce in `[1, n)`. Signers ask for another one when a nonce gives a
+    /// zero `r` or `s`.
+    pub fn next_nonce(&mut self) -> U256 {
+        if self.started {
+            self.reseed();
+        }
+        self.started = true;
+
+        loop {
+            self.value = hmac(&self.key, &[&self.value]);
+            // bits2int keeps the leftmost bits, as many as the order has
+            let candidate = U256::from_bytes_be(&self.value)
+                .expect("a block of HMAC-SHA256 has 32 bytes")
+                >> (256 - self.order.bits_le());
+            if candidate != U256::from_u64(0) && candidate < self.order {
+                return candidate;
+            }
+            self.reseed();
+        }
+    }
+
+    fn reseed(&mut self) {
+        self.key = hmac(&self.key, &[&self.value, &[0x00]]);
+        self.value = hmac(&self.key, &[&self.value]);
+    }
+}
+
+fn hmac(key: &[u8], parts: &[&[u8]]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes keys of any length");
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().into()
+}
@@ -0,0 +1,293 @@
+//! ECDSA over the STARK curve, with the conventions of StarkNet and of `starknet-crypto`.
+//!
+//! Keys, message hashes and signatures are field elements of the Stark252 field. Public keys are
+//! the `x` coordinate of `d * G` and both points with that `x` are accepted when verifying.
+//! Message hashes, `r` and `s` must be smaller than `2^251`, as in Cairo's ECDSA builtin.
+
+use lambdaworks_math::{
+    cyclic_group::IsGroup,
+    elliptic_curve::{
+        short_weierstrass::{
+            curves::stark_curve::{FrConfig, FrElement, StarkCurve},
+            point::ShortWeierstrassProjectivePoint,
+            sec1,
+        },
+        traits::IsEllipticCurve,
+    },
+    field::{
+        element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+        fields::montgomery_backed_prime_fields::IsModulus,
+    },
+    unsigned_integer::element::U256,
+};
+
+use super::rfc6979::NonceGenerator;
+
+pub mod schnorr;
+
+pub type Felt = FieldElement<Stark252PrimeField>;
+
+/// Upper bound of message hashes, `r` and `s`: `2^251`.
+const ELEMENT_UPPER_BOUND: U256 = U256::from_limbs([0x0800000000000000, 0, 0, 0]);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StarknetSignatureError {
+    /// The message hash is not smaller than `2^251`.
+    InvalidMessageHash,
+    /// The nonce is zero or gives an `r` or `s` that is zero or not smaller than `2^251`.
+    InvalidK,
+    /// The secret key is a multiple of the order of the curve.
+    InvalidPrivateKey,
+    /// The public key is not the `x` coordinate of a point of the curve.
+    InvalidPublicKey,
+    /// `r` is zero or not smaller than `2^251`.
+    InvalidR,
+    /// `s` is zero or not smaller than the order of the curve, or its inverse is not smaller
+    /// than `2^251`.
+    InvalidS,
+}
+
+/// A StarkNet ECDSA signature `(r, s)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    pub r: Felt,
+    pub s: Felt,
+}
+
+/// Public key of a secret key: the `x` coordinate of `d * G`.
+pub fn get_public_key(private_key: &Felt) -> Felt {
+    *secret_mul(&to_scalar(private_key)).to_affine().x()
+}
+
+/// Signs a message hash with the nonce `k`, which must be secret and used only once. Nonces
+/// are usually generated with [`rfc6979_generate_k`].
+pub fn sign(
+    private_key: &Felt,
+    message_hash: &Felt,
+    k: &Felt,
+) -> Result<Signature, StarknetSignatureError> {
+    if !is_below_upper_bound(message_hash) {
+        return Err(StarknetSignatureError::InvalidMessageHash);
+    }
+    let k = to_scalar(k);
+    let Ok(k_inv) = k.inv() else {
+        return Err(StarknetSignatureError::InvalidK);
+    };
+
+    let r = *secret_mul(&k).to_affine().x();
+    if r == Felt::zero() || !is_below_upper_bound(&r) {
+        return Err(StarknetSignatureError::InvalidK);
+    }
+
+    let s = (to_scalar(&r) * to_scalar(private_key) + to_scalar(message_hash)) * k_inv;
+    let s = from_scalar(&s);
+    if s == Felt::zero() || !is_below_upper_bound(&s) {
+        return Err(StarknetSignatureError::InvalidK);
+    }
+
+    Ok(Signature { r, s })
+}
+
+/// Verifies a signature of a message hash. Returns `Ok(false)` for well-formed signatures that
+/// are not valid, and an error for out of range inputs.
+pub fn verify(
+    public_key: &Felt,
+    message_hash: &Felt,
+    r: &Felt,
+    s: &Felt,
+) -> Result<bool, StarknetSignatureError> {
+    if !is_below_upper_bound(message_hash) {
+        return Err(StarknetSignatureError::InvalidMessageHash);
+    }
+    if r == &Felt::zero() || !is_below_upper_bound(r) {
+        return Err(StarknetSignatureError::InvalidR);
+    }
+    if s == &Felt::zero() || s.representative() >= FrConfig::MODULUS {
+        return Err(StarknetSignatureError::InvalidS);
+    }
+    let public_key = lift_public_key(public_key, false)?;
+
+    let w = to_scalar(s).inv().expect("s is nonzero and smaller than n");
+    if !is_below_upper_bound(&from_scalar(&w)) {
+        return Err(StarknetSignatureError::InvalidS);
+    }
+
+    // The public key is only known up to its sign, so R = w * (z * G +- r * Q).
+    let z_g = StarkCurve::generator().operate_with_self(message_hash.representative());
+    let r_q = public_key.operate_with_self(r.representative());
+    for candidate in [z_g.operate_with(&r_q), z_g.operate_with(&r_q.neg())] {
+        let big_r = candidate.operate_with_self(w.representative());
+        if !big_r.is_neutral_element() && big_r.to_affine().x() == r {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Deterministic nonce of a signature, as generated by StarkNet signers: the nonce of
+/// RFC 6979 with HMAC-SHA256, with the big-endian bytes of the seed, without leading zeros,
+/// as additional data.
+pub fn rfc6979_generate_k(message_hash: &Felt, private_key: &Felt, seed: Option<&Felt>) -> Felt {
+    let seed = seed.map(Felt::to_bytes_be).unwrap_or_default();
+    let first_nonzero = seed
+        .iter()
+        .position(|byte| *byte != 0)
+        .unwrap_or(seed.len());
+
+    let k = NonceGenerator::with_additional_data(
+        &private_key.to_bytes_be(),
+        &message_hash.to_bytes_be(),
+        &seed[first_nonzero..],
+        FrConfig::MODULUS,
+    )
+    .next_nonce();
+    Felt::new(k)
+}
+
+/// Point of the curve with the given `x` coordinate and parity of `y`.
+fn lift_public_key(
+    x: &Felt,
+    y_odd: bool,
+) -> Result<ShortWeierstrassProjectivePoint<StarkCurve>, StarknetSignatureError> {
+    let y = sec1::lift_x::<StarkCurve>(x, y_odd).ok_or(StarknetSignatureError::InvalidPublicKey)?;
+    Ok(ShortWeierstrassProjectivePoint::new([*x, y, Felt::one()]))
+}
+
+fn is_below_upper_bound(value: &Felt) -> bool {
+    value.representative() < ELEMENT_UPPER_BOUND
+}
+
+/// Multiplies the generator by a secret scalar, a secret key or a nonce, doing the same group
+/// operations for every scalar.
+fn secret_mul(scalar: &FrElement) -> ShortWeierstrassProjectivePoint<StarkCurve> {
+    StarkCurve::generator()
+        .operate_with_self_constant_time(&scalar.representative(), &FrConfig::MODULUS)
+}
+
+/// Reduces a field element modulo the order of the curve, which is smaller than the modulus of
+/// the field.
+fn to_scalar(value: &Felt) -> FrElement {
+    let value = value.representative();
+    if value >= FrConfig::MODULUS {
+        FrElement::new(value - FrConfig::MODULUS)
+    } else {
+        FrElement::new(value)
+    }
+}
+
+fn from_scalar(value: &FrElement) -> Felt {
+    Felt::new(value.representative())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test data generated from cairo-lang
+    #[test]
+    fn get_public_key_works() {
+        let private_key = Felt::from_hex_unchecked(
+            "03c1e9550e66958296d11b60f8e8e7a7ad990d07fa65d5f7652c4a6c87d4e3cc",
+        );
+        let public_key = Felt::from_hex_unchecked(
+            "077a3b314db07c45076d11f62b6f9e748a39790441823307743cf00d6597ea43",
+        );
+        assert_eq!(get_public_key(&private_key), public_key);
+    }
+
+    #[test]
+    fn sign_matches_cairo_lang() {
+        let signature = sign(&Felt::from(1), &Felt::from(2), &Felt::from(3)).unwrap();
+        assert_eq!(
+            signature,
+            Signature {
+                r: Felt::from_hex_unchecked(
+                    "0411494b501a98abd8262b0da1351e17899a0c4ef23dd2f96fec5ba847310b20"
+                ),
+                s: Felt::from_hex_unchecked(
+                    "0405c3191ab3883ef2b763af35bc5f5d15b3b4e99461d70e84c654a351a7c81b"
+                ),
+            }
+        );
+
+        let public_key = get_public_key(&Felt::from(1));
+        assert_eq!(
+            verify(&public_key, &Felt::from(2), &signature.r, &signature.s),
+            Ok(true)
+        );
+        assert_eq!(
+            verify(&public_key, &Felt::from(3), &signature.r, &signature.s),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn signatures_with_deterministic_nonces_verify() {
+        let private_key = Felt::from_hex_unchecked(
+            "03c1e9550e66958296d11b60f8e8e7a7ad990d07fa65d5f7652c4a6c87d4e3cc",
+        );
+        let message_hash = Felt::from_hex_unchecked(
+            "0397e76d1667c4454bfb83514e120583af836f8e32a516765497823eabe16a3f",
+        );
+        let public_key = get_public_key(&private_key);
+
+        for (seed, expected_k) in [
+            (
+                None,
+                "050a50e20a9fb5b33f618ce4ddec8df60f40d3ac3018453bcc002cee71140cd4",
+            ),
+            (
+                Some(Felt::from(0x1234)),
+                "05eba809bc0d7d6b6142864967c7cf4103f3ab68980f2a4871ac5e8eeb6a44e5",
+            ),
+        ] {
+            let k = rfc6979_generate_k(&message_hash, &private_key, seed.as_ref());
+            assert_eq!(k, Felt::from_hex_unchecked(expected_k));
+
+            let signature = sign(&private_key, &message_hash, &k).unwrap();
+            assert_eq!(
+                verify(&public_key, &message_hash, &signature.r, &signature.s),
+                Ok(true)
+            );
+        }
+
+        // a zero seed is the same as no seed
+        assert_eq!(
+            rfc6979_generate_k(&message_hash, &private_key, Some(&Felt::zero())),
+            rfc6979_generate_k(&message_hash, &private_key, None)
+        );
+    }
+
+    #[test]
+    fn out_of_range_inputs_are_rejected() {
+        let public_key = get_public_key(&Felt::from(1));
+        let upper_bound = Felt::new(ELEMENT_UPPER_BOUND);
+        let one = Felt::one();
+
+        assert_eq!(
+            sign(&one, &upper_bound, &one),
+            Err(StarknetSignatureError::InvalidMessageHash)
+        );
+        assert_eq!(
+            sign(&one, &one, &Felt::zero()),
+            Err(StarknetSignatureError::InvalidK)
+        );
+        assert_eq!(
+            verify(&public_key, &upper_bound, &one, &one),
+            Err(StarknetSignatureError::InvalidMessageHash)
+        );
+        assert_eq!(
+            verify(&public_key, &one, &upper_bound, &one),
+            Err(StarknetSignatureError::InvalidR)
+        );
+        assert_eq!(
+            verify(&public_key, &one, &one, &Felt::new(FrConfig::MODULUS)),
+            Err(StarknetSignatureError::InvalidS)
+        );
+        // no point of the curve has x = 5
+        assert_eq!(
+            verify(&Felt::from(5), &one, &one, &one),
+            Err(StarknetSignatureError::InvalidPublicKey)
+        );
+    }
+}
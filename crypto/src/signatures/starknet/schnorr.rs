@@ -0,0 +1,176 @@
+//! Schnorr signatures over the STARK curve, with the keys of StarkNet ECDSA.
+//!
+//! As in BIP 340, public keys and the commitment `R` are given by their `x` coordinate and
+//! stand for the point with an even `y`. The challenge is `e = Poseidon(r, x(Q), z)` and
+//! signatures `(r, s)` satisfy `s * G = R + e * Q`. Poseidon is the one of StarkNet, so
+//! signatures are cheap to verify in Cairo.
+
+use lambdaworks_math::{
+    cyclic_group::IsGroup,
+    elliptic_curve::{
+        short_weierstrass::{
+            curves::stark_curve::{FrConfig, FrElement, StarkCurve},
+            point::ShortWeierstrassProjectivePoint,
+        },
+        traits::IsEllipticCurve,
+    },
+    field::fields::montgomery_backed_prime_fields::IsModulus,
+};
+
+use super::{
+    from_scalar, lift_public_key, secret_mul, to_scalar, Felt, Signature, StarknetSignatureError,
+};
+use crate::{
+    hash::poseidon::{starknet::PoseidonCairoStark252, Poseidon},
+    signatures::rfc6979::NonceGenerator,
+};
+
+/// Additional data of the nonces, so that they differ from those of ECDSA signatures of the
+/// same message hash.
+const NONCE_DOMAIN: &[u8] = b"STARK Schnorr";
+
+/// Signs a message hash with a deterministic nonce.
+pub fn sign(private_key: &Felt, message_hash: &Felt) -> Result<Signature, StarknetSignatureError> {
+    let mut secret = to_scalar(private_key);
+    if secret == FrElement::zero() {
+        return Err(StarknetSignatureError::InvalidPrivateKey);
+    }
+    let public_key = secret_mul(&secret).to_affine();
+    if is_odd(public_key.y()) {
+        secret = -secret;
+    }
+
+    let mut nonces = NonceGenerator::with_additional_data(
+        &from_scalar(&secret).to_bytes_be(),
+        &message_hash.to_bytes_be(),
+        NONCE_DOMAIN,
+        FrConfig::MODULUS,
+    );
+    let k = FrElement::new(nonces.next_nonce());
+    let big_r = secret_mul(&k).to_affine();
+    let k = if is_odd(big_r.y()) { -k } else { k };
+
+    let r = *big_r.x();
+    let e = challenge(&r, public_key.x(), message_hash);
+    let s = k + e * secret;
+    Ok(Signature {
+        r,
+        s: from_scalar(&s),
+    })
+}
+
+/// Verifies a signature of a message hash. Returns `Ok(false)` for well-formed signatures that
+/// are not valid, and an error for out of range inputs.
+pub fn verify(
+    public_key: &Felt,
+    message_hash: &Felt,
+    r: &Felt,
+    s: &Felt,
+) -> Result<bool, StarknetSignatureError> {
+    if s.representative() >= FrConfig::MODULUS {
+        return Err(StarknetSignatureError::InvalidS);
+    }
+    let point = lift_public_key(public_key, false)?;
+
+    // R = s * G - e * Q
+    let e = challenge(r, public_key, message_hash);
+    let big_r: ShortWeierstrassProjectivePoint<StarkCurve> = StarkCurve::generator()
+        .operate_with_self(s.representative())
+        .operate_with(&point.operate_with_self(e.representative()).neg());
+    if big_r.is_neutral_element() {
+        return Ok(false);
+    }
+
+    let big_r = big_r.to_affine();
+    Ok(!is_odd(big_r.y()) && big_r.x() == r)
+}
+
+fn challenge(r: &Felt, public_key: &Felt, message_hash: &Felt) -> FrElement {
+    to_scalar(&PoseidonCairoStark252::hash_many(&[
+        *r,
+        *public_key,
+        *message_hash,
+    ]))
+}
+
+fn is_odd(value: &Felt) -> bool {
+    // limbs are big-endian
+    value.representative().limbs[3] & 1 == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signatures::starknet::get_public_key;
+
+    fn private_key() -> Felt {
+        Felt::from_hex_unchecked("03c1e9550e66958296d11b60f8e8e7a7ad990d07fa65d5f7652c4a6c87d4e3cc")
+    }
+
+    #[test]
+    fn signatures_verify() {
+        let message_hash = Felt::from(2);
+        for private_key in [Felt::from(1), Felt::from(2), private_key()] {
+            let public_key = get_public_key(&private_key);
+            let signature = sign(&private_key, &message_hash).unwrap();
+            assert_eq!(
+                verify(&public_key, &message_hash, &signature.r, &signature.s),
+                Ok(true)
+            );
+        }
+    }
+
+    #[test]
+    fn signatures_are_deterministic() {
+        let message_hash = Felt::from(2);
+        assert_eq!(
+            sign(&private_key(), &message_hash),
+            sign(&private_key(), &message_hash)
+        );
+        assert_ne!(
+            sign(&private_key(), &message_hash),
+            sign(&private_key(), &Felt::from(3))
+        );
+    }
+
+    #[test]
+    fn tampered_signatures_do_not_verify() {
+        let public_key = get_public_key(&private_key());
+        let message_hash = Felt::from(2);
+        let Signature { r, s } = sign(&private_key(), &message_hash).unwrap();
+
+        assert_eq!(verify(&public_key, &Felt::from(3), &r, &s), Ok(false));
+        assert_eq!(
+            verify(&public_key, &message_hash, &r, &(s + Felt::one())),
+            Ok(false)
+        );
+        assert_eq!(
+            verify(&public_key, &message_hash, &(r + Felt::one()), &s),
+            Ok(false)
+        );
+        let other_key = get_public_key(&Felt::from(1));
+        assert_eq!(verify(&other_key, &message_hash, &r, &s), Ok(false));
+    }
+
+    #[test]
+    fn invalid_inputs_are_rejected() {
+        assert_eq!(
+            sign(&Felt::new(FrConfig::MODULUS), &Felt::one()),
+            Err(StarknetSignatureError::InvalidPrivateKey)
+        );
+        assert_eq!(
+            verify(&Felt::from(5), &Felt::one(), &Felt::one(), &Felt::one()),
+            Err(StarknetSignatureError::InvalidPublicKey)
+        );
+        let public_key = get_public_key(&private_key());
+        assert_eq!(
+            verify(
+                &public_key,
+                &Felt::one(),
+                &Felt::one(),
+                &Felt::new(FrConfig::MODULUS)
+            ),
+            Err(StarknetSignatureError::InvalidS)
+        );
+    }
+}
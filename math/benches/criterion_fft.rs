@@ -1,6 +1,6 @@
 #![allow(dead_code)] // clippy has false positive in benchmarks
 use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
-use lambdaworks_math::field::traits::RootsConfig;
+use lambdaworks_math::field::traits::{IsFFTField, RootsConfig};
 use utils::fft_functions;
 use utils::stark252_utils;
 
@@ -49,7 +49,7 @@ pub fn fft_benchmarks(c: &mut Criterion) {
         if order % 2 == 0 {
             group.bench_with_input(
                 "Sequential from NR radix4",
-                &(input_nat, twiddles_bitrev),
+                &(input_nat.clone(), twiddles_bitrev.clone()),
                 |bench, (input, twiddles)| {
                     bench.iter_batched(
                         || input.clone(),
@@ -61,6 +61,52 @@ pub fn fft_benchmarks(c: &mut Criterion) {
                 },
             );
         }
+        group.bench_with_input(
+            "Sequential from NR mixed radix",
+            &(input_nat.clone(), twiddles_bitrev.clone()),
+            |bench, (input, twiddles)| {
+                bench.iter_batched(
+                    || input.clone(),
+                    |mut input| {
+                        fft_functions::ordered_fft_nr_mixed_radix(&mut input, twiddles);
+                    },
+                    BatchSize::LargeInput,
+                );
+            },
+        );
+        #[cfg(feature = "parallel")]
+        group.bench_with_input(
+            "Parallel from NR mixed radix",
+            &(input_nat.clone(), twiddles_bitrev),
+            |bench, (input, twiddles)| {
+                bench.iter_batched(
+                    || input.clone(),
+                    |mut input| {
+                        fft_functions::ordered_fft_nr_mixed_radix_parallel(&mut input, twiddles);
+                    },
+                    BatchSize::LargeInput,
+                );
+            },
+        );
+
+        let root = stark252_utils::F::get_primitive_root_of_unity(order).unwrap();
+        group.bench_with_input(
+            "Sequential six-step",
+            &(input_nat.clone(), root),
+            |bench, (input, root)| {
+                bench.iter_with_large_drop(|| fft_functions::ordered_fft_six_step(input, root));
+            },
+        );
+        #[cfg(feature = "parallel")]
+        group.bench_with_input(
+            "Parallel six-step",
+            &(input_nat, root),
+            |bench, (input, root)| {
+                bench.iter_with_large_drop(|| {
+                    fft_functions::ordered_fft_six_step_parallel(input, root)
+                });
+            },
+        );
     }
 
     group.finish();
@@ -140,6 +186,30 @@ fn poly_interpolation_benchmarks(c: &mut Criterion) {
     group.finish();
 }
 
+fn coset_lde_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Coset LDE");
+    const NUM_COLUMNS: usize = 8;
+    const BLOWUP_FACTOR: usize = 4;
+    let offset = stark252_utils::FE::from(3);
+
+    for order in [16, 18, 20] {
+        let columns: Vec<_> = (0..NUM_COLUMNS)
+            .map(|_| stark252_utils::rand_field_elements(order))
+            .collect();
+        group.throughput(criterion::Throughput::Elements(
+            (NUM_COLUMNS << order) as u64,
+        ));
+        group.bench_with_input("Column by column", &columns, |bench, columns| {
+            bench.iter(|| fft_functions::columns_lde_one_by_one(columns, BLOWUP_FACTOR, &offset));
+        });
+        group.bench_with_input("Batched", &columns, |bench, columns| {
+            bench.iter(|| fft_functions::columns_coset_lde(columns, BLOWUP_FACTOR, &offset));
+        });
+    }
+
+    group.finish();
+}
+
 #[cfg(not(any(feature = "metal", feature = "cuda")))]
 criterion_group!(
     name = seq_fft;
@@ -150,6 +220,7 @@ criterion_group!(
         bitrev_permutation_benchmarks,
         poly_evaluation_benchmarks,
         poly_interpolation_benchmarks,
+        coset_lde_benchmarks,
 );
 
 #[cfg(any(feature = "metal", feature = "cuda"))]
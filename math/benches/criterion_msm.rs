@@ -5,7 +5,7 @@ use lambdaworks_math::{
         short_weierstrass::curves::bls12_381::curve::BLS12381Curve, traits::IsEllipticCurve,
     },
     field::traits::IsField,
-    msm::{naive, pippenger},
+    msm::{batch_affine, naive, pippenger},
     unsigned_integer::element::UnsignedInteger,
 };
 use rand::{rngs::StdRng, Rng, SeedableRng};
@@ -60,6 +60,21 @@ pub fn msm_benchmarks_with_size(
                 bench.iter(|| black_box(pippenger::parallel_msm_with(cs, points, window_size)));
             },
         );
+
+        group.bench_function(
+            BenchmarkId::new("Batch affine Pippenger", window_size),
+            |bench| {
+                bench.iter(|| black_box(batch_affine::msm_with(cs, points, window_size)));
+            },
+        );
+
+        let fixed_base = batch_affine::FixedBaseMSM::new(points, 64 * 6, window_size);
+        group.bench_function(
+            BenchmarkId::new("Fixed base batch affine Pippenger", window_size),
+            |bench| {
+                bench.iter(|| black_box(fixed_base.msm(cs)));
+            },
+        );
     }
 }
 
@@ -3,10 +3,18 @@
 use criterion::black_box;
 use lambdaworks_math::fft::cpu::{
     bit_reversing::in_place_bit_reverse_permute,
-    fft::{in_place_nr_2radix_fft, in_place_nr_4radix_fft, in_place_rn_2radix_fft},
+    fft::{
+        in_place_nr_2radix_fft, in_place_nr_4radix_fft, in_place_nr_mixed_radix_fft,
+        in_place_rn_2radix_fft,
+    },
     roots_of_unity::get_twiddles,
+    six_step::six_step_fft,
 };
-use lambdaworks_math::{field::traits::RootsConfig, polynomial::Polynomial};
+#[cfg(feature = "parallel")]
+use lambdaworks_math::fft::cpu::{
+    fft::parallel_in_place_nr_mixed_radix_fft, six_step::parallel_six_step_fft,
+};
+use lambdaworks_math::{fft::lde::CosetLDE, field::traits::RootsConfig, polynomial::Polynomial};
 
 use super::stark252_utils::{F, FE};
 
@@ -22,6 +30,24 @@ pub fn ordered_fft_nr4(input: &mut [FE], twiddles: &[FE]) {
     in_place_nr_4radix_fft(input, twiddles);
 }
 
+pub fn ordered_fft_nr_mixed_radix(input: &mut [FE], twiddles: &[FE]) {
+    in_place_nr_mixed_radix_fft(input, twiddles);
+}
+
+#[cfg(feature = "parallel")]
+pub fn ordered_fft_nr_mixed_radix_parallel(input: &mut [FE], twiddles: &[FE]) {
+    parallel_in_place_nr_mixed_radix_fft(input, twiddles);
+}
+
+pub fn ordered_fft_six_step(input: &[FE], root: &FE) -> Vec<FE> {
+    six_step_fft(input, root).unwrap()
+}
+
+#[cfg(feature = "parallel")]
+pub fn ordered_fft_six_step_parallel(input: &[FE], root: &FE) -> Vec<FE> {
+    parallel_six_step_fft(input, root).unwrap()
+}
+
 pub fn twiddles_generation(order: u64, config: RootsConfig) {
     get_twiddles::<F>(order, config).unwrap();
 }
@@ -37,3 +63,15 @@ pub fn poly_evaluate_fft(poly: &Polynomial<FE>) -> Vec<FE> {
 pub fn poly_interpolate_fft(evals: &[FE]) {
     Polynomial::interpolate_fft::<F>(evals).unwrap();
 }
+
+pub fn columns_lde_one_by_one(columns: &[Vec<FE>], blowup_factor: usize, offset: &FE) {
+    for column in columns {
+        let poly = Polynomial::interpolate_fft::<F>(column).unwrap();
+        Polynomial::evaluate_offset_fft(&poly, blowup_factor, Some(column.len()), offset).unwrap();
+    }
+}
+
+pub fn columns_coset_lde(columns: &[Vec<FE>], blowup_factor: usize, offset: &FE) {
+    let lde = CosetLDE::new(columns[0].len(), blowup_factor, offset).unwrap();
+    lde.extend_columns(columns).unwrap();
+}
@@ -0,0 +1,64 @@
+use crate::field::{
+    element::FieldElement,
+    fields::mersenne31::field::Mersenne31Field,
+    traits::{IsField, IsSubFieldOf},
+};
+use alloc::vec::Vec;
+
+/// Runs the layers of the inverse circle FFT from `layer` on. `values` must have length
+/// `2^k` and be the evaluations of a function on the points of a standard position coset
+/// (for the first layer) or on the `x` coordinates of the first half of its points (for the
+/// following ones). The result is `2^k` times the coefficients in bit reversed order.
+pub fn icfft<E>(
+    values: &mut [FieldElement<E>],
+    twiddles: &[Vec<FieldElement<Mersenne31Field>>],
+    layer: usize,
+) where
+    E: IsField,
+    Mersenne31Field: IsSubFieldOf<E>,
+{
+    let size = values.len();
+    if size == 1 {
+        return;
+    }
+    let half_size = size / 2;
+    for (i, twiddle) in twiddles[layer].iter().enumerate().take(half_size) {
+        let a = values[i].clone();
+        let b = values[size - 1 - i].clone();
+        values[i] = &a + &b;
+        values[size - 1 - i] = twiddle * (a - b);
+    }
+    values[half_size..].reverse();
+    let (even, odd) = values.split_at_mut(half_size);
+    icfft(even, twiddles, layer + 1);
+    icfft(odd, twiddles, layer + 1);
+}
+
+/// Runs the layers of the circle FFT from `layer` on. This is the inverse of `icfft`, but
+/// takes the coefficients in bit reversed order without the `2^k` factor.
+pub fn cfft<E>(
+    values: &mut [FieldElement<E>],
+    twiddles: &[Vec<FieldElement<Mersenne31Field>>],
+    layer: usize,
+) where
+    E: IsField,
+    Mersenne31Field: IsSubFieldOf<E>,
+{
+    let size = values.len();
+    if size == 1 {
+        return;
+    }
+    let half_size = size / 2;
+    {
+        let (even, odd) = values.split_at_mut(half_size);
+        cfft(even, twiddles, layer + 1);
+        cfft(odd, twiddles, layer + 1);
+    }
+    values[half_size..].reverse();
+    for (i, twiddle) in twiddles[layer].iter().enumerate().take(half_size) {
+        let even = values[i].clone();
+        let odd = twiddle * &values[size - 1 - i];
+        values[i] = &even + &odd;
+        values[size - 1 - i] = even - odd;
+    }
+}
@@ -0,0 +1,93 @@
+use alloc::vec::Vec;
+
+use crate::field::{
+    element::FieldElement, fields::mersenne31::field::Mersenne31Field, traits::IsField,
+};
+
+use super::point::CirclePoint;
+
+/// The standard position coset of size `2^log_2_size`, that is, the coset `g_{2n} + <g_n>` where
+/// `g_k` generates the subgroup of order `k` and `n = 2^log_2_size`.
+/// Its points are ordered as `P_i = g_{2n} + i * g_n`. The conjugate of `P_i` is `P_{n - 1 - i}`,
+/// which is the property the circle FFT needs. Standard position cosets of different sizes
+/// are disjoint, so the trace domain and the low degree extension domain never intersect.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StandardCoset {
+    pub log_2_size: u32,
+}
+
+impl StandardCoset {
+    pub fn new(log_2_size: u32) -> Self {
+        debug_assert!(log_2_size < CirclePoint::<Mersenne31Field>::LOG_2_GROUP_ORDER);
+        Self { log_2_size }
+    }
+
+    pub fn size(&self) -> usize {
+        1 << self.log_2_size
+    }
+
+    /// Returns the first point of the coset, `g_{2n}`.
+    pub fn shift(&self) -> CirclePoint<Mersenne31Field> {
+        CirclePoint::get_generator_of_subgroup(self.log_2_size + 1)
+    }
+
+    /// Returns the difference between consecutive points of the coset, `g_n`.
+    pub fn step(&self) -> CirclePoint<Mersenne31Field> {
+        CirclePoint::get_generator_of_subgroup(self.log_2_size)
+    }
+
+    /// Returns the `index`-th point of the coset.
+    pub fn point(&self, index: usize) -> CirclePoint<Mersenne31Field> {
+        &self.shift() + &self.step().mul(index as u128)
+    }
+
+    /// Returns all the points of the coset in order.
+    pub fn points(&self) -> Vec<CirclePoint<Mersenne31Field>> {
+        let step = self.step();
+        let mut point = self.shift();
+        let mut points = Vec::with_capacity(self.size());
+        for _ in 0..self.size() {
+            let next = &point + &step;
+            points.push(point);
+            point = next;
+        }
+        points
+    }
+
+    /// Evaluates at `point` the polynomial that vanishes on the coset, which is the `x`
+    /// coordinate of `2^(log_2_size - 1) * point`. It has degree `n / 2`.
+    pub fn vanishing_polynomial<E: IsField>(&self, point: &CirclePoint<E>) -> FieldElement<E> {
+        debug_assert!(self.log_2_size > 0);
+        let mut x = point.x.clone();
+        for _ in 1..self.log_2_size {
+            x = x.square().double() - FieldElement::one();
+        }
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conjugate_of_a_point_of_the_coset_is_the_symmetric_point() {
+        let coset = StandardCoset::new(4);
+        let points = coset.points();
+        for (i, point) in points.iter().enumerate() {
+            assert_eq!(point.conjugate(), points[coset.size() - 1 - i]);
+            assert_eq!(*point, coset.point(i));
+        }
+    }
+
+    #[test]
+    fn vanishing_polynomial_vanishes_only_on_the_coset() {
+        let coset = StandardCoset::new(5);
+        for point in coset.points() {
+            assert_eq!(coset.vanishing_polynomial(&point), FieldElement::zero());
+        }
+        for point in StandardCoset::new(7).points() {
+            assert_ne!(coset.vanishing_polynomial(&point), FieldElement::zero());
+        }
+    }
+}
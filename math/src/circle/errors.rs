@@ -0,0 +1,7 @@
+#[derive(Debug, PartialEq, Eq)]
+pub enum CircleError {
+    /// The coordinates do not satisfy `x² + y² = 1`
+    PointDoesntSatisfyCircleEquation,
+    /// The input length is not a power of two
+    InvalidInputLength(usize),
+}
@@ -0,0 +1,12 @@
+//! The circle group over Mersenne31 and the circle FFT.
+//!
+//! Mersenne31 has no large multiplicative subgroups of power of two order, so the usual FFT
+//! does not apply. The points of the circle `x² + y² = 1` over Mersenne31 form a group of
+//! order `2^31`, and the circle FFT of https://eprint.iacr.org/2024/278.pdf interpolates and
+//! evaluates polynomials over its cosets.
+pub mod cfft;
+pub mod cosets;
+pub mod errors;
+pub mod point;
+pub mod polynomial;
+pub mod twiddles;
@@ -0,0 +1,264 @@
+use core::ops::{Add, Neg, Sub};
+
+use crate::field::{
+    element::FieldElement,
+    fields::mersenne31::field::Mersenne31Field,
+    traits::{IsField, IsSubFieldOf},
+};
+
+use super::errors::CircleError;
+
+/// A point of the circle `x² + y² = 1` over the field `F`.
+/// The circle is a group with neutral element `(1, 0)`, where the sum of two points is the
+/// product of the complex numbers `x + iy` they represent.
+#[derive(Debug, Clone)]
+pub struct CirclePoint<F: IsField> {
+    pub x: FieldElement<F>,
+    pub y: FieldElement<F>,
+}
+
+impl<F: IsField> PartialEq for CirclePoint<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+}
+
+impl<F: IsField> Eq for CirclePoint<F> {}
+
+impl<F: IsField> CirclePoint<F> {
+    pub fn new(x: FieldElement<F>, y: FieldElement<F>) -> Result<Self, CircleError> {
+        if x.square() + y.square() == FieldElement::one() {
+            Ok(Self { x, y })
+        } else {
+            Err(CircleError::PointDoesntSatisfyCircleEquation)
+        }
+    }
+
+    /// Returns the neutral element of the group, `(1, 0)`.
+    pub fn zero() -> Self {
+        Self {
+            x: FieldElement::one(),
+            y: FieldElement::zero(),
+        }
+    }
+
+    /// Returns the point `((1 - t²) / (1 + t²), 2t / (1 + t²))`, or `None` if `1 + t² = 0`.
+    /// Every point of the circle except `(-1, 0)` is obtained from exactly one `t`, so this
+    /// maps random field elements to random points.
+    pub fn from_parameter(t: &FieldElement<F>) -> Option<Self> {
+        let t_squared = t.square();
+        let denominator_inv = (FieldElement::<F>::one() + &t_squared).inv().ok()?;
+        Some(Self {
+            x: (FieldElement::<F>::one() - t_squared) * &denominator_inv,
+            y: t.double() * denominator_inv,
+        })
+    }
+
+    /// Returns `2 * self`. Its `x` coordinate is `2x² - 1`, which only depends on `x`.
+    pub fn double(&self) -> Self {
+        Self {
+            x: self.x.square().double() - FieldElement::one(),
+            y: (&self.x * &self.y).double(),
+        }
+    }
+
+    /// Returns `2^log_2_times * self`.
+    pub fn repeated_double(&self, log_2_times: u32) -> Self {
+        let mut result = self.clone();
+        for _ in 0..log_2_times {
+            result = result.double();
+        }
+        result
+    }
+
+    /// Returns `(x, -y)`, which is the inverse of the point in the group.
+    pub fn conjugate(&self) -> Self {
+        Self {
+            x: self.x.clone(),
+            y: -&self.y,
+        }
+    }
+
+    /// Returns `(-x, -y)`, the sum of the point and the point of order two `(-1, 0)`.
+    pub fn antipode(&self) -> Self {
+        Self {
+            x: -&self.x,
+            y: -&self.y,
+        }
+    }
+
+    /// Returns `scalar * self` using double and add.
+    pub fn mul(&self, mut scalar: u128) -> Self {
+        let mut result = Self::zero();
+        let mut power = self.clone();
+        while scalar > 0 {
+            if scalar & 1 == 1 {
+                result = &result + &power;
+            }
+            power = power.double();
+            scalar >>= 1;
+        }
+        result
+    }
+
+    /// Returns the point as a point over the extension `E` of `F`.
+    pub fn to_extension<E: IsField>(&self) -> CirclePoint<E>
+    where
+        F: IsSubFieldOf<E>,
+    {
+        CirclePoint {
+            x: self.x.clone().to_extension(),
+            y: self.y.clone().to_extension(),
+        }
+    }
+}
+
+impl CirclePoint<Mersenne31Field> {
+    /// The circle group over Mersenne31 has order `2^31`.
+    pub const LOG_2_GROUP_ORDER: u32 = 31;
+
+    /// Returns a generator of the circle group over Mersenne31.
+    pub fn generator() -> Self {
+        Self {
+            x: FieldElement::from(2),
+            y: FieldElement::from(1268011823),
+        }
+    }
+
+    /// Returns a generator of the subgroup of order `2^log_2_size`.
+    pub fn get_generator_of_subgroup(log_2_size: u32) -> Self {
+        debug_assert!(log_2_size <= Self::LOG_2_GROUP_ORDER);
+        Self::generator().repeated_double(Self::LOG_2_GROUP_ORDER - log_2_size)
+    }
+}
+
+impl<F: IsField> Add<&CirclePoint<F>> for &CirclePoint<F> {
+    type Output = CirclePoint<F>;
+
+    fn add(self, other: &CirclePoint<F>) -> CirclePoint<F> {
+        CirclePoint {
+            x: &self.x * &other.x - &self.y * &other.y,
+            y: &self.x * &other.y + &self.y * &other.x,
+        }
+    }
+}
+
+impl<F: IsField> Add for CirclePoint<F> {
+    type Output = CirclePoint<F>;
+
+    fn add(self, other: CirclePoint<F>) -> CirclePoint<F> {
+        &self + &other
+    }
+}
+
+impl<F: IsField> Neg for &CirclePoint<F> {
+    type Output = CirclePoint<F>;
+
+    fn neg(self) -> CirclePoint<F> {
+        self.conjugate()
+    }
+}
+
+impl<F: IsField> Neg for CirclePoint<F> {
+    type Output = CirclePoint<F>;
+
+    fn neg(self) -> CirclePoint<F> {
+        self.conjugate()
+    }
+}
+
+impl<F: IsField> Sub<&CirclePoint<F>> for &CirclePoint<F> {
+    type Output = CirclePoint<F>;
+
+    fn sub(self, other: &CirclePoint<F>) -> CirclePoint<F> {
+        CirclePoint {
+            x: &self.x * &other.x + &self.y * &other.y,
+            y: &self.y * &other.x - &self.x * &other.y,
+        }
+    }
+}
+
+impl<F: IsField> Sub for CirclePoint<F> {
+    type Output = CirclePoint<F>;
+
+    fn sub(self, other: CirclePoint<F>) -> CirclePoint<F> {
+        &self - &other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::fields::mersenne31::extension::Mersenne31ComplexQuadraticExtensionField;
+
+    type G = CirclePoint<Mersenne31Field>;
+    type FE = FieldElement<Mersenne31Field>;
+
+    #[test]
+    fn generator_is_on_the_circle() {
+        let g = G::generator();
+        assert!(G::new(g.x, g.y).is_ok());
+    }
+
+    #[test]
+    fn points_outside_the_circle_are_rejected() {
+        assert_eq!(
+            G::new(FE::from(2), FE::from(2)),
+            Err(CircleError::PointDoesntSatisfyCircleEquation)
+        );
+    }
+
+    #[test]
+    fn generator_has_order_two_to_the_31() {
+        let g = G::generator();
+        assert_eq!(
+            g.repeated_double(30),
+            G::new(-FE::one(), FE::zero()).unwrap()
+        );
+        assert_eq!(g.repeated_double(31), G::zero());
+    }
+
+    #[test]
+    fn sum_of_a_point_and_its_conjugate_is_zero() {
+        let p = G::get_generator_of_subgroup(10).mul(77);
+        assert_eq!(&p + &p.conjugate(), G::zero());
+        assert_eq!(&p - &p, G::zero());
+    }
+
+    #[test]
+    fn double_is_the_sum_of_a_point_with_itself() {
+        let p = G::generator().mul(12345);
+        assert_eq!(p.double(), &p + &p);
+        assert_eq!(p.mul(3), &(&p + &p) + &p);
+    }
+
+    #[test]
+    fn antipode_is_the_sum_with_the_point_of_order_two() {
+        let p = G::generator().mul(999);
+        assert_eq!(p.antipode(), &p + &G::get_generator_of_subgroup(1));
+    }
+
+    #[test]
+    fn subgroup_generator_has_the_requested_order() {
+        let g = G::get_generator_of_subgroup(5);
+        assert_eq!(g.mul(32), G::zero());
+        assert_ne!(g.mul(16), G::zero());
+    }
+
+    #[test]
+    fn points_from_parameters_are_on_the_circle() {
+        let t = FieldElement::<Mersenne31ComplexQuadraticExtensionField>::from(12345);
+        let p = CirclePoint::from_parameter(&t).unwrap();
+        assert!(CirclePoint::new(p.x, p.y).is_ok());
+    }
+
+    #[test]
+    fn points_over_the_extension_add_like_points_over_the_base_field() {
+        let p = G::generator().mul(3);
+        let q = G::generator().mul(5);
+        assert_eq!(
+            (&p + &q).to_extension::<Mersenne31ComplexQuadraticExtensionField>(),
+            &p.to_extension() + &q.to_extension()
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use alloc::vec::Vec;
+
+use crate::{
+    fft::cpu::bit_reversing::in_place_bit_reverse_permute,
+    field::{
+        element::FieldElement,
+        fields::mersenne31::field::Mersenne31Field,
+        traits::{IsField, IsSubFieldOf},
+    },
+};
+
+use super::{
+    cfft::{cfft, icfft},
+    cosets::StandardCoset,
+    errors::CircleError,
+    point::CirclePoint,
+    twiddles::{get_twiddles, TwiddlesConfig},
+};
+
+// Polynomials on the circle are represented by their coefficients in the basis of the circle
+// FFT. For `2^k` coefficients, the `j`-th element of the basis is
+// `y^j_0 * v_1(x)^j_1 * ... * v_(k-1)(x)^j_(k-1)`, where `j_i` is the `i`-th bit of `j`,
+// `v_1(x) = x` and `v_(i+1)(x) = 2 v_i(x)² - 1`.
+// The first `2^k` coefficients of a polynomial with `2^(k+1)` coefficients are the
+// coefficients in the smaller basis, so extending the coefficients with zeros does not change
+// the polynomial.
+
+/// Evaluates the polynomial with coefficients `coefficients` on the standard position coset
+/// of size `coefficients.len()`.
+pub fn evaluate_cfft<E>(
+    mut coefficients: Vec<FieldElement<E>>,
+) -> Result<Vec<FieldElement<E>>, CircleError>
+where
+    E: IsField,
+    Mersenne31Field: IsSubFieldOf<E>,
+{
+    let coset = coset_of_size(coefficients.len())?;
+    let twiddles = get_twiddles(&coset, TwiddlesConfig::Evaluation);
+    in_place_bit_reverse_permute(&mut coefficients);
+    cfft(&mut coefficients, &twiddles, 0);
+    Ok(coefficients)
+}
+
+/// Evaluates the polynomial with coefficients `coefficients` on the standard position coset
+/// of size `domain_size`, which must be at least the number of coefficients.
+pub fn evaluate_cfft_on_coset<F>(
+    coefficients: &[FieldElement<F>],
+    domain_size: usize,
+) -> Result<Vec<FieldElement<F>>, CircleError>
+where
+    F: IsField,
+    Mersenne31Field: IsSubFieldOf<F>,
+{
+    if domain_size < coefficients.len() {
+        return Err(CircleError::InvalidInputLength(domain_size));
+    }
+    let mut padded_coefficients = coefficients.to_vec();
+    padded_coefficients.resize(domain_size, FieldElement::zero());
+    evaluate_cfft(padded_coefficients)
+}
+
+/// Returns the coefficients of the polynomial that takes the values `values` on the standard
+/// position coset of size `values.len()`.
+pub fn interpolate_cfft<E>(
+    mut values: Vec<FieldElement<E>>,
+) -> Result<Vec<FieldElement<E>>, CircleError>
+where
+    E: IsField,
+    Mersenne31Field: IsSubFieldOf<E>,
+{
+    let coset = coset_of_size(values.len())?;
+    let twiddles = get_twiddles(&coset, TwiddlesConfig::Interpolation);
+    icfft(&mut values, &twiddles, 0);
+    in_place_bit_reverse_permute(&mut values);
+    let size_inv = FieldElement::<Mersenne31Field>::from(values.len() as u64)
+        .inv()
+        .unwrap();
+    Ok(values
+        .into_iter()
+        .map(|coefficient| size_inv * coefficient)
+        .collect())
+}
+
+/// Evaluates the polynomial with coefficients `coefficients` at `point`, which may have
+/// coordinates in an extension of the field of the coefficients.
+pub fn evaluate_point<F, E>(
+    coefficients: &[FieldElement<F>],
+    point: &CirclePoint<E>,
+) -> FieldElement<E>
+where
+    F: IsSubFieldOf<E>,
+    E: IsField,
+{
+    basis_evaluations(coefficients.len(), point)
+        .iter()
+        .zip(coefficients)
+        .map(|(basis_element, coefficient)| coefficient * basis_element)
+        .fold(FieldElement::zero(), |acc, term| acc + term)
+}
+
+/// Evaluates at `point` the first `size` elements of the basis of the circle FFT.
+pub fn basis_evaluations<E: IsField>(size: usize, point: &CirclePoint<E>) -> Vec<FieldElement<E>> {
+    let mut basis = Vec::with_capacity(size.max(1));
+    basis.push(FieldElement::one());
+    if size > 1 {
+        basis.push(point.y.clone());
+    }
+    let mut v = point.x.clone();
+    while basis.len() < size {
+        let higher: Vec<_> = basis.iter().map(|element| element * &v).collect();
+        basis.extend(higher);
+        v = v.square().double() - FieldElement::one();
+    }
+    basis.truncate(size);
+    basis
+}
+
+fn coset_of_size(size: usize) -> Result<StandardCoset, CircleError> {
+    if !size.is_power_of_two() {
+        return Err(CircleError::InvalidInputLength(size));
+    }
+    Ok(StandardCoset::new(size.trailing_zeros()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::fields::mersenne31::extension::Mersenne31ComplexQuadraticExtensionField;
+
+    type FE = FieldElement<Mersenne31Field>;
+    type Fp4E = FieldElement<Mersenne31ComplexQuadraticExtensionField>;
+
+    fn coefficients(size: usize) -> Vec<FE> {
+        (0..size as u64)
+            .map(|i| FE::from(i * i + 7 * i + 3))
+            .collect()
+    }
+
+    #[test]
+    fn evaluation_matches_evaluation_at_each_point() {
+        for log_2_size in 0..6 {
+            let coefficients = coefficients(1 << log_2_size);
+            let evaluations = evaluate_cfft(coefficients.clone()).unwrap();
+            let points = StandardCoset::new(log_2_size).points();
+            for (point, evaluation) in points.iter().zip(evaluations) {
+                assert_eq!(evaluate_point(&coefficients, point), evaluation);
+            }
+        }
+    }
+
+    #[test]
+    fn interpolation_is_the_inverse_of_evaluation() {
+        for log_2_size in 0..8 {
+            let coefficients = coefficients(1 << log_2_size);
+            let evaluations = evaluate_cfft(coefficients.clone()).unwrap();
+            assert_eq!(interpolate_cfft(evaluations).unwrap(), coefficients);
+        }
+    }
+
+    #[test]
+    fn interpolation_of_extension_values_is_the_inverse_of_evaluation() {
+        let coefficients: Vec<Fp4E> = (0..32u64)
+            .map(|i| {
+                Fp4E::new([
+                    FieldElement::new([FE::from(i), FE::from(2 * i)]),
+                    FieldElement::new([FE::from(3 * i + 1), FE::from(i * i)]),
+                ])
+            })
+            .collect();
+        let evaluations = evaluate_cfft(coefficients.clone()).unwrap();
+        assert_eq!(interpolate_cfft(evaluations).unwrap(), coefficients);
+    }
+
+    #[test]
+    fn evaluation_on_a_larger_coset_extends_the_polynomial() {
+        let coefficients = coefficients(16);
+        let evaluations = evaluate_cfft_on_coset(&coefficients, 64).unwrap();
+        let points = StandardCoset::new(6).points();
+        for (point, evaluation) in points.iter().zip(evaluations.iter()) {
+            assert_eq!(evaluate_point(&coefficients, point), *evaluation);
+        }
+        let mut extended_coefficients = interpolate_cfft(evaluations).unwrap();
+        assert!(extended_coefficients[16..].iter().all(|c| *c == FE::zero()));
+        extended_coefficients.truncate(16);
+        assert_eq!(extended_coefficients, coefficients);
+    }
+
+    #[test]
+    fn evaluation_at_a_point_over_the_extension() {
+        let coefficients = coefficients(8);
+        let t = Fp4E::new([
+            FieldElement::new([FE::from(5), FE::from(6)]),
+            FieldElement::new([FE::from(7), FE::from(8)]),
+        ]);
+        let point = CirclePoint::from_parameter(&t).unwrap();
+        let basis = basis_evaluations(8, &point);
+        assert_eq!(basis[3], &point.y * &point.x);
+        assert_eq!(
+            basis[6],
+            &point.x * (point.x.square().double() - Fp4E::one())
+        );
+        let expected = coefficients
+            .iter()
+            .zip(basis.iter())
+            .fold(Fp4E::zero(), |acc, (c, b)| acc + c * b);
+        assert_eq!(evaluate_point(&coefficients, &point), expected);
+    }
+
+    #[test]
+    fn inputs_of_length_other_than_a_power_of_two_are_rejected() {
+        assert_eq!(
+            evaluate_cfft(coefficients(6)),
+            Err(CircleError::InvalidInputLength(6))
+        );
+        assert_eq!(
+            interpolate_cfft(coefficients(12)),
+            Err(CircleError::InvalidInputLength(12))
+        );
+    }
+}
@@ -0,0 +1,48 @@
+use alloc::vec::Vec;
+
+use crate::field::{element::FieldElement, fields::mersenne31::field::Mersenne31Field};
+
+use super::cosets::StandardCoset;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TwiddlesConfig {
+    Evaluation,
+    Interpolation,
+}
+
+/// Returns the twiddles of every layer of the circle FFT over `coset`.
+/// The first layer splits a function on the coset in the parts that are even and odd in `y`,
+/// so its twiddles are the `y` coordinates of the first half of the points. The following
+/// layers split functions of `x` in their even and odd parts, and their twiddles are the `x`
+/// coordinates of the first half of the points of the previous layer, doubled once per layer.
+/// Interpolation uses the inverses of the twiddles.
+pub fn get_twiddles(
+    coset: &StandardCoset,
+    config: TwiddlesConfig,
+) -> Vec<Vec<FieldElement<Mersenne31Field>>> {
+    let log_2_size = coset.log_2_size;
+    if log_2_size == 0 {
+        return Vec::new();
+    }
+
+    let mut points = coset.points();
+    points.truncate(coset.size() / 2);
+
+    let mut twiddles = Vec::with_capacity(log_2_size as usize);
+    twiddles.push(points.iter().map(|point| point.y).collect::<Vec<_>>());
+    for layer in 1..log_2_size {
+        if layer > 1 {
+            points = points.iter().map(|point| point.double()).collect();
+        }
+        let layer_size = coset.size() >> (layer + 1);
+        twiddles.push(points[..layer_size].iter().map(|point| point.x).collect());
+        points.truncate(layer_size);
+    }
+
+    if config == TwiddlesConfig::Interpolation {
+        for layer in twiddles.iter_mut() {
+            FieldElement::inplace_batch_inverse(layer).unwrap();
+        }
+    }
+    twiddles
+}
@@ -1,4 +1,7 @@
-use crate::unsigned_integer::traits::IsUnsignedInteger;
+use crate::{
+    traits::ConditionalSwap,
+    unsigned_integer::{element::UnsignedInteger, traits::IsUnsignedInteger},
+};
 
 pub trait IsGroup: Clone + PartialEq + Eq {
     /// Returns the neutral element of the group. The equality
@@ -28,6 +31,56 @@ pub trait IsGroup: Clone + PartialEq + Eq {
         result
     }
 
+    /// Applies the group operation `exponent` times with itself, for a secret `exponent` smaller
+    /// than `order`, the order of `self` or a multiple of it.
+    ///
+    /// Unlike [`IsGroup::operate_with_self`], the group operations done don't depend on the bits
+    /// of `exponent`. `order` or twice `order` is added to it, which doesn't change the result,
+    /// so that it always has one bit more than `order`, and its bits are processed with a
+    /// Montgomery ladder whose accumulators are swapped with [`ConditionalSwap`].
+    fn operate_with_self_constant_time<const N: usize>(
+        &self,
+        exponent: &UnsignedInteger<N>,
+        order: &UnsignedInteger<N>,
+    ) -> Self
+    where
+        Self: ConditionalSwap,
+    {
+        debug_assert!(exponent < order);
+        let bits = order.bits_le();
+        // The sums have at most one bit more than the limbs, which is kept apart.
+        let bit = |(value, high_bit): &(UnsignedInteger<N>, u64), i: usize| {
+            if i == 64 * N {
+                *high_bit
+            } else {
+                (value.limbs[N - 1 - i / 64] >> (i % 64)) & 1
+            }
+        };
+        let (once, once_carry) = UnsignedInteger::add(exponent, order);
+        let (twice, twice_carry) = UnsignedInteger::add(&once, order);
+        let mut scalar = (once, u64::from(once_carry));
+        let mut twice = (twice, u64::from(once_carry) + u64::from(twice_carry));
+        // `exponent + order` lacks the bit `bits` exactly when `exponent + 2 * order` has it.
+        let use_twice = 1 ^ bit(&scalar, bits);
+        scalar.0.conditional_swap(&mut twice.0, use_twice);
+        scalar.1.conditional_swap(&mut twice.1, use_twice);
+
+        // The ladder keeps `r1 = r0 + self`, starting from the top bit, which is set.
+        let mut r0 = self.clone();
+        let mut r1 = self.operate_with(self);
+        let mut swap = 0;
+        for i in (0..bits).rev() {
+            let bit = bit(&scalar, i);
+            swap ^= bit;
+            r0.conditional_swap(&mut r1, swap);
+            swap = bit;
+            r1 = r0.operate_with(&r1);
+            r0 = r0.operate_with(&r0);
+        }
+        r0.conditional_swap(&mut r1, swap);
+        r0
+    }
+
     /// Applies the group operation between `self` and `other`.
     /// The operation can be addition or multiplication depending on
     /// the notation of the particular group.
@@ -0,0 +1,144 @@
+//! Point encoding of RFC 8032 (section 5.1.2): the little-endian `y` coordinate, with the parity
+//! of `x` in the most significant bit of the last byte. Encodings take `b / 8` bytes for a field
+//! of `b - 1` bits, so that the bit of `x` always fits: 32 bytes for edwards25519.
+
+use super::{point::EdwardsProjectivePoint, traits::IsEdwards};
+use crate::{
+    errors::ByteConversionError,
+    field::{element::FieldElement, traits::IsPrimeField},
+    traits::ByteConversion,
+};
+use alloc::vec::Vec;
+
+/// Number of bytes of the encoding of a point.
+pub fn compressed_point_len<E>() -> usize
+where
+    E: IsEdwards,
+    E::BaseField: IsPrimeField,
+{
+    (E::BaseField::field_bit_size() + 1).div_ceil(8)
+}
+
+pub fn compress_point<E>(point: &EdwardsProjectivePoint<E>) -> Vec<u8>
+where
+    E: IsEdwards,
+    E::BaseField: IsPrimeField,
+    FieldElement<E::BaseField>: ByteConversion,
+{
+    let len = compressed_point_len::<E>();
+    let point = point.to_affine();
+
+    let mut bytes = point.y().to_bytes_le();
+    bytes.resize(len, 0);
+    if is_odd(point.x()) {
+        bytes[len - 1] |= 0x80;
+    }
+    bytes
+}
+
+/// Decodes a point, rejecting encodings of `y` that are not reduced and `x` that are not on the
+/// curve. The point is not checked to be in the prime order subgroup.
+pub fn decompress_point<E>(bytes: &[u8]) -> Result<EdwardsProjectivePoint<E>, ByteConversionError>
+where
+    E: IsEdwards,
+    E::BaseField: IsPrimeField,
+    FieldElement<E::BaseField>: ByteConversion,
+{
+    let len = compressed_point_len::<E>();
+    if bytes.len() != len {
+        return Err(ByteConversionError::InvalidValue);
+    }
+
+    let x_is_odd = bytes[len - 1] & 0x80 != 0;
+    let mut y_bytes = bytes.to_vec();
+    y_bytes[len - 1] &= 0x7f;
+
+    let y = FieldElement::<E::BaseField>::from_bytes_le(&y_bytes)?;
+    let mut canonical_y_bytes = y.to_bytes_le();
+    canonical_y_bytes.resize(len, 0);
+    if canonical_y_bytes != y_bytes {
+        return Err(ByteConversionError::InvalidValue);
+    }
+
+    // a x^2 + y^2 = 1 + d x^2 y^2, so x^2 = (1 - y^2) / (a - d y^2)
+    let y_squared = y.square();
+    let x_squared =
+        (FieldElement::<E::BaseField>::one() - &y_squared) / (E::a() - E::d() * &y_squared);
+    let (x, neg_x) = x_squared.sqrt().ok_or(ByteConversionError::InvalidValue)?;
+    if x == FieldElement::zero() && x_is_odd {
+        return Err(ByteConversionError::InvalidValue);
+    }
+
+    let x = if is_odd(&x) == x_is_odd { x } else { neg_x };
+    Ok(EdwardsProjectivePoint::new([x, y, FieldElement::one()]))
+}
+
+fn is_odd<F: IsPrimeField>(value: &FieldElement<F>) -> bool
+where
+    FieldElement<F>: ByteConversion,
+{
+    value.to_bytes_le()[0] & 1 == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cyclic_group::IsGroup,
+        elliptic_curve::{
+            edwards::curves::ed25519::{curve::Ed25519, field::CURVE25519_PRIME_FIELD_ORDER},
+            traits::IsEllipticCurve,
+        },
+    };
+
+    #[test]
+    fn ed25519_generator_is_compressed_as_in_rfc8032() {
+        let mut expected = [0x66; 32];
+        expected[0] = 0x58;
+        assert_eq!(compress_point(&Ed25519::generator()), expected);
+    }
+
+    #[test]
+    fn compression_round_trips() {
+        let g = Ed25519::generator();
+        for point in [
+            EdwardsProjectivePoint::neutral_element(),
+            g.clone(),
+            g.neg(),
+            g.operate_with_self(7_u16),
+        ] {
+            let bytes = compress_point(&point);
+            assert_eq!(bytes.len(), 32);
+            assert_eq!(decompress_point::<Ed25519>(&bytes), Ok(point));
+        }
+    }
+
+    #[test]
+    fn invalid_encodings_are_rejected() {
+        assert!(decompress_point::<Ed25519>(&[0; 31]).is_err());
+
+        // y = p
+        let p = CURVE25519_PRIME_FIELD_ORDER.to_bytes_le();
+        assert_eq!(
+            decompress_point::<Ed25519>(&p),
+            Err(ByteConversionError::InvalidValue)
+        );
+
+        // y = 1 is the neutral element, whose x = 0 is even
+        let mut neutral_with_odd_x = [0; 32];
+        neutral_with_odd_x[0] = 1;
+        neutral_with_odd_x[31] = 0x80;
+        assert_eq!(
+            decompress_point::<Ed25519>(&neutral_with_odd_x),
+            Err(ByteConversionError::InvalidValue)
+        );
+
+        // y = 2 is not the y coordinate of any point
+        let mut not_on_curve = [0; 32];
+        not_on_curve[0] = 2;
+        assert_eq!(
+            decompress_point::<Ed25519>(&not_on_curve),
+            Err(ByteConversionError::InvalidValue)
+        );
+    }
+}
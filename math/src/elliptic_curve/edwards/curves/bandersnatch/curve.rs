@@ -1,6 +1,9 @@
 pub use super::field::FqField;
+use crate::cyclic_group::IsGroup;
 use crate::elliptic_curve::edwards::point::EdwardsProjectivePoint;
+use crate::elliptic_curve::glv::IsGLVCurve;
 use crate::elliptic_curve::traits::IsEllipticCurve;
+use crate::unsigned_integer::element::U256;
 use crate::{elliptic_curve::edwards::traits::IsEdwards, field::element::FieldElement};
 
 pub type BaseBandersnatchFieldElement = FqField;
@@ -41,6 +44,53 @@ impl IsEdwards for BandersnatchCurve {
     }
 }
 
+/// Order of the prime order subgroup of Bandersnatch. The full group has cofactor 4.
+pub const BANDERSNATCH_SUBGROUP_ORDER: U256 =
+    U256::from_hex_unchecked("1cfb69d4ca675f520cce760202687600ff8f87007419047174fd06b52876e7e1");
+
+/// Coefficients `b` and `c` of the degree 2 endomorphism of Bandersnatch, which in affine
+/// coordinates maps (𝑥, 𝑦) to (𝑐(1 − 𝑦²) / (𝑥𝑦), 𝑏(𝑦² + 𝑏) / (𝑦² − 𝑏)).
+/// See section 3 of "Bandersnatch: a fast elliptic curve built over the BLS12-381 scalar field"
+/// (https://eprint.iacr.org/2021/1152).
+const ENDOMORPHISM_B: FieldElement<BaseBandersnatchFieldElement> = FieldElement::from_hex_unchecked(
+    "52c9f28b828426a561f00d3a63511a882ea712770d9af4d6ee0f014d172510b4",
+);
+const ENDOMORPHISM_C: FieldElement<BaseBandersnatchFieldElement> = FieldElement::from_hex_unchecked(
+    "6cc624cf865457c3a97c6efd6c17d1078456abcfff36f4e9515c806cdf650b3d",
+);
+
+impl IsGLVCurve for BandersnatchCurve {
+    const SUBGROUP_ORDER: U256 = BANDERSNATCH_SUBGROUP_ORDER;
+    const LAMBDA: U256 = U256::from_hex_unchecked(
+        "13b4f3dc4a39a493edf849562b38c72bcfc49db970a5056ed13d21408783df05",
+    );
+    const LATTICE_BASIS: [U256; 4] = [
+        U256::from_hex_unchecked("102967ddcabd1ebbf1c4b23447ac3e88"),
+        U256::from_hex_unchecked("555fe2004be6928e4b02f94a9789181f"),
+        U256::from_hex_unchecked("555fe2004be6928e4b02f94a9789181f"),
+        U256::from_hex_unchecked("814b3eee55e8f5df8e2591a23d61f44"),
+    ];
+
+    fn endomorphism(p: &Self::PointRepresentation) -> Self::PointRepresentation {
+        let [x, y, z] = p.coordinates();
+        // The kernel of the endomorphism is the neutral element and the point of order 2
+        // (0, -1), which are the only points with x = 0.
+        if x == &FieldElement::zero() {
+            return Self::PointRepresentation::neutral_element();
+        }
+        let y_square = y.square();
+        let z_square = z.square();
+        let b_z_square = &ENDOMORPHISM_B * &z_square;
+        let xy = x * y;
+        let denominator = &y_square - &b_z_square;
+        Self::PointRepresentation::new([
+            &ENDOMORPHISM_C * (&z_square - &y_square) * &denominator,
+            &ENDOMORPHISM_B * (&y_square + &b_z_square) * &xy,
+            xy * denominator,
+        ])
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -0,0 +1,128 @@
+use super::field::Curve25519PrimeField;
+use crate::elliptic_curve::edwards::point::EdwardsProjectivePoint;
+use crate::elliptic_curve::traits::IsEllipticCurve;
+use crate::field::fields::montgomery_backed_prime_fields::{
+    IsModulus, MontgomeryBackendPrimeField,
+};
+use crate::unsigned_integer::element::U256;
+use crate::{elliptic_curve::edwards::traits::IsEdwards, field::element::FieldElement};
+
+/// edwards25519, the twisted Edwards curve of Ed25519 signatures, as specified in RFC 8032.
+/// It is birationally equivalent to the Montgomery curve curve25519 of X25519.
+/// p = 2^255 - 19
+/// a = -1
+/// d = -121665 / 121666
+/// The group of points has order 8 * l, with l = 2^252 + 27742317777372353535851937790883648493,
+/// and the generator spans the subgroup of order l.
+#[derive(Clone, Debug)]
+pub struct Ed25519;
+
+impl IsEllipticCurve for Ed25519 {
+    type BaseField = Curve25519PrimeField;
+    type PointRepresentation = EdwardsProjectivePoint<Self>;
+
+    /// Taken from https://www.rfc-editor.org/rfc/rfc8032#section-5.1
+    fn generator() -> Self::PointRepresentation {
+        Self::PointRepresentation::new([
+            FieldElement::<Self::BaseField>::from_hex_unchecked(
+                "216936d3cd6e53fec0a4e231fdd6dc5c692cc7609525a7b2c9562d608f25d51a",
+            ),
+            FieldElement::<Self::BaseField>::from_hex_unchecked(
+                "6666666666666666666666666666666666666666666666666666666666666658",
+            ),
+            FieldElement::one(),
+        ])
+    }
+}
+
+impl IsEdwards for Ed25519 {
+    fn a() -> FieldElement<Self::BaseField> {
+        -FieldElement::one()
+    }
+
+    fn d() -> FieldElement<Self::BaseField> {
+        FieldElement::from_hex_unchecked(
+            "52036cee2b6ffe738cc740797779e89800700a4d4141d8ab75eb4dca135978a3",
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FrConfig;
+
+/// Modulus (Order) of the prime subgroup of edwards25519
+impl IsModulus<U256> for FrConfig {
+    const MODULUS: U256 = U256::from_hex_unchecked(
+        "1000000000000000000000000000000014def9dea2f79cd65812631a5cf5d3ed",
+    );
+}
+
+/// edwards25519 Fr
+pub type FrField = MontgomeryBackendPrimeField<FrConfig, 4>;
+/// FrElement using MontgomeryBackend for edwards25519
+pub type FrElement = FieldElement<FrField>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cyclic_group::IsGroup, elliptic_curve::traits::EllipticCurveError};
+
+    #[allow(clippy::upper_case_acronyms)]
+    type FE = FieldElement<Curve25519PrimeField>;
+    type G = EdwardsProjectivePoint<Ed25519>;
+
+    fn generator_times_2() -> G {
+        let x = FE::from_hex_unchecked(
+            "36ab384c9f5a046c3d043b7d1833e7ac080d8e4515d7a45f83c5a14e2843ce0e",
+        );
+        let y = FE::from_hex_unchecked(
+            "2260cdf3092329c21da25ee8c9a21f5697390f51643851560e5f46ae6af8a3c9",
+        );
+        Ed25519::create_point_from_affine(x, y).unwrap()
+    }
+
+    fn generator_times_5() -> G {
+        let x = FE::from_hex_unchecked(
+            "49fda73eade3587bfcef7cf7d12da5de5c2819f93e1be1a591409cc0322ef233",
+        );
+        let y = FE::from_hex_unchecked(
+            "5f4825b298feae6fe02c6e148992466631282eca89430b5d10d21f83d676c8ed",
+        );
+        Ed25519::create_point_from_affine(x, y).unwrap()
+    }
+
+    #[test]
+    fn generator_satisfies_defining_equation() {
+        let g = Ed25519::generator();
+        assert_eq!(Ed25519::defining_equation(g.x(), g.y()), FE::zero());
+    }
+
+    #[test]
+    fn d_is_minus_121665_over_121666() {
+        assert_eq!(Ed25519::d(), -FE::from(121665) / FE::from(121666));
+    }
+
+    #[test]
+    fn adding_generator_works() {
+        let g = Ed25519::generator();
+        assert_eq!(g.operate_with(&g), generator_times_2());
+        assert_eq!(g.operate_with_self(5_u16), generator_times_5());
+    }
+
+    #[test]
+    fn create_invalid_points_returns_an_error() {
+        assert_eq!(
+            Ed25519::create_point_from_affine(FE::from(1), FE::from(1)),
+            Err(EllipticCurveError::InvalidPoint)
+        );
+    }
+
+    #[test]
+    fn generator_has_the_order_of_the_scalar_field() {
+        let g = Ed25519::generator();
+        let l_minus_one = FrConfig::MODULUS - U256::from(1u64);
+
+        assert_eq!(g.operate_with_self(FrConfig::MODULUS), G::neutral_element());
+        assert_eq!(g.operate_with_self(l_minus_one), g.neg());
+    }
+}
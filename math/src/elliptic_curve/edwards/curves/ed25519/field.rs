@@ -0,0 +1,22 @@
+//! Base field of edwards25519 and curve25519, of order p = 2^255 - 19.
+
+use crate::{
+    field::{
+        element::FieldElement,
+        fields::montgomery_backed_prime_fields::{IsModulus, MontgomeryBackendPrimeField},
+    },
+    unsigned_integer::element::U256,
+};
+
+pub const CURVE25519_PRIME_FIELD_ORDER: U256 =
+    U256::from_hex_unchecked("7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed");
+
+#[derive(Clone, Debug)]
+pub struct Curve25519FieldModulus;
+
+impl IsModulus<U256> for Curve25519FieldModulus {
+    const MODULUS: U256 = CURVE25519_PRIME_FIELD_ORDER;
+}
+
+pub type Curve25519PrimeField = MontgomeryBackendPrimeField<Curve25519FieldModulus, 4>;
+pub type Curve25519FieldElement = FieldElement<Curve25519PrimeField>;
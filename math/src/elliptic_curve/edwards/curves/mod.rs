@@ -1,3 +1,4 @@
 pub mod bandersnatch;
+pub mod ed25519;
 pub mod ed448_goldilocks;
 pub mod tiny_jub_jub;
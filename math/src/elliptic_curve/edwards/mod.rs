@@ -1,3 +1,5 @@
+#[cfg(feature = "alloc")]
+pub mod compression;
 pub mod curves;
 pub mod point;
 pub mod traits;
@@ -5,6 +5,7 @@ use crate::{
         traits::{EllipticCurveError, FromAffine, IsEllipticCurve},
     },
     field::element::FieldElement,
+    traits::ConditionalSwap,
 };
 
 use super::traits::IsEdwards;
@@ -68,6 +69,15 @@ impl<E: IsEdwards> FromAffine<E::BaseField> for EdwardsProjectivePoint<E> {
 
 impl<E: IsEllipticCurve> Eq for EdwardsProjectivePoint<E> {}
 
+impl<E: IsEllipticCurve> ConditionalSwap for EdwardsProjectivePoint<E>
+where
+    FieldElement<E::BaseField>: ConditionalSwap,
+{
+    fn conditional_swap(&mut self, other: &mut Self, swap: u64) {
+        self.0.conditional_swap(&mut other.0, swap);
+    }
+}
+
 impl<E: IsEdwards> IsGroup for EdwardsProjectivePoint<E> {
     /// The point at infinity.
     fn neutral_element() -> Self {
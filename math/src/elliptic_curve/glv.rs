@@ -0,0 +1,340 @@
+use crate::{
+    cyclic_group::IsGroup,
+    elliptic_curve::{traits::IsEllipticCurve, wnaf::multi_wnaf_mul},
+    unsigned_integer::{
+        element::{UnsignedInteger, U256},
+        traits::IsUnsignedInteger,
+    },
+};
+
+type U512 = UnsignedInteger<8>;
+
+/// An integer given by its absolute value and its sign.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SignedScalar {
+    pub magnitude: U256,
+    pub is_negative: bool,
+}
+
+impl SignedScalar {
+    /// Returns `a - b`.
+    fn difference(a: &U512, b: &U512) -> Self {
+        let (magnitude, is_negative) = if a >= b {
+            (U512::sub(a, b).0, false)
+        } else {
+            (U512::sub(b, a).0, true)
+        };
+        Self {
+            magnitude: narrow(&magnitude),
+            is_negative,
+        }
+    }
+}
+
+/// Curves with an efficiently computable endomorphism `φ` that acts on the prime order
+/// subgroup as multiplication by a scalar `λ`, as in the Gallant–Lambert–Vanstone method.
+///
+/// A scalar `k` is split as `k = k1 + k2 * λ mod r` with `k1` and `k2` of about half the
+/// size of `r`, so that `k * P = k1 * P + k2 * φ(P)` can be computed with half the doublings.
+/// See Algorithm 3.74 of "Guide to Elliptic Curve Cryptography" by Hankerson, Menezes
+/// and Vanstone.
+///
+/// Since `φ(P) = λ * P` only holds in the prime order subgroup, only curves of prime order
+/// split the multiplications of
+/// [`ShortWeierstrassProjectivePoint::glv_mul`](super::short_weierstrass::point::ShortWeierstrassProjectivePoint::glv_mul)
+/// this way (see
+/// [`IsShortWeierstrass::glv_split`](super::short_weierstrass::traits::IsShortWeierstrass::glv_split)).
+/// On curves with a cofactor, [`IsGLVCurve::glv_mul`] and
+/// [`glv_msm`](crate::msm::pippenger::glv_msm) must be called for points known to be in the
+/// subgroup. None of them is constant time, so they are only meant for public scalars.
+pub trait IsGLVCurve: IsEllipticCurve {
+    /// Order `r` of the prime order subgroup.
+    const SUBGROUP_ORDER: U256;
+
+    /// Eigenvalue `λ` of the endomorphism on the prime order subgroup.
+    const LAMBDA: U256;
+
+    /// Short basis `(a1, -b1), (a2, b2)` of the lattice of pairs `(x, y)` such that
+    /// `x + y * λ = 0 mod r`, given as `[a1, b1, a2, b2]`. The basis must satisfy
+    /// `a1 * b2 + a2 * b1 = r`.
+    const LATTICE_BASIS: [U256; 4];
+
+    /// Computes `φ(p)`. For `p` in the prime order subgroup, this equals `λ * p`.
+    fn endomorphism(p: &Self::PointRepresentation) -> Self::PointRepresentation;
+
+    /// Splits `k` into `(k1, k2)` with `k = k1 + k2 * λ mod r` and both halves
+    /// of about half the bit length of `r`.
+    fn decompose_scalar(k: &U256) -> (SignedScalar, SignedScalar) {
+        let [a1, b1, a2, b2] = Self::LATTICE_BASIS;
+        let r = Self::SUBGROUP_ORDER;
+        let mut k = *k;
+        while k >= r {
+            k = k - r;
+        }
+
+        // c1 ≈ b2 * k / r and c2 ≈ b1 * k / r, computed as round(k * g / 2^256) with
+        // g = round(2^256 * b / r). The error is below one, which keeps k1 and k2 short.
+        let g1 = const { rounding_factor(&Self::LATTICE_BASIS[3], &Self::SUBGROUP_ORDER) };
+        let g2 = const { rounding_factor(&Self::LATTICE_BASIS[1], &Self::SUBGROUP_ORDER) };
+        let (c1, c2) = (rounded_high_half(&k, &g1), rounded_high_half(&k, &g2));
+
+        // k1 = k - c1 * a1 - c2 * a2 and k2 = c1 * b1 - c2 * b2.
+        let k1 = SignedScalar::difference(&widen(&k), &(wide_mul(&c1, &a1) + wide_mul(&c2, &a2)));
+        let k2 = SignedScalar::difference(&wide_mul(&c1, &b1), &wide_mul(&c2, &b2));
+        (k1, k2)
+    }
+
+    /// Returns `(p1, k1)` and `(p2, k2)` with `k * p = k1 * p1 + k2 * p2` for `p` in the prime
+    /// order subgroup, where `p1 = ±p`, `p2 = ±φ(p)` and `k1`, `k2` are the halves of `k mod r`.
+    fn split_multiplication<T: IsUnsignedInteger>(
+        p: &Self::PointRepresentation,
+        k: T,
+    ) -> [(Self::PointRepresentation, U256); 2] {
+        let (k1, k2) = Self::decompose_scalar(&reduce(k, &Self::SUBGROUP_ORDER));
+        let p1 = if k1.is_negative { p.neg() } else { p.clone() };
+        let p2 = Self::endomorphism(p);
+        let p2 = if k2.is_negative { p2.neg() } else { p2 };
+        [(p1, k1.magnitude), (p2, k2.magnitude)]
+    }
+
+    /// Computes `k * p` for `p` in the prime order subgroup, using the endomorphism
+    /// to halve the number of doublings and a windowed NAF for each half.
+    fn glv_mul(p: &Self::PointRepresentation, k: &U256) -> Self::PointRepresentation {
+        let [(p1, k1), (p2, k2)] = Self::split_multiplication(p, *k);
+        multi_wnaf_mul([(&p1, &k1), (&p2, &k2)])
+    }
+}
+
+/// Returns `k mod r` for an integer of any size, adding the powers of two of its bits modulo `r`.
+fn reduce<T: IsUnsignedInteger>(mut k: T, r: &U256) -> U256 {
+    let add_mod = |a: &U256, b: &U256| {
+        // Both terms are below r, so the sum is below 2r even when it overflows 256 bits.
+        let (sum, overflow) = U256::add(a, b);
+        if overflow || &sum >= r {
+            U256::sub(&sum, r).0
+        } else {
+            sum
+        }
+    };
+    let mut result = U256::from_u64(0);
+    let mut power = U256::from_u64(1);
+    while k != T::from(0) {
+        if k & T::from(1) == T::from(1) {
+            result = add_mod(&result, &power);
+        }
+        power = add_mod(&power, &power);
+        k >>= 1;
+    }
+    result
+}
+
+/// Returns `round(2^256 * b / r)` for `b < r`, by long division. Only evaluated at compile time.
+const fn rounding_factor(b: &U256, r: &U256) -> U256 {
+    // Since r is odd, adding (r - 1) / 2 to the numerator rounds to the nearest integer.
+    let half_r = r.const_shr(1);
+    let numerator = U512::from_limbs([
+        b.limbs[0],
+        b.limbs[1],
+        b.limbs[2],
+        b.limbs[3],
+        half_r.limbs[0],
+        half_r.limbs[1],
+        half_r.limbs[2],
+        half_r.limbs[3],
+    ]);
+    let divisor = widen(r);
+    let mut quotient = [0u64; 4];
+    let mut remainder = U512::from_u64(0);
+    let mut i = 512;
+    while i > 0 {
+        i -= 1;
+        remainder = remainder.const_shl(1);
+        remainder.limbs[7] |= (numerator.limbs[7 - i / 64] >> (i % 64)) & 1;
+        if U512::const_le(&divisor, &remainder) {
+            remainder = U512::sub(&remainder, &divisor).0;
+            // The quotient is smaller than 2^256 because b < r.
+            assert!(i < 256);
+            quotient[3 - i / 64] |= 1 << (i % 64);
+        }
+    }
+    U256::from_limbs(quotient)
+}
+
+/// Returns `round(a * b / 2^256)`.
+fn rounded_high_half(a: &U256, b: &U256) -> U256 {
+    let (hi, lo) = U256::mul(a, b);
+    if lo.limbs[0] >> 63 == 1 {
+        hi + U256::from_u64(1)
+    } else {
+        hi
+    }
+}
+
+const fn widen(a: &U256) -> U512 {
+    U512::from_limbs([0, 0, 0, 0, a.limbs[0], a.limbs[1], a.limbs[2], a.limbs[3]])
+}
+
+fn narrow(a: &U512) -> U256 {
+    debug_assert!(a.limbs[..4].iter().all(|limb| *limb == 0));
+    U256::from_limbs([a.limbs[4], a.limbs[5], a.limbs[6], a.limbs[7]])
+}
+
+fn wide_mul(a: &U256, b: &U256) -> U512 {
+    let (hi, lo) = U256::mul(a, b);
+    let mut limbs = [0; 8];
+    limbs[..4].copy_from_slice(&hi.limbs);
+    limbs[4..].copy_from_slice(&lo.limbs);
+    U512::from_limbs(limbs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elliptic_curve::{
+        edwards::curves::bandersnatch::curve::BandersnatchCurve,
+        short_weierstrass::{
+            curves::{
+                bls12_381::curve::BLS12381Curve, bn_254::curve::BN254Curve,
+                pallas::curve::PallasCurve, secp256k1::curve::Secp256k1Curve,
+                vesta::curve::VestaCurve,
+            },
+            point::ShortWeierstrassProjectivePoint,
+            traits::IsShortWeierstrass,
+        },
+    };
+    use crate::msm::pippenger;
+
+    fn scalars<E: IsGLVCurve>() -> [U256; 6] {
+        let r = E::SUBGROUP_ORDER;
+        [
+            U256::from_u64(0),
+            U256::from_u64(1),
+            E::LAMBDA,
+            r - U256::from_u64(1),
+            U256::from_hex_unchecked(
+                "0f3a1c5b4e7d2a6981b3c5d7e9f1a2b4c6d8e0f1a3b5c7d9e1f2a4b6c8d0e2f4",
+            ),
+            U256::from_hex_unchecked(
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            ),
+        ]
+    }
+
+    fn check_endomorphism<E: IsGLVCurve>() {
+        let g = E::generator();
+        assert!(E::endomorphism(&g) == g.operate_with_self(E::LAMBDA));
+    }
+
+    fn check_decomposition<E: IsGLVCurve>() {
+        let r = widen(&E::SUBGROUP_ORDER);
+        for k in scalars::<E>() {
+            let (k1, k2) = E::decompose_scalar(&k);
+            assert!(k1.magnitude.bits_le() <= 130);
+            assert!(k2.magnitude.bits_le() <= 130);
+
+            // k1 + k2 * λ = k mod r
+            let reduce = |a: U512| a.div_rem(&r).1;
+            let term = |s: &SignedScalar, a: U512| {
+                let a = reduce(a);
+                if s.is_negative && a != U512::from_u64(0) {
+                    r - a
+                } else {
+                    a
+                }
+            };
+            let sum = reduce(
+                term(&k1, widen(&k1.magnitude)) + term(&k2, wide_mul(&k2.magnitude, &E::LAMBDA)),
+            );
+            assert_eq!(sum, reduce(widen(&k)));
+        }
+    }
+
+    /// Multiplies by double and add.
+    fn double_and_add<G: IsGroup>(p: &G, k: &U256) -> G {
+        (0..k.bits_le()).rev().fold(G::neutral_element(), |acc, i| {
+            let acc = acc.operate_with(&acc);
+            if (*k >> i).limbs[3] & 1 == 1 {
+                acc.operate_with(p)
+            } else {
+                acc
+            }
+        })
+    }
+
+    fn check_glv_mul<E: IsGLVCurve>() {
+        let g = double_and_add(&E::generator(), &U256::from_u64(7));
+        for k in scalars::<E>() {
+            assert!(E::glv_mul(&g, &k) == double_and_add(&g, &k));
+        }
+    }
+
+    fn check_prime_order_curve<E>()
+    where
+        E: IsGLVCurve<PointRepresentation = ShortWeierstrassProjectivePoint<E>>
+            + IsShortWeierstrass,
+    {
+        let g = E::generator();
+        for k in scalars::<E>() {
+            assert!(E::glv_split(&g, k).is_some());
+            assert!(g.glv_mul(k) == double_and_add(&g, &k));
+        }
+        // Scalars wider than 256 bits are reduced modulo the order: r * 2^64 + 5 gives 5 * g.
+        let r = E::SUBGROUP_ORDER;
+        let k = UnsignedInteger::<6>::from_limbs([
+            0, r.limbs[0], r.limbs[1], r.limbs[2], r.limbs[3], 5,
+        ]);
+        assert!(g.glv_mul(k) == double_and_add(&g, &U256::from_u64(5)));
+        assert!(g.glv_mul(3u64) == g.operate_with(&g).operate_with(&g));
+
+        let points = [g.clone(), double_and_add(&g, &U256::from_u64(5))];
+        let cs = [U256::from_u64(11), r - U256::from_u64(2)];
+        let expected =
+            double_and_add(&points[0], &cs[0]).operate_with(&double_and_add(&points[1], &cs[1]));
+        assert!(pippenger::glv_msm::<E>(&cs, &points).unwrap() == expected);
+        assert!(pippenger::msm(&cs, &points).unwrap() == expected);
+    }
+
+    fn check_curve<E: IsGLVCurve>() {
+        check_endomorphism::<E>();
+        check_decomposition::<E>();
+        check_glv_mul::<E>();
+    }
+
+    #[test]
+    fn glv_bls12_381() {
+        check_curve::<BLS12381Curve>();
+        // G1 has a cofactor, so its glv_mul doesn't use the endomorphism.
+        let g = BLS12381Curve::generator();
+        assert!(BLS12381Curve::glv_split(&g, 1u64).is_none());
+        assert!(g.glv_mul(5u64) == double_and_add(&g, &U256::from_u64(5)));
+    }
+
+    #[test]
+    fn glv_bn_254() {
+        check_curve::<BN254Curve>();
+        check_prime_order_curve::<BN254Curve>();
+    }
+
+    #[test]
+    fn glv_secp256k1() {
+        check_curve::<Secp256k1Curve>();
+        check_prime_order_curve::<Secp256k1Curve>();
+    }
+
+    #[test]
+    fn glv_pallas() {
+        check_curve::<PallasCurve>();
+        check_prime_order_curve::<PallasCurve>();
+    }
+
+    #[test]
+    fn glv_vesta() {
+        check_curve::<VestaCurve>();
+        check_prime_order_curve::<VestaCurve>();
+    }
+
+    #[test]
+    fn glv_bandersnatch() {
+        check_curve::<BandersnatchCurve>();
+    }
+}
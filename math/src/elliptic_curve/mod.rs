@@ -1,6 +1,8 @@
 pub mod edwards;
+pub mod glv;
 pub mod montgomery;
 /// Implementation of ProjectivePoint, a generic projective point in a curve.
 pub mod point;
 pub mod short_weierstrass;
 pub mod traits;
+pub mod wnaf;
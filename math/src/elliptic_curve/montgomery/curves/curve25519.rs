@@ -0,0 +1,87 @@
+use crate::{
+    elliptic_curve::{
+        edwards::curves::ed25519::field::Curve25519PrimeField,
+        montgomery::{point::MontgomeryProjectivePoint, traits::IsMontgomery},
+        traits::IsEllipticCurve,
+    },
+    field::element::FieldElement,
+};
+
+/// curve25519, the Montgomery curve of X25519, as specified in RFC 7748.
+/// It is birationally equivalent to edwards25519, with `u = (1 + y) / (1 - y)`.
+/// p = 2^255 - 19
+/// A = 486662
+/// B = 1
+#[derive(Debug, Clone)]
+pub struct Curve25519;
+
+impl IsEllipticCurve for Curve25519 {
+    type BaseField = Curve25519PrimeField;
+    type PointRepresentation = MontgomeryProjectivePoint<Self>;
+
+    /// Taken from https://www.rfc-editor.org/rfc/rfc7748#section-4.1
+    fn generator() -> Self::PointRepresentation {
+        Self::PointRepresentation::new([
+            FieldElement::from(9),
+            FieldElement::from_hex_unchecked(
+                "20ae19a1b8a086b4e01edd2c7748d14c923d4d7e6d7c61b229e9c5a27eced3d9",
+            ),
+            FieldElement::one(),
+        ])
+    }
+}
+
+impl IsMontgomery for Curve25519 {
+    fn a() -> FieldElement<Self::BaseField> {
+        FieldElement::from(486662)
+    }
+
+    fn b() -> FieldElement<Self::BaseField> {
+        FieldElement::one()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cyclic_group::IsGroup,
+        elliptic_curve::edwards::{curves::ed25519::curve::Ed25519, traits::IsEdwards},
+    };
+
+    #[allow(clippy::upper_case_acronyms)]
+    type FE = FieldElement<Curve25519PrimeField>;
+
+    fn generator_times_5() -> MontgomeryProjectivePoint<Curve25519> {
+        let u = FE::from_hex_unchecked(
+            "41b6ec3c50ee7af203c0026e5e079e7fa8cbc9bc581d49cb0d537d5778497c87",
+        );
+        let v = FE::from_hex_unchecked(
+            "5a5de97d7bcffb854d05fe1d42db821dd80f1cddca93c05897b2c4394f2d2be8",
+        );
+        Curve25519::create_point_from_affine(u, v).unwrap()
+    }
+
+    #[test]
+    fn generator_satisfies_defining_equation() {
+        let g = Curve25519::generator();
+        assert_eq!(Curve25519::defining_equation(g.x(), g.y()), FE::zero());
+    }
+
+    #[test]
+    fn adding_generator_five_times_works() {
+        let g = Curve25519::generator();
+        assert_eq!(g.operate_with_self(5_u16), generator_times_5());
+    }
+
+    #[test]
+    fn generators_of_both_forms_are_equivalent() {
+        let g = Ed25519::generator();
+        let u = (FE::one() + g.y()) / (FE::one() - g.y());
+        assert_eq!(&u, Curve25519::generator().x());
+
+        // A = 2 (a + d) / (a - d)
+        let a = FE::from(2) * (Ed25519::a() + Ed25519::d()) / (Ed25519::a() - Ed25519::d());
+        assert_eq!(a, Curve25519::a());
+    }
+}
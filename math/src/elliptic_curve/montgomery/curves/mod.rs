@@ -1 +1,2 @@
+pub mod curve25519;
 pub mod tiny_jub_jub;
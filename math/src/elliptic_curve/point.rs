@@ -1,5 +1,6 @@
 use crate::elliptic_curve::traits::IsEllipticCurve;
 use crate::field::element::FieldElement;
+use crate::traits::ConditionalSwap;
 use core::fmt::Debug;
 /// Represents an elliptic curve point using the projective short Weierstrass form:
 /// y^2 * z = x^3 + a * x * z^2 + b * z^3,
@@ -64,6 +65,15 @@ impl<E: IsEllipticCurve> PartialEq for ProjectivePoint<E> {
 
 impl<E: IsEllipticCurve> Eq for ProjectivePoint<E> {}
 
+impl<E: IsEllipticCurve> ConditionalSwap for ProjectivePoint<E>
+where
+    FieldElement<E::BaseField>: ConditionalSwap,
+{
+    fn conditional_swap(&mut self, other: &mut Self, swap: u64) {
+        self.value.conditional_swap(&mut other.value, swap);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::cyclic_group::IsGroup;
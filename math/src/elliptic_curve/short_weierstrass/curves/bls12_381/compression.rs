@@ -1,9 +1,16 @@
-use super::field_extension::BLS12381PrimeField;
+use super::{
+    curve::BLS12381TwistCurveFieldElement,
+    field_extension::{BLS12381FieldModulus, BLS12381PrimeField},
+    sqrt::sqrt_qfe,
+    twist::BLS12381TwistCurve,
+};
 use crate::{
     elliptic_curve::short_weierstrass::{
         curves::bls12_381::curve::BLS12381Curve, point::ShortWeierstrassProjectivePoint,
+        traits::IsShortWeierstrass,
     },
-    field::element::FieldElement,
+    field::{element::FieldElement, fields::montgomery_backed_prime_fields::IsModulus},
+    unsigned_integer::element::U384,
 };
 use core::cmp::Ordering;
 
@@ -13,6 +20,7 @@ use crate::{
 };
 
 pub type G1Point = ShortWeierstrassProjectivePoint<BLS12381Curve>;
+pub type G2Point = ShortWeierstrassProjectivePoint<BLS12381TwistCurve>;
 pub type BLS12381FieldElement = FieldElement<BLS12381PrimeField>;
 
 pub fn decompress_g1_point(input_bytes: &mut [u8; 48]) -> Result<G1Point, ByteConversionError> {
@@ -25,17 +33,22 @@ pub fn decompress_g1_point(input_bytes: &mut [u8; 48]) -> Result<G1Point, ByteCo
         return Err(ByteConversionError::ValueNotCompressed);
     }
     let second_bit = (prefix_bits & 2_u8) >> 1;
-    // If the second bit is 1, then the compressed point is the
-    // point at infinity and we return it directly.
-    if second_bit == 1 {
-        return Ok(G1Point::neutral_element());
-    }
     let third_bit = prefix_bits & 1_u8;
 
     let first_byte_without_control_bits = (first_byte << 3) >> 3;
     input_bytes[0] = first_byte_without_control_bits;
 
-    let x = BLS12381FieldElement::from_bytes_be(input_bytes)?;
+    // If the second bit is 1, then the compressed point is the
+    // point at infinity, whose other bits are all zero.
+    if second_bit == 1 {
+        return if third_bit == 0 && input_bytes.iter().all(|byte| *byte == 0) {
+            Ok(G1Point::neutral_element())
+        } else {
+            Err(ByteConversionError::InvalidValue)
+        };
+    }
+
+    let x = canonical_field_element(input_bytes)?;
 
     // We apply the elliptic curve formula to know the y^2 value.
     let y_squared = x.pow(3_u16) + BLS12381FieldElement::from(4);
@@ -92,6 +105,82 @@ pub fn compress_g1_point(point: &G1Point) -> alloc::vec::Vec<u8> {
     }
 }
 
+/// Reads a G2 point compressed as in ZCash: the flags are the 3 most significant bits and the
+/// `x` coordinate is written as `x1 || x0`, both big-endian.
+pub fn decompress_g2_point(input_bytes: &mut [u8; 96]) -> Result<G2Point, ByteConversionError> {
+    let flags = input_bytes[0] >> 5;
+    if flags & 4 == 0 {
+        return Err(ByteConversionError::ValueNotCompressed);
+    }
+    input_bytes[0] &= 0b0001_1111;
+    if flags & 2 != 0 {
+        // the point at infinity is encoded with all the other bits set to zero
+        return if flags & 1 == 0 && input_bytes.iter().all(|byte| *byte == 0) {
+            Ok(G2Point::neutral_element())
+        } else {
+            Err(ByteConversionError::InvalidValue)
+        };
+    }
+
+    let x1 = canonical_field_element(&input_bytes[..48])?;
+    let x0 = canonical_field_element(&input_bytes[48..])?;
+    let x = BLS12381TwistCurveFieldElement::new([x0, x1]);
+
+    let y_squared = x.pow(3_u16) + BLS12381TwistCurve::b();
+    let y = sqrt_qfe(&y_squared, 0).ok_or(ByteConversionError::InvalidValue)?;
+    let y = if is_lexicographically_largest(&y) == (flags & 1 == 1) {
+        y
+    } else {
+        -y
+    };
+
+    let point = G2Point::from_affine(x, y).map_err(|_| ByteConversionError::InvalidValue)?;
+    point
+        .is_in_subgroup()
+        .then_some(point)
+        .ok_or(ByteConversionError::PointNotInSubgroup)
+}
+
+/// Compresses a G2 point as in ZCash, into the flags and `x1 || x0`.
+#[cfg(feature = "alloc")]
+pub fn compress_g2_point(point: &G2Point) -> alloc::vec::Vec<u8> {
+    if *point == G2Point::neutral_element() {
+        let mut bytes = alloc::vec![0_u8; 96];
+        bytes[0] = 0b1100_0000;
+        bytes
+    } else {
+        let point_affine = point.to_affine();
+        let [x0, x1] = point_affine.x().value();
+
+        let mut bytes = x1.to_bytes_be();
+        bytes.extend(x0.to_bytes_be());
+        bytes[0] |= 1 << 7;
+        if is_lexicographically_largest(point_affine.y()) {
+            bytes[0] |= 1 << 5;
+        }
+        bytes
+    }
+}
+
+/// Whether `y` is greater than `-y`, comparing `y1` first and then `y0`.
+fn is_lexicographically_largest(y: &BLS12381TwistCurveFieldElement) -> bool {
+    let [y0, y1] = y.value();
+    let [neg_y0, neg_y1] = (-y).value().clone();
+    match y1.representative().cmp(&neg_y1.representative()) {
+        Ordering::Equal => y0.representative() > neg_y0.representative(),
+        ordering => ordering == Ordering::Greater,
+    }
+}
+
+/// Reads a big-endian element of 48 bytes, rejecting integers that are not smaller than p.
+fn canonical_field_element(bytes: &[u8]) -> Result<BLS12381FieldElement, ByteConversionError> {
+    let value = U384::from_bytes_be(bytes)?;
+    if value >= BLS12381FieldModulus::MODULUS {
+        return Err(ByteConversionError::InvalidValue);
+    }
+    Ok(BLS12381FieldElement::new(value))
+}
+
 #[cfg(test)]
 mod tests {
     use super::{BLS12381FieldElement, G1Point};
@@ -99,10 +188,12 @@ mod tests {
     use crate::elliptic_curve::traits::{FromAffine, IsEllipticCurve};
 
     #[cfg(feature = "alloc")]
-    use super::compress_g1_point;
-    use super::decompress_g1_point;
+    use super::{compress_g1_point, compress_g2_point};
+    use super::{decompress_g1_point, decompress_g2_point, G2Point};
+    use crate::elliptic_curve::short_weierstrass::curves::bls12_381::twist::BLS12381TwistCurve;
     use crate::{
-        cyclic_group::IsGroup, traits::ByteConversion, unsigned_integer::element::UnsignedInteger,
+        cyclic_group::IsGroup, errors::ByteConversionError, traits::ByteConversion,
+        unsigned_integer::element::UnsignedInteger,
     };
 
     #[test]
@@ -170,4 +261,63 @@ mod tests {
 
         assert_eq!(g_2, decompressed_g2);
     }
+
+    fn from_hex<const N: usize>(value: &str) -> [u8; N] {
+        let mut bytes = [0; N];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&value[2 * i..2 * i + 2], 16).unwrap();
+        }
+        bytes
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_g2_compress_decompress_generator() {
+        let g = BLS12381TwistCurve::generator();
+        let expected: [u8; 96] = from_hex("93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8");
+        assert_eq!(compress_g2_point(&g), expected);
+        assert_eq!(decompress_g2_point(&mut expected.clone()), Ok(g.clone()));
+
+        // -g only differs in the flag of the sign of y
+        let mut expected_neg = expected;
+        expected_neg[0] |= 1 << 5;
+        assert_eq!(compress_g2_point(&g.neg()), expected_neg);
+        assert_eq!(decompress_g2_point(&mut expected_neg), Ok(g.neg()));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_g2_compress_decompress() {
+        let points = [
+            BLS12381TwistCurve::generator().operate_with_self(5_u64),
+            G2Point::neutral_element(),
+        ];
+        for point in points {
+            let mut compressed: [u8; 96] = compress_g2_point(&point).try_into().unwrap();
+            assert_eq!(decompress_g2_point(&mut compressed), Ok(point));
+        }
+    }
+
+    #[test]
+    fn test_decompress_rejects_invalid_encodings() {
+        // x = p
+        let mut non_canonical: [u8; 48] = from_hex("9a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab");
+        assert!(decompress_g1_point(&mut non_canonical).is_err());
+
+        // infinity with the flag of the sign of y or a nonzero x
+        let mut infinity = [0; 48];
+        infinity[0] = 0b1110_0000;
+        assert!(decompress_g1_point(&mut infinity).is_err());
+        let mut infinity = [0; 96];
+        infinity[0] = 0b1100_0000;
+        infinity[95] = 1;
+        assert!(decompress_g2_point(&mut infinity).is_err());
+
+        // uncompressed
+        let mut uncompressed = [0; 96];
+        assert_eq!(
+            decompress_g2_point(&mut uncompressed),
+            Err(ByteConversionError::ValueNotCompressed)
+        );
+    }
 }
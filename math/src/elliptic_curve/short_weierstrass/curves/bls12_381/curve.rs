@@ -3,6 +3,7 @@ use super::{
     twist::BLS12381TwistCurve,
 };
 use crate::cyclic_group::IsGroup;
+use crate::elliptic_curve::glv::IsGLVCurve;
 use crate::elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint;
 use crate::elliptic_curve::traits::IsEllipticCurve;
 use crate::unsigned_integer::element::U256;
@@ -43,6 +44,23 @@ impl IsShortWeierstrass for BLS12381Curve {
     }
 }
 
+impl IsGLVCurve for BLS12381Curve {
+    const SUBGROUP_ORDER: U256 = SUBGROUP_ORDER;
+    const LAMBDA: U256 = U256::from_hex_unchecked(
+        "73eda753299d7d483339d80809a1d804a7780001fffcb7fcfffffffe00000001",
+    );
+    const LATTICE_BASIS: [U256; 4] = [
+        U256::from_u64(1),
+        U256::from_hex_unchecked("ac45a4010001a40200000000ffffffff"),
+        U256::from_hex_unchecked("ac45a4010001a4020000000100000000"),
+        U256::from_u64(1),
+    ];
+
+    fn endomorphism(p: &Self::PointRepresentation) -> Self::PointRepresentation {
+        p.phi()
+    }
+}
+
 /// This is equal to the frobenius trace of the BLS12 381 curve minus one or seed value z.
 pub const MILLER_LOOP_CONSTANT: u64 = 0xd201000000010000;
 
@@ -84,6 +102,13 @@ impl ShortWeierstrassProjectivePoint<BLS12381Curve> {
             .neg()
             == self.phi()
     }
+
+    /// Maps a point of the curve to the subgroup of order r, multiplying it by
+    /// h_eff = 1 - z = 0xd201000000010001.
+    /// https://www.rfc-editor.org/rfc/rfc9380#section-8.8.1
+    pub fn clear_cofactor(&self) -> Self {
+        self.operate_with_self(MILLER_LOOP_CONSTANT + 1)
+    }
 }
 
 impl ShortWeierstrassProjectivePoint<BLS12381TwistCurve> {
@@ -104,6 +129,22 @@ impl ShortWeierstrassProjectivePoint<BLS12381TwistCurve> {
     pub fn is_in_subgroup(&self) -> bool {
         self.psi() == self.operate_with_self(MILLER_LOOP_CONSTANT).neg()
     }
+
+    /// Maps a point of the twist to the subgroup of order r, computing
+    /// [z² - z - 1]P + [z - 1]𝜓(P) + 𝜓²(2P), which is the multiplication by h_eff.
+    /// https://www.rfc-editor.org/rfc/rfc9380#appendix-G.3
+    pub fn clear_cofactor(&self) -> Self {
+        // [z]P, where the seed z is negative
+        let times_z = |p: &Self| p.operate_with_self(MILLER_LOOP_CONSTANT).neg();
+
+        let t1 = times_z(self);
+        let t2 = self.psi();
+        let t3 = self.double().psi().psi().operate_with(&t2.neg());
+        let t2 = times_z(&t1.operate_with(&t2));
+        t3.operate_with(&t2)
+            .operate_with(&t1.neg())
+            .operate_with(&self.neg())
+    }
 }
 
 #[cfg(test)]
@@ -29,6 +29,8 @@ pub struct Degree2ExtensionField;
 impl IsField for Degree2ExtensionField {
     type BaseType = [FieldElement<BLS12381PrimeField>; 2];
 
+    const EXTENSION_DEGREE: usize = 2;
+
     /// Returns the component wise addition of `a` and `b`
     fn add(a: &Self::BaseType, b: &Self::BaseType) -> Self::BaseType {
         [&a[0] + &b[0], &a[1] + &b[1]]
@@ -101,6 +103,14 @@ impl IsField for Degree2ExtensionField {
     fn from_base_type(x: Self::BaseType) -> Self::BaseType {
         x
     }
+
+    /// Samples each coordinate independently.
+    fn from_random_limbs(next_limb: &mut impl FnMut() -> u64) -> Self::BaseType {
+        [
+            FieldElement::from_random_limbs(next_limb),
+            FieldElement::from_random_limbs(next_limb),
+        ]
+    }
 }
 
 impl IsSubFieldOf<Degree2ExtensionField> for BLS12381PrimeField {
@@ -293,6 +303,14 @@ mod tests {
     use super::*;
     type Fp12E = FieldElement<Degree12ExtensionField>;
 
+    #[test]
+    fn extension_degrees_are_the_degrees_over_the_prime_field() {
+        assert_eq!(BLS12381PrimeField::EXTENSION_DEGREE, 1);
+        assert_eq!(Degree2ExtensionField::EXTENSION_DEGREE, 2);
+        assert_eq!(Degree6ExtensionField::EXTENSION_DEGREE, 6);
+        assert_eq!(Degree12ExtensionField::EXTENSION_DEGREE, 12);
+    }
+
     #[test]
     fn element_squared_1() {
         // base = 1 + u + (1 + u)v + (1 + u)v^2 + ((1+u) + (1 + u)v + (1+ u)v^2)w
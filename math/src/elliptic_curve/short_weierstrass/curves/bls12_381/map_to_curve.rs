@@ -0,0 +1,375 @@
+//! Maps from field elements to points of BLS12-381, as specified in RFC 9380 (section 6.6.3)
+//! for the suites BLS12381G1_XMD:SHA-256_SSWU_RO_ and BLS12381G2_XMD:SHA-256_SSWU_RO_.
+//!
+//! The simplified SWU map sends a field element to a point of a curve E' isogenous to the curve
+//! and the isogeny takes it to the curve. Hashing to the subgroup of order r takes two such
+//! points, adds them and clears the cofactor.
+
+use super::{
+    curve::{BLS12381Curve, BLS12381FieldElement, BLS12381TwistCurveFieldElement},
+    sqrt::sqrt_qfe,
+    twist::BLS12381TwistCurve,
+};
+use crate::{
+    cyclic_group::IsGroup,
+    elliptic_curve::short_weierstrass::{
+        point::ShortWeierstrassProjectivePoint, traits::IsShortWeierstrass,
+    },
+    field::{element::FieldElement, traits::IsField},
+};
+
+pub type G1Point = ShortWeierstrassProjectivePoint<BLS12381Curve>;
+pub type G2Point = ShortWeierstrassProjectivePoint<BLS12381TwistCurve>;
+
+/// Maps a field element to a point of the curve, which is not necessarily in the subgroup of
+/// order r.
+pub fn map_to_curve_g1(u: &BLS12381FieldElement) -> G1Point {
+    let (x, y) = simplified_swu(
+        u,
+        &ISO_11_A,
+        &ISO_11_B,
+        &BLS12381FieldElement::from(11),
+        |value| value.sqrt().map(|(root, _)| root),
+        sgn0_fp,
+    );
+    iso_map(
+        &x,
+        &y,
+        &ISO_11_X_NUM,
+        &ISO_11_X_DEN,
+        &ISO_11_Y_NUM,
+        &ISO_11_Y_DEN,
+    )
+}
+
+/// Maps an element of Fp2 to a point of the twist, which is not necessarily in the subgroup of
+/// order r.
+pub fn map_to_curve_g2(u: &BLS12381TwistCurveFieldElement) -> G2Point {
+    // Z = -(2 + i)
+    let z = -fp2_from_u64(2, 1);
+    let (x, y) = simplified_swu(
+        u,
+        &fp2_from_u64(0, 240),
+        &fp2_from_u64(1012, 1012),
+        &z,
+        |value| sqrt_qfe(value, 0),
+        sgn0_fp2,
+    );
+    iso_map(
+        &x,
+        &y,
+        &ISO_3_X_NUM,
+        &ISO_3_X_DEN,
+        &ISO_3_Y_NUM,
+        &ISO_3_Y_DEN,
+    )
+}
+
+/// Simplified SWU map to the curve `y^2 = x^3 + a x + b`, with `a` and `b` nonzero.
+/// https://www.rfc-editor.org/rfc/rfc9380#section-6.6.2
+fn simplified_swu<F: IsField>(
+    u: &FieldElement<F>,
+    a: &FieldElement<F>,
+    b: &FieldElement<F>,
+    z: &FieldElement<F>,
+    sqrt: impl Fn(&FieldElement<F>) -> Option<FieldElement<F>>,
+    sgn0: impl Fn(&FieldElement<F>) -> bool,
+) -> (FieldElement<F>, FieldElement<F>) {
+    let g = |x: &FieldElement<F>| x.square() * x + a * x + b;
+
+    let z_u2 = z * u.square();
+    let tv1 = z_u2.square() + &z_u2;
+    let x1 = match tv1.inv() {
+        Ok(tv1_inv) => (-b / a) * (tv1_inv + FieldElement::one()),
+        // exceptional case, when Z^2 u^4 + Z u^2 = 0
+        Err(_) => b / (z * a),
+    };
+
+    let (x, y) = match sqrt(&g(&x1)) {
+        Some(y) => (x1, y),
+        None => {
+            // g(x1) is not a square, so g(x2) is one
+            let x2 = z_u2 * x1;
+            let y = sqrt(&g(&x2)).expect("g(x2) is a square when g(x1) is not");
+            (x2, y)
+        }
+    };
+    let y = if sgn0(u) == sgn0(&y) { y } else { -y };
+    (x, y)
+}
+
+/// Evaluates the isogeny, returning the point at infinity for the points of its kernel.
+fn iso_map<F: IsField, E: IsShortWeierstrass<BaseField = F>>(
+    x: &FieldElement<F>,
+    y: &FieldElement<F>,
+    x_num: &[FieldElement<F>],
+    x_den: &[FieldElement<F>],
+    y_num: &[FieldElement<F>],
+    y_den: &[FieldElement<F>],
+) -> ShortWeierstrassProjectivePoint<E> {
+    let (Ok(x_den_inv), Ok(y_den_inv)) = (evaluate(x_den, x).inv(), evaluate(y_den, x).inv())
+    else {
+        return ShortWeierstrassProjectivePoint::neutral_element();
+    };
+    ShortWeierstrassProjectivePoint::new([
+        evaluate(x_num, x) * x_den_inv,
+        y * evaluate(y_num, x) * y_den_inv,
+        FieldElement::one(),
+    ])
+}
+
+/// Evaluates the polynomial with the given coefficients, lowest degree first.
+fn evaluate<F: IsField>(coefficients: &[FieldElement<F>], x: &FieldElement<F>) -> FieldElement<F> {
+    coefficients
+        .iter()
+        .rev()
+        .fold(FieldElement::zero(), |acc, coefficient| {
+            acc * x + coefficient
+        })
+}
+
+/// Parity of the element, as defined in https://www.rfc-editor.org/rfc/rfc9380#section-4.1
+fn sgn0_fp(value: &BLS12381FieldElement) -> bool {
+    // limbs are big-endian
+    value.representative().limbs[5] & 1 == 1
+}
+
+fn sgn0_fp2(value: &BLS12381TwistCurveFieldElement) -> bool {
+    let [c0, c1] = value.value();
+    sgn0_fp(c0) || (*c0 == BLS12381FieldElement::zero() && sgn0_fp(c1))
+}
+
+fn fp2_from_u64(c0: u64, c1: u64) -> BLS12381TwistCurveFieldElement {
+    BLS12381TwistCurveFieldElement::new([FieldElement::from(c0), FieldElement::from(c1)])
+}
+
+const fn fp2(c0: &str, c1: &str) -> BLS12381TwistCurveFieldElement {
+    BLS12381TwistCurveFieldElement::const_from_raw([
+        FieldElement::from_hex_unchecked(c0),
+        FieldElement::from_hex_unchecked(c1),
+    ])
+}
+
+/// Coefficients of E1': y^2 = x^3 + A' x + B'.
+const ISO_11_A: BLS12381FieldElement = BLS12381FieldElement::from_hex_unchecked(
+    "00144698a3b8e9433d693a02c96d4982b0ea985383ee66a8d8e8981aefd881ac98936f8da0e0f97f5cf428082d584c1d",
+);
+const ISO_11_B: BLS12381FieldElement = BLS12381FieldElement::from_hex_unchecked(
+    "12e2908d11688030018b12e8753eee3b2016c1f0f24f4070a0b9c14fcef35ef55a23215a316ceaa5d1cc48e98e172be0",
+);
+
+/// Coefficients of the 11-isogeny from E1' to the curve, lowest degree first.
+/// https://www.rfc-editor.org/rfc/rfc9380#appendix-E.2
+const ISO_11_X_NUM: [BLS12381FieldElement; 12] = [
+    BLS12381FieldElement::from_hex_unchecked("11a05f2b1e833340b809101dd99815856b303e88a2d7005ff2627b56cdb4e2c85610c2d5f2e62d6eaeac1662734649b7"),
+    BLS12381FieldElement::from_hex_unchecked("17294ed3e943ab2f0588bab22147a81c7c17e75b2f6a8417f565e33c70d1e86b4838f2a6f318c356e834eef1b3cb83bb"),
+    BLS12381FieldElement::from_hex_unchecked("0d54005db97678ec1d1048c5d10a9a1bce032473295983e56878e501ec68e25c958c3e3d2a09729fe0179f9dac9edcb0"),
+    BLS12381FieldElement::from_hex_unchecked("1778e7166fcc6db74e0609d307e55412d7f5e4656a8dbf25f1b33289f1b330835336e25ce3107193c5b388641d9b6861"),
+    BLS12381FieldElement::from_hex_unchecked("0e99726a3199f4436642b4b3e4118e5499db995a1257fb3f086eeb65982fac18985a286f301e77c451154ce9ac8895d9"),
+    BLS12381FieldElement::from_hex_unchecked("1630c3250d7313ff01d1201bf7a74ab5db3cb17dd952799b9ed3ab9097e68f90a0870d2dcae73d19cd13c1c66f652983"),
+    BLS12381FieldElement::from_hex_unchecked("0d6ed6553fe44d296a3726c38ae652bfb11586264f0f8ce19008e218f9c86b2a8da25128c1052ecaddd7f225a139ed84"),
+    BLS12381FieldElement::from_hex_unchecked("17b81e7701abdbe2e8743884d1117e53356de5ab275b4db1a682c62ef0f2753339b7c8f8c8f475af9ccb5618e3f0c88e"),
+    BLS12381FieldElement::from_hex_unchecked("080d3cf1f9a78fc47b90b33563be990dc43b756ce79f5574a2c596c928c5d1de4fa295f296b74e956d71986a8497e317"),
+    BLS12381FieldElement::from_hex_unchecked("169b1f8e1bcfa7c42e0c37515d138f22dd2ecb803a0c5c99676314baf4bb1b7fa3190b2edc0327797f241067be390c9e"),
+    BLS12381FieldElement::from_hex_unchecked("10321da079ce07e272d8ec09d2565b0dfa7dccdde6787f96d50af36003b14866f69b771f8c285decca67df3f1605fb7b"),
+    BLS12381FieldElement::from_hex_unchecked("06e08c248e260e70bd1e962381edee3d31d79d7e22c837bc23c0bf1bc24c6b68c24b1b80b64d391fa9c8ba2e8ba2d229"),
+];
+
+const ISO_11_X_DEN: [BLS12381FieldElement; 11] = [
+    BLS12381FieldElement::from_hex_unchecked("08ca8d548cff19ae18b2e62f4bd3fa6f01d5ef4ba35b48ba9c9588617fc8ac62b558d681be343df8993cf9fa40d21b1c"),
+    BLS12381FieldElement::from_hex_unchecked("12561a5deb559c4348b4711298e536367041e8ca0cf0800c0126c2588c48bf5713daa8846cb026e9e5c8276ec82b3bff"),
+    BLS12381FieldElement::from_hex_unchecked("0b2962fe57a3225e8137e629bff2991f6f89416f5a718cd1fca64e00b11aceacd6a3d0967c94fedcfcc239ba5cb83e19"),
+    BLS12381FieldElement::from_hex_unchecked("03425581a58ae2fec83aafef7c40eb545b08243f16b1655154cca8abc28d6fd04976d5243eecf5c4130de8938dc62cd8"),
+    BLS12381FieldElement::from_hex_unchecked("13a8e162022914a80a6f1d5f43e7a07dffdfc759a12062bb8d6b44e833b306da9bd29ba81f35781d539d395b3532a21e"),
+    BLS12381FieldElement::from_hex_unchecked("0e7355f8e4e667b955390f7f0506c6e9395735e9ce9cad4d0a43bcef24b8982f7400d24bc4228f11c02df9a29f6304a5"),
+    BLS12381FieldElement::from_hex_unchecked("0772caacf16936190f3e0c63e0596721570f5799af53a1894e2e073062aede9cea73b3538f0de06cec2574496ee84a3a"),
+    BLS12381FieldElement::from_hex_unchecked("14a7ac2a9d64a8b230b3f5b074cf01996e7f63c21bca68a81996e1cdf9822c580fa5b9489d11e2d311f7d99bbdcc5a5e"),
+    BLS12381FieldElement::from_hex_unchecked("0a10ecf6ada54f825e920b3dafc7a3cce07f8d1d7161366b74100da67f39883503826692abba43704776ec3a79a1d641"),
+    BLS12381FieldElement::from_hex_unchecked("095fc13ab9e92ad4476d6e3eb3a56680f682b4ee96f7d03776df533978f31c1593174e4b4b7865002d6384d168ecdd0a"),
+    BLS12381FieldElement::from_hex_unchecked("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001"),
+];
+
+const ISO_11_Y_NUM: [BLS12381FieldElement; 16] = [
+    BLS12381FieldElement::from_hex_unchecked("090d97c81ba24ee0259d1f094980dcfa11ad138e48a869522b52af6c956543d3cd0c7aee9b3ba3c2be9845719707bb33"),
+    BLS12381FieldElement::from_hex_unchecked("134996a104ee5811d51036d776fb46831223e96c254f383d0f906343eb67ad34d6c56711962fa8bfe097e75a2e41c696"),
+    BLS12381FieldElement::from_hex_unchecked("00cc786baa966e66f4a384c86a3b49942552e2d658a31ce2c344be4b91400da7d26d521628b00523b8dfe240c72de1f6"),
+    BLS12381FieldElement::from_hex_unchecked("01f86376e8981c217898751ad8746757d42aa7b90eeb791c09e4a3ec03251cf9de405aba9ec61deca6355c77b0e5f4cb"),
+    BLS12381FieldElement::from_hex_unchecked("08cc03fdefe0ff135caf4fe2a21529c4195536fbe3ce50b879833fd221351adc2ee7f8dc099040a841b6daecf2e8fedb"),
+    BLS12381FieldElement::from_hex_unchecked("16603fca40634b6a2211e11db8f0a6a074a7d0d4afadb7bd76505c3d3ad5544e203f6326c95a807299b23ab13633a5f0"),
+    BLS12381FieldElement::from_hex_unchecked("04ab0b9bcfac1bbcb2c977d027796b3ce75bb8ca2be184cb5231413c4d634f3747a87ac2460f415ec961f8855fe9d6f2"),
+    BLS12381FieldElement::from_hex_unchecked("0987c8d5333ab86fde9926bd2ca6c674170a05bfe3bdd81ffd038da6c26c842642f64550fedfe935a15e4ca31870fb29"),
+    BLS12381FieldElement::from_hex_unchecked("09fc4018bd96684be88c9e221e4da1bb8f3abd16679dc26c1e8b6e6a1f20cabe69d65201c78607a360370e577bdba587"),
+    BLS12381FieldElement::from_hex_unchecked("0e1bba7a1186bdb5223abde7ada14a23c42a0ca7915af6fe06985e7ed1e4d43b9b3f7055dd4eba6f2bafaaebca731c30"),
+    BLS12381FieldElement::from_hex_unchecked("19713e47937cd1be0dfd0b8f1d43fb93cd2fcbcb6caf493fd1183e416389e61031bf3a5cce3fbafce813711ad011c132"),
+    BLS12381FieldElement::from_hex_unchecked("18b46a908f36f6deb918c143fed2edcc523559b8aaf0c2462e6bfe7f911f643249d9cdf41b44d606ce07c8a4d0074d8e"),
+    BLS12381FieldElement::from_hex_unchecked("0b182cac101b9399d155096004f53f447aa7b12a3426b08ec02710e807b4633f06c851c1919211f20d4c04f00b971ef8"),
+    BLS12381FieldElement::from_hex_unchecked("0245a394ad1eca9b72fc00ae7be315dc757b3b080d4c158013e6632d3c40659cc6cf90ad1c232a6442d9d3f5db980133"),
+    BLS12381FieldElement::from_hex_unchecked("05c129645e44cf1102a159f748c4a3fc5e673d81d7e86568d9ab0f5d396a7ce46ba1049b6579afb7866b1e715475224b"),
+    BLS12381FieldElement::from_hex_unchecked("15e6be4e990f03ce4ea50b3b42df2eb5cb181d8f84965a3957add4fa95af01b2b665027efec01c7704b456be69c8b604"),
+];
+
+const ISO_11_Y_DEN: [BLS12381FieldElement; 16] = [
+    BLS12381FieldElement::from_hex_unchecked("16112c4c3a9c98b252181140fad0eae9601a6de578980be6eec3232b5be72e7a07f3688ef60c206d01479253b03663c1"),
+    BLS12381FieldElement::from_hex_unchecked("1962d75c2381201e1a0cbd6c43c348b885c84ff731c4d59ca4a10356f453e01f78a4260763529e3532f6102c2e49a03d"),
+    BLS12381FieldElement::from_hex_unchecked("058df3306640da276faaae7d6e8eb15778c4855551ae7f310c35a5dd279cd2eca6757cd636f96f891e2538b53dbf67f2"),
+    BLS12381FieldElement::from_hex_unchecked("16b7d288798e5395f20d23bf89edb4d1d115c5dbddbcd30e123da489e726af41727364f2c28297ada8d26d98445f5416"),
+    BLS12381FieldElement::from_hex_unchecked("0be0e079545f43e4b00cc912f8228ddcc6d19c9f0f69bbb0542eda0fc9dec916a20b15dc0fd2ededda39142311a5001d"),
+    BLS12381FieldElement::from_hex_unchecked("08d9e5297186db2d9fb266eaac783182b70152c65550d881c5ecd87b6f0f5a6449f38db9dfa9cce202c6477faaf9b7ac"),
+    BLS12381FieldElement::from_hex_unchecked("166007c08a99db2fc3ba8734ace9824b5eecfdfa8d0cf8ef5dd365bc400a0051d5fa9c01a58b1fb93d1a1399126a775c"),
+    BLS12381FieldElement::from_hex_unchecked("16a3ef08be3ea7ea03bcddfabba6ff6ee5a4375efa1f4fd7feb34fd206357132b920f5b00801dee460ee415a15812ed9"),
+    BLS12381FieldElement::from_hex_unchecked("1866c8ed336c61231a1be54fd1d74cc4f9fb0ce4c6af5920abc5750c4bf39b4852cfe2f7bb9248836b233d9d55535d4a"),
+    BLS12381FieldElement::from_hex_unchecked("167a55cda70a6e1cea820597d94a84903216f763e13d87bb5308592e7ea7d4fbc7385ea3d529b35e346ef48bb8913f55"),
+    BLS12381FieldElement::from_hex_unchecked("04d2f259eea405bd48f010a01ad2911d9c6dd039bb61a6290e591b36e636a5c871a5c29f4f83060400f8b49cba8f6aa8"),
+    BLS12381FieldElement::from_hex_unchecked("0accbb67481d033ff5852c1e48c50c477f94ff8aefce42d28c0f9a88cea7913516f968986f7ebbea9684b529e2561092"),
+    BLS12381FieldElement::from_hex_unchecked("0ad6b9514c767fe3c3613144b45f1496543346d98adf02267d5ceef9a00d9b8693000763e3b90ac11e99b138573345cc"),
+    BLS12381FieldElement::from_hex_unchecked("02660400eb2e4f3b628bdd0d53cd76f2bf565b94e72927c1cb748df27942480e420517bd8714cc80d1fadc1326ed06f7"),
+    BLS12381FieldElement::from_hex_unchecked("0e0fa1d816ddc03e6b24255e0d7819c171c40f65e273b853324efcd6356caa205ca2f570f13497804415473a1d634b8f"),
+    BLS12381FieldElement::from_hex_unchecked("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001"),
+];
+
+/// Coefficients of the 3-isogeny from E2' to the twist, lowest degree first.
+/// https://www.rfc-editor.org/rfc/rfc9380#appendix-E.3
+const ISO_3_X_NUM: [BLS12381TwistCurveFieldElement; 4] = [
+    fp2("05c759507e8e333ebb5b7a9a47d7ed8532c52d39fd3a042a88b58423c50ae15d5c2638e343d9c71c6238aaaaaaaa97d6", "05c759507e8e333ebb5b7a9a47d7ed8532c52d39fd3a042a88b58423c50ae15d5c2638e343d9c71c6238aaaaaaaa97d6"),
+    fp2("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000", "11560bf17baa99bc32126fced787c88f984f87adf7ae0c7f9a208c6b4f20a4181472aaa9cb8d555526a9ffffffffc71a"),
+    fp2("11560bf17baa99bc32126fced787c88f984f87adf7ae0c7f9a208c6b4f20a4181472aaa9cb8d555526a9ffffffffc71e", "08ab05f8bdd54cde190937e76bc3e447cc27c3d6fbd7063fcd104635a790520c0a395554e5c6aaaa9354ffffffffe38d"),
+    fp2("171d6541fa38ccfaed6dea691f5fb614cb14b4e7f4e810aa22d6108f142b85757098e38d0f671c7188e2aaaaaaaa5ed1", "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"),
+];
+
+const ISO_3_X_DEN: [BLS12381TwistCurveFieldElement; 3] = [
+    fp2("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000", "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaa63"),
+    fp2("00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c", "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaa9f"),
+    fp2("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001", "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"),
+];
+
+const ISO_3_Y_NUM: [BLS12381TwistCurveFieldElement; 4] = [
+    fp2("1530477c7ab4113b59a4c18b076d11930f7da5d4a07f649bf54439d87d27e500fc8c25ebf8c92f6812cfc71c71c6d706", "1530477c7ab4113b59a4c18b076d11930f7da5d4a07f649bf54439d87d27e500fc8c25ebf8c92f6812cfc71c71c6d706"),
+    fp2("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000", "05c759507e8e333ebb5b7a9a47d7ed8532c52d39fd3a042a88b58423c50ae15d5c2638e343d9c71c6238aaaaaaaa97be"),
+    fp2("11560bf17baa99bc32126fced787c88f984f87adf7ae0c7f9a208c6b4f20a4181472aaa9cb8d555526a9ffffffffc71c", "08ab05f8bdd54cde190937e76bc3e447cc27c3d6fbd7063fcd104635a790520c0a395554e5c6aaaa9354ffffffffe38f"),
+    fp2("124c9ad43b6cf79bfbf7043de3811ad0761b0f37a1e26286b0e977c69aa274524e79097a56dc4bd9e1b371c71c718b10", "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"),
+];
+
+const ISO_3_Y_DEN: [BLS12381TwistCurveFieldElement; 4] = [
+    fp2("1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffa8fb", "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffa8fb"),
+    fp2("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000", "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffa9d3"),
+    fp2("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000012", "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaa99"),
+    fp2("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001", "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fp(value: &str) -> BLS12381FieldElement {
+        BLS12381FieldElement::from_hex_unchecked(value)
+    }
+
+    // Test vectors of RFC 9380 (appendix J.9.1), for the message ""
+    #[test]
+    fn map_to_curve_g1_matches_rfc9380() {
+        let u0 = fp("0ba14bd907ad64a016293ee7c2d276b8eae71f25a4b941eece7b0d89f17f75cb3ae5438a614fb61d6835ad59f29c564f");
+        let u1 = fp("019b9bd7979f12657976de2884c7cce192b82c177c80e0ec604436a7f538d231552f0d96d9f7babe5fa3b19b3ff25ac9");
+        let q0 = map_to_curve_g1(&u0);
+        let q1 = map_to_curve_g1(&u1);
+
+        assert_eq!(
+            q0,
+            G1Point::new([
+                fp("11a3cce7e1d90975990066b2f2643b9540fa40d6137780df4e753a8054d07580db3b7f1f03396333d4a359d1fe3766fe"),
+                fp("0eeaf6d794e479e270da10fdaf768db4c96b650a74518fc67b04b03927754bac66f3ac720404f339ecdcc028afa091b7"),
+                BLS12381FieldElement::one(),
+            ])
+        );
+        assert_eq!(
+            q1,
+            G1Point::new([
+                fp("160003aaf1632b13396dbad518effa00fff532f604de1a7fc2082ff4cb0afa2d63b2c32da1bef2bf6c5ca62dc6b72f9c"),
+                fp("0d8bb2d14e20cf9f6036152ed386d79189415b6d015a20133acb4e019139b94e9c146aaad5817f866c95d609a361735e"),
+                BLS12381FieldElement::one(),
+            ])
+        );
+
+        let p = q0.operate_with(&q1).clear_cofactor();
+        assert!(p.is_in_subgroup());
+        assert_eq!(
+            p,
+            G1Point::new([
+                fp("052926add2207b76ca4fa57a8734416c8dc95e24501772c814278700eed6d1e4e8cf62d9c09db0fac349612b759e79a1"),
+                fp("08ba738453bfed09cb546dbb0783dbb3a5f1f566ed67bb6be0e8c67e2e81a4cc68ee29813bb7994998f3eae0c9c6a265"),
+                BLS12381FieldElement::one(),
+            ])
+        );
+    }
+
+    // Test vectors of RFC 9380 (appendix J.10.1), for the message ""
+    #[test]
+    fn map_to_curve_g2_matches_rfc9380() {
+        let fp2 = |c0, c1| BLS12381TwistCurveFieldElement::new([fp(c0), fp(c1)]);
+        let u0 = fp2(
+            "03dbc2cce174e91ba93cbb08f26b917f98194a2ea08d1cce75b2b9cc9f21689d80bd79b594a613d0a68eb807dfdc1cf8",
+            "05a2acec64114845711a54199ea339abd125ba38253b70a92c876df10598bd1986b739cad67961eb94f7076511b3b39a",
+        );
+        let u1 = fp2(
+            "02f99798e8a5acdeed60d7e18e9120521ba1f47ec090984662846bc825de191b5b7641148c0dbc237726a334473eee94",
+            "145a81e418d4010cc027a68f14391b30074e89e60ee7a22f87217b2f6eb0c4b94c9115b436e6fa4607e95a98de30a435",
+        );
+        let q0 = map_to_curve_g2(&u0);
+        let q1 = map_to_curve_g2(&u1);
+
+        assert_eq!(
+            q0,
+            G2Point::new([
+                fp2(
+                    "019ad3fc9c72425a998d7ab1ea0e646a1f6093444fc6965f1cad5a3195a7b1e099c050d57f45e3fa191cc6d75ed7458c",
+                    "171c88b0b0efb5eb2b88913a9e74fe111a4f68867b59db252ce5868af4d1254bfab77ebde5d61cd1a86fb2fe4a5a1c1d",
+                ),
+                fp2(
+                    "0ba10604e62bdd9eeeb4156652066167b72c8d743b050fb4c1016c31b505129374f76e03fa127d6a156213576910fef3",
+                    "0eb22c7a543d3d376e9716a49b72e79a89c9bfe9feee8533ed931cbb5373dde1fbcd7411d8052e02693654f71e15410a",
+                ),
+                BLS12381TwistCurveFieldElement::one(),
+            ])
+        );
+        assert_eq!(
+            q1,
+            G2Point::new([
+                fp2(
+                    "113d2b9cd4bd98aee53470b27abc658d91b47a78a51584f3d4b950677cfb8a3e99c24222c406128c91296ef6b45608be",
+                    "13855912321c5cb793e9d1e88f6f8d342d49c0b0dbac613ee9e17e3c0b3c97dfbb5a49cc3fb45102fdbaf65e0efe2632",
+                ),
+                fp2(
+                    "0fd3def0b7574a1d801be44fde617162aa2e89da47f464317d9bb5abc3a7071763ce74180883ad7ad9a723a9afafcdca",
+                    "056f617902b3c0d0f78a9a8cbda43a26b65f602f8786540b9469b060db7b38417915b413ca65f875c130bebfaa59790c",
+                ),
+                BLS12381TwistCurveFieldElement::one(),
+            ])
+        );
+
+        let p = q0.operate_with(&q1).clear_cofactor();
+        assert!(p.is_in_subgroup());
+        assert_eq!(
+            p,
+            G2Point::new([
+                fp2(
+                    "0141ebfbdca40eb85b87142e130ab689c673cf60f1a3e98d69335266f30d9b8d4ac44c1038e9dcdd5393faf5c41fb78a",
+                    "05cb8437535e20ecffaef7752baddf98034139c38452458baeefab379ba13dff5bf5dd71b72418717047f5b0f37da03d",
+                ),
+                fp2(
+                    "0503921d7f6a12805e72940b963c0cf3471c7b2a524950ca195d11062ee75ec076daf2d4bc358c4b190c0c98064fdd92",
+                    "12424ac32561493f3fe3c260708a12b7c620e7be00099a974e259ddc7d1f6395c3c811cdd19f1e8dbf3e9ecfdcbab8d6",
+                ),
+                BLS12381TwistCurveFieldElement::one(),
+            ])
+        );
+    }
+
+    #[test]
+    fn cleared_points_are_in_the_subgroup() {
+        for i in 1..4 {
+            let g1 = map_to_curve_g1(&BLS12381FieldElement::from(i));
+            assert!(g1.clear_cofactor().is_in_subgroup());
+            let g2 = map_to_curve_g2(&fp2_from_u64(i, 2 * i));
+            assert!(g2.clear_cofactor().is_in_subgroup());
+        }
+    }
+}
@@ -2,6 +2,7 @@ pub mod compression;
 pub mod curve;
 pub mod default_types;
 pub mod field_extension;
+pub mod map_to_curve;
 pub mod sqrt;
 pub mod twist;
 
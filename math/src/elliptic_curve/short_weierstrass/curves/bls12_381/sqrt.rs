@@ -33,13 +33,24 @@ pub fn sqrt_qfe(
         let b = input.value()[1].clone();
         if b == BLS12381FieldElement::zero() {
             // second part is zero
-            let (y_sqrt_1, y_sqrt_2) = a.sqrt()?;
-            let y_aux = select_sqrt_value_from_third_bit(y_sqrt_1, y_sqrt_2, third_bit);
-
-            Some(BLS12381TwistCurveFieldElement::new([
-                y_aux,
-                BLS12381FieldElement::zero(),
-            ]))
+            if let Some((y_sqrt_1, y_sqrt_2)) = a.sqrt() {
+                let y_aux = select_sqrt_value_from_third_bit(y_sqrt_1, y_sqrt_2, third_bit);
+
+                Some(BLS12381TwistCurveFieldElement::new([
+                    y_aux,
+                    BLS12381FieldElement::zero(),
+                ]))
+            } else {
+                // a is not a square in Fp, so -a is one since p = 3 mod 4, and the root is
+                // i * sqrt(-a)
+                let (y_sqrt_1, y_sqrt_2) = (-a).sqrt()?;
+                let y_aux = select_sqrt_value_from_third_bit(y_sqrt_1, y_sqrt_2, third_bit);
+
+                Some(BLS12381TwistCurveFieldElement::new([
+                    BLS12381FieldElement::zero(),
+                    y_aux,
+                ]))
+            }
         } else {
             // second part of the input field number is non-zero
             // instead of "sum" is: -beta
@@ -103,6 +114,14 @@ mod tests {
         assert_eq!(value_root[1].clone(), value_qfe_expected[1].clone());
     }
 
+    #[test]
+    fn test_sqrt_qfe_of_non_square_in_base_field() {
+        // -1 is not a square in Fp, and its square roots in Fp2 are +-i
+        let minus_one = -super::BLS12381TwistCurveFieldElement::one();
+        let root = super::sqrt_qfe(&minus_one, 0).unwrap();
+        assert_eq!(root.square(), minus_one);
+    }
+
     #[test]
     fn test_sqrt_qfe_2() {
         let c0 = BLS12381FieldElement::from_hex("0x02").unwrap();
@@ -1,6 +1,10 @@
+use super::default_types::FrConfig;
 use super::field_extension::{BN254PrimeField, Degree2ExtensionField};
+use crate::elliptic_curve::glv::IsGLVCurve;
 use crate::elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint;
 use crate::elliptic_curve::traits::IsEllipticCurve;
+use crate::field::fields::montgomery_backed_prime_fields::IsModulus;
+use crate::unsigned_integer::{element::U256, traits::IsUnsignedInteger};
 use crate::{
     elliptic_curve::short_weierstrass::traits::IsShortWeierstrass, field::element::FieldElement,
 };
@@ -32,6 +36,37 @@ impl IsShortWeierstrass for BN254Curve {
     fn b() -> FieldElement<Self::BaseField> {
         FieldElement::from(3)
     }
+
+    /// The curve has prime order, so every point is in the subgroup where the endomorphism
+    /// acts as `λ`.
+    fn glv_split<T: IsUnsignedInteger>(
+        p: &ShortWeierstrassProjectivePoint<Self>,
+        k: T,
+    ) -> Option<[(ShortWeierstrassProjectivePoint<Self>, U256); 2]> {
+        Some(Self::split_multiplication(p, k))
+    }
+}
+
+/// 𝛽 : primitive cube root of unity of 𝐹ₚ such that (𝑥, 𝑦) ⇒ (𝛽𝑥, 𝑦) is the endomorphism
+/// acting as multiplication by `LAMBDA` on the curve.
+pub const CUBE_ROOT_OF_UNITY: BN254FieldElement =
+    FieldElement::from_hex_unchecked("59e26bcea0d48bacd4f263f1acdb5c4f5763473177fffffe");
+
+impl IsGLVCurve for BN254Curve {
+    const SUBGROUP_ORDER: U256 = FrConfig::MODULUS;
+    const LAMBDA: U256 =
+        U256::from_hex_unchecked("b3c4d79d41a917585bfc41088d8daaa78b17ea66b99c90dd");
+    const LATTICE_BASIS: [U256; 4] = [
+        U256::from_hex_unchecked("89d3256894d213e3"),
+        U256::from_hex_unchecked("6f4d8248eeb859fc8211bbeb7d4f1128"),
+        U256::from_hex_unchecked("6f4d8248eeb859fd0be4e1541221250b"),
+        U256::from_hex_unchecked("89d3256894d213e3"),
+    ];
+
+    fn endomorphism(p: &Self::PointRepresentation) -> Self::PointRepresentation {
+        let [x, y, z] = p.coordinates();
+        Self::PointRepresentation::new([x * CUBE_ROOT_OF_UNITY, y.clone(), z.clone()])
+    }
 }
 
 #[cfg(test)]
@@ -1,6 +1,10 @@
+use crate::elliptic_curve::glv::IsGLVCurve;
 use crate::elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint;
 use crate::elliptic_curve::traits::IsEllipticCurve;
+use crate::field::fields::montgomery_backed_prime_fields::IsModulus;
 use crate::field::fields::pallas_field::Pallas255PrimeField;
+use crate::field::fields::vesta_field::MontgomeryConfigVesta255PrimeField;
+use crate::unsigned_integer::{element::U256, traits::IsUnsignedInteger};
 use crate::{
     elliptic_curve::short_weierstrass::traits::IsShortWeierstrass, field::element::FieldElement,
 };
@@ -29,6 +33,38 @@ impl IsShortWeierstrass for PallasCurve {
     fn b() -> FieldElement<Self::BaseField> {
         FieldElement::from(5)
     }
+
+    /// The curve has prime order, so every point is in the subgroup where the endomorphism
+    /// acts as `λ`.
+    fn glv_split<T: IsUnsignedInteger>(
+        p: &ShortWeierstrassProjectivePoint<Self>,
+        k: T,
+    ) -> Option<[(ShortWeierstrassProjectivePoint<Self>, U256); 2]> {
+        Some(Self::split_multiplication(p, k))
+    }
+}
+
+/// 𝛽 : primitive cube root of unity of 𝐹ₚ such that (𝑥, 𝑦) ⇒ (𝛽𝑥, 𝑦) is the endomorphism
+/// acting as multiplication by `LAMBDA` on the curve.
+pub const CUBE_ROOT_OF_UNITY: FieldElement<Pallas255PrimeField> = FieldElement::from_hex_unchecked(
+    "12ccca834acdba712caad5dc57aab1b01d1f8bd237ad31491dad5ebdfdfe4ab9",
+);
+
+impl IsGLVCurve for PallasCurve {
+    const SUBGROUP_ORDER: U256 = MontgomeryConfigVesta255PrimeField::MODULUS;
+    const LAMBDA: U256 =
+        U256::from_hex_unchecked("6819a58283e528e511db4d81cf70f5a0fed467d47c033af2aa9d2e050aa0e4f");
+    const LATTICE_BASIS: [U256; 4] = [
+        U256::from_hex_unchecked("49e69d1640f049157fcae1c700000001"),
+        U256::from_hex_unchecked("49e69d1640a899538cb1279300000000"),
+        U256::from_hex_unchecked("49e69d1640a899538cb1279300000000"),
+        U256::from_hex_unchecked("93cd3a2c8198e2690c7c095a00000001"),
+    ];
+
+    fn endomorphism(p: &Self::PointRepresentation) -> Self::PointRepresentation {
+        let [x, y, z] = p.coordinates();
+        Self::PointRepresentation::new([x * CUBE_ROOT_OF_UNITY, y.clone(), z.clone()])
+    }
 }
 
 #[cfg(test)]
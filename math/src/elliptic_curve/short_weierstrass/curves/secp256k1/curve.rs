@@ -0,0 +1,194 @@
+use crate::elliptic_curve::glv::IsGLVCurve;
+use crate::elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint;
+use crate::elliptic_curve::traits::IsEllipticCurve;
+use crate::field::fields::montgomery_backed_prime_fields::{
+    IsModulus, MontgomeryBackendPrimeField,
+};
+use crate::unsigned_integer::{element::U256, traits::IsUnsignedInteger};
+use crate::{
+    elliptic_curve::short_weierstrass::traits::IsShortWeierstrass, field::element::FieldElement,
+};
+
+/// secp256k1, the curve of Bitcoin and Ethereum signatures, as specified in SEC 2.
+/// p = 2^256 - 2^32 - 977
+/// a = 0
+/// b = 7
+/// n = 115792089237316195423570985008687907852837564279074904382605163141518161494337
+/// The group of points has prime order n, so every point but the neutral element is a generator.
+#[derive(Clone, Debug)]
+pub struct Secp256k1Curve;
+
+impl IsEllipticCurve for Secp256k1Curve {
+    type BaseField = Secp256k1PrimeField;
+    type PointRepresentation = ShortWeierstrassProjectivePoint<Self>;
+
+    fn generator() -> Self::PointRepresentation {
+        Self::PointRepresentation::new([
+            FieldElement::<Self::BaseField>::from_hex_unchecked(
+                "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            ),
+            FieldElement::<Self::BaseField>::from_hex_unchecked(
+                "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+            ),
+            FieldElement::one(),
+        ])
+    }
+}
+
+impl IsShortWeierstrass for Secp256k1Curve {
+    fn a() -> FieldElement<Self::BaseField> {
+        FieldElement::from(0)
+    }
+
+    fn b() -> FieldElement<Self::BaseField> {
+        FieldElement::from(7)
+    }
+
+    /// The curve has prime order, so every point is in the subgroup where the endomorphism
+    /// acts as `λ`.
+    fn glv_split<T: IsUnsignedInteger>(
+        p: &ShortWeierstrassProjectivePoint<Self>,
+        k: T,
+    ) -> Option<[(ShortWeierstrassProjectivePoint<Self>, U256); 2]> {
+        Some(Self::split_multiplication(p, k))
+    }
+}
+
+/// 𝛽 : primitive cube root of unity of 𝐹ₚ such that (𝑥, 𝑦) ⇒ (𝛽𝑥, 𝑦) is the endomorphism
+/// acting as multiplication by `LAMBDA` on the curve.
+pub const CUBE_ROOT_OF_UNITY: Secp256k1FieldElement = FieldElement::from_hex_unchecked(
+    "851695d49a83f8ef919bb86153cbcb16630fb68aed0a766a3ec693d68e6afa40",
+);
+
+impl IsGLVCurve for Secp256k1Curve {
+    const SUBGROUP_ORDER: U256 = FrConfig::MODULUS;
+    const LAMBDA: U256 = U256::from_hex_unchecked(
+        "ac9c52b33fa3cf1f5ad9e3fd77ed9ba4a880b9fc8ec739c2e0cfc810b51283ce",
+    );
+    const LATTICE_BASIS: [U256; 4] = [
+        U256::from_hex_unchecked("e4437ed6010e88286f547fa90abfe4c3"),
+        U256::from_hex_unchecked("3086d221a7d46bcde86c90e49284eb15"),
+        U256::from_hex_unchecked("3086d221a7d46bcde86c90e49284eb15"),
+        U256::from_hex_unchecked("114ca50f7a8e2f3f657c1108d9d44cfd8"),
+    ];
+
+    fn endomorphism(p: &Self::PointRepresentation) -> Self::PointRepresentation {
+        let [x, y, z] = p.coordinates();
+        Self::PointRepresentation::new([x * CUBE_ROOT_OF_UNITY, y.clone(), z.clone()])
+    }
+}
+
+pub const SECP256K1_PRIME_FIELD_ORDER: U256 =
+    U256::from_hex_unchecked("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f");
+
+#[derive(Clone, Debug)]
+pub struct Secp256k1FieldModulus;
+impl IsModulus<U256> for Secp256k1FieldModulus {
+    const MODULUS: U256 = SECP256K1_PRIME_FIELD_ORDER;
+}
+
+/// secp256k1 Fp
+pub type Secp256k1PrimeField = MontgomeryBackendPrimeField<Secp256k1FieldModulus, 4>;
+pub type Secp256k1FieldElement = FieldElement<Secp256k1PrimeField>;
+
+#[derive(Clone, Debug)]
+pub struct FrConfig;
+
+/// Modulus (Order) of the secp256k1 group
+impl IsModulus<U256> for FrConfig {
+    const MODULUS: U256 = U256::from_hex_unchecked(
+        "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+    );
+}
+
+/// secp256k1 Fr
+pub type FrField = MontgomeryBackendPrimeField<FrConfig, 4>;
+/// FrElement using MontgomeryBackend for secp256k1
+pub type FrElement = FieldElement<FrField>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cyclic_group::IsGroup, elliptic_curve::traits::EllipticCurveError};
+
+    #[allow(clippy::upper_case_acronyms)]
+    type FE = Secp256k1FieldElement;
+    type G = ShortWeierstrassProjectivePoint<Secp256k1Curve>;
+
+    fn generator_times_2() -> G {
+        let x = FE::from_hex_unchecked(
+            "c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5",
+        );
+        let y = FE::from_hex_unchecked(
+            "1ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a",
+        );
+        Secp256k1Curve::create_point_from_affine(x, y).unwrap()
+    }
+
+    fn generator_times_3() -> G {
+        let x = FE::from_hex_unchecked(
+            "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+        );
+        let y = FE::from_hex_unchecked(
+            "388f7b0f632de8140fe337e62a37f3566500a99934c2231b6cb9fd7584b8e672",
+        );
+        Secp256k1Curve::create_point_from_affine(x, y).unwrap()
+    }
+
+    #[test]
+    fn generator_satisfies_the_curve_equation() {
+        let g = Secp256k1Curve::generator();
+        assert_eq!(Secp256k1Curve::defining_equation(g.x(), g.y()), FE::zero());
+    }
+
+    #[test]
+    fn doubling_the_generator_works() {
+        let g = Secp256k1Curve::generator();
+        assert_eq!(g.operate_with(&g), generator_times_2());
+        assert_eq!(g.double(), generator_times_2());
+    }
+
+    #[test]
+    fn operate_with_self_works() {
+        let g = Secp256k1Curve::generator();
+        assert_eq!(g.operate_with_self(3_u16), generator_times_3());
+    }
+
+    #[test]
+    fn create_invalid_points_returns_an_error() {
+        assert_eq!(
+            Secp256k1Curve::create_point_from_affine(FE::from(0), FE::from(1)),
+            Err(EllipticCurveError::InvalidPoint)
+        );
+    }
+
+    #[test]
+    fn generator_has_the_order_of_the_scalar_field() {
+        let g = Secp256k1Curve::generator();
+        let n_minus_one = FrConfig::MODULUS - U256::from(1u64);
+
+        assert_eq!(g.operate_with_self(FrConfig::MODULUS), G::neutral_element());
+        assert_eq!(g.operate_with_self(n_minus_one), g.neg());
+    }
+
+    #[test]
+    fn operate_with_self_constant_time_matches_operate_with_self() {
+        let g = Secp256k1Curve::generator();
+        let n = FrConfig::MODULUS;
+        // `k + n` has 257 bits for the largest scalars and 256 for the smallest ones.
+        for k in [
+            U256::from(0u64),
+            U256::from(1u64),
+            U256::from(3u64),
+            U256::from_hex_unchecked(
+                "c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5",
+            ),
+            n - U256::from(1u64),
+        ] {
+            assert_eq!(
+                g.operate_with_self_constant_time(&k, &n),
+                g.operate_with_self(k)
+            );
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use crate::elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint;
+use crate::elliptic_curve::traits::IsEllipticCurve;
+use crate::field::fields::montgomery_backed_prime_fields::{
+    IsModulus, MontgomeryBackendPrimeField,
+};
+use crate::unsigned_integer::element::U256;
+use crate::{
+    elliptic_curve::short_weierstrass::traits::IsShortWeierstrass, field::element::FieldElement,
+};
+
+/// secp256r1, also known as P-256 or prime256v1, the NIST curve used by WebAuthn and TLS.
+/// p = 2^256 - 2^224 + 2^192 + 2^96 - 1
+/// a = -3
+/// b = 41058363725152142129326129780047268409114441015993725554835256314039467401291
+/// n = 115792089210356248762697446949407573529996955224135760342422259061068512044369
+/// The group of points has prime order n, so every point but the neutral element is a generator.
+#[derive(Clone, Debug)]
+pub struct Secp256r1Curve;
+
+impl IsEllipticCurve for Secp256r1Curve {
+    type BaseField = Secp256r1PrimeField;
+    type PointRepresentation = ShortWeierstrassProjectivePoint<Self>;
+
+    fn generator() -> Self::PointRepresentation {
+        Self::PointRepresentation::new([
+            FieldElement::<Self::BaseField>::from_hex_unchecked(
+                "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296",
+            ),
+            FieldElement::<Self::BaseField>::from_hex_unchecked(
+                "4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5",
+            ),
+            FieldElement::one(),
+        ])
+    }
+}
+
+impl IsShortWeierstrass for Secp256r1Curve {
+    fn a() -> FieldElement<Self::BaseField> {
+        -FieldElement::from(3)
+    }
+
+    fn b() -> FieldElement<Self::BaseField> {
+        FieldElement::from_hex_unchecked(
+            "5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b",
+        )
+    }
+}
+
+pub const SECP256R1_PRIME_FIELD_ORDER: U256 =
+    U256::from_hex_unchecked("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff");
+
+#[derive(Clone, Debug)]
+pub struct Secp256r1FieldModulus;
+impl IsModulus<U256> for Secp256r1FieldModulus {
+    const MODULUS: U256 = SECP256R1_PRIME_FIELD_ORDER;
+}
+
+/// secp256r1 Fp
+pub type Secp256r1PrimeField = MontgomeryBackendPrimeField<Secp256r1FieldModulus, 4>;
+pub type Secp256r1FieldElement = FieldElement<Secp256r1PrimeField>;
+
+#[derive(Clone, Debug)]
+pub struct FrConfig;
+
+/// Modulus (Order) of the secp256r1 group
+impl IsModulus<U256> for FrConfig {
+    const MODULUS: U256 = U256::from_hex_unchecked(
+        "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+    );
+}
+
+/// secp256r1 Fr
+pub type FrField = MontgomeryBackendPrimeField<FrConfig, 4>;
+/// FrElement using MontgomeryBackend for secp256r1
+pub type FrElement = FieldElement<FrField>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cyclic_group::IsGroup, elliptic_curve::traits::EllipticCurveError};
+
+    #[allow(clippy::upper_case_acronyms)]
+    type FE = Secp256r1FieldElement;
+    type G = ShortWeierstrassProjectivePoint<Secp256r1Curve>;
+
+    fn generator_times_2() -> G {
+        let x = FE::from_hex_unchecked(
+            "7cf27b188d034f7e8a52380304b51ac3c08969e277f21b35a60b48fc47669978",
+        );
+        let y = FE::from_hex_unchecked(
+            "07775510db8ed040293d9ac69f7430dbba7dade63ce982299e04b79d227873d1",
+        );
+        Secp256r1Curve::create_point_from_affine(x, y).unwrap()
+    }
+
+    fn generator_times_3() -> G {
+        let x = FE::from_hex_unchecked(
+            "5ecbe4d1a6330a44c8f7ef951d4bf165e6c6b721efada985fb41661bc6e7fd6c",
+        );
+        let y = FE::from_hex_unchecked(
+            "8734640c4998ff7e374b06ce1a64a2ecd82ab036384fb83d9a79b127a27d5032",
+        );
+        Secp256r1Curve::create_point_from_affine(x, y).unwrap()
+    }
+
+    #[test]
+    fn generator_satisfies_the_curve_equation() {
+        let g = Secp256r1Curve::generator();
+        assert_eq!(Secp256r1Curve::defining_equation(g.x(), g.y()), FE::zero());
+    }
+
+    #[test]
+    fn doubling_the_generator_works() {
+        let g = Secp256r1Curve::generator();
+        assert_eq!(g.operate_with(&g), generator_times_2());
+        assert_eq!(g.double(), generator_times_2());
+    }
+
+    #[test]
+    fn operate_with_self_works() {
+        let g = Secp256r1Curve::generator();
+        assert_eq!(g.operate_with_self(3_u16), generator_times_3());
+    }
+
+    #[test]
+    fn create_invalid_points_returns_an_error() {
+        assert_eq!(
+            Secp256r1Curve::create_point_from_affine(FE::from(0), FE::from(1)),
+            Err(EllipticCurveError::InvalidPoint)
+        );
+    }
+
+    #[test]
+    fn generator_has_the_order_of_the_scalar_field() {
+        let g = Secp256r1Curve::generator();
+        let n_minus_one = FrConfig::MODULUS - U256::from(1u64);
+
+        assert_eq!(g.operate_with_self(FrConfig::MODULUS), G::neutral_element());
+        assert_eq!(g.operate_with_self(n_minus_one), g.neg());
+    }
+}
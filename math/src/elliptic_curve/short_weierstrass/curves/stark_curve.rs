@@ -4,8 +4,13 @@ use crate::{
         traits::IsEllipticCurve,
     },
     field::{
-        element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+        element::FieldElement,
+        fields::{
+            fft_friendly::stark_252_prime_field::Stark252PrimeField,
+            montgomery_backed_prime_fields::{IsModulus, MontgomeryBackendPrimeField},
+        },
     },
+    unsigned_integer::element::U256,
 };
 
 #[derive(Clone, Debug)]
@@ -52,3 +57,33 @@ impl IsShortWeierstrass for StarkCurve {
         )
     }
 }
+
+#[derive(Clone, Debug)]
+pub struct FrConfig;
+
+/// Modulus (Order) of the STARK curve
+impl IsModulus<U256> for FrConfig {
+    const MODULUS: U256 = U256::from_hex_unchecked(
+        "0800000000000010ffffffffffffffffb781126dcae7b2321e66a241adc64d2f",
+    );
+}
+
+/// STARK curve Fr
+pub type FrField = MontgomeryBackendPrimeField<FrConfig, 4>;
+/// FrElement using MontgomeryBackend for the STARK curve
+pub type FrElement = FieldElement<FrField>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cyclic_group::IsGroup;
+
+    #[test]
+    fn generator_has_the_order_of_the_scalar_field() {
+        let g = StarkCurve::generator();
+        let n_minus_one = FrConfig::MODULUS - U256::from_u64(1);
+
+        assert!(g.operate_with_self(FrConfig::MODULUS).is_neutral_element());
+        assert_eq!(g.operate_with_self(n_minus_one), g.neg());
+    }
+}
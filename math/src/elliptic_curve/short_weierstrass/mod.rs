@@ -2,5 +2,8 @@
 pub mod curves;
 /// Structs for points
 pub mod point;
+/// SEC 1 encoding of points
+#[cfg(feature = "alloc")]
+pub mod sec1;
 /// Common behaviour for Elliptic curves.
 pub mod traits;
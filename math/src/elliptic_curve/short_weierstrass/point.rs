@@ -3,10 +3,12 @@ use crate::{
     elliptic_curve::{
         point::ProjectivePoint,
         traits::{EllipticCurveError, FromAffine, IsEllipticCurve},
+        wnaf::multi_wnaf_mul,
     },
     errors::DeserializationError,
     field::element::FieldElement,
-    traits::{ByteConversion, Deserializable},
+    traits::{ByteConversion, ConditionalSwap, Deserializable},
+    unsigned_integer::traits::IsUnsignedInteger,
 };
 
 use super::traits::IsShortWeierstrass;
@@ -125,6 +127,19 @@ impl<E: IsShortWeierstrass> ShortWeierstrassProjectivePoint<E> {
 
         Self::new([x, y, z])
     }
+
+    /// Multiplies the point by a public scalar `k`. Curves that split the multiplication with
+    /// [`IsShortWeierstrass::glv_split`] use a windowed NAF over the two halves, with half the
+    /// doublings, and the rest use [`IsGroup::operate_with_self`].
+    ///
+    /// The operations done depend on the bits of `k`, so secret scalars must be multiplied with
+    /// [`IsGroup::operate_with_self_constant_time`] instead.
+    pub fn glv_mul<T: IsUnsignedInteger>(&self, k: T) -> Self {
+        match E::glv_split(self, k) {
+            Some([(p1, k1), (p2, k2)]) => multi_wnaf_mul([(&p1, &k1), (&p2, &k2)]),
+            None => self.operate_with_self(k),
+        }
+    }
 }
 
 impl<E: IsEllipticCurve> PartialEq for ShortWeierstrassProjectivePoint<E> {
@@ -135,6 +150,15 @@ impl<E: IsEllipticCurve> PartialEq for ShortWeierstrassProjectivePoint<E> {
 
 impl<E: IsEllipticCurve> Eq for ShortWeierstrassProjectivePoint<E> {}
 
+impl<E: IsEllipticCurve> ConditionalSwap for ShortWeierstrassProjectivePoint<E>
+where
+    FieldElement<E::BaseField>: ConditionalSwap,
+{
+    fn conditional_swap(&mut self, other: &mut Self, swap: u64) {
+        self.0.conditional_swap(&mut other.0, swap);
+    }
+}
+
 impl<E: IsShortWeierstrass> FromAffine<E::BaseField> for ShortWeierstrassProjectivePoint<E> {
     fn from_affine(
         x: FieldElement<E::BaseField>,
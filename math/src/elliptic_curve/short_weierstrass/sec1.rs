@@ -0,0 +1,240 @@
+//! Point encoding of SEC 1 (section 2.3), used by ECDSA keys on secp256k1 and P-256.
+//!
+//! A point is encoded as `0x04 || x || y` uncompressed or as `0x02 || x` / `0x03 || x`
+//! compressed, where the tag gives the parity of `y`. Coordinates are big-endian and as long as
+//! the modulus of the base field. The point at infinity is the single byte `0x00`.
+
+use super::{point::ShortWeierstrassProjectivePoint, traits::IsShortWeierstrass};
+use crate::{
+    cyclic_group::IsGroup,
+    errors::ByteConversionError,
+    field::{element::FieldElement, traits::IsPrimeField},
+    traits::ByteConversion,
+};
+use alloc::{vec, vec::Vec};
+
+const INFINITY_TAG: u8 = 0x00;
+const EVEN_Y_TAG: u8 = 0x02;
+const ODD_Y_TAG: u8 = 0x03;
+const UNCOMPRESSED_TAG: u8 = 0x04;
+
+/// Encodes a point, compressed or uncompressed.
+pub fn encode_point<E>(point: &ShortWeierstrassProjectivePoint<E>, compressed: bool) -> Vec<u8>
+where
+    E: IsShortWeierstrass,
+    E::BaseField: IsPrimeField,
+    FieldElement<E::BaseField>: ByteConversion,
+{
+    if point.is_neutral_element() {
+        return vec![INFINITY_TAG];
+    }
+
+    let point = point.to_affine();
+    let x = coordinate_to_bytes::<E>(point.x());
+    let y = coordinate_to_bytes::<E>(point.y());
+
+    if compressed {
+        let tag = if is_odd(&y) { ODD_Y_TAG } else { EVEN_Y_TAG };
+        [&[tag][..], &x].concat()
+    } else {
+        [&[UNCOMPRESSED_TAG][..], &x, &y].concat()
+    }
+}
+
+/// Decodes a point in any of the encodings, checking that it is on the curve.
+pub fn decode_point<E>(
+    bytes: &[u8],
+) -> Result<ShortWeierstrassProjectivePoint<E>, ByteConversionError>
+where
+    E: IsShortWeierstrass,
+    E::BaseField: IsPrimeField,
+    FieldElement<E::BaseField>: ByteConversion,
+{
+    let (&tag, coordinates) = bytes
+        .split_first()
+        .ok_or(ByteConversionError::InvalidValue)?;
+    let len = coordinate_len::<E>();
+
+    match (tag, coordinates.len()) {
+        (INFINITY_TAG, 0) => Ok(ShortWeierstrassProjectivePoint::neutral_element()),
+        (EVEN_Y_TAG | ODD_Y_TAG, l) if l == len => {
+            let x = coordinate_from_bytes::<E>(coordinates)?;
+            let y_odd = tag == ODD_Y_TAG;
+            let y = lift_x::<E>(&x, y_odd).ok_or(ByteConversionError::InvalidValue)?;
+            Ok(ShortWeierstrassProjectivePoint::new([
+                x,
+                y,
+                FieldElement::one(),
+            ]))
+        }
+        (UNCOMPRESSED_TAG, l) if l == 2 * len => {
+            let x = coordinate_from_bytes::<E>(&coordinates[..len])?;
+            let y = coordinate_from_bytes::<E>(&coordinates[len..])?;
+            if E::defining_equation(&x, &y) == FieldElement::zero() {
+                Ok(ShortWeierstrassProjectivePoint::new([
+                    x,
+                    y,
+                    FieldElement::one(),
+                ]))
+            } else {
+                Err(ByteConversionError::InvalidValue)
+            }
+        }
+        _ => Err(ByteConversionError::InvalidValue),
+    }
+}
+
+/// Returns the `y` coordinate of the point with the given `x` and parity of `y`, if there is one.
+pub fn lift_x<E>(x: &FieldElement<E::BaseField>, y_odd: bool) -> Option<FieldElement<E::BaseField>>
+where
+    E: IsShortWeierstrass,
+    E::BaseField: IsPrimeField,
+    FieldElement<E::BaseField>: ByteConversion,
+{
+    let y_squared = x.pow(3_u16) + E::a() * x + E::b();
+    let (y, neg_y) = y_squared.sqrt()?;
+    if is_odd(&coordinate_to_bytes::<E>(&y)) == y_odd {
+        Some(y)
+    } else {
+        Some(neg_y)
+    }
+}
+
+fn coordinate_len<E>() -> usize
+where
+    E: IsShortWeierstrass,
+    E::BaseField: IsPrimeField,
+{
+    E::BaseField::field_bit_size().div_ceil(8)
+}
+
+fn coordinate_to_bytes<E>(coordinate: &FieldElement<E::BaseField>) -> Vec<u8>
+where
+    E: IsShortWeierstrass,
+    E::BaseField: IsPrimeField,
+    FieldElement<E::BaseField>: ByteConversion,
+{
+    let bytes = coordinate.to_bytes_be();
+    bytes[bytes.len() - coordinate_len::<E>()..].to_vec()
+}
+
+/// Parses a big-endian coordinate, rejecting values that are not reduced modulo the field order.
+fn coordinate_from_bytes<E>(bytes: &[u8]) -> Result<FieldElement<E::BaseField>, ByteConversionError>
+where
+    E: IsShortWeierstrass,
+    E::BaseField: IsPrimeField,
+    FieldElement<E::BaseField>: ByteConversion,
+{
+    let coordinate = FieldElement::from_bytes_be(bytes)?;
+    if coordinate_to_bytes::<E>(&coordinate) == bytes {
+        Ok(coordinate)
+    } else {
+        Err(ByteConversionError::InvalidValue)
+    }
+}
+
+fn is_odd(big_endian: &[u8]) -> bool {
+    big_endian.last().is_some_and(|byte| byte & 1 == 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elliptic_curve::{
+        short_weierstrass::curves::{
+            bls12_381::curve::BLS12381Curve,
+            secp256k1::curve::{Secp256k1Curve, SECP256K1_PRIME_FIELD_ORDER},
+            secp256r1::curve::Secp256r1Curve,
+        },
+        traits::IsEllipticCurve,
+    };
+
+    type G = ShortWeierstrassProjectivePoint<Secp256k1Curve>;
+
+    #[test]
+    fn secp256k1_generator_encodings_match_sec_2() {
+        let g = Secp256k1Curve::generator();
+        let x = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+        let y = "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+
+        assert_eq!(hex(&encode_point(&g, true)), format!("02{x}"));
+        assert_eq!(hex(&encode_point(&g, false)), format!("04{x}{y}"));
+    }
+
+    #[test]
+    fn points_are_decoded_from_both_encodings() {
+        let g = Secp256r1Curve::generator();
+        for point in [
+            g.clone(),
+            g.operate_with_self(5_u16),
+            g.operate_with_self(6_u16),
+        ] {
+            for compressed in [true, false] {
+                let bytes = encode_point(&point, compressed);
+                assert_eq!(
+                    decode_point::<Secp256r1Curve>(&bytes).unwrap(),
+                    point,
+                    "compressed: {compressed}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn point_at_infinity_is_a_single_zero_byte() {
+        let bytes = encode_point(&G::neutral_element(), true);
+        assert_eq!(bytes, [0]);
+        assert!(decode_point::<Secp256k1Curve>(&bytes)
+            .unwrap()
+            .is_neutral_element());
+    }
+
+    #[test]
+    fn coordinates_are_as_long_as_the_modulus() {
+        let g = BLS12381Curve::generator();
+        assert_eq!(encode_point(&g, true).len(), 49);
+        assert_eq!(
+            decode_point::<BLS12381Curve>(&encode_point(&g, false)),
+            Ok(g)
+        );
+    }
+
+    #[test]
+    fn invalid_encodings_are_rejected() {
+        let mut bytes = encode_point(&Secp256k1Curve::generator(), false);
+
+        // y does not match x
+        bytes[64] ^= 1;
+        assert_eq!(
+            decode_point::<Secp256k1Curve>(&bytes),
+            Err(ByteConversionError::InvalidValue)
+        );
+
+        // wrong lengths and tags
+        assert!(decode_point::<Secp256k1Curve>(&[]).is_err());
+        assert!(decode_point::<Secp256k1Curve>(&bytes[..33]).is_err());
+        bytes[0] = 0x05;
+        assert!(decode_point::<Secp256k1Curve>(&bytes).is_err());
+
+        // x = p is not a field element
+        let mut not_reduced = vec![0x02];
+        not_reduced.extend(SECP256K1_PRIME_FIELD_ORDER.to_bytes_be());
+        assert_eq!(
+            decode_point::<Secp256k1Curve>(&not_reduced),
+            Err(ByteConversionError::InvalidValue)
+        );
+
+        // x = 5 has no y on secp256k1, since 5^3 + 7 = 132 is not a square
+        let mut no_y = vec![0x02; 33];
+        no_y[1..].copy_from_slice(&[0; 32]);
+        no_y[32] = 5;
+        assert_eq!(
+            decode_point::<Secp256k1Curve>(&no_y),
+            Err(ByteConversionError::InvalidValue)
+        );
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+}
@@ -1,5 +1,7 @@
+use crate::elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint;
 use crate::elliptic_curve::traits::IsEllipticCurve;
 use crate::field::element::FieldElement;
+use crate::unsigned_integer::{element::U256, traits::IsUnsignedInteger};
 use core::fmt::Debug;
 
 /// Trait to add elliptic curves behaviour to a struct.
@@ -17,4 +19,16 @@ pub trait IsShortWeierstrass: IsEllipticCurve + Clone + Debug {
     ) -> FieldElement<Self::BaseField> {
         y.pow(2_u16) - x.pow(3_u16) - Self::a() * x - Self::b()
     }
+
+    /// Returns `(p1, k1)` and `(p2, k2)` such that `k * p` is `k1 * p1 + k2 * p2`, with `k1` and
+    /// `k2` of about half the bits of the order of the group, for
+    /// [`ShortWeierstrassProjectivePoint::glv_mul`]. Returns `None` by default. Curves whose
+    /// points are all in the prime order subgroup override it with
+    /// [`IsGLVCurve::split_multiplication`](crate::elliptic_curve::glv::IsGLVCurve::split_multiplication).
+    fn glv_split<T: IsUnsignedInteger>(
+        _p: &ShortWeierstrassProjectivePoint<Self>,
+        _k: T,
+    ) -> Option<[(ShortWeierstrassProjectivePoint<Self>, U256); 2]> {
+        None
+    }
 }
@@ -0,0 +1,158 @@
+use crate::{cyclic_group::IsGroup, unsigned_integer::element::U256};
+
+/// Width of the windowed non-adjacent form used for scalar multiplication.
+/// Digits are odd and lie in `(-2^(WNAF_WIDTH - 1), 2^(WNAF_WIDTH - 1))`.
+const WNAF_WIDTH: usize = 5;
+
+/// Number of precomputed odd multiples `P, 3P, ..., (2^(WNAF_WIDTH - 1) - 1)P`.
+const TABLE_SIZE: usize = 1 << (WNAF_WIDTH - 2);
+
+/// A 256-bit scalar has at most 257 digits in width-w NAF.
+const MAX_DIGITS: usize = 257;
+
+/// Returns the width-`WNAF_WIDTH` NAF digits of `k`, least significant first,
+/// together with the number of digits used.
+fn wnaf_digits(k: &U256) -> ([i8; MAX_DIGITS], usize) {
+    const WINDOW: u64 = 1 << WNAF_WIDTH;
+    const HALF_WINDOW: u64 = WINDOW >> 1;
+
+    let mut digits = [0i8; MAX_DIGITS];
+    let mut k = *k;
+    let mut len = 0;
+    while k != U256::from_u64(0) {
+        let low = k.limbs[3];
+        if low & 1 == 1 {
+            let residue = low & (WINDOW - 1);
+            if residue < HALF_WINDOW {
+                digits[len] = residue as i8;
+                k = U256::sub(&k, &U256::from_u64(residue)).0;
+            } else {
+                // The digit is negative, so we add its absolute value. This may carry
+                // out of the 256 bits, in which case the carry is shifted back in below.
+                let magnitude = WINDOW - residue;
+                digits[len] = -(magnitude as i8);
+                let (sum, carry) = U256::add(&k, &U256::from_u64(magnitude));
+                k = sum >> 1;
+                if carry {
+                    k.limbs[0] |= 1 << 63;
+                }
+                len += 1;
+                continue;
+            }
+        }
+        k >>= 1;
+        len += 1;
+    }
+    (digits, len)
+}
+
+/// Returns the odd multiples `P, 3P, 5P, ...` of `p`.
+fn odd_multiples<G: IsGroup>(p: &G) -> [G; TABLE_SIZE] {
+    let double = p.operate_with(p);
+    let mut table: [G; TABLE_SIZE] = core::array::from_fn(|_| p.clone());
+    for i in 1..TABLE_SIZE {
+        table[i] = table[i - 1].operate_with(&double);
+    }
+    table
+}
+
+/// Computes `k_1 * p_1 + ... + k_M * p_M` with a single chain of doublings
+/// shared by all the terms, using the windowed NAF of each scalar.
+pub(crate) fn multi_wnaf_mul<G: IsGroup, const M: usize>(terms: [(&G, &U256); M]) -> G {
+    let digits = terms.map(|(_, k)| wnaf_digits(k));
+    let tables = terms.map(|(p, _)| odd_multiples(p));
+    let len = digits.iter().map(|(_, len)| *len).max().unwrap_or(0);
+
+    let mut result = G::neutral_element();
+    for i in (0..len).rev() {
+        result = result.operate_with(&result);
+        for ((digits, _), table) in digits.iter().zip(&tables) {
+            let digit = digits[i];
+            if digit > 0 {
+                result = result.operate_with(&table[(digit >> 1) as usize]);
+            } else if digit < 0 {
+                result = result.operate_with(&table[(-digit >> 1) as usize].neg());
+            }
+        }
+    }
+    result
+}
+
+/// Computes `k * p` using the windowed non-adjacent form of `k`.
+///
+/// This needs noticeably fewer group operations than the double-and-add of
+/// [`IsGroup::operate_with_self`], at the cost of precomputing a few odd multiples of `p`.
+/// It is not constant time and should not be used with secret scalars.
+pub fn wnaf_mul<G: IsGroup>(p: &G, k: &U256) -> G {
+    multi_wnaf_mul([(p, k)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elliptic_curve::{
+        short_weierstrass::curves::bls12_381::curve::BLS12381Curve, traits::IsEllipticCurve,
+    };
+
+    fn recompose(digits: &[i8]) -> (U256, U256) {
+        // Returns the sums of the positive and of the negative parts.
+        let mut positive = U256::from_u64(0);
+        let mut negative = U256::from_u64(0);
+        for (i, digit) in digits.iter().enumerate() {
+            let term = U256::from_u64(digit.unsigned_abs() as u64) << i;
+            if *digit > 0 {
+                positive = positive + term;
+            } else {
+                negative = negative + term;
+            }
+        }
+        (positive, negative)
+    }
+
+    #[test]
+    fn wnaf_digits_are_odd_non_adjacent_and_recompose() {
+        let k = U256::from_hex_unchecked(
+            "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000000",
+        );
+        let (digits, len) = wnaf_digits(&k);
+        for (i, digit) in digits[..len].iter().enumerate() {
+            if *digit != 0 {
+                assert_eq!(digit & 1, 1);
+                assert!(digits[i + 1..len.min(i + WNAF_WIDTH)]
+                    .iter()
+                    .all(|d| *d == 0));
+            }
+        }
+        let (positive, negative) = recompose(&digits[..len]);
+        assert_eq!(positive - negative, k);
+    }
+
+    #[test]
+    fn wnaf_digits_of_the_largest_scalar_use_one_more_digit() {
+        let k = U256::from_hex_unchecked(
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        );
+        let (digits, len) = wnaf_digits(&k);
+        assert_eq!(len, 257);
+        assert_eq!(digits[256], 1);
+        assert_eq!(digits[0], -1);
+    }
+
+    #[test]
+    fn wnaf_mul_matches_operate_with_self() {
+        let g = BLS12381Curve::generator();
+        for k in [
+            U256::from_u64(0),
+            U256::from_u64(1),
+            U256::from_u64(31),
+            U256::from_hex_unchecked(
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            ),
+            U256::from_hex_unchecked(
+                "1cfb69d4ca675f520cce760202687600ff8f87007419047174fd06b52876e7e1",
+            ),
+        ] {
+            assert_eq!(wnaf_mul(&g, &k), g.operate_with_self(k));
+        }
+    }
+}
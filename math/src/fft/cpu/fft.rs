@@ -2,6 +2,8 @@ use crate::field::{
     element::FieldElement,
     traits::{IsFFTField, IsField, IsSubFieldOf},
 };
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 /// In-Place Radix-2 NR DIT FFT algorithm over a slice of two-adic field elements.
 /// It's required that the twiddle factors are in bit-reverse order. Else this function will not
@@ -177,6 +179,279 @@ where
     }
 }
 
+/// In-Place Mixed-Radix NR DIT FFT algorithm over a slice of two-adic field elements.
+/// It's required that the twiddle factors are in bit-reverse order, as for
+/// [in_place_nr_2radix_fft]. Else this function will not return fourier transformed values.
+/// Also the input size needs to be a power of two, but unlike [in_place_nr_4radix_fft] it
+/// doesn't need to be a power of four.
+///
+/// Performs a fast fourier transform with the next attributes:
+/// - In-Place: an auxiliary vector of data isn't needed for the algorithm.
+/// - Mixed-Radix: a single radix-2 stage if the order of the input is odd, followed by radix-4
+///   stages, which need half the passes over the input of the radix-2 algorithm.
+/// - NR: natural to reverse order, meaning that the input is naturally ordered and the output will
+///   be bit-reversed ordered.
+/// - DIT: decimation in time
+///
+/// It supports values in a field E and domain in a subfield F.
+pub fn in_place_nr_mixed_radix_fft<F, E>(
+    input: &mut [FieldElement<E>],
+    twiddles: &[FieldElement<F>],
+) where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+{
+    debug_assert!(input.len().is_power_of_two());
+
+    let mut group_count = 1;
+    if input.len().trailing_zeros() % 2 == 1 {
+        // The only twiddle factor of the first stage is 1.
+        let (x, y) = input.split_at_mut(input.len() / 2);
+        x.iter_mut()
+            .zip(y)
+            .for_each(|(x, y)| radix2_butterfly(x, y));
+        group_count = 2;
+    }
+
+    while group_count < input.len() {
+        let group_size = input.len() / group_count;
+        for (group, chunk) in input.chunks_mut(group_size).enumerate() {
+            let (w1, w2, w3) = (
+                &twiddles[group],
+                &twiddles[2 * group],
+                &twiddles[2 * group + 1],
+            );
+            let [x, y, z, t] = quarters(chunk);
+            for (((x, y), z), t) in x.iter_mut().zip(y).zip(z).zip(t) {
+                radix4_butterfly(x, y, z, t, w1, w2, w3);
+            }
+        }
+        group_count *= 4;
+    }
+}
+
+/// Groups with at least this many radix-4 butterflies are split among threads. Smaller groups
+/// are processed by a single thread, in parallel with the other groups of their stage.
+#[cfg(feature = "parallel")]
+const PARALLEL_GROUP_MIN_BUTTERFLIES: usize = 1 << 10;
+
+/// Parallel version of [in_place_nr_mixed_radix_fft]. The first stages, which have few
+/// groups, split every group among threads; the later ones process the groups in parallel.
+#[cfg(feature = "parallel")]
+pub fn parallel_in_place_nr_mixed_radix_fft<F, E>(
+    input: &mut [FieldElement<E>],
+    twiddles: &[FieldElement<F>],
+) where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+    FieldElement<F>: Send + Sync,
+    FieldElement<E>: Send + Sync,
+{
+    debug_assert!(input.len().is_power_of_two());
+
+    let mut group_count = 1;
+    if input.len().trailing_zeros() % 2 == 1 {
+        let (x, y) = input.split_at_mut(input.len() / 2);
+        x.par_iter_mut()
+            .zip(y)
+            .for_each(|(x, y)| radix2_butterfly(x, y));
+        group_count = 2;
+    }
+
+    while group_count < input.len() {
+        let group_size = input.len() / group_count;
+        input
+            .par_chunks_mut(group_size)
+            .enumerate()
+            .for_each(|(group, chunk)| {
+                let (w1, w2, w3) = (
+                    &twiddles[group],
+                    &twiddles[2 * group],
+                    &twiddles[2 * group + 1],
+                );
+                let [x, y, z, t] = quarters(chunk);
+                if x.len() >= PARALLEL_GROUP_MIN_BUTTERFLIES {
+                    x.par_iter_mut()
+                        .zip(y)
+                        .zip(z)
+                        .zip(t)
+                        .for_each(|(((x, y), z), t)| radix4_butterfly(x, y, z, t, w1, w2, w3));
+                } else {
+                    for (((x, y), z), t) in x.iter_mut().zip(y).zip(z).zip(t) {
+                        radix4_butterfly(x, y, z, t, w1, w2, w3);
+                    }
+                }
+            });
+        group_count *= 4;
+    }
+}
+
+/// Applies [in_place_nr_mixed_radix_fft] to every column of the matrix with rows `rows`, with
+/// butterflies that combine whole rows. The rows are left in bit-reverse order, which is the
+/// layout in which the rows of extended columns are committed to.
+pub fn in_place_nr_mixed_radix_fft_rows<F, E, R>(rows: &mut [R], twiddles: &[FieldElement<F>])
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+    R: AsMut<[FieldElement<E>]>,
+{
+    debug_assert!(rows.len().is_power_of_two());
+
+    let mut group_count = 1;
+    if rows.len().trailing_zeros() % 2 == 1 {
+        let (x, y) = rows.split_at_mut(rows.len() / 2);
+        x.iter_mut()
+            .zip(y)
+            .for_each(|(x, y)| rows_radix2_butterfly(x.as_mut(), y.as_mut()));
+        group_count = 2;
+    }
+
+    while group_count < rows.len() {
+        let group_size = rows.len() / group_count;
+        for (group, chunk) in rows.chunks_mut(group_size).enumerate() {
+            let (w1, w2, w3) = (
+                &twiddles[group],
+                &twiddles[2 * group],
+                &twiddles[2 * group + 1],
+            );
+            let [x, y, z, t] = quarters(chunk);
+            for (((x, y), z), t) in x.iter_mut().zip(y).zip(z).zip(t) {
+                rows_radix4_butterfly([x, y, z, t], w1, w2, w3);
+            }
+        }
+        group_count *= 4;
+    }
+}
+
+/// Parallel version of [in_place_nr_mixed_radix_fft_rows]. As in
+/// [parallel_in_place_nr_mixed_radix_fft], the groups are processed in parallel, and large
+/// groups are also split among threads.
+#[cfg(feature = "parallel")]
+pub fn parallel_in_place_nr_mixed_radix_fft_rows<F, E, R>(
+    rows: &mut [R],
+    twiddles: &[FieldElement<F>],
+) where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+    R: AsMut<[FieldElement<E>]> + Send,
+    FieldElement<F>: Send + Sync,
+    FieldElement<E>: Send + Sync,
+{
+    debug_assert!(rows.len().is_power_of_two());
+    let width = rows.first_mut().map_or(0, |row| row.as_mut().len());
+
+    let mut group_count = 1;
+    if rows.len().trailing_zeros() % 2 == 1 {
+        let (x, y) = rows.split_at_mut(rows.len() / 2);
+        x.par_iter_mut()
+            .zip(y)
+            .for_each(|(x, y)| rows_radix2_butterfly(x.as_mut(), y.as_mut()));
+        group_count = 2;
+    }
+
+    while group_count < rows.len() {
+        let group_size = rows.len() / group_count;
+        rows.par_chunks_mut(group_size)
+            .enumerate()
+            .for_each(|(group, chunk)| {
+                let (w1, w2, w3) = (
+                    &twiddles[group],
+                    &twiddles[2 * group],
+                    &twiddles[2 * group + 1],
+                );
+                let [x, y, z, t] = quarters(chunk);
+                if x.len() * width >= PARALLEL_GROUP_MIN_BUTTERFLIES {
+                    x.par_iter_mut()
+                        .zip(y)
+                        .zip(z)
+                        .zip(t)
+                        .for_each(|(((x, y), z), t)| {
+                            rows_radix4_butterfly([x, y, z, t], w1, w2, w3)
+                        });
+                } else {
+                    for (((x, y), z), t) in x.iter_mut().zip(y).zip(z).zip(t) {
+                        rows_radix4_butterfly([x, y, z, t], w1, w2, w3);
+                    }
+                }
+            });
+        group_count *= 4;
+    }
+}
+
+/// Applies [radix2_butterfly] to the elements of the rows `x` and `y` in the same column.
+fn rows_radix2_butterfly<E: IsField>(x: &mut [FieldElement<E>], y: &mut [FieldElement<E>]) {
+    x.iter_mut()
+        .zip(y)
+        .for_each(|(x, y)| radix2_butterfly(x, y));
+}
+
+/// Applies [radix4_butterfly] to the elements of four rows in the same column.
+fn rows_radix4_butterfly<F, E, R>(
+    [x, y, z, t]: [&mut R; 4],
+    w1: &FieldElement<F>,
+    w2: &FieldElement<F>,
+    w3: &FieldElement<F>,
+) where
+    F: IsField + IsSubFieldOf<E>,
+    E: IsField,
+    R: AsMut<[FieldElement<E>]>,
+{
+    for (((x, y), z), t) in x
+        .as_mut()
+        .iter_mut()
+        .zip(y.as_mut())
+        .zip(z.as_mut())
+        .zip(t.as_mut())
+    {
+        radix4_butterfly(x, y, z, t, w1, w2, w3);
+    }
+}
+
+/// Splits a group in its four quarters.
+fn quarters<T>(group: &mut [T]) -> [&mut [T]; 4] {
+    let quarter = group.len() / 4;
+    let (x, rest) = group.split_at_mut(quarter);
+    let (y, rest) = rest.split_at_mut(quarter);
+    let (z, t) = rest.split_at_mut(quarter);
+    [x, y, z, t]
+}
+
+/// Radix-2 butterfly with twiddle factor 1: (x, y) = (x + y, x - y).
+#[inline(always)]
+fn radix2_butterfly<E: IsField>(x: &mut FieldElement<E>, y: &mut FieldElement<E>) {
+    let sum = &*x + &*y;
+    *y = &*x - &*y;
+    *x = sum;
+}
+
+/// Radix-4 butterfly, the composition of two radix-2 stages, as in [in_place_nr_4radix_fft].
+#[inline(always)]
+fn radix4_butterfly<F, E>(
+    x: &mut FieldElement<E>,
+    y: &mut FieldElement<E>,
+    z: &mut FieldElement<E>,
+    t: &mut FieldElement<E>,
+    w1: &FieldElement<F>,
+    w2: &FieldElement<F>,
+    w3: &FieldElement<F>,
+) where
+    F: IsField + IsSubFieldOf<E>,
+    E: IsField,
+{
+    let zw1 = w1 * &*z;
+    let tw1 = w1 * &*t;
+    let a = w2 * (&*y + &tw1);
+    let b = w3 * (&*y - &tw1);
+
+    let x_plus_zw1 = &*x + &zw1;
+    let x_minus_zw1 = &*x - &zw1;
+
+    *x = &x_plus_zw1 + &a;
+    *y = x_plus_zw1 - a;
+    *z = &x_minus_zw1 + &b;
+    *t = x_minus_zw1 - b;
+}
+
 #[cfg(all(test, feature = "alloc"))]
 mod tests {
     use crate::fft::cpu::bit_reversing::in_place_bit_reverse_permute;
@@ -257,5 +532,77 @@ mod tests {
 
             prop_assert_eq!(expected, result);
         }
+
+        // Property-based test that ensures NR mixed-radix FFT gives the same result as a naive DFT,
+        // for input sizes which are odd and even powers of two.
+        #[test]
+        fn test_nr_mixed_radix_fft_matches_naive_eval(coeffs in field_vec(9)) {
+            let expected = naive_matrix_dft_test(&coeffs);
+
+            let order = coeffs.len().trailing_zeros();
+            let twiddles = get_twiddles(order.into(), RootsConfig::BitReverse).unwrap();
+
+            let mut result = coeffs;
+            in_place_nr_mixed_radix_fft::<F, F>(&mut result, &twiddles);
+            in_place_bit_reverse_permute(&mut result);
+
+            prop_assert_eq!(expected, result);
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_nr_mixed_radix_fft_matches_sequential() {
+        for order in [11u64, 12] {
+            let input: alloc::vec::Vec<FE> =
+                (0..1u64 << order).map(|i| FE::from(i * i + 7)).collect();
+            let twiddles = get_twiddles(order, RootsConfig::BitReverse).unwrap();
+
+            let mut expected = input.clone();
+            in_place_nr_2radix_fft::<F, F>(&mut expected, &twiddles);
+            let mut result = input;
+            parallel_in_place_nr_mixed_radix_fft::<F, F>(&mut result, &twiddles);
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    /// Rows of the matrix with `width` columns of length `2^order`.
+    fn rows(order: u64, width: u64) -> alloc::vec::Vec<alloc::vec::Vec<FE>> {
+        (0..1u64 << order)
+            .map(|i| (0..width).map(|j| FE::from(i * i + 3 * j + 1)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn nr_mixed_radix_fft_rows_transforms_every_column() {
+        for order in [3u64, 4] {
+            let twiddles = get_twiddles(order, RootsConfig::BitReverse).unwrap();
+            let mut result = rows(order, 3);
+            let mut columns: alloc::vec::Vec<alloc::vec::Vec<FE>> = (0..3)
+                .map(|j| result.iter().map(|row| row[j].clone()).collect())
+                .collect();
+            in_place_nr_mixed_radix_fft_rows::<F, F, _>(&mut result, &twiddles);
+            for (j, column) in columns.iter_mut().enumerate() {
+                in_place_nr_mixed_radix_fft::<F, F>(column, &twiddles);
+                let result_column: alloc::vec::Vec<FE> =
+                    result.iter().map(|row| row[j].clone()).collect();
+                assert_eq!(&result_column, column);
+            }
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_nr_mixed_radix_fft_rows_matches_sequential() {
+        for order in [9u64, 10] {
+            let twiddles = get_twiddles(order, RootsConfig::BitReverse).unwrap();
+            let mut expected = rows(order, 4);
+            let mut result = expected.clone();
+            in_place_nr_mixed_radix_fft_rows::<F, F, _>(&mut expected, &twiddles);
+            parallel_in_place_nr_mixed_radix_fft_rows::<F, F, _>(&mut result, &twiddles);
+
+            assert_eq!(result, expected);
+        }
     }
 }
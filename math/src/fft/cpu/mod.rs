@@ -4,3 +4,5 @@ pub mod fft;
 pub mod ops;
 #[cfg(feature = "alloc")]
 pub mod roots_of_unity;
+#[cfg(feature = "alloc")]
+pub mod six_step;
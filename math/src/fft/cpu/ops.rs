@@ -6,7 +6,9 @@ use crate::{
     },
 };
 
-use super::{bit_reversing::in_place_bit_reverse_permute, fft::in_place_nr_2radix_fft};
+#[cfg(feature = "parallel")]
+use super::fft::parallel_in_place_nr_mixed_radix_fft;
+use super::{bit_reversing::in_place_bit_reverse_permute, fft::in_place_nr_mixed_radix_fft};
 
 /// Executes Fast Fourier Transform over elements of a two-adic finite field `E` and domain in a
 /// subfield `F`. Usually used for fast polynomial evaluation.
@@ -19,7 +21,28 @@ pub fn fft<F: IsFFTField + IsSubFieldOf<E>, E: IsField>(
     }
 
     let mut results = input.to_vec();
-    in_place_nr_2radix_fft(&mut results, twiddles);
+    in_place_nr_mixed_radix_fft(&mut results, twiddles);
+    in_place_bit_reverse_permute(&mut results);
+
+    Ok(results)
+}
+
+/// Parallel version of [fft], which splits the FFT among threads.
+#[cfg(feature = "parallel")]
+pub fn parallel_fft<F: IsFFTField + IsSubFieldOf<E>, E: IsField>(
+    input: &[FieldElement<E>],
+    twiddles: &[FieldElement<F>],
+) -> Result<alloc::vec::Vec<FieldElement<E>>, FFTError>
+where
+    FieldElement<F>: Send + Sync,
+    FieldElement<E>: Send + Sync,
+{
+    if !input.len().is_power_of_two() {
+        return Err(FFTError::InputError(input.len()));
+    }
+
+    let mut results = input.to_vec();
+    parallel_in_place_nr_mixed_radix_fft(&mut results, twiddles);
     in_place_bit_reverse_permute(&mut results);
 
     Ok(results)
@@ -0,0 +1,287 @@
+use crate::{
+    fft::errors::FFTError,
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField, IsSubFieldOf},
+    },
+};
+use alloc::{vec, vec::Vec};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use super::{bit_reversing::in_place_bit_reverse_permute, fft::in_place_nr_mixed_radix_fft};
+
+/// Inputs of at least `2^SIX_STEP_MIN_ORDER` elements no longer fit in cache, so that the
+/// six-step FFT outperforms the in-place ones. Below it, the cost of the transposes dominates.
+pub const SIX_STEP_MIN_ORDER: u32 = 22;
+
+/// Side of the square blocks in which matrices are transposed, so that both the rows read and
+/// the rows written by a block stay in cache.
+const TRANSPOSE_BLOCK_SIZE: usize = 16;
+
+/// Executes the six-step (Bailey) Fast Fourier Transform over elements of a two-adic finite field
+/// `E` and domain in a subfield `F`. `root` must be a primitive `n`-th root of unity, where `n`
+/// is the length of the input, and the result is `[P(root^0), P(root^1), ...]` for `P` the
+/// polynomial with coefficients `input`, in natural order. Use the inverse of the root for
+/// interpolation.
+///
+/// The input of length `n = rows * cols` is seen as a `rows x cols` matrix, so that the
+/// transform becomes `cols` FFTs of length `rows` and `rows` FFTs of length `cols`:
+/// 1. Transpose the matrix, so that its columns become contiguous.
+/// 2. Transform every row of length `rows`.
+/// 3. Multiply the element `k` of the row `j` by `root^(j * k)`.
+/// 4. Transpose the matrix.
+/// 5. Transform every row of length `cols`.
+/// 6. Transpose the matrix, which leaves the evaluations in natural order.
+///
+/// Every small FFT fits in cache, which makes this faster than the in-place algorithms for
+/// inputs of at least `2^SIX_STEP_MIN_ORDER` elements. The matrix is transposed between two
+/// buffers, so that only one extra copy of the input is allocated.
+pub fn six_step_fft<F, E>(
+    input: &[FieldElement<E>],
+    root: &FieldElement<F>,
+) -> Result<Vec<FieldElement<E>>, FFTError>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+{
+    if !input.len().is_power_of_two() {
+        return Err(FFTError::InputError(input.len()));
+    }
+    let (rows, cols) = matrix_dimensions(input.len());
+
+    let mut matrix = vec![FieldElement::zero(); input.len()];
+    transpose_into(input, rows, cols, &mut matrix);
+    let row_twiddles = bit_reversed_twiddles(&root.pow(cols), rows);
+    matrix
+        .chunks_mut(rows)
+        .enumerate()
+        .for_each(|(j, row)| row_fft_and_twiddle(row, &row_twiddles, &root.pow(j)));
+
+    let mut scratch = vec![FieldElement::zero(); input.len()];
+    transpose_into(&matrix, cols, rows, &mut scratch);
+    let col_twiddles = bit_reversed_twiddles(&root.pow(rows), cols);
+    scratch
+        .chunks_mut(cols)
+        .for_each(|row| row_fft(row, &col_twiddles));
+
+    transpose_into(&scratch, rows, cols, &mut matrix);
+    Ok(matrix)
+}
+
+/// Parallel version of [six_step_fft], which transforms and transposes the rows of the matrix in
+/// parallel.
+#[cfg(feature = "parallel")]
+pub fn parallel_six_step_fft<F, E>(
+    input: &[FieldElement<E>],
+    root: &FieldElement<F>,
+) -> Result<Vec<FieldElement<E>>, FFTError>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+    FieldElement<F>: Send + Sync,
+    FieldElement<E>: Send + Sync,
+{
+    if !input.len().is_power_of_two() {
+        return Err(FFTError::InputError(input.len()));
+    }
+    let (rows, cols) = matrix_dimensions(input.len());
+
+    let mut matrix = vec![FieldElement::zero(); input.len()];
+    parallel_transpose_into(input, rows, cols, &mut matrix);
+    let row_twiddles = bit_reversed_twiddles(&root.pow(cols), rows);
+    matrix
+        .par_chunks_mut(rows)
+        .enumerate()
+        .for_each(|(j, row)| row_fft_and_twiddle(row, &row_twiddles, &root.pow(j)));
+
+    let mut scratch = vec![FieldElement::zero(); input.len()];
+    parallel_transpose_into(&matrix, cols, rows, &mut scratch);
+    let col_twiddles = bit_reversed_twiddles(&root.pow(rows), cols);
+    scratch
+        .par_chunks_mut(cols)
+        .for_each(|row| row_fft(row, &col_twiddles));
+
+    parallel_transpose_into(&scratch, rows, cols, &mut matrix);
+    Ok(matrix)
+}
+
+/// Returns `(rows, cols)`, with `rows * cols = n` and `rows <= cols <= 2 * rows`.
+fn matrix_dimensions(n: usize) -> (usize, usize) {
+    let rows = 1 << (n.trailing_zeros() / 2);
+    (rows, n / rows)
+}
+
+/// Returns the powers of `root`, a primitive `n`-th root of unity, needed by
+/// [in_place_nr_mixed_radix_fft] for an input of length `n`, in bit-reverse order.
+fn bit_reversed_twiddles<F: IsField>(root: &FieldElement<F>, n: usize) -> Vec<FieldElement<F>> {
+    let mut twiddles: Vec<_> =
+        core::iter::successors(Some(FieldElement::one()), |w| Some(w * root))
+            .take(n / 2)
+            .collect();
+    in_place_bit_reverse_permute(&mut twiddles);
+    twiddles
+}
+
+/// Transforms `row` in place, leaving the result in natural order.
+fn row_fft<F, E>(row: &mut [FieldElement<E>], twiddles: &[FieldElement<F>])
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+{
+    in_place_nr_mixed_radix_fft(row, twiddles);
+    in_place_bit_reverse_permute(row);
+}
+
+/// Transforms `row` in place and multiplies its element `k` by `w^k`.
+fn row_fft_and_twiddle<F, E>(
+    row: &mut [FieldElement<E>],
+    twiddles: &[FieldElement<F>],
+    w: &FieldElement<F>,
+) where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+{
+    row_fft(row, twiddles);
+    let mut w_k = w.clone();
+    for value in row.iter_mut().skip(1) {
+        *value = &w_k * &*value;
+        w_k = &w_k * w;
+    }
+}
+
+/// Writes to `transposed` the transpose of the `rows x cols` matrix stored by rows in `matrix`.
+fn transpose_into<E: IsField>(
+    matrix: &[FieldElement<E>],
+    rows: usize,
+    cols: usize,
+    transposed: &mut [FieldElement<E>],
+) {
+    transposed
+        .chunks_mut(rows * TRANSPOSE_BLOCK_SIZE)
+        .enumerate()
+        .for_each(|(block, out)| transpose_block_rows(matrix, rows, cols, block, out));
+}
+
+/// Parallel version of [transpose_into].
+#[cfg(feature = "parallel")]
+fn parallel_transpose_into<E: IsField>(
+    matrix: &[FieldElement<E>],
+    rows: usize,
+    cols: usize,
+    transposed: &mut [FieldElement<E>],
+) where
+    FieldElement<E>: Send + Sync,
+{
+    transposed
+        .par_chunks_mut(rows * TRANSPOSE_BLOCK_SIZE)
+        .enumerate()
+        .for_each(|(block, out)| transpose_block_rows(matrix, rows, cols, block, out));
+}
+
+/// Writes to `out` the rows of the transpose of `matrix` from `block * TRANSPOSE_BLOCK_SIZE` on,
+/// that is, the corresponding columns of `matrix`, walking `matrix` in square blocks.
+fn transpose_block_rows<E: IsField>(
+    matrix: &[FieldElement<E>],
+    rows: usize,
+    cols: usize,
+    block: usize,
+    out: &mut [FieldElement<E>],
+) {
+    let first_col = block * TRANSPOSE_BLOCK_SIZE;
+    let block_cols = out.len() / rows;
+    for first_row in (0..rows).step_by(TRANSPOSE_BLOCK_SIZE) {
+        let last_row = (first_row + TRANSPOSE_BLOCK_SIZE).min(rows);
+        for c in 0..block_cols {
+            for r in first_row..last_row {
+                out[c * rows + r] = matrix[r * cols + first_col + c].clone();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fft::test_helpers::naive_matrix_dft_test;
+    use crate::field::{
+        fields::u64_goldilocks_field::{Goldilocks64ExtensionField, Goldilocks64Field},
+        test_fields::u64_test_field::U64TestField,
+    };
+    use proptest::{collection, prelude::*};
+
+    type F = U64TestField;
+    type FE = FieldElement<F>;
+
+    prop_compose! {
+        fn field_vec(max_exp: u8)(vec in (0..max_exp).prop_flat_map(|i| collection::vec(any::<u64>().prop_map(FE::from), 1 << i))) -> Vec<FE> {
+            vec
+        }
+    }
+
+    proptest! {
+        // Property-based test that ensures the six-step FFT gives the same result as a naive DFT.
+        #[test]
+        fn test_six_step_fft_matches_naive_eval(coeffs in field_vec(10)) {
+            let expected = naive_matrix_dft_test(&coeffs);
+            let root = F::get_primitive_root_of_unity(coeffs.len().trailing_zeros().into()).unwrap();
+
+            prop_assert_eq!(six_step_fft(&coeffs, &root).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn six_step_fft_with_inverse_root_interpolates() {
+        let coeffs: Vec<FE> = (0..1u64 << 9).map(|i| FE::from(3 * i + 1)).collect();
+        let root = F::get_primitive_root_of_unity(9).unwrap();
+
+        let evals = six_step_fft(&coeffs, &root).unwrap();
+        let scaled_coeffs = six_step_fft(&evals, &root.inv().unwrap()).unwrap();
+        let n_inv = FE::from(coeffs.len() as u64).inv().unwrap();
+
+        let result: Vec<FE> = scaled_coeffs.iter().map(|c| c * &n_inv).collect();
+        assert_eq!(result, coeffs);
+    }
+
+    #[test]
+    fn six_step_fft_with_values_in_field_extension() {
+        type E = Goldilocks64ExtensionField;
+        let coeffs: Vec<FieldElement<E>> = (0..1u64 << 7)
+            .map(|i| FieldElement::new([FieldElement::from(i), FieldElement::from(i * i + 5)]))
+            .collect();
+        let root = Goldilocks64Field::get_primitive_root_of_unity(7).unwrap();
+        let expected: Vec<FieldElement<E>> = (0..coeffs.len() as u64)
+            .map(|k| {
+                coeffs
+                    .iter()
+                    .rev()
+                    .fold(FieldElement::zero(), |acc, c| root.pow(k) * acc + c)
+            })
+            .collect();
+
+        assert_eq!(six_step_fft(&coeffs, &root).unwrap(), expected);
+    }
+
+    #[test]
+    fn six_step_fft_rejects_non_power_of_two_inputs() {
+        let root = F::get_primitive_root_of_unity(2).unwrap();
+        assert!(matches!(
+            six_step_fft(&vec![FE::one(); 3], &root),
+            Err(FFTError::InputError(3))
+        ));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_six_step_fft_matches_sequential() {
+        for order in [10u64, 11] {
+            let coeffs: Vec<FE> = (0..1u64 << order).map(|i| FE::from(i * i + 7)).collect();
+            let root = F::get_primitive_root_of_unity(order).unwrap();
+
+            assert_eq!(
+                parallel_six_step_fft(&coeffs, &root).unwrap(),
+                six_step_fft(&coeffs, &root).unwrap()
+            );
+        }
+    }
+}
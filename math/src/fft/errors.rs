@@ -13,6 +13,8 @@ pub enum FFTError {
     RootOfUnityError(u64),
     InputError(usize),
     OrderError(u64),
+    /// A polynomial was divided by zero, or inverted with a zero constant coefficient.
+    DivisionByZero,
     #[cfg(feature = "metal")]
     MetalError(MetalError),
     #[cfg(feature = "cuda")]
@@ -29,6 +31,7 @@ impl Display for FFTError {
             FFTError::OrderError(v) => {
                 write!(f, "Order should be less than or equal to 63, but is {v}")
             }
+            FFTError::DivisionByZero => write!(f, "Can't divide a polynomial by zero"),
             #[cfg(feature = "metal")]
             FFTError::MetalError(_) => {
                 write!(f, "A Metal related error has ocurred")
@@ -0,0 +1,541 @@
+#[cfg(feature = "parallel")]
+use crate::fft::cpu::fft::parallel_in_place_nr_mixed_radix_fft_rows;
+use crate::{
+    fft::{
+        cpu::{
+            bit_reversing::{in_place_bit_reverse_permute, reverse_index},
+            fft::{in_place_nr_mixed_radix_fft, in_place_nr_mixed_radix_fft_rows},
+            roots_of_unity::get_twiddles,
+        },
+        errors::FFTError,
+    },
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField, IsSubFieldOf, RootsConfig},
+    },
+    polynomial::Polynomial,
+};
+use alloc::{vec, vec::Vec};
+
+/// Low degree extension of columns of `trace_length` values over the coset
+/// `offset * <w>`, where `w` is a primitive root of unity of order
+/// `trace_length * blowup_factor`. The twiddles and the powers of the offset are computed once,
+/// when the `CosetLDE` is built, and shared by all the columns extended with it.
+///
+/// Columns are interpolated over the subgroup of order `trace_length`, and the resulting
+/// polynomials are evaluated over the coset. Values may lie in any extension `E` of the field
+/// `F` of the domain.
+pub struct CosetLDE<F: IsFFTField> {
+    trace_length: usize,
+    blowup_factor: usize,
+    /// Twiddles of the inverse FFT of length `trace_length`, in bit-reverse order.
+    inverse_twiddles: Vec<FieldElement<F>>,
+    /// Twiddles of the FFT of length `trace_length * blowup_factor`, in bit-reverse order.
+    lde_twiddles: Vec<FieldElement<F>>,
+    /// `offset^i` for `i < trace_length * blowup_factor`.
+    coset_powers: Vec<FieldElement<F>>,
+    /// `1 / trace_length`.
+    trace_length_inv: FieldElement<F>,
+    /// `offset^i / trace_length` for `i < trace_length`, which scales the coefficients given by
+    /// the inverse FFT and moves them to the coset at once.
+    scaled_coset_powers: Vec<FieldElement<F>>,
+    /// `offset^(trace_length * blowup_factor)`.
+    offset_to_lde_size: FieldElement<F>,
+}
+
+impl<F: IsFFTField> CosetLDE<F> {
+    /// Precomputes the twiddles and the powers of `offset` needed to extend columns of
+    /// `trace_length` values to `trace_length * blowup_factor` evaluations over the coset
+    /// `offset * <w>`. Both `trace_length` and `blowup_factor` must be powers of two.
+    pub fn new(
+        trace_length: usize,
+        blowup_factor: usize,
+        offset: &FieldElement<F>,
+    ) -> Result<Self, FFTError> {
+        if !trace_length.is_power_of_two() {
+            return Err(FFTError::InputError(trace_length));
+        }
+        if !blowup_factor.is_power_of_two() {
+            return Err(FFTError::InputError(blowup_factor));
+        }
+        let lde_size = trace_length * blowup_factor;
+
+        let inverse_twiddles = get_twiddles(
+            trace_length.trailing_zeros().into(),
+            RootsConfig::BitReverseInversed,
+        )?;
+        let lde_twiddles = get_twiddles(lde_size.trailing_zeros().into(), RootsConfig::BitReverse)?;
+
+        let mut coset_powers = Vec::with_capacity(lde_size);
+        let mut offset_power = FieldElement::one();
+        for _ in 0..lde_size {
+            coset_powers.push(offset_power.clone());
+            offset_power = &offset_power * offset;
+        }
+        let trace_length_inv = FieldElement::<F>::from(trace_length as u64).inv()?;
+        let scaled_coset_powers = coset_powers[..trace_length]
+            .iter()
+            .map(|power| power * &trace_length_inv)
+            .collect();
+
+        Ok(Self {
+            trace_length,
+            blowup_factor,
+            inverse_twiddles,
+            lde_twiddles,
+            coset_powers,
+            scaled_coset_powers,
+            trace_length_inv,
+            offset_to_lde_size: offset_power,
+        })
+    }
+
+    pub fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    pub fn blowup_factor(&self) -> usize {
+        self.blowup_factor
+    }
+
+    /// Returns the number of evaluations of every extended column.
+    pub fn lde_size(&self) -> usize {
+        self.trace_length * self.blowup_factor
+    }
+
+    /// Returns the polynomial that interpolates `column` over the subgroup of order
+    /// `trace_length`, as [Polynomial::interpolate_fft] does.
+    pub fn interpolate<E>(
+        &self,
+        column: &[FieldElement<E>],
+    ) -> Result<Polynomial<FieldElement<E>>, FFTError>
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+    {
+        self.check_column(column)?;
+        let mut coefficients = column.to_vec();
+        in_place_nr_mixed_radix_fft(&mut coefficients, &self.inverse_twiddles);
+        in_place_bit_reverse_permute(&mut coefficients);
+
+        coefficients
+            .iter_mut()
+            .for_each(|c| *c = &self.trace_length_inv * &*c);
+        Ok(Polynomial::new(&coefficients))
+    }
+
+    /// Returns the evaluations of `poly` over the coset, in natural order. Polynomials with more
+    /// than `lde_size` coefficients are first reduced modulo `X^lde_size - offset^lde_size`.
+    pub fn evaluate<E>(&self, poly: &Polynomial<FieldElement<E>>) -> Vec<FieldElement<E>>
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+    {
+        let mut evaluations = vec![FieldElement::zero(); self.lde_size()];
+        let mut block_factor = FieldElement::<F>::one();
+        for block in poly.coefficients().chunks(self.lde_size()) {
+            for ((evaluation, coefficient), power) in
+                evaluations.iter_mut().zip(block).zip(&self.coset_powers)
+            {
+                *evaluation += &(power * &block_factor) * coefficient;
+            }
+            block_factor = &block_factor * &self.offset_to_lde_size;
+        }
+        self.coset_fft(&mut evaluations);
+        evaluations
+    }
+
+    /// Returns the evaluations over the coset, in natural order, of the polynomial interpolating
+    /// `column`. The column is interpolated and evaluated in a single buffer, without building
+    /// the polynomial.
+    pub fn extend<E>(&self, column: &[FieldElement<E>]) -> Result<Vec<FieldElement<E>>, FFTError>
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+    {
+        self.check_column(column)?;
+        let mut evaluations = vec![FieldElement::zero(); self.lde_size()];
+        let coefficients = &mut evaluations[..self.trace_length];
+        coefficients.clone_from_slice(column);
+        in_place_nr_mixed_radix_fft(coefficients, &self.inverse_twiddles);
+        in_place_bit_reverse_permute(coefficients);
+        coefficients
+            .iter_mut()
+            .zip(&self.scaled_coset_powers)
+            .for_each(|(c, power)| *c = power * &*c);
+
+        self.coset_fft(&mut evaluations);
+        Ok(evaluations)
+    }
+
+    /// Interpolates every column of `columns`, as [Self::interpolate] does. The columns are
+    /// transformed together, with the butterflies of the FFT applied to the rows they form.
+    pub fn interpolate_columns<E>(
+        &self,
+        columns: &[Vec<FieldElement<E>>],
+    ) -> Result<Vec<Polynomial<FieldElement<E>>>, FFTError>
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+    {
+        self.interpolate_rows(columns, in_place_nr_mixed_radix_fft_rows)
+    }
+
+    /// Evaluates every polynomial of `polys` over the coset, as [Self::evaluate] does. The
+    /// polynomials are transformed together, and their evaluations are left in the rows in
+    /// bit-reverse order given by the FFT.
+    pub fn evaluate_polynomials<E>(
+        &self,
+        polys: &[Polynomial<FieldElement<E>>],
+    ) -> CosetEvaluations<E>
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+    {
+        self.evaluate_rows(polys, in_place_nr_mixed_radix_fft_rows)
+    }
+
+    /// Extends every column of `columns`, as [Self::extend] does. The columns are transformed
+    /// together, and their evaluations are left in the rows in bit-reverse order given by the
+    /// FFT.
+    pub fn extend_columns<E>(
+        &self,
+        columns: &[Vec<FieldElement<E>>],
+    ) -> Result<CosetEvaluations<E>, FFTError>
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+    {
+        self.extend_rows(columns, in_place_nr_mixed_radix_fft_rows)
+    }
+
+    /// Parallel version of [Self::interpolate_columns], which splits the FFT among threads.
+    #[cfg(feature = "parallel")]
+    pub fn parallel_interpolate_columns<E>(
+        &self,
+        columns: &[Vec<FieldElement<E>>],
+    ) -> Result<Vec<Polynomial<FieldElement<E>>>, FFTError>
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+        FieldElement<F>: Send + Sync,
+        FieldElement<E>: Send + Sync,
+    {
+        self.interpolate_rows(columns, parallel_in_place_nr_mixed_radix_fft_rows)
+    }
+
+    /// Parallel version of [Self::evaluate_polynomials], which splits the FFT among threads.
+    #[cfg(feature = "parallel")]
+    pub fn parallel_evaluate_polynomials<E>(
+        &self,
+        polys: &[Polynomial<FieldElement<E>>],
+    ) -> CosetEvaluations<E>
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+        FieldElement<F>: Send + Sync,
+        FieldElement<E>: Send + Sync,
+    {
+        self.evaluate_rows(polys, parallel_in_place_nr_mixed_radix_fft_rows)
+    }
+
+    /// Parallel version of [Self::extend_columns], which splits the FFTs among threads.
+    #[cfg(feature = "parallel")]
+    pub fn parallel_extend_columns<E>(
+        &self,
+        columns: &[Vec<FieldElement<E>>],
+    ) -> Result<CosetEvaluations<E>, FFTError>
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+        FieldElement<F>: Send + Sync,
+        FieldElement<E>: Send + Sync,
+    {
+        self.extend_rows(columns, parallel_in_place_nr_mixed_radix_fft_rows)
+    }
+
+    /// Interpolates the columns with the given FFT over the rows they form.
+    fn interpolate_rows<E>(
+        &self,
+        columns: &[Vec<FieldElement<E>>],
+        fft_rows: impl Fn(&mut [Vec<FieldElement<E>>], &[FieldElement<F>]),
+    ) -> Result<Vec<Polynomial<FieldElement<E>>>, FFTError>
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+    {
+        let mut rows = self.rows_of(columns, self.trace_length)?;
+        fft_rows(&mut rows, &self.inverse_twiddles);
+        in_place_bit_reverse_permute(&mut rows);
+
+        Ok((0..columns.len())
+            .map(|j| {
+                let coefficients: Vec<_> = rows
+                    .iter()
+                    .map(|row| &self.trace_length_inv * &row[j])
+                    .collect();
+                Polynomial::new(&coefficients)
+            })
+            .collect())
+    }
+
+    /// Evaluates the polynomials with the given FFT over the rows of their coefficients.
+    fn evaluate_rows<E>(
+        &self,
+        polys: &[Polynomial<FieldElement<E>>],
+        fft_rows: impl Fn(&mut [Vec<FieldElement<E>>], &[FieldElement<F>]),
+    ) -> CosetEvaluations<E>
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+    {
+        let mut rows = vec![vec![FieldElement::zero(); polys.len()]; self.lde_size()];
+        for (j, poly) in polys.iter().enumerate() {
+            let mut block_factor = FieldElement::<F>::one();
+            for block in poly.coefficients().chunks(self.lde_size()) {
+                for ((row, coefficient), power) in
+                    rows.iter_mut().zip(block).zip(&self.coset_powers)
+                {
+                    row[j] += &(power * &block_factor) * coefficient;
+                }
+                block_factor = &block_factor * &self.offset_to_lde_size;
+            }
+        }
+        fft_rows(&mut rows, &self.lde_twiddles);
+        CosetEvaluations {
+            bit_reversed_rows: rows,
+        }
+    }
+
+    /// Extends the columns with the given FFT over the rows they form, interpolating and
+    /// evaluating them in the same rows.
+    fn extend_rows<E>(
+        &self,
+        columns: &[Vec<FieldElement<E>>],
+        fft_rows: impl Fn(&mut [Vec<FieldElement<E>>], &[FieldElement<F>]),
+    ) -> Result<CosetEvaluations<E>, FFTError>
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+    {
+        let mut rows = self.rows_of(columns, self.lde_size())?;
+        let coefficients = &mut rows[..self.trace_length];
+        fft_rows(coefficients, &self.inverse_twiddles);
+        in_place_bit_reverse_permute(coefficients);
+        for (row, power) in coefficients.iter_mut().zip(&self.scaled_coset_powers) {
+            row.iter_mut().for_each(|c| *c = power * &*c);
+        }
+
+        fft_rows(&mut rows, &self.lde_twiddles);
+        Ok(CosetEvaluations {
+            bit_reversed_rows: rows,
+        })
+    }
+
+    /// Returns the rows of the matrix with columns `columns`, followed by zero rows up to
+    /// `num_rows` rows.
+    fn rows_of<E: IsField>(
+        &self,
+        columns: &[Vec<FieldElement<E>>],
+        num_rows: usize,
+    ) -> Result<Vec<Vec<FieldElement<E>>>, FFTError> {
+        columns
+            .iter()
+            .try_for_each(|column| self.check_column(column))?;
+        let rows = (0..num_rows)
+            .map(|i| match i < self.trace_length {
+                true => columns.iter().map(|column| column[i].clone()).collect(),
+                false => vec![FieldElement::zero(); columns.len()],
+            })
+            .collect();
+        Ok(rows)
+    }
+
+    fn check_column<E: IsField>(&self, column: &[FieldElement<E>]) -> Result<(), FFTError> {
+        if column.len() != self.trace_length {
+            return Err(FFTError::InputError(column.len()));
+        }
+        Ok(())
+    }
+
+    /// Transforms the coefficients of a polynomial already multiplied by the powers of the
+    /// offset into its evaluations over the coset, in natural order.
+    fn coset_fft<E>(&self, coefficients: &mut [FieldElement<E>])
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+    {
+        in_place_nr_mixed_radix_fft(coefficients, &self.lde_twiddles);
+        in_place_bit_reverse_permute(coefficients);
+    }
+}
+
+/// Evaluations over a coset of a batch of columns, as the rows of the matrix they form taken in
+/// bit-reverse order. This is the order in which the FFT leaves them, and the layout in which
+/// extended columns are committed to with a Merkle tree, so that the two evaluations `P(x)` and
+/// `P(-x)` opened together are in consecutive leaves.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CosetEvaluations<E: IsField> {
+    bit_reversed_rows: Vec<Vec<FieldElement<E>>>,
+}
+
+impl<E: IsField> CosetEvaluations<E> {
+    /// Returns the rows, in bit-reverse order.
+    pub fn bit_reversed_rows(&self) -> &[Vec<FieldElement<E>>] {
+        &self.bit_reversed_rows
+    }
+
+    /// Returns the evaluations of every column, in natural order.
+    pub fn columns(&self) -> Vec<Vec<FieldElement<E>>> {
+        let num_rows = self.bit_reversed_rows.len();
+        let num_columns = self.bit_reversed_rows.first().map_or(0, Vec::len);
+        let mut columns = vec![Vec::with_capacity(num_rows); num_columns];
+        for i in 0..num_rows {
+            let row = &self.bit_reversed_rows[reverse_index(i, num_rows as u64)];
+            for (column, value) in columns.iter_mut().zip(row) {
+                column.push(value.clone());
+            }
+        }
+        columns
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::{
+        fields::u64_goldilocks_field::{Goldilocks64ExtensionField, Goldilocks64Field},
+        test_fields::u64_test_field::U64TestField,
+    };
+    use proptest::{collection, prelude::*};
+
+    type F = U64TestField;
+    type FE = FieldElement<F>;
+
+    prop_compose! {
+        fn columns(max_exp: u8)(len in (0..max_exp).prop_map(|i| 1usize << i))
+            (columns in collection::vec(collection::vec(any::<u64>().prop_map(FE::from), len), 1..4)) -> Vec<Vec<FE>> {
+            columns
+        }
+    }
+
+    proptest! {
+        // Property-based test that ensures extending columns gives the same result as
+        // interpolating them and evaluating the polynomials with an offset one at a time.
+        #[test]
+        fn test_extend_columns_matches_evaluate_offset_fft(columns in columns(8), blowup_exp in 0..3u32) {
+            let blowup_factor = 1 << blowup_exp;
+            let offset = FE::from(3);
+            let lde = CosetLDE::new(columns[0].len(), blowup_factor, &offset).unwrap();
+
+            let expected: Vec<Vec<FE>> = columns
+                .iter()
+                .map(|column| {
+                    let poly = Polynomial::interpolate_fft::<F>(column).unwrap();
+                    Polynomial::evaluate_offset_fft(&poly, blowup_factor, Some(column.len()), &offset).unwrap()
+                })
+                .collect();
+
+            prop_assert_eq!(lde.extend_columns(&columns).unwrap().columns(), expected);
+        }
+    }
+
+    #[test]
+    fn interpolate_columns_matches_interpolate_fft() {
+        let columns: Vec<Vec<FE>> = (0..3u64)
+            .map(|j| (0..16u64).map(|i| FE::from(i * i + j)).collect())
+            .collect();
+        let lde = CosetLDE::new(16, 4, &FE::from(7)).unwrap();
+
+        let expected: Vec<_> = columns
+            .iter()
+            .map(|column| Polynomial::interpolate_fft::<F>(column).unwrap())
+            .collect();
+        assert_eq!(lde.interpolate_columns(&columns).unwrap(), expected);
+    }
+
+    #[test]
+    fn evaluate_polynomials_of_high_degree_over_coset() {
+        let offset = FE::from(5);
+        let lde = CosetLDE::new(4, 2, &offset).unwrap();
+        let poly = Polynomial::new(&(1..20u64).map(FE::from).collect::<Vec<_>>());
+        let root = F::get_primitive_root_of_unity(3).unwrap();
+
+        let expected: Vec<FE> = (0..8u64)
+            .map(|i| poly.evaluate(&(&offset * root.pow(i))))
+            .collect();
+        assert_eq!(lde.evaluate_polynomials(&[poly]).columns(), vec![expected]);
+    }
+
+    #[test]
+    fn extend_values_in_field_extension() {
+        type E = Goldilocks64ExtensionField;
+        let column: Vec<FieldElement<E>> = (0..32u64)
+            .map(|i| FieldElement::new([FieldElement::from(i), FieldElement::from(3 * i + 1)]))
+            .collect();
+        let offset = FieldElement::<Goldilocks64Field>::from(7);
+        let lde = CosetLDE::new(32, 4, &offset).unwrap();
+
+        let poly = Polynomial::interpolate_fft::<Goldilocks64Field>(&column).unwrap();
+        let expected = Polynomial::evaluate_offset_fft(&poly, 4, Some(32), &offset).unwrap();
+        assert_eq!(lde.extend(&column).unwrap(), expected);
+        assert_eq!(lde.evaluate(&poly), expected);
+    }
+
+    #[test]
+    fn extend_rejects_columns_of_wrong_length() {
+        let lde = CosetLDE::new(8, 2, &FE::from(3)).unwrap();
+        assert!(matches!(
+            lde.extend(&vec![FE::one(); 4]),
+            Err(FFTError::InputError(4))
+        ));
+        assert!(matches!(
+            CosetLDE::new(6, 2, &FE::from(3)),
+            Err(FFTError::InputError(6))
+        ));
+    }
+
+    #[test]
+    fn coset_evaluations_are_rows_in_bit_reverse_order() {
+        let columns: Vec<Vec<FE>> = (0..2u64)
+            .map(|j| (0..4u64).map(|i| FE::from(10 * j + i)).collect())
+            .collect();
+        let lde = CosetLDE::new(4, 2, &FE::from(3)).unwrap();
+        let evaluations = lde.extend_columns(&columns).unwrap();
+        let columns = evaluations.columns();
+
+        assert_eq!(evaluations.bit_reversed_rows().len(), 8);
+        for (i, row) in evaluations.bit_reversed_rows().iter().enumerate() {
+            let index = reverse_index(i, 8);
+            assert_eq!(
+                row,
+                &vec![columns[0][index].clone(), columns[1][index].clone()]
+            );
+        }
+        assert_eq!(
+            columns[0],
+            lde.extend(&(0..4u64).map(FE::from).collect::<Vec<_>>())
+                .unwrap()
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_extend_columns_matches_sequential() {
+        let columns: Vec<Vec<FE>> = (0..4u64)
+            .map(|j| (0..64u64).map(|i| FE::from(i * j + 1)).collect())
+            .collect();
+        let lde = CosetLDE::new(64, 8, &FE::from(3)).unwrap();
+
+        assert_eq!(
+            lde.parallel_extend_columns(&columns).unwrap(),
+            lde.extend_columns(&columns).unwrap()
+        );
+        let polys = lde.parallel_interpolate_columns(&columns).unwrap();
+        assert_eq!(polys, lde.interpolate_columns(&columns).unwrap());
+        assert_eq!(
+            lde.parallel_evaluate_polynomials(&polys),
+            lde.evaluate_polynomials(&polys)
+        );
+    }
+}
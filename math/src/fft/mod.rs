@@ -2,6 +2,8 @@ pub mod cpu;
 pub mod errors;
 pub mod gpu;
 #[cfg(feature = "alloc")]
+pub mod lde;
+#[cfg(feature = "alloc")]
 pub mod polynomial;
 
 #[cfg(all(test, feature = "alloc"))]
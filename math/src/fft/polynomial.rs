@@ -15,7 +15,16 @@ use crate::fft::gpu::cuda::polynomial::{evaluate_fft_cuda, interpolate_fft_cuda}
 #[cfg(feature = "metal")]
 use crate::fft::gpu::metal::polynomial::{evaluate_fft_metal, interpolate_fft_metal};
 
-use super::cpu::{ops, roots_of_unity};
+#[cfg(feature = "parallel")]
+use super::cpu::six_step::parallel_six_step_fft;
+use super::cpu::{
+    ops, roots_of_unity,
+    six_step::{six_step_fft, SIX_STEP_MIN_ORDER},
+};
+
+/// Polynomials with fewer coefficients are multiplied and divided without FFTs, which is faster
+/// for them.
+pub const FFT_MULTIPLICATION_MIN_LENGTH: usize = 64;
 
 impl<E: IsField> Polynomial<FieldElement<E>> {
     /// Returns `N` evaluations of this polynomial using FFT over a domain in a subfield F of E (so the results
@@ -26,6 +35,42 @@ impl<E: IsField> Polynomial<FieldElement<E>> {
         poly: &Polynomial<FieldElement<E>>,
         blowup_factor: usize,
         domain_size: Option<usize>,
+    ) -> Result<Vec<FieldElement<E>>, FFTError> {
+        Self::evaluate_fft_with::<F>(poly, blowup_factor, domain_size, evaluate_fft_cpu::<F, E>)
+    }
+
+    /// Parallel version of [Self::evaluate_fft], which splits the FFT among threads when it runs
+    /// on the CPU.
+    #[cfg(feature = "parallel")]
+    pub fn parallel_evaluate_fft<F: IsFFTField + IsSubFieldOf<E>>(
+        poly: &Polynomial<FieldElement<E>>,
+        blowup_factor: usize,
+        domain_size: Option<usize>,
+    ) -> Result<Vec<FieldElement<E>>, FFTError>
+    where
+        FieldElement<F>: Send + Sync,
+        FieldElement<E>: Send + Sync,
+    {
+        Self::evaluate_fft_with::<F>(
+            poly,
+            blowup_factor,
+            domain_size,
+            parallel_evaluate_fft_cpu::<F, E>,
+        )
+    }
+
+    /// Evaluates `poly` as [Self::evaluate_fft] does, with `cpu_fft` as the FFT whenever it
+    /// runs on the CPU.
+    // `F` is only used by the GPU FFTs.
+    #[cfg_attr(
+        all(not(feature = "metal"), not(feature = "cuda")),
+        allow(clippy::extra_unused_type_parameters)
+    )]
+    fn evaluate_fft_with<F: IsFFTField + IsSubFieldOf<E>>(
+        poly: &Polynomial<FieldElement<E>>,
+        blowup_factor: usize,
+        domain_size: Option<usize>,
+        cpu_fft: impl Fn(&[FieldElement<E>]) -> Result<Vec<FieldElement<E>>, FFTError>,
     ) -> Result<Vec<FieldElement<E>>, FFTError> {
         let domain_size = domain_size.unwrap_or(0);
         let len = core::cmp::max(poly.coeff_len(), domain_size).next_power_of_two() * blowup_factor;
@@ -47,7 +92,7 @@ impl<E: IsField> Polynomial<FieldElement<E>> {
                     "GPU evaluation failed for field {}. Program will fallback to CPU.",
                     core::any::type_name::<F>()
                 );
-                evaluate_fft_cpu::<F, E>(&coeffs)
+                cpu_fft(&coeffs)
             }
         }
 
@@ -57,13 +102,13 @@ impl<E: IsField> Polynomial<FieldElement<E>> {
             if F::field_name() == "stark256" {
                 Ok(evaluate_fft_cuda(&coeffs)?)
             } else {
-                evaluate_fft_cpu::<F, E>(&coeffs)
+                cpu_fft(&coeffs)
             }
         }
 
         #[cfg(all(not(feature = "metal"), not(feature = "cuda")))]
         {
-            evaluate_fft_cpu::<F, E>(&coeffs)
+            cpu_fft(&coeffs)
         }
     }
 
@@ -81,11 +126,55 @@ impl<E: IsField> Polynomial<FieldElement<E>> {
         Polynomial::evaluate_fft::<F>(&scaled, blowup_factor, domain_size)
     }
 
+    /// Parallel version of [Self::evaluate_offset_fft], which splits the FFT among threads when
+    /// it runs on the CPU.
+    #[cfg(feature = "parallel")]
+    pub fn parallel_evaluate_offset_fft<F: IsFFTField + IsSubFieldOf<E>>(
+        poly: &Polynomial<FieldElement<E>>,
+        blowup_factor: usize,
+        domain_size: Option<usize>,
+        offset: &FieldElement<F>,
+    ) -> Result<Vec<FieldElement<E>>, FFTError>
+    where
+        FieldElement<F>: Send + Sync,
+        FieldElement<E>: Send + Sync,
+    {
+        let scaled = poly.scale(offset);
+        Polynomial::parallel_evaluate_fft::<F>(&scaled, blowup_factor, domain_size)
+    }
+
     /// Returns a new polynomial that interpolates `(w^i, fft_evals[i])`, with `w` being a
     /// Nth primitive root of unity in a subfield F of E, and `i in 0..N`, with `N = fft_evals.len()`.
     /// This is considered to be the inverse operation of [Self::evaluate_fft()].
     pub fn interpolate_fft<F: IsFFTField + IsSubFieldOf<E>>(
         fft_evals: &[FieldElement<E>],
+    ) -> Result<Self, FFTError> {
+        Self::interpolate_fft_with::<F>(fft_evals, interpolate_fft_cpu::<F, E>)
+    }
+
+    /// Parallel version of [Self::interpolate_fft], which splits the FFT among threads when it
+    /// runs on the CPU.
+    #[cfg(feature = "parallel")]
+    pub fn parallel_interpolate_fft<F: IsFFTField + IsSubFieldOf<E>>(
+        fft_evals: &[FieldElement<E>],
+    ) -> Result<Self, FFTError>
+    where
+        FieldElement<F>: Send + Sync,
+        FieldElement<E>: Send + Sync,
+    {
+        Self::interpolate_fft_with::<F>(fft_evals, parallel_interpolate_fft_cpu::<F, E>)
+    }
+
+    /// Interpolates `fft_evals` as [Self::interpolate_fft] does, with `cpu_fft` as the inverse
+    /// FFT whenever it runs on the CPU.
+    // `F` is only used by the GPU FFTs.
+    #[cfg_attr(
+        all(not(feature = "metal"), not(feature = "cuda")),
+        allow(clippy::extra_unused_type_parameters)
+    )]
+    fn interpolate_fft_with<F: IsFFTField + IsSubFieldOf<E>>(
+        fft_evals: &[FieldElement<E>],
+        cpu_fft: impl Fn(&[FieldElement<E>]) -> Result<Self, FFTError>,
     ) -> Result<Self, FFTError> {
         #[cfg(feature = "metal")]
         {
@@ -96,7 +185,7 @@ impl<E: IsField> Polynomial<FieldElement<E>> {
                     "GPU interpolation failed for field {}. Program will fallback to CPU.",
                     core::any::type_name::<F>()
                 );
-                interpolate_fft_cpu::<F, E>(fft_evals)
+                cpu_fft(fft_evals)
             }
         }
 
@@ -105,13 +194,13 @@ impl<E: IsField> Polynomial<FieldElement<E>> {
             if !F::field_name().is_empty() {
                 Ok(interpolate_fft_cuda(fft_evals)?)
             } else {
-                interpolate_fft_cpu::<F, E>(fft_evals)
+                cpu_fft(fft_evals)
             }
         }
 
         #[cfg(all(not(feature = "metal"), not(feature = "cuda")))]
         {
-            interpolate_fft_cpu::<F, E>(fft_evals)
+            cpu_fft(fft_evals)
         }
     }
 
@@ -125,6 +214,154 @@ impl<E: IsField> Polynomial<FieldElement<E>> {
         let scaled = Polynomial::interpolate_fft::<F>(fft_evals)?;
         Ok(scaled.scale(&offset.inv().unwrap()))
     }
+
+    /// Parallel version of [Self::interpolate_offset_fft], which splits the FFT among threads
+    /// when it runs on the CPU.
+    #[cfg(feature = "parallel")]
+    pub fn parallel_interpolate_offset_fft<F: IsFFTField + IsSubFieldOf<E>>(
+        fft_evals: &[FieldElement<E>],
+        offset: &FieldElement<F>,
+    ) -> Result<Polynomial<FieldElement<E>>, FFTError>
+    where
+        FieldElement<F>: Send + Sync,
+        FieldElement<E>: Send + Sync,
+    {
+        let scaled = Polynomial::parallel_interpolate_fft::<F>(fft_evals)?;
+        Ok(scaled.scale(&offset.inv().unwrap()))
+    }
+
+    /// Multiplies `self` and `factor` by evaluating both over a domain of roots of unity of a
+    /// subfield F of E, multiplying the evaluations and interpolating the result.
+    pub fn fast_fft_multiplication<F: IsFFTField + IsSubFieldOf<E>>(
+        &self,
+        factor: &Self,
+    ) -> Result<Self, FFTError> {
+        if self.coefficients.is_empty() || factor.coefficients.is_empty() {
+            return Ok(Polynomial::zero());
+        }
+        let domain_size = self.coeff_len() + factor.coeff_len() - 1;
+        let self_evaluations = Polynomial::evaluate_fft::<F>(self, 1, Some(domain_size))?;
+        let factor_evaluations = Polynomial::evaluate_fft::<F>(factor, 1, Some(domain_size))?;
+
+        let product_evaluations: Vec<_> = self_evaluations
+            .iter()
+            .zip(&factor_evaluations)
+            .map(|(a, b)| a * b)
+            .collect();
+        Polynomial::interpolate_fft::<F>(&product_evaluations)
+    }
+
+    /// Multiplies `self` and `factor`, with [Self::fast_fft_multiplication] if both have at
+    /// least `FFT_MULTIPLICATION_MIN_LENGTH` coefficients and with [Self::mul_with_ref]
+    /// otherwise, which is faster for small polynomials.
+    pub fn fast_multiplication<F: IsFFTField + IsSubFieldOf<E>>(
+        &self,
+        factor: &Self,
+    ) -> Result<Self, FFTError> {
+        if self.coeff_len().min(factor.coeff_len()) < FFT_MULTIPLICATION_MIN_LENGTH {
+            Ok(self.mul_with_ref(factor))
+        } else {
+            self.fast_fft_multiplication::<F>(factor)
+        }
+    }
+
+    /// Returns the inverse of `self` modulo `X^precision`, that is, the polynomial `g` of degree
+    /// less than `precision` such that `self * g = 1 mod X^precision`. It is computed by Newton
+    /// iteration, which doubles the precision of `g` with two multiplications each step.
+    ///
+    /// Returns [FFTError::DivisionByZero] if the constant coefficient of `self` is zero, since
+    /// then there is no inverse.
+    pub fn invert_polynomial_mod<F: IsFFTField + IsSubFieldOf<E>>(
+        &self,
+        precision: usize,
+    ) -> Result<Self, FFTError> {
+        self.invert_polynomial_mod_with(precision, &|a, b| a.fast_multiplication::<F>(b))
+    }
+
+    /// Computes the quotient and remainder of the division of `self` by `divisor`, as
+    /// [Self::long_division_with_remainder] does. When both the quotient and `divisor` are large,
+    /// the quotient is obtained from the inverse of the reversed divisor, computed with
+    /// [Self::invert_polynomial_mod], so that the division takes a few multiplications.
+    /// Returns [FFTError::DivisionByZero] if `divisor` is zero.
+    ///
+    /// Output: (quotient, remainder)
+    pub fn fast_division<F: IsFFTField + IsSubFieldOf<E>>(
+        &self,
+        divisor: &Self,
+    ) -> Result<(Self, Self), FFTError> {
+        self.fast_division_with(divisor, &|a, b| a.fast_multiplication::<F>(b))
+    }
+
+    /// [Self::invert_polynomial_mod], with the products computed by `multiply`.
+    pub(crate) fn invert_polynomial_mod_with(
+        &self,
+        precision: usize,
+        multiply: &impl Fn(&Self, &Self) -> Result<Self, FFTError>,
+    ) -> Result<Self, FFTError> {
+        let constant = self
+            .coefficients
+            .first()
+            .cloned()
+            .unwrap_or_else(FieldElement::zero);
+        let constant_inv = constant.inv().map_err(|_| FFTError::DivisionByZero)?;
+        let mut inverse = Polynomial::new(&[constant_inv]);
+        let mut current_precision = 1;
+        while current_precision < precision {
+            current_precision = (2 * current_precision).min(precision);
+            // g <- g * (2 - f * g) mod X^current_precision
+            let error =
+                multiply(&self.truncate(current_precision), &inverse)?.truncate(current_precision);
+            let correction = Polynomial::new(&[FieldElement::<E>::from(2)]) - error;
+            inverse = multiply(&inverse, &correction)?.truncate(current_precision);
+        }
+        Ok(inverse.truncate(precision))
+    }
+
+    /// [Self::fast_division], with the products computed by `multiply`.
+    pub(crate) fn fast_division_with(
+        &self,
+        divisor: &Self,
+        multiply: &impl Fn(&Self, &Self) -> Result<Self, FFTError>,
+    ) -> Result<(Self, Self), FFTError> {
+        let divisor_len = divisor.coeff_len();
+        if divisor_len == 0 {
+            return Err(FFTError::DivisionByZero);
+        }
+        if self.coeff_len() < divisor_len {
+            return Ok((Polynomial::zero(), self.clone()));
+        }
+        let quotient_len = self.coeff_len() - divisor_len + 1;
+        if quotient_len.min(divisor_len) < FFT_MULTIPLICATION_MIN_LENGTH {
+            return Ok(self.clone().long_division_with_remainder(divisor));
+        }
+
+        // With rev(p) = X^deg(p) * p(1/X), self = q * divisor + r implies that
+        // rev(self) = rev(q) * rev(divisor) mod X^quotient_len.
+        let reversed_divisor_inverse = divisor
+            .reverse(divisor_len)
+            .invert_polynomial_mod_with(quotient_len, multiply)?;
+        let reversed_quotient = multiply(
+            &self.reverse(self.coeff_len()).truncate(quotient_len),
+            &reversed_divisor_inverse,
+        )?
+        .truncate(quotient_len);
+        let quotient = reversed_quotient.reverse(quotient_len);
+        let remainder = self - multiply(&quotient, divisor)?;
+        Ok((quotient, remainder))
+    }
+
+    /// Returns `self mod X^length`.
+    fn truncate(&self, length: usize) -> Self {
+        Polynomial::new(&self.coefficients[..length.min(self.coeff_len())])
+    }
+
+    /// Returns `X^(length - 1) * self(1/X)`, for `self` of degree less than `length`.
+    fn reverse(&self, length: usize) -> Self {
+        let mut coefficients = self.coefficients.clone();
+        coefficients.resize(length, FieldElement::zero());
+        coefficients.reverse();
+        Polynomial::new(&coefficients)
+    }
 }
 
 pub fn compose_fft<F, E>(
@@ -146,28 +383,91 @@ where
 }
 
 pub fn evaluate_fft_cpu<F, E>(coeffs: &[FieldElement<E>]) -> Result<Vec<FieldElement<E>>, FFTError>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+{
+    evaluate_fft_cpu_with::<F, E>(coeffs, six_step_fft, ops::fft)
+}
+
+/// Parallel version of [evaluate_fft_cpu].
+#[cfg(feature = "parallel")]
+pub fn parallel_evaluate_fft_cpu<F, E>(
+    coeffs: &[FieldElement<E>],
+) -> Result<Vec<FieldElement<E>>, FFTError>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+    FieldElement<F>: Send + Sync,
+    FieldElement<E>: Send + Sync,
+{
+    evaluate_fft_cpu_with::<F, E>(coeffs, parallel_six_step_fft, ops::parallel_fft)
+}
+
+pub fn interpolate_fft_cpu<F, E>(
+    fft_evals: &[FieldElement<E>],
+) -> Result<Polynomial<FieldElement<E>>, FFTError>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+{
+    interpolate_fft_cpu_with::<F, E>(fft_evals, six_step_fft, ops::fft)
+}
+
+/// Parallel version of [interpolate_fft_cpu].
+#[cfg(feature = "parallel")]
+pub fn parallel_interpolate_fft_cpu<F, E>(
+    fft_evals: &[FieldElement<E>],
+) -> Result<Polynomial<FieldElement<E>>, FFTError>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+    FieldElement<F>: Send + Sync,
+    FieldElement<E>: Send + Sync,
+{
+    interpolate_fft_cpu_with::<F, E>(fft_evals, parallel_six_step_fft, ops::parallel_fft)
+}
+
+/// Evaluates the polynomial with coefficients `coeffs` with `six_step` on large domains and with
+/// `fft` on the others.
+fn evaluate_fft_cpu_with<F, E>(
+    coeffs: &[FieldElement<E>],
+    six_step: impl Fn(&[FieldElement<E>], &FieldElement<F>) -> Result<Vec<FieldElement<E>>, FFTError>,
+    fft: impl Fn(&[FieldElement<E>], &[FieldElement<F>]) -> Result<Vec<FieldElement<E>>, FFTError>,
+) -> Result<Vec<FieldElement<E>>, FFTError>
 where
     F: IsFFTField + IsSubFieldOf<E>,
     E: IsField,
 {
     let order = coeffs.len().trailing_zeros();
+    if order >= SIX_STEP_MIN_ORDER {
+        let root = F::get_primitive_root_of_unity(order.into())?;
+        return six_step(coeffs, &root);
+    }
     let twiddles = roots_of_unity::get_twiddles::<F>(order.into(), RootsConfig::BitReverse)?;
     // Bit reverse order is needed for NR DIT FFT.
-    ops::fft(coeffs, &twiddles)
+    fft(coeffs, &twiddles)
 }
 
-pub fn interpolate_fft_cpu<F, E>(
+/// Interpolates `fft_evals` with `six_step` on large domains and with `fft` on the others.
+fn interpolate_fft_cpu_with<F, E>(
     fft_evals: &[FieldElement<E>],
+    six_step: impl Fn(&[FieldElement<E>], &FieldElement<F>) -> Result<Vec<FieldElement<E>>, FFTError>,
+    fft: impl Fn(&[FieldElement<E>], &[FieldElement<F>]) -> Result<Vec<FieldElement<E>>, FFTError>,
 ) -> Result<Polynomial<FieldElement<E>>, FFTError>
 where
     F: IsFFTField + IsSubFieldOf<E>,
     E: IsField,
 {
     let order = fft_evals.len().trailing_zeros();
-    let twiddles =
-        roots_of_unity::get_twiddles::<F>(order.into(), RootsConfig::BitReverseInversed)?;
-
-    let coeffs = ops::fft(fft_evals, &twiddles)?;
+    let coeffs = if order >= SIX_STEP_MIN_ORDER {
+        let root = F::get_primitive_root_of_unity(order.into())?;
+        six_step(fft_evals, &root.inv().unwrap())?
+    } else {
+        let twiddles =
+            roots_of_unity::get_twiddles::<F>(order.into(), RootsConfig::BitReverseInversed)?;
+        fft(fft_evals, &twiddles)?
+    };
 
     let scale_factor = FieldElement::from(fft_evals.len() as u64).inv().unwrap();
     Ok(Polynomial::new(&coeffs).scale_coeffs(&scale_factor))
@@ -341,6 +641,19 @@ mod tests {
 
                 prop_assert_eq!(poly, new_poly);
             }
+
+            // Property-based test that ensures the parallel FFTs give the same results as the sequential ones.
+            #[cfg(feature = "parallel")]
+            #[test]
+            fn test_parallel_fft_matches_fft(poly in poly(8), offset in offset(), blowup_factor in powers_of_two(4)) {
+                let evals = Polynomial::evaluate_offset_fft::<F>(&poly, blowup_factor, None, &offset).unwrap();
+                let parallel_evals = Polynomial::parallel_evaluate_offset_fft::<F>(&poly, blowup_factor, None, &offset).unwrap();
+                prop_assert_eq!(&parallel_evals, &evals);
+
+                let interpolated = Polynomial::interpolate_offset_fft::<F>(&evals, &offset).unwrap();
+                let parallel_interpolated = Polynomial::parallel_interpolate_offset_fft::<F>(&evals, &offset).unwrap();
+                prop_assert_eq!(parallel_interpolated, interpolated);
+            }
         }
 
         #[test]
@@ -455,4 +768,78 @@ mod tests {
             Polynomial::interpolate_offset_fft::<TF>(&eval, &FieldElement::from(2)).unwrap();
         assert_eq!(poly, new_poly);
     }
+
+    prop_compose! {
+        fn test_poly(max_len: usize)(coeffs in collection::vec(any::<u64>().prop_map(FieldElement::<U64TestField>::from), 0..max_len)) -> Polynomial<FieldElement<U64TestField>> {
+            Polynomial::new(&coeffs)
+        }
+    }
+
+    proptest! {
+        // Property-based test that ensures the FFT multiplication gives the same result as
+        // mul_with_ref.
+        #[test]
+        fn test_fast_multiplication_matches_mul_with_ref(p in test_poly(300), q in test_poly(300)) {
+            let expected = p.mul_with_ref(&q);
+            prop_assert_eq!(p.fast_fft_multiplication::<U64TestField>(&q).unwrap(), expected.clone());
+            prop_assert_eq!(p.fast_multiplication::<U64TestField>(&q).unwrap(), expected);
+        }
+
+        // Property-based test that ensures the fast division gives the same quotient and remainder
+        // as the long division.
+        #[test]
+        fn test_fast_division_matches_long_division(p in test_poly(400), q in test_poly(200).prop_filter("Avoid division by zero", |q| q.coeff_len() > 0)) {
+            let expected = p.clone().long_division_with_remainder(&q);
+            prop_assert_eq!(p.fast_division::<U64TestField>(&q).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn invert_polynomial_mod_gives_inverse_modulo_power_of_x() {
+        type FE = FieldElement<U64TestField>;
+        let poly = Polynomial::new(&(1..150u64).map(FE::from).collect::<Vec<_>>());
+
+        let inverse = poly.invert_polynomial_mod::<U64TestField>(100).unwrap();
+        let product = poly.mul_with_ref(&inverse);
+        assert!(inverse.coeff_len() <= 100);
+        assert_eq!(product.coefficients()[0], FE::one());
+        assert!(product.coefficients()[1..100]
+            .iter()
+            .all(|c| *c == FE::zero()));
+    }
+
+    #[test]
+    fn fast_division_with_values_in_field_extension() {
+        type TF = U64TestField;
+        type TL = U64TestFieldExtension;
+        let element = |i: u64| {
+            FieldElement::<TL>::from(&[FieldElement::from(i), FieldElement::from(i * i + 1)])
+        };
+        let dividend = Polynomial::new(&(0..500).map(element).collect::<Vec<_>>());
+        let divisor = Polynomial::new(&(3..200).map(element).collect::<Vec<_>>());
+
+        let (quotient, remainder) = dividend.fast_division::<TF>(&divisor).unwrap();
+        assert!(remainder.coeff_len() < divisor.coeff_len());
+        assert_eq!(quotient.mul_with_ref(&divisor) + remainder, dividend);
+    }
+
+    #[test]
+    fn fast_division_and_inversion_by_zero_fail() {
+        type FE = FieldElement<U64TestField>;
+        let poly = Polynomial::new(&(1..150u64).map(FE::from).collect::<Vec<_>>());
+
+        assert!(matches!(
+            poly.fast_division::<U64TestField>(&Polynomial::zero()),
+            Err(FFTError::DivisionByZero)
+        ));
+        assert!(matches!(
+            Polynomial::new(&[FE::from(3)]).fast_division::<U64TestField>(&Polynomial::zero()),
+            Err(FFTError::DivisionByZero)
+        ));
+        assert!(matches!(
+            (&poly * Polynomial::new(&[FE::zero(), FE::one()]))
+                .invert_polynomial_mod::<U64TestField>(100),
+            Err(FFTError::DivisionByZero)
+        ));
+    }
 }
@@ -3,6 +3,7 @@ use crate::field::errors::FieldError;
 use crate::field::traits::IsField;
 #[cfg(feature = "lambdaworks-serde-binary")]
 use crate::traits::ByteConversion;
+use crate::traits::ConditionalSwap;
 use crate::unsigned_integer::element::UnsignedInteger;
 use crate::unsigned_integer::montgomery::MontgomeryAlgorithms;
 use crate::unsigned_integer::traits::IsUnsignedInteger;
@@ -127,6 +128,16 @@ where
 
 impl<F> Eq for FieldElement<F> where F: IsField {}
 
+impl<F> ConditionalSwap for FieldElement<F>
+where
+    F: IsField,
+    F::BaseType: ConditionalSwap,
+{
+    fn conditional_swap(&mut self, other: &mut Self, swap: u64) {
+        self.value.conditional_swap(&mut other.value, swap);
+    }
+}
+
 /// Addition operator overloading for field elements
 impl<F, L> Add<&FieldElement<L>> for &FieldElement<F>
 where
@@ -475,6 +486,15 @@ where
         Self { value: F::zero() }
     }
 
+    /// Returns an element built from the 64-bit limbs returned by `next_limb`. It is uniformly
+    /// distributed over the whole field, up to a negligible statistical distance, when the
+    /// limbs are uniformly random. See [IsField::from_random_limbs].
+    pub fn from_random_limbs(next_limb: &mut impl FnMut() -> u64) -> Self {
+        Self {
+            value: F::from_random_limbs(next_limb),
+        }
+    }
+
     /// Returns the raw base type
     pub fn to_raw(self) -> F::BaseType {
         self.value
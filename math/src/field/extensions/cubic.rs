@@ -62,6 +62,8 @@ where
 {
     type BaseType = [FieldElement<F>; 3];
 
+    const EXTENSION_DEGREE: usize = 3 * F::EXTENSION_DEGREE;
+
     /// Returns the component wise addition of `a` and `b`
     fn add(a: &[FieldElement<F>; 3], b: &[FieldElement<F>; 3]) -> [FieldElement<F>; 3] {
         [&a[0] + &b[0], &a[1] + &b[1], &a[2] + &b[2]]
@@ -152,6 +154,15 @@ where
     fn from_base_type(x: [FieldElement<F>; 3]) -> [FieldElement<F>; 3] {
         x
     }
+
+    /// Samples each coordinate independently.
+    fn from_random_limbs(next_limb: &mut impl FnMut() -> u64) -> [FieldElement<F>; 3] {
+        [
+            FieldElement::from_random_limbs(next_limb),
+            FieldElement::from_random_limbs(next_limb),
+            FieldElement::from_random_limbs(next_limb),
+        ]
+    }
 }
 
 impl<F, Q> IsSubFieldOf<CubicExtensionField<F, Q>> for F
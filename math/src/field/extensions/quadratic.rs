@@ -75,6 +75,8 @@ where
 {
     type BaseType = [FieldElement<F>; 2];
 
+    const EXTENSION_DEGREE: usize = 2 * F::EXTENSION_DEGREE;
+
     /// Returns the component wise addition of `a` and `b`
     fn add(a: &[FieldElement<F>; 2], b: &[FieldElement<F>; 2]) -> [FieldElement<F>; 2] {
         [&a[0] + &b[0], &a[1] + &b[1]]
@@ -149,6 +151,14 @@ where
     fn from_base_type(x: [FieldElement<F>; 2]) -> [FieldElement<F>; 2] {
         x
     }
+
+    /// Samples each coordinate independently.
+    fn from_random_limbs(next_limb: &mut impl FnMut() -> u64) -> [FieldElement<F>; 2] {
+        [
+            FieldElement::from_random_limbs(next_limb),
+            FieldElement::from_random_limbs(next_limb),
+        ]
+    }
 }
 
 impl<F, Q> IsSubFieldOf<QuadraticExtensionField<F, Q>> for F
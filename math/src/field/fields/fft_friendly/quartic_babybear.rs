@@ -0,0 +1,426 @@
+use crate::{
+    errors::ByteConversionError,
+    field::{
+        element::FieldElement,
+        errors::FieldError,
+        fields::fft_friendly::babybear::Babybear31PrimeField,
+        traits::{IsField, IsSubFieldOf},
+    },
+    traits::ByteConversion,
+};
+
+#[cfg(feature = "alloc")]
+use crate::traits::AsBytes;
+
+/// The non residue `W` such that the quartic extension is `Babybear[X] / (X^4 - W)`.
+/// Since the order of Babybear is 1 modulo 4 and 11 is not a square, `X^4 - 11` is irreducible.
+pub const BETA: u64 = 11;
+
+/// Degree 4 binomial extension of Babybear, `Babybear[X] / (X^4 - 11)`.
+/// Its elements are represented by their coefficients `[a0, a1, a2, a3]` in the basis
+/// `1, X, X^2, X^3`. With 124 bits, it is large enough to sample the challenges of STARKs over
+/// Babybear.
+#[derive(Clone, Debug)]
+pub struct QuarticBabybearField;
+
+type BabybearElement = FieldElement<Babybear31PrimeField>;
+
+fn beta() -> BabybearElement {
+    BabybearElement::from(BETA)
+}
+
+/// Multiplies two polynomials of degree one with the Karatsuba method, returning the three
+/// coefficients of the product.
+fn karatsuba_degree_one(
+    a0: &BabybearElement,
+    a1: &BabybearElement,
+    b0: &BabybearElement,
+    b1: &BabybearElement,
+) -> [BabybearElement; 3] {
+    let low = a0 * b0;
+    let high = a1 * b1;
+    let middle = (a0 + a1) * (b0 + b1) - &low - &high;
+    [low, middle, high]
+}
+
+impl IsField for QuarticBabybearField {
+    type BaseType = [BabybearElement; 4];
+
+    const EXTENSION_DEGREE: usize = 4;
+
+    /// Returns the component wise addition of `a` and `b`
+    fn add(a: &Self::BaseType, b: &Self::BaseType) -> Self::BaseType {
+        [&a[0] + &b[0], &a[1] + &b[1], &a[2] + &b[2], &a[3] + &b[3]]
+    }
+
+    /// Returns the multiplication of `a` and `b`.
+    /// Writing `a = A0 + A1 X^2` and `b = B0 + B1 X^2`, where `A0`, `A1`, `B0` and `B1` have
+    /// degree one, the product is `A0 B0 + ((A0 + A1)(B0 + B1) - A0 B0 - A1 B1) X^2 + A1 B1 W`,
+    /// which takes three products of polynomials of degree one. Each of them is computed with
+    /// Karatsuba again, for a total of nine multiplications in Babybear.
+    fn mul(a: &Self::BaseType, b: &Self::BaseType) -> Self::BaseType {
+        let low = karatsuba_degree_one(&a[0], &a[1], &b[0], &b[1]);
+        let high = karatsuba_degree_one(&a[2], &a[3], &b[2], &b[3]);
+        let sum = karatsuba_degree_one(
+            &(&a[0] + &a[2]),
+            &(&a[1] + &a[3]),
+            &(&b[0] + &b[2]),
+            &(&b[1] + &b[3]),
+        );
+        let middle = [
+            &sum[0] - &low[0] - &high[0],
+            &sum[1] - &low[1] - &high[1],
+            &sum[2] - &low[2] - &high[2],
+        ];
+        let beta = beta();
+
+        [
+            &low[0] + &beta * (&middle[2] + &high[0]),
+            &low[1] + &beta * &high[1],
+            &low[2] + &middle[0] + &beta * &high[2],
+            middle[1].clone(),
+        ]
+    }
+
+    /// Returns the component wise subtraction of `a` and `b`
+    fn sub(a: &Self::BaseType, b: &Self::BaseType) -> Self::BaseType {
+        [&a[0] - &b[0], &a[1] - &b[1], &a[2] - &b[2], &a[3] - &b[3]]
+    }
+
+    /// Returns the component wise negation of `a`
+    fn neg(a: &Self::BaseType) -> Self::BaseType {
+        [-&a[0], -&a[1], -&a[2], -&a[3]]
+    }
+
+    /// Returns the multiplicative inverse of `a`.
+    /// Writing `a = A0 + A1 X` with `A0 = a0 + a2 X^2` and `A1 = a1 + a3 X^2` in the quadratic
+    /// subfield generated by `u = X^2`, the product `(A0 + A1 X)(A0 - A1 X) = A0^2 - A1^2 u` lies
+    /// in that subfield, where it is inverted by multiplying by its conjugate.
+    fn inv(a: &Self::BaseType) -> Result<Self::BaseType, FieldError> {
+        let beta = beta();
+        let [a0, a1, a2, a3] = a;
+
+        // norm = n0 + n1 u = A0^2 - A1^2 u
+        let n0 = a0.square() + &beta * a2.square() - (&beta * a1 * a3).double();
+        let n1 = (a0 * a2).double() - a1.square() - &beta * a3.square();
+
+        // (n0 + n1 u)^-1 = (n0 - n1 u) / (n0^2 - W n1^2)
+        let norm_inv = (n0.square() - &beta * n1.square()).inv()?;
+        let m0 = &n0 * &norm_inv;
+        let m1 = -(&n1 * &norm_inv);
+
+        // a^-1 = (A0 - A1 X) (m0 + m1 u)
+        Ok([
+            a0 * &m0 + &beta * a2 * &m1,
+            -(a1 * &m0 + &beta * a3 * &m1),
+            a0 * &m1 + a2 * &m0,
+            -(a1 * &m1 + a3 * &m0),
+        ])
+    }
+
+    /// Returns the division of `a` and `b`
+    fn div(a: &Self::BaseType, b: &Self::BaseType) -> Self::BaseType {
+        <Self as IsField>::mul(a, &Self::inv(b).unwrap())
+    }
+
+    /// Returns a boolean indicating whether `a` and `b` are equal component wise.
+    fn eq(a: &Self::BaseType, b: &Self::BaseType) -> bool {
+        a[0] == b[0] && a[1] == b[1] && a[2] == b[2] && a[3] == b[3]
+    }
+
+    /// Returns the additive neutral element of the field extension.
+    fn zero() -> Self::BaseType {
+        [
+            FieldElement::zero(),
+            FieldElement::zero(),
+            FieldElement::zero(),
+            FieldElement::zero(),
+        ]
+    }
+
+    /// Returns the multiplicative neutral element of the field extension.
+    fn one() -> Self::BaseType {
+        [
+            FieldElement::one(),
+            FieldElement::zero(),
+            FieldElement::zero(),
+            FieldElement::zero(),
+        ]
+    }
+
+    /// Returns the element `x * 1` where 1 is the multiplicative neutral element.
+    fn from_u64(x: u64) -> Self::BaseType {
+        [
+            FieldElement::from(x),
+            FieldElement::zero(),
+            FieldElement::zero(),
+            FieldElement::zero(),
+        ]
+    }
+
+    /// Takes as input an element of BaseType and returns the internal representation
+    /// of that element in the field.
+    /// Note: for this case this is simply the identity, because the components
+    /// already have correct representations.
+    fn from_base_type(x: Self::BaseType) -> Self::BaseType {
+        x
+    }
+
+    /// Samples each coordinate independently.
+    fn from_random_limbs(next_limb: &mut impl FnMut() -> u64) -> Self::BaseType {
+        core::array::from_fn(|_| FieldElement::from_random_limbs(next_limb))
+    }
+}
+
+impl IsSubFieldOf<QuarticBabybearField> for Babybear31PrimeField {
+    fn mul(
+        a: &Self::BaseType,
+        b: &<QuarticBabybearField as IsField>::BaseType,
+    ) -> <QuarticBabybearField as IsField>::BaseType {
+        let a = FieldElement::<Self>::from_raw(*a);
+        [&a * &b[0], &a * &b[1], &a * &b[2], &a * &b[3]]
+    }
+
+    fn add(
+        a: &Self::BaseType,
+        b: &<QuarticBabybearField as IsField>::BaseType,
+    ) -> <QuarticBabybearField as IsField>::BaseType {
+        let a = FieldElement::<Self>::from_raw(*a);
+        [a + &b[0], b[1].clone(), b[2].clone(), b[3].clone()]
+    }
+
+    fn div(
+        a: &Self::BaseType,
+        b: &<QuarticBabybearField as IsField>::BaseType,
+    ) -> <QuarticBabybearField as IsField>::BaseType {
+        let b_inv = QuarticBabybearField::inv(b).unwrap();
+        <Self as IsSubFieldOf<QuarticBabybearField>>::mul(a, &b_inv)
+    }
+
+    fn sub(
+        a: &Self::BaseType,
+        b: &<QuarticBabybearField as IsField>::BaseType,
+    ) -> <QuarticBabybearField as IsField>::BaseType {
+        let a = FieldElement::<Self>::from_raw(*a);
+        [a - &b[0], -&b[1], -&b[2], -&b[3]]
+    }
+
+    fn embed(a: Self::BaseType) -> <QuarticBabybearField as IsField>::BaseType {
+        [
+            FieldElement::from_raw(a),
+            FieldElement::zero(),
+            FieldElement::zero(),
+            FieldElement::zero(),
+        ]
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_subfield_vec(
+        b: <QuarticBabybearField as IsField>::BaseType,
+    ) -> alloc::vec::Vec<Self::BaseType> {
+        b.into_iter().map(|x| x.to_raw()).collect()
+    }
+}
+
+/// Number of bytes used to encode each coordinate of an element of the extension.
+const COORDINATE_BYTES: usize = 8;
+
+fn coordinates_from_bytes(
+    bytes: &[u8],
+    from_bytes: fn(&[u8]) -> Result<BabybearElement, ByteConversionError>,
+    length_error: ByteConversionError,
+) -> Result<[BabybearElement; 4], ByteConversionError> {
+    if bytes.len() != 4 * COORDINATE_BYTES {
+        return Err(length_error);
+    }
+    Ok([
+        from_bytes(&bytes[..COORDINATE_BYTES])?,
+        from_bytes(&bytes[COORDINATE_BYTES..2 * COORDINATE_BYTES])?,
+        from_bytes(&bytes[2 * COORDINATE_BYTES..3 * COORDINATE_BYTES])?,
+        from_bytes(&bytes[3 * COORDINATE_BYTES..])?,
+    ])
+}
+
+impl ByteConversion for [BabybearElement; 4] {
+    #[cfg(feature = "alloc")]
+    fn to_bytes_be(&self) -> alloc::vec::Vec<u8> {
+        self.iter().flat_map(ByteConversion::to_bytes_be).collect()
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_bytes_le(&self) -> alloc::vec::Vec<u8> {
+        self.iter().flat_map(ByteConversion::to_bytes_le).collect()
+    }
+
+    fn from_bytes_be(bytes: &[u8]) -> Result<Self, ByteConversionError>
+    where
+        Self: Sized,
+    {
+        coordinates_from_bytes(
+            bytes,
+            <BabybearElement as ByteConversion>::from_bytes_be,
+            ByteConversionError::FromBEBytesError,
+        )
+    }
+
+    fn from_bytes_le(bytes: &[u8]) -> Result<Self, ByteConversionError>
+    where
+        Self: Sized,
+    {
+        coordinates_from_bytes(
+            bytes,
+            <BabybearElement as ByteConversion>::from_bytes_le,
+            ByteConversionError::FromLEBytesError,
+        )
+    }
+}
+
+impl ByteConversion for FieldElement<QuarticBabybearField> {
+    #[cfg(feature = "alloc")]
+    fn to_bytes_be(&self) -> alloc::vec::Vec<u8> {
+        ByteConversion::to_bytes_be(self.value())
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_bytes_le(&self) -> alloc::vec::Vec<u8> {
+        ByteConversion::to_bytes_le(self.value())
+    }
+
+    fn from_bytes_be(bytes: &[u8]) -> Result<Self, ByteConversionError>
+    where
+        Self: Sized,
+    {
+        Ok(Self::new(<[BabybearElement; 4]>::from_bytes_be(bytes)?))
+    }
+
+    fn from_bytes_le(bytes: &[u8]) -> Result<Self, ByteConversionError>
+    where
+        Self: Sized,
+    {
+        Ok(Self::new(<[BabybearElement; 4]>::from_bytes_le(bytes)?))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl AsBytes for FieldElement<QuarticBabybearField> {
+    fn as_bytes(&self) -> alloc::vec::Vec<u8> {
+        ByteConversion::to_bytes_be(self)
+    }
+}
+
+/// Field element type for the quartic extension of Babybear
+pub type QuarticBabybearFieldElement = FieldElement<QuarticBabybearField>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type FE = FieldElement<Babybear31PrimeField>;
+    type Fee = QuarticBabybearFieldElement;
+
+    fn schoolbook_mul(a: &Fee, b: &Fee) -> Fee {
+        let mut product = vec![FE::zero(); 7];
+        for (i, a_i) in a.value().iter().enumerate() {
+            for (j, b_j) in b.value().iter().enumerate() {
+                product[i + j] += a_i * b_j;
+            }
+        }
+        let beta = FE::from(BETA);
+        Fee::new([
+            &product[0] + &beta * &product[4],
+            &product[1] + &beta * &product[5],
+            &product[2] + &beta * &product[6],
+            product[3].clone(),
+        ])
+    }
+
+    fn some_elements() -> Vec<Fee> {
+        vec![
+            Fee::new([FE::from(12), FE::from(5), FE::from(7), FE::from(3)]),
+            Fee::new([-FE::from(4), FE::from(2), FE::zero(), FE::from(2013265000)]),
+            Fee::new([FE::zero(), FE::zero(), FE::zero(), FE::one()]),
+            Fee::new([FE::from(1 << 30), -FE::one(), FE::from(99), FE::zero()]),
+        ]
+    }
+
+    #[test]
+    fn test_add_quartic() {
+        let a = Fee::new([FE::from(0), FE::from(3), FE::from(5), FE::from(7)]);
+        let b = Fee::new([-FE::from(2), FE::from(8), FE::from(1), FE::from(0)]);
+        let expected_result = Fee::new([-FE::from(2), FE::from(11), FE::from(6), FE::from(7)]);
+        assert_eq!(a + b, expected_result);
+    }
+
+    #[test]
+    fn test_sub_quartic() {
+        let a = Fee::new([FE::from(0), FE::from(3), FE::from(5), FE::from(7)]);
+        let b = Fee::new([-FE::from(2), FE::from(8), FE::from(1), FE::from(0)]);
+        let expected_result = Fee::new([FE::from(2), -FE::from(5), FE::from(4), FE::from(7)]);
+        assert_eq!(a - b, expected_result);
+    }
+
+    #[test]
+    fn karatsuba_mul_matches_schoolbook_mul() {
+        for a in some_elements() {
+            for b in some_elements() {
+                assert_eq!(&a * &b, schoolbook_mul(&a, &b));
+            }
+        }
+    }
+
+    #[test]
+    fn fourth_power_of_the_generator_is_the_non_residue() {
+        let x = Fee::new([FE::zero(), FE::one(), FE::zero(), FE::zero()]);
+        assert_eq!(x.pow(4_u64), Fee::from(BETA));
+    }
+
+    #[test]
+    fn test_inv_quartic() {
+        for a in some_elements() {
+            assert_eq!(&a * a.inv().unwrap(), Fee::one());
+        }
+    }
+
+    #[test]
+    fn inverse_of_zero_is_an_error() {
+        assert!(Fee::zero().inv().is_err());
+    }
+
+    #[test]
+    fn test_div_quartic() {
+        let a = Fee::new([FE::from(12), FE::from(5), FE::from(7), FE::from(3)]);
+        let b = Fee::new([-FE::from(4), FE::from(2), FE::zero(), FE::one()]);
+        let expected_result = &a * b.inv().unwrap();
+        assert_eq!(a / b, expected_result);
+    }
+
+    #[test]
+    fn operations_with_base_field_elements_match_the_embedding() {
+        let a = FE::from(1234567);
+        let b = Fee::new([FE::from(12), FE::from(5), FE::from(7), FE::from(3)]);
+        let a_extended = a.clone().to_extension::<QuarticBabybearField>();
+        assert_eq!(&a * &b, &a_extended * &b);
+        assert_eq!(&a + &b, &a_extended + &b);
+        assert_eq!(&a - &b, &a_extended - &b);
+        assert_eq!(&a / &b, &a_extended / &b);
+    }
+
+    #[test]
+    fn multiplicative_group_has_the_expected_order() {
+        let a = Fee::new([FE::from(12), FE::from(5), FE::from(7), FE::from(3)]);
+        let p = 2013265921_u128;
+        assert_eq!(a.pow(p.pow(4) - 1), Fee::one());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn byte_conversion_round_trips() {
+        for a in some_elements() {
+            let bytes_be = ByteConversion::to_bytes_be(&a);
+            let bytes_le = ByteConversion::to_bytes_le(&a);
+            assert_eq!(bytes_be.len(), 32);
+            assert_eq!(Fee::from_bytes_be(&bytes_be).unwrap(), a);
+            assert_eq!(Fee::from_bytes_le(&bytes_le).unwrap(), a);
+        }
+    }
+}
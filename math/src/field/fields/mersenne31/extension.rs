@@ -1,13 +1,20 @@
-use crate::field::{
-    element::FieldElement,
-    errors::FieldError,
-    extensions::{
-        cubic::{CubicExtensionField, HasCubicNonResidue},
-        quadratic::{HasQuadraticNonResidue, QuadraticExtensionField},
+use crate::{
+    errors::ByteConversionError,
+    field::{
+        element::FieldElement,
+        errors::FieldError,
+        extensions::{
+            cubic::{CubicExtensionField, HasCubicNonResidue},
+            quadratic::{HasQuadraticNonResidue, QuadraticExtensionField},
+        },
+        traits::IsField,
     },
-    traits::IsField,
+    traits::ByteConversion,
 };
 
+#[cfg(feature = "alloc")]
+use crate::traits::AsBytes;
+
 use super::field::Mersenne31Field;
 
 //Note: The inverse calculation in mersenne31/plonky3 differs from the default quadratic extension so I implemented the complex extension.
@@ -19,6 +26,8 @@ impl IsField for Mersenne31Complex {
     //Elements represents a[0] = real, a[1] = imaginary
     type BaseType = [FieldElement<Mersenne31Field>; 2];
 
+    const EXTENSION_DEGREE: usize = 2;
+
     /// Returns the component wise addition of `a` and `b`
     fn add(a: &Self::BaseType, b: &Self::BaseType) -> Self::BaseType {
         [a[0] + b[0], a[1] + b[1]]
@@ -91,12 +100,50 @@ impl IsField for Mersenne31Complex {
     fn from_base_type(x: Self::BaseType) -> Self::BaseType {
         x
     }
+
+    /// Samples each coordinate independently.
+    fn from_random_limbs(next_limb: &mut impl FnMut() -> u64) -> Self::BaseType {
+        [
+            FieldElement::from_random_limbs(next_limb),
+            FieldElement::from_random_limbs(next_limb),
+        ]
+    }
 }
 
+impl crate::field::traits::IsSubFieldOf<Mersenne31Complex> for Mersenne31Field {
+    fn mul(a: &Self::BaseType, b: &[FieldElement<Mersenne31Field>; 2]) -> [FieldElement<Self>; 2] {
+        let a = FieldElement::<Self>::from_raw(*a);
+        [a * b[0], a * b[1]]
+    }
+
+    fn add(a: &Self::BaseType, b: &[FieldElement<Mersenne31Field>; 2]) -> [FieldElement<Self>; 2] {
+        [FieldElement::<Self>::from_raw(*a) + b[0], b[1]]
+    }
+
+    fn div(a: &Self::BaseType, b: &[FieldElement<Mersenne31Field>; 2]) -> [FieldElement<Self>; 2] {
+        let b_inv = Mersenne31Complex::inv(b).unwrap();
+        <Self as crate::field::traits::IsSubFieldOf<Mersenne31Complex>>::mul(a, &b_inv)
+    }
+
+    fn sub(a: &Self::BaseType, b: &[FieldElement<Mersenne31Field>; 2]) -> [FieldElement<Self>; 2] {
+        [FieldElement::<Self>::from_raw(*a) - b[0], -b[1]]
+    }
+
+    fn embed(a: Self::BaseType) -> [FieldElement<Self>; 2] {
+        [FieldElement::from_raw(a), FieldElement::zero()]
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_subfield_vec(b: [FieldElement<Mersenne31Field>; 2]) -> alloc::vec::Vec<Self::BaseType> {
+        b.into_iter().map(|x| x.to_raw()).collect()
+    }
+}
+
+/// The degree 4 extension of Mersenne31, built as a quadratic extension of
+/// `Mersenne31Complex` by a square root of `2 + i`.
 pub type Mersenne31ComplexQuadraticExtensionField =
-    QuadraticExtensionField<Mersenne31Field, Mersenne31Complex>;
+    QuadraticExtensionField<Mersenne31Complex, Mersenne31Complex>;
 
-//TODO: Check this should be for complex and not base field
 impl HasQuadraticNonResidue<Mersenne31Complex> for Mersenne31Complex {
     // Verifiable in Sage with
     // ```sage
@@ -116,8 +163,10 @@ impl HasQuadraticNonResidue<Mersenne31Complex> for Mersenne31Complex {
     }
 }
 
+/// The degree 6 extension of Mersenne31, built as a cubic extension of
+/// `Mersenne31Complex` by a cubic root of `5i`.
 pub type Mersenne31ComplexCubicExtensionField =
-    CubicExtensionField<Mersenne31Field, Mersenne31Complex>;
+    CubicExtensionField<Mersenne31Complex, Mersenne31Complex>;
 
 impl HasCubicNonResidue<Mersenne31Complex> for Mersenne31Complex {
     // Verifiable in Sage with
@@ -138,6 +187,116 @@ impl HasCubicNonResidue<Mersenne31Complex> for Mersenne31Complex {
     }
 }
 
+type QuarticBaseType = [FieldElement<Mersenne31Complex>; 2];
+
+impl crate::field::traits::IsSubFieldOf<Mersenne31ComplexQuadraticExtensionField>
+    for Mersenne31Field
+{
+    fn mul(a: &Self::BaseType, b: &QuarticBaseType) -> QuarticBaseType {
+        let a = FieldElement::<Self>::from_raw(*a);
+        [a * &b[0], a * &b[1]]
+    }
+
+    fn add(a: &Self::BaseType, b: &QuarticBaseType) -> QuarticBaseType {
+        [FieldElement::<Self>::from_raw(*a) + &b[0], b[1].clone()]
+    }
+
+    fn div(a: &Self::BaseType, b: &QuarticBaseType) -> QuarticBaseType {
+        let b_inv = Mersenne31ComplexQuadraticExtensionField::inv(b).unwrap();
+        <Self as crate::field::traits::IsSubFieldOf<Mersenne31ComplexQuadraticExtensionField>>::mul(
+            a, &b_inv,
+        )
+    }
+
+    fn sub(a: &Self::BaseType, b: &QuarticBaseType) -> QuarticBaseType {
+        [FieldElement::<Self>::from_raw(*a) - &b[0], -&b[1]]
+    }
+
+    fn embed(a: Self::BaseType) -> QuarticBaseType {
+        [
+            FieldElement::<Self>::from_raw(a).to_extension(),
+            FieldElement::zero(),
+        ]
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_subfield_vec(b: QuarticBaseType) -> alloc::vec::Vec<Self::BaseType> {
+        b.into_iter()
+            .flat_map(|x| x.to_raw())
+            .map(|x| x.to_raw())
+            .collect()
+    }
+}
+
+/// Elements are encoded in 16 bytes, as the concatenation of the encodings of their four
+/// coordinates over Mersenne31. Decoding only accepts such encodings, with all the coordinates
+/// canonical.
+impl ByteConversion for FieldElement<Mersenne31ComplexQuadraticExtensionField> {
+    #[cfg(feature = "alloc")]
+    fn to_bytes_be(&self) -> alloc::vec::Vec<u8> {
+        self.value()
+            .iter()
+            .flat_map(|x| x.value().iter())
+            .flat_map(ByteConversion::to_bytes_be)
+            .collect()
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_bytes_le(&self) -> alloc::vec::Vec<u8> {
+        self.value()
+            .iter()
+            .flat_map(|x| x.value().iter())
+            .flat_map(ByteConversion::to_bytes_le)
+            .collect()
+    }
+
+    fn from_bytes_be(bytes: &[u8]) -> Result<Self, ByteConversionError> {
+        let [a, b, c, d] = quartic_coordinates_from_bytes(
+            bytes,
+            FieldElement::<Mersenne31Field>::from_bytes_be,
+            ByteConversionError::FromBEBytesError,
+        )?;
+        Ok(Self::new([
+            FieldElement::new([a, b]),
+            FieldElement::new([c, d]),
+        ]))
+    }
+
+    fn from_bytes_le(bytes: &[u8]) -> Result<Self, ByteConversionError> {
+        let [a, b, c, d] = quartic_coordinates_from_bytes(
+            bytes,
+            FieldElement::<Mersenne31Field>::from_bytes_le,
+            ByteConversionError::FromLEBytesError,
+        )?;
+        Ok(Self::new([
+            FieldElement::new([a, b]),
+            FieldElement::new([c, d]),
+        ]))
+    }
+}
+
+fn quartic_coordinates_from_bytes(
+    bytes: &[u8],
+    from_bytes: fn(&[u8]) -> Result<FieldElement<Mersenne31Field>, ByteConversionError>,
+    error: ByteConversionError,
+) -> Result<[FieldElement<Mersenne31Field>; 4], ByteConversionError> {
+    if bytes.len() != 16 {
+        return Err(error);
+    }
+    let mut coordinates = [FieldElement::zero(); 4];
+    for (coordinate, chunk) in coordinates.iter_mut().zip(bytes.chunks(4)) {
+        *coordinate = from_bytes(chunk)?;
+    }
+    Ok(coordinates)
+}
+
+#[cfg(feature = "alloc")]
+impl AsBytes for FieldElement<Mersenne31ComplexQuadraticExtensionField> {
+    fn as_bytes(&self) -> alloc::vec::Vec<u8> {
+        ByteConversion::to_bytes_be(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::field::fields::mersenne31::field::MERSENNE_31_PRIME_FIELD_ORDER;
@@ -301,4 +460,59 @@ mod tests {
         let c = Fi::from_base_type([-F::from(2), F::from(18)]);
         assert_eq!(Fi::mul(&a, &b), c)
     }
+
+    type Fp4E = FieldElement<Mersenne31ComplexQuadraticExtensionField>;
+
+    fn quartic_element(a: u64, b: u64, c: u64, d: u64) -> Fp4E {
+        Fp4E::new([
+            FieldElement::new([F::from(a), F::from(b)]),
+            FieldElement::new([F::from(c), F::from(d)]),
+        ])
+    }
+
+    #[test]
+    fn quartic_element_times_its_inverse_is_one() {
+        let a = quartic_element(1234, 5678, 91011, 121314);
+        assert_eq!(&a * a.inv().unwrap(), Fp4E::one());
+    }
+
+    #[test]
+    fn quartic_multiplication_is_distributive() {
+        let a = quartic_element(1, 2, 3, 4);
+        let b = quartic_element(5, 6, 7, 8);
+        let c = quartic_element(9, 10, 11, 12);
+        assert_eq!(&a * (&b + &c), &a * &b + &a * &c);
+    }
+
+    #[test]
+    fn base_field_element_times_quartic_element_matches_embedding() {
+        let a = F::from(123456789);
+        let b = quartic_element(1, 2, 3, 4);
+        let a_embedded: Fp4E = a.to_extension();
+        assert_eq!(a * &b, &a_embedded * &b);
+        assert_eq!(a + &b, &a_embedded + &b);
+        assert_eq!(a - &b, &a_embedded - &b);
+        assert_eq!(a / &b, &a_embedded / &b);
+    }
+
+    #[test]
+    fn quartic_byte_conversion_round_trips() {
+        let a = quartic_element(1, 2, 3, MERSENNE_31_PRIME_FIELD_ORDER as u64 - 1);
+        let bytes = ByteConversion::to_bytes_be(&a);
+        assert_eq!(bytes.len(), 16);
+        assert_eq!(Fp4E::from_bytes_be(&bytes).unwrap(), a);
+        assert_eq!(
+            Fp4E::from_bytes_le(&ByteConversion::to_bytes_le(&a)).unwrap(),
+            a
+        );
+        assert!(Fp4E::from_bytes_be(&[0u8; 32]).is_err());
+        assert!(Fp4E::from_bytes_be(&[0u8; 15]).is_err());
+
+        let mut bytes = [0u8; 16];
+        bytes[12..].copy_from_slice(&MERSENNE_31_PRIME_FIELD_ORDER.to_be_bytes());
+        assert!(Fp4E::from_bytes_be(&bytes).is_err());
+        bytes[..4].copy_from_slice(&MERSENNE_31_PRIME_FIELD_ORDER.to_le_bytes());
+        bytes[12..].fill(0);
+        assert!(Fp4E::from_bytes_le(&bytes).is_err());
+    }
 }
@@ -1,11 +1,15 @@
 use crate::{
-    errors::CreationError,
+    errors::{ByteConversionError, CreationError},
     field::{
         element::FieldElement,
         errors::FieldError,
-        traits::{IsField, IsPrimeField},
+        traits::{reduce_random_limbs, IsField, IsPrimeField},
     },
+    traits::ByteConversion,
 };
+
+#[cfg(feature = "alloc")]
+use crate::traits::AsBytes;
 use core::fmt::{self, Display};
 
 /// Represents a 31 bit integer value
@@ -148,6 +152,10 @@ impl IsField for Mersenne31Field {
     fn from_base_type(x: u32) -> u32 {
         Self::weak_reduce(x)
     }
+
+    fn from_random_limbs(next_limb: &mut impl FnMut() -> u64) -> Self::BaseType {
+        reduce_random_limbs::<Self>(next_limb)
+    }
 }
 
 impl IsPrimeField for Mersenne31Field {
@@ -195,6 +203,41 @@ impl FieldElement<Mersenne31Field> {
     }
 }
 
+/// Elements are encoded in 4 bytes. Decoding only accepts such encodings of canonical values,
+/// that is, below the order of the field.
+impl ByteConversion for FieldElement<Mersenne31Field> {
+    #[cfg(feature = "alloc")]
+    fn to_bytes_be(&self) -> alloc::vec::Vec<u8> {
+        self.representative().to_be_bytes().to_vec()
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_bytes_le(&self) -> alloc::vec::Vec<u8> {
+        self.representative().to_le_bytes().to_vec()
+    }
+
+    fn from_bytes_be(bytes: &[u8]) -> Result<Self, ByteConversionError> {
+        match bytes.try_into().map(u32::from_be_bytes) {
+            Ok(value) if value < MERSENNE_31_PRIME_FIELD_ORDER => Ok(Self::from_raw(value)),
+            _ => Err(ByteConversionError::FromBEBytesError),
+        }
+    }
+
+    fn from_bytes_le(bytes: &[u8]) -> Result<Self, ByteConversionError> {
+        match bytes.try_into().map(u32::from_le_bytes) {
+            Ok(value) if value < MERSENNE_31_PRIME_FIELD_ORDER => Ok(Self::from_raw(value)),
+            _ => Err(ByteConversionError::FromLEBytesError),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl AsBytes for FieldElement<Mersenne31Field> {
+    fn as_bytes(&self) -> alloc::vec::Vec<u8> {
+        self.representative().to_be_bytes().to_vec()
+    }
+}
+
 impl Display for FieldElement<Mersenne31Field> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{:x}", self.representative())
@@ -234,6 +277,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn byte_conversion_round_trips() {
+        let a = FieldElement::<F>::from(1314320703);
+        assert_eq!(
+            FieldElement::<F>::from_bytes_be(&ByteConversion::to_bytes_be(&a)).unwrap(),
+            a
+        );
+        assert_eq!(
+            FieldElement::<F>::from_bytes_le(&ByteConversion::to_bytes_le(&a)).unwrap(),
+            a
+        );
+    }
+
+    #[test]
+    fn from_bytes_rejects_non_canonical_encodings() {
+        type FE = FieldElement<F>;
+        let order = MERSENNE_31_PRIME_FIELD_ORDER;
+        assert_eq!(
+            FE::from_bytes_be(&(order - 1).to_be_bytes()).unwrap(),
+            -FE::one()
+        );
+        assert!(FE::from_bytes_be(&order.to_be_bytes()).is_err());
+        assert!(FE::from_bytes_le(&order.to_le_bytes()).is_err());
+        assert!(FE::from_bytes_be(&u32::MAX.to_be_bytes()).is_err());
+        assert!(FE::from_bytes_be(&[0u8; 3]).is_err());
+        assert!(FE::from_bytes_le(&[0u8; 8]).is_err());
+    }
+
     #[test]
     fn one_plus_1_is_2() {
         let a = F::one();
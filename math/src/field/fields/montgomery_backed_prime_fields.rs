@@ -1,6 +1,6 @@
 use crate::field::element::FieldElement;
 use crate::field::errors::FieldError;
-use crate::field::traits::IsPrimeField;
+use crate::field::traits::{reduce_random_limbs, IsPrimeField};
 #[cfg(feature = "alloc")]
 use crate::traits::AsBytes;
 use crate::traits::ByteConversion;
@@ -223,16 +223,20 @@ where
 
                 if v <= u {
                     u = u - v;
+                    // b + p may not fit in the limbs when the modulus has no spare bit,
+                    // but b + (p - c) < p does.
                     if b < c {
-                        b = b + modulus;
+                        b = b + (modulus - c);
+                    } else {
+                        b = b - c;
                     }
-                    b = b - c;
                 } else {
                     v = v - u;
                     if c < b {
-                        c = c + modulus;
+                        c = c + (modulus - b);
+                    } else {
+                        c = c - b;
                     }
-                    c = c - b;
                 }
             }
 
@@ -278,6 +282,10 @@ where
     fn from_base_type(x: Self::BaseType) -> Self::BaseType {
         MontgomeryAlgorithms::cios(&x, &Self::R2, &M::MODULUS, &Self::MU)
     }
+
+    fn from_random_limbs(next_limb: &mut impl FnMut() -> u64) -> Self::BaseType {
+        reduce_random_limbs::<Self>(next_limb)
+    }
 }
 
 impl<M, const NUM_LIMBS: usize> IsPrimeField for MontgomeryBackendPrimeField<M, NUM_LIMBS>
@@ -292,8 +300,8 @@ where
 
     fn field_bit_size() -> usize {
         let mut evaluated_bit = NUM_LIMBS * 64 - 1;
-        let max_element = M::MODULUS - UnsignedInteger::<NUM_LIMBS>::from_u128(1);
-        let one = UnsignedInteger::from_u128(1);
+        let max_element = M::MODULUS - UnsignedInteger::<NUM_LIMBS>::from_u64(1);
+        let one = UnsignedInteger::from_u64(1);
 
         while ((max_element >> evaluated_bit) & one) != one {
             evaluated_bit -= 1;
@@ -416,6 +424,14 @@ mod tests_u384_prime_fields {
         assert_eq!(Stark252PrimeField::field_bit_size(), 252);
     }
 
+    #[test]
+    fn babybear_prime_field_uses_31_bits() {
+        assert_eq!(
+            crate::field::fields::fft_friendly::babybear::Babybear31PrimeField::field_bit_size(),
+            31
+        );
+    }
+
     #[test]
     fn u256_mod_2_uses_1_bit() {
         #[derive(Clone, Debug)]
@@ -1211,4 +1227,28 @@ mod tests_u256_prime_fields {
         let expected_sum = GoldilocksElement::from(4638951561171426781);
         assert_eq!(a + b, expected_sum);
     }
+
+    // secp256k1 base field, whose modulus has no spare bit
+    #[derive(Clone, Debug)]
+    struct Secp256k1Modulus;
+    impl IsModulus<U256> for Secp256k1Modulus {
+        const MODULUS: U256 = U256::from_hex_unchecked(
+            "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+        );
+    }
+
+    type Secp256k1Element = FieldElement<U256PrimeField<Secp256k1Modulus>>;
+
+    #[test]
+    fn inv_works_for_moduli_without_spare_bits() {
+        for a in [
+            Secp256k1Element::from(2),
+            -Secp256k1Element::from(3),
+            Secp256k1Element::from_hex_unchecked(
+                "c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5",
+            ),
+        ] {
+            assert_eq!(&a * a.inv().unwrap(), Secp256k1Element::one());
+        }
+    }
 }
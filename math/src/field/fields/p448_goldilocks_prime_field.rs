@@ -1,6 +1,6 @@
 use crate::errors::CreationError;
 use crate::field::errors::FieldError;
-use crate::field::traits::{IsField, IsPrimeField};
+use crate::field::traits::{reduce_random_limbs, IsField, IsPrimeField};
 #[cfg(feature = "lambdaworks-serde-binary")]
 use crate::traits::ByteConversion;
 use crate::unsigned_integer::element::UnsignedInteger;
@@ -196,6 +196,10 @@ impl IsField for P448GoldilocksPrimeField {
         Self::strong_reduce(&mut x);
         x
     }
+
+    fn from_random_limbs(next_limb: &mut impl FnMut() -> u64) -> Self::BaseType {
+        reduce_random_limbs::<Self>(next_limb)
+    }
 }
 
 impl IsPrimeField for P448GoldilocksPrimeField {
@@ -1,15 +1,16 @@
 use core::fmt::{self, Display};
 
-#[cfg(feature = "lambdaworks-serde-binary")]
-use crate::traits::ByteConversion;
+#[cfg(feature = "alloc")]
+use crate::traits::AsBytes;
 use crate::{
-    errors::CreationError,
+    errors::{ByteConversionError, CreationError},
     field::{
         element::FieldElement,
         errors::FieldError,
         extensions::quadratic::{HasQuadraticNonResidue, QuadraticExtensionField},
-        traits::{IsField, IsPrimeField},
+        traits::{reduce_random_limbs, IsFFTField, IsField, IsPrimeField},
     },
+    traits::ByteConversion,
 };
 
 /// Goldilocks Prime Field F_p where p = 2^64 - 2^32 + 1;
@@ -34,14 +35,14 @@ impl ByteConversion for u64 {
         unimplemented!()
     }
 
-    fn from_bytes_be(_bytes: &[u8]) -> Result<Self, crate::errors::ByteConversionError>
+    fn from_bytes_be(_bytes: &[u8]) -> Result<Self, ByteConversionError>
     where
         Self: Sized,
     {
         unimplemented!()
     }
 
-    fn from_bytes_le(_bytes: &[u8]) -> Result<Self, crate::errors::ByteConversionError>
+    fn from_bytes_le(_bytes: &[u8]) -> Result<Self, ByteConversionError>
     where
         Self: Sized,
     {
@@ -148,6 +149,10 @@ impl IsField for Goldilocks64Field {
     fn from_base_type(x: u64) -> u64 {
         Self::representative(&x)
     }
+
+    fn from_random_limbs(next_limb: &mut impl FnMut() -> u64) -> Self::BaseType {
+        reduce_random_limbs::<Self>(next_limb)
+    }
 }
 
 impl IsPrimeField for Goldilocks64Field {
@@ -184,6 +189,16 @@ impl IsPrimeField for Goldilocks64Field {
     }
 }
 
+impl IsFFTField for Goldilocks64Field {
+    const TWO_ADICITY: u64 = 32;
+    // 7^((p - 1) / 2^32), where 7 generates the multiplicative group.
+    const TWO_ADIC_PRIMITVE_ROOT_OF_UNITY: u64 = 1753635133440165772;
+
+    fn field_name() -> &'static str {
+        "goldilocks64"
+    }
+}
+
 #[inline(always)]
 fn reduce_128(x: u128) -> u64 {
     //possibly split apart into separate function to ensure inline
@@ -226,6 +241,66 @@ impl HasQuadraticNonResidue<Goldilocks64Field> for Goldilocks64Field {
     }
 }
 
+impl IsFFTField for Goldilocks64ExtensionField {
+    // p^2 - 1 = (p - 1)(p + 1), with 2^32 dividing p - 1 and 2 dividing p + 1.
+    const TWO_ADICITY: u64 = 33;
+    // A square root of the two-adic root of unity of the base field. Since that root is not
+    // a square in the base field, its square root is `t * x` with `7 * t^2` equal to it.
+    const TWO_ADIC_PRIMITVE_ROOT_OF_UNITY: [FieldElement<Goldilocks64Field>; 2] = [
+        FieldElement::const_from_raw(0),
+        FieldElement::const_from_raw(2787638404040055058),
+    ];
+}
+
+/// Elements are encoded in 16 bytes, as the concatenation of the encodings of their two
+/// coordinates. Decoding only accepts such encodings, with both coordinates canonical.
+impl ByteConversion for FieldElement<Goldilocks64ExtensionField> {
+    #[cfg(feature = "alloc")]
+    fn to_bytes_be(&self) -> alloc::vec::Vec<u8> {
+        self.value()
+            .iter()
+            .flat_map(ByteConversion::to_bytes_be)
+            .collect()
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_bytes_le(&self) -> alloc::vec::Vec<u8> {
+        self.value()
+            .iter()
+            .flat_map(ByteConversion::to_bytes_le)
+            .collect()
+    }
+
+    fn from_bytes_be(bytes: &[u8]) -> Result<Self, ByteConversionError> {
+        if bytes.len() != 16 {
+            return Err(ByteConversionError::FromBEBytesError);
+        }
+        let (a, b) = bytes.split_at(8);
+        Ok(Self::new([
+            FieldElement::from_bytes_be(a)?,
+            FieldElement::from_bytes_be(b)?,
+        ]))
+    }
+
+    fn from_bytes_le(bytes: &[u8]) -> Result<Self, ByteConversionError> {
+        if bytes.len() != 16 {
+            return Err(ByteConversionError::FromLEBytesError);
+        }
+        let (a, b) = bytes.split_at(8);
+        Ok(Self::new([
+            FieldElement::from_bytes_le(a)?,
+            FieldElement::from_bytes_le(b)?,
+        ]))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl AsBytes for FieldElement<Goldilocks64ExtensionField> {
+    fn as_bytes(&self) -> alloc::vec::Vec<u8> {
+        ByteConversion::to_bytes_be(self)
+    }
+}
+
 impl Display for FieldElement<Goldilocks64Field> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{:x}", self.representative())?;
@@ -233,6 +308,41 @@ impl Display for FieldElement<Goldilocks64Field> {
     }
 }
 
+/// Elements are encoded in 8 bytes. Decoding only accepts such encodings of canonical values,
+/// that is, below the order of the field.
+impl ByteConversion for FieldElement<Goldilocks64Field> {
+    #[cfg(feature = "alloc")]
+    fn to_bytes_be(&self) -> alloc::vec::Vec<u8> {
+        self.representative().to_be_bytes().to_vec()
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_bytes_le(&self) -> alloc::vec::Vec<u8> {
+        self.representative().to_le_bytes().to_vec()
+    }
+
+    fn from_bytes_be(bytes: &[u8]) -> Result<Self, ByteConversionError> {
+        match bytes.try_into().map(u64::from_be_bytes) {
+            Ok(value) if value < Goldilocks64Field::ORDER => Ok(Self::from_raw(value)),
+            _ => Err(ByteConversionError::FromBEBytesError),
+        }
+    }
+
+    fn from_bytes_le(bytes: &[u8]) -> Result<Self, ByteConversionError> {
+        match bytes.try_into().map(u64::from_le_bytes) {
+            Ok(value) if value < Goldilocks64Field::ORDER => Ok(Self::from_raw(value)),
+            _ => Err(ByteConversionError::FromLEBytesError),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl AsBytes for FieldElement<Goldilocks64Field> {
+    fn as_bytes(&self) -> alloc::vec::Vec<u8> {
+        self.representative().to_be_bytes().to_vec()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -485,4 +595,110 @@ mod tests {
         let num = F::from_hex("B").unwrap();
         assert_eq!(F::to_hex(&num), "B");
     }
+
+    #[test]
+    fn two_adic_root_of_unity_has_order_two_to_the_two_adicity() {
+        type FE = FieldElement<F>;
+        let root = F::get_primitive_root_of_unity(F::TWO_ADICITY).unwrap();
+        assert_eq!(root.pow(1u64 << (F::TWO_ADICITY - 1)), -FE::one());
+    }
+
+    #[test]
+    fn extension_two_adic_root_of_unity_is_a_square_root_of_the_base_one() {
+        type E = Goldilocks64ExtensionField;
+        let root = E::get_primitive_root_of_unity(E::TWO_ADICITY).unwrap();
+        let base_root = F::get_primitive_root_of_unity(F::TWO_ADICITY).unwrap();
+        assert_eq!(root.square(), base_root.to_extension());
+        assert_eq!(
+            root.pow(1u64 << (E::TWO_ADICITY - 1)),
+            -FieldElement::<E>::one()
+        );
+        assert_eq!(
+            E::get_primitive_root_of_unity(3).unwrap(),
+            F::get_primitive_root_of_unity(3).unwrap().to_extension()
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn fft_over_goldilocks_and_its_extension_matches_naive_evaluation() {
+        use crate::{
+            fft::cpu::roots_of_unity::get_powers_of_primitive_root, field::traits::RootsConfig,
+            polynomial::Polynomial,
+        };
+        type E = Goldilocks64ExtensionField;
+
+        let coefficients: alloc::vec::Vec<_> = (0..16u64)
+            .map(|i| {
+                FieldElement::<E>::new([
+                    FieldElement::from(i * i + 3),
+                    FieldElement::from(u64::MAX - i),
+                ])
+            })
+            .collect();
+        let poly = Polynomial::new(&coefficients);
+
+        let domain: alloc::vec::Vec<FieldElement<E>> =
+            get_powers_of_primitive_root::<F>(4, 16, RootsConfig::Natural)
+                .unwrap()
+                .into_iter()
+                .map(|x| x.to_extension())
+                .collect();
+        assert_eq!(
+            Polynomial::evaluate_fft::<F>(&poly, 1, None).unwrap(),
+            poly.evaluate_slice(&domain)
+        );
+
+        let domain = get_powers_of_primitive_root::<E>(5, 32, RootsConfig::Natural).unwrap();
+        let evaluations = Polynomial::evaluate_fft::<E>(&poly, 2, None).unwrap();
+        assert_eq!(evaluations, poly.evaluate_slice(&domain));
+        assert_eq!(
+            Polynomial::interpolate_fft::<E>(&evaluations).unwrap(),
+            poly
+        );
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        type FE = FieldElement<F>;
+        let a = FE::from(0x0123_4567_89ab_cdef);
+        assert_eq!(FE::from_bytes_be(&a.to_bytes_be()).unwrap(), a);
+        assert_eq!(FE::from_bytes_le(&a.to_bytes_le()).unwrap(), a);
+
+        let b = FieldElement::<Goldilocks64ExtensionField>::new([a, -FE::from(5)]);
+        assert_eq!(b.to_bytes_be().len(), 16);
+        assert_eq!(
+            FieldElement::<Goldilocks64ExtensionField>::from_bytes_be(&b.to_bytes_be()).unwrap(),
+            b
+        );
+        assert_eq!(
+            FieldElement::<Goldilocks64ExtensionField>::from_bytes_le(&b.to_bytes_le()).unwrap(),
+            b
+        );
+    }
+
+    #[test]
+    fn from_bytes_rejects_non_canonical_encodings() {
+        type FE = FieldElement<F>;
+        type FE2 = FieldElement<Goldilocks64ExtensionField>;
+        let order = F::ORDER;
+        assert_eq!(
+            FE::from_bytes_be(&(order - 1).to_be_bytes()).unwrap(),
+            -FE::one()
+        );
+        assert!(FE::from_bytes_be(&order.to_be_bytes()).is_err());
+        assert!(FE::from_bytes_le(&order.to_le_bytes()).is_err());
+        assert!(FE::from_bytes_be(&u64::MAX.to_be_bytes()).is_err());
+        assert!(FE::from_bytes_be(&[1; 7]).is_err());
+        assert!(FE::from_bytes_le(&[0; 9]).is_err());
+
+        let mut bytes = [0u8; 16];
+        bytes[8..].copy_from_slice(&order.to_be_bytes());
+        assert!(FE2::from_bytes_be(&bytes).is_err());
+        bytes[..8].copy_from_slice(&order.to_le_bytes());
+        bytes[8..].fill(0);
+        assert!(FE2::from_bytes_le(&bytes).is_err());
+        assert!(FE2::from_bytes_be(&[0; 8]).is_err());
+        assert!(FE2::from_bytes_le(&[0; 32]).is_err());
+    }
 }
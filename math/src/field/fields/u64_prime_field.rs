@@ -4,7 +4,7 @@ use crate::errors::CreationError;
 use crate::errors::DeserializationError;
 use crate::field::element::FieldElement;
 use crate::field::errors::FieldError;
-use crate::field::traits::{IsFFTField, IsField, IsPrimeField};
+use crate::field::traits::{reduce_random_limbs, IsFFTField, IsField, IsPrimeField};
 use crate::traits::{ByteConversion, Deserializable};
 
 /// Type representing prime fields over unsigned 64-bit integers.
@@ -69,6 +69,10 @@ impl<const MODULUS: u64> IsField for U64PrimeField<MODULUS> {
     fn from_base_type(x: u64) -> u64 {
         Self::from_u64(x)
     }
+
+    fn from_random_limbs(next_limb: &mut impl FnMut() -> u64) -> Self::BaseType {
+        reduce_random_limbs::<Self>(next_limb)
+    }
 }
 
 impl<const MODULUS: u64> Copy for U64FieldElement<MODULUS> {}
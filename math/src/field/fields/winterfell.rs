@@ -5,7 +5,7 @@ use crate::{
     field::{
         element::FieldElement,
         errors::FieldError,
-        traits::{IsFFTField, IsField, IsPrimeField, IsSubFieldOf},
+        traits::{reduce_random_limbs, IsFFTField, IsField, IsPrimeField, IsSubFieldOf},
     },
     traits::{AsBytes, ByteConversion},
     unsigned_integer::element::U256,
@@ -89,6 +89,10 @@ impl IsField for Felt {
     fn from_base_type(x: Self::BaseType) -> Self::BaseType {
         x
     }
+
+    fn from_random_limbs(next_limb: &mut impl FnMut() -> u64) -> Self::BaseType {
+        reduce_random_limbs::<Self>(next_limb)
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -193,6 +197,8 @@ impl From<FieldElement<QuadFelt>> for alloc::vec::Vec<u8> {
 impl IsField for QuadFelt {
     type BaseType = QuadFelt;
 
+    const EXTENSION_DEGREE: usize = 2;
+
     fn add(a: &Self::BaseType, b: &Self::BaseType) -> Self::BaseType {
         *a + *b
     }
@@ -236,6 +242,14 @@ impl IsField for QuadFelt {
     fn from_base_type(x: Self::BaseType) -> Self::BaseType {
         x
     }
+
+    /// Samples each coordinate independently.
+    fn from_random_limbs(next_limb: &mut impl FnMut() -> u64) -> Self::BaseType {
+        QuadFelt::new(
+            <Felt as IsField>::from_random_limbs(next_limb),
+            <Felt as IsField>::from_random_limbs(next_limb),
+        )
+    }
 }
 
 impl IsSubFieldOf<QuadFelt> for Felt {
@@ -1,7 +1,7 @@
 use crate::{
     errors::CreationError,
     field::errors::FieldError,
-    field::traits::{IsFFTField, IsField, IsPrimeField},
+    field::traits::{reduce_random_limbs, IsFFTField, IsField, IsPrimeField},
 };
 
 #[cfg(feature = "lambdaworks-serde-binary")]
@@ -87,6 +87,10 @@ impl<const MODULUS: u32> IsField for U32Field<MODULUS> {
     fn from_base_type(x: u32) -> u32 {
         x % MODULUS
     }
+
+    fn from_random_limbs(next_limb: &mut impl FnMut() -> u64) -> Self::BaseType {
+        reduce_random_limbs::<Self>(next_limb)
+    }
 }
 
 impl<const MODULUS: u32> IsPrimeField for U32Field<MODULUS> {
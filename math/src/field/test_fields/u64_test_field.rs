@@ -3,7 +3,7 @@ use crate::{
     field::{
         element::FieldElement,
         extensions::quadratic::QuadraticExtensionField,
-        traits::{IsFFTField, IsField, IsPrimeField},
+        traits::{reduce_random_limbs, IsFFTField, IsField, IsPrimeField},
     },
     field::{errors::FieldError, extensions::quadratic::HasQuadraticNonResidue},
 };
@@ -60,6 +60,10 @@ impl<const MODULUS: u64> IsField for U64Field<MODULUS> {
     fn from_base_type(x: u64) -> u64 {
         Self::from_u64(x)
     }
+
+    fn from_random_limbs(next_limb: &mut impl FnMut() -> u64) -> Self::BaseType {
+        reduce_random_limbs::<Self>(next_limb)
+    }
 }
 
 impl<const MODULUS: u64> IsPrimeField for U64Field<MODULUS> {
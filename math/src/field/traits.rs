@@ -103,6 +103,9 @@ pub trait IsField: Debug + Clone {
     #[cfg(not(feature = "lambdaworks-serde-binary"))]
     type BaseType: Clone + Debug + Unpin;
 
+    /// The degree of the field over its prime subfield.
+    const EXTENSION_DEGREE: usize = 1;
+
     /// Returns the sum of `a` and `b`.
     fn add(a: &Self::BaseType, b: &Self::BaseType) -> Self::BaseType;
 
@@ -184,6 +187,25 @@ pub trait IsField: Debug + Clone {
     /// Takes as input an element of BaseType and returns the internal representation
     /// of that element in the field.
     fn from_base_type(x: Self::BaseType) -> Self::BaseType;
+
+    /// Returns an element built from the 64-bit limbs returned by `next_limb`, which is
+    /// statistically close to uniform if the limbs are uniformly random. Prime fields can
+    /// implement it with [reduce_random_limbs]. Extension fields must sample each of their
+    /// coordinates independently from their subfield.
+    fn from_random_limbs(next_limb: &mut impl FnMut() -> u64) -> Self::BaseType;
+}
+
+/// Returns the integer formed by eight limbs returned by `next_limb`, reduced in the prime field
+/// `F`. If the limbs are uniformly random, the element is at statistical distance at most
+/// `p / 2^512` from uniform, where `p` is the order of `F`. It only reaches the elements of the
+/// form `x * 1`, so it is not uniform over extension fields.
+pub fn reduce_random_limbs<F: IsField + ?Sized>(
+    next_limb: &mut impl FnMut() -> u64,
+) -> F::BaseType {
+    let two_to_64 = F::add(&F::from_u64(u64::MAX), &F::one());
+    (0..8).fold(F::zero(), |acc, _| {
+        F::add(&F::mul(&acc, &two_to_64), &F::from_u64(next_limb()))
+    })
 }
 
 #[derive(PartialEq)]
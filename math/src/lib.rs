@@ -3,6 +3,8 @@
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "alloc")]
+pub mod circle;
 pub mod cyclic_group;
 pub mod elliptic_curve;
 pub mod errors;
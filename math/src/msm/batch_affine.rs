@@ -0,0 +1,408 @@
+use crate::{
+    cyclic_group::IsGroup,
+    elliptic_curve::{
+        short_weierstrass::{point::ShortWeierstrassProjectivePoint, traits::IsShortWeierstrass},
+        traits::IsEllipticCurve,
+    },
+    field::element::FieldElement,
+    unsigned_integer::element::UnsignedInteger,
+};
+
+use super::{
+    naive::MSMError,
+    pippenger::{max_bits, num_signed_windows, optimum_window_size, signed_digits},
+};
+
+use alloc::{vec, vec::Vec};
+
+type Point<E> = ShortWeierstrassProjectivePoint<E>;
+type FE<E> = FieldElement<<E as IsEllipticCurve>::BaseField>;
+/// Affine coordinates `(x, y)` of a point other than the point at infinity.
+type Affine<E> = (FE<E>, FE<E>);
+
+/// Computes the multiscalar multiplication (MSM) of points of a short Weierstrass curve
+/// with Pippenger's algorithm, accumulating the buckets in affine coordinates.
+///
+/// An affine addition costs a single multiplication and squaring on top of an inversion,
+/// against about a dozen multiplications for a projective one. The additions into different
+/// buckets are independent, so they are done in batches that share one inversion through
+/// Montgomery's trick, which makes the bucket accumulation several times cheaper.
+///
+/// Returns an error if `cs` and `points` have different lengths.
+pub fn msm<const NUM_LIMBS: usize, E>(
+    cs: &[UnsignedInteger<NUM_LIMBS>],
+    points: &[Point<E>],
+) -> Result<Point<E>, MSMError>
+where
+    E: IsShortWeierstrass,
+{
+    if cs.len() != points.len() {
+        return Err(MSMError::LengthMismatch(cs.len(), points.len()));
+    }
+
+    let window_size = optimum_window_size(max_bits(cs), cs.len());
+
+    Ok(msm_with(cs, points, window_size))
+}
+
+/// Same as [`msm`], with a given window size. Panics if `cs` and `points` have different lengths.
+pub fn msm_with<const NUM_LIMBS: usize, E>(
+    cs: &[UnsignedInteger<NUM_LIMBS>],
+    points: &[Point<E>],
+    window_size: usize,
+) -> Point<E>
+where
+    E: IsShortWeierstrass,
+{
+    assert_eq!(cs.len(), points.len());
+    let window_size = window_size.clamp(1, 32);
+
+    let num_windows = num_signed_windows(max_bits(cs), window_size);
+    let digits = signed_digits(cs, window_size, num_windows);
+    let points = to_affine_batch(points);
+    let n_buckets = 1 << (window_size - 1);
+
+    (0..num_windows)
+        .rev()
+        .map(|window_idx| {
+            let additions = points.iter().enumerate().filter_map(|(i, p)| {
+                bucket_addition::<E>(digits[i * num_windows + window_idx], p.as_ref()?)
+            });
+            reduce_buckets(accumulate_buckets::<E>(n_buckets, additions.collect()))
+        })
+        // NOTE: this operation is non-associative and strictly sequential
+        .reduce(|t, g| {
+            (0..window_size)
+                .fold(t, |acc, _| acc.double())
+                .operate_with(&g)
+        })
+        .unwrap_or_else(Point::neutral_element)
+}
+
+/// MSM over a fixed set of points, such as the powers of τ in a structured reference string.
+///
+/// For every point `P` and window `j`, the shifted point `2^(s * j) * P` is precomputed, `s`
+/// being the window size. The digits of all the windows can then go into a single set of
+/// buckets, so an MSM needs no doublings and a single bucket reduction. The table takes
+/// `ceil((num_bits + 1) / s)` times the memory of the points.
+pub struct FixedBaseMSM<E: IsShortWeierstrass> {
+    window_size: usize,
+    num_windows: usize,
+    num_bits: usize,
+    /// Shifted points, `num_windows` per point. `None` stands for the point at infinity.
+    shifted_points: Vec<Option<Affine<E>>>,
+}
+
+impl<E: IsShortWeierstrass> FixedBaseMSM<E> {
+    /// Precomputes the shifted points for scalars of up to `num_bits` bits.
+    pub fn new(points: &[Point<E>], num_bits: usize, window_size: usize) -> Self {
+        let window_size = window_size.clamp(1, 32);
+        let num_windows = num_signed_windows(num_bits, window_size);
+        let mut shifted = Vec::with_capacity(points.len() * num_windows);
+        for p in points {
+            let mut shifted_point = p.clone();
+            for _ in 0..num_windows {
+                let next = (0..window_size).fold(shifted_point.clone(), |acc, _| acc.double());
+                shifted.push(shifted_point);
+                shifted_point = next;
+            }
+        }
+        Self {
+            window_size,
+            num_windows,
+            num_bits,
+            shifted_points: to_affine_batch(&shifted),
+        }
+    }
+
+    /// Number of points of the MSM.
+    pub fn len(&self) -> usize {
+        self.shifted_points.len() / self.num_windows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shifted_points.is_empty()
+    }
+
+    /// Computes `k_1 * P_1 + ... + k_n * P_n` for the precomputed points `P_i`.
+    ///
+    /// Returns an error if there are more scalars than points. Missing scalars are taken as 0.
+    /// Panics if a scalar has more bits than the precomputation supports.
+    pub fn msm<const NUM_LIMBS: usize>(
+        &self,
+        cs: &[UnsignedInteger<NUM_LIMBS>],
+    ) -> Result<Point<E>, MSMError> {
+        if cs.len() > self.len() {
+            return Err(MSMError::LengthMismatch(cs.len(), self.len()));
+        }
+        assert!(
+            max_bits(cs) <= self.num_bits,
+            "the scalars have more bits than the precomputation supports"
+        );
+
+        let digits = signed_digits(cs, self.window_size, self.num_windows);
+        let additions = digits
+            .iter()
+            .zip(&self.shifted_points)
+            .filter_map(|(digit, p)| bucket_addition::<E>(*digit, p.as_ref()?))
+            .collect();
+        let n_buckets = 1 << (self.window_size - 1);
+        Ok(reduce_buckets(accumulate_buckets::<E>(
+            n_buckets, additions,
+        )))
+    }
+}
+
+/// Returns the bucket and the point to add to it for a signed digit, if any.
+fn bucket_addition<E: IsShortWeierstrass>(
+    digit: i64,
+    (x, y): &Affine<E>,
+) -> Option<(usize, Affine<E>)> {
+    match digit.signum() {
+        1 => Some(((digit - 1) as usize, (x.clone(), y.clone()))),
+        -1 => Some(((-digit - 1) as usize, (x.clone(), -y))),
+        _ => None,
+    }
+}
+
+/// Converts the points to affine coordinates with a single inversion.
+fn to_affine_batch<E: IsShortWeierstrass>(points: &[Point<E>]) -> Vec<Option<Affine<E>>> {
+    let mut z_inverses: Vec<_> = points
+        .iter()
+        .filter(|p| !p.is_neutral_element())
+        .map(|p| p.z().clone())
+        .collect();
+    FieldElement::inplace_batch_inverse(&mut z_inverses)
+        .expect("only the point at infinity has z = 0");
+    let mut z_inverses = z_inverses.into_iter();
+    points
+        .iter()
+        .map(|p| {
+            if p.is_neutral_element() {
+                None
+            } else {
+                let z_inverse = z_inverses.next()?;
+                Some((p.x() * &z_inverse, p.y() * z_inverse))
+            }
+        })
+        .collect()
+}
+
+/// Adds every point to its bucket in affine coordinates.
+///
+/// The points of each bucket are summed as a binary tree: every round adds disjoint pairs of
+/// points, across all the buckets at once, so that all the slopes of a round are computed
+/// with a single batch inversion. A bucket with `m` points is done after `ceil(log2(m))` rounds.
+fn accumulate_buckets<E: IsShortWeierstrass>(
+    n_buckets: usize,
+    additions: Vec<(usize, Affine<E>)>,
+) -> Vec<Option<Affine<E>>> {
+    // Sort the points by bucket. The points of bucket `b` start at `starts[b]`.
+    let mut starts = vec![0; n_buckets + 1];
+    for (idx, _) in &additions {
+        starts[idx + 1] += 1;
+    }
+    for idx in 0..n_buckets {
+        starts[idx + 1] += starts[idx];
+    }
+    let mut lens = vec![0; n_buckets];
+    let mut points: Vec<Option<Affine<E>>> = vec![None; additions.len()];
+    for (idx, p) in additions {
+        points[starts[idx] + lens[idx]] = Some(p);
+        lens[idx] += 1;
+    }
+
+    let mut denominators = Vec::new();
+    loop {
+        denominators.clear();
+        for idx in 0..n_buckets {
+            for i in (starts[idx]..starts[idx] + lens[idx] - lens[idx] % 2).step_by(2) {
+                denominators.push(slope_denominator::<E>(&points[i], &points[i + 1]));
+            }
+        }
+        if denominators.is_empty() {
+            break;
+        }
+        FieldElement::inplace_batch_inverse(&mut denominators).expect("denominators are nonzero");
+
+        // Replace the points of each bucket by the sums of consecutive pairs.
+        let mut inverses = denominators.drain(..);
+        for idx in 0..n_buckets {
+            let (start, len) = (starts[idx], lens[idx]);
+            for i in 0..len / 2 {
+                let (p, q) = (
+                    points[start + 2 * i].take(),
+                    points[start + 2 * i + 1].take(),
+                );
+                let inverse = inverses.next().expect("one inverse per pair");
+                points[start + i] = add_with_inverse::<E>(p, q, inverse);
+            }
+            if len % 2 == 1 {
+                points[start + len / 2] = points[start + len - 1].take();
+            }
+            lens[idx] = len.div_ceil(2);
+        }
+    }
+
+    (0..n_buckets)
+        .map(|idx| {
+            if lens[idx] == 0 {
+                None
+            } else {
+                points[starts[idx]].take()
+            }
+        })
+        .collect()
+}
+
+/// Returns the denominator of the slope of the line through `p` and `q`, or one if no slope
+/// is needed to add them.
+fn slope_denominator<E: IsShortWeierstrass>(p: &Option<Affine<E>>, q: &Option<Affine<E>>) -> FE<E> {
+    match (p, q) {
+        (Some((x1, y1)), Some((x2, y2))) => {
+            if x1 != x2 {
+                x2 - x1
+            } else if y1 == y2 && *y1 != FieldElement::zero() {
+                // Doubling: the slope is (3x^2 + a) / 2y.
+                y1 + y1
+            } else {
+                // The sum is the point at infinity.
+                FieldElement::one()
+            }
+        }
+        _ => FieldElement::one(),
+    }
+}
+
+/// Adds `p` and `q` given the inverse of [`slope_denominator`].
+fn add_with_inverse<E: IsShortWeierstrass>(
+    p: Option<Affine<E>>,
+    q: Option<Affine<E>>,
+    inverse: FE<E>,
+) -> Option<Affine<E>> {
+    let ((x1, y1), (x2, y2)) = match (p, q) {
+        (Some(p), Some(q)) => (p, q),
+        (p, None) => return p,
+        (None, q) => return q,
+    };
+    let slope = if x1 != x2 {
+        (&y2 - &y1) * inverse
+    } else if y1 == y2 && y1 != FieldElement::zero() {
+        let x1_square = x1.square();
+        (&x1_square + &x1_square + &x1_square + E::a()) * inverse
+    } else {
+        return None;
+    };
+    let x3 = slope.square() - &x1 - x2;
+    let y3 = slope * (x1 - &x3) - y1;
+    Some((x3, y3))
+}
+
+/// Returns `1 * b_1 + 2 * b_2 + ... + n * b_n` for the buckets `b_1, ..., b_n`.
+fn reduce_buckets<E: IsShortWeierstrass>(buckets: Vec<Option<Affine<E>>>) -> Point<E> {
+    let mut running_sum = Point::neutral_element();
+    let mut result = Point::neutral_element();
+    for bucket in buckets.into_iter().rev() {
+        if let Some((x, y)) = bucket {
+            running_sum = running_sum.operate_with_affine(&Point::new([x, y, FieldElement::one()]));
+        }
+        result = result.operate_with(&running_sum);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        elliptic_curve::short_weierstrass::curves::bls12_381::{
+            curve::BLS12381Curve, twist::BLS12381TwistCurve,
+        },
+        msm::naive,
+        unsigned_integer::element::U256,
+    };
+    use proptest::{collection, prelude::*};
+
+    prop_compose! {
+        fn points_and_scalars()(powers in collection::vec(any::<u64>(), 0..40))
+            (scalars in collection::vec(any::<[u64; 4]>(), powers.len()), powers in Just(powers))
+            -> (Vec<U256>, Vec<Point<BLS12381Curve>>) {
+            let g = BLS12381Curve::generator();
+            (
+                scalars.into_iter().map(U256::from_limbs).collect(),
+                powers.into_iter().map(|power| g.operate_with_self(power)).collect(),
+            )
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig { cases: 20, .. ProptestConfig::default() })]
+        #[test]
+        fn batch_affine_msm_matches_naive_msm(window_size in 1usize..10, (cs, points) in points_and_scalars()) {
+            let expected = naive::msm(&cs, &points).unwrap();
+            prop_assert_eq!(msm_with(&cs, &points, window_size), expected);
+        }
+
+        #[test]
+        fn fixed_base_msm_matches_naive_msm(window_size in 1usize..10, (cs, points) in points_and_scalars()) {
+            let expected = naive::msm(&cs, &points).unwrap();
+            let fixed_base = FixedBaseMSM::new(&points, 256, window_size);
+            prop_assert_eq!(fixed_base.msm(&cs).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn repeated_and_opposite_points_and_infinity() {
+        // Exercises the doubling and the point at infinity in the bucket accumulation.
+        let g = BLS12381Curve::generator();
+        let points = [
+            g.clone(),
+            g.clone(),
+            g.neg(),
+            Point::neutral_element(),
+            g.operate_with_self(5u64),
+            g.clone(),
+        ];
+        let cs = [3u64, 3, 3, 7, 1, 3].map(U256::from_u64);
+        let expected = naive::msm(&cs, &points).unwrap();
+        assert_eq!(msm(&cs, &points).unwrap(), expected);
+        assert_eq!(FixedBaseMSM::new(&points, 8, 2).msm(&cs).unwrap(), expected);
+    }
+
+    #[test]
+    fn msm_over_g2() {
+        let g = BLS12381TwistCurve::generator();
+        let points: Vec<_> = (1..30u64).map(|i| g.operate_with_self(i)).collect();
+        let cs: Vec<_> = (1..30u64)
+            .map(|i| U256::from_u64(i.wrapping_mul(0x9e3779b97f4a7c15)).const_shl(3 * i as usize))
+            .collect();
+        assert_eq!(
+            msm(&cs, &points).unwrap(),
+            naive::msm(&cs, &points).unwrap()
+        );
+    }
+
+    #[test]
+    fn fixed_base_msm_with_fewer_scalars_than_points() {
+        let g = BLS12381Curve::generator();
+        let points: Vec<_> = (1..10u64).map(|i| g.operate_with_self(i)).collect();
+        let cs = [U256::from_u64(2), U256::from_u64(3)];
+        let fixed_base = FixedBaseMSM::new(&points, 255, 4);
+        assert_eq!(
+            fixed_base.msm(&cs).unwrap(),
+            naive::msm(&cs, &points[..2]).unwrap()
+        );
+        assert!(fixed_base.msm(&[U256::from_u64(1); 10]).is_err());
+    }
+
+    #[test]
+    fn empty_msm_is_the_neutral_element() {
+        let points: [Point<BLS12381Curve>; 0] = [];
+        let cs: [U256; 0] = [];
+        assert_eq!(msm(&cs, &points).unwrap(), Point::neutral_element());
+        assert_eq!(
+            FixedBaseMSM::new(&points, 255, 4).msm(&cs).unwrap(),
+            Point::neutral_element()
+        );
+    }
+}
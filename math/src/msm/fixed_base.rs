@@ -0,0 +1,130 @@
+use crate::{cyclic_group::IsGroup, unsigned_integer::element::UnsignedInteger};
+
+use alloc::vec::Vec;
+
+/// Precomputed multiples of a fixed base, to speed up many scalar multiplications of the
+/// same point, such as the powers of τ times the generator computed by a trusted setup.
+///
+/// For a window size `w`, the table holds `j * 2^(w * i) * g` for every window `i` and every
+/// digit `1 <= j < 2^w`. A product `k * g` is then the sum of one entry per window of `k`,
+/// without any doubling.
+pub struct FixedBaseTable<G: IsGroup> {
+    num_bits: usize,
+    window_size: usize,
+    windows: Vec<Vec<G>>,
+}
+
+impl<G: IsGroup> FixedBaseTable<G> {
+    /// Builds the table of multiples of `base` for scalars of up to `num_bits` bits,
+    /// split in windows of `window_size` bits.
+    ///
+    /// Panics if `window_size` is 0 or not smaller than 64.
+    pub fn new(base: &G, num_bits: usize, window_size: usize) -> Self {
+        assert!(
+            window_size > 0 && window_size < u64::BITS as usize,
+            "window size must be between 1 and 63"
+        );
+        let num_windows = num_bits.div_ceil(window_size);
+        let mut windows = Vec::with_capacity(num_windows);
+        let mut window_base = base.clone();
+        for _ in 0..num_windows {
+            let mut multiples = Vec::with_capacity((1 << window_size) - 1);
+            let mut multiple = window_base.clone();
+            for _ in 1..(1u64 << window_size) {
+                multiples.push(multiple.clone());
+                multiple = multiple.operate_with(&window_base);
+            }
+            // After the loop `multiple` is 2^w times the base of this window.
+            window_base = multiple;
+            windows.push(multiples);
+        }
+        Self {
+            num_bits,
+            window_size,
+            windows,
+        }
+    }
+
+    /// Computes `k * base`.
+    ///
+    /// Panics if `k` has more bits than the table was built for.
+    pub fn mul<const NUM_LIMBS: usize>(&self, k: &UnsignedInteger<NUM_LIMBS>) -> G {
+        assert!(
+            k.bits_le() <= self.num_bits,
+            "the scalar has more bits than the table supports"
+        );
+        let mask = (1u64 << self.window_size) - 1;
+        self.windows
+            .iter()
+            .take(k.bits_le().div_ceil(self.window_size))
+            .enumerate()
+            .fold(G::neutral_element(), |acc, (i, multiples)| {
+                // We truncate the number to the least significative limb.
+                // This is ok because window_size < u64::BITS.
+                let digit = (k >> (i * self.window_size)).limbs[NUM_LIMBS - 1] & mask;
+                if digit == 0 {
+                    acc
+                } else {
+                    acc.operate_with(&multiples[(digit - 1) as usize])
+                }
+            })
+    }
+
+    /// Computes `k * base` for every `k` in `cs`.
+    pub fn batch_mul<const NUM_LIMBS: usize>(&self, cs: &[UnsignedInteger<NUM_LIMBS>]) -> Vec<G> {
+        cs.iter().map(|k| self.mul(k)).collect()
+    }
+}
+
+/// Returns a window size that minimizes the total number of group operations needed to build
+/// a [`FixedBaseTable`] for `num_bits` bit scalars and multiply `num_scalars` of them.
+pub fn optimum_window_size(num_bits: usize, num_scalars: usize) -> usize {
+    (1..=20)
+        .min_by_key(|&window_size| {
+            let num_windows = num_bits.div_ceil(window_size);
+            num_windows * ((1 << window_size) - 1) + num_scalars * num_windows
+        })
+        .unwrap_or(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        elliptic_curve::{
+            short_weierstrass::curves::bls12_381::curve::BLS12381Curve, traits::IsEllipticCurve,
+        },
+        unsigned_integer::element::U256,
+    };
+    use proptest::prelude::*;
+
+    proptest! {
+        #![proptest_config(ProptestConfig { cases: 10, .. ProptestConfig::default() })]
+        #[test]
+        fn fixed_base_mul_matches_operate_with_self(limbs: [u64; 4], window_size in 1usize..10) {
+            let g = BLS12381Curve::generator();
+            let table = FixedBaseTable::new(&g, 256, window_size);
+            let k = U256::from_limbs(limbs);
+            prop_assert!(table.mul(&k) == g.operate_with_self(k));
+        }
+    }
+
+    #[test]
+    fn batch_mul_matches_operate_with_self() {
+        let g = BLS12381Curve::generator();
+        let cs: Vec<U256> = (0..20u64)
+            .map(|i| U256::from_u64(i).const_shl(12 * i as usize))
+            .collect();
+        let table = FixedBaseTable::new(&g, 255, optimum_window_size(255, cs.len()));
+        for (result, k) in table.batch_mul(&cs).iter().zip(&cs) {
+            assert!(*result == g.operate_with_self(*k));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn mul_panics_for_scalars_larger_than_the_table() {
+        let table = FixedBaseTable::new(&BLS12381Curve::generator(), 8, 4);
+        table.mul(&U256::from_u64(256));
+    }
+}
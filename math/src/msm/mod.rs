@@ -1,3 +1,7 @@
+#[cfg(feature = "alloc")]
+pub mod batch_affine;
+#[cfg(feature = "alloc")]
+pub mod fixed_base;
 pub mod naive;
 #[cfg(feature = "alloc")]
 pub mod pippenger;
@@ -1,8 +1,12 @@
-use crate::{cyclic_group::IsGroup, unsigned_integer::element::UnsignedInteger};
+use crate::{
+    cyclic_group::IsGroup,
+    elliptic_curve::glv::IsGLVCurve,
+    unsigned_integer::element::{UnsignedInteger, U256},
+};
 
 use super::naive::MSMError;
 
-use alloc::vec;
+use alloc::{vec, vec::Vec};
 
 /// This function computes the multiscalar multiplication (MSM).
 ///
@@ -26,17 +30,138 @@ where
         return Err(MSMError::LengthMismatch(cs.len(), points.len()));
     }
 
-    let window_size = optimum_window_size(cs.len());
+    let window_size = optimum_window_size(max_bits(cs), cs.len());
 
     Ok(msm_with(cs, points, window_size))
 }
 
-fn optimum_window_size(data_length: usize) -> usize {
-    const SCALE_FACTORS: (usize, usize) = (4, 5);
+/// Computes the multiscalar multiplication (MSM) of points in the prime order subgroup of a
+/// curve with an efficient endomorphism.
+///
+/// Each scalar `k` is split as `k = k1 + k2 * λ` with halves of about half its bit length (see
+/// [`IsGLVCurve`]), so the MSM of `n` full size scalars becomes an MSM of `2n` half size
+/// scalars over the points and their images under the endomorphism. This halves the number
+/// of windows, and with it the number of bucket reductions and doublings.
+///
+/// On curves with a cofactor, the points must be in the prime order subgroup.
+///
+/// Returns an error if `cs` and `points` have different lengths.
+pub fn glv_msm<E>(
+    cs: &[U256],
+    points: &[E::PointRepresentation],
+) -> Result<E::PointRepresentation, MSMError>
+where
+    E: IsGLVCurve,
+{
+    if cs.len() != points.len() {
+        return Err(MSMError::LengthMismatch(cs.len(), points.len()));
+    }
 
-    // We approximate the optimum window size with: f(n) = k * log2(n), where k is a scaling factor
-    let len_isqrt = data_length.checked_ilog2().unwrap_or(0);
-    (len_isqrt as usize * SCALE_FACTORS.0) / SCALE_FACTORS.1
+    let (scalars, bases) = split_terms(cs, points, |k, p| E::split_multiplication(p, *k));
+    let window_size = optimum_window_size(max_bits(&scalars), scalars.len());
+
+    Ok(msm_with(&scalars, &bases, window_size))
+}
+
+/// Splits every term `k * p` of an MSM into the two terms returned by `split`. The MSM of `n`
+/// terms becomes one of `2n` terms with scalars of half the size.
+fn split_terms<K, G>(
+    cs: &[K],
+    points: &[G],
+    split: impl Fn(&K, &G) -> [(G, U256); 2],
+) -> (Vec<U256>, Vec<G>)
+where
+    G: IsGroup,
+{
+    let mut scalars = Vec::with_capacity(2 * cs.len());
+    let mut bases = Vec::with_capacity(2 * cs.len());
+    for (k, p) in cs.iter().zip(points) {
+        for (base, scalar) in split(k, p) {
+            bases.push(base);
+            scalars.push(scalar);
+        }
+    }
+    (scalars, bases)
+}
+
+/// Returns the window size that minimizes the estimated number of group operations of an MSM
+/// of `data_length` points with scalars of `num_bits` bits.
+///
+/// Each window costs one addition per point to fill the buckets and two per bucket to reduce
+/// them, and there are `2^(s - 1)` buckets for a window of size `s` with signed digits.
+pub(crate) fn optimum_window_size(num_bits: usize, data_length: usize) -> usize {
+    const MAX_WINDOW_SIZE: usize = 24;
+
+    (1..=MAX_WINDOW_SIZE)
+        .min_by_key(|&window_size| {
+            num_signed_windows(num_bits, window_size) * (data_length + (1 << window_size))
+        })
+        .unwrap_or(1)
+}
+
+/// Returns the bit length of the largest scalar in `cs`.
+pub(crate) fn max_bits<const NUM_LIMBS: usize>(cs: &[UnsignedInteger<NUM_LIMBS>]) -> usize {
+    cs.iter().map(|k| k.bits_le()).max().unwrap_or(0)
+}
+
+/// Number of windows of size `window_size` needed to write a `num_bits` bit scalar with the
+/// signed digits of [`signed_digits`]. The extra bit absorbs the carry of the top window.
+pub(crate) fn num_signed_windows(num_bits: usize, window_size: usize) -> usize {
+    (num_bits + 1).div_ceil(window_size)
+}
+
+/// Writes every scalar in base `2^s`, with `s = window_size`, using digits in
+/// `[-2^(s - 1), 2^(s - 1)]`, least significant first. The digits of the `i`-th scalar are
+/// `digits[i * num_windows..(i + 1) * num_windows]`.
+///
+/// A negative digit adds the negated point to the bucket of its absolute value, so signed
+/// digits need half as many buckets as unsigned ones.
+pub(crate) fn signed_digits<const NUM_LIMBS: usize>(
+    cs: &[UnsignedInteger<NUM_LIMBS>],
+    window_size: usize,
+    num_windows: usize,
+) -> Vec<i64> {
+    let radix = 1u64 << window_size;
+    let half_radix = radix >> 1;
+    let mut digits = Vec::with_capacity(cs.len() * num_windows);
+    for k in cs {
+        let mut carry = 0;
+        for window_idx in 0..num_windows {
+            let shift = window_idx * window_size;
+            let window = if shift < 64 * NUM_LIMBS {
+                // We truncate the number to the least significative limb.
+                // This is ok because window_size < u64::BITS.
+                (k >> shift).limbs[NUM_LIMBS - 1] & (radix - 1)
+            } else {
+                0
+            };
+            let digit = window + carry;
+            if digit > half_radix {
+                digits.push(digit as i64 - radix as i64);
+                carry = 1;
+            } else {
+                digits.push(digit as i64);
+                carry = 0;
+            }
+        }
+        debug_assert_eq!(carry, 0);
+    }
+    digits
+}
+
+/// Adds `p` to the bucket of `digit`, negating it when the digit is negative.
+fn add_to_bucket<G: IsGroup>(buckets: &mut [G], digit: i64, p: &G) {
+    match digit.signum() {
+        1 => {
+            let idx = (digit - 1) as usize;
+            buckets[idx] = buckets[idx].operate_with(p);
+        }
+        -1 => {
+            let idx = (-digit - 1) as usize;
+            buckets[idx] = buckets[idx].operate_with(&p.neg());
+        }
+        _ => {}
+    }
 }
 
 pub fn msm_with<const NUM_LIMBS: usize, G>(
@@ -53,8 +178,8 @@ where
 
     let window_size = window_size.clamp(MIN_WINDOW_SIZE, MAX_WINDOW_SIZE);
 
-    // The number of windows of size `s` is ceil(lambda/s).
-    let num_windows = (64 * NUM_LIMBS - 1) / window_size + 1;
+    let num_windows = num_signed_windows(max_bits(cs), window_size);
+    let digits = signed_digits(cs, window_size, num_windows);
 
     // We define `buckets` outside of the loop so we only have to allocate once, and reuse it.
     //
@@ -63,22 +188,15 @@ where
     // If we accept a const window_size, we could make it an array instaed of a vector
     // avoiding the heap allocation. We should be aware if that might be too agressive for
     // the stack and cause a potential stack overflow.
-    let n_buckets = (1 << window_size) - 1;
+    let n_buckets = 1 << (window_size - 1);
     let mut buckets = vec![G::neutral_element(); n_buckets];
 
     (0..num_windows)
         .rev()
         .map(|window_idx| {
             // Put in the right bucket the corresponding ps[i] for the current window.
-            cs.iter().zip(points).for_each(|(k, p)| {
-                // We truncate the number to the least significative limb.
-                // This is ok because window_size < usize::BITS.
-                let window_unmasked = (k >> (window_idx * window_size)).limbs[NUM_LIMBS - 1];
-                let m_ij = window_unmasked & n_buckets as u64;
-                if m_ij != 0 {
-                    let idx = (m_ij - 1) as usize;
-                    buckets[idx] = buckets[idx].operate_with(p);
-                }
+            points.iter().enumerate().for_each(|(i, p)| {
+                add_to_bucket(&mut buckets, digits[i * num_windows + window_idx], p);
             });
 
             // Do the reduction step for the buckets.
@@ -116,11 +234,11 @@ where
 {
     use rayon::prelude::*;
 
-    assert!(window_size < usize::BITS as usize); // Program would go OOM anyways
+    assert!(window_size > 0 && window_size < usize::BITS as usize); // Program would go OOM anyways
 
-    // The number of windows of size `s` is ceil(lambda/s).
-    let num_windows = (64 * NUM_LIMBS - 1) / window_size + 1;
-    let n_buckets = (1 << window_size) - 1;
+    let num_windows = num_signed_windows(max_bits(cs), window_size);
+    let digits = signed_digits(cs, window_size, num_windows);
+    let n_buckets = 1 << (window_size - 1);
 
     // TODO: limit the number of threads, and reuse vecs
     (0..num_windows)
@@ -128,16 +246,8 @@ where
         .map(|window_idx| {
             let mut buckets = vec![G::neutral_element(); n_buckets];
             // Put in the right bucket the corresponding ps[i] for the current window.
-            let shift = window_idx * window_size;
-            cs.iter().zip(points).for_each(|(k, p)| {
-                // We truncate the number to the least significative limb.
-                // This is ok because window_size < usize::BITS.
-                let window_unmasked = (k >> shift).limbs[NUM_LIMBS - 1];
-                let m_ij = window_unmasked & n_buckets as u64;
-                if m_ij != 0 {
-                    let idx = (m_ij - 1) as usize;
-                    buckets[idx] = buckets[idx].operate_with(p);
-                }
+            points.iter().enumerate().for_each(|(i, p)| {
+                add_to_bucket(&mut buckets, digits[i * num_windows + window_idx], p);
             });
 
             let mut m = G::neutral_element();
@@ -155,13 +265,15 @@ where
                 .reduce(|g, m| g.operate_with(&m))
                 .unwrap_or_else(G::neutral_element);
 
-            window_item.operate_with_self(UnsignedInteger::<NUM_LIMBS>::from_u64(1) << shift)
+            let shift = window_idx * window_size;
+            (0..shift).fold(window_item, |acc, _| acc.operate_with(&acc))
         })
         .reduce(G::neutral_element, |a, b| a.operate_with(&b))
 }
 
 #[cfg(test)]
 mod tests {
+    use super::{num_signed_windows, signed_digits};
     use crate::cyclic_group::IsGroup;
     use crate::msm::{naive, pippenger};
     use crate::{
@@ -218,6 +330,41 @@ mod tests {
             prop_assert_eq!(naive, pippenger);
         }
 
+        // Property-based test that ensures the signed digits recompose to the original scalar.
+        #[test]
+        fn test_signed_digits_recompose(window_size in 1usize..20, k in unsigned_integer()) {
+            let num_windows = num_signed_windows(k.bits_le(), window_size);
+            let digits = signed_digits(&[k], window_size, num_windows);
+            let half_radix = 1i64 << (window_size - 1);
+
+            let (mut positive, mut negative) = (UnsignedInteger::<7>::from_u64(0), UnsignedInteger::<7>::from_u64(0));
+            for (i, digit) in digits.iter().enumerate() {
+                prop_assert!(-half_radix <= *digit && *digit <= half_radix);
+                let term = UnsignedInteger::<7>::from_u64(digit.unsigned_abs()) << (i * window_size);
+                if *digit > 0 {
+                    positive = positive + term;
+                } else {
+                    negative = negative + term;
+                }
+            }
+            let mut limbs = [0; 7];
+            limbs[1..].copy_from_slice(&k.limbs);
+            prop_assert_eq!(positive - negative, UnsignedInteger::from_limbs(limbs));
+        }
+
+        // Property-based test that ensures `pippenger::glv_msm` gives same result as `naive::msm`.
+        #[test]
+        fn test_glv_msm_matches_naive_msm(cs in collection::vec(any::<[u64; 4]>(), 0.._MAX_LEN), points in points_vec()) {
+            let min_len = cs.len().min(points.len());
+            let cs: Vec<_> = cs[..min_len].iter().map(|limbs| UnsignedInteger::from_limbs(*limbs)).collect();
+            let points = points[..min_len].to_vec();
+
+            let glv = pippenger::glv_msm::<BLS12381Curve>(&cs, &points).unwrap();
+            let naive = naive::msm(&cs, &points).unwrap();
+
+            prop_assert_eq!(naive, glv);
+        }
+
         // Property-based test that ensures `pippenger::msm_with` gives same result as `pippenger::parallel_msm_with`.
         #[test]
         #[cfg(feature = "parallel")]
@@ -1,4 +1,5 @@
 use super::field::element::FieldElement;
+use crate::fft::errors::FFTError;
 use crate::field::traits::{IsField, IsSubFieldOf};
 use alloc::{borrow::ToOwned, vec, vec::Vec};
 use core::{fmt::Display, ops};
@@ -6,6 +7,15 @@ use core::{fmt::Display, ops};
 pub mod dense_multilinear_poly;
 mod error;
 pub mod sparse_multilinear_poly;
+pub mod subproduct_tree;
+
+/// Factors with fewer coefficients than this are multiplied by [Polynomial::mul_with_ref] with
+/// the schoolbook method, which is faster than Karatsuba's for them.
+const KARATSUBA_MIN_LENGTH: usize = 32;
+
+/// [Polynomial::interpolate] interpolates at least this many points with a
+/// [subproduct_tree::SubproductTree], and fewer points with Lagrange polynomials.
+const SUBPRODUCT_TREE_MIN_POINTS: usize = 32;
 
 /// Represents the polynomial c_0 + c_1 * X + c_2 * X^2 + ... + c_n * X^n
 /// as a vector of coefficients `[c_0, c_1, ... , c_n]`
@@ -54,6 +64,11 @@ impl<F: IsField> Polynomial<FieldElement<F>> {
         if xs.is_empty() {
             return Ok(Polynomial::new(&[]));
         }
+        if xs.len() >= SUBPRODUCT_TREE_MIN_POINTS {
+            let multiply = |a: &Self, b: &Self| Ok(a.mul_with_ref(b));
+            return subproduct_tree::SubproductTree::new_with(xs, &multiply)?
+                .interpolate_with(ys, &multiply);
+        }
 
         let mut denominators = Vec::with_capacity(xs.len() * (xs.len() - 1) / 2);
         let mut indexes = Vec::with_capacity(xs.len());
@@ -176,19 +191,20 @@ impl<F: IsField> Polynomial<FieldElement<F>> {
         if dividend.degree() > self.degree() {
             (Polynomial::zero(), self)
         } else {
-            let mut n = self;
-            let mut q: Vec<FieldElement<F>> = vec![FieldElement::zero(); n.degree() + 1];
             let denominator = dividend.leading_coefficient().inv().unwrap();
-            while n != Polynomial::zero() && n.degree() >= dividend.degree() {
-                let new_coefficient = n.leading_coefficient() * &denominator;
-                q[n.degree() - dividend.degree()] = new_coefficient.clone();
-                let d = dividend.mul_with_ref(&Polynomial::new_monomial(
-                    new_coefficient,
-                    n.degree() - dividend.degree(),
-                ));
-                n = n - d;
+            let mut n = self.coefficients;
+            let mut q: Vec<FieldElement<F>> =
+                vec![FieldElement::zero(); n.len().saturating_sub(dividend.degree())];
+            for i in (0..q.len()).rev() {
+                let new_coefficient = &n[i + dividend.degree()] * &denominator;
+                for (n_coefficient, d_coefficient) in n[i..].iter_mut().zip(&dividend.coefficients)
+                {
+                    *n_coefficient = &*n_coefficient - &new_coefficient * d_coefficient;
+                }
+                q[i] = new_coefficient;
             }
-            (Polynomial::new(&q), n)
+            n.truncate(dividend.degree());
+            (Polynomial::new(&q), Polynomial::new(&n))
         }
     }
 
@@ -197,22 +213,29 @@ impl<F: IsField> Polynomial<FieldElement<F>> {
         quotient
     }
 
+    /// Multiplies `self` and `factor` with the schoolbook method, or with Karatsuba's when both
+    /// have at least `KARATSUBA_MIN_LENGTH` coefficients. For fields with FFTs,
+    /// [Self::fast_multiplication] is faster still for large polynomials.
     pub fn mul_with_ref(&self, factor: &Self) -> Self {
-        let degree = self.degree() + factor.degree();
-        let mut coefficients = vec![FieldElement::zero(); degree + 1];
-
         if self.coefficients.is_empty() || factor.coefficients.is_empty() {
             Polynomial::new(&[FieldElement::zero()])
         } else {
-            for i in 0..=factor.degree() {
-                for j in 0..=self.degree() {
-                    coefficients[i + j] += &factor.coefficients[i] * &self.coefficients[j];
-                }
-            }
-            Polynomial::new(&coefficients)
+            Polynomial::new(&mul_coefficients(&self.coefficients, &factor.coefficients))
         }
     }
 
+    /// Returns the formal derivative of the polynomial.
+    pub fn differentiate(&self) -> Self {
+        let coefficients: Vec<_> = self
+            .coefficients
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, coeff)| FieldElement::<F>::from(i as u64) * coeff)
+            .collect();
+        Polynomial::new(&coefficients)
+    }
+
     pub fn scale<S: IsSubFieldOf<F>>(&self, factor: &FieldElement<S>) -> Self {
         let scaled_coefficients = self
             .coefficients
@@ -272,6 +295,74 @@ impl<F: IsField> Polynomial<FieldElement<F>> {
     }
 }
 
+/// Returns the coefficients of the product of the nonzero polynomials with coefficients `a` and
+/// `b`. Factors of equal length are split in halves `low + X^half * high`, and the product is
+/// obtained from the three products `low * low`, `high * high` and `(low + high) * (low + high)`.
+fn mul_coefficients<F: IsField>(
+    a: &[FieldElement<F>],
+    b: &[FieldElement<F>],
+) -> Vec<FieldElement<F>> {
+    let (a, b) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    let mut product = vec![FieldElement::zero(); a.len() + b.len() - 1];
+    if a.len() < KARATSUBA_MIN_LENGTH {
+        for (i, a_i) in a.iter().enumerate() {
+            for (j, b_j) in b.iter().enumerate() {
+                product[i + j] += a_i * b_j;
+            }
+        }
+    } else if a.len() < b.len() {
+        // The longer factor is multiplied in blocks as long as the shorter one.
+        for (k, block) in b.chunks(a.len()).enumerate() {
+            let block_product = mul_coefficients(a, block);
+            for (p, c) in product[k * a.len()..].iter_mut().zip(block_product) {
+                *p += c;
+            }
+        }
+    } else {
+        let half = a.len() / 2;
+        let (a_low, a_high) = a.split_at(half);
+        let (b_low, b_high) = b.split_at(half);
+        let low = mul_coefficients(a_low, b_low);
+        let high = mul_coefficients(a_high, b_high);
+        let mut middle = mul_coefficients(
+            &add_coefficients(a_low, a_high),
+            &add_coefficients(b_low, b_high),
+        );
+        for (m, c) in middle.iter_mut().zip(&low) {
+            *m = &*m - c;
+        }
+        for (m, c) in middle.iter_mut().zip(&high) {
+            *m = &*m - c;
+        }
+
+        for (p, c) in product.iter_mut().zip(low) {
+            *p += c;
+        }
+        for (p, c) in product[half..].iter_mut().zip(middle) {
+            *p += c;
+        }
+        for (p, c) in product[2 * half..].iter_mut().zip(high) {
+            *p += c;
+        }
+    }
+    product
+}
+
+/// Returns the coefficients of the sum of the polynomials with coefficients `low` and `high`,
+/// where `high` is at least as long as `low`.
+fn add_coefficients<F: IsField>(
+    low: &[FieldElement<F>],
+    high: &[FieldElement<F>],
+) -> Vec<FieldElement<F>> {
+    high.iter()
+        .enumerate()
+        .map(|(i, h)| match low.get(i) {
+            Some(l) => l + h,
+            None => h.clone(),
+        })
+        .collect()
+}
+
 pub fn pad_with_zero_coefficients_to_length<F: IsField>(
     pa: &mut Polynomial<FieldElement<F>>,
     n: usize,
@@ -788,6 +879,7 @@ where
 pub enum InterpolateError {
     UnequalLengths(usize, usize),
     NonUniqueXs,
+    FFTError(FFTError),
 }
 
 impl Display for InterpolateError {
@@ -797,6 +889,7 @@ impl Display for InterpolateError {
                 write!(f, "xs and ys must be the same length. Got: {x} != {y}")
             }
             InterpolateError::NonUniqueXs => write!(f, "xs values should be unique."),
+            InterpolateError::FFTError(e) => write!(f, "FFT error: {e}"),
         }
     }
 }
@@ -804,6 +897,12 @@ impl Display for InterpolateError {
 #[cfg(feature = "std")]
 impl std::error::Error for InterpolateError {}
 
+impl From<FFTError> for InterpolateError {
+    fn from(error: FFTError) -> Self {
+        Self::FFTError(error)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::field::fields::u64_prime_field::U64PrimeField;
@@ -1122,4 +1221,42 @@ mod tests {
             prop_assert_eq!(q, p);
         }
     }
+
+    proptest! {
+        // Property-based test that ensures Karatsuba's multiplication, used for large factors,
+        // gives the same result as the schoolbook one.
+        #[test]
+        fn mul_with_ref_matches_schoolbook(p in proptest::collection::vec(any::<u64>(), 1..200), q in proptest::collection::vec(any::<u64>(), 1..200)) {
+            type G = U64PrimeField<0xFFFF_FFFF_0000_0001>;
+            let p: Vec<FieldElement<G>> = p.into_iter().map(FieldElement::from).collect();
+            let q: Vec<FieldElement<G>> = q.into_iter().map(FieldElement::from).collect();
+            let mut expected = vec![FieldElement::<G>::zero(); p.len() + q.len() - 1];
+            for (i, p_i) in p.iter().enumerate() {
+                for (j, q_j) in q.iter().enumerate() {
+                    expected[i + j] += p_i * q_j;
+                }
+            }
+
+            let product = Polynomial::new(&p).mul_with_ref(&Polynomial::new(&q));
+            prop_assert_eq!(product, Polynomial::new(&expected));
+        }
+    }
+
+    #[test]
+    fn interpolate_many_points() {
+        type G = U64PrimeField<65537>;
+        let xs: Vec<FieldElement<G>> = (0..100u64).map(|i| FieldElement::from(i * i + 3)).collect();
+        let ys: Vec<FieldElement<G>> = (0..100u64).map(|i| FieldElement::from(7 * i + 1)).collect();
+
+        let p = Polynomial::interpolate(&xs, &ys).unwrap();
+        assert!(p.degree() < xs.len());
+        assert_eq!(p.evaluate_slice(&xs), ys);
+
+        let mut repeated = xs.clone();
+        repeated[50] = repeated[10].clone();
+        assert!(matches!(
+            Polynomial::interpolate(&repeated, &ys),
+            Err(InterpolateError::NonUniqueXs)
+        ));
+    }
 }
@@ -0,0 +1,299 @@
+use super::{InterpolateError, Polynomial};
+use crate::{
+    fft::errors::FFTError,
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField, IsSubFieldOf},
+    },
+};
+use alloc::{vec, vec::Vec};
+
+/// Once the remainders cover at most `2^DIRECT_EVALUATION_LEVEL` points, they are evaluated at
+/// each point with Horner's rule, which is faster than dividing further.
+const DIRECT_EVALUATION_LEVEL: usize = 5;
+
+/// Subproduct tree of the points `x_0, ..., x_{n-1}`: a binary tree with the polynomials
+/// `X - x_i` as leaves, where every other node is the product of its children. The root is the
+/// vanishing polynomial of the points.
+///
+/// Both multipoint evaluation and interpolation walk the tree, dividing or multiplying with FFTs
+/// over a subfield F of E, so that they take `O(n log^2 n)` operations instead of `O(n^2)`.
+/// [Polynomial::interpolate] also uses the tree for many points over any field, multiplying with
+/// [Polynomial::mul_with_ref] instead of FFTs.
+pub struct SubproductTree<E: IsField> {
+    points: Vec<FieldElement<E>>,
+    /// `levels[0]` holds the leaves and `levels[k + 1][i]` is the product of `levels[k][2 * i]`
+    /// and `levels[k][2 * i + 1]`, or `levels[k][2 * i]` itself if it has no sibling. The last
+    /// level holds only the root. Without points, the only level holds the constant one.
+    levels: Vec<Vec<Polynomial<FieldElement<E>>>>,
+}
+
+impl<E: IsField> SubproductTree<E> {
+    /// Builds the subproduct tree of `points`.
+    pub fn new<F: IsFFTField + IsSubFieldOf<E>>(
+        points: &[FieldElement<E>],
+    ) -> Result<Self, FFTError> {
+        Self::new_with(points, &|a, b| a.fast_multiplication::<F>(b))
+    }
+
+    /// [Self::new], with the products computed by `multiply`.
+    pub(crate) fn new_with(
+        points: &[FieldElement<E>],
+        multiply: &impl Fn(
+            &Polynomial<FieldElement<E>>,
+            &Polynomial<FieldElement<E>>,
+        ) -> Result<Polynomial<FieldElement<E>>, FFTError>,
+    ) -> Result<Self, FFTError> {
+        if points.is_empty() {
+            return Ok(Self {
+                points: Vec::new(),
+                levels: vec![vec![Polynomial::new(&[FieldElement::one()])]],
+            });
+        }
+
+        let leaves: Vec<_> = points
+            .iter()
+            .map(|x| Polynomial::new(&[-x, FieldElement::one()]))
+            .collect();
+        let mut levels = vec![leaves];
+        while levels[levels.len() - 1].len() > 1 {
+            let next_level = levels[levels.len() - 1]
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => multiply(left, right),
+                    _ => Ok(pair[0].clone()),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            levels.push(next_level);
+        }
+
+        Ok(Self {
+            points: points.to_vec(),
+            levels,
+        })
+    }
+
+    pub fn points(&self) -> &[FieldElement<E>] {
+        &self.points
+    }
+
+    /// Returns the product of `X - x_i` over all the points `x_i`.
+    pub fn vanishing_polynomial(&self) -> &Polynomial<FieldElement<E>> {
+        &self.levels[self.levels.len() - 1][0]
+    }
+
+    /// Returns the evaluations of `poly` at the points of the tree, in the same order. `poly`
+    /// is reduced modulo the root of the tree, and each remainder is then reduced modulo the
+    /// children of its node, so that the remainders at the leaves are the evaluations.
+    pub fn evaluate<F: IsFFTField + IsSubFieldOf<E>>(
+        &self,
+        poly: &Polynomial<FieldElement<E>>,
+    ) -> Result<Vec<FieldElement<E>>, FFTError> {
+        self.evaluate_with(poly, &|a, b| a.fast_multiplication::<F>(b))
+    }
+
+    /// [Self::evaluate], with the products computed by `multiply`.
+    pub(crate) fn evaluate_with(
+        &self,
+        poly: &Polynomial<FieldElement<E>>,
+        multiply: &impl Fn(
+            &Polynomial<FieldElement<E>>,
+            &Polynomial<FieldElement<E>>,
+        ) -> Result<Polynomial<FieldElement<E>>, FFTError>,
+    ) -> Result<Vec<FieldElement<E>>, FFTError> {
+        if self.points.is_empty() {
+            return Ok(Vec::new());
+        }
+        let top_level = self.levels.len() - 1;
+        let last_level = DIRECT_EVALUATION_LEVEL.min(top_level);
+
+        let (_, remainder) = poly.fast_division_with(self.vanishing_polynomial(), multiply)?;
+        let mut remainders = vec![remainder];
+        for level in (last_level..top_level).rev() {
+            remainders = self.levels[level]
+                .iter()
+                .enumerate()
+                .map(|(i, node)| {
+                    let (_, remainder) = remainders[i / 2].fast_division_with(node, multiply)?;
+                    Ok(remainder)
+                })
+                .collect::<Result<Vec<_>, FFTError>>()?;
+        }
+
+        // The node `i` of a level `k` covers the points from `i * 2^k` on.
+        Ok(self
+            .points
+            .chunks(1 << last_level)
+            .zip(&remainders)
+            .flat_map(|(points, remainder)| points.iter().map(|x| remainder.evaluate(x)))
+            .collect())
+    }
+
+    /// Returns the polynomial of degree less than the number of points that takes the values
+    /// `ys` at the points of the tree. With `M` the vanishing polynomial of the points, it is
+    /// `sum_i ys[i] / M'(x_i) * M(X) / (X - x_i)`, whose terms are added up the tree.
+    pub fn interpolate<F: IsFFTField + IsSubFieldOf<E>>(
+        &self,
+        ys: &[FieldElement<E>],
+    ) -> Result<Polynomial<FieldElement<E>>, InterpolateError> {
+        self.interpolate_with(ys, &|a, b| a.fast_multiplication::<F>(b))
+    }
+
+    /// [Self::interpolate], with the products computed by `multiply`.
+    pub(crate) fn interpolate_with(
+        &self,
+        ys: &[FieldElement<E>],
+        multiply: &impl Fn(
+            &Polynomial<FieldElement<E>>,
+            &Polynomial<FieldElement<E>>,
+        ) -> Result<Polynomial<FieldElement<E>>, FFTError>,
+    ) -> Result<Polynomial<FieldElement<E>>, InterpolateError> {
+        if self.points.len() != ys.len() {
+            return Err(InterpolateError::UnequalLengths(
+                self.points.len(),
+                ys.len(),
+            ));
+        }
+        if ys.is_empty() {
+            return Ok(Polynomial::zero());
+        }
+
+        // M'(x_i) is zero exactly when x_i is a repeated point.
+        let mut weights =
+            self.evaluate_with(&self.vanishing_polynomial().differentiate(), multiply)?;
+        FieldElement::inplace_batch_inverse(&mut weights)
+            .map_err(|_| InterpolateError::NonUniqueXs)?;
+
+        let mut polys: Vec<_> = weights
+            .iter()
+            .zip(ys)
+            .map(|(weight, y)| Polynomial::new(&[weight * y]))
+            .collect();
+        for level in &self.levels[..self.levels.len() - 1] {
+            polys = polys
+                .chunks(2)
+                .zip(level.chunks(2))
+                .map(|(polys, nodes)| match (polys, nodes) {
+                    ([left, right], [left_node, right_node]) => {
+                        Ok(multiply(left, right_node)? + multiply(right, left_node)?)
+                    }
+                    _ => Ok(polys[0].clone()),
+                })
+                .collect::<Result<Vec<_>, FFTError>>()?;
+        }
+        Ok(polys.swap_remove(0))
+    }
+}
+
+impl<E: IsField> Polynomial<FieldElement<E>> {
+    /// Returns the evaluations of this polynomial at `points`, using a [SubproductTree] over a
+    /// subfield F of E. The points need not be roots of unity.
+    pub fn fast_multipoint_evaluate<F: IsFFTField + IsSubFieldOf<E>>(
+        &self,
+        points: &[FieldElement<E>],
+    ) -> Result<Vec<FieldElement<E>>, FFTError> {
+        SubproductTree::new::<F>(points)?.evaluate::<F>(self)
+    }
+
+    /// Returns the polynomial that interpolates the points with x coordinates `xs` and y
+    /// coordinates `ys`, as [Polynomial::interpolate] does, using a [SubproductTree] over a
+    /// subfield F of E.
+    pub fn fast_interpolate<F: IsFFTField + IsSubFieldOf<E>>(
+        xs: &[FieldElement<E>],
+        ys: &[FieldElement<E>],
+    ) -> Result<Self, InterpolateError> {
+        if xs.len() != ys.len() {
+            return Err(InterpolateError::UnequalLengths(xs.len(), ys.len()));
+        }
+        SubproductTree::new::<F>(xs)?.interpolate::<F>(ys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::{
+        fields::u64_goldilocks_field::{Goldilocks64ExtensionField, Goldilocks64Field},
+        test_fields::u64_test_field::U64TestField,
+    };
+    use proptest::{collection, prelude::*};
+
+    type F = U64TestField;
+    type FE = FieldElement<F>;
+
+    prop_compose! {
+        fn field_vec(max_len: usize)(vec in collection::vec(any::<u64>().prop_map(FE::from), 0..max_len)) -> Vec<FE> {
+            vec
+        }
+    }
+
+    prop_compose! {
+        fn distinct_points(max_len: usize)(vec in collection::hash_set(any::<u64>(), 0..max_len)) -> Vec<FE> {
+            vec.into_iter().map(FE::from).collect()
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_fast_multipoint_evaluate_matches_evaluate(coeffs in field_vec(200), points in field_vec(100)) {
+            let poly = Polynomial::new(&coeffs);
+            prop_assert_eq!(
+                poly.fast_multipoint_evaluate::<F>(&points).unwrap(),
+                poly.evaluate_slice(&points)
+            );
+        }
+
+        #[test]
+        fn test_fast_interpolate_matches_interpolate(xs in distinct_points(80)) {
+            let ys: Vec<FE> = xs.iter().map(|x| x * x + FE::from(3)).collect();
+            prop_assert_eq!(
+                Polynomial::fast_interpolate::<F>(&xs, &ys).unwrap(),
+                Polynomial::interpolate(&xs, &ys).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn vanishing_polynomial_vanishes_at_the_points() {
+        let points: Vec<FE> = (1..=37u64).map(|i| FE::from(i * i)).collect();
+        let tree = SubproductTree::new::<F>(&points).unwrap();
+
+        assert_eq!(tree.vanishing_polynomial().degree(), points.len());
+        assert!(points
+            .iter()
+            .all(|x| tree.vanishing_polynomial().evaluate(x) == FE::zero()));
+    }
+
+    #[test]
+    fn fast_interpolate_rejects_repeated_points() {
+        let xs = [FE::from(1), FE::from(2), FE::from(1)];
+        let ys = [FE::from(4), FE::from(5), FE::from(6)];
+        assert!(matches!(
+            Polynomial::fast_interpolate::<F>(&xs, &ys),
+            Err(InterpolateError::NonUniqueXs)
+        ));
+        assert!(matches!(
+            Polynomial::fast_interpolate::<F>(&xs, &ys[..2]),
+            Err(InterpolateError::UnequalLengths(3, 2))
+        ));
+    }
+
+    #[test]
+    fn interpolate_and_evaluate_in_field_extension() {
+        type E = Goldilocks64ExtensionField;
+        let xs: Vec<FieldElement<E>> = (0..300u64)
+            .map(|i| FieldElement::new([FieldElement::from(i), FieldElement::from(7 * i + 1)]))
+            .collect();
+        let ys: Vec<FieldElement<E>> = (0..300u64)
+            .map(|i| FieldElement::new([FieldElement::from(i * i), FieldElement::from(i + 2)]))
+            .collect();
+
+        let poly = Polynomial::fast_interpolate::<Goldilocks64Field>(&xs, &ys).unwrap();
+        assert!(poly.degree() < xs.len());
+        assert_eq!(
+            poly.fast_multipoint_evaluate::<Goldilocks64Field>(&xs)
+                .unwrap(),
+            ys
+        );
+    }
+}
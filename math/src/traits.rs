@@ -49,6 +49,30 @@ impl AsBytes for u64 {
     }
 }
 
+/// Values that can be swapped depending on a secret bit, masking their words instead of
+/// branching on the bit so that it doesn't change the operations done.
+pub trait ConditionalSwap {
+    /// Swaps `self` and `other` when `swap` is 1 and leaves them as they are when it is 0.
+    fn conditional_swap(&mut self, other: &mut Self, swap: u64);
+}
+
+impl ConditionalSwap for u64 {
+    fn conditional_swap(&mut self, other: &mut Self, swap: u64) {
+        let mask = core::hint::black_box(0u64.wrapping_sub(swap));
+        let t = mask & (*self ^ *other);
+        *self ^= t;
+        *other ^= t;
+    }
+}
+
+impl<T: ConditionalSwap, const N: usize> ConditionalSwap for [T; N] {
+    fn conditional_swap(&mut self, other: &mut Self, swap: u64) {
+        for (a, b) in self.iter_mut().zip(other.iter_mut()) {
+            a.conditional_swap(b, swap);
+        }
+    }
+}
+
 /// Deserialize function without args
 pub trait Deserializable {
     fn deserialize(bytes: &[u8]) -> Result<Self, DeserializationError>
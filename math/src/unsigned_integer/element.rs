@@ -17,6 +17,7 @@ use crate::errors::CreationError;
 #[cfg(feature = "alloc")]
 use crate::traits::AsBytes;
 use crate::traits::ByteConversion;
+use crate::traits::ConditionalSwap;
 use crate::unsigned_integer::traits::IsUnsignedInteger;
 
 use core::fmt::{self, Debug, Display};
@@ -67,6 +68,12 @@ impl<const NUM_LIMBS: usize> Ord for UnsignedInteger<NUM_LIMBS> {
     }
 }
 
+impl<const NUM_LIMBS: usize> ConditionalSwap for UnsignedInteger<NUM_LIMBS> {
+    fn conditional_swap(&mut self, other: &mut Self, swap: u64) {
+        self.limbs.conditional_swap(&mut other.limbs, swap);
+    }
+}
+
 impl<const NUM_LIMBS: usize> From<u128> for UnsignedInteger<NUM_LIMBS> {
     fn from(value: u128) -> Self {
         let mut limbs = [0u64; NUM_LIMBS];
@@ -0,0 +1,13 @@
+%builtins output bitwise
+
+from starkware.cairo.common.bitwise import bitwise_operations
+from starkware.cairo.common.cairo_builtins import BitwiseBuiltin
+from starkware.cairo.common.serialize import serialize_word
+
+func main{output_ptr: felt*, bitwise_ptr: BitwiseBuiltin*}() {
+    let (and, xor, or) = bitwise_operations(0xf0f0, 0x0ff0);
+    serialize_word(and);
+    serialize_word(xor);
+    serialize_word(or);
+    return ();
+}
@@ -0,0 +1,16 @@
+%builtins output pedersen
+
+from starkware.cairo.common.cairo_builtins import HashBuiltin
+from starkware.cairo.common.hash import hash2
+from starkware.cairo.common.serialize import serialize_word
+
+func main{output_ptr: felt*, pedersen_ptr: HashBuiltin*}() {
+    let (hash) = hash2{hash_ptr=pedersen_ptr}(0x1234, 0x5678);
+    serialize_word(hash);
+
+    // The inputs are canonical field elements with the bit 251 set.
+    let (hash) = hash2{hash_ptr=pedersen_ptr}(2 ** 251, 2 ** 251 + 17 * 2 ** 192);
+    serialize_word(hash);
+
+    return ();
+}
@@ -0,0 +1,11 @@
+%builtins output poseidon
+
+from starkware.cairo.common.builtin_poseidon.poseidon import poseidon_hash
+from starkware.cairo.common.cairo_builtins import PoseidonBuiltin
+from starkware.cairo.common.serialize import serialize_word
+
+func main{output_ptr: felt*, poseidon_ptr: PoseidonBuiltin*}() {
+    let (hash) = poseidon_hash(1, 2);
+    serialize_word(hash);
+    return ();
+}
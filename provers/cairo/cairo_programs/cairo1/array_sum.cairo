@@ -0,0 +1,11 @@
+fn main(values: Array<u32>, offset: u32) -> u32 {
+    let mut values = values;
+    let mut sum = offset;
+    loop {
+        match values.pop_front() {
+            Option::Some(value) => { sum += value; },
+            Option::None => { break; },
+        };
+    };
+    sum
+}
@@ -0,0 +1,169 @@
+#ifndef CAIRO_PLATINUM_FFI_H
+#define CAIRO_PLATINUM_FFI_H
+
+/* Generated with cbindgen from src/lib.rs. Do not edit it, run `make header` instead. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Maximum number of entries of the FRI step list of [`CairoProofOptions`].
+ */
+#define MAX_FRI_STEPS 64
+
+#define MAX_PROOF_SIZE (1024 * 1024)
+
+/**
+ * Security levels of the presets of proof options.
+ */
+typedef enum CairoSecurityLevel {
+  CAIRO_SECURITY_LEVEL_CONJECTURABLE80_BITS = 0,
+  CAIRO_SECURITY_LEVEL_CONJECTURABLE100_BITS = 1,
+  CAIRO_SECURITY_LEVEL_CONJECTURABLE128_BITS = 2,
+  CAIRO_SECURITY_LEVEL_PROVABLE80_BITS = 3,
+  CAIRO_SECURITY_LEVEL_PROVABLE100_BITS = 4,
+  CAIRO_SECURITY_LEVEL_PROVABLE128_BITS = 5,
+} CairoSecurityLevel;
+
+/**
+ * Status returned by the functions of the C ABI.
+ */
+typedef enum CairoStatus {
+  /**
+   * The call succeeded. For verifications, the proof is valid.
+   */
+  CAIRO_STATUS_OK = 0,
+  /**
+   * A pointer argument is null.
+   */
+  CAIRO_STATUS_NULL_POINTER = 1,
+  /**
+   * The layout is not the name of a layout.
+   */
+  CAIRO_STATUS_INVALID_LAYOUT = 2,
+  /**
+   * The blowup factor of the proof options is not a power of two, there are no FRI queries,
+   * the FRI step list is longer than [`MAX_FRI_STEPS`] or the degree bound of the last FRI
+   * layer is not a power of two.
+   */
+  CAIRO_STATUS_INVALID_PROOF_OPTIONS = 3,
+  /**
+   * The program could not be run, or the trace of its execution could not be built.
+   */
+  CAIRO_STATUS_PROGRAM_ERROR = 4,
+  /**
+   * The prover failed to make the proof.
+   */
+  CAIRO_STATUS_PROVING_ERROR = 5,
+  /**
+   * The proof or the public inputs could not be decoded.
+   */
+  CAIRO_STATUS_INVALID_ENCODING = 6,
+  /**
+   * The proof is not valid.
+   */
+  CAIRO_STATUS_VERIFICATION_FAILED = 7,
+  /**
+   * The library panicked.
+   */
+  CAIRO_STATUS_PANIC = 8,
+} CairoStatus;
+
+/**
+ * Options of the proofs. See `ProofOptions` in the STARK prover.
+ *
+ * The FRI step list is given by its first `fri_step_list_len` entries of `fri_step_list`, and
+ * the rest of the entries are ignored. An empty list folds each FRI layer one time.
+ */
+typedef struct CairoProofOptions {
+  uint8_t blowup_factor;
+  size_t fri_number_of_queries;
+  uint64_t coset_offset;
+  uint8_t grinding_factor;
+  bool zero_knowledge;
+  size_t fri_step_list[MAX_FRI_STEPS];
+  size_t fri_step_list_len;
+  size_t fri_last_layer_degree_bound;
+} CairoProofOptions;
+
+/**
+ * Bytes allocated by the library. They must be released with [`cairo_buffer_free`].
+ */
+typedef struct CairoBuffer {
+  uint8_t *data;
+  size_t len;
+} CairoBuffer;
+
+/**
+ * Returns the preset proof options for a security level.
+ */
+struct CairoProofOptions cairo_proof_options_secure(enum CairoSecurityLevel security_level,
+                                                    uint64_t coset_offset);
+
+/**
+ * Releases a buffer returned by the library. Empty buffers are ignored.
+ *
+ * # Safety
+ *
+ * `buffer` must have been returned by the library, and not released before.
+ */
+void cairo_buffer_free(struct CairoBuffer buffer);
+
+/**
+ * Runs a compiled Cairo 0 program in proof mode and proves its execution. On success, writes
+ * the proof and the public inputs to `proof` and `public_inputs`; otherwise, they are left
+ * empty.
+ *
+ * # Safety
+ *
+ * `program` must point to `program_len` bytes with the JSON of the compiled program, `layout`
+ * must be a NUL-terminated string with the name of the layout, such as `plain`, and `options`,
+ * `proof` and `public_inputs` must be valid pointers.
+ */
+enum CairoStatus cairo_run_and_prove(const uint8_t *program,
+                                     size_t program_len,
+                                     const char *layout,
+                                     const struct CairoProofOptions *options,
+                                     struct CairoBuffer *proof,
+                                     struct CairoBuffer *public_inputs);
+
+/**
+ * Verifies a proof made by [`cairo_run_and_prove`] for the given public inputs. Returns
+ * `Ok` if the proof is valid, and `VerificationFailed` if it's not.
+ *
+ * # Safety
+ *
+ * `proof` and `public_inputs` must point to `proof_len` and `public_inputs_len` bytes, and
+ * `options` must be a valid pointer.
+ */
+enum CairoStatus cairo_verify(const uint8_t *proof,
+                              size_t proof_len,
+                              const uint8_t *public_inputs,
+                              size_t public_inputs_len,
+                              const struct CairoProofOptions *options);
+
+/**
+ * Verifies a proof file written by the CLI, which holds the length of the proof as a
+ * little-endian `u32`, the proof and the public inputs.
+ *
+ * # Safety
+ *
+ * `proof_file` must point to `proof_file_len` bytes, and `options` must be a valid pointer.
+ */
+enum CairoStatus cairo_verify_proof_file(const uint8_t *proof_file,
+                                         size_t proof_file_len,
+                                         const struct CairoProofOptions *options);
+
+/**
+ * WASM Function for verifying a proof file of the CLI, held in the first `real_len` bytes of
+ * `proof_bytes`, with default 100 bits of security. Returns false if the proof is invalid or
+ * can't be read.
+ *
+ * Deprecated in favour of [`cairo_verify_proof_file`], which reports why a proof is rejected.
+ */
+bool verify_cairo_proof_ffi_100_bits(const uint8_t (*proof_bytes)[MAX_PROOF_SIZE], size_t real_len);
+
+#endif  /* CAIRO_PLATINUM_FFI_H */
@@ -1,71 +1,632 @@
-use cairo_platinum_prover::air::CairoAIR;
+//! C ABI of the Cairo Platinum Prover.
+//!
+//! Functions return a [`CairoStatus`] instead of panicking. Proofs and public inputs are
+//! exchanged as bincode-encoded buffers, the same encoding used by the proof files of the CLI.
+//! Buffers returned by the library are owned by the caller, who must release them with
+//! [`cairo_buffer_free`]. The header `include/cairo_platinum_ffi.h` is generated from this file
+//! with `make header`.
+
+use cairo_platinum_prover::air::{generate_cairo_proof, verify_cairo_proof, PublicInputs};
+use cairo_platinum_prover::cairo_layout::CairoLayout;
+use cairo_platinum_prover::runner::run::generate_prover_args;
+use cairo_platinum_prover::PrimeField;
 use stark_platinum_prover::proof::options::ProofOptions;
 use stark_platinum_prover::proof::options::SecurityLevel;
-use stark_platinum_prover::transcript::StoneProverTranscript;
-use stark_platinum_prover::verifier::{IsStarkVerifier, Verifier};
+use stark_platinum_prover::proof::stark::StarkProof;
+use std::ffi::{c_char, CStr};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+use std::str::FromStr;
 
-fn verify_cairo_proof_ffi(proof_bytes: &[u8], proof_options: &ProofOptions) -> bool {
-    let bytes = proof_bytes;
+type CairoProof = StarkProof<PrimeField, PrimeField>;
 
-    // This logic is the same as main verify, with only error handling changing. In ffi, we simply return a false if the proof is invalid, instead of rising an error.
+/// Status returned by the functions of the C ABI.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CairoStatus {
+    /// The call succeeded. For verifications, the proof is valid.
+    Ok = 0,
+    /// A pointer argument is null.
+    NullPointer = 1,
+    /// The layout is not the name of a layout.
+    InvalidLayout = 2,
+    /// The blowup factor of the proof options is not a power of two, there are no FRI queries,
+    /// the FRI step list is longer than [`MAX_FRI_STEPS`] or the degree bound of the last FRI
+    /// layer is not a power of two.
+    InvalidProofOptions = 3,
+    /// The program could not be run, or the trace of its execution could not be built.
+    ProgramError = 4,
+    /// The prover failed to make the proof.
+    ProvingError = 5,
+    /// The proof or the public inputs could not be decoded.
+    InvalidEncoding = 6,
+    /// The proof is not valid.
+    VerificationFailed = 7,
+    /// The library panicked.
+    Panic = 8,
+}
 
-    // Proof len was stored as an u32, 4u8 needs to be read
-    let proof_len = u32::from_le_bytes(bytes[0..4].try_into().unwrap()) as usize;
+/// Maximum number of entries of the FRI step list of [`CairoProofOptions`].
+pub const MAX_FRI_STEPS: usize = 64;
 
-    let bytes = &bytes[4..];
-    if bytes.len() < proof_len {
-        return false;
+/// Options of the proofs. See `ProofOptions` in the STARK prover.
+///
+/// The FRI step list is given by its first `fri_step_list_len` entries of `fri_step_list`, and
+/// the rest of the entries are ignored. An empty list folds each FRI layer one time.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct CairoProofOptions {
+    pub blowup_factor: u8,
+    pub fri_number_of_queries: usize,
+    pub coset_offset: u64,
+    pub grinding_factor: u8,
+    pub zero_knowledge: bool,
+    pub fri_step_list: [usize; MAX_FRI_STEPS],
+    pub fri_step_list_len: usize,
+    pub fri_last_layer_degree_bound: usize,
+}
+
+impl TryFrom<CairoProofOptions> for ProofOptions {
+    type Error = CairoStatus;
+
+    fn try_from(options: CairoProofOptions) -> Result<Self, Self::Error> {
+        if !options.blowup_factor.is_power_of_two()
+            || options.fri_number_of_queries == 0
+            || !options.fri_last_layer_degree_bound.is_power_of_two()
+        {
+            return Err(CairoStatus::InvalidProofOptions);
+        }
+        let fri_step_list = options
+            .fri_step_list
+            .get(..options.fri_step_list_len)
+            .ok_or(CairoStatus::InvalidProofOptions)?;
+        Ok(ProofOptions {
+            blowup_factor: options.blowup_factor,
+            fri_number_of_queries: options.fri_number_of_queries,
+            coset_offset: options.coset_offset,
+            grinding_factor: options.grinding_factor,
+            zero_knowledge: options.zero_knowledge,
+            fri_step_list: fri_step_list.to_vec(),
+            fri_last_layer_degree_bound: options.fri_last_layer_degree_bound,
+        })
     }
+}
 
-    let Ok((proof, _)) =
-        bincode::serde::decode_from_slice(&bytes[0..proof_len], bincode::config::standard())
-    else {
-        return false;
-    };
-    let bytes = &bytes[proof_len..];
+impl TryFrom<ProofOptions> for CairoProofOptions {
+    type Error = CairoStatus;
+
+    fn try_from(options: ProofOptions) -> Result<Self, Self::Error> {
+        let mut fri_step_list = [0; MAX_FRI_STEPS];
+        fri_step_list
+            .get_mut(..options.fri_step_list.len())
+            .ok_or(CairoStatus::InvalidProofOptions)?
+            .copy_from_slice(&options.fri_step_list);
+        Ok(CairoProofOptions {
+            blowup_factor: options.blowup_factor,
+            fri_number_of_queries: options.fri_number_of_queries,
+            coset_offset: options.coset_offset,
+            grinding_factor: options.grinding_factor,
+            zero_knowledge: options.zero_knowledge,
+            fri_step_list,
+            fri_step_list_len: options.fri_step_list.len(),
+            fri_last_layer_degree_bound: options.fri_last_layer_degree_bound,
+        })
+    }
+}
+
+/// Security levels of the presets of proof options.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub enum CairoSecurityLevel {
+    Conjecturable80Bits = 0,
+    Conjecturable100Bits = 1,
+    Conjecturable128Bits = 2,
+    Provable80Bits = 3,
+    Provable100Bits = 4,
+    Provable128Bits = 5,
+}
+
+impl From<CairoSecurityLevel> for SecurityLevel {
+    fn from(security_level: CairoSecurityLevel) -> Self {
+        match security_level {
+            CairoSecurityLevel::Conjecturable80Bits => SecurityLevel::Conjecturable80Bits,
+            CairoSecurityLevel::Conjecturable100Bits => SecurityLevel::Conjecturable100Bits,
+            CairoSecurityLevel::Conjecturable128Bits => SecurityLevel::Conjecturable128Bits,
+            CairoSecurityLevel::Provable80Bits => SecurityLevel::Provable80Bits,
+            CairoSecurityLevel::Provable100Bits => SecurityLevel::Provable100Bits,
+            CairoSecurityLevel::Provable128Bits => SecurityLevel::Provable128Bits,
+        }
+    }
+}
 
-    let Ok((pub_inputs, _)) = bincode::serde::decode_from_slice(bytes, bincode::config::standard())
-    else {
-        return false;
+/// Bytes allocated by the library. They must be released with [`cairo_buffer_free`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct CairoBuffer {
+    pub data: *mut u8,
+    pub len: usize,
+}
+
+impl CairoBuffer {
+    const EMPTY: CairoBuffer = CairoBuffer {
+        data: ptr::null_mut(),
+        len: 0,
     };
 
-    Verifier::<CairoAIR>::verify(
-        &proof,
-        &pub_inputs,
-        proof_options,
-        StoneProverTranscript::new(&[]),
-    )
+    fn from_vec(bytes: Vec<u8>) -> Self {
+        let bytes = Box::into_raw(bytes.into_boxed_slice());
+        CairoBuffer {
+            data: bytes as *mut u8,
+            len: bytes.len(),
+        }
+    }
+}
+
+/// Returns the preset proof options for a security level.
+#[no_mangle]
+pub extern "C" fn cairo_proof_options_secure(
+    security_level: CairoSecurityLevel,
+    coset_offset: u64,
+) -> CairoProofOptions {
+    ProofOptions::new_secure(security_level.into(), coset_offset)
+        .try_into()
+        .expect("the presets have an empty FRI step list")
+}
+
+/// Releases a buffer returned by the library. Empty buffers are ignored.
+///
+/// # Safety
+///
+/// `buffer` must have been returned by the library, and not released before.
+#[no_mangle]
+pub unsafe extern "C" fn cairo_buffer_free(buffer: CairoBuffer) {
+    if !buffer.data.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(
+            buffer.data,
+            buffer.len,
+        )));
+    }
+}
+
+/// Runs a compiled Cairo 0 program in proof mode and proves its execution. On success, writes
+/// the proof and the public inputs to `proof` and `public_inputs`; otherwise, they are left
+/// empty.
+///
+/// # Safety
+///
+/// `program` must point to `program_len` bytes with the JSON of the compiled program, `layout`
+/// must be a NUL-terminated string with the name of the layout, such as `plain`, and `options`,
+/// `proof` and `public_inputs` must be valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn cairo_run_and_prove(
+    program: *const u8,
+    program_len: usize,
+    layout: *const c_char,
+    options: *const CairoProofOptions,
+    proof: *mut CairoBuffer,
+    public_inputs: *mut CairoBuffer,
+) -> CairoStatus {
+    if program.is_null()
+        || layout.is_null()
+        || options.is_null()
+        || proof.is_null()
+        || public_inputs.is_null()
+    {
+        return CairoStatus::NullPointer;
+    }
+    *proof = CairoBuffer::EMPTY;
+    *public_inputs = CairoBuffer::EMPTY;
+
+    let program = slice::from_raw_parts(program, program_len);
+    let layout = CStr::from_ptr(layout);
+    let options = *options;
+
+    catch_status(|| {
+        let (proof_bytes, public_inputs_bytes) = run_and_prove(program, layout, options)?;
+        *proof = CairoBuffer::from_vec(proof_bytes);
+        *public_inputs = CairoBuffer::from_vec(public_inputs_bytes);
+        Ok(())
+    })
+}
+
+/// Verifies a proof made by [`cairo_run_and_prove`] for the given public inputs. Returns
+/// `Ok` if the proof is valid, and `VerificationFailed` if it's not.
+///
+/// # Safety
+///
+/// `proof` and `public_inputs` must point to `proof_len` and `public_inputs_len` bytes, and
+/// `options` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn cairo_verify(
+    proof: *const u8,
+    proof_len: usize,
+    public_inputs: *const u8,
+    public_inputs_len: usize,
+    options: *const CairoProofOptions,
+) -> CairoStatus {
+    if proof.is_null() || public_inputs.is_null() || options.is_null() {
+        return CairoStatus::NullPointer;
+    }
+    let proof = slice::from_raw_parts(proof, proof_len);
+    let public_inputs = slice::from_raw_parts(public_inputs, public_inputs_len);
+    let options = *options;
+
+    catch_status(|| verify(proof, public_inputs, options))
+}
+
+/// Verifies a proof file written by the CLI, which holds the length of the proof as a
+/// little-endian `u32`, the proof and the public inputs.
+///
+/// # Safety
+///
+/// `proof_file` must point to `proof_file_len` bytes, and `options` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn cairo_verify_proof_file(
+    proof_file: *const u8,
+    proof_file_len: usize,
+    options: *const CairoProofOptions,
+) -> CairoStatus {
+    if proof_file.is_null() || options.is_null() {
+        return CairoStatus::NullPointer;
+    }
+    let proof_file = slice::from_raw_parts(proof_file, proof_file_len);
+    let options = *options;
+
+    catch_status(|| verify_proof_file(proof_file, options))
+}
+
+fn catch_status(f: impl FnOnce() -> Result<(), CairoStatus>) -> CairoStatus {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => CairoStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => CairoStatus::Panic,
+    }
+}
+
+fn run_and_prove(
+    program: &[u8],
+    layout: &CStr,
+    options: CairoProofOptions,
+) -> Result<(Vec<u8>, Vec<u8>), CairoStatus> {
+    let layout = layout
+        .to_str()
+        .ok()
+        .and_then(|layout| CairoLayout::from_str(layout).ok())
+        .ok_or(CairoStatus::InvalidLayout)?;
+    let options = ProofOptions::try_from(options)?;
+
+    let (main_trace, public_inputs) =
+        generate_prover_args(program, layout).map_err(|_| CairoStatus::ProgramError)?;
+    let proof = generate_cairo_proof(&main_trace, &public_inputs, &options)
+        .map_err(|_| CairoStatus::ProvingError)?;
+
+    let config = bincode::config::standard();
+    let encode_error = |_| CairoStatus::InvalidEncoding;
+    Ok((
+        bincode::serde::encode_to_vec(proof, config).map_err(encode_error)?,
+        bincode::serde::encode_to_vec(public_inputs, config).map_err(encode_error)?,
+    ))
+}
+
+fn verify(
+    proof: &[u8],
+    public_inputs: &[u8],
+    options: CairoProofOptions,
+) -> Result<(), CairoStatus> {
+    let options = ProofOptions::try_from(options)?;
+    let config = bincode::config::standard();
+    let (proof, _): (CairoProof, _) = bincode::serde::decode_from_slice(proof, config)
+        .map_err(|_| CairoStatus::InvalidEncoding)?;
+    let (public_inputs, _): (PublicInputs, _) =
+        bincode::serde::decode_from_slice(public_inputs, config)
+            .map_err(|_| CairoStatus::InvalidEncoding)?;
+
+    if verify_cairo_proof(&proof, &public_inputs, &options) {
+        Ok(())
+    } else {
+        Err(CairoStatus::VerificationFailed)
+    }
+}
+
+fn verify_proof_file(proof_file: &[u8], options: CairoProofOptions) -> Result<(), CairoStatus> {
+    let (proof, public_inputs) =
+        split_proof_file(proof_file).ok_or(CairoStatus::InvalidEncoding)?;
+    verify(proof, public_inputs, options)
+}
+
+/// Splits a proof file of the CLI into the proof and the public inputs.
+fn split_proof_file(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+    // Proof len was stored as an u32, 4u8 needs to be read
+    if bytes.len() < 4 {
+        return None;
+    }
+    let (proof_len, bytes) = bytes.split_at(4);
+    let proof_len = u32::from_le_bytes(proof_len.try_into().unwrap()) as usize;
+    if bytes.len() < proof_len {
+        return None;
+    }
+    Some(bytes.split_at(proof_len))
 }
 
 // Fibo 70k is 260 kb
 // 2 MiB is more than enough
-const MAX_PROOF_SIZE: usize = 1024 * 1024;
+pub const MAX_PROOF_SIZE: usize = 1024 * 1024;
 
-/// WASM Function for verifying a proof with default 100 bits of security
+/// WASM Function for verifying a proof file of the CLI, held in the first `real_len` bytes of
+/// `proof_bytes`, with default 100 bits of security. Returns false if the proof is invalid or
+/// can't be read.
+///
+/// Deprecated in favour of [`cairo_verify_proof_file`], which reports why a proof is rejected.
+#[deprecated(note = "use `cairo_verify_proof_file`, which reports why a proof is rejected")]
 #[no_mangle]
 pub extern "C" fn verify_cairo_proof_ffi_100_bits(
     proof_bytes: &[u8; MAX_PROOF_SIZE],
     real_len: usize,
 ) -> bool {
-    let (real_proof_bytes, _) = proof_bytes.split_at(real_len);
-
-    println!("Len: {:?} ", real_proof_bytes.len());
-    let proof_options = ProofOptions::new_secure(SecurityLevel::Conjecturable100Bits, 3);
-    verify_cairo_proof_ffi(real_proof_bytes, &proof_options)
+    let proof_file = match proof_bytes.get(..real_len) {
+        Some(proof_file) => proof_file,
+        None => return false,
+    };
+    let options = cairo_proof_options_secure(CairoSecurityLevel::Conjecturable100Bits, 3);
+    catch_status(|| verify_proof_file(proof_file, options)) == CairoStatus::Ok
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const PROOF: &[u8; 265809] = include_bytes!("../fibo_5.proof");
+    fn fibonacci_5() -> Vec<u8> {
+        std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../cairo_programs/cairo0/fibonacci_5.json"
+        ))
+        .unwrap()
+    }
+
+    fn plain_layout() -> &'static CStr {
+        CStr::from_bytes_with_nul(b"plain\0").unwrap()
+    }
+
+    fn options_100_bits() -> CairoProofOptions {
+        cairo_proof_options_secure(CairoSecurityLevel::Conjecturable100Bits, 3)
+    }
+
+    /// Proves the Fibonacci program with 100 bits of security and returns the proof file the
+    /// CLI would write for it.
+    fn fibonacci_5_proof_file() -> Vec<u8> {
+        let program = fibonacci_5();
+        let mut proof = CairoBuffer::EMPTY;
+        let mut public_inputs = CairoBuffer::EMPTY;
+
+        let status = unsafe {
+            cairo_run_and_prove(
+                program.as_ptr(),
+                program.len(),
+                plain_layout().as_ptr(),
+                &options_100_bits(),
+                &mut proof,
+                &mut public_inputs,
+            )
+        };
+        assert_eq!(status, CairoStatus::Ok);
+
+        let proof_file = unsafe {
+            [
+                (proof.len as u32).to_le_bytes().as_slice(),
+                slice::from_raw_parts(proof.data, proof.len),
+                slice::from_raw_parts(public_inputs.data, public_inputs.len),
+            ]
+            .concat()
+        };
+        unsafe {
+            cairo_buffer_free(proof);
+            cairo_buffer_free(public_inputs);
+        }
+        proof_file
+    }
 
     #[test]
+    #[allow(deprecated)]
     fn fibo_5_proof_verifies() {
-        let mut proof_buffer = [0u8; super::MAX_PROOF_SIZE];
-        let proof_size = PROOF.len();
-        proof_buffer[..proof_size].clone_from_slice(PROOF);
-        let result = verify_cairo_proof_ffi_100_bits(&proof_buffer, proof_size);
-        assert!(result)
+        let proof_file = fibonacci_5_proof_file();
+        let mut proof_buffer = vec![0u8; MAX_PROOF_SIZE];
+        proof_buffer[..proof_file.len()].clone_from_slice(&proof_file);
+        let proof_buffer: &[u8; MAX_PROOF_SIZE] = proof_buffer.as_slice().try_into().unwrap();
+
+        assert!(verify_cairo_proof_ffi_100_bits(
+            proof_buffer,
+            proof_file.len()
+        ));
+        assert!(!verify_cairo_proof_ffi_100_bits(proof_buffer, 100));
+        assert!(!verify_cairo_proof_ffi_100_bits(
+            proof_buffer,
+            MAX_PROOF_SIZE + 1
+        ));
+    }
+
+    #[test]
+    fn proof_file_is_verified_with_its_options() {
+        let proof_file = fibonacci_5_proof_file();
+        let status = unsafe {
+            cairo_verify_proof_file(proof_file.as_ptr(), proof_file.len(), &options_100_bits())
+        };
+        assert_eq!(status, CairoStatus::Ok);
+
+        let options = CairoProofOptions {
+            coset_offset: 5,
+            ..options_100_bits()
+        };
+        let status =
+            unsafe { cairo_verify_proof_file(proof_file.as_ptr(), proof_file.len(), &options) };
+        assert_eq!(status, CairoStatus::VerificationFailed);
+    }
+
+    #[test]
+    fn malformed_arguments_are_reported() {
+        let proof_file = fibonacci_5_proof_file();
+        let status =
+            unsafe { cairo_verify_proof_file(proof_file.as_ptr(), 100, &options_100_bits()) };
+        assert_eq!(status, CairoStatus::InvalidEncoding);
+
+        let status = unsafe { cairo_verify_proof_file(ptr::null(), 0, &options_100_bits()) };
+        assert_eq!(status, CairoStatus::NullPointer);
+
+        let options = CairoProofOptions {
+            blowup_factor: 3,
+            ..options_100_bits()
+        };
+        let status =
+            unsafe { cairo_verify_proof_file(proof_file.as_ptr(), proof_file.len(), &options) };
+        assert_eq!(status, CairoStatus::InvalidProofOptions);
+    }
+
+    #[test]
+    fn program_is_run_proved_and_verified() {
+        let program = fibonacci_5();
+        let options = ProofOptions::default_test_options().try_into().unwrap();
+        let mut proof = CairoBuffer::EMPTY;
+        let mut public_inputs = CairoBuffer::EMPTY;
+
+        let status = unsafe {
+            cairo_run_and_prove(
+                program.as_ptr(),
+                program.len(),
+                plain_layout().as_ptr(),
+                &options,
+                &mut proof,
+                &mut public_inputs,
+            )
+        };
+        assert_eq!(status, CairoStatus::Ok);
+
+        let status = unsafe {
+            cairo_verify(
+                proof.data,
+                proof.len,
+                public_inputs.data,
+                public_inputs.len,
+                &options,
+            )
+        };
+        assert_eq!(status, CairoStatus::Ok);
+
+        unsafe {
+            cairo_buffer_free(proof);
+            cairo_buffer_free(public_inputs);
+        }
+    }
+
+    #[test]
+    fn fri_options_are_converted_both_ways() {
+        let options = ProofOptions {
+            fri_step_list: vec![0, 2, 3],
+            fri_last_layer_degree_bound: 4,
+            ..ProofOptions::default_test_options()
+        };
+        let c_options = CairoProofOptions::try_from(options).unwrap();
+        assert_eq!(
+            c_options.fri_step_list[..c_options.fri_step_list_len],
+            [0, 2, 3]
+        );
+        assert_eq!(c_options.fri_last_layer_degree_bound, 4);
+
+        let options = ProofOptions::try_from(c_options).unwrap();
+        assert_eq!(options.fri_step_list, [0, 2, 3]);
+        assert_eq!(options.fri_last_layer_degree_bound, 4);
+
+        let too_many_steps = ProofOptions {
+            fri_step_list: vec![1; MAX_FRI_STEPS + 1],
+            ..ProofOptions::default_test_options()
+        };
+        assert_eq!(
+            CairoProofOptions::try_from(too_many_steps).unwrap_err(),
+            CairoStatus::InvalidProofOptions
+        );
+        for options in [
+            CairoProofOptions {
+                fri_step_list_len: MAX_FRI_STEPS + 1,
+                ..c_options
+            },
+            CairoProofOptions {
+                fri_last_layer_degree_bound: 3,
+                ..c_options
+            },
+        ] {
+            assert_eq!(
+                ProofOptions::try_from(options).unwrap_err(),
+                CairoStatus::InvalidProofOptions
+            );
+        }
+    }
+
+    #[test]
+    fn fri_options_are_passed_to_the_prover_and_the_verifier() {
+        let program = fibonacci_5();
+        let prove = |options: &CairoProofOptions| {
+            let mut proof = CairoBuffer::EMPTY;
+            let mut public_inputs = CairoBuffer::EMPTY;
+            let status = unsafe {
+                cairo_run_and_prove(
+                    program.as_ptr(),
+                    program.len(),
+                    plain_layout().as_ptr(),
+                    options,
+                    &mut proof,
+                    &mut public_inputs,
+                )
+            };
+            (status, proof, public_inputs)
+        };
+
+        let options = CairoProofOptions {
+            fri_last_layer_degree_bound: 2,
+            ..options_100_bits()
+        };
+        let (status, proof, public_inputs) = prove(&options);
+        assert_eq!(status, CairoStatus::Ok);
+        let verify_with = |options: &CairoProofOptions| unsafe {
+            cairo_verify(
+                proof.data,
+                proof.len,
+                public_inputs.data,
+                public_inputs.len,
+                options,
+            )
+        };
+        assert_eq!(verify_with(&options), CairoStatus::Ok);
+        assert_ne!(verify_with(&options_100_bits()), CairoStatus::Ok);
+        unsafe {
+            cairo_buffer_free(proof);
+            cairo_buffer_free(public_inputs);
+        }
+
+        // A single folding can't take the polynomial of the trace down to the last layer.
+        let mut options = options_100_bits();
+        options.fri_step_list[..2].copy_from_slice(&[0, 1]);
+        options.fri_step_list_len = 2;
+        let (status, proof, public_inputs) = prove(&options);
+        assert_eq!(status, CairoStatus::ProvingError);
+        assert!(proof.data.is_null() && public_inputs.data.is_null());
+    }
+
+    #[test]
+    fn unknown_layout_is_rejected() {
+        let options = options_100_bits();
+        let mut proof = CairoBuffer::EMPTY;
+        let mut public_inputs = CairoBuffer::EMPTY;
+
+        let status = unsafe {
+            cairo_run_and_prove(
+                [0].as_ptr(),
+                1,
+                CStr::from_bytes_with_nul(b"big\0").unwrap().as_ptr(),
+                &options,
+                &mut proof,
+                &mut public_inputs,
+            )
+        };
+        assert_eq!(status, CairoStatus::InvalidLayout);
+        assert!(proof.data.is_null() && public_inputs.data.is_null());
     }
 }
@@ -1,4 +1,7 @@
 use super::{cairo_mem::CairoMemory, register_states::RegisterStates};
+use crate::builtins::{builtin_components, BuiltinComponent, CairoExpr, ConstraintBuilder};
+use crate::cairo_layout::CairoLayout;
+use crate::errors::PublicMemoryError;
 use crate::transition_constraints::*;
 use cairo_vm::{air_public_input::MemorySegmentAddresses, without_std::collections::HashMap};
 #[cfg(debug_assertions)]
@@ -23,7 +26,13 @@ use stark_platinum_prover::{
     verifier::{IsStarkVerifier, Verifier},
     Felt252,
 };
-use stark_platinum_prover::{constraints::transition::TransitionConstraint, table::Table};
+use stark_platinum_prover::{
+    constraints::{
+        symbolic::{Expr, SymbolicConstraint},
+        transition::TransitionConstraint,
+    },
+    table::Table,
+};
 
 // TODO: These should probably be in the TraceTable module.
 pub const FRAME_RES: usize = 16;
@@ -77,9 +86,9 @@ pub const PERMUTATION_ARGUMENT_RANGE_CHECK_COL_2: usize = 20;
 pub const PERMUTATION_ARGUMENT_RANGE_CHECK_COL_3: usize = 21;
 pub const PERMUTATION_ARGUMENT_RANGE_CHECK_COL_4: usize = 22;
 
-// Trace layout
-pub const MEM_P_TRACE_OFFSET: usize = 17;
-pub const MEM_A_TRACE_OFFSET: usize = 19;
+// Auxiliary memory columns of the builtin accesses: sorted address, sorted value and
+// permutation argument of each builtin, in the order of its component.
+pub const MEMORY_BUILTIN_FIRST_COL: usize = 23;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum SegmentName {
@@ -89,6 +98,8 @@ pub enum SegmentName {
     Execution,
     Ecdsa,
     Pedersen,
+    Bitwise,
+    Poseidon,
 }
 
 impl From<&str> for SegmentName {
@@ -100,6 +111,8 @@ impl From<&str> for SegmentName {
             "execution" => SegmentName::Execution,
             "ecdsa" => SegmentName::Ecdsa,
             "pedersen" => SegmentName::Pedersen,
+            "bitwise" => SegmentName::Bitwise,
+            "poseidon" => SegmentName::Poseidon,
             n => panic!("Invalid segment name {n}"),
         }
     }
@@ -159,6 +172,8 @@ pub struct PublicInputs {
     pub memory_segments: MemorySegmentMap,
     pub public_memory: HashMap<Felt252, Felt252>,
     pub num_steps: usize, // number of execution steps
+    /// Layout of the trace, which sets the builtin components of the AIR.
+    pub layout: CairoLayout,
 }
 
 impl PublicInputs {
@@ -187,8 +202,46 @@ impl PublicInputs {
             memory_segments: MemorySegmentMap::new(),
             public_memory,
             num_steps: register_states.steps(),
+            layout: CairoLayout::Plain,
+        }
+    }
+
+    /// The values written by the program to the output builtin, which is empty if the program
+    /// doesn't use it.
+    pub fn output(&self) -> Result<Vec<Felt252>, PublicMemoryError> {
+        match self.memory_segments.get(&SegmentName::Output) {
+            Some(output) => self.public_memory_range(output.begin_addr, output.stop_ptr),
+            None => Ok(Vec::new()),
         }
     }
+
+    /// The bytecode of the program, which is at the start of the public memory, from the
+    /// beginning of the program segment to the beginning of the execution segment.
+    pub fn program(&self) -> Result<Vec<Felt252>, PublicMemoryError> {
+        let segment = |name: SegmentName| {
+            self.memory_segments
+                .get(&name)
+                .ok_or(PublicMemoryError::MissingSegment(name))
+        };
+        let program = segment(SegmentName::Program)?;
+        let execution = segment(SegmentName::Execution)?;
+        self.public_memory_range(program.begin_addr, execution.begin_addr)
+    }
+
+    fn public_memory_range(
+        &self,
+        begin_addr: usize,
+        end_addr: usize,
+    ) -> Result<Vec<Felt252>, PublicMemoryError> {
+        (begin_addr..end_addr)
+            .map(|addr| {
+                self.public_memory
+                    .get(&Felt252::from(addr as u64))
+                    .copied()
+                    .ok_or(PublicMemoryError::MissingCell(addr))
+            })
+            .collect()
+    }
 }
 
 impl AsBytes for PublicInputs {
@@ -217,16 +270,28 @@ impl AsBytes for PublicInputs {
             bytes.extend(0u8.to_be_bytes());
         }
 
+        // The maps are written in increasing order of their keys, so that the bytes, which
+        // seed the transcript, don't depend on the iteration order of the maps.
+        let mut memory_segments = self
+            .memory_segments
+            .iter()
+            .map(|(segment, range)| {
+                let segment_type = match segment {
+                    SegmentName::RangeCheck => 0u8,
+                    SegmentName::Output => 1u8,
+                    SegmentName::Program => 2u8,
+                    SegmentName::Execution => 3u8,
+                    SegmentName::Ecdsa => 4u8,
+                    SegmentName::Pedersen => 5u8,
+                    SegmentName::Bitwise => 6u8,
+                    SegmentName::Poseidon => 7u8,
+                };
+                (segment_type, range)
+            })
+            .collect::<Vec<_>>();
+        memory_segments.sort_by_key(|(segment_type, _)| *segment_type);
         let mut memory_segment_bytes = vec![];
-        for (segment, range) in self.memory_segments.iter() {
-            let segment_type = match segment {
-                SegmentName::RangeCheck => 0u8,
-                SegmentName::Output => 1u8,
-                SegmentName::Program => 2u8,
-                SegmentName::Execution => 3u8,
-                SegmentName::Ecdsa => 4u8,
-                SegmentName::Pedersen => 5u8,
-            };
+        for (segment_type, range) in memory_segments {
             memory_segment_bytes.extend(segment_type.to_be_bytes());
             memory_segment_bytes.extend(range.begin_addr.to_be_bytes());
             memory_segment_bytes.extend(range.stop_ptr.to_be_bytes());
@@ -235,10 +300,17 @@ impl AsBytes for PublicInputs {
         bytes.extend(memory_segment_length.to_be_bytes());
         bytes.extend(memory_segment_bytes);
 
+        let mut public_memory = self
+            .public_memory
+            .iter()
+            .map(|(address, value)| (address.to_bytes_be(), value.to_bytes_be()))
+            .collect::<Vec<_>>();
+        // Big endian encodings of the same length sort as the addresses they encode.
+        public_memory.sort_unstable();
         let mut public_memory_bytes = vec![];
-        for (address, value) in self.public_memory.iter() {
-            public_memory_bytes.extend(address.to_bytes_be());
-            public_memory_bytes.extend(value.to_bytes_be());
+        for (address, value) in public_memory {
+            public_memory_bytes.extend(address);
+            public_memory_bytes.extend(value);
         }
         let public_memory_length = self.public_memory.len();
         bytes.extend(public_memory_length.to_be_bytes());
@@ -246,6 +318,12 @@ impl AsBytes for PublicInputs {
 
         bytes.extend(self.num_steps.to_be_bytes());
 
+        let layout = CairoLayout::ALL
+            .iter()
+            .position(|layout| *layout == self.layout)
+            .unwrap() as u8;
+        bytes.extend(layout.to_be_bytes());
+
         bytes
     }
 }
@@ -357,6 +435,8 @@ impl Deserializable for PublicInputs {
                 3u8 => SegmentName::Execution,
                 4u8 => SegmentName::Ecdsa,
                 5u8 => SegmentName::Pedersen,
+                6u8 => SegmentName::Bitwise,
+                7u8 => SegmentName::Poseidon,
                 _ => return Err(DeserializationError::FieldFromBytesError),
             };
             bytes = &bytes[1..];
@@ -411,6 +491,12 @@ impl Deserializable for PublicInputs {
                 .try_into()
                 .map_err(|_| DeserializationError::InvalidAmountOfBytes)?,
         );
+        bytes = &bytes[8..];
+
+        let layout = *bytes
+            .first()
+            .and_then(|layout| CairoLayout::ALL.get(*layout as usize))
+            .ok_or(DeserializationError::InvalidValue)?;
 
         Ok(Self {
             pc_init,
@@ -423,6 +509,7 @@ impl Deserializable for PublicInputs {
             memory_segments,
             public_memory,
             num_steps,
+            layout,
         })
     }
 }
@@ -433,6 +520,144 @@ pub struct CairoAIR {
     pub pub_inputs: PublicInputs,
     pub transition_constraints:
         Vec<Box<dyn TransitionConstraint<Stark252PrimeField, Stark252PrimeField>>>,
+    /// Components of the builtins of the layout.
+    pub builtins: Vec<BuiltinComponent>,
+}
+
+/// Columns of a memory access of each row: the sorted address, sorted value and permutation
+/// argument in the auxiliary trace, and the address and value in the main trace.
+struct MemoryAccessColumns {
+    sorted_addr: usize,
+    sorted_value: usize,
+    permutation: usize,
+    addr: usize,
+    value: usize,
+}
+
+/// The memory accesses of each row, in the order they are merged into the memory argument:
+/// those of the CPU, followed by one per builtin.
+fn memory_access_columns(builtins: &[BuiltinComponent]) -> Vec<MemoryAccessColumns> {
+    let cpu_accesses = [
+        (FRAME_PC, FRAME_INST),
+        (FRAME_DST_ADDR, FRAME_DST),
+        (FRAME_OP0_ADDR, FRAME_OP0),
+        (FRAME_OP1_ADDR, FRAME_OP1),
+        (EXTRA_ADDR, EXTRA_VAL),
+    ];
+    let cpu_accesses =
+        cpu_accesses
+            .iter()
+            .enumerate()
+            .map(|(i, (addr, value))| MemoryAccessColumns {
+                sorted_addr: MEMORY_ADDR_SORTED_0 + i,
+                sorted_value: MEMORY_VALUES_SORTED_0 + i,
+                permutation: PERMUTATION_ARGUMENT_COL_0 + i,
+                addr: *addr,
+                value: *value,
+            });
+    let builtin_accesses = builtins
+        .iter()
+        .enumerate()
+        .map(|(i, component)| MemoryAccessColumns {
+            sorted_addr: MEMORY_BUILTIN_FIRST_COL + 3 * i,
+            sorted_value: MEMORY_BUILTIN_FIRST_COL + 3 * i + 1,
+            permutation: MEMORY_BUILTIN_FIRST_COL + 3 * i + 2,
+            addr: component.mem_addr_column(),
+            value: component.mem_val_column(),
+        });
+    cpu_accesses.chain(builtin_accesses).collect()
+}
+
+/// Memory constraints between an access and the next one in the memory argument, which is in
+/// the frame step `next_step`: the sorted addresses are continuous, the sorted memory is
+/// single-valued and the permutation argument is accumulated.
+fn memory_link_constraints(
+    current: &MemoryAccessColumns,
+    next: &MemoryAccessColumns,
+    next_step: usize,
+) -> [CairoExpr; 3] {
+    let alpha = Expr::rap_challenge(0);
+    let z = Expr::rap_challenge(1);
+    let a_i = Expr::aux(0, current.sorted_addr);
+    let v_i = Expr::aux(0, current.sorted_value);
+    let p_i = Expr::aux(0, current.permutation);
+    let a_j = Expr::aux(next_step, next.sorted_addr);
+    let v_j = Expr::aux(next_step, next.sorted_value);
+    let p_j = Expr::aux(next_step, next.permutation);
+    let addr_j = Expr::main(next_step, next.addr);
+    let value_j = Expr::main(next_step, next.value);
+
+    let addr_increment = a_j.clone() - a_i.clone() - Expr::one();
+    [
+        (a_i.clone() - a_j.clone()) * addr_increment.clone(),
+        (v_i - v_j.clone()) * addr_increment,
+        (z.clone() - (a_j + alpha.clone() * v_j)) * p_j - (z - (addr_j + alpha * value_j)) * p_i,
+    ]
+}
+
+/// Columns of a range-checked value of each row: the sorted value and permutation argument in
+/// the auxiliary trace, and the value in the main trace.
+struct RangeCheckColumns {
+    sorted: usize,
+    permutation: usize,
+    value: usize,
+}
+
+/// The range-checked values of each row, in the order they are merged into the range-check
+/// argument: the offsets and holes of the CPU, followed by one per builtin with range-checked
+/// values. The auxiliary columns of the builtins come after their memory accesses.
+fn range_check_columns(builtins: &[BuiltinComponent]) -> Vec<RangeCheckColumns> {
+    let cpu_values = [OFF_DST, OFF_OP0, OFF_OP1, RC_HOLES]
+        .iter()
+        .enumerate()
+        .map(|(i, value)| RangeCheckColumns {
+            sorted: RANGE_CHECK_COL_1 + i,
+            permutation: PERMUTATION_ARGUMENT_RANGE_CHECK_COL_1 + i,
+            value: *value,
+        });
+    let first_col = MEMORY_BUILTIN_FIRST_COL + 3 * builtins.len();
+    let builtin_values = builtins
+        .iter()
+        .filter_map(|component| component.range_check_column())
+        .enumerate()
+        .map(|(i, value)| RangeCheckColumns {
+            sorted: first_col + 2 * i,
+            permutation: first_col + 2 * i + 1,
+            value,
+        });
+    cpu_values.chain(builtin_values).collect()
+}
+
+/// Range-check constraints between a value and the next one in the range-check argument, which
+/// is in the frame step `next_step`: the sorted values are continuous and the permutation
+/// argument is accumulated.
+fn range_check_link_constraints(
+    current: &RangeCheckColumns,
+    next: &RangeCheckColumns,
+    next_step: usize,
+) -> [CairoExpr; 2] {
+    let z = Expr::rap_challenge(2);
+    let a_i = Expr::aux(0, current.sorted);
+    let p_i = Expr::aux(0, current.permutation);
+    let a_j = Expr::aux(next_step, next.sorted);
+    let p_j = Expr::aux(next_step, next.permutation);
+    let value_j = Expr::main(next_step, next.value);
+
+    [
+        (a_i.clone() - a_j.clone()) * (a_j.clone() - a_i - Expr::one()),
+        (z.clone() - a_j) * p_j - (z - value_j) * p_i,
+    ]
+}
+
+/// Number of main and auxiliary columns of the trace: those of the CPU, followed by the ones of
+/// each builtin component, of its memory accesses and of its range-checked values.
+fn trace_layout(builtins: &[BuiltinComponent]) -> (usize, usize) {
+    let builtin_columns: usize = builtins.iter().map(|component| component.n_columns()).sum();
+    let range_checked_columns = range_check_columns(builtins).len() - 4;
+    (
+        36 + builtin_columns,
+        23 + 3 * builtins.len() + 2 * range_checked_columns,
+    )
 }
 
 /// Receives two slices corresponding to the accessed addresses and values, filled with
@@ -547,15 +772,27 @@ impl AIR for CairoAIR {
     /// * `trace_length` - Length of the Cairo execution trace. Must be a power fo two.
     /// * `pub_inputs` - Public inputs sent by the Cairo runner.
     /// * `proof_options` - STARK proving configuration options.
+    ///
+    /// Fails if the program uses builtins out of the layout, or instances of them that don't
+    /// fit in the trace.
     fn new(
         trace_length: usize,
         pub_inputs: &Self::PublicInputs,
         proof_options: &ProofOptions,
-    ) -> Self {
+    ) -> Result<Self, ProvingError> {
         debug_assert!(trace_length.is_power_of_two());
-        let trace_columns = 59;
+        let builtins = builtin_components(&pub_inputs.layout, &pub_inputs.memory_segments)
+            .and_then(|builtins| {
+                for component in &builtins {
+                    component.check_instances(trace_length)?;
+                }
+                Ok(builtins)
+            })
+            .map_err(|err| ProvingError::WrongParameter(format!("{err:?}")))?;
+        let (num_main_columns, num_aux_columns) = trace_layout(&builtins);
+        let trace_columns = num_main_columns + num_aux_columns;
 
-        let transition_constraints: Vec<
+        let mut transition_constraints: Vec<
             Box<dyn TransitionConstraint<Stark252PrimeField, Stark252PrimeField>>,
         > = vec![
             Box::new(BitPrefixFlag0::new()),
@@ -624,6 +861,54 @@ impl AIR for CairoAIR {
             Box::new(CpuOpcodesRetFlags::new()),
         ];
 
+        assert_eq!(transition_constraints.len(), 64);
+
+        // The builtin accesses are added to the memory argument after the ones of the CPU, so
+        // the constraints between the last CPU access of a row and the first one of the next row
+        // are replaced by the constraints linking the accesses through the builtin ones. The
+        // same goes for the range-checked values of the builtins.
+        let mut builder = ConstraintBuilder::new(transition_constraints.len());
+        let accesses = memory_access_columns(&builtins);
+        if accesses.len() > 5 {
+            let [diff, is_func, permutation] =
+                memory_link_constraints(&accesses[4], &accesses[5], 0);
+            transition_constraints[35] = Box::new(SymbolicConstraint::new(35, diff, 0));
+            transition_constraints[40] = Box::new(SymbolicConstraint::new(40, is_func, 0));
+            transition_constraints[45] = Box::new(SymbolicConstraint::new(45, permutation, 0));
+
+            for pair in accesses[5..].windows(2) {
+                for expr in memory_link_constraints(&pair[0], &pair[1], 0) {
+                    builder.every_row(expr, 0);
+                }
+            }
+            for expr in memory_link_constraints(accesses.last().unwrap(), &accesses[0], 1) {
+                builder.every_row(expr, 1);
+            }
+        }
+        let range_checks = range_check_columns(&builtins);
+        if range_checks.len() > 4 {
+            let [diff, permutation] =
+                range_check_link_constraints(&range_checks[3], &range_checks[4], 0);
+            transition_constraints[49] = Box::new(SymbolicConstraint::new(49, diff, 0));
+            transition_constraints[53] = Box::new(SymbolicConstraint::new(53, permutation, 0));
+
+            for pair in range_checks[4..].windows(2) {
+                for expr in range_check_link_constraints(&pair[0], &pair[1], 0) {
+                    builder.every_row(expr, 0);
+                }
+            }
+            let last = range_checks.last().unwrap();
+            for expr in range_check_link_constraints(last, &range_checks[0], 1) {
+                builder.every_row(expr, 1);
+            }
+        }
+        transition_constraints.extend(builder.build());
+
+        for component in &builtins {
+            let constraints = component.transition_constraints(transition_constraints.len());
+            transition_constraints.extend(constraints);
+        }
+
         #[cfg(debug_assertions)]
         {
             use std::collections::HashSet;
@@ -638,12 +923,8 @@ impl AIR for CairoAIR {
             );
             (0..transition_constraints.len())
                 .for_each(|idx| debug_assert!(constraints_set.iter().contains(&idx)));
-
-            assert_eq!(transition_constraints.len(), 64);
         }
 
-        assert_eq!(transition_constraints.len(), 64);
-
         let transition_exemptions = transition_constraints
             .iter()
             .map(|c| c.end_exemptions())
@@ -664,12 +945,13 @@ impl AIR for CairoAIR {
             context.num_transition_constraints
         );
 
-        Self {
+        Ok(Self {
             context,
             pub_inputs: pub_inputs.clone(),
             trace_length,
             transition_constraints,
-        }
+            builtins,
+        })
     }
 
     fn build_auxiliary_trace(
@@ -677,16 +959,12 @@ impl AIR for CairoAIR {
         main_trace: &TraceTable<Self::Field>,
         rap_challenges: &[Felt252],
     ) -> TraceTable<Self::Field> {
-        let addresses_original = main_trace.merge_columns(&[
-            FRAME_PC,
-            FRAME_DST_ADDR,
-            FRAME_OP0_ADDR,
-            FRAME_OP1_ADDR,
-            EXTRA_ADDR,
-        ]);
-
-        let values_original =
-            main_trace.merge_columns(&[FRAME_INST, FRAME_DST, FRAME_OP0, FRAME_OP1, EXTRA_VAL]);
+        let accesses = memory_access_columns(&self.builtins);
+        let n_accesses = accesses.len();
+        let address_columns: Vec<_> = accesses.iter().map(|access| access.addr).collect();
+        let value_columns: Vec<_> = accesses.iter().map(|access| access.value).collect();
+        let addresses_original = main_trace.merge_columns(&address_columns);
+        let values_original = main_trace.merge_columns(&value_columns);
 
         let (addresses, values) = add_pub_memory_in_public_input_section(
             &addresses_original,
@@ -705,7 +983,10 @@ impl AIR for CairoAIR {
         );
 
         // Range Check
-        let offsets_original = main_trace.merge_columns(&[OFF_DST, OFF_OP0, OFF_OP1, RC_HOLES]);
+        let range_checks = range_check_columns(&self.builtins);
+        let n_range_checks = range_checks.len();
+        let range_checked_columns: Vec<_> = range_checks.iter().map(|rc| rc.value).collect();
+        let offsets_original = main_trace.merge_columns(&range_checked_columns);
 
         let mut offsets_sorted: Vec<u16> = offsets_original
             .iter()
@@ -726,29 +1007,22 @@ impl AIR for CairoAIR {
         // Convert from long-format to wide-format again
         let mut aux_data = Vec::new();
         for i in 0..main_trace.n_rows() {
-            aux_data.push(offsets_sorted[4 * i]);
-            aux_data.push(offsets_sorted[4 * i + 1]);
-            aux_data.push(offsets_sorted[4 * i + 2]);
-            aux_data.push(offsets_sorted[4 * i + 3]);
-            aux_data.push(addresses[5 * i]);
-            aux_data.push(addresses[5 * i + 1]);
-            aux_data.push(addresses[5 * i + 2]);
-            aux_data.push(addresses[5 * i + 3]);
-            aux_data.push(addresses[5 * i + 4]);
-            aux_data.push(values[5 * i]);
-            aux_data.push(values[5 * i + 1]);
-            aux_data.push(values[5 * i + 2]);
-            aux_data.push(values[5 * i + 3]);
-            aux_data.push(values[5 * i + 4]);
-            aux_data.push(permutation_col[5 * i]);
-            aux_data.push(permutation_col[5 * i + 1]);
-            aux_data.push(permutation_col[5 * i + 2]);
-            aux_data.push(permutation_col[5 * i + 3]);
-            aux_data.push(permutation_col[5 * i + 4]);
-            aux_data.push(range_check_permutation_col[4 * i]);
-            aux_data.push(range_check_permutation_col[4 * i + 1]);
-            aux_data.push(range_check_permutation_col[4 * i + 2]);
-            aux_data.push(range_check_permutation_col[4 * i + 3]);
+            let row = n_accesses * i;
+            let rc_row = n_range_checks * i;
+            aux_data.extend_from_slice(&offsets_sorted[rc_row..rc_row + 4]);
+            aux_data.extend_from_slice(&addresses[row..row + 5]);
+            aux_data.extend_from_slice(&values[row..row + 5]);
+            aux_data.extend_from_slice(&permutation_col[row..row + 5]);
+            aux_data.extend_from_slice(&range_check_permutation_col[rc_row..rc_row + 4]);
+            for k in 5..n_accesses {
+                aux_data.push(addresses[row + k]);
+                aux_data.push(values[row + k]);
+                aux_data.push(permutation_col[row + k]);
+            }
+            for k in 4..n_range_checks {
+                aux_data.push(offsets_sorted[rc_row + k]);
+                aux_data.push(range_check_permutation_col[rc_row + k]);
+            }
         }
 
         let aux_table = Table::new(aux_data, self.num_auxiliary_rap_columns());
@@ -774,7 +1048,14 @@ impl AIR for CairoAIR {
     }
 
     fn trace_layout(&self) -> (usize, usize) {
-        (36, 23)
+        trace_layout(&self.builtins)
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<FieldElement<Self::Field>>> {
+        self.builtins
+            .iter()
+            .flat_map(|component| component.periodic_columns())
+            .collect()
     }
 
     /// From the Cairo whitepaper, section 9.10.
@@ -786,18 +1067,16 @@ impl AIR for CairoAIR {
     ///  * pc_0 = pc_i
     ///  * pc_t = pc_f
     fn boundary_constraints(&self, rap_challenges: &[Felt252]) -> BoundaryConstraints<Self::Field> {
-        let initial_pc =
-            BoundaryConstraint::new_main(MEM_A_TRACE_OFFSET, 0, self.pub_inputs.pc_init);
-        let initial_ap =
-            BoundaryConstraint::new_main(MEM_P_TRACE_OFFSET, 0, self.pub_inputs.ap_init);
+        let initial_pc = BoundaryConstraint::new_main(FRAME_PC, 0, self.pub_inputs.pc_init);
+        let initial_ap = BoundaryConstraint::new_main(FRAME_AP, 0, self.pub_inputs.ap_init);
 
         let final_pc = BoundaryConstraint::new_main(
-            MEM_A_TRACE_OFFSET,
+            FRAME_PC,
             self.pub_inputs.num_steps - 1,
             self.pub_inputs.pc_final,
         );
         let final_ap = BoundaryConstraint::new_main(
-            MEM_P_TRACE_OFFSET,
+            FRAME_AP,
             self.pub_inputs.num_steps - 1,
             self.pub_inputs.ap_final,
         );
@@ -821,12 +1100,17 @@ impl AIR for CairoAIR {
         let permutation_final =
             z_memory.pow(self.pub_inputs.public_memory.len()) * cumulative_product;
 
+        let permutation_final_col = memory_access_columns(&self.builtins)
+            .last()
+            .unwrap()
+            .permutation;
         let permutation_final_constraint =
-            BoundaryConstraint::new_aux(PERMUTATION_ARGUMENT_COL_4, final_index, permutation_final);
+            BoundaryConstraint::new_aux(permutation_final_col, final_index, permutation_final);
 
         let one: FieldElement<Self::Field> = FieldElement::one();
+        let last_range_check = range_check_columns(&self.builtins).pop().unwrap();
         let range_check_final_constraint =
-            BoundaryConstraint::new_aux(PERMUTATION_ARGUMENT_RANGE_CHECK_COL_4, final_index, one);
+            BoundaryConstraint::new_aux(last_range_check.permutation, final_index, one);
 
         let range_check_min = BoundaryConstraint::new_aux(
             RANGE_CHECK_COL_1,
@@ -835,12 +1119,12 @@ impl AIR for CairoAIR {
         );
 
         let range_check_max = BoundaryConstraint::new_aux(
-            RANGE_CHECK_COL_4,
+            last_range_check.sorted,
             final_index,
             FieldElement::from(self.pub_inputs.range_check_max.unwrap() as u64),
         );
 
-        let constraints = vec![
+        let mut constraints = vec![
             initial_pc,
             initial_ap,
             final_pc,
@@ -850,6 +1134,9 @@ impl AIR for CairoAIR {
             range_check_min,
             range_check_max,
         ];
+        for component in &self.builtins {
+            constraints.extend(component.boundary_constraints(self.trace_length));
+        }
 
         BoundaryConstraints::from_constraints(constraints)
     }
@@ -889,6 +1176,7 @@ impl AIR for CairoAIR {
 /// Wrapper function for generating Cairo proofs without the need to specify
 /// concrete types.
 /// The field is set to Stark252PrimeField and the AIR to CairoAIR.
+/// The transcript is seeded with the public inputs.
 pub fn generate_cairo_proof(
     trace: &TraceTable<Stark252PrimeField>,
     pub_input: &PublicInputs,
@@ -898,13 +1186,14 @@ pub fn generate_cairo_proof(
         trace,
         pub_input,
         proof_options,
-        StoneProverTranscript::new(&[]),
+        StoneProverTranscript::new(&pub_input.as_bytes()),
     )
 }
 
 /// Wrapper function for verifying Cairo proofs without the need to specify
 /// concrete types.
 /// The field is set to Stark252PrimeField and the AIR to CairoAIR.
+/// The transcript is seeded with the public inputs.
 pub fn verify_cairo_proof(
     proof: &StarkProof<Stark252PrimeField, Stark252PrimeField>,
     pub_input: &PublicInputs,
@@ -914,7 +1203,7 @@ pub fn verify_cairo_proof(
         proof,
         pub_input,
         proof_options,
-        StoneProverTranscript::new(&[]),
+        StoneProverTranscript::new(&pub_input.as_bytes()),
     )
 }
 
@@ -1042,6 +1331,7 @@ mod prop_test {
             range_check_max in proptest::option::of(any::<u16>()),
             range_check_min in proptest::option::of(any::<u16>()),
             num_steps in any::<usize>(),
+            layout in proptest::sample::select(CairoLayout::ALL.to_vec()),
         ) -> PublicInputs {
             let public_memory = public_memory.iter().map(|(k, v)| (Felt252::from(*k), Felt252::from(*v))).collect();
             let memory_segments = MemorySegmentMap::from([(SegmentName::Output, Segment::new(10u64, 16u64)), (SegmentName::RangeCheck, Segment::new(20u64, 71u64))]);
@@ -1056,6 +1346,7 @@ mod prop_test {
                 range_check_min,
                 num_steps,
                 memory_segments,
+                layout,
             }
         }
     }
@@ -1078,9 +1369,36 @@ mod prop_test {
             prop_assert_eq!(public_inputs.range_check_min, deserialized.range_check_min);
             prop_assert_eq!(public_inputs.num_steps, deserialized.num_steps);
             prop_assert_eq!(public_inputs.memory_segments, deserialized.memory_segments);
+            prop_assert_eq!(public_inputs.layout, deserialized.layout);
+            prop_assert_eq!(serialized, AsBytes::as_bytes(&deserialized));
         }
     }
 
+    #[test]
+    fn prove_serialize_deserialize_and_verify_with_deserialized_public_inputs() {
+        let program_content = std::fs::read(cairo0_program_path("fibonacci_10.json")).unwrap();
+        let (main_trace, pub_inputs) =
+            generate_prover_args(&program_content, CairoLayout::Plain).unwrap();
+
+        let proof_options = ProofOptions::default_test_options();
+
+        let proof = generate_cairo_proof(&main_trace, &pub_inputs, &proof_options).unwrap();
+        let proof_bytes: Vec<u8> = serde_cbor::to_vec(&proof).unwrap();
+        let pub_inputs_bytes = AsBytes::as_bytes(&pub_inputs);
+        drop(main_trace);
+        drop(proof);
+        drop(pub_inputs);
+
+        // The verifier rebuilds the public inputs, whose maps may iterate in another order,
+        // from their bytes, and seeds its transcript with them.
+        let proof: StarkProof<Stark252PrimeField, Stark252PrimeField> =
+            serde_cbor::from_slice(&proof_bytes).unwrap();
+        let pub_inputs: PublicInputs = Deserializable::deserialize(&pub_inputs_bytes).unwrap();
+
+        assert_eq!(AsBytes::as_bytes(&pub_inputs), pub_inputs_bytes);
+        assert!(verify_cairo_proof(&proof, &pub_inputs, &proof_options));
+    }
+
     #[test]
     fn deserialize_and_verify() {
         let program_content = std::fs::read(cairo0_program_path("fibonacci_10.json")).unwrap();
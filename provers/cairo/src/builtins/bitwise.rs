@@ -0,0 +1,110 @@
+//! Bitwise builtin.
+//!
+//! Each instance has five memory cells: the inputs `x` and `y`, and the outputs `x & y`,
+//! `x ^ y` and `x | y`. As in Stone, the inputs must be smaller than `2^251`.
+//!
+//! The instance takes 256 rows. The columns `X`, `Y`, `AND`, `XOR` and `OR` accumulate the
+//! bits of their values, least significant first: row `i` holds the value modulo `2^i`, so
+//! that the increment between two rows is either zero or the bit weight `2^i`. After the
+//! last bit the accumulators are constant, and rows 251 to 255 hold the memory cells.
+
+use super::{cur, fits_in_bits, next, CairoExpr, ConstraintBuilder};
+use crate::{errors::BuiltinTraceError, Felt252};
+use stark_platinum_prover::constraints::symbolic::Expr;
+
+pub const CELLS_PER_INSTANCE: usize = 5;
+pub const INSTANCE_PERIOD: usize = 256;
+pub const N_INPUTS: usize = 2;
+/// Number of bits of the inputs.
+pub const TOTAL_N_BITS: usize = 251;
+
+const X: usize = 0;
+const Y: usize = 1;
+const AND: usize = 2;
+const XOR: usize = 3;
+const OR: usize = 4;
+pub(crate) const N_COLUMNS: usize = 5;
+
+pub(crate) const CELLS: [(usize, usize); CELLS_PER_INSTANCE] =
+    [(251, X), (252, Y), (253, AND), (254, XOR), (255, OR)];
+
+// Periodic column with the weight of the bit added in each row.
+const BIT_WEIGHT: usize = 0;
+
+pub(crate) fn periodic_columns() -> Vec<Vec<Felt252>> {
+    let mut weight = Felt252::one();
+    let bit_weights = (0..INSTANCE_PERIOD)
+        .map(|row| {
+            if row >= TOTAL_N_BITS {
+                return Felt252::zero();
+            }
+            let current = weight;
+            weight = weight.double();
+            current
+        })
+        .collect();
+    vec![bit_weights]
+}
+
+pub(crate) fn add_constraints(builder: &mut ConstraintBuilder, columns: usize, periodic: usize) {
+    let weight = Expr::periodic(periodic + BIT_WEIGHT);
+    let diff = |column: usize| next(columns + column) - cur(columns + column);
+    let last_row = INSTANCE_PERIOD - 1;
+
+    for column in [X, Y, AND, XOR, OR] {
+        builder.at(INSTANCE_PERIOD, 0, cur(columns + column));
+        builder.except(
+            INSTANCE_PERIOD,
+            last_row,
+            diff(column) * (diff(column) - weight.clone()),
+        );
+    }
+
+    // With `x = dX / w` and `y = dY / w` bits, `dX * dY = w^2 * (x & y)`.
+    let both: CairoExpr = diff(X) * diff(Y);
+    let any: CairoExpr = diff(X) + diff(Y);
+    builder.except(
+        INSTANCE_PERIOD,
+        last_row,
+        weight.clone() * diff(AND) - both.clone(),
+    );
+    builder.except(
+        INSTANCE_PERIOD,
+        last_row,
+        weight.clone() * (diff(XOR) - any.clone()) + both.clone() + both.clone(),
+    );
+    builder.except(INSTANCE_PERIOD, last_row, weight * (diff(OR) - any) + both);
+}
+
+fn accumulated_bits(value: &Felt252) -> Vec<Felt252> {
+    let bits = value.representative();
+    (0..INSTANCE_PERIOD)
+        .map(|row| {
+            // The value modulo `2^row`, which is the whole value after its last bit.
+            let shift = row.min(TOTAL_N_BITS);
+            value - Felt252::from(&((bits >> shift) << shift))
+        })
+        .collect()
+}
+
+pub(crate) fn instance_columns(inputs: &[Felt252]) -> Result<Vec<Vec<Felt252>>, BuiltinTraceError> {
+    let (x, y) = (&inputs[0], &inputs[1]);
+    for input in [x, y] {
+        if !fits_in_bits(input, TOTAL_N_BITS) {
+            return Err(BuiltinTraceError::InputOutOfRange(*input));
+        }
+    }
+    let (x_bits, y_bits) = (x.representative(), y.representative());
+    let and = Felt252::from(&(x_bits & y_bits));
+    let xor = Felt252::from(&(x_bits ^ y_bits));
+    let or = Felt252::from(&(x_bits | y_bits));
+
+    Ok([x, y, &and, &xor, &or]
+        .iter()
+        .map(|value| accumulated_bits(value))
+        .collect())
+}
+
+pub(crate) fn padding_columns() -> Vec<Vec<Felt252>> {
+    instance_columns(&[Felt252::zero(), Felt252::zero()]).unwrap()
+}
@@ -0,0 +1,322 @@
+//! ECDSA builtin.
+//!
+//! Each instance has two memory cells: the x coordinate of a public key `Q` and a message hash
+//! `z`. The instance proves knowledge of a signature `(r, w)`, with `w` the inverse of the
+//! usual `s`, such that `r` is the x coordinate of `w * (z * G + r * Q)` for the generator
+//! `G` of the STARK curve. As in Stone, `z`, `r` and `w` must be nonzero and smaller than
+//! `2^251`. The signatures are not in memory, so they are given to the prover separately.
+//!
+//! The instance takes 1024 rows, in four parts of 256 rows. Each part multiplies a point `D`,
+//! which is doubled in each row, by a scalar `S` consumed one bit per row, adding the
+//! result to an accumulator `A` that starts at a shift point:
+//!  * rows 0 to 255 compute `A = shift + z * G`,
+//!  * rows 256 to 511 continue with `A = shift + z * G + r * Q` and set `B = A - shift`,
+//!  * rows 512 to 767 compute `A = shift + w * B` and check that `A - shift` is `(r, y)`,
+//!  * rows 768 to 1023 are not used.
+
+use super::{
+    bits_le, cur, ec_add, ec_add_constraints, ec_conditional_add, ec_double_and_add, ec_point,
+    felt, fits_in_bits, next, shift_point, shifted_values, ConstraintBuilder, EcPoint, EC_BETA,
+};
+use crate::{errors::BuiltinTraceError, Felt252};
+use cairo_vm::without_std::collections::HashMap;
+use stark_platinum_prover::constraints::symbolic::Expr;
+
+pub const CELLS_PER_INSTANCE: usize = 2;
+pub const INSTANCE_PERIOD: usize = 1024;
+pub const N_INPUTS: usize = 2;
+/// Number of bits of the message hash and of the signature.
+pub const N_BITS: usize = 251;
+
+/// Rows of each scalar multiplication.
+const PART_ROWS: usize = 256;
+
+const S: usize = 0;
+const AX: usize = 1;
+const AY: usize = 2;
+const SLOPE_A: usize = 3;
+const DX: usize = 4;
+const DY: usize = 5;
+const SLOPE_D: usize = 6;
+const R: usize = 7;
+const H: usize = 8;
+pub(crate) const N_COLUMNS: usize = 9;
+
+pub(crate) const CELLS: [(usize, usize); CELLS_PER_INSTANCE] = [(PART_ROWS, DX), (0, S)];
+
+// Periodic columns.
+const ZERO_BITS: usize = 0;
+
+/// An ECDSA signature `(r, w)` over the STARK curve, where `w` is the inverse of `s` modulo
+/// the curve order, as given in Stone's private input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EcdsaSignature {
+    pub r: Felt252,
+    pub w: Felt252,
+}
+
+/// The signatures of the ECDSA builtin instances, by index of the instance.
+pub type EcdsaSignatures = HashMap<usize, EcdsaSignature>;
+
+#[derive(serde::Deserialize)]
+struct PrivateInput {
+    #[serde(default)]
+    ecdsa: Vec<PrivateInputSignature>,
+}
+
+#[derive(serde::Deserialize)]
+struct PrivateInputSignature {
+    index: usize,
+    signature_input: PrivateInputSignatureValues,
+}
+
+#[derive(serde::Deserialize)]
+struct PrivateInputSignatureValues {
+    r: String,
+    w: String,
+}
+
+/// Reads the signatures of the ECDSA builtin from a Stone `air_private_input.json`.
+pub fn signatures_from_private_input(json: &str) -> Result<EcdsaSignatures, BuiltinTraceError> {
+    let private_input: PrivateInput = serde_json::from_str(json)
+        .map_err(|err| BuiltinTraceError::InvalidPrivateInput(err.to_string()))?;
+    let parse = |value: &str| {
+        Felt252::from_hex(value)
+            .map_err(|_| BuiltinTraceError::InvalidPrivateInput(format!("invalid felt {value}")))
+    };
+    private_input
+        .ecdsa
+        .iter()
+        .map(|entry| {
+            let signature = EcdsaSignature {
+                r: parse(&entry.signature_input.r)?,
+                w: parse(&entry.signature_input.w)?,
+            };
+            Ok((entry.index, signature))
+        })
+        .collect()
+}
+
+fn generator() -> EcPoint {
+    ec_point(
+        "01ef15c18599971b7beced415a40f0c7deacfd9b0d1819e03d723d8bc943cfca",
+        "005668060aa49730b7be4801df46ec62de53ecd11abe43a32873000c36e8dc1f",
+    )
+}
+
+/// Order of the STARK curve.
+const CURVE_ORDER: &str = "0800000000000010ffffffffffffffffb781126dcae7b2321e66a241adc64d2f";
+
+pub(crate) fn periodic_columns() -> Vec<Vec<Felt252>> {
+    let zero_bits = (0..PART_ROWS)
+        .map(|i| {
+            if i >= N_BITS {
+                Felt252::one()
+            } else {
+                Felt252::zero()
+            }
+        })
+        .collect();
+    vec![zero_bits]
+}
+
+pub(crate) fn add_constraints(builder: &mut ConstraintBuilder, columns: usize, periodic: usize) {
+    let col = |column: usize| cur(columns + column);
+    let next_col = |column: usize| next(columns + column);
+    let bit = col(S) - next_col(S) - next_col(S);
+    let last_row = PART_ROWS - 1;
+
+    // Double-and-add steps.
+    builder.except(
+        PART_ROWS,
+        last_row,
+        bit.clone() * (bit.clone() - Expr::one()),
+    );
+    for expr in ec_conditional_add(
+        bit,
+        (col(AX), col(AY)),
+        (col(DX), col(DY)),
+        col(SLOPE_A),
+        (next_col(AX), next_col(AY)),
+    ) {
+        builder.except(PART_ROWS, last_row, expr);
+    }
+    let three = felt(Felt252::from(3));
+    builder.except(
+        PART_ROWS,
+        last_row,
+        col(SLOPE_D) * (col(DY) + col(DY)) - three * col(DX).square() - Expr::one(),
+    );
+    builder.except(
+        PART_ROWS,
+        last_row,
+        next_col(DX) - col(SLOPE_D).square() + col(DX) + col(DX),
+    );
+    builder.except(
+        PART_ROWS,
+        last_row,
+        next_col(DY) + col(DY) - col(SLOPE_D) * (col(DX) - next_col(DX)),
+    );
+    builder.every_row(Expr::periodic(periodic + ZERO_BITS) * col(S), 0);
+
+    let (shift_x, shift_y) = shift_point();
+    let shift = || (felt(shift_x), felt(shift_y));
+    let (generator_x, generator_y) = generator();
+
+    // z * G, with z nonzero.
+    builder.at(INSTANCE_PERIOD, 0, col(AX) - felt(shift_x));
+    builder.at(INSTANCE_PERIOD, 0, col(AY) - felt(shift_y));
+    builder.at(INSTANCE_PERIOD, 0, col(DX) - felt(generator_x));
+    builder.at(INSTANCE_PERIOD, 0, col(DY) - felt(generator_y));
+    builder.at(INSTANCE_PERIOD, 0, col(S) * col(H) - Expr::one());
+    builder.at(INSTANCE_PERIOD, 1, col(R) * col(H) - Expr::one());
+    builder.at(INSTANCE_PERIOD, last_row, next_col(AX) - col(AX));
+    builder.at(INSTANCE_PERIOD, last_row, next_col(AY) - col(AY));
+
+    // r * Q, with Q on the curve.
+    builder.at(INSTANCE_PERIOD, PART_ROWS, col(S) - col(R));
+    builder.at(INSTANCE_PERIOD, PART_ROWS, col(H) - col(DX).square());
+    builder.at(
+        INSTANCE_PERIOD,
+        PART_ROWS,
+        col(DY).square() - col(DX) * col(H) - col(DX) - felt(Felt252::from_hex_unchecked(EC_BETA)),
+    );
+    for expr in ec_add_constraints(
+        shift(),
+        (next_col(DX), next_col(DY)),
+        col(SLOPE_A),
+        (col(AX), col(AY)),
+    ) {
+        builder.at(INSTANCE_PERIOD, 2 * PART_ROWS - 1, expr);
+    }
+
+    // w * B, with w nonzero.
+    builder.at(INSTANCE_PERIOD, 2 * PART_ROWS, col(AX) - felt(shift_x));
+    builder.at(INSTANCE_PERIOD, 2 * PART_ROWS, col(AY) - felt(shift_y));
+    builder.at(
+        INSTANCE_PERIOD,
+        2 * PART_ROWS,
+        col(S) * col(H) - Expr::one(),
+    );
+    for expr in ec_add_constraints(shift(), (col(R), col(H)), col(SLOPE_A), (col(AX), col(AY))) {
+        builder.at(INSTANCE_PERIOD, 3 * PART_ROWS - 1, expr);
+    }
+    builder.except(INSTANCE_PERIOD, INSTANCE_PERIOD - 1, next_col(R) - col(R));
+}
+
+/// The trace of a scalar multiplication: the accumulator, the doubled point and their
+/// slopes, and the scalar shifted right by the row index.
+struct ScalarMultiplication {
+    acc: Vec<EcPoint>,
+    acc_slopes: Vec<Felt252>,
+    doubled: Vec<EcPoint>,
+    doubling_slopes: Vec<Felt252>,
+    shifted: Vec<Felt252>,
+}
+
+impl ScalarMultiplication {
+    fn new(acc: EcPoint, point: EcPoint, scalar: &Felt252) -> Result<Self, BuiltinTraceError> {
+        let bits = bits_le(scalar, PART_ROWS - 1);
+        let (acc, mut acc_slopes, doubled, mut doubling_slopes) =
+            ec_double_and_add(acc, point, &bits)?;
+        acc_slopes.push(Felt252::zero());
+        doubling_slopes.push(Felt252::zero());
+        Ok(Self {
+            acc,
+            acc_slopes,
+            doubled,
+            doubling_slopes,
+            shifted: shifted_values(scalar, PART_ROWS),
+        })
+    }
+
+    fn result(&self) -> EcPoint {
+        self.acc[PART_ROWS - 1]
+    }
+}
+
+/// Computes the columns of an instance, or returns `None` if the signature is not valid for
+/// the public key `pubkey`.
+fn try_instance_columns(
+    pubkey: EcPoint,
+    msg: &Felt252,
+    signature: &EcdsaSignature,
+) -> Result<Option<Vec<Vec<Felt252>>>, BuiltinTraceError> {
+    let shift = shift_point();
+    let minus_shift = (shift.0, -shift.1);
+
+    let z_part = ScalarMultiplication::new(shift, generator(), msg)?;
+    let r_part = ScalarMultiplication::new(z_part.result(), pubkey, &signature.r)?;
+    let (b, _) = ec_add(&r_part.result(), &minus_shift)?;
+    let (_, b_slope) = ec_add(&shift, &b)?;
+    let w_part = ScalarMultiplication::new(shift, b, &signature.w)?;
+    let (result, _) = ec_add(&w_part.result(), &minus_shift)?;
+    if result.0 != signature.r {
+        return Ok(None);
+    }
+    let (_, result_slope) = ec_add(&shift, &result)?;
+    let unused_part = ScalarMultiplication::new(shift, generator(), &Felt252::zero())?;
+
+    let mut columns = vec![Vec::new(); N_COLUMNS];
+    for part in [&z_part, &r_part, &w_part, &unused_part] {
+        columns[S].extend_from_slice(&part.shifted);
+        columns[AX].extend(part.acc.iter().map(|point| point.0));
+        columns[AY].extend(part.acc.iter().map(|point| point.1));
+        columns[SLOPE_A].extend_from_slice(&part.acc_slopes);
+        columns[DX].extend(part.doubled.iter().map(|point| point.0));
+        columns[DY].extend(part.doubled.iter().map(|point| point.1));
+        columns[SLOPE_D].extend_from_slice(&part.doubling_slopes);
+    }
+    columns[SLOPE_A][2 * PART_ROWS - 1] = b_slope;
+    columns[SLOPE_A][3 * PART_ROWS - 1] = result_slope;
+    columns[R] = vec![signature.r; INSTANCE_PERIOD];
+
+    let inverse = |value: &Felt252| {
+        value
+            .inv()
+            .map_err(|_| BuiltinTraceError::InputOutOfRange(*value))
+    };
+    columns[H] = vec![Felt252::zero(); INSTANCE_PERIOD];
+    columns[H][0] = inverse(msg)?;
+    columns[H][1] = inverse(&signature.r)?;
+    columns[H][PART_ROWS] = pubkey.0.square();
+    columns[H][2 * PART_ROWS] = inverse(&signature.w)?;
+    columns[H][3 * PART_ROWS - 1] = result.1;
+    Ok(Some(columns))
+}
+
+pub(crate) fn instance_columns(
+    inputs: &[Felt252],
+    signature: &EcdsaSignature,
+) -> Result<Vec<Vec<Felt252>>, BuiltinTraceError> {
+    let (pubkey_x, msg) = (inputs[0], inputs[1]);
+    for value in [&msg, &signature.r, &signature.w] {
+        if *value == Felt252::zero() || !fits_in_bits(value, N_BITS) {
+            return Err(BuiltinTraceError::InputOutOfRange(*value));
+        }
+    }
+
+    // The public key is given by its x coordinate, so both points with that x are tried.
+    let beta = Felt252::from_hex_unchecked(EC_BETA);
+    let (y, minus_y) = (pubkey_x.square() * pubkey_x + pubkey_x + beta)
+        .sqrt()
+        .ok_or(BuiltinTraceError::InvalidSignature(pubkey_x))?;
+    for pubkey_y in [y, minus_y] {
+        if let Some(columns) = try_instance_columns((pubkey_x, pubkey_y), &msg, signature)? {
+            return Ok(columns);
+        }
+    }
+    Err(BuiltinTraceError::InvalidSignature(pubkey_x))
+}
+
+/// The columns of an instance signing with the key `G` and `r = G.x`, `w = 1` and
+/// `z = 1 - r` modulo the curve order, so that `w * (z * G + r * G) = G`.
+pub(crate) fn padding_columns() -> Vec<Vec<Felt252>> {
+    let generator_x = generator().0;
+    let msg = Felt252::from_hex_unchecked(CURVE_ORDER) + Felt252::one() - generator_x;
+    let signature = EcdsaSignature {
+        r: generator_x,
+        w: Felt252::one(),
+    };
+    instance_columns(&[generator_x, msg], &signature).unwrap()
+}
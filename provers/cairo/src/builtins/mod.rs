@@ -0,0 +1,928 @@
+//! AIR components of the Cairo builtins.
+//!
+//! Each builtin instance takes a fixed number of consecutive rows of the trace (the instance
+//! period) in a group of columns of its own, placed after the CPU columns. Besides the columns
+//! used to compute the builtin, every component has four columns:
+//!  * `ACTIVE`: 1 in the instances used by the program and 0 in the ones padding the trace.
+//!  * `BASE`: the address of the first memory cell of the instance.
+//!  * `MEM_ADDR` and `MEM_VAL`: a memory access per row, which is added to the memory
+//!    permutation argument. The rows holding a memory cell of an active instance access it and
+//!    the rest access the `(0, 0)` dummy address.
+//!
+//! The layouts only share with the Stone layouts the builtins, their ratios and the memory cells
+//! of each instance: the number of cells, the input bounds and the function computed by the
+//! builtin are the same. The trace layout, the column offsets and the constraints are the ones
+//! of this AIR, not Stone's, so a proof of a layout isn't a Stone proof of that layout and can't
+//! be checked by Stone verifiers.
+
+pub mod bitwise;
+pub mod ecdsa;
+pub mod pedersen;
+pub mod poseidon;
+pub mod range_check;
+
+use crate::{
+    air::{MemorySegmentMap, Segment, SegmentName, RC_HOLES},
+    cairo_layout::{CairoLayout, LayoutBuiltin},
+    cairo_mem::CairoMemory,
+    errors::BuiltinTraceError,
+    Felt252,
+};
+use lambdaworks_math::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
+use stark_platinum_prover::constraints::{
+    boundary::BoundaryConstraint,
+    symbolic::{Expr, SymbolicConstraint},
+    transition::TransitionConstraint,
+};
+
+pub use ecdsa::{EcdsaSignature, EcdsaSignatures};
+
+/// Index of the first main trace column used by the builtin components.
+pub const BUILTINS_FIRST_COLUMN: usize = RC_HOLES + 1;
+
+// Columns shared by every builtin component, relative to its first column.
+pub const BUILTIN_ACTIVE: usize = 0;
+pub const BUILTIN_BASE: usize = 1;
+pub const BUILTIN_MEM_ADDR: usize = 2;
+pub const BUILTIN_MEM_VAL: usize = 3;
+const N_SHARED_COLUMNS: usize = 4;
+
+// Periodic columns shared by every builtin component, relative to its first periodic column.
+const CELL_SELECTOR: usize = 0;
+const INSTANCE_END: usize = 1;
+const N_SHARED_PERIODIC_COLUMNS: usize = 2;
+
+pub(crate) type CairoExpr = Expr<Stark252PrimeField>;
+type CairoTransitionConstraint =
+    Box<dyn TransitionConstraint<Stark252PrimeField, Stark252PrimeField>>;
+
+/// Element of the main trace at column `col` of the current row.
+pub(crate) fn cur(col: usize) -> CairoExpr {
+    Expr::main(0, col)
+}
+
+/// Element of the main trace at column `col` of the next row.
+pub(crate) fn next(col: usize) -> CairoExpr {
+    Expr::main(1, col)
+}
+
+pub(crate) fn felt(value: Felt252) -> CairoExpr {
+    Expr::constant(value)
+}
+
+/// Collects transition constraints, numbering them consecutively from a given index.
+pub(crate) struct ConstraintBuilder {
+    constraints: Vec<CairoTransitionConstraint>,
+    next_idx: usize,
+}
+
+impl ConstraintBuilder {
+    pub(crate) fn new(first_idx: usize) -> Self {
+        Self {
+            constraints: Vec::new(),
+            next_idx: first_idx,
+        }
+    }
+
+    fn push(&mut self, constraint: SymbolicConstraint<Stark252PrimeField, Stark252PrimeField>) {
+        self.constraints.push(Box::new(constraint));
+        self.next_idx += 1;
+    }
+
+    /// Adds a constraint that holds in every row but the last `end_exemptions` ones.
+    pub(crate) fn every_row(&mut self, expr: CairoExpr, end_exemptions: usize) {
+        self.push(SymbolicConstraint::new(self.next_idx, expr, end_exemptions));
+    }
+
+    /// Adds a constraint that holds in the rows `offset`, `offset + period`, etc.
+    pub(crate) fn at(&mut self, period: usize, offset: usize, expr: CairoExpr) {
+        self.push(SymbolicConstraint::new(self.next_idx, expr, 0).with_period(period, offset));
+    }
+
+    /// Adds a constraint that holds in every row except `offset`, `offset + period`, etc.
+    pub(crate) fn except(&mut self, period: usize, offset: usize, expr: CairoExpr) {
+        self.push(
+            SymbolicConstraint::new(self.next_idx, expr, 0)
+                .with_periodic_exemptions(period, offset),
+        );
+    }
+
+    pub(crate) fn build(self) -> Vec<CairoTransitionConstraint> {
+        self.constraints
+    }
+}
+
+/// The builtins with an AIR component.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Builtin {
+    Pedersen,
+    RangeCheck,
+    Ecdsa,
+    Bitwise,
+    Poseidon,
+}
+
+impl Builtin {
+    /// The builtins in the order their columns are laid out in the trace.
+    pub const ALL: [Builtin; 5] = [
+        Builtin::Pedersen,
+        Builtin::RangeCheck,
+        Builtin::Ecdsa,
+        Builtin::Bitwise,
+        Builtin::Poseidon,
+    ];
+
+    pub fn segment_name(&self) -> SegmentName {
+        match self {
+            Builtin::Pedersen => SegmentName::Pedersen,
+            Builtin::RangeCheck => SegmentName::RangeCheck,
+            Builtin::Ecdsa => SegmentName::Ecdsa,
+            Builtin::Bitwise => SegmentName::Bitwise,
+            Builtin::Poseidon => SegmentName::Poseidon,
+        }
+    }
+
+    /// Number of memory cells of each instance.
+    pub fn cells_per_instance(&self) -> usize {
+        match self {
+            Builtin::Pedersen => pedersen::CELLS_PER_INSTANCE,
+            Builtin::RangeCheck => range_check::CELLS_PER_INSTANCE,
+            Builtin::Ecdsa => ecdsa::CELLS_PER_INSTANCE,
+            Builtin::Bitwise => bitwise::CELLS_PER_INSTANCE,
+            Builtin::Poseidon => poseidon::CELLS_PER_INSTANCE,
+        }
+    }
+
+    /// Number of trace rows taken by each instance.
+    pub fn instance_period(&self) -> usize {
+        match self {
+            Builtin::Pedersen => pedersen::INSTANCE_PERIOD,
+            Builtin::RangeCheck => range_check::INSTANCE_PERIOD,
+            Builtin::Ecdsa => ecdsa::INSTANCE_PERIOD,
+            Builtin::Bitwise => bitwise::INSTANCE_PERIOD,
+            Builtin::Poseidon => poseidon::INSTANCE_PERIOD,
+        }
+    }
+
+    /// Number of memory cells of each instance written by the program.
+    fn n_inputs(&self) -> usize {
+        match self {
+            Builtin::Pedersen => pedersen::N_INPUTS,
+            Builtin::RangeCheck => range_check::N_INPUTS,
+            Builtin::Ecdsa => ecdsa::N_INPUTS,
+            Builtin::Bitwise => bitwise::N_INPUTS,
+            Builtin::Poseidon => poseidon::N_INPUTS,
+        }
+    }
+
+    /// Number of columns used to compute the builtin.
+    fn n_columns(&self) -> usize {
+        match self {
+            Builtin::Pedersen => pedersen::N_COLUMNS,
+            Builtin::RangeCheck => range_check::N_COLUMNS,
+            Builtin::Ecdsa => ecdsa::N_COLUMNS,
+            Builtin::Bitwise => bitwise::N_COLUMNS,
+            Builtin::Poseidon => poseidon::N_COLUMNS,
+        }
+    }
+
+    /// For each memory cell of an instance, the row of the instance and the column holding
+    /// its value.
+    fn cells(&self) -> &'static [(usize, usize)] {
+        match self {
+            Builtin::Pedersen => &pedersen::CELLS,
+            Builtin::RangeCheck => &range_check::CELLS,
+            Builtin::Ecdsa => &ecdsa::CELLS,
+            Builtin::Bitwise => &bitwise::CELLS,
+            Builtin::Poseidon => &poseidon::CELLS,
+        }
+    }
+
+    fn periodic_columns(&self) -> Vec<Vec<Felt252>> {
+        match self {
+            Builtin::Pedersen => pedersen::periodic_columns(),
+            Builtin::RangeCheck => range_check::periodic_columns(),
+            Builtin::Ecdsa => ecdsa::periodic_columns(),
+            Builtin::Bitwise => bitwise::periodic_columns(),
+            Builtin::Poseidon => poseidon::periodic_columns(),
+        }
+    }
+
+    fn add_constraints(&self, builder: &mut ConstraintBuilder, columns: usize, periodic: usize) {
+        match self {
+            Builtin::Pedersen => pedersen::add_constraints(builder, columns, periodic),
+            Builtin::RangeCheck => range_check::add_constraints(builder, columns, periodic),
+            Builtin::Ecdsa => ecdsa::add_constraints(builder, columns, periodic),
+            Builtin::Bitwise => bitwise::add_constraints(builder, columns, periodic),
+            Builtin::Poseidon => poseidon::add_constraints(builder, columns, periodic),
+        }
+    }
+
+    /// The column of the builtin whose values are added to the range-check argument, if any.
+    fn range_checked_column(&self) -> Option<usize> {
+        match self {
+            Builtin::RangeCheck => Some(range_check::LIMB),
+            _ => None,
+        }
+    }
+}
+
+/// The columns of a builtin component in the trace.
+#[derive(Clone, Debug)]
+pub struct BuiltinComponent {
+    pub builtin: Builtin,
+    pub segment: Segment,
+    /// Number of trace steps per instance, given by the layout.
+    pub ratio: usize,
+    /// Main trace column of the `ACTIVE` column of the component.
+    pub first_column: usize,
+    /// Index of the first periodic column of the component.
+    pub first_periodic_column: usize,
+}
+
+impl BuiltinComponent {
+    /// Number of instances used by the program.
+    pub fn n_instances(&self) -> usize {
+        (self.segment.stop_ptr - self.segment.begin_addr) / self.builtin.cells_per_instance()
+    }
+
+    /// Number of main trace columns of the component.
+    pub fn n_columns(&self) -> usize {
+        N_SHARED_COLUMNS + self.builtin.n_columns()
+    }
+
+    pub fn n_periodic_columns(&self) -> usize {
+        N_SHARED_PERIODIC_COLUMNS + self.builtin.periodic_columns().len()
+    }
+
+    pub fn mem_addr_column(&self) -> usize {
+        self.first_column + BUILTIN_MEM_ADDR
+    }
+
+    pub fn mem_val_column(&self) -> usize {
+        self.first_column + BUILTIN_MEM_VAL
+    }
+
+    /// Main trace column added to the range-check argument, if the builtin has one.
+    pub fn range_check_column(&self) -> Option<usize> {
+        self.builtin
+            .range_checked_column()
+            .map(|column| self.first_column + N_SHARED_COLUMNS + column)
+    }
+
+    /// The periodic columns of the component: the shared selectors of the cell rows and of the
+    /// last row of each instance, followed by the ones of the builtin.
+    pub fn periodic_columns(&self) -> Vec<Vec<Felt252>> {
+        let period = self.builtin.instance_period();
+        let mut cell_selector = vec![Felt252::zero(); period];
+        for (row, _) in self.builtin.cells() {
+            cell_selector[*row] = Felt252::one();
+        }
+        let mut instance_end = vec![Felt252::zero(); period];
+        instance_end[period - 1] = Felt252::one();
+
+        let mut columns = vec![cell_selector, instance_end];
+        columns.extend(self.builtin.periodic_columns());
+        columns
+    }
+
+    /// Transition constraints of the component, numbered from `first_idx`.
+    ///
+    /// The shared constraints state that `ACTIVE` is a boolean constant in each instance that
+    /// can only go from 1 to 0, that `BASE` grows by the number of cells per instance, and that
+    /// the memory accesses of the cell rows are `(BASE + k, value_k)` in the active instances
+    /// and `(0, 0)` everywhere else.
+    pub(crate) fn transition_constraints(
+        &self,
+        first_idx: usize,
+    ) -> Vec<CairoTransitionConstraint> {
+        let mut builder = ConstraintBuilder::new(first_idx);
+        let period = self.builtin.instance_period();
+        let cells = Felt252::from(self.builtin.cells_per_instance() as u64);
+        let active = self.first_column + BUILTIN_ACTIVE;
+        let base = self.first_column + BUILTIN_BASE;
+        let mem_addr = self.first_column + BUILTIN_MEM_ADDR;
+        let mem_val = self.first_column + BUILTIN_MEM_VAL;
+        let cell_selector = Expr::periodic(self.first_periodic_column + CELL_SELECTOR);
+        let instance_end = Expr::periodic(self.first_periodic_column + INSTANCE_END);
+
+        builder.at(period, 0, cur(active) * (cur(active) - Expr::one()));
+        builder.except(period, period - 1, next(active) - cur(active));
+        builder.every_row(
+            instance_end.clone() * next(active) * (Expr::one() - cur(active)),
+            1,
+        );
+        builder.except(period, period - 1, next(base) - cur(base));
+        builder.every_row(instance_end * (next(base) - cur(base) - felt(cells)), 1);
+        builder.every_row((Expr::one() - cell_selector.clone()) * cur(mem_addr), 0);
+        builder.every_row((Expr::one() - cell_selector) * cur(mem_val), 0);
+
+        let columns = self.first_column + N_SHARED_COLUMNS;
+        for (k, (row, column)) in self.builtin.cells().iter().enumerate() {
+            let address = cur(base) + felt(Felt252::from(k as u64));
+            builder.at(period, *row, cur(mem_addr) - cur(active) * address);
+            builder.at(
+                period,
+                *row,
+                cur(mem_val) - cur(active) * cur(columns + column),
+            );
+        }
+
+        let periodic = self.first_periodic_column + N_SHARED_PERIODIC_COLUMNS;
+        self.builtin
+            .add_constraints(&mut builder, columns, periodic);
+        builder.build()
+    }
+
+    /// Boundary constraints of the component: the address of the first instance, and the rows
+    /// where `ACTIVE` goes from 1 to 0.
+    pub fn boundary_constraints(
+        &self,
+        trace_length: usize,
+    ) -> Vec<BoundaryConstraint<Stark252PrimeField>> {
+        let period = self.builtin.instance_period();
+        let n_instances = self.n_instances();
+        let active = self.first_column + BUILTIN_ACTIVE;
+
+        let mut constraints = vec![BoundaryConstraint::new_main(
+            self.first_column + BUILTIN_BASE,
+            0,
+            Felt252::from(self.segment.begin_addr as u64),
+        )];
+        if n_instances > 0 {
+            constraints.push(BoundaryConstraint::new_main(
+                active,
+                (n_instances - 1) * period,
+                Felt252::one(),
+            ));
+        }
+        if n_instances * period < trace_length {
+            constraints.push(BoundaryConstraint::new_main(
+                active,
+                n_instances * period,
+                Felt252::zero(),
+            ));
+        }
+        constraints
+    }
+
+    /// Number of trace rows reserved for each instance: the layout reserves `ratio` steps per
+    /// instance, as Stone does, and the columns of the component compute an instance in
+    /// `instance_period` rows.
+    pub fn rows_per_instance(&self) -> usize {
+        self.ratio.max(self.builtin.instance_period())
+    }
+
+    /// Minimum trace length needed to fit the instances used by the program. There is always
+    /// room for at least one instance, so that all the columns of the component are defined.
+    pub fn min_trace_length(&self) -> usize {
+        self.n_instances().max(1) * self.rows_per_instance()
+    }
+
+    /// Checks that the segment of the component holds whole instances, and that they fit in a
+    /// trace of length `trace_length`.
+    pub fn check_instances(&self, trace_length: usize) -> Result<(), BuiltinTraceError> {
+        let segment_size = self.segment.stop_ptr - self.segment.begin_addr;
+        if segment_size % self.builtin.cells_per_instance() != 0 {
+            return Err(BuiltinTraceError::IncompleteInstance(self.builtin));
+        }
+        if self.min_trace_length() > trace_length {
+            return Err(BuiltinTraceError::TraceTooShort(self.builtin));
+        }
+        Ok(())
+    }
+}
+
+/// The components of the builtins of `layout`, in the order their columns are laid out in the
+/// trace. The builtins of the `dynamic` layout are the ones with a segment in `memory_segments`,
+/// with the ratio of their instance period: the densest layout of their columns.
+///
+/// Fails if the layout is not supported, or if the program uses a builtin that is not in the
+/// layout.
+pub fn builtin_components(
+    layout: &CairoLayout,
+    memory_segments: &MemorySegmentMap,
+) -> Result<Vec<BuiltinComponent>, BuiltinTraceError> {
+    let builtins = match layout {
+        CairoLayout::Dynamic => Builtin::ALL
+            .iter()
+            .filter(|builtin| memory_segments.contains_key(&builtin.segment_name()))
+            .map(|builtin| LayoutBuiltin {
+                builtin: *builtin,
+                ratio: builtin.instance_period(),
+            })
+            .collect(),
+        _ => layout
+            .builtins()
+            .ok_or(BuiltinTraceError::UnsupportedLayout(*layout))?
+            .to_vec(),
+    };
+    if let Some(builtin) = Builtin::ALL.iter().find(|builtin| {
+        memory_segments.contains_key(&builtin.segment_name())
+            && builtins.iter().all(|b| b.builtin != **builtin)
+    }) {
+        return Err(BuiltinTraceError::BuiltinNotInLayout(*builtin));
+    }
+
+    let mut first_column = BUILTINS_FIRST_COLUMN;
+    let mut first_periodic_column = 0;
+    Ok(builtins
+        .iter()
+        .map(|LayoutBuiltin { builtin, ratio }| {
+            // The builtins of the layout unused by the program only have padding instances.
+            let segment = memory_segments
+                .get(&builtin.segment_name())
+                .cloned()
+                .unwrap_or(Segment::new(0, 0));
+            let component = BuiltinComponent {
+                builtin: *builtin,
+                segment,
+                ratio: *ratio,
+                first_column,
+                first_periodic_column,
+            };
+            first_column += component.n_columns();
+            first_periodic_column += component.n_periodic_columns();
+            component
+        })
+        .collect())
+}
+
+/// The trace columns of a builtin component, before being padded to the trace length.
+pub(crate) struct BuiltinTrace {
+    component: BuiltinComponent,
+    /// Columns of the instances used by the program.
+    columns: Vec<Vec<Felt252>>,
+    /// Columns of the instance used to pad the component.
+    padding: Vec<Vec<Felt252>>,
+}
+
+impl BuiltinTrace {
+    /// Computes the instances of a builtin from their inputs in memory.
+    pub(crate) fn new(
+        component: &BuiltinComponent,
+        memory: &CairoMemory,
+        ecdsa_signatures: &EcdsaSignatures,
+    ) -> Result<Self, BuiltinTraceError> {
+        let builtin = component.builtin;
+        let segment = &component.segment;
+        let cells_per_instance = builtin.cells_per_instance();
+        if (segment.stop_ptr - segment.begin_addr) % cells_per_instance != 0 {
+            return Err(BuiltinTraceError::IncompleteInstance(builtin));
+        }
+
+        let pedersen_points = match builtin {
+            Builtin::Pedersen => pedersen::row_points(),
+            _ => Vec::new(),
+        };
+        let instance_columns = |index: usize, inputs: &[Felt252]| match builtin {
+            Builtin::Pedersen => pedersen::instance_columns(inputs, &pedersen_points),
+            Builtin::Ecdsa => {
+                let signature = ecdsa_signatures
+                    .get(&index)
+                    .ok_or(BuiltinTraceError::MissingSignature(index))?;
+                ecdsa::instance_columns(inputs, signature)
+            }
+            Builtin::RangeCheck => range_check::instance_columns(inputs),
+            Builtin::Bitwise => bitwise::instance_columns(inputs),
+            Builtin::Poseidon => poseidon::instance_columns(inputs),
+        };
+
+        let mut columns = vec![Vec::new(); builtin.n_columns()];
+        for index in 0..component.n_instances() {
+            let base = (segment.begin_addr + index * cells_per_instance) as u64;
+            let inputs = (0..builtin.n_inputs() as u64)
+                .map(|k| {
+                    memory
+                        .get(&(base + k))
+                        .copied()
+                        .ok_or(BuiltinTraceError::MissingInput(base + k))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            for (column, values) in columns.iter_mut().zip(instance_columns(index, &inputs)?) {
+                column.extend(values);
+            }
+        }
+
+        let padding = match builtin {
+            Builtin::Pedersen => pedersen::padding_columns(&pedersen_points),
+            Builtin::RangeCheck => range_check::padding_columns(&Felt252::zero()),
+            Builtin::Ecdsa => ecdsa::padding_columns(),
+            Builtin::Bitwise => bitwise::padding_columns(),
+            Builtin::Poseidon => poseidon::padding_columns(),
+        };
+
+        Ok(Self {
+            component: component.clone(),
+            columns,
+            padding,
+        })
+    }
+
+    pub(crate) fn min_trace_length(&self) -> usize {
+        self.component.min_trace_length()
+    }
+
+    /// Values of the instances used by the program that are added to the range-check argument.
+    pub(crate) fn range_checked_values(&self) -> &[Felt252] {
+        match self.builtin().range_checked_column() {
+            Some(column) => &self.columns[column],
+            None => &[],
+        }
+    }
+
+    /// Sets the range-checked values of the padding instances to `value`, which should be in
+    /// the range of the range-check argument.
+    pub(crate) fn set_range_check_padding(&mut self, value: &Felt252) {
+        if self.builtin() == Builtin::RangeCheck {
+            self.padding = range_check::padding_columns(value);
+        }
+    }
+
+    fn builtin(&self) -> Builtin {
+        self.component.builtin
+    }
+
+    /// Addresses of the memory cells of the instances used by the program.
+    pub(crate) fn addresses(&self) -> impl Iterator<Item = Felt252> + '_ {
+        let segment = &self.component.segment;
+        (segment.begin_addr..segment.stop_ptr).map(|address| Felt252::from(address as u64))
+    }
+
+    /// Returns the columns of the component, padded with inactive instances up to
+    /// `trace_length` rows.
+    pub(crate) fn into_columns(self, trace_length: usize) -> Vec<Vec<Felt252>> {
+        let component = &self.component;
+        let builtin = component.builtin;
+        let period = builtin.instance_period();
+        let n_instances = component.n_instances();
+        debug_assert!(trace_length >= component.min_trace_length());
+
+        let mut columns = self.columns;
+        for _ in n_instances..trace_length / period {
+            for (column, values) in columns.iter_mut().zip(&self.padding) {
+                column.extend_from_slice(values);
+            }
+        }
+
+        let mut active = vec![Felt252::one(); n_instances * period];
+        active.resize(trace_length, Felt252::zero());
+        let base = (0..trace_length)
+            .map(|row| {
+                let instance = row / period;
+                Felt252::from(
+                    (component.segment.begin_addr + instance * builtin.cells_per_instance()) as u64,
+                )
+            })
+            .collect();
+
+        let mut mem_addr = vec![Felt252::zero(); trace_length];
+        let mut mem_val = vec![Felt252::zero(); trace_length];
+        for instance in 0..n_instances {
+            let first_address =
+                component.segment.begin_addr + instance * builtin.cells_per_instance();
+            for (k, (row, column)) in builtin.cells().iter().enumerate() {
+                let row = instance * period + row;
+                mem_addr[row] = Felt252::from((first_address + k) as u64);
+                mem_val[row] = columns[*column][row];
+            }
+        }
+
+        let mut component_columns = vec![active, base, mem_addr, mem_val];
+        component_columns.extend(columns);
+        component_columns
+    }
+}
+
+// Parameters of the STARK curve, y^2 = x^3 + alpha * x + beta, with alpha = 1.
+pub(crate) const EC_BETA: &str = "6f21413efbe40de150e596d72f7a8c5609ad26c15c915c1f4cdfcb99cee9e89";
+
+/// Point of the STARK curve in affine coordinates.
+pub(crate) type EcPoint = (Felt252, Felt252);
+
+pub(crate) fn ec_point(x: &str, y: &str) -> EcPoint {
+    (
+        Felt252::from_hex_unchecked(x),
+        Felt252::from_hex_unchecked(y),
+    )
+}
+
+/// The shift point of the Pedersen hash, also used as the initial value of the accumulators of
+/// the ECDSA builtin.
+pub(crate) fn shift_point() -> EcPoint {
+    ec_point(
+        "049ee3eba8c1600700ee1b87eb599f16716b0b1022947733551fde4050ca6804",
+        "03ca0cfe4b3bc6ddf346d49d06ea0ed34e621062c0e056c1d0405d266e10268a",
+    )
+}
+
+/// Adds two points of the STARK curve with different x coordinates, returning the result and
+/// the slope of the line through them.
+pub(crate) fn ec_add(p: &EcPoint, q: &EcPoint) -> Result<(EcPoint, Felt252), BuiltinTraceError> {
+    let slope = (q.1 - p.1)
+        * (q.0 - p.0)
+            .inv()
+            .map_err(|_| BuiltinTraceError::DegenerateEcAddition)?;
+    let x = slope.square() - p.0 - q.0;
+    let y = slope * (p.0 - x) - p.1;
+    Ok(((x, y), slope))
+}
+
+/// Doubles a point of the STARK curve, returning the result and the slope of the tangent line.
+pub(crate) fn ec_double(p: &EcPoint) -> Result<(EcPoint, Felt252), BuiltinTraceError> {
+    let slope = (Felt252::from(3) * p.0.square() + Felt252::one())
+        * (Felt252::from(2) * p.1)
+            .inv()
+            .map_err(|_| BuiltinTraceError::DegenerateEcAddition)?;
+    let x = slope.square() - Felt252::from(2) * p.0;
+    let y = slope * (p.0 - x) - p.1;
+    Ok(((x, y), slope))
+}
+
+/// Constraints of a step of a double-and-add, `A' = A + bit * Q`, where `slope` is the slope
+/// of the line through `A` and `Q` when the bit is set, and zero otherwise. The bit must be
+/// constrained to be boolean separately.
+pub(crate) fn ec_conditional_add(
+    bit: CairoExpr,
+    a: (CairoExpr, CairoExpr),
+    q: (CairoExpr, CairoExpr),
+    slope: CairoExpr,
+    a_next: (CairoExpr, CairoExpr),
+) -> [CairoExpr; 5] {
+    let (ax, ay) = a;
+    let (qx, qy) = q;
+    let (ax_next, ay_next) = a_next;
+    let not_bit = Expr::one() - bit.clone();
+    [
+        bit.clone() * (ay.clone() - qy) - slope.clone() * (ax.clone() - qx.clone()),
+        slope.clone().square() - bit.clone() * (ax.clone() + qx + ax_next.clone()),
+        bit * (ay.clone() + ay_next.clone()) - slope * (ax.clone() - ax_next.clone()),
+        not_bit.clone() * (ax_next - ax),
+        not_bit * (ay_next - ay),
+    ]
+}
+
+/// Constraints of `R = P + Q` for points of the STARK curve, given the slope of the line
+/// through `P` and `Q`.
+pub(crate) fn ec_add_constraints(
+    p: (CairoExpr, CairoExpr),
+    q: (CairoExpr, CairoExpr),
+    slope: CairoExpr,
+    r: (CairoExpr, CairoExpr),
+) -> [CairoExpr; 3] {
+    let (px, py) = p;
+    let (qx, qy) = q;
+    let (rx, ry) = r;
+    [
+        slope.clone() * (qx.clone() - px.clone()) - (qy - py.clone()),
+        rx.clone() - (slope.clone().square() - px.clone() - qx),
+        ry - (slope * (px - rx) - py),
+    ]
+}
+
+/// Computes the trace of a double-and-add over `n_bits` bits of `scalar`, starting from
+/// `a` and `q`: the rows `i` in `0..n_bits` hold `A_i`, the slope of `A_i + b_i * Q_i` (zero
+/// if `b_i = 0`), `Q_i = 2^i * q` and the slope of its doubling. Only the rows whose bit is
+/// set add `Q_i`.
+#[allow(clippy::type_complexity)]
+pub(crate) fn ec_double_and_add(
+    a: EcPoint,
+    q: EcPoint,
+    bits: &[bool],
+) -> Result<(Vec<EcPoint>, Vec<Felt252>, Vec<EcPoint>, Vec<Felt252>), BuiltinTraceError> {
+    let mut acc = vec![a];
+    let mut acc_slopes = Vec::with_capacity(bits.len());
+    let mut doubled = vec![q];
+    let mut doubling_slopes = Vec::with_capacity(bits.len());
+    for bit in bits {
+        let current = *acc.last().unwrap();
+        let q = *doubled.last().unwrap();
+        if *bit {
+            let (sum, slope) = ec_add(&current, &q)?;
+            acc.push(sum);
+            acc_slopes.push(slope);
+        } else {
+            acc.push(current);
+            acc_slopes.push(Felt252::zero());
+        }
+        let (double, slope) = ec_double(&q)?;
+        doubled.push(double);
+        doubling_slopes.push(slope);
+    }
+    Ok((acc, acc_slopes, doubled, doubling_slopes))
+}
+
+/// The bits of `value`, least significant first.
+pub(crate) fn bits_le(value: &Felt252, n_bits: usize) -> Vec<bool> {
+    let value = value.representative();
+    (0..n_bits)
+        .map(|i| {
+            let limb = value.limbs[3 - i / 64];
+            (limb >> (i % 64)) & 1 == 1
+        })
+        .collect()
+}
+
+/// The values `value >> i` for `i` in `0..n_rows`.
+pub(crate) fn shifted_values(value: &Felt252, n_rows: usize) -> Vec<Felt252> {
+    let value = value.representative();
+    (0..n_rows).map(|i| Felt252::from(&(value >> i))).collect()
+}
+
+/// Returns true if `value` is smaller than `2^n_bits`.
+pub(crate) fn fits_in_bits(value: &Felt252, n_bits: usize) -> bool {
+    value.representative().bits_le() <= n_bits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lambdaworks_crypto::hash::{
+        pedersen::{Pedersen, PedersenStarkCurve},
+        poseidon::{starknet::PoseidonCairoStark252, Poseidon},
+    };
+
+    /// Reads the memory cells of an instance from its columns.
+    fn cells(builtin: Builtin, columns: &[Vec<Felt252>]) -> Vec<Felt252> {
+        builtin
+            .cells()
+            .iter()
+            .map(|&(row, column)| columns[column][row])
+            .collect()
+    }
+
+    #[test]
+    fn pedersen_instance_outputs_the_pedersen_hash() {
+        let points = pedersen::row_points();
+        let inputs = [
+            Felt252::from_hex_unchecked(
+                "3d937c035c878245caf64531a5756109c53068da139362728feb561405371cb",
+            ),
+            Felt252::from_hex_unchecked(
+                "208a0a10250e382e1e4bbe2880906c2791bf6275695e02fbbc6aeff9cd8b31a",
+            ),
+        ];
+        let columns = pedersen::instance_columns(&inputs, &points).unwrap();
+
+        assert_eq!(
+            cells(Builtin::Pedersen, &columns),
+            [
+                inputs[0],
+                inputs[1],
+                PedersenStarkCurve::hash(&inputs[0], &inputs[1])
+            ]
+        );
+    }
+
+    #[test]
+    fn pedersen_instance_hashes_inputs_with_the_top_bit_set() {
+        let points = pedersen::row_points();
+        let inputs = [-Felt252::one(), Felt252::from(7)];
+        let columns = pedersen::instance_columns(&inputs, &points).unwrap();
+
+        assert_eq!(
+            cells(Builtin::Pedersen, &columns)[2],
+            PedersenStarkCurve::hash(&inputs[0], &inputs[1])
+        );
+    }
+
+    #[test]
+    fn poseidon_instance_outputs_the_hades_permutation() {
+        let inputs = [Felt252::from(1), Felt252::from(2), Felt252::from(3)];
+        let mut state = inputs;
+        PoseidonCairoStark252::hades_permutation(&mut state);
+        let columns = poseidon::instance_columns(&inputs).unwrap();
+
+        assert_eq!(
+            cells(Builtin::Poseidon, &columns),
+            [inputs.as_slice(), state.as_slice()].concat()
+        );
+    }
+
+    #[test]
+    fn bitwise_instance_outputs_and_xor_and_or() {
+        let x = Felt252::from(2).pow(250_u64) + Felt252::from(12);
+        let y = Felt252::from(10);
+        let columns = bitwise::instance_columns(&[x, y]).unwrap();
+
+        assert_eq!(
+            cells(Builtin::Bitwise, &columns),
+            [
+                x,
+                y,
+                Felt252::from(8),
+                Felt252::from(2).pow(250_u64) + Felt252::from(6),
+                Felt252::from(2).pow(250_u64) + Felt252::from(14),
+            ]
+        );
+    }
+
+    #[test]
+    fn bitwise_instance_rejects_inputs_of_more_than_251_bits() {
+        let x = Felt252::from(2).pow(251_u64);
+
+        assert_eq!(
+            bitwise::instance_columns(&[x, Felt252::one()]),
+            Err(BuiltinTraceError::InputOutOfRange(x))
+        );
+    }
+
+    #[test]
+    fn range_check_instance_splits_the_value_into_limbs() {
+        let value = Felt252::from_hex_unchecked("123456789abcdef0fedcba9876543210");
+        let columns = range_check::instance_columns(&[value]).unwrap();
+
+        assert_eq!(cells(Builtin::RangeCheck, &columns), [value]);
+        assert_eq!(
+            columns[range_check::LIMB],
+            [0x1234, 0x5678, 0x9abc, 0xdef0, 0xfedc, 0xba98, 0x7654, 0x3210].map(Felt252::from)
+        );
+    }
+
+    #[test]
+    fn range_check_instance_rejects_values_of_more_than_128_bits() {
+        let value = Felt252::from(2).pow(128_u64);
+
+        assert_eq!(
+            range_check::instance_columns(&[value]),
+            Err(BuiltinTraceError::InputOutOfRange(value))
+        );
+    }
+
+    #[test]
+    fn ecdsa_instance_rejects_an_invalid_signature() {
+        // The padding instance holds a valid signature, which no longer verifies with another `w`.
+        let inputs = cells(Builtin::Ecdsa, &ecdsa::padding_columns());
+        let signature = EcdsaSignature {
+            r: inputs[0],
+            w: Felt252::from(2),
+        };
+
+        assert_eq!(
+            ecdsa::instance_columns(&inputs, &signature),
+            Err(BuiltinTraceError::InvalidSignature(inputs[0]))
+        );
+    }
+
+    #[test]
+    fn components_reserve_the_rows_of_the_layout_ratio() {
+        let mut memory_segments = MemorySegmentMap::new();
+        memory_segments.insert(SegmentName::Pedersen, Segment::new(10, 16));
+        memory_segments.insert(SegmentName::RangeCheck, Segment::new(20, 30));
+
+        // Two Pedersen instances of 512 rows, with 128 steps per instance in the recursive layout.
+        let recursive = builtin_components(&CairoLayout::Recursive, &memory_segments).unwrap();
+        assert_eq!(recursive[0].builtin, Builtin::Pedersen);
+        assert_eq!(recursive[0].min_trace_length(), 1024);
+        // Ten range-check instances of 8 rows, with 8 steps per instance.
+        assert_eq!(recursive[1].min_trace_length(), 80);
+
+        let mut component = recursive[0].clone();
+        component.ratio = 2048;
+        assert_eq!(component.min_trace_length(), 4096);
+        assert_eq!(
+            component.check_instances(2048),
+            Err(BuiltinTraceError::TraceTooShort(Builtin::Pedersen))
+        );
+        assert_eq!(component.check_instances(4096), Ok(()));
+    }
+
+    #[test]
+    fn dynamic_layout_components_take_the_ratio_of_their_instance_period() {
+        let mut memory_segments = MemorySegmentMap::new();
+        memory_segments.insert(SegmentName::Poseidon, Segment::new(10, 15));
+        let components = builtin_components(&CairoLayout::Dynamic, &memory_segments).unwrap();
+
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].ratio, poseidon::INSTANCE_PERIOD);
+        assert_eq!(
+            components[0].check_instances(128),
+            Err(BuiltinTraceError::IncompleteInstance(Builtin::Poseidon))
+        );
+    }
+
+    #[test]
+    fn ecdsa_signatures_are_read_from_the_private_input() {
+        let json = r#"{
+            "trace_path": "trace.bin",
+            "ecdsa": [
+                {
+                    "index": 3,
+                    "pubkey": "0x1ef15c18599971b7beced415a40f0c7deacfd9b0d1819e03d723d8bc943cfca",
+                    "msg": "0x2",
+                    "signature_input": { "r": "0x5", "w": "0xa" }
+                }
+            ]
+        }"#;
+        let signatures = ecdsa::signatures_from_private_input(json).unwrap();
+
+        assert_eq!(signatures.len(), 1);
+        assert_eq!(
+            signatures[&3],
+            EcdsaSignature {
+                r: Felt252::from(5),
+                w: Felt252::from(10),
+            }
+        );
+    }
+}
@@ -0,0 +1,235 @@
+//! Pedersen builtin.
+//!
+//! Each instance has three memory cells: the inputs `x` and `y`, and their StarkNet Pedersen
+//! hash, the x coordinate of `P0 + x_low * P1 + x_high * P2 + y_low * P3 + y_high * P4`, where
+//! `low` are the 248 least significant bits of the input and `high` the other four.
+//!
+//! The instance takes 512 rows, 256 per input. In the rows of an input, `S` holds the input
+//! shifted right by the row index, so that each row consumes the bit `S - 2 * S'`, and the
+//! accumulator `A` adds the multiple of the constant point given by the periodic columns `Q`
+//! when the bit is set.
+//!
+//! As in Stone, the unpacking checks that the 252 bits of each input are the canonical
+//! representation of a field element, that is, smaller than `p = 2^251 + 17 * 2^192 + 1`:
+//!  * if bit 251 is set, bits 197 to 250 are zero (Stone's `zeroes_between_ones196`),
+//!  * if bits 251 and 196 are set, bits 193 to 195 are zero (`zeroes_between_ones192`),
+//!  * if bits 251, 196 and 192 are set, bits 0 to 191 are zero (`zeroes_between_ones0` and
+//!    `last_one_is_zero`).
+//!
+//! Stone reads the shifted inputs of rows 0, 193, 197 and so on from the row where it checks
+//! them, while this AIR only relates consecutive rows. The column `C` copies `S` from row 0 down
+//! to row 192, from row 193 to 196 and from row 197 to 251, and the column `D` holds bit 251 in
+//! rows 197 to 251 and the product of bits 251 and 196 in rows 0 to 196 (Stone's
+//! `cumulative_bit196`), so that each check reads a single row.
+
+use super::{
+    bits_le, cur, ec_add, ec_conditional_add, ec_double, ec_point, felt, next, shift_point,
+    shifted_values, ConstraintBuilder, EcPoint,
+};
+use crate::{errors::BuiltinTraceError, Felt252};
+use stark_platinum_prover::constraints::symbolic::Expr;
+
+pub const CELLS_PER_INSTANCE: usize = 3;
+pub const INSTANCE_PERIOD: usize = 512;
+pub const N_INPUTS: usize = 2;
+
+/// Rows of each input.
+const INPUT_ROWS: usize = 256;
+/// Number of bits of each input.
+const N_BITS: usize = 252;
+/// Number of low bits of each input, multiplied by `P1` and `P3`.
+const N_LOW_BITS: usize = 248;
+/// Rows where the column `C` starts a copy of `S`.
+const UNPACKING_BITS: [usize; 3] = [0, 193, 197];
+
+const S: usize = 0;
+const AX: usize = 1;
+const AY: usize = 2;
+const SLOPE: usize = 3;
+const C: usize = 4;
+const D: usize = 5;
+pub(crate) const N_COLUMNS: usize = 6;
+
+pub(crate) const CELLS: [(usize, usize); CELLS_PER_INSTANCE] =
+    [(0, S), (INPUT_ROWS, S), (INSTANCE_PERIOD - 1, AX)];
+
+// Periodic columns.
+const QX: usize = 0;
+const QY: usize = 1;
+const C_COPY: usize = 2;
+const D_COPY: usize = 3;
+const ZERO_BITS: usize = 4;
+
+fn constant_points() -> [EcPoint; 4] {
+    [
+        ec_point(
+            "0234287dcbaffe7f969c748655fca9e58fa8120b6d56eb0c1080d17957ebe47b",
+            "03b056f100f96fb21e889527d41f4e39940135dd7a6c94cc6ed0268ee89e5615",
+        ),
+        ec_point(
+            "04fa56f376c83db33f9dab2656558f3399099ec1de5e3018b7a6932dba8aa378",
+            "03fa0984c931c9e38113e0c0e47e4401562761f92a7a23b45168f4e80ff5b54d",
+        ),
+        ec_point(
+            "04ba4cc166be8dec764910f75b45f74b40c690c74709e90f3aa372f0bd2d6997",
+            "0040301cf5c1751f4b971e46c4ede85fcac5c59a5ce5ae7c48151f27b24b219c",
+        ),
+        ec_point(
+            "054302dcb0e6cc1c6e44cca8f61a63bb2ca65048d53fb325d36ff12c49a58202",
+            "01b77b3e37d13504b348046268d8ae25ce98ad783c25561a879dcc77e99c2426",
+        ),
+    ]
+}
+
+/// The point added in each row when its bit is set: `2^i * P1` for the low bits of `x`,
+/// `2^(i - 248) * P2` for its high bits, and the same with `P3` and `P4` for `y`. The rows
+/// without bits hold the point `(0, 0)`.
+pub(crate) fn row_points() -> Vec<EcPoint> {
+    let mut points = Vec::with_capacity(INSTANCE_PERIOD);
+    for [low, high] in constant_points().chunks(2).map(|p| [p[0], p[1]]) {
+        let mut point = low;
+        for i in 0..INPUT_ROWS {
+            if i == N_LOW_BITS {
+                point = high;
+            }
+            if i < N_BITS {
+                points.push(point);
+                point = ec_double(&point).unwrap().0;
+            } else {
+                points.push((Felt252::zero(), Felt252::zero()));
+            }
+        }
+    }
+    points
+}
+
+pub(crate) fn periodic_columns() -> Vec<Vec<Felt252>> {
+    let (qx, qy) = row_points().into_iter().unzip();
+    // `C` changes after the rows 192 and 196, and `D` after the row 196.
+    let copy = |breaks: &[usize]| {
+        (0..INPUT_ROWS)
+            .map(|i| {
+                if i < N_BITS - 1 && !breaks.contains(&i) {
+                    Felt252::one()
+                } else {
+                    Felt252::zero()
+                }
+            })
+            .collect()
+    };
+    let zero_bits = (0..INPUT_ROWS)
+        .map(|i| {
+            if i >= N_BITS {
+                Felt252::one()
+            } else {
+                Felt252::zero()
+            }
+        })
+        .collect();
+    vec![qx, qy, copy(&[192, 196]), copy(&[196]), zero_bits]
+}
+
+pub(crate) fn add_constraints(builder: &mut ConstraintBuilder, columns: usize, periodic: usize) {
+    let col = |column: usize| cur(columns + column);
+    let next_col = |column: usize| next(columns + column);
+    let bit = col(S) - next_col(S) - next_col(S);
+
+    builder.except(
+        INPUT_ROWS,
+        INPUT_ROWS - 1,
+        bit.clone() * (bit.clone() - Expr::one()),
+    );
+    for expr in ec_conditional_add(
+        bit.clone(),
+        (col(AX), col(AY)),
+        (Expr::periodic(periodic + QX), Expr::periodic(periodic + QY)),
+        col(SLOPE),
+        (next_col(AX), next_col(AY)),
+    ) {
+        builder.except(INPUT_ROWS, INPUT_ROWS - 1, expr);
+    }
+    builder.every_row(Expr::periodic(periodic + ZERO_BITS) * col(S), 0);
+
+    // Unpacking of the inputs.
+    for row in UNPACKING_BITS {
+        builder.at(INPUT_ROWS, row, col(C) - col(S));
+    }
+    builder.every_row(
+        Expr::periodic(periodic + C_COPY) * (next_col(C) - col(C)),
+        0,
+    );
+    builder.every_row(
+        Expr::periodic(periodic + D_COPY) * (next_col(D) - col(D)),
+        0,
+    );
+    let power_of_two = |exponent: u64| felt(Felt252::from(2).pow(exponent));
+    // Bit 251 and the bits 197 to 250.
+    builder.at(INPUT_ROWS, N_BITS - 1, col(D) - bit.clone());
+    builder.at(
+        INPUT_ROWS,
+        N_BITS - 1,
+        col(D) * (col(C) - power_of_two(54) * col(S)),
+    );
+    // Bit 196 and the bits 193 to 195.
+    builder.at(INPUT_ROWS, 196, col(D) - next_col(D) * bit.clone());
+    builder.at(
+        INPUT_ROWS,
+        196,
+        col(D) * (col(C) - power_of_two(3) * col(S)),
+    );
+    // Bit 192 and the bits 0 to 191.
+    builder.at(
+        INPUT_ROWS,
+        192,
+        col(D) * bit * (col(C) - power_of_two(192) * col(S)),
+    );
+
+    // The accumulator starts at the shift point and is kept between the two inputs.
+    let (shift_x, shift_y) = shift_point();
+    builder.at(INSTANCE_PERIOD, 0, col(AX) - felt(shift_x));
+    builder.at(INSTANCE_PERIOD, 0, col(AY) - felt(shift_y));
+    builder.at(INSTANCE_PERIOD, INPUT_ROWS - 1, next_col(AX) - col(AX));
+    builder.at(INSTANCE_PERIOD, INPUT_ROWS - 1, next_col(AY) - col(AY));
+}
+
+/// Computes the columns of an instance, given the points of [`row_points`].
+pub(crate) fn instance_columns(
+    inputs: &[Felt252],
+    points: &[EcPoint],
+) -> Result<Vec<Vec<Felt252>>, BuiltinTraceError> {
+    let mut columns = vec![Vec::new(); N_COLUMNS];
+    let mut acc = shift_point();
+
+    for (input, points) in inputs.iter().zip(points.chunks(INPUT_ROWS)) {
+        let shifted = shifted_values(input, INPUT_ROWS);
+        let bits = bits_le(input, INPUT_ROWS);
+        let top_bit = Felt252::from(bits[N_BITS - 1] as u64);
+        let top_bits_196 = Felt252::from((bits[N_BITS - 1] && bits[196]) as u64);
+        for i in 0..INPUT_ROWS {
+            columns[S].push(shifted[i]);
+            columns[AX].push(acc.0);
+            columns[AY].push(acc.1);
+            let slope = if bits[i] && i < INPUT_ROWS - 1 {
+                let (sum, slope) = ec_add(&acc, &points[i])?;
+                acc = sum;
+                slope
+            } else {
+                Felt252::zero()
+            };
+            columns[SLOPE].push(slope);
+            let (c, d) = match i {
+                0..=192 => (shifted[0], top_bits_196),
+                193..=196 => (shifted[193], top_bits_196),
+                197..=251 => (shifted[197], top_bit),
+                _ => (Felt252::zero(), Felt252::zero()),
+            };
+            columns[C].push(c);
+            columns[D].push(d);
+        }
+    }
+    Ok(columns)
+}
+
+pub(crate) fn padding_columns(points: &[EcPoint]) -> Vec<Vec<Felt252>> {
+    instance_columns(&[Felt252::zero(), Felt252::zero()], points).unwrap()
+}
@@ -0,0 +1,194 @@
+//! Poseidon builtin.
+//!
+//! Each instance has six memory cells: a state of three field elements and the result of
+//! applying to it the Hades permutation of StarkNet's Poseidon, with 8 full rounds and 83
+//! partial rounds.
+//!
+//! The instance takes 128 rows. The columns `S0`, `S1` and `S2` hold the state, which is
+//! copied along the first three rows, goes through a round in each of the next 91 transitions
+//! and is copied along the three rows of the output. The columns `Q` hold the squares of the
+//! state plus the round constants, so that each round has degree two in the trace.
+
+use super::{cur, felt, next, CairoExpr, ConstraintBuilder};
+use crate::{errors::BuiltinTraceError, Felt252};
+use lambdaworks_crypto::hash::poseidon::{
+    parameters::PermutationParameters, starknet::PoseidonCairoStark252,
+};
+use stark_platinum_prover::constraints::symbolic::Expr;
+
+pub const CELLS_PER_INSTANCE: usize = 6;
+pub const INSTANCE_PERIOD: usize = 128;
+pub const N_INPUTS: usize = 3;
+
+const N_FULL_ROUNDS: usize = PoseidonCairoStark252::N_FULL_ROUNDS;
+const N_PARTIAL_ROUNDS: usize = PoseidonCairoStark252::N_PARTIAL_ROUNDS;
+/// Row holding the state before the first round.
+const FIRST_ROUND_ROW: usize = 2;
+/// Row holding the state after the last round.
+const OUTPUT_ROW: usize = FIRST_ROUND_ROW + N_FULL_ROUNDS + N_PARTIAL_ROUNDS;
+
+const S: [usize; 3] = [0, 1, 2];
+const Q: [usize; 3] = [3, 4, 5];
+pub(crate) const N_COLUMNS: usize = 6;
+
+pub(crate) const CELLS: [(usize, usize); CELLS_PER_INSTANCE] = [
+    (0, S[0]),
+    (1, S[1]),
+    (2, S[2]),
+    (OUTPUT_ROW, S[0]),
+    (OUTPUT_ROW + 1, S[1]),
+    (OUTPUT_ROW + 2, S[2]),
+];
+
+/// The MDS matrix of the optimized round constants of StarkNet's Poseidon.
+const MDS: [[i64; 3]; 3] = [[3, 1, 1], [1, -1, 1], [1, 1, -2]];
+
+// Periodic columns.
+const ROUND_CONSTANTS: [usize; 3] = [0, 1, 2];
+const FULL_ROUND: usize = 3;
+const PARTIAL_ROUND: usize = 4;
+const COPY: usize = 5;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Transition {
+    Full,
+    Partial,
+    Copy,
+    Free,
+}
+
+fn transition(row: usize) -> Transition {
+    let half_full_rounds = N_FULL_ROUNDS / 2;
+    match row {
+        0 | 1 => Transition::Copy,
+        r if r == OUTPUT_ROW || r == OUTPUT_ROW + 1 => Transition::Copy,
+        r if (FIRST_ROUND_ROW..OUTPUT_ROW).contains(&r) => {
+            let round = r - FIRST_ROUND_ROW;
+            if round < half_full_rounds || round >= half_full_rounds + N_PARTIAL_ROUNDS {
+                Transition::Full
+            } else {
+                Transition::Partial
+            }
+        }
+        _ => Transition::Free,
+    }
+}
+
+/// The round constants added to the state in each row.
+fn round_constants() -> Vec<[Felt252; 3]> {
+    let constants = PoseidonCairoStark252::ROUND_CONSTANTS;
+    let mut index = 0;
+    (0..INSTANCE_PERIOD)
+        .map(|row| match transition(row) {
+            Transition::Full => {
+                let row_constants = [constants[index], constants[index + 1], constants[index + 2]];
+                index += 3;
+                row_constants
+            }
+            Transition::Partial => {
+                let row_constants = [Felt252::zero(), Felt252::zero(), constants[index]];
+                index += 1;
+                row_constants
+            }
+            _ => [Felt252::zero(); 3],
+        })
+        .collect()
+}
+
+fn selector(kind: Transition) -> Vec<Felt252> {
+    (0..INSTANCE_PERIOD)
+        .map(|row| {
+            if transition(row) == kind {
+                Felt252::one()
+            } else {
+                Felt252::zero()
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn periodic_columns() -> Vec<Vec<Felt252>> {
+    let constants = round_constants();
+    let mut columns: Vec<Vec<Felt252>> = (0..3)
+        .map(|k| constants.iter().map(|row| row[k]).collect())
+        .collect();
+    columns.push(selector(Transition::Full));
+    columns.push(selector(Transition::Partial));
+    columns.push(selector(Transition::Copy));
+    columns
+}
+
+fn mds_entry(j: usize, k: usize) -> Felt252 {
+    let entry = MDS[j][k];
+    if entry < 0 {
+        -Felt252::from(entry.unsigned_abs())
+    } else {
+        Felt252::from(entry as u64)
+    }
+}
+
+pub(crate) fn add_constraints(builder: &mut ConstraintBuilder, columns: usize, periodic: usize) {
+    let state = |k: usize| cur(columns + S[k]);
+    let square = |k: usize| cur(columns + Q[k]);
+    let constant = |k: usize| Expr::periodic(periodic + ROUND_CONSTANTS[k]);
+    let full = Expr::periodic(periodic + FULL_ROUND);
+    let partial = Expr::periodic(periodic + PARTIAL_ROUND);
+    let copy = Expr::periodic(periodic + COPY);
+
+    for k in 0..3 {
+        builder.every_row(square(k) - (state(k) + constant(k)).square(), 0);
+    }
+
+    let cube = |k: usize| (state(k) + constant(k)) * square(k);
+    for (j, &state_column) in S.iter().enumerate() {
+        let full_round = (0..3)
+            .map(|k| felt(mds_entry(j, k)) * cube(k))
+            .reduce(|acc, term| acc + term)
+            .unwrap();
+        let partial_round: CairoExpr = felt(mds_entry(j, 0)) * state(0)
+            + felt(mds_entry(j, 1)) * state(1)
+            + felt(mds_entry(j, 2)) * cube(2);
+        builder.every_row(
+            (full.clone() + partial.clone() + copy.clone()) * next(columns + state_column)
+                - full.clone() * full_round
+                - partial.clone() * partial_round
+                - copy.clone() * state(j),
+            0,
+        );
+    }
+}
+
+fn mix(state: &[Felt252; 3]) -> [Felt252; 3] {
+    let mut mixed = [Felt252::zero(); 3];
+    for (j, value) in mixed.iter_mut().enumerate() {
+        for (k, element) in state.iter().enumerate() {
+            *value += mds_entry(j, k) * element;
+        }
+    }
+    mixed
+}
+
+pub(crate) fn instance_columns(inputs: &[Felt252]) -> Result<Vec<Vec<Felt252>>, BuiltinTraceError> {
+    let constants = round_constants();
+    let mut state = [inputs[0], inputs[1], inputs[2]];
+    let mut columns = vec![Vec::new(); N_COLUMNS];
+
+    for (row, row_constants) in constants.iter().enumerate() {
+        let shifted: Vec<Felt252> = (0..3).map(|k| state[k] + row_constants[k]).collect();
+        for k in 0..3 {
+            columns[S[k]].push(state[k]);
+            columns[Q[k]].push(shifted[k].square());
+        }
+        let cube = |k: usize| shifted[k].square() * shifted[k];
+        state = match transition(row) {
+            Transition::Full => mix(&[cube(0), cube(1), cube(2)]),
+            Transition::Partial => mix(&[state[0], state[1], cube(2)]),
+            Transition::Copy | Transition::Free => state,
+        };
+    }
+    Ok(columns)
+}
+
+pub(crate) fn padding_columns() -> Vec<Vec<Felt252>> {
+    instance_columns(&[Felt252::zero(); N_INPUTS]).unwrap()
+}
@@ -0,0 +1,74 @@
+//! Range-check builtin.
+//!
+//! Each instance has a single memory cell, whose value must be smaller than `2^128`. As in
+//! Stone, the value is split into eight 16-bit limbs which are added to the range-check
+//! argument of the offsets.
+//!
+//! The instance takes 8 rows. The column `LIMB` holds a limb per row, most significant first,
+//! and the column `VALUE` accumulates them, so that its last row holds the value of the cell.
+
+use super::{cur, felt, fits_in_bits, next, ConstraintBuilder};
+use crate::{errors::BuiltinTraceError, Felt252};
+
+pub const CELLS_PER_INSTANCE: usize = 1;
+pub const INSTANCE_PERIOD: usize = 8;
+pub const N_INPUTS: usize = 1;
+/// Number of bits of each limb.
+pub const LIMB_BITS: usize = 16;
+/// Number of bits of the values.
+pub const TOTAL_N_BITS: usize = LIMB_BITS * INSTANCE_PERIOD;
+
+/// The column added to the range-check argument.
+pub(crate) const LIMB: usize = 0;
+const VALUE: usize = 1;
+pub(crate) const N_COLUMNS: usize = 2;
+
+pub(crate) const CELLS: [(usize, usize); CELLS_PER_INSTANCE] = [(INSTANCE_PERIOD - 1, VALUE)];
+
+pub(crate) fn periodic_columns() -> Vec<Vec<Felt252>> {
+    Vec::new()
+}
+
+pub(crate) fn add_constraints(builder: &mut ConstraintBuilder, columns: usize, _periodic: usize) {
+    let col = |column: usize| cur(columns + column);
+    let next_col = |column: usize| next(columns + column);
+    let limb_shift = Felt252::from(1 << LIMB_BITS);
+
+    builder.at(INSTANCE_PERIOD, 0, col(VALUE) - col(LIMB));
+    builder.except(
+        INSTANCE_PERIOD,
+        INSTANCE_PERIOD - 1,
+        next_col(VALUE) - (col(VALUE) * felt(limb_shift) + next_col(LIMB)),
+    );
+}
+
+fn columns_from_limbs(limbs: &[Felt252]) -> Vec<Vec<Felt252>> {
+    let limb_shift = Felt252::from(1 << LIMB_BITS);
+    let values = limbs
+        .iter()
+        .scan(Felt252::zero(), |value, limb| {
+            *value = *value * limb_shift + limb;
+            Some(*value)
+        })
+        .collect();
+    vec![limbs.to_vec(), values]
+}
+
+pub(crate) fn instance_columns(inputs: &[Felt252]) -> Result<Vec<Vec<Felt252>>, BuiltinTraceError> {
+    let value = &inputs[0];
+    if !fits_in_bits(value, TOTAL_N_BITS) {
+        return Err(BuiltinTraceError::InputOutOfRange(*value));
+    }
+    let value = value.representative();
+    let limbs: Vec<_> = (0..INSTANCE_PERIOD)
+        .rev()
+        .map(|i| Felt252::from((value.limbs[3 - i / 4] >> (LIMB_BITS * (i % 4))) & 0xffff))
+        .collect();
+    Ok(columns_from_limbs(&limbs))
+}
+
+/// The columns of an instance whose limbs are all `limb`, so that padding the component doesn't
+/// widen the range of the range-check argument.
+pub(crate) fn padding_columns(limb: &Felt252) -> Vec<Vec<Felt252>> {
+    columns_from_limbs(&[*limb; INSTANCE_PERIOD])
+}
@@ -1,4 +1,7 @@
-#[derive(Debug, Clone, PartialEq, Eq)]
+use crate::builtins::Builtin;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum CairoLayout {
     Plain,
     Small,
@@ -12,7 +15,43 @@ pub enum CairoLayout {
     Dynamic,
 }
 
+/// A builtin of a layout, with the number of steps of the trace per instance of the builtin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayoutBuiltin {
+    pub builtin: Builtin,
+    pub ratio: usize,
+}
+
+const fn layout_builtin(builtin: Builtin, ratio: usize) -> LayoutBuiltin {
+    LayoutBuiltin { builtin, ratio }
+}
+
+const SMALL_BUILTINS: [LayoutBuiltin; 3] = [
+    layout_builtin(Builtin::Pedersen, 8),
+    layout_builtin(Builtin::RangeCheck, 8),
+    layout_builtin(Builtin::Ecdsa, 512),
+];
+
+const RECURSIVE_BUILTINS: [LayoutBuiltin; 3] = [
+    layout_builtin(Builtin::Pedersen, 128),
+    layout_builtin(Builtin::RangeCheck, 8),
+    layout_builtin(Builtin::Bitwise, 8),
+];
+
 impl CairoLayout {
+    pub const ALL: [CairoLayout; 10] = [
+        CairoLayout::Plain,
+        CairoLayout::Small,
+        CairoLayout::Dex,
+        CairoLayout::Recursive,
+        CairoLayout::Starknet,
+        CairoLayout::StarknetWithKeccak,
+        CairoLayout::RecursiveLargeOutput,
+        CairoLayout::AllCairo,
+        CairoLayout::AllSolidity,
+        CairoLayout::Dynamic,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             CairoLayout::Plain => "plain",
@@ -27,4 +66,33 @@ impl CairoLayout {
             CairoLayout::Dynamic => "dynamic",
         }
     }
+
+    /// The builtins of the layout and their ratios, in the order their components are laid out
+    /// in the trace. Every proof of the layout has a component for each of them, whether the
+    /// program uses it or not.
+    ///
+    /// Only these match the Stone layout of the same name. The columns the components take and
+    /// their constraints are the ones of `CairoAIR`, so the proofs aren't compatible with Stone.
+    ///
+    /// Returns `None` for the `dynamic` layout, whose builtins are the ones used by the program,
+    /// and for the layouts `CairoAIR` doesn't support.
+    pub fn builtins(&self) -> Option<&'static [LayoutBuiltin]> {
+        match self {
+            CairoLayout::Plain => Some(&[]),
+            CairoLayout::Small => Some(&SMALL_BUILTINS),
+            CairoLayout::Recursive => Some(&RECURSIVE_BUILTINS),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for CairoLayout {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        CairoLayout::ALL
+            .into_iter()
+            .find(|layout| layout.as_str() == name)
+            .ok_or_else(|| format!("Invalid layout {name}"))
+    }
 }
@@ -1,3 +1,5 @@
+use cairo_platinum_prover::cairo_layout::CairoLayout;
+use cairo_platinum_prover::fact::ProgramHashFunction;
 use clap::{Args, Parser, Subcommand};
 
 #[derive(Parser, Debug)]
@@ -11,12 +13,18 @@ pub struct ProverArgs {
 pub enum ProverEntity {
     #[clap(about = "Compile a given cairo program")]
     Compile(CompileArgs),
-    #[clap(about = "Run and generate a proof for a given compiled cairo program")]
+    #[clap(
+        about = "Run and generate a proof for a given compiled cairo program, or the Sierra of a Cairo 1 program"
+    )]
     RunAndProve(RunAndProveArgs),
     #[clap(about = "Generate a proof from a given trace of a cairo program execution")]
     Prove(ProveArgs),
-    #[clap(about = "Verify a proof for a given compiled cairo program")]
+    #[clap(
+        about = "Verify a proof for a given compiled cairo program, and print its output, program hash and fact hash"
+    )]
     Verify(VerifyArgs),
+    #[clap(about = "Verify a proof of this prover written in the proof.json format of Stone")]
+    VerifyStoneJson(VerifyArgs),
     #[clap(about = "Generate and verify a proof for a given compiled cairo program")]
     ProveAndVerify(ProveAndVerifyArgs),
     #[clap(about = "Compile and prove a given cairo program")]
@@ -34,6 +42,8 @@ pub struct CompileArgs {
 pub struct RunAndProveArgs {
     pub program_path: String,
     pub proof_path: String,
+    #[clap(flatten)]
+    pub run_args: RunArgs,
 }
 #[derive(Args, Debug)]
 pub struct ProveArgs {
@@ -45,11 +55,26 @@ pub struct ProveArgs {
 #[derive(Args, Debug)]
 pub struct VerifyArgs {
     pub proof_path: String,
+    /// Hash function of the program hash, either pedersen or poseidon
+    #[arg(long, default_value = "pedersen")]
+    pub program_hash: ProgramHashFunction,
 }
 
 #[derive(Args, Debug)]
 pub struct ProveAndVerifyArgs {
     pub program_path: String,
+    #[clap(flatten)]
+    pub run_args: RunArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct RunArgs {
+    /// Layout used to run and prove the program
+    #[arg(long, default_value = "plain")]
+    pub layout: CairoLayout,
+    /// Arguments of the `main` function of a Cairo 1 program, as in "1 [2 3] 4"
+    #[arg(long, default_value = "")]
+    pub program_args: String,
 }
 
 #[derive(Args, Debug)]
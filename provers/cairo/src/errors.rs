@@ -1,3 +1,5 @@
+use crate::{air::SegmentName, builtins::Builtin, cairo_layout::CairoLayout, Felt252};
+
 #[derive(Debug)]
 pub enum CairoImportError {
     /// Bytes should be a multiple of 24 for trace or 40 for memory
@@ -22,3 +24,37 @@ pub enum InstructionDecodingError {
     InvalidDstReg,
     InstructionNotFound,
 }
+
+/// Errors building the trace columns of the builtins.
+#[derive(Debug, PartialEq)]
+pub enum BuiltinTraceError {
+    /// The segment of the builtin does not hold a whole number of instances.
+    IncompleteInstance(Builtin),
+    /// An input cell of a builtin instance is not in memory.
+    MissingInput(u64),
+    /// An input of a builtin instance is out of the range accepted by the builtin.
+    InputOutOfRange(Felt252),
+    /// There is no signature for the ECDSA instance with this index.
+    MissingSignature(usize),
+    /// The signature of an ECDSA instance is not valid for the public key with this x coordinate.
+    InvalidSignature(Felt252),
+    /// The computation of a builtin instance adds two points with the same x coordinate.
+    DegenerateEcAddition,
+    /// The private input with the ECDSA signatures could not be read.
+    InvalidPrivateInput(String),
+    /// The AIR has no fixed set of builtins for the layout.
+    UnsupportedLayout(CairoLayout),
+    /// The program uses a builtin that is not in the layout.
+    BuiltinNotInLayout(Builtin),
+    /// The instances of the builtin used by the program don't fit in the trace.
+    TraceTooShort(Builtin),
+}
+
+/// Errors reading the values of a memory segment from the public memory.
+#[derive(Debug, PartialEq)]
+pub enum PublicMemoryError {
+    /// The public inputs have no bounds for the segment.
+    MissingSegment(SegmentName),
+    /// A cell of the segment is not in the public memory.
+    MissingCell(usize),
+}
@@ -10,6 +10,8 @@ use super::{
     register_states::RegisterStates,
 };
 use crate::air::{EXTRA_ADDR, RC_HOLES};
+use crate::builtins::{builtin_components, BuiltinTrace, EcdsaSignatures};
+use crate::errors::BuiltinTraceError;
 use crate::{
     air::{
         PublicInputs, FRAME_DST_ADDR, FRAME_OP0_ADDR, FRAME_OP1_ADDR, FRAME_PC, OFF_DST, OFF_OP0,
@@ -43,20 +45,43 @@ type CairoTraceTable = TraceTable<Stark252PrimeField>;
 /// Builds the Cairo main trace (i.e. the trace without the auxiliary columns).
 /// Builds the execution trace, fills the offset range-check holes and memory holes, adds
 /// public memory dummy accesses (See section 9.8 of the Cairo whitepaper) and pads the result
-/// so that it has a trace length equal to the closest power of two.
+/// so that it has a trace length equal to the closest power of two. Then appends the columns
+/// of the builtins of the layout of the public input, whose instances are computed from their
+/// inputs in memory and, for the ECDSA builtin, from `ecdsa_signatures`.
 pub fn build_main_trace(
     register_states: &RegisterStates,
     memory: &CairoMemory,
     public_input: &mut PublicInputs,
-) -> CairoTraceTable {
+    ecdsa_signatures: &EcdsaSignatures,
+) -> Result<CairoTraceTable, BuiltinTraceError> {
     let mut main_trace = build_cairo_execution_trace(register_states, memory);
 
+    let mut builtin_traces =
+        builtin_components(&public_input.layout, &public_input.memory_segments)?
+            .iter()
+            .map(|component| BuiltinTrace::new(component, memory, ecdsa_signatures))
+            .collect::<Result<Vec<_>, _>>()?;
+
     let mut address_cols =
         main_trace.merge_columns(&[FRAME_PC, FRAME_DST_ADDR, FRAME_OP0_ADDR, FRAME_OP1_ADDR]);
+    // The cells of the builtins are accessed by their components, so they are not holes.
+    address_cols.extend(builtin_traces.iter().flat_map(|trace| trace.addresses()));
 
     address_cols.sort_by_key(|x| x.representative());
 
-    let (rc_holes, rc_min, rc_max) = get_rc_holes(&main_trace, &[OFF_DST, OFF_OP0, OFF_OP1]);
+    let builtin_rc_values: Vec<_> = builtin_traces
+        .iter()
+        .flat_map(|trace| trace.range_checked_values())
+        .copied()
+        .collect();
+    let (rc_holes, rc_min, rc_max) = get_rc_holes(
+        &main_trace,
+        &[OFF_DST, OFF_OP0, OFF_OP1],
+        &builtin_rc_values,
+    );
+    for trace in builtin_traces.iter_mut() {
+        trace.set_range_check_padding(&Felt252::from(rc_min as u64));
+    }
 
     // this will avaluate to true if the public inputs weren't obtained from the run_program() function
     if public_input.range_check_min.is_none() && public_input.range_check_max.is_none() {
@@ -77,11 +102,22 @@ pub fn build_main_trace(
         memory_holes.len(),
     );
 
-    let trace_len_next_power_of_two = main_trace.n_rows().next_power_of_two();
+    let trace_len_next_power_of_two = builtin_traces
+        .iter()
+        .map(|trace| trace.min_trace_length())
+        .fold(main_trace.n_rows(), usize::max)
+        .next_power_of_two();
     let padding_len = trace_len_next_power_of_two - main_trace.n_rows();
     main_trace.pad_with_last_row(padding_len);
 
-    main_trace
+    if builtin_traces.is_empty() {
+        return Ok(main_trace);
+    }
+    let mut columns = main_trace.columns();
+    for trace in builtin_traces {
+        columns.extend(trace.into_columns(trace_len_next_power_of_two));
+    }
+    Ok(TraceTable::from_columns_main(columns, 1))
 }
 
 /// Artificial `(0, 0)` dummy memory accesses must be added for the public memory.
@@ -98,16 +134,22 @@ fn add_pub_memory_dummy_accesses(
 
 /// Gets holes from the range-checked columns. These holes must be filled for the
 /// permutation range-checks, as can be read in section 9.9 of the Cairo whitepaper.
-/// Receives the trace and the indexes of the range-checked columns.
+/// Receives the trace, the indexes of the range-checked columns and the range-checked values
+/// of the builtins.
 /// Outputs the holes that must be filled to make the range continuous and the extreme
 /// values rc_min and rc_max, corresponding to the minimum and maximum values of the range.
 /// NOTE: These extreme values should be received as public inputs in the future and not
 /// calculated here.
-fn get_rc_holes(trace: &CairoTraceTable, columns_indices: &[usize]) -> (Vec<Felt252>, u16, u16) {
+fn get_rc_holes(
+    trace: &CairoTraceTable,
+    columns_indices: &[usize],
+    builtin_values: &[Felt252],
+) -> (Vec<Felt252>, u16, u16) {
     let offset_columns = trace.merge_columns(columns_indices);
 
     let mut sorted_offset_representatives: Vec<u16> = offset_columns
         .iter()
+        .chain(builtin_values)
         .map(|x| x.representative().into())
         .collect();
     sorted_offset_representatives.sort();
@@ -610,7 +652,7 @@ mod test {
         ];
         let table = TraceTable::<Stark252PrimeField>::from_columns(columns, 3, 1);
 
-        let (col, rc_min, rc_max) = get_rc_holes(&table, &[0, 1, 2]);
+        let (col, rc_min, rc_max) = get_rc_holes(&table, &[0, 1, 2], &[]);
         assert_eq!(col, expected_col);
         assert_eq!(rc_min, 1);
         assert_eq!(rc_max, 7);
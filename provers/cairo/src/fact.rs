@@ -0,0 +1,236 @@
+//! Hashes identifying the statement proved by a proof of a Cairo program, computed from its
+//! public inputs.
+//!
+//! The program hash is computed over the bytecode of the program, as Stone verifiers do, with
+//! either StarkNet's Pedersen hash chain (`compute_hash_on_elements`) or Poseidon. The fact hash
+//! is the one registered by the fact registries of Ethereum:
+//! `keccak256(program_hash || keccak256(output))`, with every felt as 32 big-endian bytes.
+
+use crate::{air::PublicInputs, errors::PublicMemoryError, Felt252};
+use lambdaworks_crypto::hash::{
+    pedersen::{Pedersen, PedersenStarkCurve},
+    poseidon::{starknet::PoseidonCairoStark252, Poseidon},
+};
+use sha3::{Digest, Keccak256};
+use std::str::FromStr;
+
+/// Hash function used for the program hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgramHashFunction {
+    Pedersen,
+    Poseidon,
+}
+
+impl ProgramHashFunction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProgramHashFunction::Pedersen => "pedersen",
+            ProgramHashFunction::Poseidon => "poseidon",
+        }
+    }
+
+    /// Hashes a sequence of felts.
+    pub fn hash_many(&self, values: &[Felt252]) -> Felt252 {
+        match self {
+            ProgramHashFunction::Pedersen => {
+                let chain = values.iter().fold(Felt252::zero(), |hash, value| {
+                    PedersenStarkCurve::hash(&hash, value)
+                });
+                PedersenStarkCurve::hash(&chain, &Felt252::from(values.len() as u64))
+            }
+            ProgramHashFunction::Poseidon => PoseidonCairoStark252::hash_many(values),
+        }
+    }
+}
+
+impl FromStr for ProgramHashFunction {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        [ProgramHashFunction::Pedersen, ProgramHashFunction::Poseidon]
+            .into_iter()
+            .find(|hash_function| hash_function.as_str() == name)
+            .ok_or_else(|| format!("Invalid program hash function {name}"))
+    }
+}
+
+/// Hash of the bytecode of the program.
+pub fn program_hash(
+    public_inputs: &PublicInputs,
+    hash_function: ProgramHashFunction,
+) -> Result<Felt252, PublicMemoryError> {
+    Ok(hash_function.hash_many(&public_inputs.program()?))
+}
+
+/// Keccak-256 of the output of the program.
+pub fn output_hash(public_inputs: &PublicInputs) -> Result<[u8; 32], PublicMemoryError> {
+    Ok(keccak_felts(&public_inputs.output()?))
+}
+
+/// Fact of the statement proved: the program with this hash produced the output.
+pub fn fact_hash(
+    public_inputs: &PublicInputs,
+    hash_function: ProgramHashFunction,
+) -> Result<[u8; 32], PublicMemoryError> {
+    let mut hasher = Keccak256::new();
+    hasher.update(program_hash(public_inputs, hash_function)?.to_bytes_be());
+    hasher.update(output_hash(public_inputs)?);
+    Ok(hasher.finalize().into())
+}
+
+fn keccak_felts(values: &[Felt252]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    for value in values {
+        hasher.update(value.to_bytes_be());
+    }
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        air::{MemorySegmentMap, Segment, SegmentName},
+        cairo_layout::CairoLayout,
+        stone_json::encode_hex,
+    };
+
+    /// Public inputs of a program with bytecode `[1, 2, 3]` at address 1, whose output is `[7, 8]`.
+    fn public_inputs() -> PublicInputs {
+        let memory_segments = MemorySegmentMap::from([
+            (SegmentName::Program, Segment::new(1, 3)),
+            (SegmentName::Execution, Segment::new(4, 10)),
+            (SegmentName::Output, Segment::new(10, 12)),
+        ]);
+        let public_memory = [(1, 1), (2, 2), (3, 3), (4, 4), (5, 5), (10, 7), (11, 8)]
+            .into_iter()
+            .map(|(addr, value)| (Felt252::from(addr), Felt252::from(value)))
+            .collect();
+
+        PublicInputs {
+            pc_init: Felt252::one(),
+            ap_init: Felt252::from(6),
+            fp_init: Felt252::from(6),
+            pc_final: Felt252::from(3),
+            ap_final: Felt252::from(10),
+            range_check_min: None,
+            range_check_max: None,
+            memory_segments,
+            public_memory,
+            num_steps: 1,
+            layout: CairoLayout::Plain,
+        }
+    }
+
+    #[test]
+    fn program_and_output_are_read_from_the_public_memory() {
+        let public_inputs = public_inputs();
+
+        assert_eq!(
+            public_inputs.program().unwrap(),
+            [1, 2, 3].map(Felt252::from)
+        );
+        assert_eq!(public_inputs.output().unwrap(), [7, 8].map(Felt252::from));
+    }
+
+    #[test]
+    fn missing_segments_and_cells_are_reported() {
+        let mut public_inputs = public_inputs();
+        public_inputs.public_memory.remove(&Felt252::from(11_u64));
+        assert_eq!(
+            public_inputs.output(),
+            Err(PublicMemoryError::MissingCell(11))
+        );
+
+        public_inputs.memory_segments.remove(&SegmentName::Output);
+        assert_eq!(public_inputs.output(), Ok(Vec::new()));
+
+        public_inputs
+            .memory_segments
+            .remove(&SegmentName::Execution);
+        assert_eq!(
+            public_inputs.program(),
+            Err(PublicMemoryError::MissingSegment(SegmentName::Execution))
+        );
+    }
+
+    #[test]
+    fn pedersen_hash_of_no_elements_is_the_hash_of_zero_and_zero() {
+        assert_eq!(
+            ProgramHashFunction::Pedersen.hash_many(&[]),
+            Felt252::from_hex_unchecked(
+                "49ee3eba8c1600700ee1b87eb599f16716b0b1022947733551fde4050ca6804"
+            )
+        );
+    }
+
+    #[test]
+    fn fact_hash_is_the_keccak_of_the_program_hash_and_the_output_hash() {
+        let public_inputs = public_inputs();
+        let program_hash = program_hash(&public_inputs, ProgramHashFunction::Poseidon).unwrap();
+        assert_eq!(
+            program_hash,
+            PoseidonCairoStark252::hash_many(&[1, 2, 3].map(Felt252::from))
+        );
+
+        let mut output = [0; 64];
+        output[31] = 7;
+        output[63] = 8;
+        let expected_output_hash: [u8; 32] = Keccak256::digest(output).into();
+        assert_eq!(output_hash(&public_inputs).unwrap(), expected_output_hash);
+
+        let fact = [program_hash.to_bytes_be(), expected_output_hash].concat();
+        assert_eq!(
+            fact_hash(&public_inputs, ProgramHashFunction::Poseidon).unwrap(),
+            <[u8; 32]>::from(Keccak256::digest(fact))
+        );
+    }
+
+    #[test]
+    fn hashes_match_an_independent_implementation_of_cairo_lang() {
+        // Computed with a Python implementation of cairo-lang's `hash_felts` (`hash_state`),
+        // `poseidon_hash_many` and Keccak-256, checked against the hashes of StarkNet below.
+        let public_inputs = public_inputs();
+
+        assert_eq!(
+            program_hash(&public_inputs, ProgramHashFunction::Pedersen).unwrap(),
+            Felt252::from_hex_unchecked(
+                "f9d95fbf356fbeda26538c92f7040abe51bf142350f73c9ee5ba7c660bae71"
+            )
+        );
+        assert_eq!(
+            program_hash(&public_inputs, ProgramHashFunction::Poseidon).unwrap(),
+            Felt252::from_hex_unchecked(
+                "2f0d8840bcf3bc629598d8a6cc80cb7c0d9e52d93dab244bbf9cd0dca0ad082"
+            )
+        );
+        assert_eq!(
+            encode_hex(&output_hash(&public_inputs).unwrap()),
+            "24cd397636bedc6cf9b490d0edd57c769c19b367fb7d5c2344ae1ddc7d21c144"
+        );
+        assert_eq!(
+            encode_hex(&fact_hash(&public_inputs, ProgramHashFunction::Pedersen).unwrap()),
+            "049be06d6cc1047fcfe865d9614471510f633ed7d8b5bc8e8d12fed0475d41db"
+        );
+        assert_eq!(
+            encode_hex(&fact_hash(&public_inputs, ProgramHashFunction::Poseidon).unwrap()),
+            "e454511d36d5a259c147d58cf8ac20051f09f3b16769d4e89a23b99d9dbd81bf"
+        );
+    }
+
+    #[test]
+    fn hashes_of_starknet_are_reproduced() {
+        assert_eq!(
+            PedersenStarkCurve::hash(&Felt252::from(1), &Felt252::from(2)),
+            Felt252::from_hex_unchecked(
+                "5bb9440e27889a364bcb678b1f679ecd1347acdedcbf36e83494f857cc58026"
+            )
+        );
+        assert_eq!(
+            ProgramHashFunction::Poseidon.hash_many(&[]),
+            Felt252::from_hex_unchecked(
+                "2272be0f580fd156823304800919530eaa97430e972d7213ee13f4fbf7a5dbc"
+            )
+        );
+    }
+}
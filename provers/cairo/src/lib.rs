@@ -3,13 +3,16 @@ use lambdaworks_math::field::{
 };
 
 pub mod air;
+pub mod builtins;
 pub mod cairo_layout;
 pub mod cairo_mem;
 pub mod decode;
 pub mod errors;
 pub mod execution_trace;
+pub mod fact;
 pub mod register_states;
 pub mod runner;
+pub mod stone_json;
 pub mod transition_constraints;
 
 #[cfg(test)]
@@ -1,10 +1,14 @@
 use cairo_platinum_prover::air::{generate_cairo_proof, verify_cairo_proof, PublicInputs};
 use cairo_platinum_prover::cairo_layout::CairoLayout;
+use cairo_platinum_prover::fact::{fact_hash, program_hash, ProgramHashFunction};
 use cairo_platinum_prover::runner::run::generate_prover_args;
 use cairo_platinum_prover::runner::run::generate_prover_args_from_trace;
+use cairo_platinum_prover::runner::run::{generate_cairo1_prover_args, parse_cairo1_args};
+use cairo_platinum_prover::stone_json::StoneProof;
 use lambdaworks_math::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
 use stark_platinum_prover::proof::options::{ProofOptions, SecurityLevel};
 use stark_platinum_prover::proof::stark::StarkProof;
+use stark_platinum_prover::trace::TraceTable;
 mod commands;
 use clap::Parser;
 
@@ -105,8 +109,46 @@ fn try_compile(program_path: &String, out_file_path: &String) -> Result<(), Erro
     }
 }
 
+/// Runs the program in `input_path` and builds the trace to prove it. Programs with the
+/// `.sierra` extension are run as Cairo 1 programs with the arguments in `program_args`, and
+/// the rest as compiled Cairo 0 programs.
+fn run_program(
+    input_path: &String,
+    run_args: &commands::RunArgs,
+) -> Option<(TraceTable<Stark252PrimeField>, PublicInputs)> {
+    let prover_args = if input_path.ends_with(".sierra") {
+        let Ok(sierra_program) = std::fs::read_to_string(input_path) else {
+            eprintln!("Error opening {input_path} file");
+            return None;
+        };
+        let program_args = match parse_cairo1_args(&run_args.program_args) {
+            Ok(program_args) => program_args,
+            Err(err) => {
+                eprintln!("Error parsing the program arguments: {err:?}");
+                return None;
+            }
+        };
+        generate_cairo1_prover_args(&sierra_program, &program_args, run_args.layout)
+    } else {
+        let Ok(program_content) = std::fs::read(input_path) else {
+            eprintln!("Error opening {input_path} file");
+            return None;
+        };
+        generate_prover_args(&program_content, run_args.layout)
+    };
+
+    match prover_args {
+        Ok(prover_args) => Some(prover_args),
+        Err(err) => {
+            eprintln!("Error generating prover args: {err:?}");
+            None
+        }
+    }
+}
+
 fn generate_proof(
     input_path: &String,
+    run_args: &commands::RunArgs,
     proof_options: &ProofOptions,
 ) -> Option<(
     StarkProof<Stark252PrimeField, Stark252PrimeField>,
@@ -114,18 +156,7 @@ fn generate_proof(
 )> {
     let timer = Instant::now();
 
-    let Ok(program_content) = std::fs::read(input_path) else {
-        eprintln!("Error opening {input_path} file");
-        return None;
-    };
-
-    // FIXME: We should set this through the CLI in the future
-    let layout = CairoLayout::Plain;
-
-    let Ok((main_trace, pub_inputs)) = generate_prover_args(&program_content, layout) else {
-        eprintln!("Error generating prover args");
-        return None;
-    };
+    let (main_trace, pub_inputs) = run_program(input_path, run_args)?;
 
     println!("  Time spent: {:?} \n", timer.elapsed());
 
@@ -197,6 +228,34 @@ fn verify_proof(
     proof_verified
 }
 
+/// Prints the output of the program and the hashes identifying the statement proved.
+fn print_statement(pub_inputs: &PublicInputs, hash_function: ProgramHashFunction) {
+    let statement = pub_inputs.output().and_then(|output| {
+        let program_hash = program_hash(pub_inputs, hash_function)?;
+        let fact_hash = fact_hash(pub_inputs, hash_function)?;
+        Ok((output, program_hash, fact_hash))
+    });
+    let (output, program_hash, fact_hash) = match statement {
+        Ok(statement) => statement,
+        Err(err) => {
+            eprintln!("Error reading the public memory: {err:?}");
+            return;
+        }
+    };
+
+    println!("Program output:");
+    for value in output {
+        println!("  {}", value.representative());
+    }
+    println!(
+        "Program hash ({}): {}",
+        hash_function.as_str(),
+        program_hash.representative()
+    );
+    let fact_hash: String = fact_hash.iter().map(|byte| format!("{byte:02x}")).collect();
+    println!("Fact hash: 0x{fact_hash}");
+}
+
 fn write_proof(
     proof: StarkProof<Stark252PrimeField, Stark252PrimeField>,
     pub_inputs: PublicInputs,
@@ -228,6 +287,14 @@ fn write_proof(
     println!("Proof written to {}", &proof_path);
 }
 
+/// Arguments to run the Cairo 0 programs compiled by the CLI.
+fn cairo0_run_args() -> commands::RunArgs {
+    commands::RunArgs {
+        layout: CairoLayout::Plain,
+        program_args: String::new(),
+    }
+}
+
 fn main() {
     let proof_options = ProofOptions::new_secure(SecurityLevel::Conjecturable100Bits, 3);
     let args: commands::ProverArgs = commands::ProverArgs::parse();
@@ -247,7 +314,8 @@ fn main() {
                 return;
             }
 
-            let Some((proof, pub_inputs)) = generate_proof(&args.program_path, &proof_options)
+            let Some((proof, pub_inputs)) =
+                generate_proof(&args.program_path, &args.run_args, &proof_options)
             else {
                 return;
             };
@@ -301,7 +369,32 @@ fn main() {
                 return;
             };
 
-            verify_proof(proof, pub_inputs, &proof_options);
+            if verify_proof(proof, pub_inputs.clone(), &proof_options) {
+                print_statement(&pub_inputs, args.program_hash);
+            }
+        }
+        commands::ProverEntity::VerifyStoneJson(args) => {
+            let Ok(json) = std::fs::read_to_string(&args.proof_path) else {
+                eprintln!("Error opening {} file", args.proof_path);
+                return;
+            };
+
+            let parsed_proof =
+                StoneProof::from_json(&json).and_then(|proof| proof.to_cairo_proof());
+            let (proof, pub_inputs, proof_options) = match parsed_proof {
+                Ok(parsed_proof) => parsed_proof,
+                Err(err) => {
+                    eprintln!(
+                        "Error reading Stone JSON proof from file {}: {err:?}",
+                        args.proof_path
+                    );
+                    return;
+                }
+            };
+
+            if verify_proof(proof, pub_inputs.clone(), &proof_options) {
+                print_statement(&pub_inputs, args.program_hash);
+            }
         }
         commands::ProverEntity::ProveAndVerify(args) => {
             if args.program_path.contains(".cairo") {
@@ -309,7 +402,8 @@ fn main() {
                 return;
             }
 
-            let Some((proof, pub_inputs)) = generate_proof(&args.program_path, &proof_options)
+            let Some((proof, pub_inputs)) =
+                generate_proof(&args.program_path, &args.run_args, &proof_options)
             else {
                 return;
             };
@@ -319,7 +413,8 @@ fn main() {
             let out_file_path = args.program_path.replace(".cairo", ".json");
             match try_compile(&args.program_path, &out_file_path) {
                 Ok(_) => {
-                    let Some((proof, pub_inputs)) = generate_proof(&out_file_path, &proof_options)
+                    let Some((proof, pub_inputs)) =
+                        generate_proof(&out_file_path, &cairo0_run_args(), &proof_options)
                     else {
                         return;
                     };
@@ -335,7 +430,8 @@ fn main() {
             let out_file_path = args.program_path.replace(".cairo", ".json");
             match try_compile(&args.program_path, &out_file_path) {
                 Ok(_) => {
-                    let Some((proof, pub_inputs)) = generate_proof(&out_file_path, &proof_options)
+                    let Some((proof, pub_inputs)) =
+                        generate_proof(&out_file_path, &cairo0_run_args(), &proof_options)
                     else {
                         return;
                     };
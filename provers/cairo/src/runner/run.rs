@@ -1,18 +1,26 @@
 use crate::air::{PublicInputs, Segment, SegmentName};
+use crate::builtins::{EcdsaSignature, EcdsaSignatures};
 use crate::cairo_layout::CairoLayout;
 use crate::cairo_mem::CairoMemory;
+use crate::errors::BuiltinTraceError;
 use crate::execution_trace::build_main_trace;
 use crate::register_states::RegisterStates;
 use crate::Felt252;
 
 use super::vec_writer::VecWriter;
+use cairo1_run::{cairo_run_program, Cairo1RunConfig, FuncArg};
+use cairo_lang_sierra::ProgramParser;
+use cairo_vm::air_private_input::PrivateInput;
 use cairo_vm::cairo_run::{self, EncodeTraceError};
+use cairo_vm::felt::Felt252 as VMFelt252;
 
 use cairo_vm::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor;
 
 use cairo_vm::vm::errors::{
     cairo_run_errors::CairoRunError, trace_errors::TraceError, vm_errors::VirtualMachineError,
 };
+use cairo_vm::vm::runners::cairo_runner::CairoRunner;
+use cairo_vm::vm::vm_core::VirtualMachine;
 
 use cairo_vm::without_std::collections::HashMap;
 use lambdaworks_math::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
@@ -25,6 +33,12 @@ pub enum Error {
     EncodeTrace(EncodeTraceError),
     VirtualMachine(VirtualMachineError),
     Trace(TraceError),
+    BuiltinTrace(BuiltinTraceError),
+    Cairo1Runner(cairo1_run::error::Error),
+    /// The Sierra program couldn't be parsed.
+    SierraProgram(String),
+    /// The arguments of a Cairo 1 program couldn't be parsed.
+    InvalidProgramArgs(String),
 }
 
 impl From<std::io::Error> for Error {
@@ -57,6 +71,18 @@ impl From<TraceError> for Error {
     }
 }
 
+impl From<cairo1_run::error::Error> for Error {
+    fn from(err: cairo1_run::error::Error) -> Error {
+        Error::Cairo1Runner(err)
+    }
+}
+
+impl From<BuiltinTraceError> for Error {
+    fn from(err: BuiltinTraceError) -> Error {
+        Error::BuiltinTrace(err)
+    }
+}
+
 /// Runs a cairo program in JSON format and returns trace, memory and program length.
 /// Uses [cairo-rs](https://github.com/lambdaclass/cairo-rs/) project to run the program.
 ///
@@ -73,15 +99,15 @@ impl From<TraceError> for Error {
 /// Ok() in case of succes, with the following values:
 /// - register_states
 /// - cairo_mem
-/// - data_len
-/// - range_check: an Option<(usize, usize)> containing the start and end of range check.
+/// - public_inputs
+/// - ecdsa_signatures: the signatures added to the ECDSA builtin by the hints of the program.
 /// `Error` indicating the type of error.
 #[allow(clippy::type_complexity)]
 pub fn run_program(
     entrypoint_function: Option<&str>,
     layout: CairoLayout,
     program_content: &[u8],
-) -> Result<(RegisterStates, CairoMemory, PublicInputs), Error> {
+) -> Result<(RegisterStates, CairoMemory, PublicInputs, EcdsaSignatures), Error> {
     // default value for entrypoint is "main"
     let entrypoint = entrypoint_function.unwrap_or("main");
 
@@ -106,6 +132,94 @@ pub fn run_program(
             }
         };
 
+    Ok(prover_inputs_from_run(&runner, &vm, layout))
+}
+
+/// Runs a Cairo 1 program in proof mode, with the given arguments for its `main` function, and
+/// returns its trace, memory, public inputs and ECDSA signatures. The program is given as the text of its Sierra,
+/// as output by `cairo-compile --replace-ids`, and it's compiled to CASM before running it.
+///
+/// The builtins of the program must be in `layout`.
+pub fn run_cairo1_program(
+    layout: CairoLayout,
+    sierra_program: &str,
+    program_args: &[FuncArg],
+) -> Result<(RegisterStates, CairoMemory, PublicInputs, EcdsaSignatures), Error> {
+    let sierra_program = ProgramParser::new()
+        .parse(sierra_program)
+        .map_err(|err| Error::SierraProgram(err.to_string()))?;
+
+    let cairo_run_config = Cairo1RunConfig {
+        args: program_args,
+        trace_enabled: true,
+        relocate_mem: true,
+        layout: layout.as_str(),
+        proof_mode: true,
+        ..Default::default()
+    };
+
+    let (runner, vm, _, _) = cairo_run_program(&sierra_program, cairo_run_config)?;
+
+    Ok(prover_inputs_from_run(&runner, &vm, layout))
+}
+
+/// Parses the arguments of a Cairo 1 program. Arguments are separated by whitespace, and they
+/// are either a felt, in decimal or in hexadecimal with the `0x` prefix, or an array of felts
+/// between brackets, as in `1 [2 0x3 4] 5`.
+pub fn parse_cairo1_args(program_args: &str) -> Result<Vec<FuncArg>, Error> {
+    let invalid_args = || Error::InvalidProgramArgs(program_args.to_string());
+    let mut args = Vec::new();
+    let mut array: Option<Vec<VMFelt252>> = None;
+
+    for token in program_args.split_whitespace() {
+        let (opens_array, token) = match token.strip_prefix('[') {
+            Some(token) => (true, token),
+            None => (false, token),
+        };
+        let (closes_array, token) = match token.strip_suffix(']') {
+            Some(token) => (true, token),
+            None => (false, token),
+        };
+
+        if opens_array {
+            if array.is_some() {
+                return Err(invalid_args());
+            }
+            array = Some(Vec::new());
+        }
+
+        if !token.is_empty() {
+            let value = match token.strip_prefix("0x") {
+                Some(hex) => VMFelt252::parse_bytes(hex.as_bytes(), 16),
+                None => VMFelt252::parse_bytes(token.as_bytes(), 10),
+            }
+            .ok_or_else(invalid_args)?;
+            match array.as_mut() {
+                Some(array) => array.push(value),
+                None => args.push(FuncArg::Single(value)),
+            }
+        }
+
+        if closes_array {
+            let array = array.take().ok_or_else(invalid_args)?;
+            args.push(FuncArg::Array(array));
+        }
+    }
+
+    if array.is_some() {
+        return Err(invalid_args());
+    }
+
+    Ok(args)
+}
+
+/// Takes the relocated trace, the memory, the public inputs and the ECDSA signatures of a
+/// finished run of the Cairo VM.
+fn prover_inputs_from_run(
+    runner: &CairoRunner,
+    vm: &VirtualMachine,
+    layout: CairoLayout,
+) -> (RegisterStates, CairoMemory, PublicInputs, EcdsaSignatures) {
     let relocated_trace = vm.get_relocated_trace().unwrap();
 
     let mut trace_vec = Vec::<u8>::new();
@@ -125,7 +239,7 @@ pub fn run_program(
     let cairo_mem = CairoMemory::from_bytes_le(&memory_vec).unwrap();
     let register_states = RegisterStates::from_bytes_le(&trace_vec).unwrap();
 
-    let vm_pub_inputs = runner.get_air_public_input(&vm).unwrap();
+    let vm_pub_inputs = runner.get_air_public_input(vm).unwrap();
 
     let mut pub_memory: HashMap<Felt252, Felt252> = HashMap::new();
     vm_pub_inputs.public_memory.iter().for_each(|mem_cell| {
@@ -151,18 +265,74 @@ pub fn run_program(
         memory_segments,
         public_memory: pub_memory,
         num_steps,
+        layout,
     };
 
-    Ok((register_states, cairo_mem, public_inputs))
+    (
+        register_states,
+        cairo_mem,
+        public_inputs,
+        ecdsa_signatures(runner, vm),
+    )
+}
+
+/// Reads the signatures of the ECDSA builtin instances from the private input of the run, the
+/// same signatures the Cairo VM writes to Stone's `air_private_input.json`.
+fn ecdsa_signatures(runner: &CairoRunner, vm: &VirtualMachine) -> EcdsaSignatures {
+    let to_felt = |value: &VMFelt252| Felt252::from_hex_unchecked(&value.to_str_radix(16));
+    runner
+        .get_air_private_input(vm)
+        .0
+        .values()
+        .flatten()
+        .filter_map(|input| match input {
+            PrivateInput::Signature(signature) => Some((
+                signature.index,
+                EcdsaSignature {
+                    r: to_felt(&signature.signature_input.r),
+                    w: to_felt(&signature.signature_input.w),
+                },
+            )),
+            _ => None,
+        })
+        .collect()
 }
 
+/// Runs a program and builds the trace to prove it, with the ECDSA signatures added by the
+/// program while running.
 pub fn generate_prover_args(
     program_content: &[u8],
     layout: CairoLayout,
 ) -> Result<(TraceTable<Stark252PrimeField>, PublicInputs), Error> {
-    let (register_states, memory, mut public_inputs) = run_program(None, layout, program_content)?;
+    let (register_states, memory, mut public_inputs, ecdsa_signatures) =
+        run_program(None, layout, program_content)?;
+
+    let main_trace = build_main_trace(
+        &register_states,
+        &memory,
+        &mut public_inputs,
+        &ecdsa_signatures,
+    )?;
+
+    Ok((main_trace, public_inputs))
+}
+
+/// Runs a Cairo 1 program with the given arguments and builds the trace to prove it. See
+/// [`run_cairo1_program`].
+pub fn generate_cairo1_prover_args(
+    sierra_program: &str,
+    program_args: &[FuncArg],
+    layout: CairoLayout,
+) -> Result<(TraceTable<Stark252PrimeField>, PublicInputs), Error> {
+    let (register_states, memory, mut public_inputs, ecdsa_signatures) =
+        run_cairo1_program(layout, sierra_program, program_args)?;
 
-    let main_trace = build_main_trace(&register_states, &memory, &mut public_inputs);
+    let main_trace = build_main_trace(
+        &register_states,
+        &memory,
+        &mut public_inputs,
+        &ecdsa_signatures,
+    )?;
 
     Ok((main_trace, public_inputs))
 }
@@ -181,7 +351,12 @@ pub fn generate_prover_args_from_trace(
     let data_len = 0_usize;
     let mut pub_inputs = PublicInputs::from_regs_and_mem(&register_states, &memory, data_len);
 
-    let main_trace = build_main_trace(&register_states, &memory, &mut pub_inputs);
+    let main_trace = build_main_trace(
+        &register_states,
+        &memory,
+        &mut pub_inputs,
+        &EcdsaSignatures::new(),
+    )?;
 
     Ok((main_trace, pub_inputs))
 }
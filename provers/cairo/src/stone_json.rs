@@ -0,0 +1,433 @@
+//! A serializer of lambdaworks Cairo proofs in the JSON layout of the `proof.json` files of the
+//! Stone prover (https://github.com/starkware-libs/stone-prover/).
+//!
+//! A proof file holds the proof bytes (`proof_hex`), the public input of the Cairo run and the
+//! parameters the proof was generated with. `StoneProof` writes a lambdaworks Cairo proof in that
+//! layout and parses it back into the `StarkProof`, the `PublicInputs` and the `ProofOptions`
+//! that `verify_cairo_proof` takes. The transcript is seeded with the public input, so the
+//! challenges of a proof depend on the statement it proves.
+//!
+//! Only the file layout is shared with Stone. The proofs are checked against the constraints and
+//! trace layout of `CairoAIR`, which differ from the ones of Stone, so proofs generated by the
+//! Stone prover don't verify here and no such proof is tested.
+//!
+//! The `fri_step_list` must start with 0, for the deep composition polynomial, followed by the
+//! number of times each committed layer is folded, and its steps must fold the polynomial down to
+//! the power of two `last_layer_degree_bound`. The layout must be one of the layouts with a fixed
+//! set of builtins in `CairoAIR`: `plain`, `small` or `recursive`.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
+
+use lambdaworks_math::{traits::AsBytes, unsigned_integer::element::UnsignedInteger};
+use stark_platinum_prover::{
+    proof::{
+        options::ProofOptions,
+        stark::{
+            StarkProof, StoneCompatibleDeserializer, StoneCompatibleSerializer,
+            StoneDeserializationError,
+        },
+    },
+    prover::ProvingError,
+    transcript::StoneProverTranscript,
+};
+
+use crate::{
+    air::{verify_cairo_proof, CairoAIR, MemorySegmentMap, PublicInputs, Segment, SegmentName},
+    cairo_layout::CairoLayout,
+    Felt252, PrimeField,
+};
+
+/// The coset offset of proofs in the Stone format, the generator of the multiplicative group.
+const STONE_COSET_OFFSET: u64 = 3;
+
+#[derive(Debug)]
+pub enum StoneProofError {
+    Json(serde_json::Error),
+    /// `proof_hex` or a public memory value is not a valid hex string.
+    InvalidHex(String),
+    UnsupportedLayout(String),
+    /// The proof parameters can't be expressed as `ProofOptions`.
+    UnsupportedParameters(String),
+    InvalidPublicInput(String),
+    Deserialization(StoneDeserializationError),
+}
+
+impl From<serde_json::Error> for StoneProofError {
+    fn from(err: serde_json::Error) -> StoneProofError {
+        StoneProofError::Json(err)
+    }
+}
+
+impl From<StoneDeserializationError> for StoneProofError {
+    fn from(err: StoneDeserializationError) -> StoneProofError {
+        StoneProofError::Deserialization(err)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct StoneMemorySegment {
+    pub begin_addr: u64,
+    pub stop_ptr: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct StonePublicMemoryCell {
+    pub address: u64,
+    /// Hex string with a `0x` prefix.
+    pub value: String,
+    pub page: u64,
+}
+
+/// The public input of a Cairo run, as written by the Cairo VM with `--air_public_input`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct StonePublicInput {
+    pub layout: String,
+    pub rc_min: u16,
+    pub rc_max: u16,
+    pub n_steps: usize,
+    pub memory_segments: BTreeMap<String, StoneMemorySegment>,
+    pub public_memory: Vec<StonePublicMemoryCell>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct StoneFriParameters {
+    pub fri_step_list: Vec<usize>,
+    pub last_layer_degree_bound: usize,
+    pub n_queries: usize,
+    pub proof_of_work_bits: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct StoneStarkParameters {
+    pub fri: StoneFriParameters,
+    pub log_n_cosets: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct StoneProofParameters {
+    pub stark: StoneStarkParameters,
+}
+
+/// The contents of a `proof.json` in the Stone format. Other fields of the file, like the
+/// annotations, are ignored.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct StoneProof {
+    pub proof_hex: String,
+    pub public_input: StonePublicInput,
+    pub proof_parameters: StoneProofParameters,
+}
+
+impl StoneProof {
+    pub fn from_json(json: &str) -> Result<Self, StoneProofError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn to_json(&self) -> Result<String, StoneProofError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Writes a lambdaworks Cairo proof in the Stone JSON format.
+    pub fn from_cairo_proof(
+        proof: &StarkProof<PrimeField, PrimeField>,
+        pub_inputs: &PublicInputs,
+        proof_options: &ProofOptions,
+    ) -> Result<Self, StoneProofError> {
+        if proof_options.coset_offset != STONE_COSET_OFFSET || proof_options.zero_knowledge {
+            return Err(StoneProofError::UnsupportedParameters(
+                "proofs in the Stone format use a coset offset of 3 and no zero knowledge"
+                    .to_string(),
+            ));
+        }
+        if pub_inputs.fp_init != pub_inputs.ap_init {
+            return Err(StoneProofError::InvalidPublicInput(
+                "the initial fp and ap differ".to_string(),
+            ));
+        }
+        let (Some(rc_min), Some(rc_max)) = (pub_inputs.range_check_min, pub_inputs.range_check_max)
+        else {
+            return Err(StoneProofError::InvalidPublicInput(
+                "the range check bounds are missing".to_string(),
+            ));
+        };
+
+        let mut memory_segments: BTreeMap<String, StoneMemorySegment> = pub_inputs
+            .memory_segments
+            .iter()
+            .map(|(name, segment)| {
+                (
+                    segment_name(name).to_string(),
+                    StoneMemorySegment {
+                        begin_addr: segment.begin_addr as u64,
+                        stop_ptr: segment.stop_ptr as u64,
+                    },
+                )
+            })
+            .collect();
+        // The registers at the beginning and the end of the run are part of these two segments.
+        memory_segments.insert(
+            "program".to_string(),
+            StoneMemorySegment {
+                begin_addr: felt_to_u64(&pub_inputs.pc_init)?,
+                stop_ptr: felt_to_u64(&pub_inputs.pc_final)?,
+            },
+        );
+        memory_segments.insert(
+            "execution".to_string(),
+            StoneMemorySegment {
+                begin_addr: felt_to_u64(&pub_inputs.ap_init)?,
+                stop_ptr: felt_to_u64(&pub_inputs.ap_final)?,
+            },
+        );
+
+        let mut public_memory = pub_inputs
+            .public_memory
+            .iter()
+            .map(|(address, value)| {
+                Ok(StonePublicMemoryCell {
+                    address: felt_to_u64(address)?,
+                    value: value.representative().to_string(),
+                    page: 0,
+                })
+            })
+            .collect::<Result<Vec<_>, StoneProofError>>()?;
+        public_memory.sort_by_key(|cell| cell.address);
+
+        let log_trace_length = proof.trace_length.trailing_zeros() as usize;
+        let fri_step_list = match proof_options.fri_folding_steps(log_trace_length) {
+            Some(steps) => [vec![0], steps].concat(),
+            None => {
+                return Err(StoneProofError::UnsupportedParameters(format!(
+                    "FRI steps {:?} with a last layer of degree bound {}",
+                    proof_options.fri_step_list, proof_options.fri_last_layer_degree_bound
+                )))
+            }
+        };
+
+        let bytes = StoneCompatibleSerializer::serialize_proof::<CairoAIR>(
+            proof,
+            pub_inputs,
+            proof_options,
+        )
+        .map_err(|ProvingError::WrongParameter(message)| {
+            StoneProofError::InvalidPublicInput(message)
+        })?;
+
+        Ok(Self {
+            proof_hex: format!("0x{}", encode_hex(&bytes)),
+            public_input: StonePublicInput {
+                layout: pub_inputs.layout.as_str().to_string(),
+                rc_min,
+                rc_max,
+                n_steps: pub_inputs.num_steps,
+                memory_segments,
+                public_memory,
+            },
+            proof_parameters: StoneProofParameters {
+                stark: StoneStarkParameters {
+                    fri: StoneFriParameters {
+                        fri_step_list,
+                        last_layer_degree_bound: proof_options.fri_last_layer_degree_bound,
+                        n_queries: proof_options.fri_number_of_queries,
+                        proof_of_work_bits: proof_options.grinding_factor,
+                    },
+                    log_n_cosets: proof_options.blowup_factor.trailing_zeros(),
+                },
+            },
+        })
+    }
+
+    /// Returns the length of the trace, which the Stone format doesn't record but follows from the FRI
+    /// parameters: FRI folds the deep composition polynomial, of degree less than the trace
+    /// length, down to the last layer.
+    pub fn trace_length(&self) -> Result<usize, StoneProofError> {
+        let fri = &self.proof_parameters.stark.fri;
+        let unsupported = || {
+            StoneProofError::UnsupportedParameters(format!(
+                "FRI steps {:?} with a last layer of degree bound {}",
+                fri.fri_step_list, fri.last_layer_degree_bound
+            ))
+        };
+        if fri.fri_step_list.first() != Some(&0) || !fri.last_layer_degree_bound.is_power_of_two() {
+            return Err(unsupported());
+        }
+        let log_trace_length = fri
+            .fri_step_list
+            .iter()
+            .try_fold(
+                fri.last_layer_degree_bound.trailing_zeros() as usize,
+                |log_degree_bound, step| log_degree_bound.checked_add(*step),
+            )
+            .ok_or_else(unsupported)?;
+        let trace_length = u32::try_from(log_trace_length)
+            .ok()
+            .and_then(|log_trace_length| 1usize.checked_shl(log_trace_length))
+            .filter(|trace_length| *trace_length > 1)
+            .ok_or_else(unsupported)?;
+
+        match self.proof_options()?.fri_folding_steps(log_trace_length) {
+            Some(_) => Ok(trace_length),
+            None => Err(unsupported()),
+        }
+    }
+
+    pub fn proof_options(&self) -> Result<ProofOptions, StoneProofError> {
+        let stark = &self.proof_parameters.stark;
+        let blowup_factor = 1u8.checked_shl(stark.log_n_cosets).ok_or_else(|| {
+            StoneProofError::UnsupportedParameters(format!(
+                "a blowup factor of 2^{}",
+                stark.log_n_cosets
+            ))
+        })?;
+
+        Ok(ProofOptions {
+            blowup_factor,
+            fri_number_of_queries: stark.fri.n_queries,
+            coset_offset: STONE_COSET_OFFSET,
+            grinding_factor: stark.fri.proof_of_work_bits,
+            zero_knowledge: false,
+            fri_step_list: stark.fri.fri_step_list.clone(),
+            fri_last_layer_degree_bound: stark.fri.last_layer_degree_bound,
+        })
+    }
+
+    /// Returns the public inputs of the `CairoAIR`. The initial and final `pc` are the bounds of
+    /// the program segment and the initial and final `ap` the ones of the execution segment.
+    pub fn public_inputs(&self) -> Result<PublicInputs, StoneProofError> {
+        let public_input = &self.public_input;
+        let layout = CairoLayout::from_str(&public_input.layout)
+            .ok()
+            .filter(|layout| layout.builtins().is_some())
+            .ok_or_else(|| StoneProofError::UnsupportedLayout(public_input.layout.clone()))?;
+
+        let segment = |name: &str| {
+            public_input.memory_segments.get(name).ok_or_else(|| {
+                StoneProofError::InvalidPublicInput(format!("missing {name} segment"))
+            })
+        };
+        let program = segment("program")?;
+        let execution = segment("execution")?;
+
+        let mut memory_segments = MemorySegmentMap::new();
+        for (name, segment) in public_input.memory_segments.iter() {
+            let name = match name.as_str() {
+                "program" | "execution" => continue,
+                "range_check" => SegmentName::RangeCheck,
+                "output" => SegmentName::Output,
+                "ecdsa" => SegmentName::Ecdsa,
+                "pedersen" => SegmentName::Pedersen,
+                "bitwise" => SegmentName::Bitwise,
+                "poseidon" => SegmentName::Poseidon,
+                name => return Err(StoneProofError::UnsupportedLayout(name.to_string())),
+            };
+            if segment.stop_ptr < segment.begin_addr {
+                return Err(StoneProofError::InvalidPublicInput(format!(
+                    "segment {name:?} ends before it begins"
+                )));
+            }
+            memory_segments.insert(name, Segment::new(segment.begin_addr, segment.stop_ptr));
+        }
+
+        let public_memory = public_input
+            .public_memory
+            .iter()
+            .map(|cell| {
+                let value = Felt252::from_hex(&cell.value)
+                    .map_err(|_| StoneProofError::InvalidHex(cell.value.clone()))?;
+                Ok((Felt252::from(cell.address), value))
+            })
+            .collect::<Result<HashMap<_, _>, StoneProofError>>()?;
+
+        Ok(PublicInputs {
+            pc_init: Felt252::from(program.begin_addr),
+            ap_init: Felt252::from(execution.begin_addr),
+            fp_init: Felt252::from(execution.begin_addr),
+            pc_final: Felt252::from(program.stop_ptr),
+            ap_final: Felt252::from(execution.stop_ptr),
+            range_check_min: Some(public_input.rc_min),
+            range_check_max: Some(public_input.rc_max),
+            memory_segments,
+            public_memory,
+            num_steps: public_input.n_steps,
+            layout,
+        })
+    }
+
+    /// Parses the proof, returning it along with what `verify_cairo_proof` needs to check it.
+    pub fn to_cairo_proof(
+        &self,
+    ) -> Result<
+        (
+            StarkProof<PrimeField, PrimeField>,
+            PublicInputs,
+            ProofOptions,
+        ),
+        StoneProofError,
+    > {
+        let trace_length = self.trace_length()?;
+        let pub_inputs = self.public_inputs()?;
+        let proof_options = self.proof_options()?;
+        let bytes = decode_hex(&self.proof_hex)?;
+
+        let proof = StoneCompatibleDeserializer::deserialize_proof::<CairoAIR>(
+            &bytes,
+            trace_length,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&pub_inputs.as_bytes()),
+        )?;
+
+        Ok((proof, pub_inputs, proof_options))
+    }
+}
+
+/// Parses a `proof.json` in the Stone format and verifies it with the `CairoAIR`. Returns an
+/// error if the file can't be parsed and `Ok(false)` if the proof is invalid.
+pub fn verify_stone_json_proof(json: &str) -> Result<bool, StoneProofError> {
+    let (proof, pub_inputs, proof_options) = StoneProof::from_json(json)?.to_cairo_proof()?;
+    Ok(verify_cairo_proof(&proof, &pub_inputs, &proof_options))
+}
+
+fn segment_name(name: &SegmentName) -> &'static str {
+    match name {
+        SegmentName::RangeCheck => "range_check",
+        SegmentName::Output => "output",
+        SegmentName::Program => "program",
+        SegmentName::Execution => "execution",
+        SegmentName::Ecdsa => "ecdsa",
+        SegmentName::Pedersen => "pedersen",
+        SegmentName::Bitwise => "bitwise",
+        SegmentName::Poseidon => "poseidon",
+    }
+}
+
+fn felt_to_u64(felt: &Felt252) -> Result<u64, StoneProofError> {
+    let UnsignedInteger { limbs } = felt.representative();
+    if limbs[..3].iter().any(|limb| *limb != 0) {
+        return Err(StoneProofError::InvalidPublicInput(format!(
+            "{} is not an address",
+            felt.representative()
+        )));
+    }
+    Ok(limbs[3])
+}
+
+pub(crate) fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn decode_hex(hex: &str) -> Result<Vec<u8>, StoneProofError> {
+    let digits = hex.strip_prefix("0x").unwrap_or(hex);
+    if digits.len() % 2 == 1 || !digits.is_ascii() {
+        return Err(StoneProofError::InvalidHex(hex.to_string()));
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&digits[i..i + 2], 16)
+                .map_err(|_| StoneProofError::InvalidHex(hex.to_string()))
+        })
+        .collect()
+}
@@ -1,12 +1,32 @@
 use crate::{
-    air::{generate_cairo_proof, verify_cairo_proof, CairoAIR},
+    air::{
+        generate_cairo_proof, verify_cairo_proof, CairoAIR, MemorySegmentMap, PublicInputs,
+        Segment, SegmentName,
+    },
+    builtins::{Builtin, EcdsaSignature, EcdsaSignatures},
     cairo_layout::CairoLayout,
-    runner::run::generate_prover_args,
+    cairo_mem::CairoMemory,
+    errors::BuiltinTraceError,
+    execution_trace::build_main_trace,
+    fact::{program_hash, ProgramHashFunction},
+    register_states::RegisterStates,
+    runner::run::{
+        generate_prover_args, generate_prover_args_from_trace, parse_cairo1_args, run_program,
+        Error,
+    },
+    stone_json::{verify_stone_json_proof, StoneProof, StoneProofError},
     tests::utils::{
-        cairo0_program_path, test_prove_cairo_program, test_prove_cairo_program_from_trace,
+        cairo0_program_path, cairo1_program_path, test_prove_cairo1_program,
+        test_prove_cairo_program, test_prove_cairo_program_from_trace,
     },
     Felt252,
 };
+use assert_matches::assert_matches;
+use cairo1_run::FuncArg;
+use lambdaworks_crypto::hash::{
+    pedersen::{Pedersen, PedersenStarkCurve},
+    poseidon::{starknet::PoseidonCairoStark252, Poseidon},
+};
 use lambdaworks_math::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
 use stark_platinum_prover::{
     debug::validate_trace,
@@ -15,6 +35,7 @@ use stark_platinum_prover::{
         options::{ProofOptions, SecurityLevel},
         stark::StarkProof,
     },
+    trace::TraceTable,
     traits::AIR,
     transcript::StoneProverTranscript,
 };
@@ -70,18 +91,151 @@ fn test_verifier_rejects_wrong_authentication_paths() {
     assert!(!verify_cairo_proof(&proof, &pub_inputs, &proof_options));
 }
 
+#[test_log::test]
+fn test_output_and_program_are_read_from_the_public_inputs() {
+    let program_content = std::fs::read(cairo0_program_path("output_program.json")).unwrap();
+    let (_, pub_inputs) = generate_prover_args(&program_content, CairoLayout::Plain).unwrap();
+
+    let program: serde_json::Value = serde_json::from_slice(&program_content).unwrap();
+    let bytecode: Vec<_> = program["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|value| Felt252::from_hex_unchecked(value.as_str().unwrap()))
+        .collect();
+
+    assert_eq!(pub_inputs.output().unwrap(), [Felt252::from(1234)]);
+    assert_eq!(pub_inputs.program().unwrap(), bytecode);
+    assert_eq!(
+        program_hash(&pub_inputs, ProgramHashFunction::Pedersen).unwrap(),
+        ProgramHashFunction::Pedersen.hash_many(&bytecode)
+    );
+}
+
 #[test_log::test]
 fn test_prove_cairo_fibonacci_1000() {
     let layout = CairoLayout::Plain;
     test_prove_cairo_program(&cairo0_program_path("fibonacci_1000.json"), layout);
 }
 
-// #[cfg_attr(feature = "metal", ignore)]
-// #[test_log::test]
-// fn test_prove_cairo_fibonacci_casm() {
-//     let layout = CairoLayout::Plain;
-//     test_prove_cairo1_program(&cairo1_program_path("fibonacci_cairo1_mod.casm"), layout);
-// }
+#[test_log::test]
+fn test_prove_cairo1_fibonacci() {
+    let layout = CairoLayout::Small;
+    test_prove_cairo1_program(&cairo1_program_path("fibonacci.sierra"), "10", layout);
+}
+
+#[test_log::test]
+fn test_prove_cairo1_array_sum() {
+    let layout = CairoLayout::Recursive;
+    test_prove_cairo1_program(
+        &cairo1_program_path("array_sum.sierra"),
+        "[1 2 0x3] 4",
+        layout,
+    );
+}
+
+#[test_log::test]
+fn test_prove_cairo_pedersen_program() {
+    let program_content = std::fs::read(cairo0_program_path("pedersen_program.json")).unwrap();
+    let (_, pub_inputs) = generate_prover_args(&program_content, CairoLayout::Small).unwrap();
+    let hash = |x: Felt252, y: Felt252| PedersenStarkCurve::hash(&x, &y);
+    let two = Felt252::from(2);
+
+    // The second hash has canonical inputs with the bits 251, 196 and 192 set.
+    assert_eq!(
+        pub_inputs.output().unwrap(),
+        [
+            hash(Felt252::from(0x1234), Felt252::from(0x5678)),
+            hash(two.pow(251_u64), -Felt252::one()),
+        ]
+    );
+    test_prove_cairo_program(
+        &cairo0_program_path("pedersen_program.json"),
+        CairoLayout::Small,
+    );
+}
+
+#[test_log::test]
+fn test_prove_cairo_range_check_program() {
+    test_prove_cairo_program(&cairo0_program_path("rc_program.json"), CairoLayout::Small);
+}
+
+#[test_log::test]
+fn test_prove_cairo_ecdsa_program() {
+    // The signatures added by the program while running are read from the Cairo VM.
+    test_prove_cairo_program(
+        &cairo0_program_path("ecdsa_program.json"),
+        CairoLayout::Small,
+    );
+}
+
+#[test_log::test]
+fn test_prove_cairo_bitwise_program() {
+    let program_content = std::fs::read(cairo0_program_path("bitwise_program.json")).unwrap();
+    let (_, pub_inputs) = generate_prover_args(&program_content, CairoLayout::Recursive).unwrap();
+
+    assert_eq!(
+        pub_inputs.output().unwrap(),
+        [
+            Felt252::from(0x00f0),
+            Felt252::from(0xff00),
+            Felt252::from(0xfff0)
+        ]
+    );
+    test_prove_cairo_program(
+        &cairo0_program_path("bitwise_program.json"),
+        CairoLayout::Recursive,
+    );
+}
+
+#[test_log::test]
+fn test_prove_cairo_poseidon_program_in_the_dynamic_layout() {
+    // None of the layouts with a fixed set of builtins in `CairoAIR` has the poseidon builtin,
+    // so the program runs in a layout of the Cairo VM that has it and is proven in the dynamic
+    // layout, which has the builtins used by the program.
+    let program_content = std::fs::read(cairo0_program_path("poseidon_program.json")).unwrap();
+    let (register_states, memory, mut pub_inputs, ecdsa_signatures) =
+        run_program(None, CairoLayout::RecursiveLargeOutput, &program_content).unwrap();
+    pub_inputs.layout = CairoLayout::Dynamic;
+    let main_trace = build_main_trace(
+        &register_states,
+        &memory,
+        &mut pub_inputs,
+        &ecdsa_signatures,
+    )
+    .unwrap();
+
+    assert_eq!(
+        pub_inputs.output().unwrap(),
+        [PoseidonCairoStark252::hash(
+            &Felt252::from(1),
+            &Felt252::from(2)
+        )]
+    );
+    let proof_options = ProofOptions::default_test_options();
+    let proof = generate_cairo_proof(&main_trace, &pub_inputs, &proof_options).unwrap();
+    assert!(verify_cairo_proof(&proof, &pub_inputs, &proof_options));
+}
+
+#[test_log::test]
+fn test_cairo1_program_args_are_parsed() {
+    let program_args = parse_cairo1_args("1 [2 0x3] []").unwrap();
+
+    assert_matches!(
+        program_args.as_slice(),
+        [FuncArg::Single(_), FuncArg::Array(array), FuncArg::Array(empty)]
+            if array.len() == 2 && empty.is_empty()
+    );
+    assert_matches!(
+        parse_cairo1_args("[1 [2]]"),
+        Err(Error::InvalidProgramArgs(_))
+    );
+    assert_matches!(parse_cairo1_args("[1 2"), Err(Error::InvalidProgramArgs(_)));
+    assert_matches!(
+        parse_cairo1_args("1 two"),
+        Err(Error::InvalidProgramArgs(_))
+    );
+}
 
 #[test_log::test]
 fn test_verifier_rejects_proof_of_a_slightly_different_program() {
@@ -148,7 +302,7 @@ fn check_simple_cairo_trace_evaluates_to_zero() {
     let mut transcript = StoneProverTranscript::new(&[]);
 
     let proof_options = ProofOptions::default_test_options();
-    let cairo_air = CairoAIR::new(main_trace.n_rows(), &public_input, &proof_options);
+    let cairo_air = CairoAIR::new(main_trace.n_rows(), &public_input, &proof_options).unwrap();
     let rap_challenges = cairo_air.build_rap_challenges(&mut transcript);
 
     let aux_trace = cairo_air.build_auxiliary_trace(&main_trace, &rap_challenges);
@@ -164,7 +318,8 @@ fn check_simple_cairo_trace_evaluates_to_zero() {
         &aux_polys,
         &domain,
         &rap_challenges
-    ));
+    )
+    .is_valid());
 }
 
 #[test]
@@ -192,3 +347,405 @@ fn deserialize_and_verify() {
     // The proof is verified successfully.
     assert!(verify_cairo_proof(&proof, &pub_inputs, &proof_options));
 }
+
+/// A lambdaworks proof of Fibonacci 5 written in the Stone JSON format. No proof generated by the
+/// Stone prover is checked, since those prove Stone's own AIR.
+fn fibonacci_5_stone_proof() -> StoneProof {
+    fibonacci_5_stone_proof_with_options(&ProofOptions::default_test_options())
+}
+
+fn fibonacci_5_stone_proof_with_options(proof_options: &ProofOptions) -> StoneProof {
+    let (main_trace, pub_inputs) = generate_prover_args_from_trace(
+        &cairo0_program_path("fibonacci_5_trace.bin"),
+        &cairo0_program_path("fibonacci_5_memory.bin"),
+    )
+    .unwrap();
+    let proof = generate_cairo_proof(&main_trace, &pub_inputs, proof_options).unwrap();
+
+    StoneProof::from_cairo_proof(&proof, &pub_inputs, proof_options).unwrap()
+}
+
+#[test]
+fn test_stone_proof_json_verifies() {
+    let json = fibonacci_5_stone_proof().to_json().unwrap();
+
+    assert!(verify_stone_json_proof(&json).unwrap());
+}
+
+#[test]
+fn test_stone_proof_json_is_parsed_back_into_the_same_proof() {
+    let stone_proof = fibonacci_5_stone_proof();
+    let (proof, pub_inputs, proof_options) = stone_proof.to_cairo_proof().unwrap();
+
+    assert_eq!(
+        StoneProof::from_cairo_proof(&proof, &pub_inputs, &proof_options).unwrap(),
+        stone_proof
+    );
+}
+
+#[test]
+fn test_stone_proof_json_with_a_tampered_proof_does_not_verify() {
+    let mut stone_proof = fibonacci_5_stone_proof();
+    // The last byte belongs to the decommitment of the last FRI layer.
+    let last_digit = stone_proof.proof_hex.pop().unwrap();
+    stone_proof
+        .proof_hex
+        .push(if last_digit == '0' { '1' } else { '0' });
+
+    assert!(!verify_stone_json_proof(&stone_proof.to_json().unwrap()).unwrap());
+}
+
+#[test]
+fn test_stone_proof_json_with_different_range_check_bounds_does_not_verify() {
+    let mut stone_proof = fibonacci_5_stone_proof();
+    stone_proof.public_input.rc_max -= 1;
+
+    assert!(!verify_stone_json_proof(&stone_proof.to_json().unwrap()).unwrap());
+}
+
+#[test]
+fn test_stone_proof_json_with_fri_steps_of_more_than_one_layer_verifies() {
+    let log_trace_length = fibonacci_5_stone_proof()
+        .proof_parameters
+        .stark
+        .fri
+        .fri_step_list
+        .len()
+        - 1;
+    let mut fri_step_list = vec![0, 2];
+    fri_step_list.resize(log_trace_length - 1, 1);
+    let proof_options = ProofOptions {
+        fri_step_list: fri_step_list.clone(),
+        fri_last_layer_degree_bound: 2,
+        ..ProofOptions::default_test_options()
+    };
+
+    let stone_proof = fibonacci_5_stone_proof_with_options(&proof_options);
+    assert_eq!(
+        stone_proof.proof_parameters.stark.fri.fri_step_list,
+        fri_step_list
+    );
+    assert_eq!(
+        stone_proof
+            .proof_parameters
+            .stark
+            .fri
+            .last_layer_degree_bound,
+        2
+    );
+    assert_eq!(stone_proof.trace_length().unwrap(), 1 << log_trace_length);
+    assert!(verify_stone_json_proof(&stone_proof.to_json().unwrap()).unwrap());
+}
+
+#[test]
+fn test_stone_proof_json_with_invalid_fri_steps_is_rejected() {
+    let stone_proof = fibonacci_5_stone_proof();
+    let fri = &stone_proof.proof_parameters.stark.fri;
+    let mut ones = fri.fri_step_list.clone();
+    ones[0] = 1;
+    let mut empty_step = fri.fri_step_list.clone();
+    empty_step.push(0);
+
+    for (fri_step_list, last_layer_degree_bound) in [
+        (ones, 1),
+        (empty_step, 1),
+        (vec![], 1),
+        (fri.fri_step_list.clone(), 3),
+    ] {
+        let mut stone_proof = stone_proof.clone();
+        stone_proof.proof_parameters.stark.fri.fri_step_list = fri_step_list;
+        stone_proof
+            .proof_parameters
+            .stark
+            .fri
+            .last_layer_degree_bound = last_layer_degree_bound;
+
+        assert_matches!(
+            verify_stone_json_proof(&stone_proof.to_json().unwrap()),
+            Err(StoneProofError::UnsupportedParameters(_))
+        );
+    }
+}
+
+/// Builds the trace of Fibonacci 5 in `layout`, with the memory cells of some builtin instances
+/// written to memory after the program's own cells.
+fn fibonacci_5_with_instances(
+    layout: CairoLayout,
+    instances: Vec<(SegmentName, Vec<Felt252>)>,
+    ecdsa_signatures: &EcdsaSignatures,
+) -> Result<(TraceTable<Stark252PrimeField>, PublicInputs), BuiltinTraceError> {
+    let register_states =
+        RegisterStates::from_file(&cairo0_program_path("fibonacci_5_trace.bin")).unwrap();
+    let mut memory =
+        CairoMemory::from_file(&cairo0_program_path("fibonacci_5_memory.bin")).unwrap();
+
+    let mut memory_segments = MemorySegmentMap::new();
+    let mut address = memory.data.keys().max().unwrap() + 1;
+    for (segment_name, cells) in instances {
+        memory_segments.insert(
+            segment_name,
+            Segment::new(address, address + cells.len() as u64),
+        );
+        for value in cells {
+            memory.data.insert(address, value);
+            address += 1;
+        }
+    }
+
+    let mut pub_inputs = PublicInputs::from_regs_and_mem(&register_states, &memory, 0);
+    pub_inputs.memory_segments = memory_segments;
+    pub_inputs.layout = layout;
+    let main_trace =
+        build_main_trace(&register_states, &memory, &mut pub_inputs, ecdsa_signatures)?;
+
+    Ok((main_trace, pub_inputs))
+}
+
+/// Fibonacci 5 in the dynamic layout with an instance of each of the pedersen, ecdsa, bitwise
+/// and poseidon builtins.
+fn fibonacci_5_with_builtins() -> (TraceTable<Stark252PrimeField>, PublicInputs) {
+    let (x, y) = (Felt252::from(0x1234), Felt252::from(0x5678));
+    let mut poseidon_state = [Felt252::from(1), Felt252::from(2), Felt252::from(3)];
+    PoseidonCairoStark252::hades_permutation(&mut poseidon_state);
+    // The STARK curve generator signs `n + 1 - g_x` with `r = g_x` and `w = 1`.
+    let generator_x = Felt252::from_hex_unchecked(
+        "1ef15c18599971b7beced415a40f0c7deacfd9b0d1819e03d723d8bc943cfca",
+    );
+    let curve_order = Felt252::from_hex_unchecked(
+        "800000000000010ffffffffffffffffb781126dcae7b2321e66a241adc64d2f",
+    );
+    let instances = [
+        (
+            SegmentName::Pedersen,
+            vec![x, y, PedersenStarkCurve::hash(&x, &y)],
+        ),
+        (
+            SegmentName::Ecdsa,
+            vec![generator_x, curve_order + Felt252::one() - generator_x],
+        ),
+        (
+            SegmentName::Bitwise,
+            vec![
+                x,
+                y,
+                Felt252::from(0x1234 & 0x5678),
+                Felt252::from(0x1234 ^ 0x5678),
+                Felt252::from(0x1234 | 0x5678),
+            ],
+        ),
+        (
+            SegmentName::Poseidon,
+            [
+                [Felt252::from(1), Felt252::from(2), Felt252::from(3)],
+                poseidon_state,
+            ]
+            .concat(),
+        ),
+    ];
+
+    let mut ecdsa_signatures = EcdsaSignatures::new();
+    ecdsa_signatures.insert(
+        0,
+        EcdsaSignature {
+            r: generator_x,
+            w: Felt252::one(),
+        },
+    );
+
+    fibonacci_5_with_instances(CairoLayout::Dynamic, instances.to_vec(), &ecdsa_signatures).unwrap()
+}
+
+fn builtin_trace_is_valid(
+    main_trace: &TraceTable<Stark252PrimeField>,
+    pub_inputs: &PublicInputs,
+) -> bool {
+    let proof_options = ProofOptions::default_test_options();
+    let cairo_air = CairoAIR::new(main_trace.n_rows(), pub_inputs, &proof_options).unwrap();
+    let mut transcript = StoneProverTranscript::new(&[]);
+    let rap_challenges = cairo_air.build_rap_challenges(&mut transcript);
+    let aux_trace = cairo_air.build_auxiliary_trace(main_trace, &rap_challenges);
+
+    let mut trace_polys = main_trace.compute_trace_polys::<Stark252PrimeField>();
+    let aux_polys = aux_trace.compute_trace_polys::<Stark252PrimeField>();
+    trace_polys.extend_from_slice(&aux_polys);
+    let domain = Domain::new(&cairo_air);
+
+    validate_trace(
+        &cairo_air,
+        &trace_polys,
+        &aux_polys,
+        &domain,
+        &rap_challenges,
+    )
+    .is_valid()
+}
+
+#[test]
+fn test_prove_cairo_fibonacci_5_with_builtins() {
+    let (main_trace, pub_inputs) = fibonacci_5_with_builtins();
+    let proof_options = ProofOptions::default_test_options();
+
+    assert!(builtin_trace_is_valid(&main_trace, &pub_inputs));
+    let proof = generate_cairo_proof(&main_trace, &pub_inputs, &proof_options).unwrap();
+    assert!(verify_cairo_proof(&proof, &pub_inputs, &proof_options));
+}
+
+#[test]
+fn test_builtin_trace_with_a_wrong_output_is_not_valid() {
+    let (mut main_trace, pub_inputs) = fibonacci_5_with_builtins();
+    let proof_options = ProofOptions::default_test_options();
+    let cairo_air = CairoAIR::new(main_trace.n_rows(), &pub_inputs, &proof_options).unwrap();
+    let pedersen = &cairo_air.builtins[0];
+    assert_eq!(pedersen.builtin, Builtin::Pedersen);
+
+    // The hash is the third cell of the instance, in its last row.
+    let row = Builtin::Pedersen.instance_period() - 1;
+    main_trace.get_row_mut(row)[pedersen.mem_val_column()] += Felt252::one();
+
+    assert!(!builtin_trace_is_valid(&main_trace, &pub_inputs));
+}
+
+#[test]
+fn test_builtin_trace_with_a_missing_ecdsa_signature_is_rejected() {
+    let instances = vec![(SegmentName::Ecdsa, vec![Felt252::one(), Felt252::one()])];
+
+    assert_matches!(
+        fibonacci_5_with_instances(CairoLayout::Dynamic, instances, &EcdsaSignatures::new()),
+        Err(BuiltinTraceError::MissingSignature(0))
+    );
+}
+
+/// Proves Fibonacci 5 in a layout whose builtins are not used by the program, so that their
+/// components only have padding instances.
+fn test_prove_cairo_fibonacci_5_with_unused_builtins(layout: CairoLayout) {
+    let (main_trace, pub_inputs) =
+        fibonacci_5_with_instances(layout, Vec::new(), &EcdsaSignatures::new()).unwrap();
+    let proof_options = ProofOptions::default_test_options();
+
+    let proof = generate_cairo_proof(&main_trace, &pub_inputs, &proof_options).unwrap();
+    assert!(verify_cairo_proof(&proof, &pub_inputs, &proof_options));
+}
+
+#[test]
+fn test_prove_cairo_fibonacci_5_with_small_layout() {
+    test_prove_cairo_fibonacci_5_with_unused_builtins(CairoLayout::Small);
+}
+
+#[test]
+fn test_prove_cairo_fibonacci_5_with_recursive_layout() {
+    test_prove_cairo_fibonacci_5_with_unused_builtins(CairoLayout::Recursive);
+}
+
+#[test]
+fn test_prove_cairo_fibonacci_5_with_the_builtins_of_the_recursive_layout() {
+    let (x, y) = (Felt252::from(0xf0f0), Felt252::from(0x0ff0));
+    // The limbs of the range-checked values are in the range of the offsets of the program.
+    let range_checked = Felt252::from_hex_unchecked("80008000800080008000800080008000");
+    let instances = vec![
+        (
+            SegmentName::Pedersen,
+            vec![x, y, PedersenStarkCurve::hash(&x, &y)],
+        ),
+        (SegmentName::RangeCheck, vec![range_checked, range_checked]),
+        (
+            SegmentName::Bitwise,
+            vec![
+                x,
+                y,
+                Felt252::from(0x00f0),
+                Felt252::from(0xff00),
+                Felt252::from(0xfff0),
+            ],
+        ),
+    ];
+    let (main_trace, pub_inputs) =
+        fibonacci_5_with_instances(CairoLayout::Recursive, instances, &EcdsaSignatures::new())
+            .unwrap();
+    let proof_options = ProofOptions::default_test_options();
+
+    assert!(builtin_trace_is_valid(&main_trace, &pub_inputs));
+    let proof = generate_cairo_proof(&main_trace, &pub_inputs, &proof_options).unwrap();
+    assert!(verify_cairo_proof(&proof, &pub_inputs, &proof_options));
+}
+
+#[test]
+fn test_pedersen_trace_of_canonical_inputs_with_the_top_bits_set_is_valid() {
+    let two = Felt252::from(2);
+    // `p - 1` has the bits 251, 196 and 192 set.
+    let (x, y) = (
+        -Felt252::one(),
+        two.pow(251_u64) + two.pow(196_u64) + Felt252::from(5),
+    );
+    let instances = vec![(
+        SegmentName::Pedersen,
+        vec![x, y, PedersenStarkCurve::hash(&x, &y)],
+    )];
+    let (main_trace, pub_inputs) =
+        fibonacci_5_with_instances(CairoLayout::Recursive, instances, &EcdsaSignatures::new())
+            .unwrap();
+
+    assert!(builtin_trace_is_valid(&main_trace, &pub_inputs));
+}
+
+#[test]
+fn test_range_check_trace_with_a_limb_out_of_the_range_is_not_valid() {
+    let range_checked = Felt252::from_hex_unchecked("80008000800080008000800080008000");
+    let instances = vec![(SegmentName::RangeCheck, vec![range_checked])];
+    let (mut main_trace, pub_inputs) =
+        fibonacci_5_with_instances(CairoLayout::Recursive, instances, &EcdsaSignatures::new())
+            .unwrap();
+    let proof_options = ProofOptions::default_test_options();
+    let cairo_air = CairoAIR::new(main_trace.n_rows(), &pub_inputs, &proof_options).unwrap();
+    let range_check = &cairo_air.builtins[1];
+    assert_eq!(range_check.builtin, Builtin::RangeCheck);
+
+    // Moves a unit from the last limb to the previous one, which keeps the value of the cell but
+    // takes the last limb out of the range. The accumulated value follows the limb column.
+    let limb = range_check.range_check_column().unwrap();
+    main_trace.get_row_mut(6)[limb] += Felt252::one();
+    main_trace.get_row_mut(6)[limb + 1] += Felt252::one();
+    main_trace.get_row_mut(7)[limb] += -Felt252::from(1 << 16);
+
+    assert!(!builtin_trace_is_valid(&main_trace, &pub_inputs));
+}
+
+#[test]
+fn test_builtin_trace_of_a_builtin_out_of_the_layout_is_rejected() {
+    let instances = vec![(SegmentName::Bitwise, vec![Felt252::one(), Felt252::one()])];
+
+    assert_matches!(
+        fibonacci_5_with_instances(CairoLayout::Small, instances, &EcdsaSignatures::new()),
+        Err(BuiltinTraceError::BuiltinNotInLayout(Builtin::Bitwise))
+    );
+    assert_matches!(
+        fibonacci_5_with_instances(CairoLayout::Starknet, Vec::new(), &EcdsaSignatures::new()),
+        Err(BuiltinTraceError::UnsupportedLayout(CairoLayout::Starknet))
+    );
+}
+
+#[test]
+fn test_cairo_air_of_a_builtin_out_of_the_layout_is_an_error() {
+    let (main_trace, mut pub_inputs) =
+        fibonacci_5_with_instances(CairoLayout::Small, Vec::new(), &EcdsaSignatures::new())
+            .unwrap();
+    let proof_options = ProofOptions::default_test_options();
+    pub_inputs
+        .memory_segments
+        .insert(SegmentName::Bitwise, Segment::new(0, 0));
+
+    assert!(CairoAIR::new(main_trace.n_rows(), &pub_inputs, &proof_options).is_err());
+}
+
+#[test]
+fn test_verifier_rejects_proof_with_a_builtin_out_of_the_layout() {
+    let (main_trace, mut pub_inputs) =
+        fibonacci_5_with_instances(CairoLayout::Small, Vec::new(), &EcdsaSignatures::new())
+            .unwrap();
+    let proof_options = ProofOptions::default_test_options();
+    let proof = generate_cairo_proof(&main_trace, &pub_inputs, &proof_options).unwrap();
+
+    pub_inputs
+        .memory_segments
+        .insert(SegmentName::Poseidon, Segment::new(0, 0));
+    assert!(!verify_cairo_proof(&proof, &pub_inputs, &proof_options));
+}
@@ -3,6 +3,7 @@ use crate::{
     cairo_layout::CairoLayout,
     runner::run::generate_prover_args,
     runner::run::generate_prover_args_from_trace,
+    runner::run::{generate_cairo1_prover_args, parse_cairo1_args},
 };
 use stark_platinum_prover::proof::options::ProofOptions;
 use std::time::Instant;
@@ -35,6 +36,23 @@ pub fn test_prove_cairo_program(file_path: &str, layout: CairoLayout) {
     assert!(verify_cairo_proof(&proof, &pub_inputs, &proof_options));
 }
 
+/// Loads the Sierra of a Cairo 1 program, runs it with the given arguments, and makes a proof
+/// of it
+pub fn test_prove_cairo1_program(file_path: &str, program_args: &str, layout: CairoLayout) {
+    let proof_options = ProofOptions::default_test_options();
+    let timer = Instant::now();
+    println!("Making proof ...");
+
+    let sierra_program = std::fs::read_to_string(file_path).unwrap();
+    let program_args = parse_cairo1_args(program_args).unwrap();
+    let (main_trace, pub_inputs) =
+        generate_cairo1_prover_args(&sierra_program, &program_args, layout).unwrap();
+    let proof = generate_cairo_proof(&main_trace, &pub_inputs, &proof_options).unwrap();
+    println!("  Time spent in proving: {:?} \n", timer.elapsed());
+
+    assert!(verify_cairo_proof(&proof, &pub_inputs, &proof_options));
+}
+
 pub fn test_prove_cairo_program_from_trace(trace_bin_path: &str, memory_bin_path: &str) {
     let proof_options = ProofOptions::default_test_options();
     let (main_trace, pub_inputs) =
@@ -1,6 +1,7 @@
-use super::air::CairoAIR;
+use super::air::{CairoAIR, PublicInputs};
 use lambdaworks_math::field::element::FieldElement;
 use lambdaworks_math::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
+use lambdaworks_math::traits::AsBytes;
 use serde::{Deserialize, Serialize};
 use stark_platinum_prover::proof::options::ProofOptions;
 use stark_platinum_prover::proof::options::SecurityLevel;
@@ -37,7 +38,8 @@ pub fn verify_cairo_proof_wasm(proof_bytes: &[u8], proof_options: &ProofOptions)
     };
     let bytes = &bytes[proof_len..];
 
-    let Ok((pub_inputs, _)) = bincode::serde::decode_from_slice(bytes, bincode::config::standard())
+    let Ok((pub_inputs, _)): Result<(PublicInputs, _), _> =
+        bincode::serde::decode_from_slice(bytes, bincode::config::standard())
     else {
         return false;
     };
@@ -46,7 +48,7 @@ pub fn verify_cairo_proof_wasm(proof_bytes: &[u8], proof_options: &ProofOptions)
         &proof,
         &pub_inputs,
         proof_options,
-        StoneProverTranscript::new(&[]),
+        StoneProverTranscript::new(&pub_inputs.as_bytes()),
     )
 }
 
@@ -69,5 +71,8 @@ pub fn new_proof_options(
         fri_number_of_queries,
         coset_offset: coset_offset as u64,
         grinding_factor,
+        zero_knowledge: false,
+        fri_step_list: vec![],
+        fri_last_layer_degree_bound: 1,
     }
 }
@@ -5,6 +5,7 @@ use lambdaworks_math::{
         short_weierstrass::{point::ShortWeierstrassProjectivePoint, traits::IsShortWeierstrass},
         traits::{IsEllipticCurve, IsPairing},
     },
+    msm::fixed_base::{optimum_window_size, FixedBaseTable},
 };
 
 pub struct VerifyingKey {
@@ -128,8 +129,14 @@ fn batch_operate<E: IsShortWeierstrass>(
     elems: &[FrElement],
     point: &ShortWeierstrassProjectivePoint<E>,
 ) -> Vec<ShortWeierstrassProjectivePoint<E>> {
+    const SCALAR_BITS: usize = 255;
+    let table = FixedBaseTable::new(
+        point,
+        SCALAR_BITS,
+        optimum_window_size(SCALAR_BITS, elems.len()),
+    );
     elems
         .iter()
-        .map(|elem| point.operate_with_self(elem.representative()))
+        .map(|elem| table.mul(&elem.representative()))
         .collect()
 }
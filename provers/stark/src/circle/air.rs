@@ -0,0 +1,66 @@
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsField, IsSubFieldOf},
+};
+
+use crate::{constraints::boundary::BoundaryConstraints, proof::options::ProofOptions};
+
+use super::M31;
+
+/// The Algebraic Intermediate Representation of a computation proven with the circle STARK.
+/// Row `i` of the trace is placed at the `i`-th point of the standard position coset with as many
+/// points as rows, so that the row following any other is obtained by adding the generator of
+/// the subgroup of that size.
+pub trait CircleAIR {
+    type PublicInputs;
+
+    fn new(
+        trace_length: usize,
+        pub_inputs: &Self::PublicInputs,
+        proof_options: &ProofOptions,
+    ) -> Self;
+
+    /// Evaluates the transition constraints on `frame`. The `i`-th row of the frame holds the
+    /// trace row at offset `transition_offsets()[i]` from the current one. The frame has entries
+    /// in Mersenne31 when the prover evaluates the constraints over the trace, and in its degree 4
+    /// extension when the verifier evaluates them at the out of domain point.
+    fn compute_transition<F>(&self, frame: &[Vec<FieldElement<F>>]) -> Vec<FieldElement<F>>
+    where
+        F: IsField,
+        M31: IsSubFieldOf<F>;
+
+    fn boundary_constraints(&self) -> BoundaryConstraints<M31>;
+
+    fn trace_length(&self) -> usize;
+
+    fn trace_columns(&self) -> usize;
+
+    fn transition_offsets(&self) -> Vec<usize>;
+
+    fn num_transition_constraints(&self) -> usize;
+
+    /// The maximum degree of the transition constraints as polynomials in the trace values.
+    fn transition_degree(&self) -> usize;
+
+    /// The number of rows at the end of the trace where the transition constraints are not
+    /// enforced.
+    fn transition_exemptions(&self) -> usize;
+
+    fn options(&self) -> &ProofOptions;
+
+    fn pub_inputs(&self) -> &Self::PublicInputs;
+
+    /// The number of parts the composition polynomial is split in. Every part is a polynomial
+    /// with as many coefficients as the trace has rows, so the parts together have to fit the
+    /// composition polynomial, whose degree is `(d - 1) * n / 2 + e` for transition constraints
+    /// of degree `d`, a trace of length `n` and `e` exempted rows.
+    fn num_composition_poly_parts(&self) -> usize {
+        let trace_length = self.trace_length();
+        let composition_poly_size = self.transition_degree().max(1) * trace_length - trace_length
+            + 2 * self.transition_exemptions()
+            + 2;
+        ((composition_poly_size + trace_length - 1) / trace_length)
+            .next_power_of_two()
+            .max(2)
+    }
+}
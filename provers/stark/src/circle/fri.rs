@@ -0,0 +1,243 @@
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::{
+    circle::{
+        cosets::StandardCoset,
+        twiddles::{get_twiddles, TwiddlesConfig},
+    },
+    field::element::FieldElement,
+};
+
+use crate::config::{BatchedMerkleTree, BatchedMerkleTreeBackend};
+use crate::fri::fri_decommit::FriDecommitment;
+
+use super::{proof::CircleStarkProof, M31, QM31};
+
+/// A layer of circle FRI, holding the evaluations of a polynomial in `x` on the `x` coordinates of
+/// a domain derived from the LDE coset. The `i`-th leaf of the Merkle tree holds the evaluations
+/// at the `i`-th point and at its opposite, which is the one at position `len - 1 - i`.
+#[derive(Clone)]
+pub struct CircleFriLayer {
+    pub evaluations: Vec<FieldElement<QM31>>,
+    pub merkle_tree: BatchedMerkleTree<QM31>,
+}
+
+impl CircleFriLayer {
+    pub fn new(evaluations: Vec<FieldElement<QM31>>) -> Self {
+        let size = evaluations.len();
+        let leaves: Vec<_> = (0..size / 2)
+            .map(|i| vec![evaluations[i].clone(), evaluations[size - 1 - i].clone()])
+            .collect();
+        let merkle_tree = BatchedMerkleTree::build(&leaves);
+        Self {
+            evaluations,
+            merkle_tree,
+        }
+    }
+}
+
+/// Folds the evaluations of a function at a pair of opposite points into the evaluation of
+/// `f_e + 𝛼 f_o` at their image in the next layer, where `f_e` and `f_o` are the even and odd
+/// parts of the function. `twiddle_inv` is the inverse of the coordinate that splits them: `y`
+/// for the first fold and `x` for the others.
+pub fn fold_pair(
+    value: &FieldElement<QM31>,
+    value_sym: &FieldElement<QM31>,
+    twiddle_inv: &FieldElement<M31>,
+    alpha: &FieldElement<QM31>,
+) -> FieldElement<QM31> {
+    let two_inv = FieldElement::<M31>::from(2).inv().unwrap();
+    let even = value + value_sym;
+    let odd = twiddle_inv * (value - value_sym);
+    two_inv * (even + alpha * odd)
+}
+
+fn fold(
+    evaluations: &[FieldElement<QM31>],
+    twiddles_inv: &[FieldElement<M31>],
+    alpha: &FieldElement<QM31>,
+) -> Vec<FieldElement<QM31>> {
+    let size = evaluations.len();
+    twiddles_inv
+        .iter()
+        .take(size / 2)
+        .enumerate()
+        .map(|(i, twiddle_inv)| {
+            fold_pair(
+                &evaluations[i],
+                &evaluations[size - 1 - i],
+                twiddle_inv,
+                alpha,
+            )
+        })
+        .collect()
+}
+
+/// Runs the commit phase of circle FRI on the evaluations of a polynomial with `2^number_of_folds`
+/// coefficients over `lde_coset`. The first fold maps the circle to the line of its `x`
+/// coordinates and every following fold halves the line domain, so after `number_of_folds` folds
+/// the result is a constant, which is sent in the clear.
+pub fn commit_phase(
+    number_of_folds: usize,
+    evaluations: Vec<FieldElement<QM31>>,
+    lde_coset: &StandardCoset,
+    transcript: &mut impl IsTranscript<QM31>,
+) -> (FieldElement<QM31>, Vec<CircleFriLayer>) {
+    let twiddles = get_twiddles(lde_coset, TwiddlesConfig::Interpolation);
+    let mut fri_layer_list = Vec::with_capacity(number_of_folds.saturating_sub(1));
+
+    // <<<< Receive challenge 𝛼₀
+    let alpha = transcript.sample_field_element();
+    let mut current_evaluations = fold(&evaluations, &twiddles[0], &alpha);
+
+    for layer_twiddles in twiddles.iter().take(number_of_folds).skip(1) {
+        let layer = CircleFriLayer::new(current_evaluations);
+
+        // >>>> Send commitment: [pₖ]
+        transcript.append_bytes(&layer.merkle_tree.root);
+
+        // <<<< Receive challenge 𝛼ₖ
+        let alpha = transcript.sample_field_element();
+        current_evaluations = fold(&layer.evaluations, layer_twiddles, &alpha);
+        fri_layer_list.push(layer);
+    }
+
+    // >>>> Send value: pₙ
+    let last_value = current_evaluations[0].clone();
+    transcript.append_field_element(&last_value);
+
+    (last_value, fri_layer_list)
+}
+
+/// Opens every layer at the positions reached from the query indexes `iotas`, which index the
+/// leaves of the LDE coset.
+pub fn query_phase(fri_layers: &[CircleFriLayer], iotas: &[usize]) -> Vec<FriDecommitment<QM31>> {
+    iotas
+        .iter()
+        .map(|iota| {
+            let mut index = *iota;
+            let mut layers_auth_paths = Vec::with_capacity(fri_layers.len());
+            let mut layers_evaluations_coset = Vec::with_capacity(fri_layers.len());
+            for layer in fri_layers {
+                let index_sym = layer.evaluations.len() - 1 - index;
+                let leaf_index = index.min(index_sym);
+                layers_auth_paths.push(layer.merkle_tree.get_proof_by_pos(leaf_index).unwrap());
+                layers_evaluations_coset.push(vec![layer.evaluations[index_sym].clone()]);
+                index = leaf_index;
+            }
+            FriDecommitment {
+                layers_auth_paths,
+                layers_evaluations_coset,
+            }
+        })
+        .collect()
+}
+
+/// Checks a query of circle FRI against the layers committed in `proof`. `evaluation` and
+/// `evaluation_sym` are the evaluations of the committed polynomial at the `iota`-th point of
+/// `lde_coset` and at its conjugate, and `alphas` are the folding challenges of every layer.
+pub fn verify_query(
+    proof: &CircleStarkProof,
+    lde_coset: &StandardCoset,
+    iota: usize,
+    fri_decommitment: &FriDecommitment<QM31>,
+    alphas: &[FieldElement<QM31>],
+    evaluation: &FieldElement<QM31>,
+    evaluation_sym: &FieldElement<QM31>,
+) -> bool {
+    let fri_layers_merkle_roots = &proof.fri_layers_merkle_roots;
+    if fri_decommitment.layers_auth_paths.len() != fri_layers_merkle_roots.len()
+        || fri_decommitment.layers_evaluations_coset.len() != fri_layers_merkle_roots.len()
+        || fri_decommitment
+            .layers_evaluations_coset
+            .iter()
+            .any(|coset| coset.len() != 1)
+        || alphas.len() != fri_layers_merkle_roots.len() + 1
+    {
+        return false;
+    }
+
+    let point = lde_coset.point(iota);
+    let y_inv = match point.y.inv() {
+        Ok(y_inv) => y_inv,
+        Err(_) => return false,
+    };
+    let mut value = fold_pair(evaluation, evaluation_sym, &y_inv, &alphas[0]);
+
+    let mut index = iota;
+    let mut layer_size = lde_coset.size() / 2;
+    for (layer_number, ((merkle_root, auth_path), evaluation_sym)) in fri_layers_merkle_roots
+        .iter()
+        .zip(&fri_decommitment.layers_auth_paths)
+        .zip(&fri_decommitment.layers_evaluations_coset)
+        .enumerate()
+    {
+        // Circle FRI folds pairs of conjugate points, so every coset holds a single element.
+        let evaluation_sym = &evaluation_sym[0];
+        let index_sym = layer_size - 1 - index;
+        let leaf_index = index.min(index_sym);
+        let leaf = if index < index_sym {
+            vec![value, evaluation_sym.clone()]
+        } else {
+            vec![evaluation_sym.clone(), value]
+        };
+        if !auth_path.verify::<BatchedMerkleTreeBackend<QM31>>(merkle_root, leaf_index, &leaf) {
+            return false;
+        }
+
+        let x = lde_coset
+            .point(leaf_index)
+            .repeated_double(layer_number as u32)
+            .x;
+        let x_inv = match x.inv() {
+            Ok(x_inv) => x_inv,
+            Err(_) => return false,
+        };
+        value = fold_pair(&leaf[0], &leaf[1], &x_inv, &alphas[layer_number + 1]);
+        index = leaf_index;
+        layer_size /= 2;
+    }
+
+    value == proof.fri_last_value
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::circle::polynomial::evaluate_cfft_on_coset;
+
+    use super::*;
+
+    fn low_degree_evaluations(
+        lde_coset: &StandardCoset,
+        number_of_coefficients: usize,
+    ) -> Vec<FieldElement<QM31>> {
+        let coefficients: Vec<FieldElement<QM31>> = (0..number_of_coefficients as u64)
+            .map(|i| FieldElement::<M31>::from(3 * i + 7).to_extension())
+            .collect();
+        evaluate_cfft_on_coset(&coefficients, lde_coset.size()).unwrap()
+    }
+
+    #[test]
+    fn folding_a_low_degree_polynomial_ends_in_a_constant() {
+        let lde_coset = StandardCoset::new(6);
+        let twiddles = get_twiddles(&lde_coset, TwiddlesConfig::Interpolation);
+        let mut evaluations = low_degree_evaluations(&lde_coset, 16);
+        for (layer, layer_twiddles) in twiddles.iter().take(4).enumerate() {
+            let alpha = FieldElement::<QM31>::from(layer as u64 + 5);
+            evaluations = fold(&evaluations, layer_twiddles, &alpha);
+        }
+        assert_eq!(evaluations.len(), 4);
+        assert!(evaluations.iter().all(|value| value == &evaluations[0]));
+    }
+
+    #[test]
+    fn folding_a_polynomial_of_too_high_degree_does_not_end_in_a_constant() {
+        let lde_coset = StandardCoset::new(6);
+        let twiddles = get_twiddles(&lde_coset, TwiddlesConfig::Interpolation);
+        let mut evaluations = low_degree_evaluations(&lde_coset, 32);
+        for (layer, layer_twiddles) in twiddles.iter().take(4).enumerate() {
+            let alpha = FieldElement::<QM31>::from(layer as u64 + 5);
+            evaluations = fold(&evaluations, layer_twiddles, &alpha);
+        }
+        assert!(evaluations.iter().any(|value| value != &evaluations[0]));
+    }
+}
@@ -0,0 +1,23 @@
+//! A STARK over the Mersenne31 prime field, following the Circle STARKs paper
+//! (<https://eprint.iacr.org/2024/278>).
+//!
+//! The multiplicative group of Mersenne31 has no large subgroup of order a power of two, so the
+//! traces are interpolated over standard position cosets of the circle group `x² + y² = 1`
+//! instead, using the circle FFT from `lambdaworks_math::circle`. The low degree test is the
+//! circle version of FRI, and the random challenges are sampled from the degree 4 extension
+//! of Mersenne31.
+
+pub mod air;
+pub mod fri;
+pub mod proof;
+pub mod prover;
+pub mod verifier;
+
+use lambdaworks_math::field::fields::mersenne31::{
+    extension::Mersenne31ComplexQuadraticExtensionField, field::Mersenne31Field,
+};
+
+/// The field of the execution trace.
+pub type M31 = Mersenne31Field;
+/// The degree 4 extension of Mersenne31 used for the challenges and out of domain evaluations.
+pub type QM31 = Mersenne31ComplexQuadraticExtensionField;
@@ -0,0 +1,52 @@
+use lambdaworks_crypto::merkle_tree::proof::Proof;
+use lambdaworks_math::field::{element::FieldElement, traits::IsField};
+
+use crate::{config::Commitment, fri::fri_decommit::FriDecommitment, table::Table};
+
+use super::{M31, QM31};
+
+/// The opening of a batch of polynomials at a query point and at its conjugate, which share a
+/// leaf of the Merkle tree committing to their evaluations.
+#[derive(Debug, Clone)]
+pub struct CircleOpenings<F: IsField> {
+    pub proof: Proof<Commitment>,
+    pub evaluations: Vec<FieldElement<F>>,
+    pub evaluations_sym: Vec<FieldElement<F>>,
+}
+
+/// The openings of the trace and of the composition polynomial parts needed to compute the
+/// DEEP composition polynomial at a query point and at its conjugate.
+#[derive(Debug, Clone)]
+pub struct CircleDeepPolynomialOpening {
+    pub trace_polys: CircleOpenings<M31>,
+    pub composition_poly: CircleOpenings<QM31>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CircleStarkProof {
+    // Length of the execution trace
+    pub trace_length: usize,
+    // Commitments of the trace columns
+    // [tⱼ]
+    pub trace_merkle_root: Commitment,
+    // Commitment to the composition polynomial parts
+    // [H₀], ..., [Hₘ₋₁]
+    pub composition_poly_root: Commitment,
+    // tⱼ(z + k g), one row per transition offset k
+    pub trace_ood_evaluations: Table<QM31>,
+    // Hᵢ(z)
+    pub composition_poly_parts_ood_evaluation: Vec<FieldElement<QM31>>,
+    // Coefficient λ of the vanishing polynomial of the trace domain in the DEEP composition
+    // polynomial, which is subtracted before running FRI
+    pub deep_poly_vanishing_coefficient: FieldElement<QM31>,
+    // [pₖ]
+    pub fri_layers_merkle_roots: Vec<Commitment>,
+    // pₙ
+    pub fri_last_value: FieldElement<QM31>,
+    // Open(pₖ(Dₖ), −𝜐ₛ^(2ᵏ))
+    pub query_list: Vec<FriDecommitment<QM31>>,
+    // Open(H₀(D_LDE, 𝜐ᵢ), Open(tⱼ(D_LDE), 𝜐ᵢ) and their conjugates
+    pub deep_poly_openings: Vec<CircleDeepPolynomialOpening>,
+    // nonce obtained from grinding
+    pub nonce: Option<u64>,
+}
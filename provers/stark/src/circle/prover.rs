@@ -0,0 +1,487 @@
+use std::marker::PhantomData;
+
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::{
+    circle::{
+        cosets::StandardCoset,
+        point::CirclePoint,
+        polynomial::{evaluate_cfft_on_coset, evaluate_point, interpolate_cfft},
+    },
+    field::{
+        element::FieldElement,
+        traits::{IsField, IsSubFieldOf},
+    },
+};
+use log::info;
+
+use crate::{
+    config::BatchedMerkleTree,
+    grinding,
+    proof::options::ProofOptions,
+    prover::ProvingError,
+    table::Table,
+    trace::{columns2rows, TraceTable},
+};
+
+use super::{
+    air::CircleAIR,
+    fri,
+    proof::{CircleDeepPolynomialOpening, CircleOpenings, CircleStarkProof},
+    M31, QM31,
+};
+
+/// A prover of the circle STARK protocol over Mersenne31.
+pub struct CircleProver<A: CircleAIR> {
+    phantom: PhantomData<A>,
+}
+
+/// Checks that the trace and the proof options can be proven with the circle STARK.
+pub(crate) fn check_circle_parameters<A: CircleAIR>(air: &A) -> Result<(), ProvingError> {
+    let trace_length = air.trace_length();
+    let options = air.options();
+    if !trace_length.is_power_of_two() || trace_length < 4 {
+        return Err(ProvingError::WrongParameter(format!(
+            "the trace length must be a power of two greater than 2, got {trace_length}"
+        )));
+    }
+    if !options.blowup_factor.is_power_of_two()
+        || (options.blowup_factor as usize) < air.num_composition_poly_parts()
+    {
+        return Err(ProvingError::WrongParameter(format!(
+            "the blowup factor must be a power of two not smaller than the {} parts of the composition polynomial",
+            air.num_composition_poly_parts()
+        )));
+    }
+    let lde_log_2_size = trace_length.trailing_zeros() + options.blowup_factor.trailing_zeros();
+    if lde_log_2_size >= CirclePoint::<M31>::LOG_2_GROUP_ORDER {
+        return Err(ProvingError::WrongParameter(
+            "the low degree extension does not fit in the circle group".to_string(),
+        ));
+    }
+    if options.zero_knowledge {
+        return Err(ProvingError::WrongParameter(
+            "the circle STARK does not support zero knowledge".to_string(),
+        ));
+    }
+    if air.transition_offsets().first() != Some(&0) {
+        return Err(ProvingError::WrongParameter(
+            "the first transition offset must be zero".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Samples the out of domain point from the transcript, retrying in the unlikely case that the
+/// sampled parameter does not correspond to a point of the circle.
+pub(crate) fn sample_ood_point(transcript: &mut impl IsTranscript<QM31>) -> CirclePoint<QM31> {
+    loop {
+        let parameter = transcript.sample_field_element();
+        if let Some(point) = CirclePoint::from_parameter(&parameter) {
+            return point;
+        }
+    }
+}
+
+/// Returns the numerator and denominator of `(1 + h.x) / h.y` for `h = point - vanishing_point`.
+/// This is the inverse of a function of degree one that vanishes on the circle only at
+/// `vanishing_point`, so multiplying by it divides out a root at that point.
+pub(crate) fn point_vanishing_inverse_fraction<E: IsField>(
+    point: &CirclePoint<E>,
+    vanishing_point: &CirclePoint<E>,
+) -> (FieldElement<E>, FieldElement<E>) {
+    let h = point - vanishing_point;
+    (FieldElement::<E>::one() + h.x, h.y)
+}
+
+/// Evaluates at `point` the polynomial vanishing on the last `exemptions` rows of the trace,
+/// which multiplies the transition constraints so that they don't apply there.
+pub(crate) fn transition_exemptions_evaluation<E>(
+    trace_coset: &StandardCoset,
+    exemptions: usize,
+    point: &CirclePoint<E>,
+) -> FieldElement<E>
+where
+    E: IsField,
+    M31: IsSubFieldOf<E>,
+{
+    (1..=exemptions)
+        .map(|j| {
+            let exempted_point = trace_coset
+                .point(trace_coset.size() - j)
+                .to_extension::<E>();
+            (point - &exempted_point).x - FieldElement::one()
+        })
+        .fold(FieldElement::one(), |acc, factor| acc * factor)
+}
+
+/// Returns the values of `v_k, v_(k+1), ...` at `point` that weight the composition polynomial
+/// parts, where `k` is the logarithm of the trace length. The `m`-th part is multiplied by the
+/// product of the factors selected by the bits of `m`.
+pub(crate) fn composition_parts_weights(
+    trace_coset: &StandardCoset,
+    number_of_parts: usize,
+    point: &CirclePoint<QM31>,
+) -> Vec<FieldElement<QM31>> {
+    let mut weights = vec![FieldElement::<QM31>::one()];
+    let mut v = trace_coset.vanishing_polynomial(point);
+    while weights.len() < number_of_parts {
+        let higher: Vec<_> = weights.iter().map(|weight| weight * &v).collect();
+        weights.extend(higher);
+        v = v.square().double() - FieldElement::one();
+    }
+    weights
+}
+
+/// Evaluates the DEEP composition polynomial at a point, given the trace and composition parts
+/// values there and the inverses of the functions vanishing at the frame points `z + k g`.
+/// The first frame point is `z`, where the composition polynomial parts were opened.
+pub(crate) fn evaluate_deep_composition(
+    trace_row: &[FieldElement<M31>],
+    composition_parts_row: &[FieldElement<QM31>],
+    frame_factors: &[FieldElement<QM31>],
+    trace_ood_evaluations: &Table<QM31>,
+    composition_poly_parts_ood_evaluation: &[FieldElement<QM31>],
+    gammas: &[FieldElement<QM31>],
+) -> FieldElement<QM31> {
+    let mut gammas = gammas.iter();
+    let mut result = FieldElement::<QM31>::zero();
+
+    for (part_value, part_ood_value) in composition_parts_row
+        .iter()
+        .zip(composition_poly_parts_ood_evaluation)
+    {
+        let gamma = gammas.next().unwrap();
+        result += gamma * (part_value - part_ood_value) * &frame_factors[0];
+    }
+
+    for (row, factor) in frame_factors.iter().enumerate() {
+        for (trace_value, trace_ood_value) in
+            trace_row.iter().zip(trace_ood_evaluations.get_row(row))
+        {
+            let gamma = gammas.next().unwrap();
+            result += gamma * (trace_value - trace_ood_value) * factor;
+        }
+    }
+    result
+}
+
+pub(crate) fn number_of_deep_coefficients<A: CircleAIR>(air: &A) -> usize {
+    air.num_composition_poly_parts() + air.transition_offsets().len() * air.trace_columns()
+}
+
+pub(crate) fn powers(gamma: &FieldElement<QM31>, count: usize) -> Vec<FieldElement<QM31>> {
+    core::iter::successors(Some(FieldElement::one()), |power| Some(power * gamma))
+        .take(count)
+        .collect()
+}
+
+/// Commits to the rows of a table of evaluations over the LDE coset. The `i`-th leaf holds the
+/// `i`-th row and the one of its conjugate point.
+fn commit_rows<F: IsField>(rows: &[Vec<FieldElement<F>>]) -> BatchedMerkleTree<F>
+where
+    FieldElement<F>: lambdaworks_math::traits::AsBytes + Sync + Send,
+{
+    let size = rows.len();
+    let leaves: Vec<_> = (0..size / 2)
+        .map(|i| [rows[i].clone(), rows[size - 1 - i].clone()].concat())
+        .collect();
+    BatchedMerkleTree::build(&leaves)
+}
+
+fn open_rows<F: IsField>(
+    tree: &BatchedMerkleTree<F>,
+    rows: &[Vec<FieldElement<F>>],
+    iota: usize,
+) -> CircleOpenings<F>
+where
+    FieldElement<F>: lambdaworks_math::traits::AsBytes + Sync + Send,
+{
+    CircleOpenings {
+        proof: tree.get_proof_by_pos(iota).unwrap(),
+        evaluations: rows[iota].clone(),
+        evaluations_sym: rows[rows.len() - 1 - iota].clone(),
+    }
+}
+
+impl<A: CircleAIR> CircleProver<A> {
+    /// Generates a circle STARK proof that `trace` satisfies the constraints of the AIR `A`.
+    pub fn prove(
+        trace: &TraceTable<M31>,
+        pub_inputs: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        mut transcript: impl IsTranscript<QM31>,
+    ) -> Result<CircleStarkProof, ProvingError> {
+        info!("Started circle STARK proof generation...");
+
+        let trace_length = trace.n_rows();
+        let air = A::new(trace_length, pub_inputs, proof_options);
+        check_circle_parameters(&air)?;
+
+        let blowup_factor = proof_options.blowup_factor as usize;
+        let trace_coset = StandardCoset::new(trace_length.trailing_zeros());
+        let lde_coset = StandardCoset::new(
+            trace_coset.log_2_size + proof_options.blowup_factor.trailing_zeros(),
+        );
+        let lde_size = lde_coset.size();
+        let lde_points = lde_coset.points();
+
+        // ===================================
+        // ==========|   Round 1   |==========
+        // ===================================
+
+        let trace_polys: Vec<Vec<FieldElement<M31>>> = trace
+            .columns()
+            .into_iter()
+            .map(|column| interpolate_cfft(column).unwrap())
+            .collect();
+        let lde_trace_rows = columns2rows(
+            trace_polys
+                .iter()
+                .map(|poly| evaluate_cfft_on_coset(poly, lde_size).unwrap())
+                .collect(),
+        );
+        let trace_tree = commit_rows(&lde_trace_rows);
+
+        // >>>> Send commitment: [tⱼ]
+        transcript.append_bytes(&trace_tree.root);
+
+        // ===================================
+        // ==========|   Round 2   |==========
+        // ===================================
+
+        // <<<< Receive challenges: 𝛼ⱼ, 𝛽ⱼ
+        let boundary_constraints = air.boundary_constraints();
+        let transition_coefficients: Vec<FieldElement<QM31>> = (0..air
+            .num_transition_constraints())
+            .map(|_| transcript.sample_field_element())
+            .collect();
+        let boundary_coefficients: Vec<FieldElement<QM31>> =
+            (0..boundary_constraints.constraints.len())
+                .map(|_| transcript.sample_field_element())
+                .collect();
+
+        // The transition constraints are divided by the vanishing polynomial of the trace coset
+        // and multiplied by the one of the exempted rows.
+        let trace_coset_vanishing: Vec<_> = lde_points
+            .iter()
+            .map(|point| trace_coset.vanishing_polynomial(point))
+            .collect();
+        let mut transition_factors = trace_coset_vanishing.clone();
+        FieldElement::inplace_batch_inverse(&mut transition_factors).unwrap();
+        for (factor, point) in transition_factors.iter_mut().zip(&lde_points) {
+            *factor *=
+                transition_exemptions_evaluation(&trace_coset, air.transition_exemptions(), point);
+        }
+
+        // Each boundary constraint is divided by the function vanishing at its row.
+        let boundary_factors: Vec<Vec<FieldElement<M31>>> = boundary_constraints
+            .constraints
+            .iter()
+            .map(|constraint| {
+                let row_point = trace_coset.point(constraint.step);
+                let (numerators, mut denominators): (Vec<_>, Vec<_>) = lde_points
+                    .iter()
+                    .map(|point| point_vanishing_inverse_fraction(point, &row_point))
+                    .unzip();
+                FieldElement::inplace_batch_inverse(&mut denominators).unwrap();
+                numerators
+                    .iter()
+                    .zip(&denominators)
+                    .map(|(numerator, denominator)| numerator * denominator)
+                    .collect()
+            })
+            .collect();
+
+        let offsets = air.transition_offsets();
+        let composition_evaluations: Vec<FieldElement<QM31>> = (0..lde_size)
+            .map(|i| {
+                let frame: Vec<_> = offsets
+                    .iter()
+                    .map(|offset| lde_trace_rows[(i + offset * blowup_factor) % lde_size].clone())
+                    .collect();
+                let transitions = air.compute_transition(&frame);
+                let transition_sum = transitions
+                    .iter()
+                    .zip(&transition_coefficients)
+                    .fold(FieldElement::<QM31>::zero(), |acc, (value, coefficient)| {
+                        acc + value * coefficient
+                    });
+
+                let boundary_sum = boundary_constraints
+                    .constraints
+                    .iter()
+                    .zip(&boundary_coefficients)
+                    .zip(&boundary_factors)
+                    .fold(
+                        FieldElement::<QM31>::zero(),
+                        |acc, ((constraint, coefficient), factors)| {
+                            let value = lde_trace_rows[i][constraint.col] - constraint.value;
+                            acc + value * factors[i] * coefficient
+                        },
+                    );
+
+                transition_factors[i] * transition_sum + boundary_sum
+            })
+            .collect();
+
+        // The composition polynomial is split in parts Hₘ of `trace_length` coefficients each,
+        // so that H = Σ Hₘ ∏ vₖ₊ₛ^(mₛ), where mₛ are the bits of m.
+        let number_of_parts = air.num_composition_poly_parts();
+        let composition_poly = interpolate_cfft(composition_evaluations).unwrap();
+        let composition_poly_parts: Vec<Vec<FieldElement<QM31>>> = composition_poly
+            .chunks(trace_length)
+            .take(number_of_parts)
+            .map(|part| part.to_vec())
+            .collect();
+        let lde_composition_rows = columns2rows(
+            composition_poly_parts
+                .iter()
+                .map(|part| evaluate_cfft_on_coset(part, lde_size).unwrap())
+                .collect(),
+        );
+        let composition_tree = commit_rows(&lde_composition_rows);
+
+        // >>>> Send commitment: [H₀], ..., [Hₘ₋₁]
+        transcript.append_bytes(&composition_tree.root);
+
+        // ===================================
+        // ==========|   Round 3   |==========
+        // ===================================
+
+        // <<<< Receive challenge: z
+        let z = sample_ood_point(&mut transcript);
+        let trace_step = trace_coset.step().to_extension::<QM31>();
+        let frame_points: Vec<CirclePoint<QM31>> = offsets
+            .iter()
+            .map(|offset| &z + &trace_step.mul(*offset as u128))
+            .collect();
+
+        let trace_ood_evaluations = Table::new(
+            frame_points
+                .iter()
+                .flat_map(|point| trace_polys.iter().map(|poly| evaluate_point(poly, point)))
+                .collect(),
+            air.trace_columns(),
+        );
+        let composition_poly_parts_ood_evaluation: Vec<_> = composition_poly_parts
+            .iter()
+            .map(|part| evaluate_point(part, &z))
+            .collect();
+
+        // >>>> Send values: tⱼ(z + k g), Hₘ(z)
+        for value in trace_ood_evaluations.data.iter() {
+            transcript.append_field_element(value);
+        }
+        for value in composition_poly_parts_ood_evaluation.iter() {
+            transcript.append_field_element(value);
+        }
+
+        // ===================================
+        // ==========|   Round 4   |==========
+        // ===================================
+
+        // <<<< Receive challenge: 𝛾
+        let gamma = transcript.sample_field_element();
+        let gammas = powers(&gamma, number_of_deep_coefficients(&air));
+
+        let frame_factors: Vec<Vec<FieldElement<QM31>>> = frame_points
+            .iter()
+            .map(|frame_point| {
+                let (numerators, mut denominators): (Vec<_>, Vec<_>) = lde_points
+                    .iter()
+                    .map(|point| {
+                        point_vanishing_inverse_fraction(&point.to_extension(), frame_point)
+                    })
+                    .unzip();
+                FieldElement::inplace_batch_inverse(&mut denominators).unwrap();
+                numerators
+                    .iter()
+                    .zip(&denominators)
+                    .map(|(numerator, denominator)| numerator * denominator)
+                    .collect()
+            })
+            .collect();
+
+        let mut deep_composition_evaluations: Vec<FieldElement<QM31>> = (0..lde_size)
+            .map(|i| {
+                let factors: Vec<_> = frame_factors
+                    .iter()
+                    .map(|factors| factors[i].clone())
+                    .collect();
+                evaluate_deep_composition(
+                    &lde_trace_rows[i],
+                    &lde_composition_rows[i],
+                    &factors,
+                    &trace_ood_evaluations,
+                    &composition_poly_parts_ood_evaluation,
+                    &gammas,
+                )
+            })
+            .collect();
+
+        // The DEEP composition polynomial has one coefficient more than the trace polynomials:
+        // the one of the vanishing polynomial of the trace coset, which is sent to the verifier
+        // and subtracted so that FRI runs on a polynomial with `trace_length` coefficients.
+        let deep_poly_vanishing_coefficient =
+            interpolate_cfft(deep_composition_evaluations.clone()).unwrap()[trace_length].clone();
+        for (value, vanishing) in deep_composition_evaluations
+            .iter_mut()
+            .zip(trace_coset_vanishing.iter())
+        {
+            *value = &*value - vanishing * &deep_poly_vanishing_coefficient;
+        }
+        let fri_evaluations = deep_composition_evaluations;
+
+        // >>>> Send value: λ
+        transcript.append_field_element(&deep_poly_vanishing_coefficient);
+
+        let (fri_last_value, fri_layers) = fri::commit_phase(
+            trace_coset.log_2_size as usize,
+            fri_evaluations,
+            &lde_coset,
+            &mut transcript,
+        );
+
+        // grinding: generate nonce and append it to the transcript
+        let security_bits = proof_options.grinding_factor;
+        let mut nonce = None;
+        if security_bits > 0 {
+            let nonce_value = grinding::generate_nonce(&transcript.state(), security_bits)
+                .expect("nonce not found");
+            transcript.append_bytes(&nonce_value.to_be_bytes());
+            nonce = Some(nonce_value);
+        }
+
+        // <<<< Receive challenges: 𝜄ₛ
+        let iotas: Vec<usize> = (0..proof_options.fri_number_of_queries)
+            .map(|_| transcript.sample_u64((lde_size >> 1) as u64) as usize)
+            .collect();
+        let query_list = fri::query_phase(&fri_layers, &iotas);
+        let deep_poly_openings = iotas
+            .iter()
+            .map(|iota| CircleDeepPolynomialOpening {
+                trace_polys: open_rows(&trace_tree, &lde_trace_rows, *iota),
+                composition_poly: open_rows(&composition_tree, &lde_composition_rows, *iota),
+            })
+            .collect();
+
+        info!("End circle STARK proof generation");
+
+        Ok(CircleStarkProof {
+            trace_length,
+            trace_merkle_root: trace_tree.root,
+            composition_poly_root: composition_tree.root,
+            trace_ood_evaluations,
+            composition_poly_parts_ood_evaluation,
+            deep_poly_vanishing_coefficient,
+            fri_layers_merkle_roots: fri_layers
+                .iter()
+                .map(|layer| layer.merkle_tree.root)
+                .collect(),
+            fri_last_value,
+            query_list,
+            deep_poly_openings,
+            nonce,
+        })
+    }
+}
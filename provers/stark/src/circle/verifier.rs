@@ -0,0 +1,362 @@
+use std::marker::PhantomData;
+
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::{
+    circle::{cosets::StandardCoset, point::CirclePoint},
+    field::element::FieldElement,
+};
+use log::error;
+
+use crate::{
+    config::{BatchedMerkleTreeBackend, Commitment},
+    grinding,
+    proof::options::ProofOptions,
+};
+
+use super::{
+    air::CircleAIR,
+    fri,
+    proof::{CircleOpenings, CircleStarkProof},
+    prover::{
+        check_circle_parameters, composition_parts_weights, evaluate_deep_composition,
+        number_of_deep_coefficients, point_vanishing_inverse_fraction, powers, sample_ood_point,
+        transition_exemptions_evaluation,
+    },
+    M31, QM31,
+};
+
+/// A verifier of the circle STARK protocol over Mersenne31.
+pub struct CircleVerifier<A: CircleAIR> {
+    phantom: PhantomData<A>,
+}
+
+/// The challenges of the verifier, recovered by replaying the transcript of the proof.
+struct CircleChallenges {
+    z: CirclePoint<QM31>,
+    frame_points: Vec<CirclePoint<QM31>>,
+    transition_coefficients: Vec<FieldElement<QM31>>,
+    boundary_coefficients: Vec<FieldElement<QM31>>,
+    gammas: Vec<FieldElement<QM31>>,
+    alphas: Vec<FieldElement<QM31>>,
+    iotas: Vec<usize>,
+}
+
+fn verify_openings<F>(root: &Commitment, openings: &CircleOpenings<F>, iota: usize) -> bool
+where
+    F: lambdaworks_math::field::traits::IsField,
+    FieldElement<F>: lambdaworks_math::traits::AsBytes + Sync + Send,
+{
+    let leaf = [
+        openings.evaluations.clone(),
+        openings.evaluations_sym.clone(),
+    ]
+    .concat();
+    openings
+        .proof
+        .verify::<BatchedMerkleTreeBackend<F>>(root, iota, &leaf)
+}
+
+impl<A: CircleAIR> CircleVerifier<A> {
+    fn replay_rounds_and_recover_challenges(
+        air: &A,
+        proof: &CircleStarkProof,
+        lde_size: usize,
+        transcript: &mut impl IsTranscript<QM31>,
+    ) -> Option<CircleChallenges> {
+        // >>>> Send commitment: [tⱼ]
+        transcript.append_bytes(&proof.trace_merkle_root);
+
+        // <<<< Receive challenges: 𝛼ⱼ, 𝛽ⱼ
+        let transition_coefficients = (0..air.num_transition_constraints())
+            .map(|_| transcript.sample_field_element())
+            .collect();
+        let boundary_coefficients = (0..air.boundary_constraints().constraints.len())
+            .map(|_| transcript.sample_field_element())
+            .collect();
+
+        // >>>> Send commitment: [H₀], ..., [Hₘ₋₁]
+        transcript.append_bytes(&proof.composition_poly_root);
+
+        // <<<< Receive challenge: z
+        let z = sample_ood_point(transcript);
+        let trace_step = StandardCoset::new(air.trace_length().trailing_zeros())
+            .step()
+            .to_extension::<QM31>();
+        let frame_points = air
+            .transition_offsets()
+            .iter()
+            .map(|offset| &z + &trace_step.mul(*offset as u128))
+            .collect();
+
+        // >>>> Send values: tⱼ(z + k g), Hₘ(z)
+        for value in proof.trace_ood_evaluations.data.iter() {
+            transcript.append_field_element(value);
+        }
+        for value in proof.composition_poly_parts_ood_evaluation.iter() {
+            transcript.append_field_element(value);
+        }
+
+        // <<<< Receive challenge: 𝛾
+        let gamma = transcript.sample_field_element();
+        let gammas = powers(&gamma, number_of_deep_coefficients(air));
+
+        // >>>> Send value: λ
+        transcript.append_field_element(&proof.deep_poly_vanishing_coefficient);
+
+        // <<<< Receive challenges: 𝛼ₖ
+        let mut alphas = vec![transcript.sample_field_element()];
+        for root in proof.fri_layers_merkle_roots.iter() {
+            transcript.append_bytes(root);
+            alphas.push(transcript.sample_field_element());
+        }
+        transcript.append_field_element(&proof.fri_last_value);
+
+        // Receive grinding value
+        let security_bits = air.options().grinding_factor;
+        if security_bits > 0 {
+            let nonce = proof.nonce?;
+            if !grinding::is_valid_nonce(&transcript.state(), nonce, security_bits) {
+                error!("Grinding factor not satisfied");
+                return None;
+            }
+            transcript.append_bytes(&nonce.to_be_bytes());
+        }
+
+        // <<<< Receive challenges: 𝜄ₛ
+        let iotas = (0..air.options().fri_number_of_queries)
+            .map(|_| transcript.sample_u64((lde_size >> 1) as u64) as usize)
+            .collect();
+
+        Some(CircleChallenges {
+            z,
+            frame_points,
+            transition_coefficients,
+            boundary_coefficients,
+            gammas,
+            alphas,
+            iotas,
+        })
+    }
+
+    /// Checks that the composition polynomial parts opened at `z` are consistent with the
+    /// constraints evaluated on the trace values opened at the frame of `z`.
+    fn verify_composition_poly_ood_evaluation(
+        air: &A,
+        proof: &CircleStarkProof,
+        trace_coset: &StandardCoset,
+        challenges: &CircleChallenges,
+    ) -> bool {
+        let z = &challenges.z;
+        let trace_ood = &proof.trace_ood_evaluations;
+        let frame: Vec<Vec<FieldElement<QM31>>> = (0..trace_ood.height)
+            .map(|row| trace_ood.get_row(row).to_vec())
+            .collect();
+
+        let transitions = air.compute_transition(&frame);
+        let transition_sum = transitions
+            .iter()
+            .zip(&challenges.transition_coefficients)
+            .fold(FieldElement::<QM31>::zero(), |acc, (value, coefficient)| {
+                acc + value * coefficient
+            });
+        let vanishing = trace_coset.vanishing_polynomial(z);
+        let vanishing_inv = match vanishing.inv() {
+            Ok(vanishing_inv) => vanishing_inv,
+            Err(_) => return false,
+        };
+        let transition_term = transition_sum
+            * transition_exemptions_evaluation(trace_coset, air.transition_exemptions(), z)
+            * vanishing_inv;
+
+        let mut boundary_term = FieldElement::<QM31>::zero();
+        for (constraint, coefficient) in air
+            .boundary_constraints()
+            .constraints
+            .iter()
+            .zip(&challenges.boundary_coefficients)
+        {
+            let row_point = trace_coset.point(constraint.step).to_extension::<QM31>();
+            let (numerator, denominator) = point_vanishing_inverse_fraction(z, &row_point);
+            let denominator_inv = match denominator.inv() {
+                Ok(denominator_inv) => denominator_inv,
+                Err(_) => return false,
+            };
+            let value =
+                &trace_ood.get_row(0)[constraint.col] - constraint.value.to_extension::<QM31>();
+            boundary_term += value * numerator * denominator_inv * coefficient;
+        }
+
+        let composition_poly_claimed_ood_evaluation = composition_parts_weights(
+            trace_coset,
+            proof.composition_poly_parts_ood_evaluation.len(),
+            z,
+        )
+        .iter()
+        .zip(&proof.composition_poly_parts_ood_evaluation)
+        .fold(FieldElement::<QM31>::zero(), |acc, (weight, part)| {
+            acc + weight * part
+        });
+
+        transition_term + boundary_term == composition_poly_claimed_ood_evaluation
+    }
+
+    /// Evaluates the polynomial committed by FRI at the `index`-th point of the LDE coset, from
+    /// the values of the trace and composition polynomial parts opened there.
+    fn reconstruct_fri_evaluation(
+        proof: &CircleStarkProof,
+        trace_coset: &StandardCoset,
+        lde_coset: &StandardCoset,
+        challenges: &CircleChallenges,
+        index: usize,
+        trace_row: &[FieldElement<M31>],
+        composition_parts_row: &[FieldElement<QM31>],
+    ) -> Option<FieldElement<QM31>> {
+        let point = lde_coset.point(index);
+        let point_in_extension = point.to_extension::<QM31>();
+        let mut frame_factors = Vec::with_capacity(challenges.frame_points.len());
+        for frame_point in challenges.frame_points.iter() {
+            let (numerator, denominator) =
+                point_vanishing_inverse_fraction(&point_in_extension, frame_point);
+            frame_factors.push(numerator * denominator.inv().ok()?);
+        }
+        let deep_composition_evaluation = evaluate_deep_composition(
+            trace_row,
+            composition_parts_row,
+            &frame_factors,
+            &proof.trace_ood_evaluations,
+            &proof.composition_poly_parts_ood_evaluation,
+            &challenges.gammas,
+        );
+        Some(
+            deep_composition_evaluation
+                - trace_coset.vanishing_polynomial(&point) * &proof.deep_poly_vanishing_coefficient,
+        )
+    }
+
+    /// Checks the openings of the trace and composition polynomial parts at every query, and
+    /// that the DEEP composition polynomial they determine passes the circle FRI queries.
+    fn verify_queries(
+        proof: &CircleStarkProof,
+        trace_coset: &StandardCoset,
+        lde_coset: &StandardCoset,
+        challenges: &CircleChallenges,
+    ) -> bool {
+        challenges
+            .iotas
+            .iter()
+            .zip(&proof.query_list)
+            .zip(&proof.deep_poly_openings)
+            .all(|((iota, fri_decommitment), openings)| {
+                if !verify_openings(&proof.trace_merkle_root, &openings.trace_polys, *iota)
+                    || !verify_openings(
+                        &proof.composition_poly_root,
+                        &openings.composition_poly,
+                        *iota,
+                    )
+                {
+                    error!("Trace or composition polynomial opening verification failed");
+                    return false;
+                }
+
+                let evaluation = Self::reconstruct_fri_evaluation(
+                    proof,
+                    trace_coset,
+                    lde_coset,
+                    challenges,
+                    *iota,
+                    &openings.trace_polys.evaluations,
+                    &openings.composition_poly.evaluations,
+                );
+                let evaluation_sym = Self::reconstruct_fri_evaluation(
+                    proof,
+                    trace_coset,
+                    lde_coset,
+                    challenges,
+                    lde_coset.size() - 1 - iota,
+                    &openings.trace_polys.evaluations_sym,
+                    &openings.composition_poly.evaluations_sym,
+                );
+                match (evaluation, evaluation_sym) {
+                    (Some(evaluation), Some(evaluation_sym)) => fri::verify_query(
+                        proof,
+                        lde_coset,
+                        *iota,
+                        fri_decommitment,
+                        &challenges.alphas,
+                        &evaluation,
+                        &evaluation_sym,
+                    ),
+                    _ => false,
+                }
+            })
+    }
+
+    /// Checks that the proof has the shape expected for the AIR, so that the rest of the
+    /// verification can index it safely.
+    fn check_proof_shape(air: &A, proof: &CircleStarkProof) -> bool {
+        let number_of_queries = air.options().fri_number_of_queries;
+        let trace_columns = air.trace_columns();
+        let number_of_parts = air.num_composition_poly_parts();
+        let trace_log_2_length = proof.trace_length.trailing_zeros() as usize;
+
+        proof.query_list.len() == number_of_queries
+            && proof.deep_poly_openings.len() == number_of_queries
+            && proof.trace_ood_evaluations.width == trace_columns
+            && proof.trace_ood_evaluations.height == air.transition_offsets().len()
+            && proof.trace_ood_evaluations.data.len()
+                == trace_columns * air.transition_offsets().len()
+            && proof.composition_poly_parts_ood_evaluation.len() == number_of_parts
+            && proof.fri_layers_merkle_roots.len() == trace_log_2_length - 1
+            && proof.deep_poly_openings.iter().all(|openings| {
+                openings.trace_polys.evaluations.len() == trace_columns
+                    && openings.trace_polys.evaluations_sym.len() == trace_columns
+                    && openings.composition_poly.evaluations.len() == number_of_parts
+                    && openings.composition_poly.evaluations_sym.len() == number_of_parts
+            })
+    }
+
+    /// Verifies a circle STARK proof for the AIR `A` with public inputs `pub_inputs`.
+    pub fn verify(
+        proof: &CircleStarkProof,
+        pub_inputs: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        mut transcript: impl IsTranscript<QM31>,
+    ) -> bool {
+        let air = A::new(proof.trace_length, pub_inputs, proof_options);
+        if check_circle_parameters(&air).is_err() {
+            error!("Invalid parameters for the circle STARK");
+            return false;
+        }
+        if !Self::check_proof_shape(&air, proof) {
+            error!("Proof does not have the expected shape");
+            return false;
+        }
+
+        let trace_coset = StandardCoset::new(proof.trace_length.trailing_zeros());
+        let lde_coset = StandardCoset::new(
+            trace_coset.log_2_size + proof_options.blowup_factor.trailing_zeros(),
+        );
+
+        let challenges = match Self::replay_rounds_and_recover_challenges(
+            &air,
+            proof,
+            lde_coset.size(),
+            &mut transcript,
+        ) {
+            Some(challenges) => challenges,
+            None => return false,
+        };
+
+        if !Self::verify_composition_poly_ood_evaluation(&air, proof, &trace_coset, &challenges) {
+            error!("Composition Polynomial verification failed");
+            return false;
+        }
+
+        if !Self::verify_queries(proof, &trace_coset, &lde_coset, &challenges) {
+            error!("FRI verification failed");
+            return false;
+        }
+
+        true
+    }
+}
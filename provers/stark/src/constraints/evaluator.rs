@@ -2,18 +2,22 @@ use super::boundary::BoundaryConstraints;
 #[cfg(all(debug_assertions, not(feature = "parallel")))]
 use crate::debug::check_boundary_polys_divisibility;
 use crate::domain::Domain;
+use crate::frame::Frame;
+use crate::prover::evaluate_polynomial_on_coset;
 use crate::trace::LDETraceTable;
 use crate::traits::AIR;
-use crate::{frame::Frame, prover::evaluate_polynomial_on_lde_domain};
-use itertools::Itertools;
-#[cfg(all(debug_assertions, not(feature = "parallel")))]
-use lambdaworks_math::polynomial::Polynomial;
-use lambdaworks_math::{fft::errors::FFTError, field::element::FieldElement, traits::AsBytes};
-#[cfg(feature = "parallel")]
-use rayon::{
-    iter::IndexedParallelIterator,
-    prelude::{IntoParallelIterator, ParallelIterator},
+use lambdaworks_math::{
+    fft::{cpu::roots_of_unity::get_powers_of_primitive_root_coset, lde::CosetLDE},
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField},
+    },
+    polynomial::Polynomial,
+    traits::AsBytes,
 };
+#[cfg(feature = "parallel")]
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+use std::collections::HashMap;
 #[cfg(feature = "instruments")]
 use std::time::Instant;
 
@@ -43,179 +47,373 @@ impl<A: AIR> ConstraintEvaluator<A> {
         FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
         A: Send + Sync,
     {
-        let boundary_constraints = &self.boundary_constraints;
-        let number_of_b_constraints = boundary_constraints.constraints.len();
-        let boundary_zerofiers_inverse_evaluations: Vec<Vec<FieldElement<A::Field>>> =
-            boundary_constraints
-                .constraints
-                .iter()
-                .map(|bc| {
-                    let point = &domain.trace_primitive_root.pow(bc.step as u64);
-                    let mut evals = domain
-                        .lde_roots_of_unity_coset
-                        .iter()
-                        .map(|v| v.clone() - point)
-                        .collect::<Vec<FieldElement<A::Field>>>();
-                    FieldElement::inplace_batch_inverse(&mut evals).unwrap();
-                    evals
-                })
-                .collect::<Vec<Vec<FieldElement<A::Field>>>>();
-
         #[cfg(all(debug_assertions, not(feature = "parallel")))]
         let boundary_polys: Vec<Polynomial<FieldElement<A::Field>>> = Vec::new();
 
+        let domain_evaluations = self.evaluate_on_domain(air, domain);
+
+        #[cfg(all(debug_assertions, not(feature = "parallel")))]
+        let boundary_zerofiers = Vec::new();
+
+        #[cfg(all(debug_assertions, not(feature = "parallel")))]
+        check_boundary_polys_divisibility(boundary_polys, boundary_zerofiers);
+
+        // Iterate over all LDE domain and compute the composition polynomial evaluations,
+        // adding the parts related to the boundary and to the transition constraints.
+
         #[cfg(feature = "instruments")]
         let timer = Instant::now();
+        let evaluations_t_iter = 0..domain.lde_roots_of_unity_coset.len();
 
-        let lde_periodic_columns = air
-            .get_periodic_column_polynomials()
-            .iter()
-            .map(|poly| {
-                evaluate_polynomial_on_lde_domain(
-                    poly,
-                    domain.blowup_factor,
-                    domain.interpolation_domain_size,
-                    &domain.coset_offset,
+        #[cfg(feature = "parallel")]
+        let evaluations_t_iter = evaluations_t_iter.into_par_iter();
+
+        let evaluations_t = evaluations_t_iter
+            .map(|i| {
+                self.evaluate_at(
+                    air,
+                    &domain_evaluations,
+                    lde_trace,
+                    i,
+                    i,
+                    transition_coefficients,
+                    boundary_coefficients,
+                    rap_challenges,
                 )
             })
-            .collect::<Result<Vec<Vec<FieldElement<A::Field>>>, FFTError>>()
-            .unwrap();
+            .collect();
 
         #[cfg(feature = "instruments")]
         println!(
-            "     Evaluating periodic columns on lde: {:#?}",
+            "     Evaluated transitions and accumulated results: {:#?}",
             timer.elapsed()
         );
 
-        #[cfg(feature = "instruments")]
-        let timer = Instant::now();
+        evaluations_t
+    }
 
-        let boundary_polys_evaluations = boundary_constraints
-            .constraints
+    /// Precomputes the values that only depend on the LDE domain and are needed to evaluate the
+    /// composition polynomial one coset of the trace domain at a time with `evaluate_on_coset`.
+    pub(crate) fn coset_domain(&self, air: &A, domain: &Domain<A::Field>) -> CosetDomain<A::Field> {
+        let trace_length = domain.interpolation_domain_size;
+
+        let periodic_column_polys = air
+            .get_periodic_column_values()
             .iter()
-            .map(|constraint| {
-                if constraint.is_aux {
-                    (0..lde_trace.num_rows())
-                        .map(|row| {
-                            let v = lde_trace.get_aux(row, constraint.col);
-                            v - &constraint.value
-                        })
-                        .collect_vec()
-                } else {
-                    (0..lde_trace.num_rows())
-                        .map(|row| {
-                            let v = lde_trace.get_main(row, constraint.col);
-                            v - &constraint.value
-                        })
-                        .collect_vec()
-                }
+            .map(|values| {
+                let column: Vec<_> = values.iter().cycle().take(trace_length).cloned().collect();
+                Polynomial::<FieldElement<A::Field>>::interpolate_fft::<A::Field>(&column).unwrap()
             })
-            .collect_vec();
+            .collect();
 
-        #[cfg(feature = "instruments")]
-        println!("     Created boundary polynomials: {:#?}", timer.elapsed());
-        #[cfg(feature = "instruments")]
-        let timer = Instant::now();
+        // The transition constraints with the same zerofier share its evaluations, as in
+        // `AIR::transition_zerofier_evaluations`.
+        let mut transition_zerofiers = Vec::new();
+        let mut transition_zerofier_groups = vec![0; air.num_transition_constraints()];
+        let mut zerofier_groups = HashMap::new();
+        for constraint in air.transition_constraints() {
+            let zerofier_group_key = (
+                constraint.period(),
+                constraint.offset(),
+                constraint.exemptions_period(),
+                constraint.periodic_exemptions_offset(),
+                constraint.end_exemptions(),
+            );
+            let group = *zerofier_groups
+                .entry(zerofier_group_key)
+                .or_insert_with(|| {
+                    transition_zerofiers.push(TransitionZerofier {
+                        cycle_evaluations: constraint
+                            .periodic_zerofier_evaluations_on_extended_domain(domain),
+                        end_exemptions_poly: constraint
+                            .end_exemptions_poly(&domain.trace_primitive_root, trace_length),
+                    });
+                    transition_zerofiers.len() - 1
+                });
+            transition_zerofier_groups[constraint.constraint_idx()] = group;
+        }
+
+        CosetDomain {
+            periodic_column_polys,
+            transition_zerofiers,
+            transition_zerofier_groups,
+        }
+    }
+
+    /// Evaluates the composition polynomial over the coset of the trace domain with index
+    /// `coset_index` in the LDE domain, that is, over the points of the LDE domain with indexes
+    /// `coset_index + blowup_factor * k`, in increasing order of `k`. `lde_trace` must hold the
+    /// evaluations of the trace polynomials over that coset, with a blowup factor of one. Only
+    /// the values over that coset are computed, so `domain` may have no LDE roots of unity.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn evaluate_on_coset(
+        &self,
+        air: &A,
+        domain: &Domain<A::Field>,
+        coset_domain: &CosetDomain<A::Field>,
+        coset_index: usize,
+        lde_trace: &LDETraceTable<A::Field, A::FieldExtension>,
+        transition_coefficients: &[FieldElement<A::FieldExtension>],
+        boundary_coefficients: &[FieldElement<A::FieldExtension>],
+        rap_challenges: &[FieldElement<A::FieldExtension>],
+    ) -> Vec<FieldElement<A::FieldExtension>>
+    where
+        FieldElement<A::Field>: AsBytes + Send + Sync,
+        FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+        A: Send + Sync,
+    {
+        let domain_evaluations =
+            self.evaluate_on_coset_of_domain(domain, coset_domain, coset_index);
 
+        let rows_iter = 0..domain.interpolation_domain_size;
         #[cfg(feature = "parallel")]
-        let boundary_eval_iter = (0..domain.lde_roots_of_unity_coset.len()).into_par_iter();
-        #[cfg(not(feature = "parallel"))]
-        let boundary_eval_iter = 0..domain.lde_roots_of_unity_coset.len();
-
-        let boundary_evaluation: Vec<_> = boundary_eval_iter
-            .map(|domain_index| {
-                (0..number_of_b_constraints)
-                    .zip(boundary_coefficients)
-                    .fold(FieldElement::zero(), |acc, (constraint_index, beta)| {
-                        acc + &boundary_zerofiers_inverse_evaluations[constraint_index]
-                            [domain_index]
-                            * beta
-                            * &boundary_polys_evaluations[constraint_index][domain_index]
-                    })
+        let rows_iter = rows_iter.into_par_iter();
+
+        rows_iter
+            .map(|row| {
+                self.evaluate_at(
+                    air,
+                    &domain_evaluations,
+                    lde_trace,
+                    row,
+                    row,
+                    transition_coefficients,
+                    boundary_coefficients,
+                    rap_challenges,
+                )
+            })
+            .collect()
+    }
+
+    /// Computes the values that only depend on the LDE domain over the coset with index
+    /// `coset_index`, indexed by the position of each point in the coset.
+    fn evaluate_on_coset_of_domain(
+        &self,
+        domain: &Domain<A::Field>,
+        coset_domain: &CosetDomain<A::Field>,
+        coset_index: usize,
+    ) -> DomainEvaluations<A::Field> {
+        let trace_length = domain.interpolation_domain_size;
+        let blowup_factor = domain.blowup_factor;
+        let lde_root_order = (trace_length * blowup_factor).trailing_zeros() as u64;
+        let lde_root = A::Field::get_primitive_root_of_unity(lde_root_order).unwrap();
+        let coset_offset = &domain.coset_offset * lde_root.pow(coset_index);
+        let points = get_powers_of_primitive_root_coset(
+            domain.root_order as u64,
+            trace_length,
+            &coset_offset,
+        )
+        .unwrap();
+
+        let boundary_zerofiers_inverse_evaluations = self
+            .boundary_constraints
+            .constraints
+            .iter()
+            .map(|bc| {
+                let point = &domain.trace_primitive_root.pow(bc.step as u64);
+                let mut evals = points.iter().map(|v| v - point).collect::<Vec<_>>();
+                FieldElement::inplace_batch_inverse(&mut evals).unwrap();
+                evals
             })
             .collect();
 
-        #[cfg(feature = "instruments")]
-        println!(
-            "     Evaluated boundary polynomials on LDE: {:#?}",
-            timer.elapsed()
-        );
+        let lde_periodic_columns = coset_domain
+            .periodic_column_polys
+            .iter()
+            .map(|poly| evaluate_polynomial_on_coset(poly, trace_length, &coset_offset).unwrap())
+            .collect();
 
-        #[cfg(all(debug_assertions, not(feature = "parallel")))]
-        let boundary_zerofiers = Vec::new();
+        let transition_zerofiers_evaluations = coset_domain
+            .transition_zerofiers
+            .iter()
+            .map(|zerofier| {
+                let cycle_evaluations = &zerofier.cycle_evaluations;
+                evaluate_polynomial_on_coset(
+                    &zerofier.end_exemptions_poly,
+                    trace_length,
+                    &coset_offset,
+                )
+                .unwrap()
+                .into_iter()
+                .enumerate()
+                .map(|(k, exemption_eval)| {
+                    let index = (coset_index + blowup_factor * k) % cycle_evaluations.len();
+                    &cycle_evaluations[index] * exemption_eval
+                })
+                .collect()
+            })
+            .collect();
 
-        #[cfg(all(debug_assertions, not(feature = "parallel")))]
-        check_boundary_polys_divisibility(boundary_polys, boundary_zerofiers);
+        DomainEvaluations {
+            boundary_zerofiers_inverse_evaluations,
+            lde_periodic_columns,
+            transition_zerofiers_evaluations,
+            transition_zerofier_groups: coset_domain.transition_zerofier_groups.clone(),
+        }
+    }
 
-        #[cfg(all(debug_assertions, not(feature = "parallel")))]
-        let mut transition_evaluations = Vec::new();
+    /// Computes the values needed to evaluate the composition polynomial that only depend on
+    /// the LDE domain: the inverses of the boundary zerofiers, the periodic columns and the
+    /// transition zerofiers.
+    fn evaluate_on_domain(
+        &self,
+        air: &A,
+        domain: &Domain<A::Field>,
+    ) -> DomainEvaluations<A::Field> {
+        let boundary_zerofiers_inverse_evaluations: Vec<Vec<FieldElement<A::Field>>> = self
+            .boundary_constraints
+            .constraints
+            .iter()
+            .map(|bc| {
+                let point = &domain.trace_primitive_root.pow(bc.step as u64);
+                let mut evals = domain
+                    .lde_roots_of_unity_coset
+                    .iter()
+                    .map(|v| v.clone() - point)
+                    .collect::<Vec<FieldElement<A::Field>>>();
+                FieldElement::inplace_batch_inverse(&mut evals).unwrap();
+                evals
+            })
+            .collect::<Vec<Vec<FieldElement<A::Field>>>>();
 
         #[cfg(feature = "instruments")]
         let timer = Instant::now();
-        let zerofiers_evals = air.transition_zerofier_evaluations(domain);
+
+        // The periodic columns are extended from their values, without building their
+        // polynomials.
+        let lde_periodic_columns = CosetLDE::new(
+            domain.interpolation_domain_size,
+            domain.blowup_factor,
+            &domain.coset_offset,
+        )
+        .and_then(|lde| {
+            let columns: Vec<Vec<_>> = air
+                .get_periodic_column_values()
+                .iter()
+                .map(|values| {
+                    values
+                        .iter()
+                        .cycle()
+                        .take(domain.interpolation_domain_size)
+                        .cloned()
+                        .collect()
+                })
+                .collect();
+            lde.extend_columns::<A::Field>(&columns)
+        })
+        .unwrap()
+        .columns();
+
         #[cfg(feature = "instruments")]
         println!(
-            "     Evaluated transition zerofiers: {:#?}",
+            "     Evaluating periodic columns on lde: {:#?}",
             timer.elapsed()
         );
 
-        // Iterate over all LDE domain and compute the part of the composition polynomial
-        // related to the transition constraints and add it to the already computed part of the
-        // boundary constraints.
-
         #[cfg(feature = "instruments")]
         let timer = Instant::now();
-        let evaluations_t_iter = 0..domain.lde_roots_of_unity_coset.len();
+        let transition_zerofiers_evaluations = air.transition_zerofier_evaluations(domain);
+        #[cfg(feature = "instruments")]
+        println!(
+            "     Evaluated transition zerofiers: {:#?}",
+            timer.elapsed()
+        );
 
-        #[cfg(feature = "parallel")]
-        let boundary_evaluation = boundary_evaluation.into_par_iter();
-        #[cfg(feature = "parallel")]
-        let evaluations_t_iter = evaluations_t_iter.into_par_iter();
+        DomainEvaluations {
+            boundary_zerofiers_inverse_evaluations,
+            lde_periodic_columns,
+            transition_zerofier_groups: (0..transition_zerofiers_evaluations.len()).collect(),
+            transition_zerofiers_evaluations,
+        }
+    }
 
-        let evaluations_t = evaluations_t_iter
-            .zip(boundary_evaluation)
-            .map(|(i, boundary)| {
-                let frame = Frame::read_from_lde(lde_trace, i, &air.context().transition_offsets);
+    /// Returns the evaluation of the composition polynomial at the point of the LDE domain
+    /// with index `domain_index`, whose frame starts at the row `row` of `lde_trace`.
+    #[allow(clippy::too_many_arguments)]
+    fn evaluate_at(
+        &self,
+        air: &A,
+        domain_evaluations: &DomainEvaluations<A::Field>,
+        lde_trace: &LDETraceTable<A::Field, A::FieldExtension>,
+        row: usize,
+        domain_index: usize,
+        transition_coefficients: &[FieldElement<A::FieldExtension>],
+        boundary_coefficients: &[FieldElement<A::FieldExtension>],
+        rap_challenges: &[FieldElement<A::FieldExtension>],
+    ) -> FieldElement<A::FieldExtension> {
+        let boundary = itertools::izip!(
+            &self.boundary_constraints.constraints,
+            &domain_evaluations.boundary_zerofiers_inverse_evaluations,
+            boundary_coefficients
+        )
+        .fold(
+            FieldElement::zero(),
+            |acc, (constraint, zerofier_inv, beta)| {
+                let boundary_poly_evaluation = if constraint.is_aux {
+                    lde_trace.get_aux(row, constraint.col) - &constraint.value
+                } else {
+                    lde_trace.get_main(row, constraint.col) - &constraint.value
+                };
+                acc + &zerofier_inv[domain_index] * beta * boundary_poly_evaluation
+            },
+        );
 
-                let periodic_values: Vec<_> = lde_periodic_columns
-                    .iter()
-                    .map(|col| col[i].clone())
-                    .collect();
-
-                // Compute all the transition constraints at this point of the LDE domain.
-                let evaluations_transition =
-                    air.compute_transition_prover(&frame, &periodic_values, rap_challenges);
-
-                #[cfg(all(debug_assertions, not(feature = "parallel")))]
-                transition_evaluations.push(evaluations_transition.clone());
-
-                // Add each term of the transition constraints to the composition polynomial, including the zerofier,
-                // the challenge and the exemption polynomial if it is necessary.
-                let acc_transition = itertools::izip!(
-                    evaluations_transition,
-                    &zerofiers_evals,
-                    transition_coefficients
-                )
-                .fold(FieldElement::zero(), |acc, (eval, zerof_eval, beta)| {
-                    // Zerofier evaluations are cyclical, so we only calculate one cycle.
-                    // This means that here we have to wrap around
-                    // Ex: Suppose the full zerofier vector is Z = [1,2,3,1,2,3]
-                    // we will instead have calculated Z' = [1,2,3]
-                    // Now if you need Z[4] this is equal to Z'[1]
-                    let wrapped_idx = i % zerof_eval.len();
-                    acc + &zerof_eval[wrapped_idx] * eval * beta
-                });
+        let frame = Frame::read_from_lde(lde_trace, row, &air.context().transition_offsets);
 
-                acc_transition + boundary
-            })
+        let periodic_values: Vec<_> = domain_evaluations
+            .lde_periodic_columns
+            .iter()
+            .map(|col| col[domain_index].clone())
             .collect();
 
-        #[cfg(feature = "instruments")]
-        println!(
-            "     Evaluated transitions and accumulated results: {:#?}",
-            timer.elapsed()
-        );
+        // Compute all the transition constraints at this point of the LDE domain.
+        let evaluations_transition =
+            air.compute_transition_prover(&frame, &periodic_values, rap_challenges);
 
-        evaluations_t
+        // Add each term of the transition constraints to the composition polynomial, including the zerofier,
+        // the challenge and the exemption polynomial if it is necessary.
+        let acc_transition = itertools::izip!(
+            evaluations_transition,
+            &domain_evaluations.transition_zerofier_groups,
+            transition_coefficients
+        )
+        .fold(FieldElement::zero(), |acc, (eval, group, beta)| {
+            let zerof_eval = &domain_evaluations.transition_zerofiers_evaluations[*group];
+            // Zerofier evaluations are cyclical, so we only calculate one cycle.
+            // This means that here we have to wrap around
+            // Ex: Suppose the full zerofier vector is Z = [1,2,3,1,2,3]
+            // we will instead have calculated Z' = [1,2,3]
+            // Now if you need Z[4] this is equal to Z'[1]
+            let wrapped_idx = domain_index % zerof_eval.len();
+            acc + &zerof_eval[wrapped_idx] * eval * beta
+        });
+
+        acc_transition + boundary
     }
 }
+
+/// The evaluations over the LDE domain needed to evaluate the composition polynomial that do
+/// not depend on the trace. The transition constraint of index `i` uses the zerofier
+/// evaluations of index `transition_zerofier_groups[i]`.
+struct DomainEvaluations<F: IsField> {
+    boundary_zerofiers_inverse_evaluations: Vec<Vec<FieldElement<F>>>,
+    lde_periodic_columns: Vec<Vec<FieldElement<F>>>,
+    transition_zerofiers_evaluations: Vec<Vec<FieldElement<F>>>,
+    transition_zerofier_groups: Vec<usize>,
+}
+
+/// The values needed to compute the `DomainEvaluations` over any coset of the trace domain in
+/// the LDE domain, without computing them over the whole LDE domain.
+pub(crate) struct CosetDomain<F: IsField> {
+    periodic_column_polys: Vec<Polynomial<FieldElement<F>>>,
+    /// The distinct zerofiers of the transition constraints.
+    transition_zerofiers: Vec<TransitionZerofier<F>>,
+    transition_zerofier_groups: Vec<usize>,
+}
+
+/// A zerofier of transition constraints, given by its evaluations without end exemptions over
+/// one cycle of the LDE domain and by its end exemptions polynomial.
+struct TransitionZerofier<F: IsField> {
+    cycle_evaluations: Vec<FieldElement<F>>,
+    end_exemptions_poly: Polynomial<FieldElement<F>>,
+}
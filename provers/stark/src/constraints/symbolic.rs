@@ -0,0 +1,493 @@
+use std::{
+    collections::HashMap,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ops::{Add, Mul, Neg, Sub},
+    sync::Mutex,
+};
+
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsFFTField, IsField, IsSubFieldOf},
+};
+
+use super::transition::TransitionConstraint;
+use crate::frame::Frame;
+
+/// A symbolic expression over the elements of an evaluation frame, used to write a
+/// transition constraint once and derive from it both its degree and its evaluators.
+///
+/// Trace elements are addressed by the `step` of the frame (the position of the
+/// step in the transition offsets of the AIR context), the `row` inside that step
+/// and the column. Constants live in the base field `F`.
+#[derive(Clone, Debug)]
+pub enum Expr<F: IsField> {
+    Constant(FieldElement<F>),
+    Main { step: usize, row: usize, col: usize },
+    Aux { step: usize, row: usize, col: usize },
+    Periodic(usize),
+    RapChallenge(usize),
+    Add(Box<Expr<F>>, Box<Expr<F>>),
+    Sub(Box<Expr<F>>, Box<Expr<F>>),
+    Mul(Box<Expr<F>>, Box<Expr<F>>),
+    Neg(Box<Expr<F>>),
+}
+
+impl<F: IsField> Expr<F> {
+    pub fn constant(value: FieldElement<F>) -> Self {
+        Self::Constant(value)
+    }
+
+    pub fn zero() -> Self {
+        Self::Constant(FieldElement::zero())
+    }
+
+    pub fn one() -> Self {
+        Self::Constant(FieldElement::one())
+    }
+
+    /// Element of the main trace at column `col` of the first row of the frame step `step`.
+    pub fn main(step: usize, col: usize) -> Self {
+        Self::Main { step, row: 0, col }
+    }
+
+    /// Element of the auxiliary trace at column `col` of the first row of the frame step `step`.
+    pub fn aux(step: usize, col: usize) -> Self {
+        Self::Aux { step, row: 0, col }
+    }
+
+    /// Element of the main trace for AIRs with a step size greater than one.
+    pub fn main_at(step: usize, row: usize, col: usize) -> Self {
+        Self::Main { step, row, col }
+    }
+
+    /// Element of the auxiliary trace for AIRs with a step size greater than one.
+    pub fn aux_at(step: usize, row: usize, col: usize) -> Self {
+        Self::Aux { step, row, col }
+    }
+
+    pub fn periodic(idx: usize) -> Self {
+        Self::Periodic(idx)
+    }
+
+    pub fn rap_challenge(idx: usize) -> Self {
+        Self::RapChallenge(idx)
+    }
+
+    pub fn square(self) -> Self {
+        self.clone() * self
+    }
+
+    pub fn pow(self, exponent: usize) -> Self {
+        debug_assert!(exponent > 0);
+        (1..exponent).fold(self.clone(), |acc, _| acc * self.clone())
+    }
+
+    /// The degree of the expression interpreted as a multivariate polynomial in the
+    /// trace elements. Periodic values, RAP challenges and constants have degree zero.
+    pub fn degree(&self) -> usize {
+        match self {
+            Self::Constant(_) | Self::Periodic(_) | Self::RapChallenge(_) => 0,
+            Self::Main { .. } | Self::Aux { .. } => 1,
+            Self::Add(a, b) | Self::Sub(a, b) => a.degree().max(b.degree()),
+            Self::Mul(a, b) => a.degree() + b.degree(),
+            Self::Neg(a) => a.degree(),
+        }
+    }
+
+    /// The largest frame step referenced by the expression, if any.
+    pub fn max_step(&self) -> Option<usize> {
+        match self {
+            Self::Main { step, .. } | Self::Aux { step, .. } => Some(*step),
+            Self::Constant(_) | Self::Periodic(_) | Self::RapChallenge(_) => None,
+            Self::Add(a, b) | Self::Sub(a, b) | Self::Mul(a, b) => a.max_step().max(b.max_step()),
+            Self::Neg(a) => a.max_step(),
+        }
+    }
+}
+
+impl<F: IsField> Add for Expr<F> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::Add(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl<F: IsField> Sub for Expr<F> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::Sub(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl<F: IsField> Mul for Expr<F> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::Mul(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl<F: IsField> Neg for Expr<F> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::Neg(Box::new(self))
+    }
+}
+
+impl<F: IsField> From<FieldElement<F>> for Expr<F> {
+    fn from(value: FieldElement<F>) -> Self {
+        Self::Constant(value)
+    }
+}
+
+/// A single instruction of a compiled expression. Operands are indexes of the
+/// registers holding the results of previous instructions.
+#[derive(Clone, Debug)]
+enum Op<F: IsField> {
+    Constant(FieldElement<F>),
+    Main { step: usize, row: usize, col: usize },
+    Aux { step: usize, row: usize, col: usize },
+    Periodic(usize),
+    RapChallenge(usize),
+    Add(usize, usize),
+    Sub(usize, usize),
+    Mul(usize, usize),
+    Neg(usize),
+}
+
+impl<F: IsField> PartialEq for Op<F> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Constant(a), Self::Constant(b)) => a == b,
+            (
+                Self::Main { step, row, col },
+                Self::Main {
+                    step: s,
+                    row: r,
+                    col: c,
+                },
+            )
+            | (
+                Self::Aux { step, row, col },
+                Self::Aux {
+                    step: s,
+                    row: r,
+                    col: c,
+                },
+            ) => step == s && row == r && col == c,
+            (Self::Periodic(a), Self::Periodic(b))
+            | (Self::RapChallenge(a), Self::RapChallenge(b))
+            | (Self::Neg(a), Self::Neg(b)) => a == b,
+            (Self::Add(a, b), Self::Add(c, d))
+            | (Self::Sub(a, b), Self::Sub(c, d))
+            | (Self::Mul(a, b), Self::Mul(c, d)) => a == c && b == d,
+            _ => false,
+        }
+    }
+}
+
+impl<F: IsField> Eq for Op<F> {}
+
+/// Hashes the kind of the instruction and its indexes. Constants are compared only by `eq`,
+/// since equal field elements may have different representations.
+impl<F: IsField> Hash for Op<F> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        core::mem::discriminant(self).hash(state);
+        match self {
+            Self::Constant(_) => {}
+            Self::Main { step, row, col } | Self::Aux { step, row, col } => {
+                (step, row, col).hash(state)
+            }
+            Self::Periodic(a) | Self::RapChallenge(a) | Self::Neg(a) => a.hash(state),
+            Self::Add(a, b) | Self::Sub(a, b) | Self::Mul(a, b) => (a, b).hash(state),
+        }
+    }
+}
+
+/// An expression flattened into a list of instructions, where repeated
+/// subexpressions are computed only once.
+#[derive(Clone, Debug)]
+struct Program<F: IsField> {
+    ops: Vec<Op<F>>,
+    result: usize,
+}
+
+impl<F: IsField> Program<F> {
+    fn compile(expr: &Expr<F>) -> Self {
+        let mut program = Self {
+            ops: Vec::new(),
+            result: 0,
+        };
+        let mut registers = HashMap::new();
+        program.result = program.push_expr(expr, &mut registers);
+        program
+    }
+
+    /// Appends the instructions computing `expr` and returns the register holding its value.
+    /// `registers` maps each instruction already in the program to its register.
+    fn push_expr(&mut self, expr: &Expr<F>, registers: &mut HashMap<Op<F>, usize>) -> usize {
+        let op = match expr {
+            Expr::Constant(value) => Op::Constant(value.clone()),
+            Expr::Main { step, row, col } => Op::Main {
+                step: *step,
+                row: *row,
+                col: *col,
+            },
+            Expr::Aux { step, row, col } => Op::Aux {
+                step: *step,
+                row: *row,
+                col: *col,
+            },
+            Expr::Periodic(idx) => Op::Periodic(*idx),
+            Expr::RapChallenge(idx) => Op::RapChallenge(*idx),
+            Expr::Add(a, b) => Op::Add(self.push_expr(a, registers), self.push_expr(b, registers)),
+            Expr::Sub(a, b) => Op::Sub(self.push_expr(a, registers), self.push_expr(b, registers)),
+            Expr::Mul(a, b) => Op::Mul(self.push_expr(a, registers), self.push_expr(b, registers)),
+            Expr::Neg(a) => Op::Neg(self.push_expr(a, registers)),
+        };
+
+        *registers.entry(op).or_insert_with_key(|op| {
+            self.ops.push(op.clone());
+            self.ops.len() - 1
+        })
+    }
+
+    /// Evaluates the program over `frame`, using `registers` as scratch space.
+    fn evaluate<S, E>(
+        &self,
+        frame: &Frame<S, E>,
+        periodic_values: &[FieldElement<S>],
+        rap_challenges: &[FieldElement<E>],
+        registers: &mut Vec<FieldElement<E>>,
+    ) -> FieldElement<E>
+    where
+        F: IsSubFieldOf<E>,
+        S: IsSubFieldOf<E>,
+        E: IsField,
+    {
+        registers.clear();
+        for op in self.ops.iter() {
+            let value = match op {
+                Op::Constant(value) => value.clone().to_extension(),
+                Op::Main { step, row, col } => frame
+                    .get_evaluation_step(*step)
+                    .get_main_evaluation_element(*row, *col)
+                    .clone()
+                    .to_extension(),
+                Op::Aux { step, row, col } => frame
+                    .get_evaluation_step(*step)
+                    .get_aux_evaluation_element(*row, *col)
+                    .clone(),
+                Op::Periodic(idx) => periodic_values[*idx].clone().to_extension(),
+                Op::RapChallenge(idx) => rap_challenges[*idx].clone(),
+                Op::Add(a, b) => &registers[*a] + &registers[*b],
+                Op::Sub(a, b) => &registers[*a] - &registers[*b],
+                Op::Mul(a, b) => &registers[*a] * &registers[*b],
+                Op::Neg(a) => -&registers[*a],
+            };
+            registers.push(value);
+        }
+        registers[self.result].clone()
+    }
+}
+
+/// A transition constraint defined by a symbolic expression that must evaluate to
+/// zero. Its degree is derived from the expression, and the same expression is
+/// evaluated by the prover, over `Frame<F, E>`, and by the verifier, over `Frame<E, E>`.
+pub struct SymbolicConstraint<F: IsField, E: IsField> {
+    program: Program<F>,
+    /// Register buffers reused across evaluations, one for each evaluation in progress.
+    scratch: Mutex<Vec<Vec<FieldElement<E>>>>,
+    degree: usize,
+    constraint_idx: usize,
+    end_exemptions: usize,
+    period: usize,
+    offset: usize,
+    exemptions_period: Option<usize>,
+    periodic_exemptions_offset: Option<usize>,
+    phantom: PhantomData<E>,
+}
+
+impl<F: IsField, E: IsField> SymbolicConstraint<F, E> {
+    pub fn new(constraint_idx: usize, expr: Expr<F>, end_exemptions: usize) -> Self {
+        Self {
+            program: Program::compile(&expr),
+            scratch: Mutex::new(Vec::new()),
+            degree: expr.degree(),
+            constraint_idx,
+            end_exemptions,
+            period: 1,
+            offset: 0,
+            exemptions_period: None,
+            periodic_exemptions_offset: None,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Applies the constraint only to the rows `offset`, `offset + period`, `offset + 2 * period`, etc.
+    pub fn with_period(mut self, period: usize, offset: usize) -> Self {
+        self.period = period;
+        self.offset = offset;
+        self
+    }
+
+    /// Excludes the rows `offset`, `offset + exemptions_period`, etc. from the rows where
+    /// the constraint applies.
+    pub fn with_periodic_exemptions(mut self, exemptions_period: usize, offset: usize) -> Self {
+        self.exemptions_period = Some(exemptions_period);
+        self.periodic_exemptions_offset = Some(offset);
+        self
+    }
+
+    fn evaluate_program<S>(
+        &self,
+        frame: &Frame<S, E>,
+        periodic_values: &[FieldElement<S>],
+        rap_challenges: &[FieldElement<E>],
+    ) -> FieldElement<E>
+    where
+        F: IsSubFieldOf<E>,
+        S: IsSubFieldOf<E>,
+    {
+        let mut registers = self.scratch.lock().unwrap().pop().unwrap_or_default();
+        let value = self
+            .program
+            .evaluate(frame, periodic_values, rap_challenges, &mut registers);
+        self.scratch.lock().unwrap().push(registers);
+        value
+    }
+
+    /// Evaluates the constraint over the out of domain frame of the verifier, where both main
+    /// and auxiliary elements take values in the extension field.
+    pub fn evaluate_verifier(
+        &self,
+        frame: &Frame<E, E>,
+        transition_evaluations: &mut [FieldElement<E>],
+        periodic_values: &[FieldElement<E>],
+        rap_challenges: &[FieldElement<E>],
+    ) where
+        F: IsSubFieldOf<E>,
+    {
+        transition_evaluations[self.constraint_idx] =
+            self.evaluate_program(frame, periodic_values, rap_challenges);
+    }
+}
+
+impl<F, E> TransitionConstraint<F, E> for SymbolicConstraint<F, E>
+where
+    F: IsFFTField + IsSubFieldOf<E> + Send + Sync,
+    E: IsField + Send + Sync,
+    FieldElement<F>: Send + Sync,
+    FieldElement<E>: Send + Sync,
+{
+    fn degree(&self) -> usize {
+        self.degree
+    }
+
+    fn constraint_idx(&self) -> usize {
+        self.constraint_idx
+    }
+
+    fn period(&self) -> usize {
+        self.period
+    }
+
+    fn offset(&self) -> usize {
+        self.offset
+    }
+
+    fn exemptions_period(&self) -> Option<usize> {
+        self.exemptions_period
+    }
+
+    fn periodic_exemptions_offset(&self) -> Option<usize> {
+        self.periodic_exemptions_offset
+    }
+
+    fn end_exemptions(&self) -> usize {
+        self.end_exemptions
+    }
+
+    fn evaluate(
+        &self,
+        frame: &Frame<F, E>,
+        transition_evaluations: &mut [FieldElement<E>],
+        periodic_values: &[FieldElement<F>],
+        rap_challenges: &[FieldElement<E>],
+    ) {
+        transition_evaluations[self.constraint_idx] =
+            self.evaluate_program(frame, periodic_values, rap_challenges);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::table::Table;
+    use lambdaworks_math::field::fields::u64_prime_field::{F17, FE17};
+
+    fn fibonacci_rap_expressions() -> (Expr<F17>, Expr<F17>) {
+        // a_{i+2} - a_{i+1} - a_i
+        let fib = Expr::main(2, 0) - Expr::main(1, 0) - Expr::main(0, 0);
+        // z_{i+1} * (b_i + gamma) - z_i * (a_i + gamma)
+        let gamma = Expr::rap_challenge(0);
+        let permutation = Expr::aux(1, 0) * (Expr::main(0, 1) + gamma.clone())
+            - Expr::aux(0, 0) * (Expr::main(0, 0) + gamma);
+        (fib, permutation)
+    }
+
+    #[test]
+    fn degrees_are_derived_from_the_expression() {
+        let (fib, permutation) = fibonacci_rap_expressions();
+        assert_eq!(fib.degree(), 1);
+        assert_eq!(permutation.degree(), 2);
+        assert_eq!(Expr::<F17>::main(0, 0).pow(3).degree(), 3);
+        assert_eq!((Expr::<F17>::periodic(0) * Expr::main(0, 0)).degree(), 1);
+        assert_eq!(fib.max_step(), Some(2));
+    }
+
+    #[test]
+    fn repeated_subexpressions_are_compiled_once() {
+        let x = Expr::<F17>::main(0, 0) + Expr::main(1, 0);
+        let program = Program::compile(&(x.clone() * x));
+        // main(0, 0), main(1, 0), the addition and the multiplication.
+        assert_eq!(program.ops.len(), 4);
+    }
+
+    #[test]
+    fn prover_and_verifier_evaluations_match_hand_written_constraints() {
+        let (fib, permutation) = fibonacci_rap_expressions();
+        let fib_constraint = SymbolicConstraint::<F17, F17>::new(0, fib, 2);
+        let permutation_constraint = SymbolicConstraint::<F17, F17>::new(1, permutation, 1);
+
+        // Columns: a, b (main) and z (aux).
+        let rows = [[1, 3, 5], [2, 8, 7], [3, 6, 11]];
+        let data = rows.iter().flatten().map(|v| FE17::from(*v)).collect();
+        let table = Table::new(data, 3);
+        let frame = table.into_frame(2, 1);
+        let gamma = FE17::from(4);
+
+        let mut prover_evaluations = vec![FE17::zero(); 2];
+        fib_constraint.evaluate(&frame, &mut prover_evaluations, &[], &[gamma]);
+        permutation_constraint.evaluate(&frame, &mut prover_evaluations, &[], &[gamma]);
+
+        let mut verifier_evaluations = vec![FE17::zero(); 2];
+        fib_constraint.evaluate_verifier(&frame, &mut verifier_evaluations, &[], &[gamma]);
+        permutation_constraint.evaluate_verifier(&frame, &mut verifier_evaluations, &[], &[gamma]);
+
+        let expected_fib = FE17::from(3) - FE17::from(2) - FE17::from(1);
+        let expected_permutation =
+            FE17::from(7) * (FE17::from(3) + gamma) - FE17::from(5) * (FE17::from(1) + gamma);
+
+        assert_eq!(prover_evaluations, vec![expected_fib, expected_permutation]);
+        assert_eq!(prover_evaluations, verifier_evaluations);
+        assert_eq!(
+            TransitionConstraint::<F17, F17>::degree(&permutation_constraint),
+            2
+        );
+    }
+}
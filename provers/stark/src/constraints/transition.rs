@@ -106,6 +106,41 @@ where
 
     /// Compute evaluations of the constraints zerofier over a LDE domain.
     fn zerofier_evaluations_on_extended_domain(&self, domain: &Domain<F>) -> Vec<FieldElement<F>> {
+        let end_exemptions_poly = self.end_exemptions_poly(
+            &domain.trace_primitive_root,
+            domain.interpolation_domain_size,
+        );
+        let evaluations = self.periodic_zerofier_evaluations_on_extended_domain(domain);
+
+        // FIXME: Instead of computing this evaluations for each constraint, they can be computed
+        // once for every constraint with the same end exemptions (combination of end_exemptions()
+        // and period).
+        let end_exemption_evaluations = evaluate_polynomial_on_lde_domain(
+            &end_exemptions_poly,
+            domain.blowup_factor,
+            domain.interpolation_domain_size,
+            &domain.coset_offset,
+        )
+        .unwrap();
+
+        let cycled_evaluations = evaluations
+            .iter()
+            .cycle()
+            .take(end_exemption_evaluations.len());
+
+        std::iter::zip(cycled_evaluations, end_exemption_evaluations)
+            .map(|(eval, exemption_eval)| eval * exemption_eval)
+            .collect()
+    }
+
+    /// Computes the evaluations of the zerofier of the constraint without its end exemptions
+    /// over the first points of a LDE domain. They repeat cyclically over the rest of the
+    /// domain, so the evaluation at the point of index `i` is the one of index
+    /// `i % evaluations.len()`.
+    fn periodic_zerofier_evaluations_on_extended_domain(
+        &self,
+        domain: &Domain<F>,
+    ) -> Vec<FieldElement<F>> {
         let blowup_factor = domain.blowup_factor;
         let trace_length = domain.trace_roots_of_unity.len();
         let trace_primitive_root = &domain.trace_primitive_root;
@@ -113,8 +148,6 @@ where
         let lde_root_order = u64::from((blowup_factor * trace_length).trailing_zeros());
         let lde_root = F::get_primitive_root_of_unity(lde_root_order).unwrap();
 
-        let end_exemptions_poly = self.end_exemptions_poly(trace_primitive_root, trace_length);
-
         // If there is an exemptions period defined for this constraint, the evaluations are calculated directly
         // by computing P_exemptions(x) / Zerofier(x)
         if let Some(exemptions_period) = self.exemptions_period() {
@@ -128,7 +161,7 @@ where
             // so we only need to compute those.
             let last_exponent = blowup_factor * exemptions_period;
 
-            let evaluations: Vec<_> = (0..last_exponent)
+            (0..last_exponent)
                 .map(|exponent| {
                     let x = lde_root.pow(exponent);
                     let offset_times_x = coset_offset * &x;
@@ -142,31 +175,10 @@ where
 
                     numerator.div(denominator)
                 })
-                .collect();
-
-            // FIXME: Instead of computing this evaluations for each constraint, they can be computed
-            // once for every constraint with the same end exemptions (combination of end_exemptions()
-            // and period).
-            let end_exemption_evaluations = evaluate_polynomial_on_lde_domain(
-                &end_exemptions_poly,
-                blowup_factor,
-                domain.interpolation_domain_size,
-                coset_offset,
-            )
-            .unwrap();
-
-            let cycled_evaluations = evaluations
-                .iter()
-                .cycle()
-                .take(end_exemption_evaluations.len());
-
-            std::iter::zip(cycled_evaluations, end_exemption_evaluations)
-                .map(|(eval, exemption_eval)| eval * exemption_eval)
                 .collect()
 
         // In this else branch, the zerofiers are computed as the numerator, then inverted
-        // using batch inverse and then multiplied by P_exemptions(x). This way we don't do
-        // useless divisions.
+        // using batch inverse. This way we don't do useless divisions.
         } else {
             let last_exponent = blowup_factor * self.period();
 
@@ -179,26 +191,7 @@ where
                 .collect_vec();
 
             FieldElement::inplace_batch_inverse(&mut evaluations).unwrap();
-
-            // FIXME: Instead of computing this evaluations for each constraint, they can be computed
-            // once for every constraint with the same end exemptions (combination of end_exemptions()
-            // and period).
-            let end_exemption_evaluations = evaluate_polynomial_on_lde_domain(
-                &end_exemptions_poly,
-                blowup_factor,
-                domain.interpolation_domain_size,
-                coset_offset,
-            )
-            .unwrap();
-
-            let cycled_evaluations = evaluations
-                .iter()
-                .cycle()
-                .take(end_exemption_evaluations.len());
-
-            std::iter::zip(cycled_evaluations, end_exemption_evaluations)
-                .map(|(eval, exemption_eval)| eval * exemption_eval)
-                .collect()
+            evaluations
         }
     }
 
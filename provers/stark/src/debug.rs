@@ -1,33 +1,232 @@
 use super::domain::Domain;
 use super::traits::AIR;
-use crate::{frame::Frame, trace::LDETraceTable};
+use crate::{frame::Frame, prover::evaluate_polynomial_on_coset, trace::LDETraceTable};
 use lambdaworks_math::{
     field::{
         element::FieldElement,
-        traits::{IsFFTField, IsField},
+        traits::{IsFFTField, IsField, IsSubFieldOf},
     },
     polynomial::Polynomial,
 };
 use log::{error, info};
+use std::fmt;
 
-/// Validates that the trace is valid with respect to the supplied AIR constraints
+/// A row of the trace involved in a failing constraint, with the values of its main and
+/// auxiliary columns.
+#[derive(Clone, Debug)]
+pub struct TraceRow<F: IsField, E: IsField> {
+    pub row: usize,
+    pub main: Vec<FieldElement<F>>,
+    pub aux: Vec<FieldElement<E>>,
+}
+
+/// A boundary constraint whose value does not match the one found in the trace.
+#[derive(Clone, Debug)]
+pub struct BoundaryConstraintFailure<E: IsField> {
+    /// Index of the constraint in the boundary constraints of the AIR.
+    pub constraint_idx: usize,
+    pub step: usize,
+    pub col: usize,
+    pub is_aux: bool,
+    pub expected: FieldElement<E>,
+    pub found: FieldElement<E>,
+}
+
+/// A transition constraint that does not evaluate to zero at a step of the trace where it is
+/// not exempted.
+#[derive(Clone, Debug)]
+pub struct TransitionConstraintFailure<F: IsField, E: IsField> {
+    /// Index of the constraint in the transition constraints of the AIR.
+    pub constraint_idx: usize,
+    pub step: usize,
+    pub evaluation: FieldElement<E>,
+    /// The rows of the evaluation frame of the step, in the order of the transition offsets.
+    pub frame: Vec<TraceRow<F, E>>,
+}
+
+/// The result of validating a trace against the constraints of an AIR. It holds every
+/// violated constraint together with the RAP challenges used to build the auxiliary trace.
+#[derive(Clone, Debug)]
+pub struct TraceValidationReport<F: IsField, E: IsField> {
+    pub rap_challenges: Vec<FieldElement<E>>,
+    pub boundary_failures: Vec<BoundaryConstraintFailure<E>>,
+    pub transition_failures: Vec<TransitionConstraintFailure<F, E>>,
+}
+
+impl<F: IsField, E: IsField> TraceValidationReport<F, E> {
+    /// Returns true if the trace satisfies all the constraints.
+    pub fn is_valid(&self) -> bool {
+        self.boundary_failures.is_empty() && self.transition_failures.is_empty()
+    }
+
+    /// Returns the indexes of the transition constraints that fail at some step, without
+    /// repetitions and in increasing order.
+    pub fn failing_transition_constraints(&self) -> Vec<usize> {
+        let mut indexes: Vec<usize> = self
+            .transition_failures
+            .iter()
+            .map(|failure| failure.constraint_idx)
+            .collect();
+        indexes.sort_unstable();
+        indexes.dedup();
+        indexes
+    }
+}
+
+fn write_elements<F: IsField>(
+    f: &mut fmt::Formatter<'_>,
+    elements: &[FieldElement<F>],
+) -> fmt::Result
+where
+    FieldElement<F>: fmt::Display,
+{
+    write!(f, "[")?;
+    for (i, element) in elements.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", element)?;
+    }
+    write!(f, "]")
+}
+
+impl<F: IsField, E: IsField> fmt::Display for TraceRow<F, E>
+where
+    FieldElement<F>: fmt::Display,
+    FieldElement<E>: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "row {:>8} | main: ", self.row)?;
+        write_elements(f, &self.main)?;
+        if !self.aux.is_empty() {
+            write!(f, " | aux: ")?;
+            write_elements(f, &self.aux)?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: IsField> fmt::Display for BoundaryConstraintFailure<E>
+where
+    FieldElement<E>: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let table = if self.is_aux { "aux" } else { "main" };
+        write!(
+            f,
+            "boundary constraint {} failed at step {}, {} column {}: expected {}, found {}",
+            self.constraint_idx, self.step, table, self.col, self.expected, self.found
+        )
+    }
+}
+
+impl<F: IsField, E: IsField> fmt::Display for TransitionConstraintFailure<F, E>
+where
+    FieldElement<F>: fmt::Display,
+    FieldElement<E>: fmt::Display,
+{
+    /// Renders the failure followed by the window of the trace read by the constraint.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "transition constraint {} failed at step {}: evaluated to {}",
+            self.constraint_idx, self.step, self.evaluation
+        )?;
+        for (i, row) in self.frame.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "    {}", row)?;
+        }
+        Ok(())
+    }
+}
+
+impl<F: IsField, E: IsField> fmt::Display for TraceValidationReport<F, E>
+where
+    FieldElement<F>: fmt::Display,
+    FieldElement<E>: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_valid() {
+            return write!(f, "the trace satisfies all the constraints");
+        }
+        write!(
+            f,
+            "{} boundary and {} transition constraint failures",
+            self.boundary_failures.len(),
+            self.transition_failures.len()
+        )?;
+        if !self.rap_challenges.is_empty() {
+            write!(f, "\nRAP challenges: ")?;
+            write_elements(f, &self.rap_challenges)?;
+        }
+        for failure in &self.boundary_failures {
+            write!(f, "\n{}", failure)?;
+        }
+        for failure in &self.transition_failures {
+            write!(f, "\n{}", failure)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads the rows of the trace that make up the evaluation frame of `step`.
+fn read_frame_rows<F, E>(
+    lde_trace: &LDETraceTable<F, E>,
+    step: usize,
+    offsets: &[usize],
+) -> Vec<TraceRow<F, E>>
+where
+    F: IsSubFieldOf<E>,
+    E: IsField,
+{
+    let num_rows = lde_trace.num_rows();
+    let step_size = lde_trace.lde_step_size;
+    let row = lde_trace.step_to_row(step);
+
+    offsets
+        .iter()
+        .flat_map(|offset| {
+            let initial_step_row = row + offset * step_size;
+            (initial_step_row..initial_step_row + step_size).step_by(lde_trace.blowup_factor)
+        })
+        .map(|step_row| {
+            let row = step_row % num_rows;
+            TraceRow {
+                row,
+                main: lde_trace.get_main_row(row).to_vec(),
+                aux: lde_trace.get_aux_row(row).to_vec(),
+            }
+        })
+        .collect()
+}
+
+/// Validates that the trace is valid with respect to the supplied AIR constraints, returning a
+/// report of every violated constraint.
 pub fn validate_trace<A: AIR>(
     air: &A,
     main_trace_polys: &[Polynomial<FieldElement<A::Field>>],
     aux_trace_polys: &[Polynomial<FieldElement<A::FieldExtension>>],
     domain: &Domain<A::Field>,
     rap_challenges: &[FieldElement<A::FieldExtension>],
-) -> bool {
+) -> TraceValidationReport<A::Field, A::FieldExtension> {
     info!("Starting constraints validation over trace...");
-    let mut ret = true;
+    let mut report = TraceValidationReport {
+        rap_challenges: rap_challenges.to_vec(),
+        boundary_failures: Vec::new(),
+        transition_failures: Vec::new(),
+    };
 
+    // The trace polynomials may be masked in zero-knowledge mode, so they are evaluated
+    // reducing them modulo the vanishing polynomial of the trace domain.
     let main_trace_columns: Vec<_> = main_trace_polys
         .iter()
         .map(|poly| {
-            Polynomial::<FieldElement<A::Field>>::evaluate_fft::<A::Field>(
+            evaluate_polynomial_on_coset(
                 poly,
-                1,
-                Some(domain.interpolation_domain_size),
+                domain.interpolation_domain_size,
+                &FieldElement::<A::Field>::one(),
             )
             .unwrap()
         })
@@ -36,8 +235,12 @@ pub fn validate_trace<A: AIR>(
     let aux_trace_columns: Vec<_> = aux_trace_polys
         .iter()
         .map(|poly| {
-            Polynomial::evaluate_fft::<A::Field>(poly, 1, Some(domain.interpolation_domain_size))
-                .unwrap()
+            evaluate_polynomial_on_coset(
+                poly,
+                domain.interpolation_domain_size,
+                &FieldElement::<A::Field>::one(),
+            )
+            .unwrap()
         })
         .collect();
 
@@ -61,7 +264,8 @@ pub fn validate_trace<A: AIR>(
     air.boundary_constraints(rap_challenges)
         .constraints
         .iter()
-        .for_each(|constraint| {
+        .enumerate()
+        .for_each(|(constraint_idx, constraint)| {
             let col = constraint.col;
             let step = constraint.step;
             let boundary_value = constraint.value.clone();
@@ -72,24 +276,48 @@ pub fn validate_trace<A: AIR>(
                 lde_trace.get_aux(step,  col).clone()
             };
 
-            if boundary_value.clone().to_extension() != trace_value {
-                ret = false;
+            if boundary_value != trace_value {
                 error!("Boundary constraint inconsistency - Expected value {:?} in step {} and column {}, found: {:?}", boundary_value, step, col, trace_value);
+                report.boundary_failures.push(BoundaryConstraintFailure {
+                    constraint_idx,
+                    step,
+                    col,
+                    is_aux: constraint.is_aux,
+                    expected: boundary_value,
+                    found: trace_value,
+                });
             }
         });
 
     // --------- VALIDATE TRANSITION CONSTRAINTS -----------
-    let n_transition_constraints = air.context().num_transition_constraints();
-    let transition_exemptions = &air.context().transition_exemptions;
-
-    let exemption_steps: Vec<usize> = vec![lde_trace.num_rows(); n_transition_constraints]
-        .iter()
-        .zip(transition_exemptions)
-        .map(|(trace_steps, exemptions)| trace_steps - exemptions)
-        .collect();
+    // The steps where each transition constraint has to hold, taking into account its
+    // period, its periodic exemptions and its end exemptions.
+    let num_steps = lde_trace.num_steps();
+    let mut constraint_steps = vec![None; air.context().num_transition_constraints()];
+    for constraint in air.transition_constraints() {
+        constraint_steps[constraint.constraint_idx()] = Some((
+            constraint.period(),
+            constraint.offset(),
+            constraint
+                .exemptions_period()
+                .zip(constraint.periodic_exemptions_offset()),
+            constraint.end_exemptions(),
+        ));
+    }
+    let is_constrained_step =
+        |constraint_idx: usize, step: usize| match constraint_steps[constraint_idx] {
+            Some((period, offset, periodic_exemptions, end_exemptions)) => {
+                step % period == offset
+                    && periodic_exemptions.map_or(true, |(exemptions_period, exemptions_offset)| {
+                        step % exemptions_period != exemptions_offset
+                    })
+                    && (1..=end_exemptions).all(|exemption| step + exemption * period != num_steps)
+            }
+            None => step < num_steps - air.context().transition_exemptions[constraint_idx],
+        };
 
     // Iterate over trace and compute transitions
-    for step in 0..lde_trace.num_steps() {
+    for step in 0..num_steps {
         let frame = Frame::read_step_from_lde(&lde_trace, step, &air.context().transition_offsets);
         let periodic_values: Vec<_> = periodic_columns
             .iter()
@@ -103,17 +331,24 @@ pub fn validate_trace<A: AIR>(
         evaluations.iter().enumerate().for_each(|(i, eval)| {
             // Check that all the transition constraint evaluations of the trace are zero.
             // We don't take into account the transition exemptions.
-            if step < exemption_steps[i] && eval != &FieldElement::zero() {
-                ret = false;
+            if is_constrained_step(i, step) && eval != &FieldElement::zero() {
                 error!(
                     "Inconsistent evaluation of transition {} in step {} - expected 0, got {:?}",
                     i, step, eval
                 );
+                report
+                    .transition_failures
+                    .push(TransitionConstraintFailure {
+                        constraint_idx: i,
+                        step,
+                        evaluation: eval.clone(),
+                        frame: read_frame_rows(&lde_trace, step, &air.context().transition_offsets),
+                    });
             }
         })
     }
     info!("Constraints validation check ended");
-    ret
+    report
 }
 
 pub fn check_boundary_polys_divisibility<F: IsFFTField>(
@@ -141,3 +376,97 @@ where
     let rows: Vec<Vec<FieldElement<F>>> = data.chunks(width).map(|c| c.to_vec()).collect();
     rows.iter().all(|r| r.len() == rows[0].len())
 }
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::field::fields::fft_friendly::babybear::Babybear31PrimeField;
+
+    use super::*;
+    use crate::{
+        examples::simple_fibonacci::{fibonacci_trace, FibonacciAIR, FibonacciPublicInputs},
+        proof::options::ProofOptions,
+        trace::TraceTable,
+    };
+
+    type F = Babybear31PrimeField;
+    type FE = FieldElement<F>;
+
+    fn validate_fibonacci_trace(
+        trace_values: &mut dyn FnMut(&mut Vec<FE>),
+    ) -> TraceValidationReport<F, F> {
+        let trace = fibonacci_trace([FE::from(1), FE::from(1)], 8);
+        let mut column = trace.columns()[0].clone();
+        trace_values(&mut column);
+        let trace = TraceTable::from_columns(vec![column], 1, 1);
+
+        let pub_inputs = FibonacciPublicInputs {
+            a0: FE::one(),
+            a1: FE::one(),
+        };
+        let air = FibonacciAIR::new(8, &pub_inputs, &ProofOptions::default_test_options()).unwrap();
+        let domain = Domain::new(&air);
+        let trace_polys = trace.compute_trace_polys::<F>();
+
+        validate_trace(&air, &trace_polys, &[], &domain, &[])
+    }
+
+    #[test]
+    fn valid_trace_has_an_empty_report() {
+        let report = validate_fibonacci_trace(&mut |_| {});
+        assert!(report.is_valid());
+        assert_eq!(
+            report.to_string(),
+            "the trace satisfies all the constraints"
+        );
+    }
+
+    #[test]
+    fn report_has_the_violated_transitions_and_their_frames() {
+        let report = validate_fibonacci_trace(&mut |column| column[4] = FE::from(0));
+
+        assert!(!report.is_valid());
+        assert!(report.boundary_failures.is_empty());
+        assert_eq!(report.failing_transition_constraints(), vec![0]);
+
+        // Row 4 is read by the frames of steps 2, 3 and 4.
+        let steps: Vec<usize> = report
+            .transition_failures
+            .iter()
+            .map(|failure| failure.step)
+            .collect();
+        assert_eq!(steps, vec![2, 3, 4]);
+
+        let failure = &report.transition_failures[0];
+        let rows: Vec<usize> = failure.frame.iter().map(|row| row.row).collect();
+        assert_eq!(rows, vec![2, 3, 4]);
+        assert_eq!(failure.frame[2].main, vec![FE::from(0)]);
+        assert_eq!(failure.evaluation, FE::from(0) - FE::from(3) - FE::from(2));
+    }
+
+    #[test]
+    fn report_has_the_violated_boundary_constraints() {
+        let report = validate_fibonacci_trace(&mut |column| column[1] = FE::from(5));
+
+        assert_eq!(report.boundary_failures.len(), 1);
+        let failure = &report.boundary_failures[0];
+        assert_eq!(
+            (failure.constraint_idx, failure.step, failure.col),
+            (1, 1, 0)
+        );
+        assert!(!failure.is_aux);
+        assert_eq!(failure.expected, FE::one());
+        assert_eq!(failure.found, FE::from(5));
+    }
+
+    #[test]
+    fn pretty_printed_report_renders_the_failing_window() {
+        let report = validate_fibonacci_trace(&mut |column| column[4] = FE::from(0));
+        let rendered = report.to_string();
+
+        assert!(rendered.starts_with("0 boundary and 3 transition constraint failures"));
+        assert!(
+            rendered.contains("transition constraint 0 failed at step 2: evaluated to 0x77fffffc")
+        );
+        assert!(rendered.contains("    row        4 | main: [0x0]"));
+    }
+}
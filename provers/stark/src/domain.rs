@@ -17,6 +17,23 @@ pub struct Domain<F: IsFFTField> {
 
 impl<F: IsFFTField> Domain<F> {
     pub fn new<A>(air: &A) -> Self
+    where
+        A: AIR<Field = F>,
+    {
+        let mut domain = Self::without_lde_roots(air);
+        let lde_root_order = (air.trace_length() * domain.blowup_factor).trailing_zeros();
+        domain.lde_roots_of_unity_coset = get_powers_of_primitive_root_coset(
+            lde_root_order as u64,
+            air.trace_length() * domain.blowup_factor,
+            &domain.coset_offset,
+        )
+        .unwrap();
+        domain
+    }
+
+    /// Builds the domain of `air` leaving `lde_roots_of_unity_coset` empty, for the provers
+    /// that compute the points of the LDE domain one coset of the trace domain at a time.
+    pub(crate) fn without_lde_roots<A>(air: &A) -> Self
     where
         A: AIR<Field = F>,
     {
@@ -34,17 +51,9 @@ impl<F: IsFFTField> Domain<F> {
         )
         .unwrap();
 
-        let lde_root_order = (air.trace_length() * blowup_factor).trailing_zeros();
-        let lde_roots_of_unity_coset = get_powers_of_primitive_root_coset(
-            lde_root_order as u64,
-            air.trace_length() * blowup_factor,
-            &coset_offset,
-        )
-        .unwrap();
-
         Self {
             root_order,
-            lde_roots_of_unity_coset,
+            lde_roots_of_unity_coset: Vec::new(),
             trace_primitive_root,
             trace_roots_of_unity,
             blowup_factor,
@@ -0,0 +1,176 @@
+use crate::{
+    constraints::{
+        boundary::{BoundaryConstraint, BoundaryConstraints},
+        transition::TransitionConstraint,
+    },
+    context::AirContext,
+    frame::Frame,
+    proof::options::ProofOptions,
+    prover::ProvingError,
+    trace::TraceTable,
+    traits::AIR,
+};
+use lambdaworks_math::field::{
+    element::FieldElement,
+    fields::fft_friendly::{
+        babybear::Babybear31PrimeField, quartic_babybear::QuarticBabybearField,
+    },
+};
+
+type F = Babybear31PrimeField;
+type E = QuarticBabybearField;
+
+/// The Fibonacci transition `a₂ - a₁ - a₀` over BabyBear. The main trace takes values in the
+/// base field and the result is lifted to the quartic extension, where the challenges live.
+#[derive(Clone)]
+struct BabybearFibConstraint;
+
+impl TransitionConstraint<F, E> for BabybearFibConstraint {
+    fn degree(&self) -> usize {
+        1
+    }
+
+    fn constraint_idx(&self) -> usize {
+        0
+    }
+
+    fn end_exemptions(&self) -> usize {
+        2
+    }
+
+    fn evaluate(
+        &self,
+        frame: &Frame<F, E>,
+        transition_evaluations: &mut [FieldElement<E>],
+        _periodic_values: &[FieldElement<F>],
+        _rap_challenges: &[FieldElement<E>],
+    ) {
+        let first_step = frame.get_evaluation_step(0);
+        let second_step = frame.get_evaluation_step(1);
+        let third_step = frame.get_evaluation_step(2);
+
+        let a0 = first_step.get_main_evaluation_element(0, 0);
+        let a1 = second_step.get_main_evaluation_element(0, 0);
+        let a2 = third_step.get_main_evaluation_element(0, 0);
+
+        let res = a2 - a1 - a0;
+
+        transition_evaluations[self.constraint_idx()] = res.to_extension();
+    }
+}
+
+/// A Fibonacci AIR whose trace is over BabyBear and whose challenges are sampled from its
+/// quartic extension, so that the 31 bits of the base field do not bound the soundness.
+pub struct BabybearFibonacciAIR {
+    context: AirContext,
+    trace_length: usize,
+    pub_inputs: BabybearFibonacciPublicInputs,
+    constraints: Vec<Box<dyn TransitionConstraint<F, E>>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct BabybearFibonacciPublicInputs {
+    pub a0: FieldElement<F>,
+    pub a1: FieldElement<F>,
+}
+
+impl AIR for BabybearFibonacciAIR {
+    type Field = F;
+    type FieldExtension = E;
+    type PublicInputs = BabybearFibonacciPublicInputs;
+
+    const STEP_SIZE: usize = 1;
+
+    fn new(
+        trace_length: usize,
+        pub_inputs: &Self::PublicInputs,
+        proof_options: &ProofOptions,
+    ) -> Result<Self, ProvingError> {
+        let constraints: Vec<Box<dyn TransitionConstraint<F, E>>> =
+            vec![Box::new(BabybearFibConstraint)];
+
+        let context = AirContext {
+            proof_options: proof_options.clone(),
+            trace_columns: 1,
+            transition_exemptions: vec![2],
+            transition_offsets: vec![0, 1, 2],
+            num_transition_constraints: constraints.len(),
+        };
+
+        Ok(Self {
+            pub_inputs: pub_inputs.clone(),
+            context,
+            trace_length,
+            constraints,
+        })
+    }
+
+    fn composition_poly_degree_bound(&self) -> usize {
+        self.trace_length()
+    }
+
+    fn transition_constraints(&self) -> &Vec<Box<dyn TransitionConstraint<F, E>>> {
+        &self.constraints
+    }
+
+    fn boundary_constraints(
+        &self,
+        _rap_challenges: &[FieldElement<Self::FieldExtension>],
+    ) -> BoundaryConstraints<Self::FieldExtension> {
+        let a0 = BoundaryConstraint::new_simple_main(0, self.pub_inputs.a0.clone().to_extension());
+        let a1 = BoundaryConstraint::new_simple_main(1, self.pub_inputs.a1.clone().to_extension());
+
+        BoundaryConstraints::from_constraints(vec![a0, a1])
+    }
+
+    fn context(&self) -> &AirContext {
+        &self.context
+    }
+
+    fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    fn trace_layout(&self) -> (usize, usize) {
+        (1, 0)
+    }
+
+    fn pub_inputs(&self) -> &Self::PublicInputs {
+        &self.pub_inputs
+    }
+
+    fn compute_transition_verifier(
+        &self,
+        frame: &Frame<Self::FieldExtension, Self::FieldExtension>,
+        _periodic_values: &[FieldElement<Self::FieldExtension>],
+        _rap_challenges: &[FieldElement<Self::FieldExtension>],
+    ) -> Vec<FieldElement<Self::FieldExtension>> {
+        let a0 = frame
+            .get_evaluation_step(0)
+            .get_main_evaluation_element(0, 0);
+        let a1 = frame
+            .get_evaluation_step(1)
+            .get_main_evaluation_element(0, 0);
+        let a2 = frame
+            .get_evaluation_step(2)
+            .get_main_evaluation_element(0, 0);
+
+        vec![a2 - a1 - a0]
+    }
+}
+
+pub fn babybear_fibonacci_trace(
+    initial_values: [FieldElement<F>; 2],
+    trace_length: usize,
+) -> TraceTable<F> {
+    let mut ret: Vec<FieldElement<F>> = vec![];
+
+    ret.push(initial_values[0].clone());
+    ret.push(initial_values[1].clone());
+
+    for i in 2..(trace_length) {
+        ret.push(ret[i - 1].clone() + ret[i - 2].clone());
+    }
+
+    TraceTable::from_columns(vec![ret], 1, 1)
+}
@@ -3,6 +3,7 @@ use crate::{
     context::AirContext,
     frame::Frame,
     proof::options::ProofOptions,
+    prover::ProvingError,
     trace::TraceTable,
     traits::AIR,
     Felt252,
@@ -125,7 +126,7 @@ impl AIR for BitFlagsAIR {
         trace_length: usize,
         _pub_inputs: &Self::PublicInputs,
         proof_options: &ProofOptions,
-    ) -> Self {
+    ) -> Result<Self, ProvingError> {
         let bit_constraint = Box::new(BitConstraint::new());
         let flag_constraint = Box::new(ZeroFlagConstraint::new());
         let constraints: Vec<Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>> =
@@ -145,11 +146,11 @@ impl AIR for BitFlagsAIR {
             num_transition_constraints,
         };
 
-        Self {
+        Ok(Self {
             context,
             trace_length,
             constraints,
-        }
+        })
     }
 
     fn transition_constraints(
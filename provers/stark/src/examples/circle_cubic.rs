@@ -0,0 +1,103 @@
+use crate::{
+    circle::{air::CircleAIR, M31},
+    constraints::boundary::{BoundaryConstraint, BoundaryConstraints},
+    proof::options::ProofOptions,
+    trace::TraceTable,
+};
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsField, IsSubFieldOf},
+};
+
+/// Repeated cubing over Mersenne31, proven with the circle STARK. Its transition constraint has
+/// degree 3, so the composition polynomial is split in four parts.
+pub struct CircleCubicAIR {
+    options: ProofOptions,
+    trace_length: usize,
+    pub_inputs: CircleCubicPublicInputs,
+}
+
+#[derive(Clone, Debug)]
+pub struct CircleCubicPublicInputs {
+    pub a0: FieldElement<M31>,
+}
+
+impl CircleAIR for CircleCubicAIR {
+    type PublicInputs = CircleCubicPublicInputs;
+
+    fn new(
+        trace_length: usize,
+        pub_inputs: &Self::PublicInputs,
+        proof_options: &ProofOptions,
+    ) -> Self {
+        Self {
+            options: proof_options.clone(),
+            trace_length,
+            pub_inputs: pub_inputs.clone(),
+        }
+    }
+
+    fn compute_transition<F>(&self, frame: &[Vec<FieldElement<F>>]) -> Vec<FieldElement<F>>
+    where
+        F: IsField,
+        M31: IsSubFieldOf<F>,
+    {
+        let x = &frame[0][0];
+        let x_next = &frame[1][0];
+
+        vec![x_next - x * x * x]
+    }
+
+    fn boundary_constraints(&self) -> BoundaryConstraints<M31> {
+        let a0 = BoundaryConstraint::new_simple_main(0, self.pub_inputs.a0);
+
+        BoundaryConstraints::from_constraints(vec![a0])
+    }
+
+    fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    fn trace_columns(&self) -> usize {
+        1
+    }
+
+    fn transition_offsets(&self) -> Vec<usize> {
+        vec![0, 1]
+    }
+
+    fn num_transition_constraints(&self) -> usize {
+        1
+    }
+
+    fn transition_degree(&self) -> usize {
+        3
+    }
+
+    fn transition_exemptions(&self) -> usize {
+        1
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn pub_inputs(&self) -> &Self::PublicInputs {
+        &self.pub_inputs
+    }
+}
+
+pub fn circle_cubic_trace(
+    initial_value: FieldElement<M31>,
+    trace_length: usize,
+) -> TraceTable<M31> {
+    let mut ret: Vec<FieldElement<M31>> = vec![];
+
+    ret.push(initial_value);
+
+    for i in 1..(trace_length) {
+        ret.push(ret[i - 1] * ret[i - 1] * ret[i - 1]);
+    }
+
+    TraceTable::from_columns(vec![ret], 1, 1)
+}
@@ -0,0 +1,106 @@
+use crate::{
+    circle::{air::CircleAIR, M31},
+    constraints::boundary::{BoundaryConstraint, BoundaryConstraints},
+    proof::options::ProofOptions,
+    trace::TraceTable,
+};
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsField, IsSubFieldOf},
+};
+
+/// The Fibonacci sequence over Mersenne31, proven with the circle STARK.
+pub struct CircleFibonacciAIR {
+    options: ProofOptions,
+    trace_length: usize,
+    pub_inputs: CircleFibonacciPublicInputs,
+}
+
+#[derive(Clone, Debug)]
+pub struct CircleFibonacciPublicInputs {
+    pub a0: FieldElement<M31>,
+    pub a1: FieldElement<M31>,
+}
+
+impl CircleAIR for CircleFibonacciAIR {
+    type PublicInputs = CircleFibonacciPublicInputs;
+
+    fn new(
+        trace_length: usize,
+        pub_inputs: &Self::PublicInputs,
+        proof_options: &ProofOptions,
+    ) -> Self {
+        Self {
+            options: proof_options.clone(),
+            trace_length,
+            pub_inputs: pub_inputs.clone(),
+        }
+    }
+
+    fn compute_transition<F>(&self, frame: &[Vec<FieldElement<F>>]) -> Vec<FieldElement<F>>
+    where
+        F: IsField,
+        M31: IsSubFieldOf<F>,
+    {
+        let a0 = &frame[0][0];
+        let a1 = &frame[1][0];
+        let a2 = &frame[2][0];
+
+        vec![a2 - a1 - a0]
+    }
+
+    fn boundary_constraints(&self) -> BoundaryConstraints<M31> {
+        let a0 = BoundaryConstraint::new_simple_main(0, self.pub_inputs.a0);
+        let a1 = BoundaryConstraint::new_simple_main(1, self.pub_inputs.a1);
+
+        BoundaryConstraints::from_constraints(vec![a0, a1])
+    }
+
+    fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    fn trace_columns(&self) -> usize {
+        1
+    }
+
+    fn transition_offsets(&self) -> Vec<usize> {
+        vec![0, 1, 2]
+    }
+
+    fn num_transition_constraints(&self) -> usize {
+        1
+    }
+
+    fn transition_degree(&self) -> usize {
+        1
+    }
+
+    fn transition_exemptions(&self) -> usize {
+        2
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn pub_inputs(&self) -> &Self::PublicInputs {
+        &self.pub_inputs
+    }
+}
+
+pub fn circle_fibonacci_trace(
+    initial_values: [FieldElement<M31>; 2],
+    trace_length: usize,
+) -> TraceTable<M31> {
+    let mut ret: Vec<FieldElement<M31>> = vec![];
+
+    ret.push(initial_values[0]);
+    ret.push(initial_values[1]);
+
+    for i in 2..(trace_length) {
+        ret.push(ret[i - 1] + ret[i - 2]);
+    }
+
+    TraceTable::from_columns(vec![ret], 1, 1)
+}
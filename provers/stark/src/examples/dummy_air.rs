@@ -8,6 +8,7 @@ use crate::{
     context::AirContext,
     frame::Frame,
     proof::options::ProofOptions,
+    prover::ProvingError,
     trace::TraceTable,
     traits::AIR,
 };
@@ -129,7 +130,7 @@ impl AIR for DummyAIR {
         trace_length: usize,
         _pub_inputs: &Self::PublicInputs,
         proof_options: &ProofOptions,
-    ) -> Self {
+    ) -> Result<Self, ProvingError> {
         let transition_constraints: Vec<
             Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>,
         > = vec![
@@ -145,11 +146,11 @@ impl AIR for DummyAIR {
             num_transition_constraints: 2,
         };
 
-        Self {
+        Ok(Self {
             context,
             trace_length,
             transition_constraints,
-        }
+        })
     }
 
     fn boundary_constraints(
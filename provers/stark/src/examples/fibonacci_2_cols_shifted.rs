@@ -6,6 +6,7 @@ use crate::{
     context::AirContext,
     frame::Frame,
     proof::options::ProofOptions,
+    prover::ProvingError,
     trace::TraceTable,
     traits::AIR,
 };
@@ -161,7 +162,7 @@ where
         trace_length: usize,
         pub_inputs: &Self::PublicInputs,
         proof_options: &ProofOptions,
-    ) -> Self {
+    ) -> Result<Self, ProvingError> {
         let transition_constraints: Vec<
             Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>,
         > = vec![
@@ -177,12 +178,12 @@ where
             trace_columns: 2,
         };
 
-        Self {
+        Ok(Self {
             trace_length,
             context,
             pub_inputs: pub_inputs.clone(),
             transition_constraints,
-        }
+        })
     }
 
     fn boundary_constraints(
@@ -9,6 +9,7 @@ use crate::{
     context::AirContext,
     frame::Frame,
     proof::options::ProofOptions,
+    prover::ProvingError,
     trace::TraceTable,
     traits::AIR,
 };
@@ -140,7 +141,7 @@ where
         trace_length: usize,
         pub_inputs: &Self::PublicInputs,
         proof_options: &ProofOptions,
-    ) -> Self {
+    ) -> Result<Self, ProvingError> {
         let constraints: Vec<Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>> = vec![
             Box::new(FibTransition1::new()),
             Box::new(FibTransition2::new()),
@@ -154,12 +155,12 @@ where
             trace_columns: 2,
         };
 
-        Self {
+        Ok(Self {
             trace_length,
             context,
             constraints,
             pub_inputs: pub_inputs.clone(),
-        }
+        })
     }
 
     fn boundary_constraints(
@@ -8,6 +8,7 @@ use crate::{
     context::AirContext,
     frame::Frame,
     proof::options::ProofOptions,
+    prover::ProvingError,
     trace::TraceTable,
     traits::AIR,
 };
@@ -158,7 +159,7 @@ where
         trace_length: usize,
         pub_inputs: &Self::PublicInputs,
         proof_options: &ProofOptions,
-    ) -> Self {
+    ) -> Result<Self, ProvingError> {
         let transition_constraints: Vec<
             Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>,
         > = vec![
@@ -176,12 +177,12 @@ where
             num_transition_constraints: transition_constraints.len(),
         };
 
-        Self {
+        Ok(Self {
             context,
             trace_length,
             pub_inputs: pub_inputs.clone(),
             transition_constraints,
-        }
+        })
     }
 
     fn build_auxiliary_trace(
@@ -1,8 +1,13 @@
+pub mod babybear_fibonacci;
 pub mod bit_flags;
+pub mod circle_cubic;
+pub mod circle_fibonacci;
 pub mod dummy_air;
 pub mod fibonacci_2_cols_shifted;
 pub mod fibonacci_2_columns;
 pub mod fibonacci_rap;
 pub mod quadratic_air;
+pub mod range_check_logup;
+pub mod round_constants;
 pub mod simple_fibonacci;
 pub mod simple_periodic_cols;
@@ -8,6 +8,7 @@ use crate::{
     context::AirContext,
     frame::Frame,
     proof::options::ProofOptions,
+    prover::ProvingError,
     trace::TraceTable,
     traits::AIR,
 };
@@ -93,7 +94,7 @@ where
         trace_length: usize,
         pub_inputs: &Self::PublicInputs,
         proof_options: &ProofOptions,
-    ) -> Self {
+    ) -> Result<Self, ProvingError> {
         let constraints: Vec<Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>> =
             vec![Box::new(QuadraticConstraint::new())];
 
@@ -105,12 +106,12 @@ where
             num_transition_constraints: constraints.len(),
         };
 
-        Self {
+        Ok(Self {
             trace_length,
             context,
             pub_inputs: pub_inputs.clone(),
             constraints,
-        }
+        })
     }
 
     fn boundary_constraints(
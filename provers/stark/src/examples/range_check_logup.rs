@@ -0,0 +1,209 @@
+use std::marker::PhantomData;
+
+use crate::{
+    constraints::{
+        boundary::{BoundaryConstraint, BoundaryConstraints},
+        transition::TransitionConstraint,
+    },
+    context::AirContext,
+    frame::Frame,
+    lookup::{LogUpLookup, LOGUP_TRANSITION_OFFSETS},
+    proof::options::ProofOptions,
+    prover::ProvingError,
+    trace::TraceTable,
+    traits::AIR,
+};
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::field::{element::FieldElement, traits::IsFFTField};
+
+/// Constraint enforcing that the table column is the sequence 0, 1, 2, ...
+#[derive(Clone)]
+struct TableConstraint<F: IsFFTField> {
+    phantom: PhantomData<F>,
+}
+
+impl<F: IsFFTField> TableConstraint<F> {
+    pub fn new() -> Self {
+        Self {
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<F> TransitionConstraint<F, F> for TableConstraint<F>
+where
+    F: IsFFTField + Send + Sync,
+{
+    fn degree(&self) -> usize {
+        1
+    }
+
+    fn constraint_idx(&self) -> usize {
+        0
+    }
+
+    fn end_exemptions(&self) -> usize {
+        1
+    }
+
+    fn evaluate(
+        &self,
+        frame: &Frame<F, F>,
+        transition_evaluations: &mut [FieldElement<F>],
+        _periodic_values: &[FieldElement<F>],
+        _rap_challenges: &[FieldElement<F>],
+    ) {
+        let first_step = frame.get_evaluation_step(0);
+        let second_step = frame.get_evaluation_step(1);
+
+        let t0 = first_step.get_main_evaluation_element(0, 1);
+        let t1 = second_step.get_main_evaluation_element(0, 1);
+
+        let res = t1 - t0 - FieldElement::<F>::one();
+
+        transition_evaluations[self.constraint_idx()] = res;
+    }
+}
+
+/// An AIR proving that all the values of a column are in the range [0, trace_length),
+/// using a LogUp lookup argument into a table column holding 0, 1, ..., trace_length - 1.
+///
+/// The main trace has three columns:
+///   * C0: the values being range checked.
+///   * C1: the table.
+///   * C2: the number of times each table value appears in C0.
+pub struct RangeCheckLogUpAIR<F>
+where
+    F: IsFFTField,
+{
+    context: AirContext,
+    trace_length: usize,
+    logup: LogUpLookup,
+    transition_constraints: Vec<Box<dyn TransitionConstraint<F, F>>>,
+}
+
+impl<F> AIR for RangeCheckLogUpAIR<F>
+where
+    F: IsFFTField + Send + Sync + 'static,
+{
+    type Field = F;
+    type FieldExtension = F;
+    type PublicInputs = ();
+
+    const STEP_SIZE: usize = 1;
+
+    fn new(
+        trace_length: usize,
+        _pub_inputs: &Self::PublicInputs,
+        proof_options: &ProofOptions,
+    ) -> Result<Self, ProvingError> {
+        let logup = LogUpLookup::new(vec![vec![0]], vec![1], 2).with_offsets(0, 0, 1);
+
+        let mut transition_constraints: Vec<
+            Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>,
+        > = vec![Box::new(TableConstraint::new())];
+        transition_constraints.extend(logup.transition_constraints());
+
+        let mut transition_exemptions = vec![1];
+        transition_exemptions.extend(vec![0; logup.num_transition_constraints()]);
+
+        let context = AirContext {
+            proof_options: proof_options.clone(),
+            trace_columns: 3 + logup.num_auxiliary_columns(),
+            transition_offsets: LOGUP_TRANSITION_OFFSETS.to_vec(),
+            transition_exemptions,
+            num_transition_constraints: transition_constraints.len(),
+        };
+
+        Ok(Self {
+            context,
+            trace_length,
+            logup,
+            transition_constraints,
+        })
+    }
+
+    fn build_auxiliary_trace(
+        &self,
+        main_trace: &TraceTable<Self::Field>,
+        challenges: &[FieldElement<Self::FieldExtension>],
+    ) -> TraceTable<Self::FieldExtension> {
+        let aux_columns = self.logup.build_auxiliary_columns(main_trace, challenges);
+        TraceTable::from_columns(aux_columns, 0, 1)
+    }
+
+    fn build_rap_challenges(
+        &self,
+        transcript: &mut impl IsTranscript<Self::FieldExtension>,
+    ) -> Vec<FieldElement<Self::FieldExtension>> {
+        self.logup.build_rap_challenges(transcript)
+    }
+
+    fn trace_layout(&self) -> (usize, usize) {
+        (3, self.logup.num_auxiliary_columns())
+    }
+
+    fn boundary_constraints(
+        &self,
+        _rap_challenges: &[FieldElement<Self::FieldExtension>],
+    ) -> BoundaryConstraints<Self::FieldExtension> {
+        let mut constraints = vec![BoundaryConstraint::new_main(1, 0, FieldElement::zero())];
+        constraints.extend(self.logup.boundary_constraints());
+
+        BoundaryConstraints::from_constraints(constraints)
+    }
+
+    fn transition_constraints(
+        &self,
+    ) -> &Vec<Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>> {
+        &self.transition_constraints
+    }
+
+    fn context(&self) -> &AirContext {
+        &self.context
+    }
+
+    fn composition_poly_degree_bound(&self) -> usize {
+        2 * self.trace_length()
+    }
+
+    fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    fn pub_inputs(&self) -> &Self::PublicInputs {
+        &()
+    }
+
+    fn compute_transition_verifier(
+        &self,
+        frame: &Frame<Self::FieldExtension, Self::FieldExtension>,
+        periodic_values: &[FieldElement<Self::FieldExtension>],
+        rap_challenges: &[FieldElement<Self::FieldExtension>],
+    ) -> Vec<FieldElement<Self::Field>> {
+        self.compute_transition_prover(frame, periodic_values, rap_challenges)
+    }
+}
+
+/// Builds the main trace of `RangeCheckLogUpAIR` for the given values. The trace length
+/// is the number of values, which must be a power of two.
+pub fn range_check_logup_trace<F: IsFFTField>(values: &[u64]) -> TraceTable<F> {
+    let trace_length = values.len();
+    debug_assert!(trace_length.is_power_of_two());
+
+    let mut multiplicities = vec![0u64; trace_length];
+    for value in values {
+        multiplicities[*value as usize] += 1;
+    }
+
+    let values = values.iter().map(|v| FieldElement::<F>::from(*v)).collect();
+    let table = (0..trace_length as u64)
+        .map(FieldElement::<F>::from)
+        .collect();
+    let multiplicities = multiplicities
+        .into_iter()
+        .map(FieldElement::<F>::from)
+        .collect();
+
+    TraceTable::from_columns(vec![values, table, multiplicities], 3, 1)
+}
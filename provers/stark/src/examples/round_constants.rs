@@ -0,0 +1,196 @@
+use std::marker::PhantomData;
+
+use crate::{
+    constraints::{
+        boundary::{BoundaryConstraint, BoundaryConstraints},
+        transition::TransitionConstraint,
+    },
+    context::AirContext,
+    frame::Frame,
+    proof::options::ProofOptions,
+    prover::ProvingError,
+    trace::TraceTable,
+    traits::AIR,
+};
+use lambdaworks_math::field::{element::FieldElement, traits::IsFFTField};
+
+/// The transition `aᵢ₊₁ = aᵢ² + kᵢ`, where `kᵢ` is the value of the preprocessed column of round
+/// constants, which is read as the main column next to `a`.
+#[derive(Clone)]
+struct RoundConstantsConstraint<F: IsFFTField> {
+    phantom: PhantomData<F>,
+}
+
+impl<F: IsFFTField> RoundConstantsConstraint<F> {
+    pub fn new() -> Self {
+        Self {
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<F> TransitionConstraint<F, F> for RoundConstantsConstraint<F>
+where
+    F: IsFFTField + Send + Sync,
+{
+    fn degree(&self) -> usize {
+        2
+    }
+
+    fn constraint_idx(&self) -> usize {
+        0
+    }
+
+    fn end_exemptions(&self) -> usize {
+        1
+    }
+
+    fn evaluate(
+        &self,
+        frame: &Frame<F, F>,
+        transition_evaluations: &mut [FieldElement<F>],
+        _periodic_values: &[FieldElement<F>],
+        _rap_challenges: &[FieldElement<F>],
+    ) {
+        let first_step = frame.get_evaluation_step(0);
+        let second_step = frame.get_evaluation_step(1);
+
+        let a = first_step.get_main_evaluation_element(0, 0);
+        let k = first_step.get_main_evaluation_element(0, 1);
+        let a_next = second_step.get_main_evaluation_element(0, 0);
+
+        let res = a_next - a * a - k;
+
+        transition_evaluations[self.constraint_idx()] = res;
+    }
+}
+
+/// An AIR iterating `x ↦ x² + kᵢ` with a fixed table of round constants `kᵢ`. The round
+/// constants are a preprocessed column, committed once at setup instead of in every proof.
+pub struct RoundConstantsAIR<F>
+where
+    F: IsFFTField,
+{
+    context: AirContext,
+    trace_length: usize,
+    pub_inputs: RoundConstantsPublicInputs<F>,
+    constraints: Vec<Box<dyn TransitionConstraint<F, F>>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct RoundConstantsPublicInputs<F>
+where
+    F: IsFFTField,
+{
+    pub a0: FieldElement<F>,
+}
+
+/// Returns the round constant of the `i`-th step.
+pub fn round_constant<F: IsFFTField>(i: usize) -> FieldElement<F> {
+    let i = i as u64;
+    FieldElement::from(i * i + 7 * i + 3)
+}
+
+impl<F> AIR for RoundConstantsAIR<F>
+where
+    F: IsFFTField + Send + Sync + 'static,
+{
+    type Field = F;
+    type FieldExtension = F;
+    type PublicInputs = RoundConstantsPublicInputs<Self::Field>;
+
+    const STEP_SIZE: usize = 1;
+
+    fn new(
+        trace_length: usize,
+        pub_inputs: &Self::PublicInputs,
+        proof_options: &ProofOptions,
+    ) -> Result<Self, ProvingError> {
+        let constraints: Vec<Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>> =
+            vec![Box::new(RoundConstantsConstraint::new())];
+
+        let context = AirContext {
+            proof_options: proof_options.clone(),
+            trace_columns: 1,
+            transition_exemptions: vec![1],
+            transition_offsets: vec![0, 1],
+            num_transition_constraints: constraints.len(),
+        };
+
+        Ok(Self {
+            trace_length,
+            context,
+            pub_inputs: pub_inputs.clone(),
+            constraints,
+        })
+    }
+
+    fn boundary_constraints(
+        &self,
+        _rap_challenges: &[FieldElement<Self::Field>],
+    ) -> BoundaryConstraints<Self::Field> {
+        let a0 = BoundaryConstraint::new_simple_main(0, self.pub_inputs.a0.clone());
+
+        BoundaryConstraints::from_constraints(vec![a0])
+    }
+
+    fn transition_constraints(
+        &self,
+    ) -> &Vec<Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>> {
+        &self.constraints
+    }
+
+    fn context(&self) -> &AirContext {
+        &self.context
+    }
+
+    fn composition_poly_degree_bound(&self) -> usize {
+        2 * self.trace_length()
+    }
+
+    fn trace_layout(&self) -> (usize, usize) {
+        (1, 0)
+    }
+
+    fn num_preprocessed_columns(&self) -> usize {
+        1
+    }
+
+    fn preprocessed_columns(&self) -> Vec<Vec<FieldElement<Self::Field>>> {
+        vec![(0..self.trace_length).map(round_constant).collect()]
+    }
+
+    fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    fn pub_inputs(&self) -> &Self::PublicInputs {
+        &self.pub_inputs
+    }
+
+    fn compute_transition_verifier(
+        &self,
+        frame: &Frame<Self::FieldExtension, Self::FieldExtension>,
+        periodic_values: &[FieldElement<Self::FieldExtension>],
+        rap_challenges: &[FieldElement<Self::FieldExtension>],
+    ) -> Vec<FieldElement<Self::Field>> {
+        self.compute_transition_prover(frame, periodic_values, rap_challenges)
+    }
+}
+
+/// Returns the main trace of `RoundConstantsAIR`. It doesn't include the round constants, which
+/// are preprocessed.
+pub fn round_constants_trace<F: IsFFTField>(
+    initial_value: FieldElement<F>,
+    trace_length: usize,
+) -> TraceTable<F> {
+    let mut ret: Vec<FieldElement<F>> = vec![];
+
+    ret.push(initial_value);
+
+    for i in 1..(trace_length) {
+        ret.push(ret[i - 1].clone() * ret[i - 1].clone() + round_constant(i - 1));
+    }
+
+    TraceTable::from_columns(vec![ret], 1, 1)
+}
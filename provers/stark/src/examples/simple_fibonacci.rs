@@ -6,6 +6,7 @@ use crate::{
     context::AirContext,
     frame::Frame,
     proof::options::ProofOptions,
+    prover::ProvingError,
     trace::TraceTable,
     traits::AIR,
 };
@@ -95,7 +96,7 @@ where
         trace_length: usize,
         pub_inputs: &Self::PublicInputs,
         proof_options: &ProofOptions,
-    ) -> Self {
+    ) -> Result<Self, ProvingError> {
         let constraints: Vec<Box<dyn TransitionConstraint<F, F>>> =
             vec![Box::new(FibConstraint::new())];
 
@@ -107,12 +108,12 @@ where
             num_transition_constraints: constraints.len(),
         };
 
-        Self {
+        Ok(Self {
             pub_inputs: pub_inputs.clone(),
             context,
             trace_length,
             constraints,
-        }
+        })
     }
 
     fn composition_poly_degree_bound(&self) -> usize {
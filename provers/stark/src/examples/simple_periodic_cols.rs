@@ -8,6 +8,7 @@ use crate::{
     context::AirContext,
     frame::Frame,
     proof::options::ProofOptions,
+    prover::ProvingError,
     trace::TraceTable,
     traits::AIR,
 };
@@ -113,7 +114,7 @@ where
         trace_length: usize,
         pub_inputs: &Self::PublicInputs,
         proof_options: &ProofOptions,
-    ) -> Self {
+    ) -> Result<Self, ProvingError> {
         let transition_constraints: Vec<
             Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>,
         > = vec![Box::new(PeriodicConstraint::new())];
@@ -126,12 +127,12 @@ where
             num_transition_constraints: transition_constraints.len(),
         };
 
-        Self {
+        Ok(Self {
             pub_inputs: pub_inputs.clone(),
             context,
             trace_length,
             transition_constraints,
-        }
+        })
     }
 
     fn composition_poly_degree_bound(&self) -> usize {
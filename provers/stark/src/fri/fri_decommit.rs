@@ -7,5 +7,7 @@ use crate::config::Commitment;
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct FriDecommitment<F: IsField> {
     pub layers_auth_paths: Vec<Proof<Commitment>>,
-    pub layers_evaluations_sym: Vec<FieldElement<F>>,
+    /// For each layer, the evaluations of the coset of the query, without the evaluation at
+    /// the query, which the verifier computes from the previous layer.
+    pub layers_evaluations_coset: Vec<Vec<FieldElement<F>>>,
 }
@@ -2,6 +2,8 @@ pub mod fri_commitment;
 pub mod fri_decommit;
 mod fri_functions;
 
+use std::ops::Range;
+
 use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
 use lambdaworks_math::field::traits::{IsFFTField, IsField};
 use lambdaworks_math::traits::AsBytes;
@@ -13,123 +15,195 @@ pub use lambdaworks_math::{
     polynomial::Polynomial,
 };
 
-use crate::config::{BatchedMerkleTree, BatchedMerkleTreeBackend};
+use crate::config::{BatchedMerkleTree, BatchedMerkleTreeBackend, Commitment};
+use crate::traits::AIR;
 
 use self::fri_commitment::FriLayer;
 use self::fri_decommit::FriDecommitment;
 use self::fri_functions::fold_polynomial;
 
+/// The coefficients of the last layer of FRI and the committed layers.
+pub type FriCommitPhaseOutput<E> = (
+    Vec<FieldElement<E>>,
+    Vec<FriLayer<E, BatchedMerkleTreeBackend<E>>>,
+);
+
+/// Returns the number of times each layer of FRI folds the DEEP composition polynomial of `air`
+/// in two, or `None` if its proof options don't fold it down to the degree bound of the last layer.
+pub fn fri_folding_steps<A: AIR>(air: &A) -> Option<Vec<usize>> {
+    // The masked trace polynomials and the random part have degree up to twice the trace
+    // length, so the DEEP composition polynomial needs one extra folding in zero-knowledge mode.
+    let log_degree_bound =
+        air.trace_length().trailing_zeros() as usize + usize::from(air.options().zero_knowledge);
+    air.options().fri_folding_steps(log_degree_bound)
+}
+
+/// Returns the indexes of the pairs of symmetric elements of the LDE domain whose evaluations
+/// are folded into the query `iota` when the first layer of FRI is folded `first_step` times.
+/// The pair `i` holds the elements of positions `2i` and `2i + 1` in bit-reversed order.
+pub fn first_layer_pair_indexes(iota: usize, first_step: usize) -> Range<usize> {
+    let pairs_per_coset = 1 << (first_step - 1);
+    iota * pairs_per_coset..(iota + 1) * pairs_per_coset
+}
+
+/// Runs the commit phase of FRI on `p_0`, folding it in two `fri_steps[k]` times to obtain the
+/// layer `k + 1`. The last layer has degree less than `last_layer_degree_bound` and its
+/// coefficients are sent instead of a commitment. The evaluations of the other layers are
+/// committed in cosets of the size the next step folds, so that each query opens one leaf.
 pub fn commit_phase<F: IsFFTField + IsSubFieldOf<E>, E: IsField>(
-    number_layers: usize,
+    fri_steps: &[usize],
+    last_layer_degree_bound: usize,
     p_0: Polynomial<FieldElement<E>>,
     transcript: &mut impl IsTranscript<E>,
     coset_offset: &FieldElement<F>,
     domain_size: usize,
-) -> (
-    FieldElement<E>,
-    Vec<FriLayer<E, BatchedMerkleTreeBackend<E>>>,
-)
+) -> FriCommitPhaseOutput<E>
 where
     FieldElement<F>: AsBytes + Sync + Send,
     FieldElement<E>: AsBytes + Sync + Send,
+{
+    commit_phase_with(
+        fri_steps,
+        last_layer_degree_bound,
+        p_0,
+        transcript,
+        coset_offset,
+        domain_size,
+        |poly, coset_offset, domain_size, coset_size| {
+            let layer = new_fri_layer(poly, coset_offset, domain_size, coset_size);
+            let root = layer.merkle_tree.root;
+            (layer, root)
+        },
+    )
+}
+
+/// Runs the commit phase of FRI as `commit_phase` does, committing to each layer with
+/// `new_layer`. It receives the polynomial of the layer, the offset and size of its domain and
+/// the number of evaluations in each leaf, and returns the layer and its commitment.
+pub(crate) fn commit_phase_with<F: IsFFTField + IsSubFieldOf<E>, E: IsField, L>(
+    fri_steps: &[usize],
+    last_layer_degree_bound: usize,
+    p_0: Polynomial<FieldElement<E>>,
+    transcript: &mut impl IsTranscript<E>,
+    coset_offset: &FieldElement<F>,
+    domain_size: usize,
+    mut new_layer: impl FnMut(
+        &Polynomial<FieldElement<E>>,
+        &FieldElement<F>,
+        usize,
+        usize,
+    ) -> (L, Commitment),
+) -> (Vec<FieldElement<E>>, Vec<L>)
+where
+    FieldElement<E>: AsBytes,
 {
     let mut domain_size = domain_size;
 
-    let mut fri_layer_list = Vec::with_capacity(number_layers);
-    let mut current_layer: FriLayer<E, BatchedMerkleTreeBackend<E>>;
+    let mut fri_layer_list = Vec::with_capacity(fri_steps.len().saturating_sub(1));
     let mut current_poly = p_0;
 
     let mut coset_offset = coset_offset.clone();
 
-    for _ in 1..number_layers {
-        // <<<< Receive challenge 𝜁ₖ₋₁
-        let zeta = transcript.sample_field_element();
-        coset_offset = coset_offset.square();
-        domain_size /= 2;
-
-        // Compute layer polynomial and domain
-        current_poly = FieldElement::<F>::from(2) * fold_polynomial(&current_poly, &zeta);
-        current_layer = new_fri_layer(&current_poly, &coset_offset, domain_size);
-        let new_data = &current_layer.merkle_tree.root;
-        fri_layer_list.push(current_layer.clone()); // TODO: remove this clone
-
-        // >>>> Send commitment: [pₖ]
-        transcript.append_bytes(new_data);
+    for (k, step) in fri_steps.iter().enumerate() {
+        // <<<< Receive challenge 𝜁ₖ
+        let mut zeta = transcript.sample_field_element();
+
+        // Compute layer polynomial and domain. Each folding in two uses the square of the
+        // challenge of the previous one.
+        for _ in 0..*step {
+            current_poly = FieldElement::<F>::from(2) * fold_polynomial(&current_poly, &zeta);
+            zeta = zeta.square();
+            coset_offset = coset_offset.square();
+            domain_size /= 2;
+        }
+
+        if let Some(next_step) = fri_steps.get(k + 1) {
+            let (current_layer, new_data) =
+                new_layer(&current_poly, &coset_offset, domain_size, 1 << next_step);
+
+            // >>>> Send commitment: [pₖ₊₁]
+            transcript.append_bytes(&new_data);
+            fri_layer_list.push(current_layer);
+        }
     }
 
-    // <<<< Receive challenge: 𝜁ₙ₋₁
-    let zeta = transcript.sample_field_element();
-
-    let last_poly = FieldElement::<F>::from(2) * fold_polynomial(&current_poly, &zeta);
-
-    let last_value = last_poly
-        .coefficients()
-        .first()
-        .unwrap_or(&FieldElement::zero())
-        .clone();
+    let mut last_layer_coefficients = current_poly.coefficients().to_vec();
+    last_layer_coefficients.resize(last_layer_degree_bound, FieldElement::zero());
 
-    // >>>> Send value: pₙ
-    transcript.append_field_element(&last_value);
+    // >>>> Send coefficients: pₙ
+    for coefficient in last_layer_coefficients.iter() {
+        transcript.append_field_element(coefficient);
+    }
 
-    (last_value, fri_layer_list)
+    (last_layer_coefficients, fri_layer_list)
 }
 
+/// Opens the layers of FRI at the queries `iotas`, which are indexes of the first layer after
+/// the DEEP composition polynomial. For each layer, the evaluations of the coset that the next
+/// step folds are sent, except the one the verifier computes from the previous layer.
 pub fn query_phase<F: IsField>(
-    fri_layers: &Vec<FriLayer<F, BatchedMerkleTreeBackend<F>>>,
+    fri_layers: &[FriLayer<F, BatchedMerkleTreeBackend<F>>],
+    fri_steps: &[usize],
     iotas: &[usize],
 ) -> Vec<FriDecommitment<F>>
 where
     FieldElement<F>: AsBytes + Sync + Send,
 {
-    if !fri_layers.is_empty() {
-        let query_list = iotas
-            .iter()
-            .map(|iota_s| {
-                let mut layers_evaluations_sym = Vec::new();
-                let mut layers_auth_paths_sym = Vec::new();
-
-                let mut index = *iota_s;
-                for layer in fri_layers {
-                    // symmetric element
-                    let evaluation_sym = layer.evaluation[index ^ 1].clone();
-                    let auth_path_sym = layer.merkle_tree.get_proof_by_pos(index >> 1).unwrap();
-                    layers_evaluations_sym.push(evaluation_sym);
-                    layers_auth_paths_sym.push(auth_path_sym);
-
-                    index >>= 1;
-                }
-
-                FriDecommitment {
-                    layers_auth_paths: layers_auth_paths_sym,
-                    layers_evaluations_sym,
-                }
-            })
-            .collect();
-
-        query_list
-    } else {
-        vec![]
-    }
+    iotas
+        .iter()
+        .map(|iota_s| {
+            let mut layers_evaluations_coset = Vec::new();
+            let mut layers_auth_paths = Vec::new();
+
+            let mut index = *iota_s;
+            for (layer, next_step) in fri_layers.iter().zip(&fri_steps[1..]) {
+                let coset_index = index >> next_step;
+                let coset_start = coset_index << next_step;
+                let evaluations_coset = (coset_start..coset_start + (1 << next_step))
+                    .filter(|position| *position != index)
+                    .map(|position| layer.evaluation[position].clone())
+                    .collect();
+                let auth_path = layer.merkle_tree.get_proof_by_pos(coset_index).unwrap();
+                layers_evaluations_coset.push(evaluations_coset);
+                layers_auth_paths.push(auth_path);
+
+                index = coset_index;
+            }
+
+            FriDecommitment {
+                layers_auth_paths,
+                layers_evaluations_coset,
+            }
+        })
+        .collect()
 }
 
+/// Evaluates `poly` on the coset of size `domain_size` with offset `coset_offset` and commits to
+/// the evaluations in bit-reversed order, with `coset_size` consecutive evaluations in each leaf.
+/// In that order they are the evaluations on a coset of the subgroup of order `coset_size`.
 pub fn new_fri_layer<F: IsFFTField + IsSubFieldOf<E>, E: IsField>(
     poly: &Polynomial<FieldElement<E>>,
     coset_offset: &FieldElement<F>,
     domain_size: usize,
+    coset_size: usize,
 ) -> crate::fri::fri_commitment::FriLayer<E, BatchedMerkleTreeBackend<E>>
 where
     FieldElement<F>: AsBytes + Sync + Send,
     FieldElement<E>: AsBytes + Sync + Send,
 {
+    #[cfg(not(feature = "parallel"))]
     let mut evaluation =
         Polynomial::evaluate_offset_fft(poly, 1, Some(domain_size), coset_offset).unwrap(); // TODO: return error
+    #[cfg(feature = "parallel")]
+    let mut evaluation =
+        Polynomial::parallel_evaluate_offset_fft(poly, 1, Some(domain_size), coset_offset).unwrap(); // TODO: return error
 
     in_place_bit_reverse_permute(&mut evaluation);
 
-    let mut to_commit = Vec::new();
-    for chunk in evaluation.chunks(2) {
-        to_commit.push(vec![chunk[0].clone(), chunk[1].clone()]);
-    }
+    let to_commit: Vec<_> = evaluation
+        .chunks(coset_size)
+        .map(|chunk| chunk.to_vec())
+        .collect();
 
     let merkle_tree = BatchedMerkleTree::build(&to_commit);
 
@@ -140,3 +214,34 @@ where
         domain_size,
     )
 }
+
+/// Folds in two `log2(evaluations.len())` times the evaluations of a layer of FRI on a coset of
+/// the subgroup of order `evaluations.len()`, given in bit-reversed order. The first folding uses
+/// the challenge `zeta` and each of the next ones the square of the previous challenge.
+/// `points_inv` are the inverses of the points of the even positions, so that the points of the
+/// positions `2i` and `2i + 1` are `x` and `-x`, with `points_inv[i]` equal to `x⁻¹`.
+pub fn fold_coset<F: IsSubFieldOf<E>, E: IsField>(
+    mut evaluations: Vec<FieldElement<E>>,
+    mut points_inv: Vec<FieldElement<F>>,
+    zeta: &FieldElement<E>,
+) -> FieldElement<E> {
+    let mut zeta = zeta.clone();
+    while evaluations.len() > 1 {
+        // pₖ₊₁(x²) = pₖ(x) + pₖ(-x) + 𝜁 x⁻¹ (pₖ(x) - pₖ(-x))
+        evaluations = evaluations
+            .chunks(2)
+            .zip(&points_inv)
+            .map(|(pair, point_inv)| {
+                (&pair[0] + &pair[1]) + point_inv * &zeta * (&pair[0] - &pair[1])
+            })
+            .collect();
+        // The points of the next layer are the squares of the points of this one.
+        points_inv = points_inv
+            .iter()
+            .step_by(2)
+            .map(|point_inv| point_inv.square())
+            .collect();
+        zeta = zeta.square();
+    }
+    evaluations.swap_remove(0)
+}
@@ -2,6 +2,7 @@ use lambdaworks_math::field::{
     element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
 };
 
+pub mod circle;
 pub mod constraints;
 pub mod context;
 pub mod debug;
@@ -10,8 +11,11 @@ pub mod examples;
 pub mod frame;
 pub mod fri;
 pub mod grinding;
+pub mod lookup;
+pub mod preprocessing;
 pub mod proof;
 pub mod prover;
+pub mod streaming;
 pub mod table;
 pub mod trace;
 pub mod traits;
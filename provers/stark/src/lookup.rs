@@ -0,0 +1,429 @@
+use std::marker::PhantomData;
+
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsFFTField, IsField, IsSubFieldOf},
+};
+
+use crate::{
+    constraints::{boundary::BoundaryConstraint, transition::TransitionConstraint},
+    frame::Frame,
+    trace::TraceTable,
+};
+
+/// Number of RAP challenges used by a LogUp argument: `alpha`, the point where the
+/// logarithmic derivatives are evaluated, and `beta`, used to compress tuples of
+/// columns into a single field element.
+pub const LOGUP_NUM_RAP_CHALLENGES: usize = 2;
+
+/// The transition offsets that the evaluation frame of an AIR using a LogUp argument
+/// must contain.
+pub const LOGUP_TRANSITION_OFFSETS: [usize; 2] = [0, 1];
+
+/// Description of a LogUp (logarithmic derivative) lookup argument between columns
+/// of the main trace.
+///
+/// Each element of `lookups` is a tuple of main trace columns. In every row, the values of
+/// those columns must be equal to the values of the `table` columns in some row of the trace.
+/// The `multiplicities` main column states, for every row of the table, how many times that
+/// row is looked up, adding all the lookups of all the rows of the trace.
+///
+/// The argument proves that
+///
+///   Σ_i Σ_j 1 / (α - c_j(i)) = Σ_i m(i) / (α - t(i))
+///
+/// where `c_j(i)` and `t(i)` are the compressions of the lookup tuple `j` and the table tuple
+/// at row `i`, computed as `Σ_k β^k * column_k(i)`.
+///
+/// The argument adds the following auxiliary columns, starting at `aux_column_offset`:
+///   * One helper column `h_j = 1 / (α - c_j)` for each lookup.
+///   * One helper column `h_t = m / (α - t)` for the table.
+///   * An accumulator column `s`, with `s(0) = 0` and `s(i + 1) = s(i) + Σ_j h_j(i) - h_t(i)`.
+///
+/// Since the accumulator wraps around the trace domain, the transition constraint of the
+/// accumulator evaluated at the last row enforces that the total sum is zero, so no
+/// boundary constraint other than `s(0) = 0` is needed.
+#[derive(Clone, Debug)]
+pub struct LogUpLookup {
+    pub lookups: Vec<Vec<usize>>,
+    pub table: Vec<usize>,
+    pub multiplicities: usize,
+    /// Index of the first auxiliary column used by the argument.
+    pub aux_column_offset: usize,
+    /// Index of `alpha` in the RAP challenges. `beta` is the following one.
+    pub rap_challenge_offset: usize,
+    /// Index of the first transition constraint of the argument.
+    pub constraint_idx_offset: usize,
+}
+
+impl LogUpLookup {
+    /// Creates a LogUp argument that uses the first auxiliary columns, RAP challenges
+    /// and transition constraint indexes. See `with_offsets` for AIRs that need other
+    /// auxiliary columns or constraints.
+    pub fn new(lookups: Vec<Vec<usize>>, table: Vec<usize>, multiplicities: usize) -> Self {
+        debug_assert!(lookups.iter().all(|lookup| lookup.len() == table.len()));
+        Self {
+            lookups,
+            table,
+            multiplicities,
+            aux_column_offset: 0,
+            rap_challenge_offset: 0,
+            constraint_idx_offset: 0,
+        }
+    }
+
+    pub fn with_offsets(
+        mut self,
+        aux_column_offset: usize,
+        rap_challenge_offset: usize,
+        constraint_idx_offset: usize,
+    ) -> Self {
+        self.aux_column_offset = aux_column_offset;
+        self.rap_challenge_offset = rap_challenge_offset;
+        self.constraint_idx_offset = constraint_idx_offset;
+        self
+    }
+
+    /// Number of auxiliary columns added by the argument.
+    pub fn num_auxiliary_columns(&self) -> usize {
+        self.lookups.len() + 2
+    }
+
+    /// Number of transition constraints added by the argument.
+    pub fn num_transition_constraints(&self) -> usize {
+        self.lookups.len() + 2
+    }
+
+    /// Index of the auxiliary column holding the running sum of the argument.
+    pub fn accumulator_column(&self) -> usize {
+        self.aux_column_offset + self.lookups.len() + 1
+    }
+
+    /// Samples the RAP challenges used by the argument. They should be placed in
+    /// the RAP challenges vector starting at `rap_challenge_offset`.
+    pub fn build_rap_challenges<E: IsField>(
+        &self,
+        transcript: &mut impl IsTranscript<E>,
+    ) -> Vec<FieldElement<E>> {
+        (0..LOGUP_NUM_RAP_CHALLENGES)
+            .map(|_| transcript.sample_field_element())
+            .collect()
+    }
+
+    /// Builds the auxiliary columns of the argument, in the order described in the
+    /// documentation of `LogUpLookup`.
+    pub fn build_auxiliary_columns<F, E>(
+        &self,
+        main_trace: &TraceTable<F>,
+        rap_challenges: &[FieldElement<E>],
+    ) -> Vec<Vec<FieldElement<E>>>
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+    {
+        let (alpha, beta) = self.challenges(rap_challenges);
+        let trace_len = main_trace.n_rows();
+
+        let denominators = |columns: &[usize]| -> Vec<FieldElement<E>> {
+            let mut denominators: Vec<_> = (0..trace_len)
+                .map(|row| {
+                    let values = main_trace.get_row(row);
+                    fingerprint(columns.iter().map(|col| &values[*col]), alpha, beta)
+                })
+                .collect();
+            FieldElement::inplace_batch_inverse(&mut denominators)
+                .expect("LogUp denominators should be non-zero");
+            denominators
+        };
+
+        let mut aux_columns: Vec<Vec<FieldElement<E>>> = self
+            .lookups
+            .iter()
+            .map(|lookup| denominators(lookup))
+            .collect();
+
+        let table_helper: Vec<_> = denominators(&self.table)
+            .into_iter()
+            .enumerate()
+            .map(|(row, inverse)| &main_trace.get_row(row)[self.multiplicities] * inverse)
+            .collect();
+
+        let mut accumulator = Vec::with_capacity(trace_len);
+        accumulator.push(FieldElement::<E>::zero());
+        for row in 0..trace_len - 1 {
+            let lookups_sum = aux_columns
+                .iter()
+                .fold(FieldElement::<E>::zero(), |acc, helper| acc + &helper[row]);
+            let next = &accumulator[row] + lookups_sum - &table_helper[row];
+            accumulator.push(next);
+        }
+
+        aux_columns.push(table_helper);
+        aux_columns.push(accumulator);
+        aux_columns
+    }
+
+    /// The boundary constraint fixing the first value of the accumulator to zero.
+    pub fn boundary_constraints<E: IsField>(&self) -> Vec<BoundaryConstraint<E>> {
+        vec![BoundaryConstraint::new_aux(
+            self.accumulator_column(),
+            0,
+            FieldElement::zero(),
+        )]
+    }
+
+    /// The transition constraints of the argument. They need the evaluation frame
+    /// to contain the offsets in `LOGUP_TRANSITION_OFFSETS`.
+    pub fn transition_constraints<F, E>(&self) -> Vec<Box<dyn TransitionConstraint<F, E>>>
+    where
+        F: IsFFTField + IsSubFieldOf<E> + Send + Sync + 'static,
+        E: IsField + Send + Sync + 'static,
+    {
+        let mut constraints: Vec<Box<dyn TransitionConstraint<F, E>>> = self
+            .lookups
+            .iter()
+            .enumerate()
+            .map(|(i, lookup)| {
+                Box::new(LogUpHelperConstraint::new(
+                    lookup.clone(),
+                    None,
+                    self.aux_column_offset + i,
+                    self.rap_challenge_offset,
+                    self.constraint_idx_offset + i,
+                )) as Box<dyn TransitionConstraint<F, E>>
+            })
+            .collect();
+
+        constraints.push(Box::new(LogUpHelperConstraint::new(
+            self.table.clone(),
+            Some(self.multiplicities),
+            self.aux_column_offset + self.lookups.len(),
+            self.rap_challenge_offset,
+            self.constraint_idx_offset + self.lookups.len(),
+        )));
+
+        constraints.push(Box::new(LogUpAccumulatorConstraint::new(
+            self.aux_column_offset,
+            self.lookups.len(),
+            self.constraint_idx_offset + self.lookups.len() + 1,
+        )));
+
+        constraints
+    }
+
+    fn challenges<'a, E: IsField>(
+        &self,
+        rap_challenges: &'a [FieldElement<E>],
+    ) -> (&'a FieldElement<E>, &'a FieldElement<E>) {
+        (
+            &rap_challenges[self.rap_challenge_offset],
+            &rap_challenges[self.rap_challenge_offset + 1],
+        )
+    }
+}
+
+/// Computes `α - Σ_k β^k * v_k` for the values `v_k` of a tuple of columns.
+fn fingerprint<'a, F, E>(
+    values: impl Iterator<Item = &'a FieldElement<F>>,
+    alpha: &FieldElement<E>,
+    beta: &FieldElement<E>,
+) -> FieldElement<E>
+where
+    F: IsSubFieldOf<E> + 'a,
+    E: IsField,
+{
+    let mut beta_power = FieldElement::<E>::one();
+    let mut compressed = FieldElement::<E>::zero();
+    for value in values {
+        compressed += value * &beta_power;
+        beta_power *= beta;
+    }
+    alpha - compressed
+}
+
+/// Constraint `h * (α - c) - m = 0` for a helper column `h`, where `c` is the compression
+/// of a tuple of main columns and `m` is either one, for lookups, or the multiplicities
+/// column, for the table.
+struct LogUpHelperConstraint<F, E> {
+    columns: Vec<usize>,
+    multiplicities: Option<usize>,
+    helper_column: usize,
+    rap_challenge_offset: usize,
+    constraint_idx: usize,
+    phantom: PhantomData<(F, E)>,
+}
+
+impl<F, E> LogUpHelperConstraint<F, E> {
+    fn new(
+        columns: Vec<usize>,
+        multiplicities: Option<usize>,
+        helper_column: usize,
+        rap_challenge_offset: usize,
+        constraint_idx: usize,
+    ) -> Self {
+        Self {
+            columns,
+            multiplicities,
+            helper_column,
+            rap_challenge_offset,
+            constraint_idx,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<F, E> TransitionConstraint<F, E> for LogUpHelperConstraint<F, E>
+where
+    F: IsFFTField + IsSubFieldOf<E> + Send + Sync,
+    E: IsField + Send + Sync,
+{
+    fn degree(&self) -> usize {
+        2
+    }
+
+    fn constraint_idx(&self) -> usize {
+        self.constraint_idx
+    }
+
+    fn end_exemptions(&self) -> usize {
+        0
+    }
+
+    fn evaluate(
+        &self,
+        frame: &Frame<F, E>,
+        transition_evaluations: &mut [FieldElement<E>],
+        _periodic_values: &[FieldElement<F>],
+        rap_challenges: &[FieldElement<E>],
+    ) {
+        let step = frame.get_evaluation_step(0);
+        let alpha = &rap_challenges[self.rap_challenge_offset];
+        let beta = &rap_challenges[self.rap_challenge_offset + 1];
+
+        let denominator = fingerprint(
+            self.columns
+                .iter()
+                .map(|col| step.get_main_evaluation_element(0, *col)),
+            alpha,
+            beta,
+        );
+        let helper = step.get_aux_evaluation_element(0, self.helper_column);
+
+        let res = match self.multiplicities {
+            Some(col) => {
+                let multiplicity = step.get_main_evaluation_element(0, col);
+                -(multiplicity - helper * denominator)
+            }
+            None => helper * denominator - FieldElement::<E>::one(),
+        };
+
+        transition_evaluations[self.constraint_idx()] = res;
+    }
+}
+
+/// Constraint `s(i + 1) - s(i) - Σ_j h_j(i) + h_t(i) = 0` for the accumulator column `s`.
+struct LogUpAccumulatorConstraint<F, E> {
+    aux_column_offset: usize,
+    num_lookups: usize,
+    constraint_idx: usize,
+    phantom: PhantomData<(F, E)>,
+}
+
+impl<F, E> LogUpAccumulatorConstraint<F, E> {
+    fn new(aux_column_offset: usize, num_lookups: usize, constraint_idx: usize) -> Self {
+        Self {
+            aux_column_offset,
+            num_lookups,
+            constraint_idx,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<F, E> TransitionConstraint<F, E> for LogUpAccumulatorConstraint<F, E>
+where
+    F: IsFFTField + IsSubFieldOf<E> + Send + Sync,
+    E: IsField + Send + Sync,
+{
+    fn degree(&self) -> usize {
+        1
+    }
+
+    fn constraint_idx(&self) -> usize {
+        self.constraint_idx
+    }
+
+    fn end_exemptions(&self) -> usize {
+        0
+    }
+
+    fn evaluate(
+        &self,
+        frame: &Frame<F, E>,
+        transition_evaluations: &mut [FieldElement<E>],
+        _periodic_values: &[FieldElement<F>],
+        _rap_challenges: &[FieldElement<E>],
+    ) {
+        let first_step = frame.get_evaluation_step(0);
+        let second_step = frame.get_evaluation_step(1);
+
+        let table_column = self.aux_column_offset + self.num_lookups;
+        let accumulator_column = table_column + 1;
+
+        let lookups_sum = (self.aux_column_offset..table_column)
+            .fold(FieldElement::<E>::zero(), |acc, col| {
+                acc + first_step.get_aux_evaluation_element(0, col)
+            });
+        let table_helper = first_step.get_aux_evaluation_element(0, table_column);
+        let s_i = first_step.get_aux_evaluation_element(0, accumulator_column);
+        let s_i_plus_one = second_step.get_aux_evaluation_element(0, accumulator_column);
+
+        let res = s_i_plus_one - s_i - lookups_sum + table_helper;
+
+        transition_evaluations[self.constraint_idx()] = res;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use lambdaworks_math::field::fields::u64_prime_field::FE17;
+
+    #[test]
+    fn accumulator_wraps_around_to_zero_when_lookups_are_in_table() {
+        // Column 0 holds the looked up values, column 1 the table and column 2 the multiplicities.
+        let looked_up = [3, 3, 0, 1, 3, 2, 0, 3].map(FE17::from).to_vec();
+        let table = (0..8).map(FE17::from).collect();
+        let multiplicities = [2, 1, 1, 4, 0, 0, 0, 0].map(FE17::from).to_vec();
+        let trace = TraceTable::from_columns_main(vec![looked_up, table, multiplicities], 1);
+
+        let logup = LogUpLookup::new(vec![vec![0]], vec![1], 2);
+        let challenges = [FE17::from(10), FE17::from(3)];
+        let aux_columns = logup.build_auxiliary_columns(&trace, &challenges);
+        assert_eq!(aux_columns.len(), logup.num_auxiliary_columns());
+
+        let accumulator = &aux_columns[logup.accumulator_column()];
+        let last = trace.n_rows() - 1;
+        let total = accumulator[last] + aux_columns[0][last] - aux_columns[1][last];
+        assert_eq!(accumulator[0], FE17::zero());
+        assert_eq!(total, FE17::zero());
+    }
+
+    #[test]
+    fn accumulator_does_not_wrap_around_to_zero_when_a_lookup_is_missing() {
+        let looked_up = [3, 3, 0, 1, 3, 2, 0, 9].map(FE17::from).to_vec();
+        let table = (0..8).map(FE17::from).collect();
+        let multiplicities = [2, 1, 1, 3, 0, 0, 0, 0].map(FE17::from).to_vec();
+        let trace = TraceTable::from_columns_main(vec![looked_up, table, multiplicities], 1);
+
+        let logup = LogUpLookup::new(vec![vec![0]], vec![1], 2);
+        let challenges = [FE17::from(12), FE17::from(3)];
+        let aux_columns = logup.build_auxiliary_columns(&trace, &challenges);
+
+        let accumulator = &aux_columns[logup.accumulator_column()];
+        let last = trace.n_rows() - 1;
+        let total = accumulator[last] + aux_columns[0][last] - aux_columns[1][last];
+        assert_ne!(total, FE17::zero());
+    }
+}
@@ -0,0 +1,153 @@
+//! Preprocessed columns of an AIR.
+//!
+//! Some columns of a trace don't depend on the witness: program ROMs, lookup tables or selectors
+//! fixed by the statement. They are returned by `AIR::preprocessed_columns` and committed once,
+//! by `setup`, instead of in every proof. The prover receives the resulting `ProvingKey`, which
+//! holds their polynomials, their evaluations over the LDE domain and their Merkle tree. The
+//! verifier receives the `VerifyingKey`, which holds the root of that tree, and checks the
+//! openings of the preprocessed columns against it.
+//!
+//! In the evaluation frames the preprocessed columns come right after the main columns, so the
+//! `j`-th preprocessed column is the main column `trace_layout().0 + j`. The commitment depends
+//! on the trace length, the blowup factor and the coset offset. The keys record them, and the
+//! prover and the verifier reject keys computed for other ones.
+
+use lambdaworks_math::{
+    fft::lde::CosetLDE,
+    field::{element::FieldElement, traits::IsFFTField},
+    polynomial::Polynomial,
+    traits::AsBytes,
+};
+
+use crate::{
+    config::{BatchedMerkleTree, Commitment},
+    domain::Domain,
+    proof::options::ProofOptions,
+    prover::{ProvingError, Round1CommitmentData},
+    trace::TraceTable,
+    traits::AIR,
+};
+
+/// The commitment to the preprocessed columns of an AIR, computed once by `setup`.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct VerifyingKey {
+    /// Length of the trace the preprocessed columns belong to.
+    pub trace_length: usize,
+    /// Number of preprocessed columns.
+    pub num_preprocessed_columns: usize,
+    /// Blowup factor of the LDE domain the preprocessed columns are committed over.
+    pub blowup_factor: u8,
+    /// Offset of the coset of the LDE domain the preprocessed columns are committed over.
+    pub coset_offset: u64,
+    /// Root of the Merkle tree of the evaluations of the preprocessed columns over the LDE domain.
+    pub preprocessed_root: Commitment,
+}
+
+impl VerifyingKey {
+    /// Returns whether the key commits to the preprocessed columns of traces of length
+    /// `trace_length`, over the LDE domain given by `proof_options`.
+    pub fn matches(&self, trace_length: usize, proof_options: &ProofOptions) -> bool {
+        self.trace_length == trace_length
+            && self.blowup_factor == proof_options.blowup_factor
+            && self.coset_offset == proof_options.coset_offset
+    }
+}
+
+/// The data of the preprocessed columns of an AIR needed by the prover, computed once by `setup`.
+pub struct ProvingKey<F>
+where
+    F: IsFFTField,
+    FieldElement<F>: AsBytes + Send + Sync,
+{
+    /// The polynomials interpolating the preprocessed columns and their commitment.
+    pub(crate) commitment: Round1CommitmentData<F>,
+    /// The evaluations of the above polynomials over the LDE domain, one vector per column.
+    pub(crate) lde_evaluations: Vec<Vec<FieldElement<F>>>,
+    pub verifying_key: VerifyingKey,
+}
+
+impl<F> ProvingKey<F>
+where
+    F: IsFFTField,
+    FieldElement<F>: AsBytes + Send + Sync,
+{
+    pub fn verifying_key(&self) -> &VerifyingKey {
+        &self.verifying_key
+    }
+}
+
+/// Commits to the preprocessed columns of the AIR `A` for traces of length `trace_length`
+/// proven with options `proof_options`, returning the keys used to prove and to verify.
+pub fn setup<A>(
+    trace_length: usize,
+    pub_inputs: &A::PublicInputs,
+    proof_options: &ProofOptions,
+) -> Result<ProvingKey<A::Field>, ProvingError>
+where
+    A: AIR,
+    FieldElement<A::Field>: AsBytes + Send + Sync,
+{
+    let air = A::new(trace_length, pub_inputs, proof_options)?;
+    let columns = air.preprocessed_columns();
+
+    let num_preprocessed_columns = air.num_preprocessed_columns();
+    if num_preprocessed_columns == 0 || columns.len() != num_preprocessed_columns {
+        return Err(ProvingError::WrongParameter(format!(
+            "the AIR declares {num_preprocessed_columns} preprocessed columns but returns {}",
+            columns.len()
+        )));
+    }
+    if let Some(column) = columns.iter().find(|column| column.len() != trace_length) {
+        return Err(ProvingError::WrongParameter(format!(
+            "preprocessed column of length {} for a trace of length {trace_length}",
+            column.len()
+        )));
+    }
+
+    let domain = Domain::new(&air);
+    let trace = TraceTable::from_columns(columns, num_preprocessed_columns, A::STEP_SIZE);
+    let trace_polys = trace.compute_trace_polys::<A::Field>();
+
+    let lde = CosetLDE::new(
+        domain.interpolation_domain_size,
+        domain.blowup_factor,
+        &domain.coset_offset,
+    )
+    .map_err(|error| ProvingError::WrongParameter(format!("{error:?}")))?;
+    let evaluations = lde.evaluate_polynomials::<A::Field>(&trace_polys);
+    let lde_evaluations = evaluations.columns();
+    let lde_trace_merkle_tree = BatchedMerkleTree::build(evaluations.bit_reversed_rows());
+    let lde_trace_merkle_root = lde_trace_merkle_tree.root;
+
+    Ok(ProvingKey {
+        commitment: Round1CommitmentData {
+            trace_polys,
+            lde_trace_merkle_tree,
+            lde_trace_merkle_root,
+        },
+        lde_evaluations,
+        verifying_key: VerifyingKey {
+            trace_length,
+            num_preprocessed_columns,
+            blowup_factor: proof_options.blowup_factor,
+            coset_offset: proof_options.coset_offset,
+            preprocessed_root: lde_trace_merkle_root,
+        },
+    })
+}
+
+/// Returns the polynomials interpolating the main columns followed by the ones interpolating
+/// the preprocessed columns, which is the order of the columns in the evaluation frames.
+pub(crate) fn main_and_preprocessed_polys<F: IsFFTField>(
+    main_trace_polys: &[Polynomial<FieldElement<F>>],
+    preprocessed: Option<&Round1CommitmentData<F>>,
+) -> Vec<Polynomial<FieldElement<F>>>
+where
+    FieldElement<F>: AsBytes + Send + Sync,
+{
+    let mut polys = main_trace_polys.to_vec();
+    if let Some(preprocessed) = preprocessed {
+        polys.extend_from_slice(&preprocessed.trace_polys);
+    }
+    polys
+}
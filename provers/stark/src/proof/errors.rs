@@ -1,7 +1,11 @@
+use crate::prover::ProvingError;
+
 #[derive(Debug)]
 pub enum InsecureOptionError {
     /// Field Size is not big enough
     FieldSize,
     /// Number of security bits is not enough
     LowSecurityBits,
+    /// The AIR can't be built for the statement
+    InvalidAir(ProvingError),
 }
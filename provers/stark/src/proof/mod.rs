@@ -1,3 +1,4 @@
 pub mod errors;
 pub mod options;
+pub mod security;
 pub mod stark;
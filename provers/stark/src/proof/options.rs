@@ -4,6 +4,7 @@ use lambdaworks_math::field::traits::IsPrimeField;
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::wasm_bindgen;
 
+#[derive(Clone, Copy, Debug)]
 pub enum SecurityLevel {
     Conjecturable80Bits,
     Conjecturable100Bits,
@@ -13,12 +14,41 @@ pub enum SecurityLevel {
     Provable128Bits,
 }
 
+impl SecurityLevel {
+    /// Returns the number of security bits of the level.
+    pub fn bits(&self) -> usize {
+        match self {
+            SecurityLevel::Conjecturable80Bits | SecurityLevel::Provable80Bits => 80,
+            SecurityLevel::Conjecturable100Bits | SecurityLevel::Provable100Bits => 100,
+            SecurityLevel::Conjecturable128Bits | SecurityLevel::Provable128Bits => 128,
+        }
+    }
+
+    /// Returns whether the level is reached by the provable security of the proofs instead of
+    /// their conjectured security.
+    pub fn is_provable(&self) -> bool {
+        matches!(
+            self,
+            SecurityLevel::Provable80Bits
+                | SecurityLevel::Provable100Bits
+                | SecurityLevel::Provable128Bits
+        )
+    }
+}
+
 /// The options for the proof
 ///
 /// - `blowup_factor`: the blowup factor for the trace
 /// - `fri_number_of_queries`: the number of queries for the FRI layer
 /// - `coset_offset`: the offset for the coset
 /// - `grinding_factor`: the number of leading zeros that we want for the Hash(hash || nonce)
+/// - `zero_knowledge`: whether the trace and the composition polynomial are masked so that the
+///   proof reveals nothing about the witness
+/// - `fri_step_list`: the number of times FRI folds the polynomial in two to obtain each layer, as
+///   in the `fri_step_list` of Stone, whose first entry is 0. If it's empty, each layer is folded
+///   one time
+/// - `fri_last_layer_degree_bound`: the degree bound of the last layer of FRI, whose coefficients
+///   are sent in the proof. It must be a power of two
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone, Debug)]
 pub struct ProofOptions {
@@ -26,11 +56,13 @@ pub struct ProofOptions {
     pub fri_number_of_queries: usize,
     pub coset_offset: u64,
     pub grinding_factor: u8,
+    pub zero_knowledge: bool,
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    pub fri_step_list: Vec<usize>,
+    pub fri_last_layer_degree_bound: usize,
 }
 
 impl ProofOptions {
-    // TODO: Make it work for extended fields
-    const EXTENSION_DEGREE: usize = 1;
     // Estimated maximum domain size. 2^40 = 1 TB
     const NUM_BITS_MAX_DOMAIN_SIZE: usize = 40;
 
@@ -42,36 +74,54 @@ impl ProofOptions {
                 fri_number_of_queries: 31,
                 coset_offset,
                 grinding_factor: 20,
+                zero_knowledge: false,
+                fri_step_list: vec![],
+                fri_last_layer_degree_bound: 1,
             },
             SecurityLevel::Conjecturable100Bits => ProofOptions {
                 blowup_factor: 4,
                 fri_number_of_queries: 41,
                 coset_offset,
                 grinding_factor: 20,
+                zero_knowledge: false,
+                fri_step_list: vec![],
+                fri_last_layer_degree_bound: 1,
             },
             SecurityLevel::Conjecturable128Bits => ProofOptions {
                 blowup_factor: 4,
                 fri_number_of_queries: 55,
                 coset_offset,
                 grinding_factor: 20,
+                zero_knowledge: false,
+                fri_step_list: vec![],
+                fri_last_layer_degree_bound: 1,
             },
             SecurityLevel::Provable80Bits => ProofOptions {
                 blowup_factor: 4,
                 fri_number_of_queries: 80,
                 coset_offset,
                 grinding_factor: 20,
+                zero_knowledge: false,
+                fri_step_list: vec![],
+                fri_last_layer_degree_bound: 1,
             },
             SecurityLevel::Provable100Bits => ProofOptions {
                 blowup_factor: 4,
                 fri_number_of_queries: 104,
                 coset_offset,
                 grinding_factor: 20,
+                zero_knowledge: false,
+                fri_step_list: vec![],
+                fri_last_layer_degree_bound: 1,
             },
             SecurityLevel::Provable128Bits => ProofOptions {
                 blowup_factor: 4,
                 fri_number_of_queries: 140,
                 coset_offset,
                 grinding_factor: 20,
+                zero_knowledge: false,
+                fri_step_list: vec![],
+                fri_last_layer_degree_bound: 1,
             },
         }
     }
@@ -84,7 +134,27 @@ impl ProofOptions {
         grinding_factor: u8,
         security_target: u8,
     ) -> Result<Self, InsecureOptionError> {
-        Self::check_field_security::<F>(security_target)?;
+        Self::new_with_checked_security_over_extension::<F>(
+            blowup_factor,
+            fri_number_of_queries,
+            coset_offset,
+            grinding_factor,
+            1,
+            security_target,
+        )
+    }
+
+    /// Checks security of proof options for an AIR whose challenges are sampled from an
+    /// extension of degree `extension_degree` of the prime field `F`.
+    pub fn new_with_checked_security_over_extension<F: IsPrimeField>(
+        blowup_factor: u8,
+        fri_number_of_queries: usize,
+        coset_offset: u64,
+        grinding_factor: u8,
+        extension_degree: usize,
+        security_target: u8,
+    ) -> Result<Self, InsecureOptionError> {
+        Self::check_field_security::<F>(extension_degree, security_target)?;
 
         let num_bits_blowup_factor = blowup_factor.trailing_zeros() as usize;
 
@@ -99,6 +169,9 @@ impl ProofOptions {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            zero_knowledge: false,
+            fri_step_list: vec![],
+            fri_last_layer_degree_bound: 1,
         })
     }
 
@@ -112,7 +185,27 @@ impl ProofOptions {
         grinding_factor: u8,
         security_target: u8,
     ) -> Result<Self, InsecureOptionError> {
-        Self::check_field_security::<F>(security_target)?;
+        Self::new_with_checked_provable_security_over_extension::<F>(
+            blowup_factor,
+            fri_number_of_queries,
+            coset_offset,
+            grinding_factor,
+            1,
+            security_target,
+        )
+    }
+
+    /// Checks provable security of proof options for an AIR whose challenges are sampled from
+    /// an extension of degree `extension_degree` of the prime field `F`.
+    pub fn new_with_checked_provable_security_over_extension<F: IsPrimeField>(
+        blowup_factor: u8,
+        fri_number_of_queries: usize,
+        coset_offset: u64,
+        grinding_factor: u8,
+        extension_degree: usize,
+        security_target: u8,
+    ) -> Result<Self, InsecureOptionError> {
+        Self::check_field_security::<F>(extension_degree, security_target)?;
 
         let num_bits_blowup_factor = blowup_factor.leading_zeros() as usize;
 
@@ -127,13 +220,19 @@ impl ProofOptions {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            zero_knowledge: false,
+            fri_step_list: vec![],
+            fri_last_layer_degree_bound: 1,
         })
     }
 
+    /// The challenges are sampled from an extension of degree `extension_degree` of `F`, so
+    /// they have `extension_degree` times as many bits as the elements of `F`.
     fn check_field_security<F: IsPrimeField>(
+        extension_degree: usize,
         security_target: u8,
     ) -> Result<(), InsecureOptionError> {
-        if F::field_bit_size() * Self::EXTENSION_DEGREE
+        if F::field_bit_size() * extension_degree
             <= security_target as usize + Self::NUM_BITS_MAX_DOMAIN_SIZE
         {
             return Err(InsecureOptionError::FieldSize);
@@ -150,14 +249,43 @@ impl ProofOptions {
             fri_number_of_queries: 3,
             coset_offset: 3,
             grinding_factor: 1,
+            zero_knowledge: false,
+            fri_step_list: vec![],
+            fri_last_layer_degree_bound: 1,
         }
     }
+
+    /// Returns the number of times each layer of FRI is folded in two, for a polynomial of degree
+    /// less than `2^log_degree_bound`. The first entry of `fri_step_list` is skipped. Returns
+    /// `None` if the steps don't fold the polynomial down to `fri_last_layer_degree_bound`.
+    pub fn fri_folding_steps(&self, log_degree_bound: usize) -> Option<Vec<usize>> {
+        if !self.fri_last_layer_degree_bound.is_power_of_two() {
+            return None;
+        }
+        let log_last_layer_degree_bound =
+            self.fri_last_layer_degree_bound.trailing_zeros() as usize;
+        let number_of_foldings = log_degree_bound.checked_sub(log_last_layer_degree_bound)?;
+
+        let steps = match self.fri_step_list.split_first() {
+            None => vec![1; number_of_foldings],
+            Some((0, steps)) => steps.to_vec(),
+            Some(_) => return None,
+        };
+        if steps.is_empty()
+            || steps.contains(&0)
+            || steps.iter().sum::<usize>() != number_of_foldings
+        {
+            return None;
+        }
+        Some(steps)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use lambdaworks_math::field::fields::{
-        fft_friendly::stark_252_prime_field::Stark252PrimeField, u64_prime_field::F17,
+        fft_friendly::{babybear::Babybear31PrimeField, stark_252_prime_field::Stark252PrimeField},
+        u64_prime_field::F17,
     };
 
     use crate::proof::{errors::InsecureOptionError, options::SecurityLevel};
@@ -171,6 +299,7 @@ mod tests {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            ..
         } = ProofOptions::new_secure(SecurityLevel::Conjecturable128Bits, 1);
 
         let u64_options = ProofOptions::new_with_checked_security::<F17>(
@@ -191,6 +320,7 @@ mod tests {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            ..
         } = ProofOptions::new_secure(SecurityLevel::Conjecturable128Bits, 1);
 
         let secure_options = ProofOptions::new_with_checked_security::<Stark252PrimeField>(
@@ -211,6 +341,7 @@ mod tests {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            ..
         } = ProofOptions::new_secure(SecurityLevel::Conjecturable128Bits, 1);
 
         let insecure_options = ProofOptions::new_with_checked_security::<Stark252PrimeField>(
@@ -234,6 +365,7 @@ mod tests {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            ..
         } = ProofOptions::new_secure(SecurityLevel::Conjecturable100Bits, 1);
 
         let secure_options = ProofOptions::new_with_checked_security::<Stark252PrimeField>(
@@ -254,6 +386,7 @@ mod tests {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            ..
         } = ProofOptions::new_secure(SecurityLevel::Conjecturable80Bits, 1);
 
         let secure_options = ProofOptions::new_with_checked_security::<Stark252PrimeField>(
@@ -266,4 +399,78 @@ mod tests {
 
         assert!(secure_options.is_ok());
     }
+
+    #[test]
+    fn babybear_is_large_enough_to_be_secure_only_over_its_quartic_extension() {
+        let ProofOptions {
+            blowup_factor,
+            fri_number_of_queries,
+            coset_offset,
+            grinding_factor,
+            ..
+        } = ProofOptions::new_secure(SecurityLevel::Conjecturable80Bits, 1);
+
+        for extension_degree in [1, 2] {
+            let insecure_options =
+                ProofOptions::new_with_checked_security_over_extension::<Babybear31PrimeField>(
+                    blowup_factor,
+                    fri_number_of_queries,
+                    coset_offset,
+                    grinding_factor,
+                    extension_degree,
+                    80,
+                );
+            assert!(matches!(
+                insecure_options,
+                Err(InsecureOptionError::FieldSize)
+            ));
+        }
+
+        let secure_options =
+            ProofOptions::new_with_checked_security_over_extension::<Babybear31PrimeField>(
+                blowup_factor,
+                fri_number_of_queries,
+                coset_offset,
+                grinding_factor,
+                4,
+                80,
+            );
+        assert!(secure_options.is_ok());
+    }
+
+    #[test]
+    fn empty_fri_step_list_folds_once_per_layer() {
+        let options = ProofOptions::default_test_options();
+        assert_eq!(options.fri_folding_steps(5), Some(vec![1; 5]));
+    }
+
+    #[test]
+    fn fri_step_list_folds_down_to_the_last_layer_degree_bound() {
+        let options = ProofOptions {
+            fri_step_list: vec![0, 3, 2],
+            fri_last_layer_degree_bound: 8,
+            ..ProofOptions::default_test_options()
+        };
+        assert_eq!(options.fri_folding_steps(8), Some(vec![3, 2]));
+        assert_eq!(options.fri_folding_steps(7), None);
+        assert_eq!(options.fri_folding_steps(2), None);
+    }
+
+    #[test]
+    fn invalid_fri_step_lists_are_rejected() {
+        for (fri_step_list, fri_last_layer_degree_bound) in [
+            (vec![1, 2, 2], 1),
+            (vec![0], 1),
+            (vec![0, 2, 0, 3], 1),
+            (vec![0, 2, 3], 3),
+            (vec![0, 2, 3], 0),
+        ] {
+            let options = ProofOptions {
+                fri_step_list,
+                fri_last_layer_degree_bound,
+                ..ProofOptions::default_test_options()
+            };
+            assert_eq!(options.fri_folding_steps(5), None);
+        }
+    }
 }
@@ -0,0 +1,678 @@
+//! Estimates of the security level and the size of STARK proofs.
+//!
+//! The conjectured security follows the ethSTARK conjecture on the soundness of FRI
+//! (https://eprint.iacr.org/2021/582.pdf, section 5.10.1). The provable security bounds the
+//! soundness error of the protocol when FRI is run up to the Johnson bound, following the
+//! analysis of the DEEP-ALI protocol in the ethSTARK paper and of FRI in
+//! https://eprint.iacr.org/2020/654.pdf (theorem 8.3).
+//! Both estimates take into account the degree of the field extension the challenges are
+//! sampled from, and are capped by the collision resistance of the hash used in the commitments.
+
+use lambdaworks_math::field::traits::{IsField, IsPrimeField};
+
+use crate::{config::COMMITMENT_SIZE, traits::AIR};
+
+use super::{
+    errors::InsecureOptionError,
+    options::{ProofOptions, SecurityLevel},
+};
+
+/// The proximity parameter used in the provable security estimate. Larger values give
+/// a smaller list size but a larger error in the commit phase of FRI.
+const JOHNSON_BOUND_PROXIMITY_PARAMETER: f64 = 3.0;
+
+/// Grinding factor of the recommended proof options. It matches the grinding factor
+/// used by `ProofOptions::new_secure`.
+const RECOMMENDED_GRINDING_FACTOR: u8 = 20;
+
+/// Blowup factors tried when recommending proof options.
+const RECOMMENDED_BLOWUP_FACTORS: [u8; 7] = [2, 4, 8, 16, 32, 64, 128];
+
+/// Maximum number of FRI queries of the recommended proof options.
+const MAX_NUMBER_OF_QUERIES: usize = 1024;
+
+/// The parameters of a statement that determine the security and the size of its proofs.
+///
+/// - `base_field_bits`: the number of bits of the field of the trace
+/// - `extension_degree`: the degree of the extension the challenges are sampled from
+/// - `trace_length`: the length of the trace, a power of two
+/// - `num_main_columns`: the number of columns of the main trace
+/// - `num_aux_columns`: the number of columns of the auxiliary trace
+/// - `frame_size`: the number of rows of the out of domain evaluation frame
+/// - `num_composition_poly_parts`: the number of parts the composition polynomial is broken
+///   into, not counting the random part of zero-knowledge proofs
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProofParameters {
+    pub base_field_bits: usize,
+    pub extension_degree: usize,
+    pub trace_length: usize,
+    pub num_main_columns: usize,
+    pub num_aux_columns: usize,
+    pub frame_size: usize,
+    pub num_composition_poly_parts: usize,
+}
+
+impl ProofParameters {
+    /// Returns the parameters of the statement described by `air`, whose challenges are sampled
+    /// from the field extension of the AIR.
+    pub fn from_air<A>(air: &A) -> Self
+    where
+        A: AIR,
+        A::Field: IsPrimeField,
+    {
+        let (num_main_columns, num_aux_columns) = air.trace_layout();
+        Self {
+            base_field_bits: A::Field::field_bit_size(),
+            extension_degree: A::FieldExtension::EXTENSION_DEGREE / A::Field::EXTENSION_DEGREE,
+            trace_length: air.trace_length(),
+            num_main_columns,
+            num_aux_columns,
+            frame_size: air.context().transition_offsets.len() * A::STEP_SIZE,
+            num_composition_poly_parts: air.num_composition_poly_parts(),
+        }
+    }
+
+    fn extension_field_bits(&self) -> usize {
+        self.base_field_bits * self.extension_degree
+    }
+
+    fn base_field_element_size(&self) -> usize {
+        (self.base_field_bits + 7) / 8
+    }
+
+    fn extension_field_element_size(&self) -> usize {
+        self.base_field_element_size() * self.extension_degree
+    }
+}
+
+/// The estimated security and size of a STARK proof.
+///
+/// - `conjectured_security_bits`: the security level assuming the ethSTARK conjecture on FRI
+/// - `provable_security_bits`: the security level that follows from the known soundness
+///   bounds of FRI
+/// - `proof_size_bytes`: the size of the data sent in the proof, without the overhead of
+///   its serialization format
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SecurityEstimate {
+    pub conjectured_security_bits: usize,
+    pub provable_security_bits: usize,
+    pub proof_size_bytes: usize,
+}
+
+impl SecurityEstimate {
+    /// Estimates the security and the size of the proofs of a statement with parameters
+    /// `parameters` generated with `options`. The number of composition polynomial parts in
+    /// `parameters` depends on the options of zero-knowledge proofs, so both should describe
+    /// the same AIR.
+    pub fn new(parameters: &ProofParameters, options: &ProofOptions) -> Self {
+        Self {
+            conjectured_security_bits: conjectured_security_bits(parameters, options),
+            provable_security_bits: provable_security_bits(parameters, options),
+            proof_size_bytes: proof_size_bytes(parameters, options),
+        }
+    }
+
+    /// Returns the security bits of the estimate for the kind of security of `security_level`.
+    pub fn security_bits(&self, security_level: &SecurityLevel) -> usize {
+        if security_level.is_provable() {
+            self.provable_security_bits
+        } else {
+            self.conjectured_security_bits
+        }
+    }
+}
+
+/// Estimates the security and the size of the proofs of the statement described by `air`
+/// with the proof options of `air`.
+pub fn estimate_security<A>(air: &A) -> SecurityEstimate
+where
+    A: AIR,
+    A::Field: IsPrimeField,
+{
+    SecurityEstimate::new(&ProofParameters::from_air(air), air.options())
+}
+
+/// Returns the proof options with the smallest estimated proof size that reach
+/// `security_level` for the statement described by `A`, `trace_length` and `pub_inputs`.
+/// Fails with `InsecureOptionError::FieldSize` if the field extension of the AIR is too
+/// small to reach the security level, with `InsecureOptionError::LowSecurityBits` if no
+/// proof options reach it, and with `InsecureOptionError::InvalidAir` if the AIR can't be
+/// built for the statement.
+pub fn recommend_proof_options<A>(
+    trace_length: usize,
+    pub_inputs: &A::PublicInputs,
+    security_level: &SecurityLevel,
+    zero_knowledge: bool,
+    coset_offset: u64,
+) -> Result<ProofOptions, InsecureOptionError>
+where
+    A: AIR,
+    A::Field: IsPrimeField,
+{
+    let security_target = security_level.bits();
+    let mut best: Option<(ProofOptions, usize)> = None;
+    let mut field_is_too_small = true;
+
+    for blowup_factor in RECOMMENDED_BLOWUP_FACTORS {
+        if zero_knowledge && blowup_factor < 4 {
+            continue;
+        }
+        let mut options = ProofOptions {
+            blowup_factor,
+            fri_number_of_queries: 1,
+            coset_offset,
+            grinding_factor: RECOMMENDED_GRINDING_FACTOR,
+            zero_knowledge,
+            fri_step_list: vec![],
+            fri_last_layer_degree_bound: 1,
+        };
+
+        // Start from the number of queries needed when the error of the queries dominates
+        // and add queries until the estimate reaches the target.
+        let query_bits = if security_level.is_provable() {
+            provable_query_security_bits(blowup_factor)
+        } else {
+            blowup_factor.trailing_zeros() as f64
+        };
+        let missing_bits =
+            (security_target as f64 + 1.0 - RECOMMENDED_GRINDING_FACTOR as f64).max(query_bits);
+        options.fri_number_of_queries = ((missing_bits / query_bits).floor() as usize).max(1);
+
+        while options.fri_number_of_queries <= MAX_NUMBER_OF_QUERIES {
+            let air = A::new(trace_length, pub_inputs, &options)
+                .map_err(InsecureOptionError::InvalidAir)?;
+            let parameters = ProofParameters::from_air(&air);
+            if field_security_bits(&parameters, &options, security_level) < security_target {
+                break;
+            }
+            field_is_too_small = false;
+
+            let blowup_is_large_enough = if zero_knowledge {
+                crate::prover::check_zero_knowledge_parameters(&air).is_ok()
+            } else {
+                parameters.num_composition_poly_parts <= blowup_factor as usize
+            };
+            if !blowup_is_large_enough {
+                break;
+            }
+
+            let estimate = SecurityEstimate::new(&parameters, &options);
+            if estimate.security_bits(security_level) >= security_target {
+                if best
+                    .as_ref()
+                    .map_or(true, |(_, size)| estimate.proof_size_bytes < *size)
+                {
+                    best = Some((options.clone(), estimate.proof_size_bytes));
+                }
+                break;
+            }
+            options.fri_number_of_queries += 1;
+        }
+    }
+
+    match best {
+        Some((options, _)) => Ok(options),
+        None if field_is_too_small => Err(InsecureOptionError::FieldSize),
+        None => Err(InsecureOptionError::LowSecurityBits),
+    }
+}
+
+/// Returns the security bits of the hash used in the commitments against collisions.
+fn collision_resistance_bits() -> usize {
+    COMMITMENT_SIZE * 8 / 2
+}
+
+fn lde_domain_size(parameters: &ProofParameters, options: &ProofOptions) -> usize {
+    parameters.trace_length * options.blowup_factor as usize
+}
+
+/// Returns the security bits of the steps of the protocol whose error only depends on the
+/// size of the field, that is, an upper bound of the security for any number of queries.
+fn field_security_bits(
+    parameters: &ProofParameters,
+    options: &ProofOptions,
+    security_level: &SecurityLevel,
+) -> usize {
+    if security_level.is_provable() {
+        to_security_bits(provable_field_error(parameters, options))
+    } else {
+        conjectured_field_security_bits(parameters, options)
+    }
+}
+
+fn conjectured_field_security_bits(parameters: &ProofParameters, options: &ProofOptions) -> usize {
+    let lde_domain_size_bits = lde_domain_size(parameters, options).trailing_zeros() as usize;
+    parameters
+        .extension_field_bits()
+        .saturating_sub(lde_domain_size_bits)
+}
+
+/// Under the ethSTARK conjecture every query adds `log2(blowup_factor)` bits of security,
+/// and the out of domain sampling and FRI folding lose `log2(|D_LDE|)` bits of the field.
+fn conjectured_security_bits(parameters: &ProofParameters, options: &ProofOptions) -> usize {
+    let field_security = conjectured_field_security_bits(parameters, options);
+    let query_security = options.blowup_factor.trailing_zeros() as usize
+        * options.fri_number_of_queries
+        + options.grinding_factor as usize;
+    field_security
+        .min(query_security)
+        .saturating_sub(1)
+        .min(collision_resistance_bits())
+}
+
+/// Returns the security bits added by each query when FRI is run up to the Johnson bound,
+/// that is, `-log2((1 + 1 / 2m) * sqrt(rho))`.
+fn provable_query_security_bits(blowup_factor: u8) -> f64 {
+    let m = JOHNSON_BOUND_PROXIMITY_PARAMETER;
+    let sqrt_rate = (1.0 / blowup_factor as f64).sqrt();
+    -((1.0 + 0.5 / m) * sqrt_rate).log2()
+}
+
+/// Returns the sum of the soundness errors of the DEEP-ALI protocol and of the commit phase
+/// of FRI, which only depend on the size of the field.
+fn provable_field_error(parameters: &ProofParameters, options: &ProofOptions) -> f64 {
+    let m = JOHNSON_BOUND_PROXIMITY_PARAMETER;
+    let field_size = 2f64.powi(parameters.extension_field_bits() as i32);
+    let rate = 1.0 / options.blowup_factor as f64;
+    let lde_domain_size = lde_domain_size(parameters, options) as f64;
+    let list_size = (m + 0.5) / rate.sqrt();
+
+    // The composition polynomial has degree at most one less than the number of parts times
+    // the trace length, so a wrong out of domain evaluation passes with that many points.
+    let mut composition_parts = parameters.num_composition_poly_parts;
+    if options.zero_knowledge {
+        composition_parts += 1;
+    }
+    let composition_degree = (composition_parts * parameters.trace_length) as f64;
+    let ali_error = list_size / field_size;
+    let deep_error = list_size * composition_degree / field_size;
+
+    let fri_rounds = number_of_fri_layers(parameters, options) as f64;
+    let fri_batching_error =
+        (m + 0.5).powi(7) * lde_domain_size.powi(2) / (2.0 * rate.powf(1.5) * field_size);
+    let fri_folding_error =
+        (2.0 * m + 1.0) * (lde_domain_size + 1.0) * 2.0 * fri_rounds / (rate.sqrt() * field_size);
+
+    ali_error + deep_error + fri_batching_error + fri_folding_error
+}
+
+/// The provable security adds the errors of the DEEP-ALI protocol, of the commit phase of
+/// FRI and of the queries of FRI, where the last one is reduced by the grinding.
+fn provable_security_bits(parameters: &ProofParameters, options: &ProofOptions) -> usize {
+    let query_security = provable_query_security_bits(options.blowup_factor)
+        * options.fri_number_of_queries as f64
+        + options.grinding_factor as f64;
+    let query_error = 2f64.powf(-query_security);
+    to_security_bits(provable_field_error(parameters, options) + query_error)
+        .min(collision_resistance_bits())
+}
+
+fn to_security_bits(error: f64) -> usize {
+    (-error.log2()).floor().max(0.0) as usize
+}
+
+/// Returns the number of times the DEEP composition polynomial is folded, which is the
+/// number of bits of its degree bound.
+fn number_of_fri_layers(parameters: &ProofParameters, options: &ProofOptions) -> usize {
+    parameters.trace_length.trailing_zeros() as usize + options.zero_knowledge as usize
+}
+
+/// Returns the number of foldings of each FRI step, falling back to folding once per layer
+/// when the steps of the options are invalid.
+fn fri_folding_steps(parameters: &ProofParameters, options: &ProofOptions) -> Vec<usize> {
+    let log_degree_bound = number_of_fri_layers(parameters, options);
+    options
+        .fri_folding_steps(log_degree_bound)
+        .unwrap_or_else(|| vec![1; log_degree_bound])
+}
+
+/// Adds the sizes of the commitments, the out of domain evaluations, the openings of the
+/// trace and composition polynomials and the FRI decommitments of a proof.
+fn proof_size_bytes(parameters: &ProofParameters, options: &ProofOptions) -> usize {
+    let base_element_size = parameters.base_field_element_size();
+    let extension_element_size = parameters.extension_field_element_size();
+    let lde_domain_size_bits = lde_domain_size(parameters, options).trailing_zeros() as usize;
+    let has_aux_trace = parameters.num_aux_columns > 0;
+    let mut composition_parts = parameters.num_composition_poly_parts;
+    if options.zero_knowledge {
+        composition_parts += 1;
+    }
+    let fri_steps = fri_folding_steps(parameters, options);
+
+    // Trace length, nonce and commitments to the main trace, auxiliary trace, composition
+    // polynomial and the layers of FRI.
+    let mut size = std::mem::size_of::<usize>();
+    if options.grinding_factor > 0 {
+        size += std::mem::size_of::<u64>();
+    }
+    size += COMMITMENT_SIZE * (2 + has_aux_trace as usize + fri_steps.len() - 1);
+
+    // Out of domain evaluations of the trace and of the composition polynomial parts and
+    // the coefficients of the last layer of FRI.
+    let num_columns = parameters.num_main_columns + parameters.num_aux_columns;
+    size += extension_element_size
+        * (num_columns * parameters.frame_size
+            + composition_parts
+            + options.fri_last_layer_degree_bound);
+
+    // Every pair of symmetric points of the first coset of a query opens the trace at two
+    // leaves and the composition polynomial at a leaf holding both symmetric evaluations,
+    // whose path is sent twice.
+    let mut pair_size = 2 * lde_domain_size_bits * COMMITMENT_SIZE
+        + 2 * parameters.num_main_columns * base_element_size;
+    if has_aux_trace {
+        pair_size += 2 * lde_domain_size_bits * COMMITMENT_SIZE
+            + 2 * parameters.num_aux_columns * extension_element_size;
+    }
+    pair_size += 2 * (lde_domain_size_bits - 1) * COMMITMENT_SIZE
+        + 2 * composition_parts * extension_element_size;
+    let mut query_size = pair_size << (fri_steps[0] - 1);
+
+    // A layer of FRI folded σ times and committed in cosets of 2^s evaluations has leaves
+    // over a domain of size |D_LDE| / 2^(σ + s), and every query opens one of them.
+    let mut number_of_foldings = fri_steps[0];
+    for step in fri_steps[1..].iter() {
+        query_size += lde_domain_size_bits.saturating_sub(number_of_foldings + step)
+            * COMMITMENT_SIZE
+            + ((1 << step) - 1) * extension_element_size;
+        number_of_foldings += step;
+    }
+
+    size + query_size * options.fri_number_of_queries
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::field::{
+        element::FieldElement,
+        fields::fft_friendly::{
+            babybear::Babybear31PrimeField, stark_252_prime_field::Stark252PrimeField,
+        },
+    };
+
+    use crate::{
+        examples::{
+            babybear_fibonacci::{BabybearFibonacciAIR, BabybearFibonacciPublicInputs},
+            fibonacci_rap::{FibonacciRAP, FibonacciRAPPublicInputs},
+            simple_fibonacci::{self, FibonacciPublicInputs},
+        },
+        proof::{
+            errors::InsecureOptionError,
+            options::{ProofOptions, SecurityLevel},
+            stark::StarkProof,
+        },
+        prover::{IsStarkProver, Prover},
+        traits::AIR,
+        transcript::StoneProverTranscript,
+        verifier::{IsStarkVerifier, Verifier},
+    };
+
+    use super::{
+        estimate_security, proof_size_bytes, recommend_proof_options, ProofParameters,
+        SecurityEstimate,
+    };
+
+    type FE = FieldElement<Stark252PrimeField>;
+
+    fn parameters(base_field_bits: usize, extension_degree: usize) -> ProofParameters {
+        ProofParameters {
+            base_field_bits,
+            extension_degree,
+            trace_length: 1 << 20,
+            num_main_columns: 10,
+            num_aux_columns: 2,
+            frame_size: 2,
+            num_composition_poly_parts: 2,
+        }
+    }
+
+    #[test]
+    fn options_for_each_security_level_reach_their_security_level() {
+        let levels = [
+            SecurityLevel::Conjecturable80Bits,
+            SecurityLevel::Conjecturable100Bits,
+            SecurityLevel::Conjecturable128Bits,
+            SecurityLevel::Provable80Bits,
+            SecurityLevel::Provable100Bits,
+            SecurityLevel::Provable128Bits,
+        ];
+        for level in levels {
+            let options = ProofOptions::new_secure(level, 3);
+            let estimate = SecurityEstimate::new(&parameters(252, 1), &options);
+            assert!(estimate.security_bits(&level) >= level.bits());
+        }
+    }
+
+    #[test]
+    fn one_query_less_lowers_the_conjectured_security() {
+        let mut options = ProofOptions::new_secure(SecurityLevel::Conjecturable128Bits, 3);
+        options.fri_number_of_queries -= 1;
+        let estimate = SecurityEstimate::new(&parameters(252, 1), &options);
+        assert!(estimate.conjectured_security_bits < 128);
+    }
+
+    #[test]
+    fn security_of_small_fields_depends_on_the_extension_degree() {
+        let options = ProofOptions::new_secure(SecurityLevel::Conjecturable100Bits, 3);
+        let base_field = SecurityEstimate::new(&parameters(64, 1), &options);
+        let quadratic_extension = SecurityEstimate::new(&parameters(64, 2), &options);
+
+        assert_eq!(base_field.conjectured_security_bits, 64 - 22 - 1);
+        assert!(quadratic_extension.conjectured_security_bits >= 100);
+        assert!(base_field.provable_security_bits < quadratic_extension.provable_security_bits);
+    }
+
+    #[test]
+    fn provable_security_is_lower_than_conjectured_security() {
+        let options = ProofOptions::new_secure(SecurityLevel::Conjecturable128Bits, 3);
+        let estimate = SecurityEstimate::new(&parameters(252, 1), &options);
+        assert!(estimate.provable_security_bits < estimate.conjectured_security_bits);
+    }
+
+    #[test]
+    fn security_is_capped_by_the_collision_resistance_of_the_hash() {
+        let mut options = ProofOptions::new_secure(SecurityLevel::Provable128Bits, 3);
+        options.fri_number_of_queries *= 2;
+        let estimate = SecurityEstimate::new(&parameters(252, 1), &options);
+        assert_eq!(estimate.conjectured_security_bits, 128);
+        assert_eq!(estimate.provable_security_bits, 128);
+    }
+
+    fn assert_estimated_proof_size_matches(fri_step_list: Vec<usize>, last_layer_degree: usize) {
+        let trace_length = 256;
+        let trace = simple_fibonacci::fibonacci_trace([FE::one(), FE::one()], trace_length);
+        let pub_inputs = FibonacciPublicInputs {
+            a0: FE::one(),
+            a1: FE::one(),
+        };
+        let options = ProofOptions {
+            blowup_factor: 4,
+            fri_number_of_queries: 7,
+            coset_offset: 3,
+            grinding_factor: 1,
+            zero_knowledge: false,
+            fri_step_list,
+            fri_last_layer_degree_bound: last_layer_degree,
+        };
+        let proof = Prover::<simple_fibonacci::FibonacciAIR<Stark252PrimeField>>::prove(
+            &trace,
+            &pub_inputs,
+            &options,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+        let air = simple_fibonacci::FibonacciAIR::<Stark252PrimeField>::new(
+            trace_length,
+            &pub_inputs,
+            &options,
+        )
+        .unwrap();
+
+        assert_eq!(
+            proof_size_bytes(&ProofParameters::from_air(&air), &options),
+            proof_data_size(&proof)
+        );
+    }
+
+    #[test]
+    fn estimated_proof_size_matches_the_size_of_the_proof_data() {
+        assert_estimated_proof_size_matches(vec![], 1);
+    }
+
+    #[test]
+    fn estimated_proof_size_matches_the_size_of_the_proof_data_with_fri_steps() {
+        assert_estimated_proof_size_matches(vec![0, 2, 3, 1], 4);
+    }
+
+    fn proof_data_size(proof: &StarkProof<Stark252PrimeField, Stark252PrimeField>) -> usize {
+        let element_size = 32;
+        let hash_size = 32;
+        let openings_size = |openings: &crate::proof::stark::PolynomialOpenings<_>| {
+            (openings.proof.merkle_path.len() + openings.proof_sym.merkle_path.len()) * hash_size
+                + (openings.evaluations.len() + openings.evaluations_sym.len()) * element_size
+        };
+
+        let mut size = 8 + 8 * proof.nonce.is_some() as usize;
+        size += hash_size
+            * (2 + proof.lde_trace_aux_merkle_root.is_some() as usize
+                + proof.fri_layers_merkle_roots.len());
+        size += element_size
+            * (proof.trace_ood_evaluations.data.len()
+                + proof.composition_poly_parts_ood_evaluation.len()
+                + proof.fri_last_layer_coefficients.len());
+        for opening in proof.deep_poly_openings.iter() {
+            size += openings_size(&opening.main_trace_polys);
+            size += opening.aux_trace_polys.as_ref().map_or(0, openings_size);
+            size += openings_size(&opening.composition_poly);
+        }
+        for decommitment in proof.query_list.iter() {
+            size += decommitment
+                .layers_auth_paths
+                .iter()
+                .map(|path| path.merkle_path.len() * hash_size)
+                .sum::<usize>();
+            size += decommitment
+                .layers_evaluations_coset
+                .iter()
+                .map(|coset| coset.len() * element_size)
+                .sum::<usize>();
+        }
+        size
+    }
+
+    #[test]
+    fn estimate_security_of_an_air_uses_its_options() {
+        let pub_inputs = FibonacciRAPPublicInputs {
+            steps: 256,
+            a0: FE::one(),
+            a1: FE::one(),
+        };
+        let options = ProofOptions::new_secure(SecurityLevel::Conjecturable100Bits, 3);
+        let air = FibonacciRAP::new(512, &pub_inputs, &options).unwrap();
+        let estimate = estimate_security(&air);
+        assert!(estimate.conjectured_security_bits >= 100);
+        assert!(estimate.conjectured_security_bits < 128);
+    }
+
+    #[test]
+    fn recommended_options_reach_the_security_level_with_the_smallest_proof() {
+        let trace_length = 1 << 10;
+        let pub_inputs = FibonacciRAPPublicInputs {
+            steps: trace_length / 2,
+            a0: FE::one(),
+            a1: FE::one(),
+        };
+        for level in [
+            SecurityLevel::Conjecturable128Bits,
+            SecurityLevel::Provable100Bits,
+        ] {
+            let options = recommend_proof_options::<FibonacciRAP<Stark252PrimeField>>(
+                trace_length,
+                &pub_inputs,
+                &level,
+                false,
+                3,
+            )
+            .unwrap();
+            let air = FibonacciRAP::new(trace_length, &pub_inputs, &options).unwrap();
+            let estimate = estimate_security(&air);
+            assert!(estimate.security_bits(&level) >= level.bits());
+
+            let mut fewer_queries = options.clone();
+            fewer_queries.fri_number_of_queries -= 1;
+            let air = FibonacciRAP::new(trace_length, &pub_inputs, &fewer_queries).unwrap();
+            assert!(estimate_security(&air).security_bits(&level) < level.bits());
+
+            let default_options = ProofOptions::new_secure(level, 3);
+            let air = FibonacciRAP::new(trace_length, &pub_inputs, &default_options).unwrap();
+            assert!(estimate_security(&air).proof_size_bytes >= estimate.proof_size_bytes);
+        }
+    }
+
+    #[test]
+    fn recommended_options_can_be_used_to_prove() {
+        let trace_length = 1 << 7;
+        let pub_inputs = FibonacciPublicInputs {
+            a0: FE::one(),
+            a1: FE::one(),
+        };
+        let options =
+            recommend_proof_options::<simple_fibonacci::FibonacciAIR<Stark252PrimeField>>(
+                trace_length,
+                &pub_inputs,
+                &SecurityLevel::Conjecturable80Bits,
+                true,
+                3,
+            )
+            .unwrap();
+        assert!(options.blowup_factor >= 4);
+
+        let trace = simple_fibonacci::fibonacci_trace([FE::one(), FE::one()], trace_length);
+        let proof = Prover::<simple_fibonacci::FibonacciAIR<Stark252PrimeField>>::prove(
+            &trace,
+            &pub_inputs,
+            &options,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+        assert!(
+            Verifier::<simple_fibonacci::FibonacciAIR<Stark252PrimeField>>::verify(
+                &proof,
+                &pub_inputs,
+                &options,
+                StoneProverTranscript::new(&[]),
+            )
+        );
+    }
+
+    #[test]
+    fn recommending_options_fails_if_the_field_is_too_small() {
+        let trace_length = 1 << 10;
+        let pub_inputs = FibonacciPublicInputs {
+            a0: FieldElement::<Babybear31PrimeField>::one(),
+            a1: FieldElement::<Babybear31PrimeField>::one(),
+        };
+        let options = recommend_proof_options::<simple_fibonacci::FibonacciAIR<Babybear31PrimeField>>(
+            trace_length,
+            &pub_inputs,
+            &SecurityLevel::Conjecturable80Bits,
+            false,
+            3,
+        );
+        assert!(matches!(options, Err(InsecureOptionError::FieldSize)));
+
+        // The challenges of this AIR are sampled from the quartic extension of Babybear.
+        let pub_inputs = BabybearFibonacciPublicInputs {
+            a0: FieldElement::one(),
+            a1: FieldElement::one(),
+        };
+        let options = recommend_proof_options::<BabybearFibonacciAIR>(
+            trace_length,
+            &pub_inputs,
+            &SecurityLevel::Conjecturable80Bits,
+            false,
+            3,
+        );
+        assert!(options.is_ok());
+    }
+}
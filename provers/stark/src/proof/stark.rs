@@ -1,19 +1,29 @@
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
-use lambdaworks_crypto::merkle_tree::proof::Proof;
+use lambdaworks_crypto::{
+    fiat_shamir::is_transcript::IsTranscript,
+    merkle_tree::{proof::Proof, traits::IsMerkleTreeBackend},
+};
 use lambdaworks_math::{
     field::{
         element::FieldElement,
-        fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+        fields::{
+            fft_friendly::stark_252_prime_field::{
+                MontgomeryConfigStark252PrimeField, Stark252PrimeField,
+            },
+            montgomery_backed_prime_fields::IsModulus,
+        },
         traits::{IsField, IsSubFieldOf},
     },
-    traits::AsBytes,
+    traits::{AsBytes, ByteConversion},
+    unsigned_integer::element::U256,
 };
 
 use crate::{
-    config::Commitment,
+    config::{BatchedMerkleTreeBackend, Commitment, COMMITMENT_SIZE},
     domain::Domain,
-    fri::fri_decommit::FriDecommitment,
+    fri::{self, fri_decommit::FriDecommitment},
+    prover::ProvingError,
     table::Table,
     traits::AIR,
     transcript::StoneProverTranscript,
@@ -34,6 +44,8 @@ pub struct PolynomialOpenings<F: IsField> {
 pub struct DeepPolynomialOpening<F: IsSubFieldOf<E>, E: IsField> {
     pub composition_poly: PolynomialOpenings<E>,
     pub main_trace_polys: PolynomialOpenings<F>,
+    /// Openings of the preprocessed columns, checked against the verifying key.
+    pub preprocessed_trace_polys: Option<PolynomialOpenings<F>>,
     pub aux_trace_polys: Option<PolynomialOpenings<E>>,
 }
 
@@ -57,8 +69,8 @@ pub struct StarkProof<F: IsSubFieldOf<E>, E: IsField> {
     pub composition_poly_parts_ood_evaluation: Vec<FieldElement<E>>,
     // [pₖ]
     pub fri_layers_merkle_roots: Vec<Commitment>,
-    // pₙ
-    pub fri_last_value: FieldElement<E>,
+    // Coefficients of pₙ
+    pub fri_last_layer_coefficients: Vec<FieldElement<E>>,
     // Open(pₖ(Dₖ), −𝜐ₛ^(2ᵏ))
     pub query_list: Vec<FriDecommitment<E>>,
     // Open(H₁(D_LDE, 𝜐ᵢ), Open(H₂(D_LDE, 𝜐ᵢ), Open(tⱼ(D_LDE), 𝜐ᵢ)
@@ -73,14 +85,35 @@ pub struct StarkProof<F: IsSubFieldOf<E>, E: IsField> {
 pub struct StoneCompatibleSerializer;
 
 impl StoneCompatibleSerializer {
+    /// Serializes `proof` in the Stone format. Fails if the AIR can't be built for
+    /// `public_inputs`.
     pub fn serialize_proof<A>(
         proof: &StarkProof<Stark252PrimeField, Stark252PrimeField>,
         public_inputs: &A::PublicInputs,
         options: &ProofOptions,
-    ) -> Vec<u8>
+    ) -> Result<Vec<u8>, ProvingError>
     where
         A: AIR<Field = Stark252PrimeField, FieldExtension = Stark252PrimeField>,
         A::PublicInputs: AsBytes,
+    {
+        Self::serialize_proof_with_transcript::<A>(
+            proof,
+            public_inputs,
+            options,
+            StoneProverTranscript::new(&public_inputs.as_bytes()),
+        )
+    }
+
+    /// Same as `serialize_proof`, for proofs whose transcript didn't start from the bytes of the
+    /// public inputs. `transcript` must be in the same state as the one the prover started with.
+    pub fn serialize_proof_with_transcript<A>(
+        proof: &StarkProof<Stark252PrimeField, Stark252PrimeField>,
+        public_inputs: &A::PublicInputs,
+        options: &ProofOptions,
+        transcript: impl IsTranscript<Stark252PrimeField>,
+    ) -> Result<Vec<u8>, ProvingError>
+    where
+        A: AIR<Field = Stark252PrimeField, FieldExtension = Stark252PrimeField>,
     {
         let mut output = Vec::new();
 
@@ -90,11 +123,23 @@ impl StoneCompatibleSerializer {
         Self::append_fri_commit_phase_commitments(proof, &mut output);
         Self::append_proof_of_work_nonce(proof, &mut output);
 
-        let fri_query_indexes = Self::get_fri_query_indexes::<A>(proof, public_inputs, options);
-        Self::append_fri_query_phase_first_layer(proof, &fri_query_indexes, &mut output);
-        Self::append_fri_query_phase_inner_layers(proof, &fri_query_indexes, &mut output);
+        let air = A::new(proof.trace_length, public_inputs, options)?;
+        // The proofs of options with invalid FRI steps are serialized as if each layer had been
+        // folded one time.
+        let fri_steps = fri::fri_folding_steps(&air)
+            .unwrap_or_else(|| vec![1; proof.fri_layers_merkle_roots.len() + 1]);
+        let fri_query_indexes = Self::get_fri_query_indexes(&air, proof, transcript);
+        let first_layer_pair_indexes =
+            Verifier::<A>::first_layer_pair_indexes(&fri_query_indexes, fri_steps[0]);
+        Self::append_fri_query_phase_first_layer(proof, &first_layer_pair_indexes, &mut output);
+        Self::append_fri_query_phase_inner_layers(
+            proof,
+            &fri_steps,
+            &fri_query_indexes,
+            &mut output,
+        );
 
-        output
+        Ok(output)
     }
 
     /// Appends the root bytes of the Merkle tree for the main trace, and if there is a RAP round,
@@ -142,7 +187,8 @@ impl StoneCompatibleSerializer {
         }
     }
 
-    /// Appends the commitments to the inner layers of FRI followed by the element of the last layer.
+    /// Appends the commitments to the inner layers of FRI followed by the coefficients of the last
+    /// layer.
     fn append_fri_commit_phase_commitments(
         proof: &StarkProof<Stark252PrimeField, Stark252PrimeField>,
         output: &mut Vec<u8>,
@@ -156,7 +202,9 @@ impl StoneCompatibleSerializer {
                 .collect::<Vec<_>>(),
         );
 
-        output.extend_from_slice(&proof.fri_last_value.as_bytes());
+        for coefficient in proof.fri_last_layer_coefficients.iter() {
+            output.extend_from_slice(&coefficient.as_bytes());
+        }
     }
 
     /// Appends the proof of work nonce in case there is one. There could be none if the `grinding_factor`
@@ -173,8 +221,10 @@ impl StoneCompatibleSerializer {
     /// Appends the values and authentication paths of the trace and composition polynomial parts
     /// needed for the first layer of FRI. Next we describe the order in which these are appended.
     ///
-    /// Each FRI query index `i` determines a pair of elements `d_i` and `-d_i` on the domain of the
-    /// first layer.
+    /// Each FRI query index determines a coset of the domain of the first layer, which is folded into
+    /// the query by the first step of FRI. The coset is made of pairs of elements `d_i` and `-d_i`,
+    /// and `fri_pair_indexes` are the indexes `i` of the pairs of all the queries. When the first
+    /// layer is folded one time, there is one pair per query and its index is the query index.
     /// Let BT_i be the concatenation of the bytes of the following values
     /// t_1(d_i), t_2(d_i), ..., t_m(d_i), t_1(-d_i), t_2(-d_i), ..., t_m(-d_i),
     /// where m is the total number of columns, including RAP extended ones.
@@ -182,7 +232,7 @@ impl StoneCompatibleSerializer {
     /// H_1(d_i), ..., H_s(d_i), H_1(-d_i), ..., H_s(-d_i),
     /// where s is the number of parts into which the composition polynomial was broken.
     ///
-    /// If i_1, ..., i_k are all the FRI query indexes sorted in increasing order and without repeated
+    /// If i_1, ..., i_k are all the pair indexes sorted in increasing order and without repeated
     /// values, then this method appends the following to the output:
     ///
     /// BT_{i_1} | BT_{i_2} | ... | BT_{i_k} | TraceMergedPaths | BH_{i_1} | BH_{i_2} | ... | B_{i_k} | CompositionMergedPaths.
@@ -197,13 +247,13 @@ impl StoneCompatibleSerializer {
     /// `BT_1 | BT_2 | BT_3 | BT_5 | TraceMergedPaths | BH_1 | BH_2 | BH_3 | BH_5 | CompositionMergedPaths`
     fn append_fri_query_phase_first_layer(
         proof: &StarkProof<Stark252PrimeField, Stark252PrimeField>,
-        fri_query_indexes: &[usize],
+        fri_pair_indexes: &[usize],
         output: &mut Vec<u8>,
     ) {
         let mut fri_first_layer_openings: Vec<_> = proof
             .deep_poly_openings
             .iter()
-            .zip(fri_query_indexes.iter())
+            .zip(fri_pair_indexes.iter())
             .collect();
         // Remove repeated values
         let mut seen = HashSet::new();
@@ -232,7 +282,7 @@ impl StoneCompatibleSerializer {
             }
         }
 
-        let fri_trace_query_indexes: Vec<_> = fri_query_indexes
+        let fri_trace_query_indexes: Vec<_> = fri_pair_indexes
             .iter()
             .flat_map(|query| vec![query * 2, query * 2 + 1])
             .collect();
@@ -289,7 +339,7 @@ impl StoneCompatibleSerializer {
             .iter()
             .map(|opening| &opening.composition_poly.proof)
             .collect();
-        let nodes = Self::merge_authentication_paths(&fri_composition_paths, fri_query_indexes);
+        let nodes = Self::merge_authentication_paths(&fri_composition_paths, fri_pair_indexes);
         for node in nodes.iter() {
             output.extend_from_slice(node);
         }
@@ -303,71 +353,55 @@ impl StoneCompatibleSerializer {
     /// a repeated query, or we are sending a field element that the verifier could simply
     /// derive from values from previous layers.
     ///
-    /// For each layer i there are:
-    /// - X_i = { p_i(-d_j), p_i(d_j) for all queries j }, the elements the verifier needs.
+    /// For each layer i, which the next step of FRI folds in cosets, there are:
+    /// - X_i = { p_i(d) for all d in the coset of d_j for all queries j }, the elements the
+    ///         verifier needs.
     /// - Y_i = { p_i( d_j) for all queries j }, the elements that the verifier computes from
     ///         previous layers.
     /// - Z_i = X_i - Y_i, the elements that the verifier needs but cannot compute from previous layers.
-    ///         sorted by increasing value of query.  
-    /// - MergedPathsLayer_i: the merged authentication paths for all p_i(-d_j) and p_i(d_j).
+    ///         sorted by increasing value of query.
+    /// - MergedPathsLayer_i: the merged authentication paths for the cosets of all d_j.
     ///
     /// This method appends:
     ///
     /// Z_1 | MergedPathsLayer_1 | Z_2 | MergedPathsLayer_2 | ... | Z_n | MergedPathsLayer_n,
     ///
-    /// where n is the total number of FRI layers.
+    /// where n is the total number of FRI layers. `fri_steps` are the number of times each layer
+    /// is folded in two, so the cosets of the layer i have `2^fri_steps[i]` elements.
     fn append_fri_query_phase_inner_layers(
         proof: &StarkProof<Stark252PrimeField, Stark252PrimeField>,
+        fri_steps: &[usize],
         fri_query_indexes: &[usize],
         output: &mut Vec<u8>,
     ) {
-        let mut fri_layers_evaluations: HashMap<(u64, usize, usize), FieldElement<_>> =
-            HashMap::new();
-        for (decommitment, query_index) in proof.query_list.iter().zip(fri_query_indexes.iter()) {
-            let mut query_layer_index = *query_index;
-            for (i, element) in decommitment.layers_evaluations_sym.iter().enumerate() {
-                fri_layers_evaluations.insert(
-                    (
-                        i as u64,
-                        query_layer_index >> 1,
-                        (query_layer_index + 1) % 2,
-                    ),
-                    *element,
-                );
-                query_layer_index >>= 1;
+        let mut indexes_previous_layer = fri_query_indexes.to_owned();
+        for (i, step) in fri_steps[1..].iter().enumerate() {
+            // Compute set X_i - Y_i from the decommitments of the queries
+            let mut fri_layer_evaluations: BTreeMap<usize, FieldElement<_>> = BTreeMap::new();
+            for (decommitment, index) in proof.query_list.iter().zip(&indexes_previous_layer) {
+                let coset_start = (index >> step) << step;
+                let positions =
+                    (coset_start..coset_start + (1 << step)).filter(|position| position != index);
+                for (position, element) in positions.zip(&decommitment.layers_evaluations_coset[i])
+                {
+                    fri_layer_evaluations.insert(position, *element);
+                }
             }
-        }
 
-        let mut indexes_previous_layer = fri_query_indexes.to_owned();
-        for i in 0..proof.query_list[0].layers_evaluations_sym.len() {
             // Compute set Y_i
-            let reconstructed_row_col: BTreeSet<_> = indexes_previous_layer
-                .iter()
-                .map(|index| (index >> 1, index % 2))
-                .collect();
-
-            // Compute set X_i
-            let reconstructed_row_col_sym: BTreeSet<_> = reconstructed_row_col
-                .iter()
-                .map(|(x, y)| (*x, 1 - y))
-                .collect();
-
-            // Compute set Z_i
-            let row_col_to_send: Vec<_> = reconstructed_row_col_sym
-                .difference(&reconstructed_row_col)
-                .collect();
+            let reconstructed: BTreeSet<_> = indexes_previous_layer.iter().copied().collect();
 
             // Append Z_i
-            for element in row_col_to_send
+            for (_, element) in fri_layer_evaluations
                 .iter()
-                .map(|(row, col)| &fri_layers_evaluations[&(i as u64, *row, *col)])
+                .filter(|(position, _)| !reconstructed.contains(position))
             {
                 output.extend_from_slice(&element.as_bytes());
             }
 
             indexes_previous_layer = indexes_previous_layer
                 .iter()
-                .map(|index| index >> 1)
+                .map(|index| index >> step)
                 .collect();
 
             let layer_auth_paths: Vec<_> = proof
@@ -427,15 +461,22 @@ impl StoneCompatibleSerializer {
             }
         }
 
+        // Paths of different lengths don't belong to the same tree. Their missing nodes are
+        // skipped, so that the resulting proof is rejected by the verifier.
         let mut result = Vec::new();
         let mut level_indexes: BTreeSet<usize> = leaf_indexes.iter().copied().collect();
-        let merkle_tree_height = authentication_paths[0].merkle_path.len();
+        let merkle_tree_height = authentication_paths
+            .iter()
+            .map(|path| path.merkle_path.len())
+            .max()
+            .unwrap_or(0);
         for tree_level in 0..merkle_tree_height {
             for node_index in level_indexes.iter() {
                 let sibling_index = node_index ^ 1;
                 if !level_indexes.contains(&sibling_index) {
-                    let node = &merkle_tree[&(tree_level, sibling_index)];
-                    result.push(*node);
+                    if let Some(node) = merkle_tree.get(&(tree_level, sibling_index)) {
+                        result.push(*node);
+                    }
                 }
             }
             level_indexes = level_indexes.iter().map(|index| *index >> 1).collect();
@@ -443,19 +484,16 @@ impl StoneCompatibleSerializer {
         result
     }
     fn get_fri_query_indexes<A>(
+        air: &A,
         proof: &StarkProof<Stark252PrimeField, Stark252PrimeField>,
-        public_inputs: &A::PublicInputs,
-        proof_options: &ProofOptions,
+        mut transcript: impl IsTranscript<Stark252PrimeField>,
     ) -> Vec<usize>
     where
         A: AIR<Field = Stark252PrimeField, FieldExtension = Stark252PrimeField>,
-        A::PublicInputs: AsBytes,
     {
-        let mut transcript = StoneProverTranscript::new(&public_inputs.as_bytes());
-        let air = A::new(proof.trace_length, public_inputs, proof_options);
-        let domain = Domain::<Stark252PrimeField>::new(&air);
+        let domain = Domain::<Stark252PrimeField>::new(air);
         let challenges = Verifier::step_1_replay_rounds_and_recover_challenges(
-            &air,
+            air,
             proof,
             &domain,
             &mut transcript,
@@ -464,15 +502,476 @@ impl StoneCompatibleSerializer {
     }
 }
 
+#[derive(Debug, PartialEq, Eq)]
+pub enum StoneDeserializationError {
+    /// The proof ended before all the expected values were read.
+    NotEnoughBytes,
+    /// The proof has bytes left after all the expected values were read.
+    TrailingBytes(usize),
+    /// A field element is not smaller than the modulus.
+    InvalidFieldElement,
+    /// The AIR has a trace layout that can't be expressed in the Stone format.
+    UnsupportedAir(String),
+    /// A node of a Merkle tree can't be recovered from the authentication paths of the proof.
+    InvalidAuthenticationPath,
+    /// The FRI steps of the proof options don't fold the DEEP composition polynomial down to the
+    /// degree bound of the last layer.
+    InvalidFriSteps,
+}
+
+/// Deserializer of proofs in the format of the Stone prover
+/// (https://github.com/starkware-libs/stone-prover/). It is the inverse of
+/// `StoneCompatibleSerializer`.
+///
+/// The Stone format only sends what the verifier can't compute by itself: Merkle paths are merged,
+/// repeated queries are sent once and the values of the inner layers of FRI that follow from the
+/// previous layers are omitted. The deserializer replays the verifier to recover them, so it needs
+/// the same AIR, public inputs and options the proof was generated with, and a transcript in the
+/// same state as the one the prover started with.
+pub struct StoneCompatibleDeserializer;
+
+/// Reads the values of a Stone proof in order.
+struct StoneProofReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> StoneProofReader<'a> {
+    fn read_bytes(&mut self, length: usize) -> Result<&'a [u8], StoneDeserializationError> {
+        if self.bytes.len() < length {
+            return Err(StoneDeserializationError::NotEnoughBytes);
+        }
+        let (value, rest) = self.bytes.split_at(length);
+        self.bytes = rest;
+        Ok(value)
+    }
+
+    fn read_commitment(&mut self) -> Result<Commitment, StoneDeserializationError> {
+        let mut commitment = [0; COMMITMENT_SIZE];
+        commitment.copy_from_slice(self.read_bytes(COMMITMENT_SIZE)?);
+        Ok(commitment)
+    }
+
+    /// Reads a field element in the same representation `AsBytes` writes it, which is Montgomery
+    /// form.
+    fn read_field_element(
+        &mut self,
+    ) -> Result<FieldElement<Stark252PrimeField>, StoneDeserializationError> {
+        let value = U256::from_bytes_be(self.read_bytes(32)?)
+            .map_err(|_| StoneDeserializationError::InvalidFieldElement)?;
+        if value >= MontgomeryConfigStark252PrimeField::MODULUS {
+            return Err(StoneDeserializationError::InvalidFieldElement);
+        }
+        Ok(FieldElement::from_raw(value))
+    }
+
+    fn read_field_elements(
+        &mut self,
+        length: usize,
+    ) -> Result<Vec<FieldElement<Stark252PrimeField>>, StoneDeserializationError> {
+        (0..length).map(|_| self.read_field_element()).collect()
+    }
+
+    fn read_u64(&mut self) -> Result<u64, StoneDeserializationError> {
+        let mut value = [0; 8];
+        value.copy_from_slice(self.read_bytes(8)?);
+        Ok(u64::from_be_bytes(value))
+    }
+}
+
+/// The values of the first layer of FRI opened at the query `q`: the main and auxiliary trace
+/// columns and the composition polynomial parts at `d_q` and `-d_q`.
+struct FirstLayerOpening {
+    main: Vec<FieldElement<Stark252PrimeField>>,
+    main_sym: Vec<FieldElement<Stark252PrimeField>>,
+    aux: Vec<FieldElement<Stark252PrimeField>>,
+    aux_sym: Vec<FieldElement<Stark252PrimeField>>,
+    composition: Vec<FieldElement<Stark252PrimeField>>,
+    composition_sym: Vec<FieldElement<Stark252PrimeField>>,
+}
+
+impl StoneCompatibleDeserializer {
+    /// Parses the bytes of a proof of a trace of length `trace_length` in the Stone format.
+    pub fn deserialize_proof<A>(
+        bytes: &[u8],
+        trace_length: usize,
+        public_inputs: &A::PublicInputs,
+        options: &ProofOptions,
+        mut transcript: impl IsTranscript<Stark252PrimeField>,
+    ) -> Result<StarkProof<Stark252PrimeField, Stark252PrimeField>, StoneDeserializationError>
+    where
+        A: AIR<Field = Stark252PrimeField, FieldExtension = Stark252PrimeField>,
+    {
+        let air = match A::new(trace_length, public_inputs, options) {
+            Ok(air) => air,
+            Err(ProvingError::WrongParameter(message)) => {
+                return Err(StoneDeserializationError::UnsupportedAir(message))
+            }
+        };
+        if air.num_preprocessed_columns() > 0 {
+            return Err(StoneDeserializationError::UnsupportedAir(
+                "the Stone format has no preprocessed columns".to_string(),
+            ));
+        }
+        let domain = Domain::<Stark252PrimeField>::new(&air);
+        let (num_main_columns, num_aux_columns) = air.trace_layout();
+        let mut reader = StoneProofReader { bytes };
+
+        // Commitments, out of domain evaluations and FRI commit phase.
+        let lde_trace_main_merkle_root = reader.read_commitment()?;
+        let lde_trace_aux_merkle_root = if num_aux_columns > 0 {
+            Some(reader.read_commitment()?)
+        } else {
+            None
+        };
+        let composition_poly_root = reader.read_commitment()?;
+
+        let frame_size = air.context().transition_offsets.len();
+        let trace_ood_columns = (0..num_main_columns + num_aux_columns)
+            .map(|_| reader.read_field_elements(frame_size))
+            .collect::<Result<Vec<_>, _>>()?;
+        let composition_poly_parts_ood_evaluation =
+            reader.read_field_elements(air.num_composition_poly_parts())?;
+
+        let fri_steps =
+            fri::fri_folding_steps(&air).ok_or(StoneDeserializationError::InvalidFriSteps)?;
+        let fri_layers_merkle_roots = (1..fri_steps.len())
+            .map(|_| reader.read_commitment())
+            .collect::<Result<Vec<_>, _>>()?;
+        let fri_last_layer_coefficients =
+            reader.read_field_elements(options.fri_last_layer_degree_bound)?;
+
+        let nonce = if options.grinding_factor > 0 {
+            Some(reader.read_u64()?)
+        } else {
+            None
+        };
+
+        let mut proof = StarkProof {
+            trace_length,
+            lde_trace_main_merkle_root,
+            lde_trace_aux_merkle_root,
+            trace_ood_evaluations: Table::from_columns(trace_ood_columns),
+            composition_poly_root,
+            composition_poly_parts_ood_evaluation,
+            fri_layers_merkle_roots,
+            fri_last_layer_coefficients,
+            query_list: Vec::new(),
+            deep_poly_openings: Vec::new(),
+            nonce,
+        };
+
+        // The query indexes only depend on what was read so far.
+        let challenges = Verifier::step_1_replay_rounds_and_recover_challenges(
+            &air,
+            &proof,
+            &domain,
+            &mut transcript,
+        );
+        let iotas = &challenges.iotas;
+        let pair_indexes = Verifier::<A>::first_layer_pair_indexes(iotas, fri_steps[0]);
+        let pairs: BTreeSet<usize> = pair_indexes.iter().copied().collect();
+        let lde_log_size = domain.lde_roots_of_unity_coset.len().trailing_zeros() as usize;
+
+        // FRI query phase, first layer.
+        let mut openings: BTreeMap<usize, FirstLayerOpening> = BTreeMap::new();
+        for pair in pairs.iter() {
+            let main = reader.read_field_elements(num_main_columns)?;
+            let aux = reader.read_field_elements(num_aux_columns)?;
+            let main_sym = reader.read_field_elements(num_main_columns)?;
+            let aux_sym = reader.read_field_elements(num_aux_columns)?;
+            openings.insert(
+                *pair,
+                FirstLayerOpening {
+                    main,
+                    main_sym,
+                    aux,
+                    aux_sym,
+                    composition: Vec::new(),
+                    composition_sym: Vec::new(),
+                },
+            );
+        }
+
+        let main_leaves = openings
+            .iter()
+            .flat_map(|(pair, opening)| {
+                [(pair * 2, &opening.main), (pair * 2 + 1, &opening.main_sym)]
+            })
+            .map(|(index, leaf)| {
+                (
+                    index,
+                    BatchedMerkleTreeBackend::<Stark252PrimeField>::hash_data(leaf),
+                )
+            })
+            .collect();
+        let main_paths =
+            Self::unmerge_authentication_paths(main_leaves, lde_log_size, &mut reader)?;
+
+        let aux_paths = if num_aux_columns > 0 {
+            let aux_leaves = openings
+                .iter()
+                .flat_map(|(pair, opening)| {
+                    [(pair * 2, &opening.aux), (pair * 2 + 1, &opening.aux_sym)]
+                })
+                .map(|(index, leaf)| {
+                    (
+                        index,
+                        BatchedMerkleTreeBackend::<Stark252PrimeField>::hash_data(leaf),
+                    )
+                })
+                .collect();
+            Some(Self::unmerge_authentication_paths(
+                aux_leaves,
+                lde_log_size,
+                &mut reader,
+            )?)
+        } else {
+            None
+        };
+
+        let number_of_parts = air.num_composition_poly_parts();
+        for opening in openings.values_mut() {
+            opening.composition = reader.read_field_elements(number_of_parts)?;
+            opening.composition_sym = reader.read_field_elements(number_of_parts)?;
+        }
+
+        let composition_leaves = openings
+            .iter()
+            .map(|(pair, opening)| {
+                let mut leaf = opening.composition.clone();
+                leaf.extend_from_slice(&opening.composition_sym);
+                (
+                    *pair,
+                    BatchedMerkleTreeBackend::<Stark252PrimeField>::hash_data(&leaf),
+                )
+            })
+            .collect();
+        let composition_paths =
+            Self::unmerge_authentication_paths(composition_leaves, lde_log_size - 1, &mut reader)?;
+
+        proof.deep_poly_openings = pair_indexes
+            .iter()
+            .map(|iota| {
+                let opening = &openings[iota];
+                let path = |paths: &BTreeMap<usize, Proof<Commitment>>, index: usize| {
+                    paths[&index].clone()
+                };
+                DeepPolynomialOpening {
+                    composition_poly: PolynomialOpenings {
+                        proof: path(&composition_paths, *iota),
+                        proof_sym: path(&composition_paths, *iota),
+                        evaluations: opening.composition.clone(),
+                        evaluations_sym: opening.composition_sym.clone(),
+                    },
+                    main_trace_polys: PolynomialOpenings {
+                        proof: path(&main_paths, iota * 2),
+                        proof_sym: path(&main_paths, iota * 2 + 1),
+                        evaluations: opening.main.clone(),
+                        evaluations_sym: opening.main_sym.clone(),
+                    },
+                    preprocessed_trace_polys: None,
+                    aux_trace_polys: aux_paths.as_ref().map(|aux_paths| PolynomialOpenings {
+                        proof: path(aux_paths, iota * 2),
+                        proof_sym: path(aux_paths, iota * 2 + 1),
+                        evaluations: opening.aux.clone(),
+                        evaluations_sym: opening.aux_sym.clone(),
+                    }),
+                }
+            })
+            .collect();
+
+        // FRI query phase, inner layers. The verifier computes the evaluation of each layer at the
+        // query by folding the coset of the previous one, starting from the deep composition
+        // polynomial.
+        let (deep_poly_evaluations, deep_poly_evaluations_sym) =
+            Verifier::reconstruct_deep_composition_poly_evaluations_for_all_queries(
+                &air,
+                &challenges,
+                &domain,
+                &proof,
+                &pair_indexes,
+            );
+        let pairs_per_query = 1 << (fri_steps[0] - 1);
+        let mut evaluations: Vec<_> = iotas
+            .iter()
+            .enumerate()
+            .map(|(i, iota)| {
+                let pairs = i * pairs_per_query..(i + 1) * pairs_per_query;
+                let coset_evaluations = deep_poly_evaluations[pairs.clone()]
+                    .iter()
+                    .zip(&deep_poly_evaluations_sym[pairs])
+                    .flat_map(|(evaluation, evaluation_sym)| [*evaluation, *evaluation_sym])
+                    .collect();
+                let points_inv =
+                    Verifier::<A>::fri_coset_points_inv(&domain, *iota, fri_steps[0], 0);
+                fri::fold_coset(coset_evaluations, points_inv, &challenges.zetas[0])
+            })
+            .collect();
+        let mut indexes = iotas.clone();
+        let mut number_of_foldings = fri_steps[0];
+        let mut query_list: Vec<FriDecommitment<Stark252PrimeField>> = iotas
+            .iter()
+            .map(|_| FriDecommitment {
+                layers_auth_paths: Vec::new(),
+                layers_evaluations_coset: Vec::new(),
+            })
+            .collect();
+
+        for (i, step) in fri_steps[1..].iter().enumerate() {
+            let coset_size = 1 << step;
+            // The elements Y_i computed by the verifier, followed by the elements Z_i sent.
+            let mut layer: BTreeMap<usize, FieldElement<Stark252PrimeField>> = indexes
+                .iter()
+                .copied()
+                .zip(evaluations.iter().cloned())
+                .collect();
+            let coset_indexes: BTreeSet<usize> = layer.keys().map(|index| index >> step).collect();
+            let positions_sent: Vec<usize> = coset_indexes
+                .iter()
+                .flat_map(|coset_index| (coset_index << step)..((coset_index + 1) << step))
+                .filter(|position| !layer.contains_key(position))
+                .collect();
+            for position in positions_sent {
+                layer.insert(position, reader.read_field_element()?);
+            }
+
+            let coset = |coset_index: usize| -> Vec<_> {
+                ((coset_index << step)..((coset_index + 1) << step))
+                    .map(|position| layer[&position])
+                    .collect()
+            };
+            let leaves = coset_indexes
+                .iter()
+                .map(|coset_index| {
+                    (
+                        *coset_index,
+                        BatchedMerkleTreeBackend::<Stark252PrimeField>::hash_data(&coset(
+                            *coset_index,
+                        )),
+                    )
+                })
+                .collect();
+            let tree_height = lde_log_size - number_of_foldings - step;
+            let paths = Self::unmerge_authentication_paths(leaves, tree_height, &mut reader)?;
+
+            for ((decommitment, index), evaluation) in query_list
+                .iter_mut()
+                .zip(indexes.iter_mut())
+                .zip(evaluations.iter_mut())
+            {
+                let coset_index = *index >> step;
+                let coset_evaluations = coset(coset_index);
+                decommitment.layers_evaluations_coset.push(
+                    coset_evaluations
+                        .iter()
+                        .enumerate()
+                        .filter(|(position, _)| *position != *index & (coset_size - 1))
+                        .map(|(_, element)| *element)
+                        .collect(),
+                );
+                decommitment
+                    .layers_auth_paths
+                    .push(paths[&coset_index].clone());
+
+                let points_inv = Verifier::<A>::fri_coset_points_inv(
+                    &domain,
+                    coset_index,
+                    *step,
+                    number_of_foldings,
+                );
+                *evaluation =
+                    fri::fold_coset(coset_evaluations, points_inv, &challenges.zetas[i + 1]);
+                *index = coset_index;
+            }
+            number_of_foldings += step;
+        }
+        proof.query_list = query_list;
+
+        if !reader.bytes.is_empty() {
+            return Err(StoneDeserializationError::TrailingBytes(reader.bytes.len()));
+        }
+
+        Ok(proof)
+    }
+
+    /// Inverse of `StoneCompatibleSerializer::merge_authentication_paths`. Reads from `reader` the
+    /// nodes of a Merkle tree of height `tree_height` needed to reach the root from all the
+    /// `leaves`, given as pairs of leaf index and leaf hash, and returns the authentication path
+    /// of each of them. The nodes that were not sent are computed from the leaves.
+    fn unmerge_authentication_paths(
+        leaves: BTreeMap<usize, Commitment>,
+        tree_height: usize,
+        reader: &mut StoneProofReader,
+    ) -> Result<BTreeMap<usize, Proof<Commitment>>, StoneDeserializationError> {
+        let mut merkle_tree: HashMap<(usize, usize), Commitment> = leaves
+            .iter()
+            .map(|(index, leaf)| ((0, *index), *leaf))
+            .collect();
+
+        let mut level_indexes: BTreeSet<usize> = leaves.keys().copied().collect();
+        for tree_level in 0..tree_height {
+            for node_index in level_indexes.iter() {
+                let sibling_index = node_index ^ 1;
+                if !level_indexes.contains(&sibling_index) {
+                    merkle_tree.insert((tree_level, sibling_index), reader.read_commitment()?);
+                }
+            }
+            level_indexes = level_indexes.iter().map(|index| *index >> 1).collect();
+            for parent_index in level_indexes.iter() {
+                let parent = BatchedMerkleTreeBackend::<Stark252PrimeField>::hash_new_parent(
+                    Self::get_node(&merkle_tree, tree_level, parent_index * 2)?,
+                    Self::get_node(&merkle_tree, tree_level, parent_index * 2 + 1)?,
+                );
+                merkle_tree.insert((tree_level + 1, *parent_index), parent);
+            }
+        }
+
+        leaves
+            .keys()
+            .map(|leaf_index| {
+                let merkle_path = (0..tree_height)
+                    .map(|tree_level| {
+                        Self::get_node(&merkle_tree, tree_level, (leaf_index >> tree_level) ^ 1)
+                            .copied()
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok((*leaf_index, Proof { merkle_path }))
+            })
+            .collect()
+    }
+
+    fn get_node(
+        merkle_tree: &HashMap<(usize, usize), Commitment>,
+        tree_level: usize,
+        node_index: usize,
+    ) -> Result<&Commitment, StoneDeserializationError> {
+        merkle_tree
+            .get(&(tree_level, node_index))
+            .ok_or(StoneDeserializationError::InvalidAuthenticationPath)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use lambdaworks_math::{field::element::FieldElement, traits::AsBytes};
+    use lambdaworks_math::{
+        field::{
+            element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+        },
+        traits::AsBytes,
+    };
+    use rstest::rstest;
 
     use crate::{
         examples::fibonacci_2_cols_shifted::{self, Fibonacci2ColsShifted},
-        proof::{options::ProofOptions, stark::StoneCompatibleSerializer},
+        proof::{
+            options::ProofOptions,
+            stark::{
+                StoneCompatibleDeserializer, StoneCompatibleSerializer, StoneDeserializationError,
+            },
+        },
         prover::{IsStarkProver, Prover},
         transcript::StoneProverTranscript,
+        verifier::{IsStarkVerifier, Verifier},
     };
 
     #[test]
@@ -486,6 +985,9 @@ mod tests {
             coset_offset: 3,
             grinding_factor: 0,
             fri_number_of_queries: 1,
+            zero_knowledge: false,
+            fri_step_list: vec![],
+            fri_last_layer_degree_bound: 1,
         };
 
         let pub_inputs = fibonacci_2_cols_shifted::PublicInputs {
@@ -545,11 +1047,13 @@ mod tests {
             117, 87, 201,
         ];
 
-        let serialized_proof = StoneCompatibleSerializer::serialize_proof::<Fibonacci2ColsShifted<_>>(
-            &proof,
-            &pub_inputs,
-            &proof_options,
-        );
+        let serialized_proof =
+            StoneCompatibleSerializer::serialize_proof::<Fibonacci2ColsShifted<_>>(
+                &proof,
+                &pub_inputs,
+                &proof_options,
+            )
+            .unwrap();
         assert_eq!(serialized_proof, expected_bytes);
     }
 
@@ -564,6 +1068,9 @@ mod tests {
             coset_offset: 3,
             grinding_factor: 0,
             fri_number_of_queries: 10,
+            zero_knowledge: false,
+            fri_step_list: vec![],
+            fri_last_layer_degree_bound: 1,
         };
 
         let pub_inputs = fibonacci_2_cols_shifted::PublicInputs {
@@ -637,11 +1144,13 @@ mod tests {
             202, 193, 129, 242,
         ];
 
-        let serialized_proof = StoneCompatibleSerializer::serialize_proof::<Fibonacci2ColsShifted<_>>(
-            &proof,
-            &pub_inputs,
-            &proof_options,
-        );
+        let serialized_proof =
+            StoneCompatibleSerializer::serialize_proof::<Fibonacci2ColsShifted<_>>(
+                &proof,
+                &pub_inputs,
+                &proof_options,
+            )
+            .unwrap();
         assert_eq!(serialized_proof, expected_bytes);
     }
 
@@ -656,6 +1165,9 @@ mod tests {
             coset_offset: 3,
             grinding_factor: 0,
             fri_number_of_queries: 1,
+            zero_knowledge: false,
+            fri_step_list: vec![],
+            fri_last_layer_degree_bound: 1,
         };
 
         let pub_inputs = fibonacci_2_cols_shifted::PublicInputs {
@@ -901,11 +1413,13 @@ mod tests {
             183, 47, 228, 161, 87, 75, 132, 11, 107, 45, 45, 160, 169, 115, 73, 0, 14, 163,
         ];
 
-        let serialized_proof = StoneCompatibleSerializer::serialize_proof::<Fibonacci2ColsShifted<_>>(
-            &proof,
-            &pub_inputs,
-            &proof_options,
-        );
+        let serialized_proof =
+            StoneCompatibleSerializer::serialize_proof::<Fibonacci2ColsShifted<_>>(
+                &proof,
+                &pub_inputs,
+                &proof_options,
+            )
+            .unwrap();
         assert_eq!(serialized_proof, expected_bytes);
     }
 
@@ -920,6 +1434,9 @@ mod tests {
             coset_offset: 3,
             grinding_factor: 0,
             fri_number_of_queries: 2,
+            zero_knowledge: false,
+            fri_step_list: vec![],
+            fri_last_layer_degree_bound: 1,
         };
 
         let pub_inputs = fibonacci_2_cols_shifted::PublicInputs {
@@ -979,11 +1496,13 @@ mod tests {
             134, 72, 157, 118, 238, 0, 156,
         ];
 
-        let serialized_proof = StoneCompatibleSerializer::serialize_proof::<Fibonacci2ColsShifted<_>>(
-            &proof,
-            &pub_inputs,
-            &proof_options,
-        );
+        let serialized_proof =
+            StoneCompatibleSerializer::serialize_proof::<Fibonacci2ColsShifted<_>>(
+                &proof,
+                &pub_inputs,
+                &proof_options,
+            )
+            .unwrap();
         assert_eq!(serialized_proof, expected_bytes);
     }
 
@@ -998,6 +1517,9 @@ mod tests {
             coset_offset: 3,
             grinding_factor: 0,
             fri_number_of_queries: 3,
+            zero_knowledge: false,
+            fri_step_list: vec![],
+            fri_last_layer_degree_bound: 1,
         };
 
         let pub_inputs = fibonacci_2_cols_shifted::PublicInputs {
@@ -1214,11 +1736,263 @@ mod tests {
             210, 33, 191, 114, 98, 40, 235, 19, 219, 101, 88, 189,
         ];
 
-        let serialized_proof = StoneCompatibleSerializer::serialize_proof::<Fibonacci2ColsShifted<_>>(
+        let serialized_proof =
+            StoneCompatibleSerializer::serialize_proof::<Fibonacci2ColsShifted<_>>(
+                &proof,
+                &pub_inputs,
+                &proof_options,
+            )
+            .unwrap();
+        assert_eq!(serialized_proof, expected_bytes);
+    }
+
+    fn stone_proof_bytes(
+        trace_length: usize,
+        claimed_index: usize,
+        proof_options: &ProofOptions,
+    ) -> (
+        Vec<u8>,
+        fibonacci_2_cols_shifted::PublicInputs<Stark252PrimeField>,
+    ) {
+        let trace = fibonacci_2_cols_shifted::compute_trace(FieldElement::one(), trace_length);
+        let pub_inputs = fibonacci_2_cols_shifted::PublicInputs {
+            claimed_value: trace.get_row(claimed_index)[0],
+            claimed_index,
+        };
+
+        let proof = Prover::<Fibonacci2ColsShifted<_>>::prove(
+            &trace,
+            &pub_inputs,
+            proof_options,
+            StoneProverTranscript::new(&pub_inputs.as_bytes()),
+        )
+        .unwrap();
+
+        let bytes = StoneCompatibleSerializer::serialize_proof::<Fibonacci2ColsShifted<_>>(
+            &proof,
+            &pub_inputs,
+            proof_options,
+        )
+        .unwrap();
+        (bytes, pub_inputs)
+    }
+
+    #[rstest]
+    #[case(4, 3, 4, 0, 1, vec![], 1)]
+    #[case(4, 2, 2, 0, 10, vec![], 1)]
+    #[case(128, 111, 4, 0, 3, vec![], 1)]
+    #[case(64, 20, 8, 2, 5, vec![], 1)]
+    #[case(128, 111, 4, 0, 3, vec![0, 2, 3], 4)]
+    #[case(64, 20, 8, 2, 5, vec![0, 3, 1, 2], 1)]
+    #[case(16, 7, 2, 0, 4, vec![0, 2], 4)]
+    fn test_deserialized_stone_proof_verifies_and_serializes_back_to_the_same_bytes(
+        #[case] trace_length: usize,
+        #[case] claimed_index: usize,
+        #[case] blowup_factor: u8,
+        #[case] grinding_factor: u8,
+        #[case] fri_number_of_queries: usize,
+        #[case] fri_step_list: Vec<usize>,
+        #[case] fri_last_layer_degree_bound: usize,
+    ) {
+        let proof_options = ProofOptions {
+            blowup_factor,
+            coset_offset: 3,
+            grinding_factor,
+            fri_number_of_queries,
+            zero_knowledge: false,
+            fri_step_list,
+            fri_last_layer_degree_bound,
+        };
+        let (bytes, pub_inputs) = stone_proof_bytes(trace_length, claimed_index, &proof_options);
+
+        let proof = StoneCompatibleDeserializer::deserialize_proof::<Fibonacci2ColsShifted<_>>(
+            &bytes,
+            trace_length,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&pub_inputs.as_bytes()),
+        )
+        .unwrap();
+
+        assert!(Verifier::<Fibonacci2ColsShifted<_>>::verify(
             &proof,
             &pub_inputs,
             &proof_options,
+            StoneProverTranscript::new(&pub_inputs.as_bytes()),
+        ));
+        assert_eq!(
+            StoneCompatibleSerializer::serialize_proof::<Fibonacci2ColsShifted<_>>(
+                &proof,
+                &pub_inputs,
+                &proof_options,
+            )
+            .unwrap(),
+            bytes
         );
-        assert_eq!(serialized_proof, expected_bytes);
+    }
+
+    #[test]
+    fn test_deserialized_stone_proof_with_a_tampered_value_does_not_verify() {
+        let proof_options = ProofOptions {
+            blowup_factor: 4,
+            coset_offset: 3,
+            grinding_factor: 0,
+            fri_number_of_queries: 3,
+            zero_knowledge: false,
+            fri_step_list: vec![],
+            fri_last_layer_degree_bound: 1,
+        };
+        let (mut bytes, pub_inputs) = stone_proof_bytes(128, 111, &proof_options);
+
+        // The last byte belongs to the decommitment of the last FRI layer.
+        *bytes.last_mut().unwrap() ^= 1;
+
+        let proof = StoneCompatibleDeserializer::deserialize_proof::<Fibonacci2ColsShifted<_>>(
+            &bytes,
+            128,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&pub_inputs.as_bytes()),
+        )
+        .unwrap();
+
+        assert!(!Verifier::<Fibonacci2ColsShifted<_>>::verify(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&pub_inputs.as_bytes()),
+        ));
+    }
+
+    #[test]
+    fn test_deserialization_of_a_stone_proof_with_the_wrong_length_fails() {
+        let proof_options = ProofOptions {
+            blowup_factor: 4,
+            coset_offset: 3,
+            grinding_factor: 0,
+            fri_number_of_queries: 3,
+            zero_knowledge: false,
+            fri_step_list: vec![],
+            fri_last_layer_degree_bound: 1,
+        };
+        let (bytes, pub_inputs) = stone_proof_bytes(128, 111, &proof_options);
+
+        let truncated = StoneCompatibleDeserializer::deserialize_proof::<Fibonacci2ColsShifted<_>>(
+            &bytes[..bytes.len() - 1],
+            128,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&pub_inputs.as_bytes()),
+        );
+        assert_eq!(
+            truncated.unwrap_err(),
+            StoneDeserializationError::NotEnoughBytes
+        );
+
+        let mut extended = bytes.clone();
+        extended.push(0);
+        let extended = StoneCompatibleDeserializer::deserialize_proof::<Fibonacci2ColsShifted<_>>(
+            &extended,
+            128,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&pub_inputs.as_bytes()),
+        );
+        assert_eq!(
+            extended.unwrap_err(),
+            StoneDeserializationError::TrailingBytes(1)
+        );
+    }
+
+    #[test]
+    fn test_deserialization_of_a_stone_proof_with_invalid_fri_steps_fails() {
+        let proof_options = ProofOptions {
+            blowup_factor: 4,
+            coset_offset: 3,
+            grinding_factor: 0,
+            fri_number_of_queries: 3,
+            zero_knowledge: false,
+            fri_step_list: vec![0, 2, 3],
+            fri_last_layer_degree_bound: 4,
+        };
+        let (bytes, pub_inputs) = stone_proof_bytes(128, 111, &proof_options);
+
+        let proof_options = ProofOptions {
+            fri_step_list: vec![0, 2, 2],
+            ..proof_options
+        };
+        let proof = StoneCompatibleDeserializer::deserialize_proof::<Fibonacci2ColsShifted<_>>(
+            &bytes,
+            128,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&pub_inputs.as_bytes()),
+        );
+        assert_eq!(
+            proof.unwrap_err(),
+            StoneDeserializationError::InvalidFriSteps
+        );
+    }
+
+    #[test]
+    fn merged_authentication_paths_are_unmerged_into_the_original_paths() {
+        use std::collections::BTreeMap;
+
+        use lambdaworks_crypto::merkle_tree::traits::IsMerkleTreeBackend;
+
+        use crate::{
+            config::{BatchedMerkleTree, BatchedMerkleTreeBackend},
+            proof::stark::StoneProofReader,
+        };
+
+        let rows: Vec<Vec<FieldElement<Stark252PrimeField>>> =
+            (0..16u64).map(|i| vec![FieldElement::from(i)]).collect();
+        let tree = BatchedMerkleTree::<Stark252PrimeField>::build(&rows);
+        let leaf_indexes = [0, 3, 6, 7];
+        let paths: Vec<_> = leaf_indexes
+            .iter()
+            .map(|index| tree.get_proof_by_pos(*index).unwrap())
+            .collect();
+
+        let nodes = StoneCompatibleSerializer::merge_authentication_paths(
+            &paths.iter().collect::<Vec<_>>(),
+            &leaf_indexes,
+        );
+        let bytes: Vec<u8> = nodes.concat();
+        let leaves: BTreeMap<_, _> = leaf_indexes
+            .iter()
+            .map(|index| {
+                (
+                    *index,
+                    BatchedMerkleTreeBackend::<Stark252PrimeField>::hash_data(&rows[*index]),
+                )
+            })
+            .collect();
+        let mut reader = StoneProofReader { bytes: &bytes };
+        let unmerged =
+            StoneCompatibleDeserializer::unmerge_authentication_paths(leaves, 4, &mut reader)
+                .unwrap();
+
+        assert!(reader.bytes.is_empty());
+        for (index, path) in leaf_indexes.iter().zip(&paths) {
+            assert_eq!(unmerged[index].merkle_path, path.merkle_path);
+        }
+    }
+
+    #[test]
+    fn merging_authentication_paths_of_different_lengths_does_not_panic() {
+        use lambdaworks_crypto::merkle_tree::proof::Proof;
+
+        let short_path = Proof {
+            merkle_path: vec![],
+        };
+        let long_path = Proof {
+            merkle_path: vec![[1; 32], [2; 32]],
+        };
+        let nodes = StoneCompatibleSerializer::merge_authentication_paths(
+            &[&short_path, &long_path],
+            &[0, 2],
+        );
+        assert_eq!(nodes, vec![[1; 32]]);
     }
 }
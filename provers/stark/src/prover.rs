@@ -1,10 +1,12 @@
 use std::marker::PhantomData;
+use std::ops::Range;
 #[cfg(feature = "instruments")]
 use std::time::Instant;
 
 use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
 use lambdaworks_math::fft::cpu::bit_reversing::{in_place_bit_reverse_permute, reverse_index};
 use lambdaworks_math::fft::errors::FFTError;
+use lambdaworks_math::fft::lde::CosetLDE;
 
 use lambdaworks_math::field::traits::{IsField, IsSubFieldOf};
 use lambdaworks_math::traits::AsBytes;
@@ -13,6 +15,7 @@ use lambdaworks_math::{
     polynomial::Polynomial,
 };
 use log::info;
+use rand::Rng;
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
@@ -20,9 +23,10 @@ use rayon::prelude::{IndexedParallelIterator, IntoParallelRefIterator, ParallelI
 #[cfg(debug_assertions)]
 use crate::debug::validate_trace;
 use crate::fri;
+use crate::preprocessing::{main_and_preprocessed_polys, ProvingKey};
 use crate::proof::stark::{DeepPolynomialOpenings, PolynomialOpenings};
 use crate::table::Table;
-use crate::trace::{columns2rows, LDETraceTable};
+use crate::trace::LDETraceTable;
 
 use super::config::{BatchedMerkleTree, Commitment};
 use super::constraints::evaluator::ConstraintEvaluator;
@@ -48,6 +52,7 @@ pub enum ProvingError {
 
 /// A container for the intermediate results of the commitments to a trace table, main or auxiliary in case of RAP,
 /// in the first round of the STARK Prove protocol.
+#[derive(Clone)]
 pub struct Round1CommitmentData<F>
 where
     F: IsField,
@@ -72,6 +77,8 @@ where
     pub(crate) lde_trace: LDETraceTable<A::Field, A::FieldExtension>,
     /// The intermediate results of the commitment to the main trace table.
     pub(crate) main: Round1CommitmentData<A::Field>,
+    /// The commitment to the preprocessed columns, computed at setup, if the AIR has any.
+    pub(crate) preprocessed: Option<Round1CommitmentData<A::Field>>,
     /// The intermediate results of the commitment to the auxiliary trace table in case of RAP.
     pub(crate) aux: Option<Round1CommitmentData<A::FieldExtension>>,
     /// The challenges of the RAP round.
@@ -84,14 +91,12 @@ where
     FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
     FieldElement<A::Field>: AsBytes + Sync + Send,
 {
-    /// Returns the full list of the polynomials interpolating the trace. It includes both
-    /// main and auxiliary trace polynomials. The main trace polynomials are casted to
-    /// polynomials with coefficients over `Self::FieldExtension`.
+    /// Returns the full list of the polynomials interpolating the trace. It includes the main,
+    /// preprocessed and auxiliary trace polynomials, in that order. The main and preprocessed
+    /// trace polynomials are casted to polynomials with coefficients over `Self::FieldExtension`.
     fn all_trace_polys(&self) -> Vec<Polynomial<FieldElement<A::FieldExtension>>> {
         let mut trace_polys: Vec<_> = self
-            .main
-            .trace_polys
-            .clone()
+            .main_and_preprocessed_trace_polys()
             .into_iter()
             .map(|poly| poly.to_extension())
             .collect();
@@ -101,6 +106,12 @@ where
         }
         trace_polys
     }
+
+    /// Returns the polynomials interpolating the main trace followed by the ones interpolating
+    /// the preprocessed columns.
+    fn main_and_preprocessed_trace_polys(&self) -> Vec<Polynomial<FieldElement<A::Field>>> {
+        main_and_preprocessed_polys(&self.main.trace_polys, self.preprocessed.as_ref())
+    }
 }
 
 /// A container for the results of the second round of the STARK Prove protocol.
@@ -122,25 +133,26 @@ where
 /// A container for the results of the third round of the STARK Prove protocol.
 pub struct Round3<F: IsField> {
     /// Evaluations of the trace polynomials, main ans auxiliary, at the out-of-domain challenge.
-    trace_ood_evaluations: Table<F>,
+    pub(crate) trace_ood_evaluations: Table<F>,
     /// Evaluations of the composition polynomial parts at the out-of-domain challenge.
-    composition_poly_parts_ood_evaluation: Vec<FieldElement<F>>,
+    pub(crate) composition_poly_parts_ood_evaluation: Vec<FieldElement<F>>,
 }
 
 /// A container for the results of the fourth round of the STARK Prove protocol.
 pub struct Round4<F: IsSubFieldOf<E>, E: IsField> {
-    /// The final value resulting from folding the Deep composition polynomial all the way down to a constant value.
-    fri_last_value: FieldElement<E>,
+    /// The coefficients of the last layer of FRI, obtained by folding the Deep composition polynomial down to the
+    /// degree bound of the last layer.
+    pub(crate) fri_last_layer_coefficients: Vec<FieldElement<E>>,
     /// The commitments to the fold polynomials of the inner layers of FRI.
-    fri_layers_merkle_roots: Vec<Commitment>,
+    pub(crate) fri_layers_merkle_roots: Vec<Commitment>,
     /// The values and proofs of validity of the evaluations of the trace polynomials and the composition polynomials
     /// parts at the domain values corresponding to the FRI query challenges and their symmetric counterparts.
-    deep_poly_openings: DeepPolynomialOpenings<F, E>,
+    pub(crate) deep_poly_openings: DeepPolynomialOpenings<F, E>,
     /// The values and proofs of validity of the evaluations of the fold polynomials of the inner
     /// layers of FRI at the values corresponding to the symmetrics of the FRI query challenges.
-    query_list: Vec<FriDecommitment<E>>,
+    pub(crate) query_list: Vec<FriDecommitment<E>>,
     /// The proof of work nonce.
-    nonce: Option<u64>,
+    pub(crate) nonce: Option<u64>,
 }
 
 /// Returns the evaluations of the polynomial `p` over the lde domain defined by the given
@@ -164,6 +176,135 @@ where
     }
 }
 
+/// Returns the evaluations of the polynomial `p` over the coset `offset * <w>`, where `w` is a
+/// primitive root of unity of order `domain_size`. The polynomial may have degree greater than
+/// or equal to `domain_size`; it is first reduced modulo `X^domain_size - offset^domain_size`.
+pub fn evaluate_polynomial_on_coset<F, E>(
+    p: &Polynomial<FieldElement<E>>,
+    domain_size: usize,
+    offset: &FieldElement<F>,
+) -> Result<Vec<FieldElement<E>>, FFTError>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+{
+    let mut reduced_coefficients = vec![FieldElement::<E>::zero(); domain_size];
+    let mut offset_power = FieldElement::<F>::one();
+    for (i, coefficient) in p.coefficients().iter().enumerate() {
+        reduced_coefficients[i % domain_size] += &offset_power * coefficient;
+        offset_power *= offset;
+    }
+    Polynomial::evaluate_fft::<F>(
+        &Polynomial::new(&reduced_coefficients),
+        1,
+        Some(domain_size),
+    )
+}
+
+/// Returns a random field element, statistically close to uniform over the whole field.
+/// Elements of extension fields have all their coordinates sampled.
+pub(crate) fn sample_random_field_element<E: IsField>(rng: &mut impl Rng) -> FieldElement<E> {
+    FieldElement::from_random_limbs(&mut || rng.gen::<u64>())
+}
+
+/// Returns `p + Z_H * r`, where `Z_H = X^n - 1` is the vanishing polynomial of the trace domain
+/// of size `n = trace_length` and `r` is the polynomial with coefficients `mask`.
+pub(crate) fn mask_with_vanishing_polynomial<E: IsField>(
+    p: &Polynomial<FieldElement<E>>,
+    mask: &[FieldElement<E>],
+    trace_length: usize,
+) -> Polynomial<FieldElement<E>> {
+    let mut coefficients = p.coefficients().to_vec();
+    coefficients.resize(trace_length + mask.len(), FieldElement::zero());
+    for (i, r_i) in mask.iter().enumerate() {
+        coefficients[i] = &coefficients[i] - r_i;
+        coefficients[trace_length + i] = &coefficients[trace_length + i] + r_i;
+    }
+    Polynomial::new(&coefficients)
+}
+
+/// Checks that the proof options allow masking the trace and the composition polynomial
+/// of `air`. The masked trace polynomials must have degree less than twice the trace length,
+/// and the composition polynomial parts must fit in the LDE domain.
+pub(crate) fn check_zero_knowledge_parameters<A: AIR>(air: &A) -> Result<(), ProvingError> {
+    let trace_length = air.trace_length();
+    let blowup_factor = air.blowup_factor() as usize;
+    if blowup_factor < 4 {
+        return Err(ProvingError::WrongParameter(format!(
+            "zero-knowledge proofs need a blowup factor of at least 4, got {blowup_factor}"
+        )));
+    }
+    let masking_degree = air.zk_masking_degree();
+    if masking_degree > trace_length {
+        return Err(ProvingError::WrongParameter(format!(
+            "trace length {trace_length} is too small for a masking degree of {masking_degree}"
+        )));
+    }
+    let number_of_parts = air.num_composition_poly_parts();
+    if number_of_parts > blowup_factor {
+        return Err(ProvingError::WrongParameter(format!(
+            "the masked composition polynomial needs {number_of_parts} parts, more than the blowup factor {blowup_factor}"
+        )));
+    }
+    Ok(())
+}
+
+/// Returns the number of times each layer of FRI folds the DEEP composition polynomial of `air`
+/// in two, checking that the proof options fold it down to the degree bound of the last layer.
+pub(crate) fn check_fri_steps<A: AIR>(air: &A) -> Result<Vec<usize>, ProvingError> {
+    fri::fri_folding_steps(air).ok_or_else(|| {
+        let options = air.options();
+        ProvingError::WrongParameter(format!(
+            "the FRI steps {:?} don't fold a trace of length {} down to the last layer degree bound {}",
+            options.fri_step_list,
+            air.trace_length(),
+            options.fri_last_layer_degree_bound
+        ))
+    })
+}
+
+/// Checks that `proving_key` holds the preprocessed columns of `air`, committed over the LDE
+/// domain of its proof options, and that it is given if and only if `air` has preprocessed
+/// columns.
+pub(crate) fn check_proving_key<A: AIR>(
+    air: &A,
+    proving_key: Option<&ProvingKey<A::Field>>,
+) -> Result<(), ProvingError>
+where
+    FieldElement<A::Field>: AsBytes + Send + Sync,
+{
+    let num_preprocessed_columns = air.num_preprocessed_columns();
+    let key = match proving_key.map(|proving_key| &proving_key.verifying_key) {
+        Some(key) => key,
+        None if num_preprocessed_columns == 0 => return Ok(()),
+        None => {
+            return Err(ProvingError::WrongParameter(format!(
+                "the AIR has {num_preprocessed_columns} preprocessed columns but no proving key was given"
+            )))
+        }
+    };
+    if key.num_preprocessed_columns != num_preprocessed_columns
+        || key.trace_length != air.trace_length()
+    {
+        return Err(ProvingError::WrongParameter(format!(
+            "the proving key holds {} preprocessed columns of length {}, but the AIR has {num_preprocessed_columns} columns of length {}",
+            key.num_preprocessed_columns,
+            key.trace_length,
+            air.trace_length()
+        )));
+    }
+    if !key.matches(air.trace_length(), air.options()) {
+        return Err(ProvingError::WrongParameter(format!(
+            "the proving key was computed with blowup factor {} and coset offset {}, but the proof options have blowup factor {} and coset offset {}",
+            key.blowup_factor,
+            key.coset_offset,
+            air.options().blowup_factor,
+            air.options().coset_offset
+        )));
+    }
+    Ok(())
+}
+
 /// The functionality of a STARK prover providing methods to run the STARK Prove protocol
 /// https://lambdaclass.github.io/lambdaworks/starks/protocol.html
 /// The default implementation is complete and is compatible with Stone prover
@@ -185,15 +326,20 @@ pub trait IsStarkProver<A: AIR> {
 
     /// Given a `TraceTable`, this method interpolates its columns, computes the commitment to the
     /// table and appends it to the transcript.
+    /// If `masking_degree` is not zero, each interpolating polynomial `t` is replaced by
+    /// `t + Z_H * r`, where `Z_H` is the vanishing polynomial of the trace domain and `r` is a
+    /// random polynomial with `masking_degree` coefficients. This does not change the values of
+    /// `t` over the trace domain.
     /// Output: a touple of length 4 with the following:
-    /// • The polynomials interpolating the columns of `trace`.
-    /// • The evaluations of the above polynomials over the domain `domain`.
-    /// • The Merkle tree of evaluations of the above polynomials over the domain `domain`.
+    /// • The (masked) polynomials interpolating the columns of `trace`.
+    /// • The evaluations of the above polynomials over the LDE domain of `lde`.
+    /// • The Merkle tree of evaluations of the above polynomials over the LDE domain of `lde`.
     /// • The roots of the above Merkle trees.
     #[allow(clippy::type_complexity)]
     fn interpolate_and_commit<E>(
         trace: &TraceTable<E>,
-        domain: &Domain<A::Field>,
+        lde: &CosetLDE<A::Field>,
+        masking_degree: usize,
         transcript: &mut impl IsTranscript<A::FieldExtension>,
     ) -> (
         Vec<Polynomial<FieldElement<E>>>,
@@ -209,75 +355,68 @@ pub trait IsStarkProver<A: AIR> {
         A::Field: IsSubFieldOf<E>,
     {
         // Interpolate columns of `trace`.
-        let trace_polys = trace.compute_trace_polys::<A::Field>();
-
-        // Evaluate those polynomials t_j on the large domain D_LDE.
-        let lde_trace_evaluations = Self::compute_lde_trace_evaluations(&trace_polys, domain);
-
-        let mut lde_trace_permuted = lde_trace_evaluations.clone();
-        for col in lde_trace_permuted.iter_mut() {
-            in_place_bit_reverse_permute(col);
+        let columns = trace.columns();
+        #[cfg(not(feature = "parallel"))]
+        let mut trace_polys = lde.interpolate_columns(&columns).unwrap();
+        #[cfg(feature = "parallel")]
+        let mut trace_polys = lde.parallel_interpolate_columns(&columns).unwrap();
+
+        if masking_degree > 0 {
+            let mut rng = rand::thread_rng();
+            for poly in trace_polys.iter_mut() {
+                let mask: Vec<_> = (0..masking_degree)
+                    .map(|_| sample_random_field_element(&mut rng))
+                    .collect();
+                *poly = mask_with_vanishing_polynomial(poly, &mask, trace.n_rows());
+            }
         }
 
+        // Evaluate those polynomials t_j on the large domain D_LDE. The FFT leaves the
+        // evaluations as rows in bit-reverse order, which is the layout that is committed.
+        #[cfg(not(feature = "parallel"))]
+        let lde_trace = lde.evaluate_polynomials(&trace_polys);
+        #[cfg(feature = "parallel")]
+        let lde_trace = lde.parallel_evaluate_polynomials(&trace_polys);
+
         // Compute commitment.
-        let lde_trace_permuted_rows = columns2rows(lde_trace_permuted);
         let (lde_trace_merkle_tree, lde_trace_merkle_root) =
-            Self::batch_commit(&lde_trace_permuted_rows);
+            Self::batch_commit(lde_trace.bit_reversed_rows());
 
         // >>>> Send commitment.
         transcript.append_bytes(&lde_trace_merkle_root);
 
         (
             trace_polys,
-            lde_trace_evaluations,
+            lde_trace.columns(),
             lde_trace_merkle_tree,
             lde_trace_merkle_root,
         )
     }
 
-    /// Evaluate polynomials `trace_polys` over the domain `domain`.
-    /// The i-th entry of the returned vector contains the evaluations of the i-th polynomial in `trace_polys`.
-    fn compute_lde_trace_evaluations<E>(
-        trace_polys: &[Polynomial<FieldElement<E>>],
-        domain: &Domain<A::Field>,
-    ) -> Vec<Vec<FieldElement<E>>>
-    where
-        FieldElement<A::Field>: Send + Sync,
-        FieldElement<E>: Send + Sync,
-        E: IsSubFieldOf<A::FieldExtension>,
-        A::Field: IsSubFieldOf<E>,
-    {
-        #[cfg(not(feature = "parallel"))]
-        let trace_polys_iter = trace_polys.iter();
-        #[cfg(feature = "parallel")]
-        let trace_polys_iter = trace_polys.par_iter();
-
-        trace_polys_iter
-            .map(|poly| {
-                evaluate_polynomial_on_lde_domain(
-                    poly,
-                    domain.blowup_factor,
-                    domain.interpolation_domain_size,
-                    &domain.coset_offset,
-                )
-            })
-            .collect::<Result<Vec<Vec<FieldElement<E>>>, FFTError>>()
-            .unwrap()
-    }
-
-    /// Returns the result of the first round of the STARK Prove protocol.
+    /// Returns the result of the first round of the STARK Prove protocol. The preprocessed
+    /// columns, if any, are taken from `proving_key` instead of being committed again.
     fn round_1_randomized_air_with_preprocessing(
         air: &A,
         main_trace: &TraceTable<A::Field>,
         domain: &Domain<A::Field>,
+        proving_key: Option<&ProvingKey<A::Field>>,
         transcript: &mut impl IsTranscript<A::FieldExtension>,
     ) -> Result<Round1<A>, ProvingError>
     where
         FieldElement<A::Field>: AsBytes + Send + Sync,
         FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
     {
-        let (trace_polys, evaluations, main_merkle_tree, main_merkle_root) =
-            Self::interpolate_and_commit::<A::Field>(main_trace, domain, transcript);
+        let masking_degree = air.zk_masking_degree();
+        // The twiddles and the powers of the coset offset are shared by the main and the
+        // auxiliary traces.
+        let lde = CosetLDE::new(
+            domain.interpolation_domain_size,
+            domain.blowup_factor,
+            &domain.coset_offset,
+        )
+        .map_err(|error| ProvingError::WrongParameter(error.to_string()))?;
+        let (trace_polys, mut evaluations, main_merkle_tree, main_merkle_root) =
+            Self::interpolate_and_commit::<A::Field>(main_trace, &lde, masking_degree, transcript);
 
         let main = Round1CommitmentData::<A::Field> {
             trace_polys,
@@ -285,12 +424,18 @@ pub trait IsStarkProver<A: AIR> {
             lde_trace_merkle_root: main_merkle_root,
         };
 
+        // The preprocessed columns are read from the LDE table as main columns.
+        let preprocessed = proving_key.map(|proving_key| {
+            evaluations.extend_from_slice(&proving_key.lde_evaluations);
+            proving_key.commitment.clone()
+        });
+
         let rap_challenges = air.build_rap_challenges(transcript);
 
         let aux_trace = air.build_auxiliary_trace(main_trace, &rap_challenges);
         let (aux, aux_evaluations) = if !aux_trace.is_empty() {
             let (aux_trace_polys, aux_trace_polys_evaluations, aux_merkle_tree, aux_merkle_root) =
-                Self::interpolate_and_commit(&aux_trace, domain, transcript);
+                Self::interpolate_and_commit(&aux_trace, &lde, masking_degree, transcript);
             let aux_evaluations = aux_trace_polys_evaluations;
             let aux = Some(Round1CommitmentData::<A::FieldExtension> {
                 trace_polys: aux_trace_polys,
@@ -312,6 +457,7 @@ pub trait IsStarkProver<A: AIR> {
         Ok(Round1 {
             lde_trace,
             main,
+            preprocessed,
             aux,
             rap_challenges,
         })
@@ -373,12 +519,30 @@ pub trait IsStarkProver<A: AIR> {
         );
 
         // Get coefficients of the composition poly H
+        #[cfg(not(feature = "parallel"))]
         let composition_poly =
             Polynomial::interpolate_offset_fft(&constraint_evaluations, &domain.coset_offset)
                 .unwrap();
+        #[cfg(feature = "parallel")]
+        let composition_poly = Polynomial::parallel_interpolate_offset_fft(
+            &constraint_evaluations,
+            &domain.coset_offset,
+        )
+        .unwrap();
 
-        let number_of_parts = air.composition_poly_degree_bound() / air.trace_length();
-        let composition_poly_parts = composition_poly.break_in_parts(number_of_parts);
+        let number_of_parts = air.num_composition_poly_parts();
+        let mut composition_poly_parts = composition_poly.break_in_parts(number_of_parts);
+
+        // In zero-knowledge mode, a random polynomial of degree less than twice the trace length
+        // is committed along with the parts. It enters the DEEP composition polynomial as one
+        // more part, masking the evaluations revealed during FRI.
+        if air.options().zero_knowledge {
+            let mut rng = rand::thread_rng();
+            let randomizer: Vec<_> = (0..2 * air.trace_length())
+                .map(|_| sample_random_field_element(&mut rng))
+                .collect();
+            composition_poly_parts.push(Polynomial::new(&randomizer));
+        }
 
         let lde_composition_poly_parts_evaluations: Vec<_> = composition_poly_parts
             .iter()
@@ -416,10 +580,10 @@ pub trait IsStarkProver<A: AIR> {
         FieldElement<A::Field>: AsBytes + Sync + Send,
         FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
     {
-        let z_power = z.pow(round_2_result.composition_poly_parts.len());
+        let z_power = z.pow(air.num_composition_poly_parts());
 
         // Evaluate H_i in z^N for all i, where N is the number of parts the composition poly was
-        // broken into.
+        // broken into. In zero-knowledge mode, the random part is also evaluated at z^N.
         let composition_poly_parts_ood_evaluation: Vec<_> = round_2_result
             .composition_poly_parts
             .iter()
@@ -435,7 +599,7 @@ pub trait IsStarkProver<A: AIR> {
         // polynomial and `g` is the primitive root of unity used when interpolating `t`.
         let trace_ood_evaluations =
             crate::trace::get_trace_evaluations::<A::Field, A::FieldExtension>(
-                &round_1_result.main.trace_polys,
+                &round_1_result.main_and_preprocessed_trace_polys(),
                 round_1_result
                     .aux
                     .as_ref()
@@ -454,15 +618,50 @@ pub trait IsStarkProver<A: AIR> {
     }
 
     /// Returns the result of the fourth round of the STARK Prove protocol.
+    #[allow(clippy::too_many_arguments)]
     fn round_4_compute_and_run_fri_on_the_deep_composition_polynomial(
         air: &A,
         domain: &Domain<A::Field>,
+        fri_steps: &[usize],
         round_1_result: &Round1<A>,
         round_2_result: &Round2<A::FieldExtension>,
         round_3_result: &Round3<A::FieldExtension>,
         z: &FieldElement<A::FieldExtension>,
         transcript: &mut impl IsTranscript<A::FieldExtension>,
     ) -> Round4<A::Field, A::FieldExtension>
+    where
+        FieldElement<A::Field>: AsBytes + Send + Sync,
+        FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+    {
+        Self::run_fri_on_the_deep_composition_polynomial(
+            air,
+            domain,
+            fri_steps,
+            &round_1_result.all_trace_polys(),
+            &round_2_result.composition_poly_parts,
+            round_3_result,
+            z,
+            transcript,
+            |iotas| Self::open_deep_composition_poly(domain, round_1_result, round_2_result, iotas),
+        )
+    }
+
+    /// Computes the DEEP composition polynomial from the trace polynomials and the parts of the
+    /// composition polynomial, runs FRI on it with the folding steps `fri_steps` and samples the
+    /// query indexes. The openings of the trace and composition polynomials at the pairs of
+    /// symmetric elements of the first layer that the queries fold are computed by `open`.
+    #[allow(clippy::too_many_arguments)]
+    fn run_fri_on_the_deep_composition_polynomial(
+        air: &A,
+        domain: &Domain<A::Field>,
+        fri_steps: &[usize],
+        trace_polys: &[Polynomial<FieldElement<A::FieldExtension>>],
+        composition_poly_parts: &[Polynomial<FieldElement<A::FieldExtension>>],
+        round_3_result: &Round3<A::FieldExtension>,
+        z: &FieldElement<A::FieldExtension>,
+        transcript: &mut impl IsTranscript<A::FieldExtension>,
+        open: impl FnOnce(&[usize]) -> DeepPolynomialOpenings<A::Field, A::FieldExtension>,
+    ) -> Round4<A::Field, A::FieldExtension>
     where
         FieldElement<A::Field>: AsBytes + Send + Sync,
         FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
@@ -470,9 +669,74 @@ pub trait IsStarkProver<A: AIR> {
         let coset_offset_u64 = air.context().proof_options.coset_offset;
         let coset_offset = FieldElement::<A::Field>::from(coset_offset_u64);
 
+        // Compute p₀ (deep composition polynomial)
+        let deep_composition_poly = Self::sample_deep_composition_poly(
+            air,
+            domain,
+            trace_polys,
+            composition_poly_parts,
+            round_3_result,
+            z,
+            transcript,
+        );
+
+        let domain_size = domain.lde_roots_of_unity_coset.len();
+
+        // FRI commit and query phases
+        let (fri_last_layer_coefficients, fri_layers) =
+            fri::commit_phase::<A::Field, A::FieldExtension>(
+                fri_steps,
+                air.options().fri_last_layer_degree_bound,
+                deep_composition_poly,
+                transcript,
+                &coset_offset,
+                domain_size,
+            );
+
+        let (nonce, iotas) =
+            Self::grind_and_sample_query_indexes(air, domain_size, fri_steps[0], transcript);
+        let query_list = fri::query_phase(&fri_layers, fri_steps, &iotas);
+
+        let fri_layers_merkle_roots: Vec<_> = fri_layers
+            .iter()
+            .map(|layer| layer.merkle_tree.root)
+            .collect();
+
+        let pair_indexes: Vec<_> = iotas
+            .iter()
+            .flat_map(|iota| fri::first_layer_pair_indexes(*iota, fri_steps[0]))
+            .collect();
+        let deep_poly_openings = open(&pair_indexes);
+
+        Round4 {
+            fri_last_layer_coefficients,
+            fri_layers_merkle_roots,
+            deep_poly_openings,
+            query_list,
+            nonce,
+        }
+    }
+
+    /// Samples the challenges 𝛾 of the DEEP composition polynomial and returns it, computed
+    /// from the trace polynomials and the parts of the composition polynomial.
+    #[allow(clippy::too_many_arguments)]
+    fn sample_deep_composition_poly(
+        air: &A,
+        domain: &Domain<A::Field>,
+        trace_polys: &[Polynomial<FieldElement<A::FieldExtension>>],
+        composition_poly_parts: &[Polynomial<FieldElement<A::FieldExtension>>],
+        round_3_result: &Round3<A::FieldExtension>,
+        z: &FieldElement<A::FieldExtension>,
+        transcript: &mut impl IsTranscript<A::FieldExtension>,
+    ) -> Polynomial<FieldElement<A::FieldExtension>>
+    where
+        FieldElement<A::Field>: AsBytes + Send + Sync,
+        FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+    {
         let gamma = transcript.sample_field_element();
-        let n_terms_composition_poly = round_2_result.lde_composition_poly_evaluations.len();
-        let n_terms_trace = air.context().transition_offsets.len() * air.context().trace_columns;
+        let n_terms_composition_poly = composition_poly_parts.len();
+        let n_terms_trace = air.context().transition_offsets.len()
+            * (air.context().trace_columns + air.num_preprocessed_columns());
 
         // <<<< Receive challenges: 𝛾, 𝛾'
         let mut deep_composition_coefficients: Vec<_> =
@@ -487,29 +751,27 @@ pub trait IsStarkProver<A: AIR> {
         // <<<< Receive challenges: 𝛾ⱼ, 𝛾ⱼ'
         let gammas = deep_composition_coefficients;
 
-        // Compute p₀ (deep composition polynomial)
-        let deep_composition_poly = Self::compute_deep_composition_poly(
+        Self::compute_deep_composition_poly(
             air,
-            &round_1_result.all_trace_polys(),
-            round_2_result,
+            trace_polys,
+            composition_poly_parts,
             round_3_result,
             z,
             &domain.trace_primitive_root,
             &gammas,
             &trace_poly_coeffients,
-        );
-
-        let domain_size = domain.lde_roots_of_unity_coset.len();
-
-        // FRI commit and query phases
-        let (fri_last_value, fri_layers) = fri::commit_phase::<A::Field, A::FieldExtension>(
-            domain.root_order as usize,
-            deep_composition_poly,
-            transcript,
-            &coset_offset,
-            domain_size,
-        );
+        )
+    }
 
+    /// Generates the proof of work nonce if the proof options ask for grinding, appending it to
+    /// the transcript, and then samples the query indexes of FRI over a LDE domain of size
+    /// `domain_size`.
+    fn grind_and_sample_query_indexes(
+        air: &A,
+        domain_size: usize,
+        first_step: usize,
+        transcript: &mut impl IsTranscript<A::FieldExtension>,
+    ) -> (Option<u64>, Vec<usize>) {
         // grinding: generate nonce and append it to the transcript
         let security_bits = air.context().proof_options.grinding_factor;
         let mut nonce = None;
@@ -521,34 +783,21 @@ pub trait IsStarkProver<A: AIR> {
         }
 
         let number_of_queries = air.options().fri_number_of_queries;
-        let iotas = Self::sample_query_indexes(number_of_queries, domain, transcript);
-        let query_list = fri::query_phase(&fri_layers, &iotas);
-
-        let fri_layers_merkle_roots: Vec<_> = fri_layers
-            .iter()
-            .map(|layer| layer.merkle_tree.root)
-            .collect();
-
-        let deep_poly_openings =
-            Self::open_deep_composition_poly(domain, round_1_result, round_2_result, &iotas);
-
-        Round4 {
-            fri_last_value,
-            fri_layers_merkle_roots,
-            deep_poly_openings,
-            query_list,
-            nonce,
-        }
+        let iotas =
+            Self::sample_query_indexes(number_of_queries, domain_size, first_step, transcript);
+        (nonce, iotas)
     }
 
+    /// Samples the query indexes, which are indexes of the layer obtained by folding the LDE
+    /// domain of size `domain_size` in two `first_step` times.
     fn sample_query_indexes(
         number_of_queries: usize,
-        domain: &Domain<A::Field>,
+        domain_size: usize,
+        first_step: usize,
         transcript: &mut impl IsTranscript<A::FieldExtension>,
     ) -> Vec<usize> {
-        let domain_size = domain.lde_roots_of_unity_coset.len() as u64;
         (0..number_of_queries)
-            .map(|_| (transcript.sample_u64(domain_size >> 1)) as usize)
+            .map(|_| (transcript.sample_u64((domain_size as u64) >> first_step)) as usize)
             .collect::<Vec<usize>>()
     }
 
@@ -559,7 +808,7 @@ pub trait IsStarkProver<A: AIR> {
     fn compute_deep_composition_poly(
         air: &A,
         trace_polys: &[Polynomial<FieldElement<A::FieldExtension>>],
-        round_2_result: &Round2<A::FieldExtension>,
+        composition_poly_parts: &[Polynomial<FieldElement<A::FieldExtension>>],
         round_3_result: &Round3<A::FieldExtension>,
         z: &FieldElement<A::FieldExtension>,
         primitive_root: &FieldElement<A::Field>,
@@ -570,11 +819,11 @@ pub trait IsStarkProver<A: AIR> {
         FieldElement<A::Field>: AsBytes + Send + Sync,
         FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
     {
-        let z_power = z.pow(round_2_result.composition_poly_parts.len());
+        let z_power = z.pow(air.num_composition_poly_parts());
 
         // ∑ᵢ 𝛾ᵢ ( Hᵢ − Hᵢ(z^N) ) / ( X − z^N )
         let mut h_terms = Polynomial::zero();
-        for (i, part) in round_2_result.composition_poly_parts.iter().enumerate() {
+        for (i, part) in composition_poly_parts.iter().enumerate() {
             // h_i_eval is the evaluation of the i-th part of the composition polynomial at z^N,
             // where N is the number of parts of the composition polynomial.
             let h_i_eval = &round_3_result.composition_poly_parts_ood_evaluation[i];
@@ -711,11 +960,12 @@ pub trait IsStarkProver<A: AIR> {
 
     /// Computes values and validity proofs of the evaluations of the trace polynomials
     /// at the domain value corresponding to the FRI query challenge `index` and its symmetric
-    /// element.
+    /// element. Only the columns of `lde_trace` in `columns` are committed in `tree`.
     fn open_trace_polys<E>(
         domain: &Domain<A::Field>,
         tree: &BatchedMerkleTree<E>,
         lde_trace: &Table<E>,
+        columns: Range<usize>,
         challenge: usize,
     ) -> PolynomialOpenings<E>
     where
@@ -731,11 +981,11 @@ pub trait IsStarkProver<A: AIR> {
         PolynomialOpenings {
             proof: tree.get_proof_by_pos(index).unwrap(),
             proof_sym: tree.get_proof_by_pos(index_sym).unwrap(),
-            evaluations: lde_trace
-                .get_row(reverse_index(index, domain_size as u64))
-                .to_vec(),
-            evaluations_sym: lde_trace
-                .get_row(reverse_index(index_sym, domain_size as u64))
+            evaluations: lde_trace.get_row(reverse_index(index, domain_size as u64))
+                [columns.clone()]
+            .to_vec(),
+            evaluations_sym: lde_trace.get_row(reverse_index(index_sym, domain_size as u64))
+                [columns]
                 .to_vec(),
         }
     }
@@ -752,15 +1002,28 @@ pub trait IsStarkProver<A: AIR> {
         FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
     {
         let mut openings = Vec::new();
+        let num_main_columns = round_1_result.main.trace_polys.len();
 
         for index in indexes_to_open.iter() {
             let main_trace_opening = Self::open_trace_polys::<A::Field>(
                 domain,
                 &round_1_result.main.lde_trace_merkle_tree,
                 &round_1_result.lde_trace.main_table,
+                0..num_main_columns,
                 *index,
             );
 
+            let preprocessed_trace_polys =
+                round_1_result.preprocessed.as_ref().map(|preprocessed| {
+                    Self::open_trace_polys::<A::Field>(
+                        domain,
+                        &preprocessed.lde_trace_merkle_tree,
+                        &round_1_result.lde_trace.main_table,
+                        num_main_columns..num_main_columns + preprocessed.trace_polys.len(),
+                        *index,
+                    )
+                });
+
             let composition_openings = Self::open_composition_poly(
                 &round_2_result.composition_poly_merkle_tree,
                 &round_2_result.lde_composition_poly_evaluations,
@@ -772,6 +1035,7 @@ pub trait IsStarkProver<A: AIR> {
                     domain,
                     &aux.lde_trace_merkle_tree,
                     &round_1_result.lde_trace.aux_table,
+                    0..aux.trace_polys.len(),
                     *index,
                 )
             });
@@ -779,6 +1043,7 @@ pub trait IsStarkProver<A: AIR> {
             openings.push(DeepPolynomialOpening {
                 composition_poly: composition_openings,
                 main_trace_polys: main_trace_opening,
+                preprocessed_trace_polys,
                 aux_trace_polys,
             });
         }
@@ -786,13 +1051,40 @@ pub trait IsStarkProver<A: AIR> {
         openings
     }
 
-    // FIXME remove unwrap() calls and return errors
     /// Generates a STARK proof for the trace `main_trace` with public inputs `pub_inputs`.
+    /// The AIR must not have preprocessed columns; use `prove_with_preprocessed_columns` for
+    /// those that do.
     /// Warning: the transcript must be safely initializated before passing it to this method.
     fn prove(
         main_trace: &TraceTable<A::Field>,
         pub_inputs: &A::PublicInputs,
         proof_options: &ProofOptions,
+        transcript: impl IsTranscript<A::FieldExtension>,
+    ) -> Result<StarkProof<A::Field, A::FieldExtension>, ProvingError>
+    where
+        A: Send + Sync,
+        FieldElement<A::Field>: AsBytes + Send + Sync,
+        FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+    {
+        Self::prove_with_preprocessed_columns(
+            main_trace,
+            pub_inputs,
+            proof_options,
+            None,
+            transcript,
+        )
+    }
+
+    // FIXME remove unwrap() calls and return errors
+    /// Generates a STARK proof for the trace `main_trace` with public inputs `pub_inputs`. The
+    /// preprocessed columns of the AIR are taken from `proving_key`, the result of
+    /// `crate::preprocessing::setup`, which must be given if and only if the AIR has any.
+    /// Warning: the transcript must be safely initializated before passing it to this method.
+    fn prove_with_preprocessed_columns(
+        main_trace: &TraceTable<A::Field>,
+        pub_inputs: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        proving_key: Option<&ProvingKey<A::Field>>,
         mut transcript: impl IsTranscript<A::FieldExtension>,
     ) -> Result<StarkProof<A::Field, A::FieldExtension>, ProvingError>
     where
@@ -806,9 +1098,15 @@ pub trait IsStarkProver<A: AIR> {
         #[cfg(feature = "instruments")]
         let timer0 = Instant::now();
 
-        let air = A::new(main_trace.n_rows(), pub_inputs, proof_options);
+        let air = A::new(main_trace.n_rows(), pub_inputs, proof_options)?;
         let domain = Domain::new(&air);
 
+        if proof_options.zero_knowledge {
+            check_zero_knowledge_parameters(&air)?;
+        }
+        check_proving_key(&air, proving_key)?;
+        let fri_steps = check_fri_steps(&air)?;
+
         #[cfg(feature = "instruments")]
         let elapsed0 = timer0.elapsed();
         #[cfg(feature = "instruments")]
@@ -823,17 +1121,23 @@ pub trait IsStarkProver<A: AIR> {
         #[cfg(feature = "instruments")]
         let timer1 = Instant::now();
 
+        // >>>> Send commitment: [preprocessed columns], fixed at setup
+        if let Some(proving_key) = proving_key {
+            transcript.append_bytes(&proving_key.verifying_key.preprocessed_root);
+        }
+
         let round_1_result = Self::round_1_randomized_air_with_preprocessing(
             &air,
             main_trace,
             &domain,
+            proving_key,
             &mut transcript,
         )?;
 
         #[cfg(debug_assertions)]
         validate_trace(
             &air,
-            &round_1_result.main.trace_polys,
+            &round_1_result.main_and_preprocessed_trace_polys(),
             round_1_result
                 .aux
                 .as_ref()
@@ -947,6 +1251,7 @@ pub trait IsStarkProver<A: AIR> {
         let round_4_result = Self::round_4_compute_and_run_fri_on_the_deep_composition_polynomial(
             &air,
             &domain,
+            &fri_steps,
             &round_1_result,
             &round_2_result,
             &round_3_result,
@@ -989,7 +1294,7 @@ pub trait IsStarkProver<A: AIR> {
             // [pₖ]
             fri_layers_merkle_roots: round_4_result.fri_layers_merkle_roots,
             // pₙ
-            fri_last_value: round_4_result.fri_last_value,
+            fri_last_layer_coefficients: round_4_result.fri_last_layer_coefficients,
             // Open(p₀(D₀), 𝜐ₛ), Open(pₖ(Dₖ), −𝜐ₛ^(2ᵏ))
             query_list: round_4_result.query_list,
             // Open(H₁(D_LDE, 𝜐₀), Open(H₂(D_LDE, 𝜐₀), Open(tⱼ(D_LDE), 𝜐₀)
@@ -1051,13 +1356,15 @@ mod tests {
             fri_number_of_queries: 1,
             coset_offset,
             grinding_factor,
+            zero_knowledge: false,
+            fri_step_list: vec![],
+            fri_last_layer_degree_bound: 1,
         };
 
-        let domain = Domain::new(&simple_fibonacci::FibonacciAIR::new(
-            trace_length,
-            &pub_inputs,
-            &proof_options,
-        ));
+        let domain = Domain::new(
+            &simple_fibonacci::FibonacciAIR::new(trace_length, &pub_inputs, &proof_options)
+                .unwrap(),
+        );
         assert_eq!(domain.blowup_factor, 2);
         assert_eq!(domain.interpolation_domain_size, trace_length);
         assert_eq!(domain.root_order, trace_length.trailing_zeros());
@@ -1169,7 +1476,7 @@ mod tests {
     ) -> Challenges<Fibonacci2ColsShifted<Stark252PrimeField>> {
         let (proof, public_inputs, options, seed) = proof_parts_stone_compatibility_case_1();
 
-        let air = Fibonacci2ColsShifted::new(proof.trace_length, &public_inputs, &options);
+        let air = Fibonacci2ColsShifted::new(proof.trace_length, &public_inputs, &options).unwrap();
         let domain = Domain::new(&air);
         Verifier::step_1_replay_rounds_and_recover_challenges(
             &air,
@@ -1340,7 +1647,7 @@ mod tests {
         let proof = stone_compatibility_case_1_proof();
 
         assert_eq!(
-            proof.fri_last_value,
+            proof.fri_last_layer_coefficients[0],
             FieldElement::from_hex_unchecked(
                 "43fedf9f9e3d1469309862065c7d7ca0e7e9ce451906e9c01553056f695aec9"
             )
@@ -1486,7 +1793,7 @@ mod tests {
 
         assert_eq!(proof.query_list.len(), 1);
 
-        assert_eq!(proof.query_list[0].layers_evaluations_sym.len(), 1);
+        assert_eq!(proof.query_list[0].layers_evaluations_coset.len(), 1);
 
         assert_eq!(
             proof.query_list[0].layers_auth_paths[0].merkle_path.len(),
@@ -1499,7 +1806,7 @@ mod tests {
         let proof = stone_compatibility_case_1_proof();
 
         assert_eq!(
-            proof.query_list[0].layers_evaluations_sym[0],
+            proof.query_list[0].layers_evaluations_coset[0][0],
             FieldElement::from_hex_unchecked(
                 "0684991e76e5c08db17f33ea7840596be876d92c143f863e77cad10548289fd0"
             )
@@ -1565,7 +1872,7 @@ mod tests {
     ) -> Challenges<Fibonacci2ColsShifted<Stark252PrimeField>> {
         let (proof, public_inputs, options, seed) = proof_parts_stone_compatibility_case_2();
 
-        let air = Fibonacci2ColsShifted::new(proof.trace_length, &public_inputs, &options);
+        let air = Fibonacci2ColsShifted::new(proof.trace_length, &public_inputs, &options).unwrap();
         let domain = Domain::new(&air);
         Verifier::step_1_replay_rounds_and_recover_challenges(
             &air,
@@ -1596,7 +1903,7 @@ mod tests {
         let proof = stone_compatibility_case_2_proof();
 
         assert_eq!(
-            proof.query_list[0].layers_evaluations_sym[7],
+            proof.query_list[0].layers_evaluations_coset[7][0],
             FieldElement::from_hex_unchecked(
                 "7aa40c5a4e30b44fee5bcc47c54072a435aa35c1a31b805cad8126118cc6860"
             )
@@ -1613,4 +1920,19 @@ mod tests {
             decode_hex("f12f159b548ca2c571a270870d43e7ec2ead78b3e93b635738c31eb9bcda3dda").unwrap()
         );
     }
+
+    #[test]
+    fn random_elements_of_an_extension_field_are_not_in_the_base_field() {
+        use lambdaworks_math::field::fields::fft_friendly::quartic_babybear::QuarticBabybearField;
+
+        let mut rng = rand::thread_rng();
+        let elements: Vec<FieldElement<QuarticBabybearField>> = (0..8)
+            .map(|_| sample_random_field_element(&mut rng))
+            .collect();
+        for coordinate in 1..4 {
+            assert!(elements
+                .iter()
+                .any(|element| element.value()[coordinate] != FieldElement::zero()));
+        }
+    }
 }
@@ -0,0 +1,800 @@
+//! STARK proving with a bounded amount of evaluations over the LDE domain in memory.
+//!
+//! The default prover keeps the evaluations of the trace, of the constraints and of every layer
+//! of FRI over their whole domains, together with the Merkle trees committing to them. The
+//! `StreamingProver` only keeps polynomials. Each domain is split into blocks of at most
+//! `trace_length` points, which are the cosets of the trace domain in the LDE domain, and the
+//! evaluations over a block are computed when needed, in chunks of blocks whose size is bounded
+//! by a chunk budget:
+//!
+//! - The trace, the parts of the composition polynomial and the layers of FRI are committed one
+//!   chunk of blocks at a time. The evaluations over a block are hashed into the leaves of the
+//!   subtree of the block and only its root is kept, so each Merkle tree keeps the nodes above
+//!   the roots of its blocks.
+//! - The constraints are evaluated over one chunk of cosets at a time, and only over as many
+//!   cosets as needed to interpolate the composition polynomial, whose degree is less than
+//!   `trace_length` times the number of its parts.
+//! - The openings of the queries recompute the evaluations over the blocks holding the opened
+//!   leaves and the subtrees of those blocks.
+//!
+//! Besides the chunk, the prover keeps the trace polynomials, plus a copy of the main ones over
+//! the extension field while computing the DEEP composition polynomial, the composition
+//! polynomial and its parts, the polynomials of the layers of FRI and the nodes of the Merkle
+//! trees above the blocks. None of them grows with the blowup factor, except the evaluations
+//! used to interpolate the composition polynomial, which are as many as its coefficients. The
+//! smallest chunk is a single block, so budgets smaller than the evaluations over one block
+//! behave as a budget of one block. The price is computing the evaluations over the blocks of
+//! each commitment that are opened a second time.
+//!
+//! The proofs are identical to the ones generated by the default prover for traces satisfying
+//! the constraints, so they are checked by the same verifier.
+
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_crypto::merkle_tree::proof::Proof;
+use lambdaworks_crypto::merkle_tree::traits::IsMerkleTreeBackend;
+use lambdaworks_math::fft::cpu::bit_reversing::{in_place_bit_reverse_permute, reverse_index};
+use lambdaworks_math::field::element::FieldElement;
+use lambdaworks_math::field::traits::{IsFFTField, IsField, IsSubFieldOf};
+use lambdaworks_math::polynomial::Polynomial;
+use lambdaworks_math::traits::AsBytes;
+use log::info;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+
+use crate::config::{BatchedMerkleTree, BatchedMerkleTreeBackend, Commitment};
+use crate::constraints::evaluator::ConstraintEvaluator;
+#[cfg(debug_assertions)]
+use crate::debug::validate_trace;
+use crate::domain::Domain;
+use crate::fri;
+use crate::fri::fri_decommit::FriDecommitment;
+use crate::proof::options::ProofOptions;
+use crate::proof::stark::{
+    DeepPolynomialOpening, DeepPolynomialOpenings, PolynomialOpenings, StarkProof,
+};
+use crate::prover::{
+    check_fri_steps, check_zero_knowledge_parameters, evaluate_polynomial_on_coset,
+    mask_with_vanishing_polynomial, sample_random_field_element, IsStarkProver, Prover,
+    ProvingError, Round3,
+};
+use crate::trace::{columns2rows, get_trace_evaluations, LDETraceTable, TraceTable};
+use crate::traits::AIR;
+
+/// A STARK prover that evaluates polynomials over the LDE domain in chunks of at most
+/// `chunk_budget` bytes. See the module documentation for what else it keeps in memory.
+pub struct StreamingProver<A: AIR> {
+    phantom: PhantomData<A>,
+}
+
+impl<A> StreamingProver<A>
+where
+    A: AIR + Send + Sync,
+    FieldElement<A::Field>: AsBytes + Send + Sync,
+    FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+{
+    /// Generates a STARK proof for the trace `main_trace` with public inputs `pub_inputs`,
+    /// evaluating polynomials over chunks of the LDE domain of at most `chunk_budget` bytes.
+    /// Warning: the transcript must be safely initializated before passing it to this method.
+    pub fn prove(
+        main_trace: &TraceTable<A::Field>,
+        pub_inputs: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        chunk_budget: usize,
+        mut transcript: impl IsTranscript<A::FieldExtension>,
+    ) -> Result<StarkProof<A::Field, A::FieldExtension>, ProvingError> {
+        info!("Started streaming proof generation...");
+
+        let air = A::new(main_trace.n_rows(), pub_inputs, proof_options)?;
+        let domain = Domain::without_lde_roots(&air);
+
+        if proof_options.zero_knowledge {
+            check_zero_knowledge_parameters(&air)?;
+        }
+        if air.num_preprocessed_columns() > 0 {
+            return Err(ProvingError::WrongParameter(
+                "the streaming prover does not support preprocessed columns".to_string(),
+            ));
+        }
+        let fri_steps = check_fri_steps(&air)?;
+
+        let number_of_parts = air.num_composition_poly_parts();
+        let composition_cosets = number_of_parts.next_power_of_two();
+        if composition_cosets > domain.blowup_factor {
+            return Err(ProvingError::WrongParameter(format!(
+                "the composition polynomial has {number_of_parts} parts, more than the blowup factor {}",
+                domain.blowup_factor
+            )));
+        }
+
+        let trace_length = domain.interpolation_domain_size;
+        let lde_size = trace_length * domain.blowup_factor;
+        let lde_domain = BlockDomain {
+            offset: domain.coset_offset.clone(),
+            size: lde_size,
+            block_size: trace_length,
+        };
+
+        // ===================================
+        // ==========|   Round 1   |==========
+        // ===================================
+
+        let masking_degree = air.zk_masking_degree();
+
+        let main_trace_polys = interpolate_trace::<A::Field, A::Field>(main_trace, masking_degree);
+        let main_commitment =
+            BlockCommitment::commit(&main_trace_polys, lde_domain.clone(), 1, chunk_budget);
+        transcript.append_bytes(&main_commitment.root);
+
+        let rap_challenges = air.build_rap_challenges(&mut transcript);
+
+        let aux_trace = air.build_auxiliary_trace(main_trace, &rap_challenges);
+        let (aux_trace_polys, aux_commitment) = if !aux_trace.is_empty() {
+            let aux_trace_polys =
+                interpolate_trace::<A::Field, A::FieldExtension>(&aux_trace, masking_degree);
+            let aux_commitment =
+                BlockCommitment::commit(&aux_trace_polys, lde_domain.clone(), 1, chunk_budget);
+            transcript.append_bytes(&aux_commitment.root);
+            (aux_trace_polys, Some(aux_commitment))
+        } else {
+            (Vec::new(), None)
+        };
+        drop(aux_trace);
+
+        #[cfg(debug_assertions)]
+        validate_trace(
+            &air,
+            &main_trace_polys,
+            &aux_trace_polys,
+            &domain,
+            &rap_challenges,
+        );
+
+        // ===================================
+        // ==========|   Round 2   |==========
+        // ===================================
+
+        // <<<< Receive challenge: 𝛽
+        let beta = transcript.sample_field_element();
+        let num_boundary_constraints = air.boundary_constraints(&rap_challenges).constraints.len();
+        let num_transition_constraints = air.context().num_transition_constraints;
+
+        let mut coefficients: Vec<_> =
+            core::iter::successors(Some(FieldElement::one()), |x| Some(x * &beta))
+                .take(num_boundary_constraints + num_transition_constraints)
+                .collect();
+
+        let transition_coefficients: Vec<_> =
+            coefficients.drain(..num_transition_constraints).collect();
+        let boundary_coefficients = coefficients;
+
+        // The composition polynomial has degree less than `trace_length * number_of_parts`, so
+        // it is interpolated from its evaluations over the first `composition_cosets` blocks of
+        // the LDE domain in bit-reversed order. They form the coset of the subgroup of order
+        // `trace_length * composition_cosets` with the offset of the LDE domain.
+        let evaluator = ConstraintEvaluator::new(&air, &rap_challenges);
+        let coset_domain = evaluator.coset_domain(&air, &domain);
+        let (main_columns, aux_columns) = air.trace_layout();
+        let coset_row_size = main_columns * std::mem::size_of::<FieldElement<A::Field>>()
+            + aux_columns * std::mem::size_of::<FieldElement<A::FieldExtension>>()
+            + (num_boundary_constraints
+                + num_transition_constraints
+                + air.get_periodic_column_values().len())
+                * std::mem::size_of::<FieldElement<A::Field>>()
+            + std::mem::size_of::<FieldElement<A::FieldExtension>>();
+        let cosets_per_chunk = blocks_per_chunk(
+            chunk_budget,
+            coset_row_size * trace_length,
+            composition_cosets,
+        );
+
+        let mut constraint_evaluations =
+            vec![FieldElement::<A::FieldExtension>::zero(); composition_cosets * trace_length];
+        let blocks: Vec<_> = (0..composition_cosets).collect();
+        for chunk in blocks.chunks(cosets_per_chunk) {
+            #[cfg(feature = "parallel")]
+            let chunk_iter = chunk.par_iter();
+            #[cfg(not(feature = "parallel"))]
+            let chunk_iter = chunk.iter();
+
+            let chunk_evaluations: Vec<_> = chunk_iter
+                .map(|block| {
+                    let coset_offset = lde_domain.block_offset(*block);
+                    let lde_trace = LDETraceTable::from_columns(
+                        evaluate_on_coset(&main_trace_polys, trace_length, &coset_offset),
+                        evaluate_on_coset(&aux_trace_polys, trace_length, &coset_offset),
+                        A::STEP_SIZE,
+                        1,
+                    );
+                    evaluator.evaluate_on_coset(
+                        &air,
+                        &domain,
+                        &coset_domain,
+                        reverse_index(*block, domain.blowup_factor as u64),
+                        &lde_trace,
+                        &transition_coefficients,
+                        &boundary_coefficients,
+                        &rap_challenges,
+                    )
+                })
+                .collect();
+
+            for (block, coset_evaluations) in chunk.iter().zip(chunk_evaluations) {
+                let coset_index = reverse_index(*block, composition_cosets as u64);
+                for (k, evaluation) in coset_evaluations.into_iter().enumerate() {
+                    constraint_evaluations[coset_index + composition_cosets * k] = evaluation;
+                }
+            }
+        }
+        drop(coset_domain);
+
+        #[cfg(not(feature = "parallel"))]
+        let composition_poly =
+            Polynomial::interpolate_offset_fft(&constraint_evaluations, &domain.coset_offset)
+                .unwrap();
+        #[cfg(feature = "parallel")]
+        let composition_poly = Polynomial::parallel_interpolate_offset_fft(
+            &constraint_evaluations,
+            &domain.coset_offset,
+        )
+        .unwrap();
+        drop(constraint_evaluations);
+
+        let mut composition_poly_parts = composition_poly.break_in_parts(number_of_parts);
+        drop(composition_poly);
+
+        if proof_options.zero_knowledge {
+            let mut rng = rand::thread_rng();
+            let randomizer: Vec<_> = (0..2 * air.trace_length())
+                .map(|_| sample_random_field_element(&mut rng))
+                .collect();
+            composition_poly_parts.push(Polynomial::new(&randomizer));
+        }
+
+        // The leaves of the composition polynomial tree hold two consecutive rows.
+        let composition_commitment =
+            BlockCommitment::commit(&composition_poly_parts, lde_domain, 2, chunk_budget);
+
+        // >>>> Send commitments: [H₁], [H₂]
+        transcript.append_bytes(&composition_commitment.root);
+
+        // ===================================
+        // ==========|   Round 3   |==========
+        // ===================================
+
+        // <<<< Receive challenge: z
+        let z = sample_z_ood(&mut transcript, &domain);
+
+        let z_power = z.pow(air.num_composition_poly_parts());
+        let round_3_result = Round3 {
+            trace_ood_evaluations: get_trace_evaluations(
+                &main_trace_polys,
+                &aux_trace_polys,
+                &z,
+                &air.context().transition_offsets,
+                &domain.trace_primitive_root,
+                A::STEP_SIZE,
+            ),
+            composition_poly_parts_ood_evaluation: composition_poly_parts
+                .iter()
+                .map(|part| part.evaluate(&z_power))
+                .collect(),
+        };
+
+        // >>>> Send values: tⱼ(zgᵏ)
+        for col in round_3_result.trace_ood_evaluations.columns().iter() {
+            for elem in col.iter() {
+                transcript.append_field_element(elem);
+            }
+        }
+
+        // >>>> Send values: Hᵢ(z^N)
+        for element in round_3_result.composition_poly_parts_ood_evaluation.iter() {
+            transcript.append_field_element(element);
+        }
+
+        // ===================================
+        // ==========|   Round 4   |==========
+        // ===================================
+
+        let mut all_trace_polys: Vec<_> = main_trace_polys
+            .iter()
+            .map(|poly| poly.clone().to_extension())
+            .collect();
+        all_trace_polys.extend_from_slice(&aux_trace_polys);
+
+        // Compute p₀ (deep composition polynomial)
+        let deep_composition_poly = Prover::<A>::sample_deep_composition_poly(
+            &air,
+            &domain,
+            &all_trace_polys,
+            &composition_poly_parts,
+            &round_3_result,
+            &z,
+            &mut transcript,
+        );
+        drop(all_trace_polys);
+
+        // FRI commit and query phases. Each layer is split in blocks of at most `trace_length`
+        // evaluations, and at least the evaluations of one leaf.
+        let (fri_last_layer_coefficients, fri_layers) = fri::commit_phase_with(
+            &fri_steps,
+            air.options().fri_last_layer_degree_bound,
+            deep_composition_poly,
+            &mut transcript,
+            &domain.coset_offset,
+            lde_size,
+            |poly, coset_offset, domain_size, coset_size| {
+                let layer_domain = BlockDomain {
+                    offset: coset_offset.clone(),
+                    size: domain_size,
+                    block_size: domain_size.min(trace_length.max(coset_size)),
+                };
+                let commitment = BlockCommitment::commit(
+                    std::slice::from_ref(poly),
+                    layer_domain,
+                    coset_size,
+                    chunk_budget,
+                );
+                let root = commitment.root;
+                let layer = FriLayer {
+                    poly: poly.clone(),
+                    commitment,
+                };
+                (layer, root)
+            },
+        );
+
+        let (nonce, iotas) = Prover::<A>::grind_and_sample_query_indexes(
+            &air,
+            lde_size,
+            fri_steps[0],
+            &mut transcript,
+        );
+        let query_list = fri_query_phase(&fri_layers, &fri_steps, &iotas);
+
+        let fri_layers_merkle_roots: Vec<_> = fri_layers
+            .iter()
+            .map(|layer| layer.commitment.root)
+            .collect();
+        drop(fri_layers);
+
+        let pair_indexes: Vec<_> = iotas
+            .iter()
+            .flat_map(|iota| fri::first_layer_pair_indexes(*iota, fri_steps[0]))
+            .collect();
+        let deep_poly_openings = open_deep_composition_poly(
+            &pair_indexes,
+            (&main_trace_polys, &main_commitment),
+            (&aux_trace_polys, aux_commitment.as_ref()),
+            (&composition_poly_parts, &composition_commitment),
+        );
+
+        info!("End streaming proof generation");
+
+        Ok(StarkProof {
+            lde_trace_main_merkle_root: main_commitment.root,
+            lde_trace_aux_merkle_root: aux_commitment.map(|commitment| commitment.root),
+            trace_ood_evaluations: round_3_result.trace_ood_evaluations,
+            composition_poly_root: composition_commitment.root,
+            composition_poly_parts_ood_evaluation: round_3_result
+                .composition_poly_parts_ood_evaluation,
+            fri_layers_merkle_roots,
+            fri_last_layer_coefficients,
+            query_list,
+            deep_poly_openings,
+            nonce,
+            trace_length: air.trace_length(),
+        })
+    }
+}
+
+/// Returns the number of blocks whose evaluations fit in `chunk_budget` bytes, given the size
+/// in bytes of the evaluations over one block. It is at least one and at most `num_blocks`.
+fn blocks_per_chunk(chunk_budget: usize, block_size_in_bytes: usize, num_blocks: usize) -> usize {
+    (chunk_budget / block_size_in_bytes.max(1)).clamp(1, num_blocks)
+}
+
+/// Interpolates the columns of `trace` and masks the resulting polynomials if
+/// `masking_degree` is not zero.
+fn interpolate_trace<F, E>(
+    trace: &TraceTable<E>,
+    masking_degree: usize,
+) -> Vec<Polynomial<FieldElement<E>>>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+    FieldElement<E>: Send + Sync,
+{
+    let trace_polys = trace.compute_trace_polys::<F>();
+    if masking_degree == 0 {
+        return trace_polys;
+    }
+    let mut rng = rand::thread_rng();
+    trace_polys
+        .iter()
+        .map(|poly| {
+            let mask: Vec<_> = (0..masking_degree)
+                .map(|_| sample_random_field_element(&mut rng))
+                .collect();
+            mask_with_vanishing_polynomial(poly, &mask, trace.n_rows())
+        })
+        .collect()
+}
+
+/// Samples the out of domain challenge as `IsTranscript::sample_z_ood` does for the LDE and
+/// trace domains of `domain`, without their points: a value lies in the coset `hG` of the
+/// subgroup `G` of order `n` if and only if its `n`-th power is `hⁿ`.
+fn sample_z_ood<F, E>(transcript: &mut impl IsTranscript<E>, domain: &Domain<F>) -> FieldElement<E>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+{
+    let lde_size = (domain.interpolation_domain_size * domain.blowup_factor) as u64;
+    let lde_offset_power = domain.coset_offset.pow(lde_size).to_extension::<E>();
+    loop {
+        let value: FieldElement<E> = transcript.sample_field_element();
+        if value.pow(lde_size) != lde_offset_power
+            && value.pow(domain.interpolation_domain_size) != FieldElement::one()
+        {
+            return value;
+        }
+    }
+}
+
+/// Returns the evaluations of each polynomial of `polys` over the coset of the subgroup of
+/// order `size` with offset `offset`, in natural order.
+fn evaluate_on_coset<F, E>(
+    polys: &[Polynomial<FieldElement<E>>],
+    size: usize,
+    offset: &FieldElement<F>,
+) -> Vec<Vec<FieldElement<E>>>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+    FieldElement<F>: Send + Sync,
+    FieldElement<E>: Send + Sync,
+{
+    #[cfg(feature = "parallel")]
+    let polys_iter = polys.par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let polys_iter = polys.iter();
+
+    polys_iter
+        .map(|poly| evaluate_polynomial_on_coset(poly, size, offset).unwrap())
+        .collect()
+}
+
+/// The coset of the subgroup of order `size` with offset `offset`, with its points in
+/// bit-reversed order split into blocks of `block_size` consecutive points.
+///
+/// The position `block * block_size + t` holds the point `offset * ω^rev(position)`, where `ω`
+/// is the root of unity of order `size` and `rev` reverses the bits of the position. Since
+/// `rev(position) = rev(t) * num_blocks + rev(block)`, the block holds the points of the coset
+/// of the subgroup of order `block_size` with offset `offset * ω^rev(block)`, also in
+/// bit-reversed order.
+#[derive(Clone)]
+struct BlockDomain<F: IsFFTField> {
+    offset: FieldElement<F>,
+    size: usize,
+    block_size: usize,
+}
+
+impl<F: IsFFTField> BlockDomain<F> {
+    fn num_blocks(&self) -> usize {
+        self.size / self.block_size
+    }
+
+    /// Returns the offset of the coset of the subgroup of order `block_size` formed by the
+    /// points of the block `block`.
+    fn block_offset(&self, block: usize) -> FieldElement<F> {
+        let root = F::get_primitive_root_of_unity(self.size.trailing_zeros() as u64).unwrap();
+        &self.offset * root.pow(reverse_index(block, self.num_blocks() as u64))
+    }
+
+    /// Returns the rows of evaluations of `polys` over the points of the block `block`, in
+    /// bit-reversed order.
+    fn evaluate_block<E>(
+        &self,
+        polys: &[Polynomial<FieldElement<E>>],
+        block: usize,
+    ) -> Vec<Vec<FieldElement<E>>>
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+        FieldElement<F>: Send + Sync,
+        FieldElement<E>: Send + Sync,
+    {
+        let mut columns = evaluate_on_coset(polys, self.block_size, &self.block_offset(block));
+        for column in columns.iter_mut() {
+            in_place_bit_reverse_permute(column);
+        }
+        columns2rows(columns)
+    }
+}
+
+/// The Merkle proof and the rows of evaluations of each opened leaf, by position.
+type LeafOpenings<E> = HashMap<usize, (Proof<Commitment>, Vec<Vec<FieldElement<E>>>)>;
+
+/// A commitment to the evaluations of some polynomials over a `BlockDomain`. Its root is the
+/// one of the `BatchedMerkleTree` of the rows of evaluations, with `rows_per_leaf` consecutive
+/// rows in each leaf, but only the nodes above the roots of the blocks are kept. The
+/// evaluations over a block and its subtree are recomputed to open its leaves.
+struct BlockCommitment<F: IsFFTField, E: IsField>
+where
+    FieldElement<E>: AsBytes + Send + Sync,
+{
+    domain: BlockDomain<F>,
+    rows_per_leaf: usize,
+    root: Commitment,
+    /// The tree whose leaves are the roots of the blocks, if there is more than one block.
+    blocks_tree: Option<BatchedMerkleTree<E>>,
+}
+
+impl<F, E> BlockCommitment<F, E>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+    FieldElement<F>: Send + Sync,
+    FieldElement<E>: AsBytes + Send + Sync,
+{
+    /// Commits to the evaluations of `polys` over `domain`, computing them one chunk of blocks
+    /// of at most `chunk_budget` bytes at a time.
+    fn commit(
+        polys: &[Polynomial<FieldElement<E>>],
+        domain: BlockDomain<F>,
+        rows_per_leaf: usize,
+        chunk_budget: usize,
+    ) -> Self {
+        let num_blocks = domain.num_blocks();
+        if num_blocks == 1 {
+            let hashed_leaves = Self::hash_block(polys, &domain, rows_per_leaf, 0);
+            let tree = BatchedMerkleTree::<E>::build_from_hashed_leaves(hashed_leaves);
+            return Self {
+                domain,
+                rows_per_leaf,
+                root: tree.root,
+                blocks_tree: None,
+            };
+        }
+
+        let block_size_in_bytes =
+            domain.block_size * polys.len() * std::mem::size_of::<FieldElement<E>>();
+        let blocks: Vec<_> = (0..num_blocks).collect();
+        let mut block_roots = Vec::with_capacity(num_blocks);
+        for chunk in blocks.chunks(blocks_per_chunk(
+            chunk_budget,
+            block_size_in_bytes,
+            num_blocks,
+        )) {
+            #[cfg(feature = "parallel")]
+            let chunk_iter = chunk.par_iter();
+            #[cfg(not(feature = "parallel"))]
+            let chunk_iter = chunk.iter();
+
+            let chunk_roots: Vec<_> = chunk_iter
+                .map(|block| {
+                    let hashed_leaves = Self::hash_block(polys, &domain, rows_per_leaf, *block);
+                    match Self::block_subtree(hashed_leaves) {
+                        Ok(subtree) => subtree.root,
+                        Err(leaf) => leaf,
+                    }
+                })
+                .collect();
+            block_roots.extend(chunk_roots);
+        }
+
+        let blocks_tree = BatchedMerkleTree::<E>::build_from_hashed_leaves(block_roots);
+        Self {
+            domain,
+            rows_per_leaf,
+            root: blocks_tree.root,
+            blocks_tree: Some(blocks_tree),
+        }
+    }
+
+    /// Opens the leaves at the positions `positions`, computing the evaluations over each block
+    /// holding some of them once. Returns the Merkle proof and the rows of each opened leaf.
+    fn open(&self, polys: &[Polynomial<FieldElement<E>>], positions: &[usize]) -> LeafOpenings<E> {
+        let leaves_per_block = self.domain.block_size / self.rows_per_leaf;
+        let mut positions_by_block: HashMap<usize, Vec<usize>> = HashMap::new();
+        for position in positions {
+            positions_by_block
+                .entry(position / leaves_per_block)
+                .or_default()
+                .push(*position);
+        }
+
+        let mut openings = HashMap::new();
+        for (block, block_positions) in positions_by_block {
+            let rows = self.domain.evaluate_block(polys, block);
+            let hashed_leaves = Self::leaves(&rows, self.rows_per_leaf);
+            let hashed_leaves = BatchedMerkleTreeBackend::<E>::hash_leaves(&hashed_leaves);
+
+            // The path of a leaf is its path in the subtree of its block followed by the path
+            // of the block in the tree of the blocks.
+            let (subtree, blocks_path) = match &self.blocks_tree {
+                None => (
+                    Ok(BatchedMerkleTree::<E>::build_from_hashed_leaves(
+                        hashed_leaves,
+                    )),
+                    Vec::new(),
+                ),
+                Some(blocks_tree) => (
+                    Self::block_subtree(hashed_leaves),
+                    blocks_tree.get_proof_by_pos(block).unwrap().merkle_path,
+                ),
+            };
+
+            for position in block_positions {
+                let leaf = position % leaves_per_block;
+                let mut merkle_path = match &subtree {
+                    Ok(subtree) => subtree.get_proof_by_pos(leaf).unwrap().merkle_path,
+                    Err(_) => Vec::new(),
+                };
+                merkle_path.extend_from_slice(&blocks_path);
+                let leaf_rows =
+                    rows[leaf * self.rows_per_leaf..(leaf + 1) * self.rows_per_leaf].to_vec();
+                openings.insert(position, (Proof { merkle_path }, leaf_rows));
+            }
+        }
+        openings
+    }
+
+    /// Returns the hashed leaves of the evaluations of `polys` over the block `block`.
+    fn hash_block(
+        polys: &[Polynomial<FieldElement<E>>],
+        domain: &BlockDomain<F>,
+        rows_per_leaf: usize,
+        block: usize,
+    ) -> Vec<Commitment> {
+        let rows = domain.evaluate_block(polys, block);
+        BatchedMerkleTreeBackend::<E>::hash_leaves(&Self::leaves(&rows, rows_per_leaf))
+    }
+
+    fn leaves(rows: &[Vec<FieldElement<E>>], rows_per_leaf: usize) -> Vec<Vec<FieldElement<E>>> {
+        rows.chunks(rows_per_leaf)
+            .map(|rows| rows.concat())
+            .collect()
+    }
+
+    /// Returns the subtree of a block with the given hashed leaves, or its only leaf, which is
+    /// its root, if it has one leaf.
+    fn block_subtree(
+        mut hashed_leaves: Vec<Commitment>,
+    ) -> Result<BatchedMerkleTree<E>, Commitment> {
+        if hashed_leaves.len() == 1 {
+            return Err(hashed_leaves.remove(0));
+        }
+        Ok(BatchedMerkleTree::<E>::build_from_hashed_leaves(
+            hashed_leaves,
+        ))
+    }
+}
+
+/// A layer of FRI whose evaluations are recomputed from its polynomial to open them.
+struct FriLayer<F: IsFFTField, E: IsField>
+where
+    FieldElement<E>: AsBytes + Send + Sync,
+{
+    poly: Polynomial<FieldElement<E>>,
+    commitment: BlockCommitment<F, E>,
+}
+
+/// Opens the layers of FRI at the queries `iotas`, as `fri::query_phase` does.
+fn fri_query_phase<F, E>(
+    fri_layers: &[FriLayer<F, E>],
+    fri_steps: &[usize],
+    iotas: &[usize],
+) -> Vec<FriDecommitment<E>>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+    FieldElement<F>: Send + Sync,
+    FieldElement<E>: AsBytes + Send + Sync,
+{
+    let mut decommitments: Vec<_> = iotas
+        .iter()
+        .map(|_| FriDecommitment {
+            layers_auth_paths: Vec::new(),
+            layers_evaluations_coset: Vec::new(),
+        })
+        .collect();
+
+    let mut indexes = iotas.to_vec();
+    for (layer, next_step) in fri_layers.iter().zip(&fri_steps[1..]) {
+        let coset_indexes: Vec<_> = indexes.iter().map(|index| index >> next_step).collect();
+        let openings = layer
+            .commitment
+            .open(std::slice::from_ref(&layer.poly), &coset_indexes);
+
+        for ((decommitment, index), coset_index) in decommitments
+            .iter_mut()
+            .zip(indexes.iter_mut())
+            .zip(coset_indexes)
+        {
+            let (auth_path, rows) = &openings[&coset_index];
+            let coset_start = coset_index << next_step;
+            let evaluations_coset = rows
+                .iter()
+                .enumerate()
+                .filter(|(offset, _)| coset_start + offset != *index)
+                .map(|(_, row)| row[0].clone())
+                .collect();
+            decommitment
+                .layers_evaluations_coset
+                .push(evaluations_coset);
+            decommitment.layers_auth_paths.push(auth_path.clone());
+
+            *index = coset_index;
+        }
+    }
+    decommitments
+}
+
+/// Opens the trace and composition polynomials at the pairs of symmetric elements of the LDE
+/// domain with indexes `pair_indexes`, recomputing the evaluations over their blocks.
+#[allow(clippy::type_complexity)]
+fn open_deep_composition_poly<F, E>(
+    pair_indexes: &[usize],
+    (main_trace_polys, main_commitment): (&[Polynomial<FieldElement<F>>], &BlockCommitment<F, F>),
+    (aux_trace_polys, aux_commitment): (
+        &[Polynomial<FieldElement<E>>],
+        Option<&BlockCommitment<F, E>>,
+    ),
+    (composition_poly_parts, composition_commitment): (
+        &[Polynomial<FieldElement<E>>],
+        &BlockCommitment<F, E>,
+    ),
+) -> DeepPolynomialOpenings<F, E>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+    FieldElement<F>: AsBytes + Send + Sync,
+    FieldElement<E>: AsBytes + Send + Sync,
+{
+    // The pair `i` is formed by the rows of positions `2i` and `2i + 1` of the trace trees and
+    // by the leaf `i` of the composition polynomial tree.
+    let trace_positions: Vec<_> = pair_indexes
+        .iter()
+        .flat_map(|index| [2 * index, 2 * index + 1])
+        .collect();
+    let main_openings = main_commitment.open(main_trace_polys, &trace_positions);
+    let aux_openings =
+        aux_commitment.map(|commitment| commitment.open(aux_trace_polys, &trace_positions));
+    let composition_openings = composition_commitment.open(composition_poly_parts, pair_indexes);
+
+    fn open_trace<E: IsField>(openings: &LeafOpenings<E>, index: usize) -> PolynomialOpenings<E> {
+        let (proof, rows) = &openings[&(2 * index)];
+        let (proof_sym, rows_sym) = &openings[&(2 * index + 1)];
+        PolynomialOpenings {
+            proof: proof.clone(),
+            proof_sym: proof_sym.clone(),
+            evaluations: rows[0].clone(),
+            evaluations_sym: rows_sym[0].clone(),
+        }
+    }
+
+    pair_indexes
+        .iter()
+        .map(|index| {
+            let (proof, rows) = &composition_openings[index];
+            DeepPolynomialOpening {
+                composition_poly: PolynomialOpenings {
+                    proof: proof.clone(),
+                    proof_sym: proof.clone(),
+                    evaluations: rows[0].clone(),
+                    evaluations_sym: rows[1].clone(),
+                },
+                main_trace_polys: open_trace(&main_openings, *index),
+                preprocessed_trace_polys: None,
+                aux_trace_polys: aux_openings
+                    .as_ref()
+                    .map(|openings| open_trace(openings, *index)),
+            }
+        })
+        .collect()
+}
@@ -1,20 +1,41 @@
+use lambdaworks_crypto::fiat_shamir::default_transcript::DefaultTranscript;
 use lambdaworks_math::field::{
-    element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+    element::FieldElement,
+    fields::{
+        fft_friendly::{
+            babybear::Babybear31PrimeField, quartic_babybear::QuarticBabybearField,
+            stark_252_prime_field::Stark252PrimeField,
+        },
+        u64_goldilocks_field::Goldilocks64Field,
+    },
 };
 
 use crate::{
+    circle::{prover::CircleProver, verifier::CircleVerifier, QM31},
     examples::{
+        babybear_fibonacci::{
+            babybear_fibonacci_trace, BabybearFibonacciAIR, BabybearFibonacciPublicInputs,
+        },
         bit_flags::{self, BitFlagsAIR},
+        circle_cubic::{circle_cubic_trace, CircleCubicAIR, CircleCubicPublicInputs},
+        circle_fibonacci::{
+            circle_fibonacci_trace, CircleFibonacciAIR, CircleFibonacciPublicInputs,
+        },
         dummy_air::{self, DummyAIR},
         fibonacci_2_cols_shifted::{self, Fibonacci2ColsShifted},
         fibonacci_2_columns::{self, Fibonacci2ColsAIR},
         fibonacci_rap::{fibonacci_rap_trace, FibonacciRAP, FibonacciRAPPublicInputs},
         quadratic_air::{self, QuadraticAIR, QuadraticPublicInputs},
+        range_check_logup::{range_check_logup_trace, RangeCheckLogUpAIR},
+        round_constants::{round_constants_trace, RoundConstantsAIR, RoundConstantsPublicInputs},
         simple_fibonacci::{self, FibonacciAIR, FibonacciPublicInputs},
         simple_periodic_cols::{self, SimplePeriodicAIR, SimplePeriodicPublicInputs},
     },
-    proof::options::ProofOptions,
-    prover::{IsStarkProver, Prover},
+    preprocessing::setup,
+    proof::{options::ProofOptions, security::estimate_security},
+    prover::{IsStarkProver, Prover, ProvingError},
+    streaming::StreamingProver,
+    traits::AIR,
     transcript::StoneProverTranscript,
     verifier::{IsStarkVerifier, Verifier},
     Felt252,
@@ -46,6 +67,164 @@ fn test_prove_fib() {
     ));
 }
 
+#[test_log::test]
+fn test_prove_fib_over_goldilocks() {
+    type FE = FieldElement<Goldilocks64Field>;
+    let trace = simple_fibonacci::fibonacci_trace([FE::from(1), FE::from(1)], 256);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: FE::one(),
+        a1: FE::one(),
+    };
+
+    let proof = Prover::<FibonacciAIR<Goldilocks64Field>>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::<Goldilocks64Field>::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::<FibonacciAIR<Goldilocks64Field>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::<Goldilocks64Field>::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_prove_babybear_fib_with_challenges_in_the_quartic_extension() {
+    type FE = FieldElement<Babybear31PrimeField>;
+    let trace = babybear_fibonacci_trace([FE::from(1), FE::from(1)], 64);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = BabybearFibonacciPublicInputs {
+        a0: FE::one(),
+        a1: FE::one(),
+    };
+
+    let proof = Prover::<BabybearFibonacciAIR>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::<QuarticBabybearField>::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::<BabybearFibonacciAIR>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::<QuarticBabybearField>::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_prove_babybear_fib_zk_with_challenges_in_the_quartic_extension() {
+    type FE = FieldElement<Babybear31PrimeField>;
+    let trace = babybear_fibonacci_trace([FE::from(1), FE::from(1)], 64);
+
+    let proof_options = zero_knowledge_test_options();
+
+    let pub_inputs = BabybearFibonacciPublicInputs {
+        a0: FE::one(),
+        a1: FE::one(),
+    };
+
+    let prove = || {
+        Prover::<BabybearFibonacciAIR>::prove(
+            &trace,
+            &pub_inputs,
+            &proof_options,
+            DefaultTranscript::<QuarticBabybearField>::new(&[]),
+        )
+        .unwrap()
+    };
+    let (proof_1, proof_2) = (prove(), prove());
+
+    assert!(Verifier::<BabybearFibonacciAIR>::verify(
+        &proof_1,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::<QuarticBabybearField>::new(&[]),
+    ));
+    assert_ne!(proof_1.composition_poly_root, proof_2.composition_poly_root);
+}
+
+#[test_log::test]
+fn test_prove_babybear_fib_with_wrong_public_inputs_is_rejected() {
+    type FE = FieldElement<Babybear31PrimeField>;
+    let trace = babybear_fibonacci_trace([FE::from(1), FE::from(1)], 64);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = BabybearFibonacciPublicInputs {
+        a0: FE::one(),
+        a1: FE::one(),
+    };
+    let wrong_pub_inputs = BabybearFibonacciPublicInputs {
+        a0: FE::one(),
+        a1: FE::from(2),
+    };
+
+    let proof = Prover::<BabybearFibonacciAIR>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::<QuarticBabybearField>::new(&[]),
+    )
+    .unwrap();
+    assert!(!Verifier::<BabybearFibonacciAIR>::verify(
+        &proof,
+        &wrong_pub_inputs,
+        &proof_options,
+        DefaultTranscript::<QuarticBabybearField>::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn babybear_fib_security_estimate_grows_with_the_extension_degree() {
+    let proof_options = ProofOptions {
+        blowup_factor: 8,
+        fri_number_of_queries: 30,
+        coset_offset: 3,
+        grinding_factor: 20,
+        zero_knowledge: false,
+        fri_step_list: vec![],
+        fri_last_layer_degree_bound: 1,
+    };
+    let over_base_field = estimate_security(
+        &FibonacciAIR::<Babybear31PrimeField>::new(
+            1 << 16,
+            &FibonacciPublicInputs {
+                a0: FieldElement::one(),
+                a1: FieldElement::one(),
+            },
+            &proof_options,
+        )
+        .unwrap(),
+    );
+    let over_quartic_extension = estimate_security(
+        &BabybearFibonacciAIR::new(
+            1 << 16,
+            &BabybearFibonacciPublicInputs {
+                a0: FieldElement::one(),
+                a1: FieldElement::one(),
+            },
+            &proof_options,
+        )
+        .unwrap(),
+    );
+
+    assert!(
+        over_quartic_extension.conjectured_security_bits
+            > over_base_field.conjectured_security_bits
+    );
+    assert!(over_quartic_extension.provable_security_bits > over_base_field.provable_security_bits);
+}
+
 #[test_log::test]
 fn test_prove_fib17() {
     type FE = FieldElement<Stark252PrimeField>;
@@ -56,6 +235,9 @@ fn test_prove_fib17() {
         fri_number_of_queries: 7,
         coset_offset: 3,
         grinding_factor: 1,
+        zero_knowledge: false,
+        fri_step_list: vec![],
+        fri_last_layer_degree_bound: 1,
     };
 
     let pub_inputs = FibonacciPublicInputs {
@@ -236,6 +418,76 @@ fn test_prove_rap_fib() {
     ));
 }
 
+#[test_log::test]
+fn test_prove_range_check_logup() {
+    let values = [3, 7, 0, 1, 3, 12, 15, 15, 2, 9, 3, 4, 4, 0, 11, 5];
+    let trace = range_check_logup_trace(&values);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let proof = Prover::<RangeCheckLogUpAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &(),
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::<RangeCheckLogUpAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &(),
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_prove_range_check_logup_with_a_value_missing_from_the_table_is_rejected() {
+    let values = [3, 7, 0, 1, 3, 12, 15, 15, 2, 9, 3, 4, 4, 0, 11, 5];
+    let mut trace = range_check_logup_trace::<Stark252PrimeField>(&values);
+    // 16 is out of the range, so it is not in the table.
+    trace.get_row_mut(5)[0] = Felt252::from(16);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let proof = Prover::<RangeCheckLogUpAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &(),
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(!Verifier::<RangeCheckLogUpAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &(),
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_prove_range_check_logup_with_a_wrong_multiplicity_is_rejected() {
+    let values = [3, 7, 0, 1, 3, 12, 15, 15, 2, 9, 3, 4, 4, 0, 11, 5];
+    let mut trace = range_check_logup_trace::<Stark252PrimeField>(&values);
+    // The value 3 is looked up three times.
+    trace.get_row_mut(3)[2] = Felt252::from(2);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let proof = Prover::<RangeCheckLogUpAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &(),
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(!Verifier::<RangeCheckLogUpAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &(),
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+}
+
 #[test_log::test]
 fn test_prove_dummy() {
     let trace_length = 16;
@@ -271,3 +523,848 @@ fn test_prove_bit_flags() {
         StoneProverTranscript::new(&[]),
     ));
 }
+
+fn zero_knowledge_test_options() -> ProofOptions {
+    ProofOptions {
+        zero_knowledge: true,
+        ..ProofOptions::default_test_options()
+    }
+}
+
+#[test_log::test]
+fn test_prove_fib_zk() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 1024);
+
+    let proof_options = zero_knowledge_test_options();
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::<FibonacciAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_prove_quadratic_zk() {
+    let trace = quadratic_air::quadratic_trace(Felt252::from(3), 32);
+
+    let proof_options = zero_knowledge_test_options();
+
+    let pub_inputs = QuadraticPublicInputs {
+        a0: Felt252::from(3),
+    };
+
+    let proof = Prover::<QuadraticAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::<QuadraticAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_prove_rap_fib_zk() {
+    let steps = 16;
+    let trace = fibonacci_rap_trace([Felt252::from(1), Felt252::from(1)], steps);
+
+    let proof_options = zero_knowledge_test_options();
+
+    let pub_inputs = FibonacciRAPPublicInputs {
+        steps,
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::<FibonacciRAP<Stark252PrimeField>>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::<FibonacciRAP<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_zk_proof_is_rejected_by_non_zk_verifier() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 32);
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::<FibonacciAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &pub_inputs,
+        &zero_knowledge_test_options(),
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(!Verifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &ProofOptions::default_test_options(),
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_zk_proofs_of_the_same_trace_are_different() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 32);
+
+    let proof_options = zero_knowledge_test_options();
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let prove = || {
+        Prover::<FibonacciAIR<Stark252PrimeField>>::prove(
+            &trace,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap()
+    };
+    let (proof_1, proof_2) = (prove(), prove());
+
+    assert_ne!(
+        proof_1.lde_trace_main_merkle_root,
+        proof_2.lde_trace_main_merkle_root
+    );
+    assert_ne!(
+        proof_1.trace_ood_evaluations.get_row(0),
+        proof_2.trace_ood_evaluations.get_row(0)
+    );
+}
+
+#[test_log::test]
+fn test_prove_zk_fails_if_the_trace_is_too_short_to_be_masked() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 4);
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::<FibonacciAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &pub_inputs,
+        &zero_knowledge_test_options(),
+        StoneProverTranscript::new(&[]),
+    );
+    assert!(matches!(proof, Err(ProvingError::WrongParameter(_))));
+}
+
+#[test_log::test]
+fn test_streaming_prover_generates_the_same_proof_as_the_default_prover() {
+    let steps = 16;
+    let trace = fibonacci_rap_trace([Felt252::from(1), Felt252::from(1)], steps);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = FibonacciRAPPublicInputs {
+        steps,
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::<FibonacciRAP<Stark252PrimeField>>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+
+    // A budget of zero bytes computes a single coset of the LDE at a time.
+    for chunk_budget in [0, usize::MAX] {
+        let streaming_proof = StreamingProver::<FibonacciRAP<Stark252PrimeField>>::prove(
+            &trace,
+            &pub_inputs,
+            &proof_options,
+            chunk_budget,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&proof).unwrap(),
+            serde_json::to_string(&streaming_proof).unwrap()
+        );
+    }
+}
+
+fn fri_steps_test_options(fri_step_list: Vec<usize>, last_layer_degree: usize) -> ProofOptions {
+    ProofOptions {
+        fri_step_list,
+        fri_last_layer_degree_bound: last_layer_degree,
+        ..ProofOptions::default_test_options()
+    }
+}
+
+#[test_log::test]
+fn test_prove_fib_with_fri_steps_and_last_layer_degree_bound() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 1024);
+
+    let proof_options = fri_steps_test_options(vec![0, 3, 2, 3], 4);
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let mut proof = Prover::<FibonacciAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert_eq!(proof.fri_layers_merkle_roots.len(), 2);
+    assert_eq!(proof.fri_last_layer_coefficients.len(), 4);
+    assert!(Verifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    ));
+
+    // The verifier must use the FRI steps the proof was generated with.
+    for other_options in [
+        ProofOptions::default_test_options(),
+        fri_steps_test_options(vec![0, 2, 3, 3], 4),
+        fri_steps_test_options(vec![0, 3, 2, 2], 8),
+    ] {
+        assert!(!Verifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+            &proof,
+            &pub_inputs,
+            &other_options,
+            StoneProverTranscript::new(&[]),
+        ));
+    }
+
+    let coset_evaluation = proof.query_list[0].layers_evaluations_coset[1][2];
+    proof.query_list[0].layers_evaluations_coset[1][2] += Felt252::one();
+    assert!(!Verifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    ));
+    proof.query_list[0].layers_evaluations_coset[1][2] = coset_evaluation;
+
+    proof.fri_last_layer_coefficients[3] += Felt252::one();
+    assert!(!Verifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_prove_babybear_fib_zk_with_fri_steps() {
+    type FE = FieldElement<Babybear31PrimeField>;
+    let trace = babybear_fibonacci_trace([FE::from(1), FE::from(1)], 64);
+
+    let proof_options = ProofOptions {
+        zero_knowledge: true,
+        ..fri_steps_test_options(vec![0, 2, 4], 2)
+    };
+
+    let pub_inputs = BabybearFibonacciPublicInputs {
+        a0: FE::one(),
+        a1: FE::one(),
+    };
+
+    let proof = Prover::<BabybearFibonacciAIR>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::<QuarticBabybearField>::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::<BabybearFibonacciAIR>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::<QuarticBabybearField>::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_prove_with_fri_steps_not_reaching_the_last_layer_fails() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 1024);
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    for proof_options in [
+        fri_steps_test_options(vec![0, 3, 3], 4),
+        fri_steps_test_options(vec![1, 3, 3, 3], 1),
+        fri_steps_test_options(vec![], 3),
+    ] {
+        let proof = Prover::<FibonacciAIR<Stark252PrimeField>>::prove(
+            &trace,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        );
+        assert!(matches!(proof, Err(ProvingError::WrongParameter(_))));
+    }
+}
+
+#[test_log::test]
+fn test_streaming_prover_with_fri_steps_generates_the_same_proof_as_the_default_prover() {
+    let steps = 16;
+    let trace = fibonacci_rap_trace([Felt252::from(1), Felt252::from(1)], steps);
+
+    let proof_options = fri_steps_test_options(vec![0, 2, 1], 4);
+
+    let pub_inputs = FibonacciRAPPublicInputs {
+        steps,
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::<FibonacciRAP<Stark252PrimeField>>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    let streaming_proof = StreamingProver::<FibonacciRAP<Stark252PrimeField>>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        0,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+
+    assert_eq!(
+        serde_json::to_string(&proof).unwrap(),
+        serde_json::to_string(&streaming_proof).unwrap()
+    );
+    assert!(Verifier::<FibonacciRAP<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_streaming_prove_range_check_logup() {
+    let values = [3, 7, 0, 1, 3, 12, 15, 15, 2, 9, 3, 4, 4, 0, 11, 5];
+    let trace = range_check_logup_trace(&values);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let proof = StreamingProver::<RangeCheckLogUpAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &(),
+        &proof_options,
+        0,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::<RangeCheckLogUpAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &(),
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_streaming_prove_quadratic_zk() {
+    let trace = quadratic_air::quadratic_trace(Felt252::from(3), 32);
+
+    let proof_options = zero_knowledge_test_options();
+
+    let pub_inputs = QuadraticPublicInputs {
+        a0: Felt252::from(3),
+    };
+
+    let proof = StreamingProver::<QuadraticAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        0,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::<QuadraticAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_streaming_prover_with_periodic_columns_and_exemptions_generates_the_same_proof() {
+    let proof_options = ProofOptions::default_test_options();
+
+    let trace = simple_periodic_cols::simple_periodic_trace::<Stark252PrimeField>(32);
+    let pub_inputs = SimplePeriodicPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::from(32768),
+    };
+    let proof = Prover::<SimplePeriodicAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+
+    let bit_flags_trace = bit_flags::bit_prefix_flag_trace(32);
+    let bit_flags_proof = Prover::<BitFlagsAIR>::prove(
+        &bit_flags_trace,
+        &(),
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+
+    // The budgets fit one coset, two cosets and the whole LDE of the trace.
+    for chunk_budget in [0, 2 * 32 * 32, usize::MAX] {
+        let streaming_proof = StreamingProver::<SimplePeriodicAIR<Stark252PrimeField>>::prove(
+            &trace,
+            &pub_inputs,
+            &proof_options,
+            chunk_budget,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+        assert_eq!(
+            serde_json::to_string(&proof).unwrap(),
+            serde_json::to_string(&streaming_proof).unwrap()
+        );
+
+        let streaming_proof = StreamingProver::<BitFlagsAIR>::prove(
+            &bit_flags_trace,
+            &(),
+            &proof_options,
+            chunk_budget,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+        assert_eq!(
+            serde_json::to_string(&bit_flags_proof).unwrap(),
+            serde_json::to_string(&streaming_proof).unwrap()
+        );
+    }
+}
+
+#[test_log::test]
+fn test_prove_circle_fib() {
+    let trace = circle_fibonacci_trace([FieldElement::one(), FieldElement::one()], 64);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = CircleFibonacciPublicInputs {
+        a0: FieldElement::one(),
+        a1: FieldElement::one(),
+    };
+
+    let proof = CircleProver::<CircleFibonacciAIR>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::<QM31>::new(&[]),
+    )
+    .unwrap();
+    assert!(CircleVerifier::<CircleFibonacciAIR>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::<QM31>::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_prove_circle_cubic_with_four_composition_poly_parts() {
+    let trace = circle_cubic_trace(FieldElement::from(3), 32);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = CircleCubicPublicInputs {
+        a0: FieldElement::from(3),
+    };
+
+    let proof = CircleProver::<CircleCubicAIR>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::<QM31>::new(&[]),
+    )
+    .unwrap();
+    assert_eq!(proof.composition_poly_parts_ood_evaluation.len(), 4);
+    assert!(CircleVerifier::<CircleCubicAIR>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::<QM31>::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_circle_proof_of_wrong_public_inputs_is_rejected() {
+    let trace = circle_fibonacci_trace([FieldElement::one(), FieldElement::one()], 16);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = CircleFibonacciPublicInputs {
+        a0: FieldElement::one(),
+        a1: FieldElement::one(),
+    };
+
+    let proof = CircleProver::<CircleFibonacciAIR>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::<QM31>::new(&[]),
+    )
+    .unwrap();
+
+    let wrong_pub_inputs = CircleFibonacciPublicInputs {
+        a0: FieldElement::one(),
+        a1: FieldElement::from(2),
+    };
+    assert!(!CircleVerifier::<CircleFibonacciAIR>::verify(
+        &proof,
+        &wrong_pub_inputs,
+        &proof_options,
+        DefaultTranscript::<QM31>::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_circle_proof_of_invalid_trace_is_rejected() {
+    let mut trace = circle_fibonacci_trace([FieldElement::one(), FieldElement::one()], 16);
+    trace.set_or_extend(7, 0, &FieldElement::from(5));
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = CircleFibonacciPublicInputs {
+        a0: FieldElement::one(),
+        a1: FieldElement::one(),
+    };
+
+    let proof = CircleProver::<CircleFibonacciAIR>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::<QM31>::new(&[]),
+    )
+    .unwrap();
+    assert!(!CircleVerifier::<CircleFibonacciAIR>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::<QM31>::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_circle_proof_with_tampered_fri_last_value_is_rejected() {
+    let trace = circle_fibonacci_trace([FieldElement::one(), FieldElement::one()], 16);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = CircleFibonacciPublicInputs {
+        a0: FieldElement::one(),
+        a1: FieldElement::one(),
+    };
+
+    let mut proof = CircleProver::<CircleFibonacciAIR>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::<QM31>::new(&[]),
+    )
+    .unwrap();
+    proof.fri_last_value += FieldElement::<QM31>::one();
+    assert!(!CircleVerifier::<CircleFibonacciAIR>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        DefaultTranscript::<QM31>::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_circle_prove_fails_with_zero_knowledge() {
+    let trace = circle_fibonacci_trace([FieldElement::one(), FieldElement::one()], 16);
+
+    let pub_inputs = CircleFibonacciPublicInputs {
+        a0: FieldElement::one(),
+        a1: FieldElement::one(),
+    };
+
+    let proof = CircleProver::<CircleFibonacciAIR>::prove(
+        &trace,
+        &pub_inputs,
+        &zero_knowledge_test_options(),
+        DefaultTranscript::<QM31>::new(&[]),
+    );
+    assert!(matches!(proof, Err(ProvingError::WrongParameter(_))));
+}
+
+#[test_log::test]
+fn test_prove_round_constants_with_preprocessed_columns() {
+    let trace = round_constants_trace(Felt252::from(3), 32);
+    let proof_options = ProofOptions::default_test_options();
+    let pub_inputs = RoundConstantsPublicInputs {
+        a0: Felt252::from(3),
+    };
+
+    let proving_key =
+        setup::<RoundConstantsAIR<Stark252PrimeField>>(32, &pub_inputs, &proof_options).unwrap();
+
+    let proof = Prover::<RoundConstantsAIR<Stark252PrimeField>>::prove_with_preprocessed_columns(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        Some(&proving_key),
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(
+        Verifier::<RoundConstantsAIR<Stark252PrimeField>>::verify_with_preprocessed_columns(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            Some(proving_key.verifying_key()),
+            StoneProverTranscript::new(&[]),
+        )
+    );
+}
+
+#[test_log::test]
+fn test_round_constants_needs_the_preprocessing_keys() {
+    let trace = round_constants_trace(Felt252::from(3), 32);
+    let proof_options = ProofOptions::default_test_options();
+    let pub_inputs = RoundConstantsPublicInputs {
+        a0: Felt252::from(3),
+    };
+
+    let result = Prover::<RoundConstantsAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    );
+    assert!(matches!(result, Err(ProvingError::WrongParameter(_))));
+
+    let proving_key =
+        setup::<RoundConstantsAIR<Stark252PrimeField>>(32, &pub_inputs, &proof_options).unwrap();
+    let proof = Prover::<RoundConstantsAIR<Stark252PrimeField>>::prove_with_preprocessed_columns(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        Some(&proving_key),
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(!Verifier::<RoundConstantsAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_round_constants_with_wrong_verifying_key_is_rejected() {
+    let trace = round_constants_trace(Felt252::from(3), 32);
+    let proof_options = ProofOptions::default_test_options();
+    let pub_inputs = RoundConstantsPublicInputs {
+        a0: Felt252::from(3),
+    };
+
+    let proving_key =
+        setup::<RoundConstantsAIR<Stark252PrimeField>>(32, &pub_inputs, &proof_options).unwrap();
+    let proof = Prover::<RoundConstantsAIR<Stark252PrimeField>>::prove_with_preprocessed_columns(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        Some(&proving_key),
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+
+    let mut tampered_key = proving_key.verifying_key().clone();
+    tampered_key.preprocessed_root[0] ^= 1;
+    assert!(
+        !Verifier::<RoundConstantsAIR<Stark252PrimeField>>::verify_with_preprocessed_columns(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            Some(&tampered_key),
+            StoneProverTranscript::new(&[]),
+        )
+    );
+
+    let other_length_key =
+        setup::<RoundConstantsAIR<Stark252PrimeField>>(64, &pub_inputs, &proof_options).unwrap();
+    assert!(
+        !Verifier::<RoundConstantsAIR<Stark252PrimeField>>::verify_with_preprocessed_columns(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            Some(other_length_key.verifying_key()),
+            StoneProverTranscript::new(&[]),
+        )
+    );
+
+    // The keys computed over other LDE domains are rejected, even if they have the same root.
+    for other_options in [
+        ProofOptions {
+            blowup_factor: 2 * proof_options.blowup_factor,
+            ..proof_options.clone()
+        },
+        ProofOptions {
+            coset_offset: proof_options.coset_offset + 2,
+            ..proof_options.clone()
+        },
+    ] {
+        let mut other_domain_key =
+            setup::<RoundConstantsAIR<Stark252PrimeField>>(32, &pub_inputs, &other_options)
+                .unwrap()
+                .verifying_key()
+                .clone();
+        other_domain_key.preprocessed_root = proving_key.verifying_key().preprocessed_root;
+        assert!(
+            !Verifier::<RoundConstantsAIR<Stark252PrimeField>>::verify_with_preprocessed_columns(
+                &proof,
+                &pub_inputs,
+                &proof_options,
+                Some(&other_domain_key),
+                StoneProverTranscript::new(&[]),
+            )
+        );
+    }
+}
+
+#[test_log::test]
+fn test_round_constants_with_proving_key_of_other_proof_options_fails() {
+    let trace = round_constants_trace(Felt252::from(3), 32);
+    let proof_options = ProofOptions::default_test_options();
+    let pub_inputs = RoundConstantsPublicInputs {
+        a0: Felt252::from(3),
+    };
+    let other_options = ProofOptions {
+        blowup_factor: 2 * proof_options.blowup_factor,
+        ..proof_options.clone()
+    };
+
+    let proving_key =
+        setup::<RoundConstantsAIR<Stark252PrimeField>>(32, &pub_inputs, &other_options).unwrap();
+    let result = Prover::<RoundConstantsAIR<Stark252PrimeField>>::prove_with_preprocessed_columns(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        Some(&proving_key),
+        StoneProverTranscript::new(&[]),
+    );
+    assert!(matches!(result, Err(ProvingError::WrongParameter(_))));
+}
+
+#[test_log::test]
+fn test_round_constants_with_trace_not_following_the_preprocessed_columns_is_rejected() {
+    // The trace iterates x ↦ x² + 1 instead of using the round constants.
+    let mut column = vec![Felt252::from(3)];
+    for i in 1..32 {
+        column.push(column[i - 1].square() + Felt252::one());
+    }
+    let trace = crate::trace::TraceTable::from_columns(vec![column], 1, 1);
+    let proof_options = ProofOptions::default_test_options();
+    let pub_inputs = RoundConstantsPublicInputs {
+        a0: Felt252::from(3),
+    };
+
+    let proving_key =
+        setup::<RoundConstantsAIR<Stark252PrimeField>>(32, &pub_inputs, &proof_options).unwrap();
+    let proof = Prover::<RoundConstantsAIR<Stark252PrimeField>>::prove_with_preprocessed_columns(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        Some(&proving_key),
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(
+        !Verifier::<RoundConstantsAIR<Stark252PrimeField>>::verify_with_preprocessed_columns(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            Some(proving_key.verifying_key()),
+            StoneProverTranscript::new(&[]),
+        )
+    );
+}
+
+#[test_log::test]
+fn test_prove_round_constants_with_preprocessed_columns_in_zero_knowledge() {
+    let trace = round_constants_trace(Felt252::from(3), 32);
+    let proof_options = zero_knowledge_test_options();
+    let pub_inputs = RoundConstantsPublicInputs {
+        a0: Felt252::from(3),
+    };
+
+    let proving_key =
+        setup::<RoundConstantsAIR<Stark252PrimeField>>(32, &pub_inputs, &proof_options).unwrap();
+    let proof = Prover::<RoundConstantsAIR<Stark252PrimeField>>::prove_with_preprocessed_columns(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        Some(&proving_key),
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(
+        Verifier::<RoundConstantsAIR<Stark252PrimeField>>::verify_with_preprocessed_columns(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            Some(proving_key.verifying_key()),
+            StoneProverTranscript::new(&[]),
+        )
+    );
+}
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use num_integer::Integer;
+
 use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
 use lambdaworks_math::{
     field::{
@@ -9,7 +11,7 @@ use lambdaworks_math::{
     polynomial::Polynomial,
 };
 
-use crate::{constraints::transition::TransitionConstraint, domain::Domain};
+use crate::{constraints::transition::TransitionConstraint, domain::Domain, prover::ProvingError};
 
 use super::{
     constraints::boundary::BoundaryConstraints, context::AirContext, frame::Frame,
@@ -26,11 +28,15 @@ pub trait AIR {
 
     const STEP_SIZE: usize;
 
+    /// Builds the AIR of a trace of length `trace_length`. Fails if `pub_inputs` don't
+    /// describe a statement the AIR can express.
     fn new(
         trace_length: usize,
         pub_inputs: &Self::PublicInputs,
         proof_options: &ProofOptions,
-    ) -> Self;
+    ) -> Result<Self, ProvingError>
+    where
+        Self: Sized;
 
     fn build_auxiliary_trace(
         &self,
@@ -53,8 +59,60 @@ pub trait AIR {
         self.trace_layout().1
     }
 
+    /// Returns the number of preprocessed columns: columns that don't depend on the witness and
+    /// are committed once, at setup. They are not counted in `trace_layout` nor in the
+    /// `trace_columns` of the context. In the evaluation frames they come right after the main
+    /// columns. See `crate::preprocessing`.
+    fn num_preprocessed_columns(&self) -> usize {
+        0
+    }
+
+    /// Returns the values of the preprocessed columns, one vector of length `trace_length` per
+    /// column. It is only called at setup.
+    fn preprocessed_columns(&self) -> Vec<Vec<FieldElement<Self::Field>>> {
+        Vec::new()
+    }
+
     fn composition_poly_degree_bound(&self) -> usize;
 
+    /// Returns the number of random coefficients used to mask each trace polynomial when
+    /// the proof is zero-knowledge, and zero otherwise. Every trace polynomial is revealed
+    /// at one point per row of the out of domain frame and at two points per FRI query,
+    /// so that many random coefficients make all the revealed values uniformly distributed.
+    fn zk_masking_degree(&self) -> usize {
+        let options = self.options();
+        if !options.zero_knowledge {
+            return 0;
+        }
+        2 * options.fri_number_of_queries
+            + self.context().transition_offsets.len() * Self::STEP_SIZE
+    }
+
+    /// Returns the number of parts the composition polynomial is broken into.
+    /// Masking the trace polynomials raises the degree of the composition polynomial by
+    /// the masking degree times the maximum degree of the transition constraints, so
+    /// zero-knowledge proofs need extra parts. This does not count the random polynomial
+    /// committed along with the parts to mask the DEEP composition polynomial.
+    fn num_composition_poly_parts(&self) -> usize {
+        let number_of_parts = self.composition_poly_degree_bound() / self.trace_length();
+        let masking_degree = self.zk_masking_degree();
+        if masking_degree == 0 {
+            return number_of_parts;
+        }
+        let max_constraint_degree = self
+            .transition_constraints()
+            .iter()
+            .map(|c| c.degree())
+            .max()
+            .unwrap_or(1)
+            .max(1);
+        number_of_parts
+            + Integer::div_ceil(
+                &(max_constraint_degree * masking_degree),
+                &self.trace_length(),
+            )
+    }
+
     /// The method called by the prover to evaluate the transitions corresponding to an evaluation frame.
     /// In the case of the prover, the main evaluation table of the frame takes values in
     /// `Self::Field`, since they are the evaluations of the main trace at the LDE domain.
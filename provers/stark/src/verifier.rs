@@ -1,12 +1,12 @@
 use super::{
     config::BatchedMerkleTreeBackend,
     domain::Domain,
-    fri::fri_decommit::FriDecommitment,
+    fri::{self, fri_decommit::FriDecommitment},
     grinding,
     proof::{options::ProofOptions, stark::StarkProof},
     traits::AIR,
 };
-use crate::{config::Commitment, proof::stark::DeepPolynomialOpening};
+use crate::{config::Commitment, preprocessing::VerifyingKey, proof::stark::DeepPolynomialOpening};
 use lambdaworks_crypto::{fiat_shamir::is_transcript::IsTranscript, merkle_tree::proof::Proof};
 use lambdaworks_math::{
     fft::cpu::bit_reversing::reverse_index,
@@ -14,6 +14,7 @@ use lambdaworks_math::{
         element::FieldElement,
         traits::{IsFFTField, IsField, IsSubFieldOf},
     },
+    polynomial::Polynomial,
     traits::AsBytes,
 };
 #[cfg(not(feature = "test_fiat_shamir"))]
@@ -60,17 +61,29 @@ pub type DeepPolynomialEvaluations<F> = (Vec<FieldElement<F>>, Vec<FieldElement<
 /// The functionality of a STARK verifier providing methods to run the STARK Verify protocol
 /// https://lambdaclass.github.io/lambdaworks/starks/protocol.html
 pub trait IsStarkVerifier<A: AIR> {
+    /// Samples the query indexes, which are indexes of the layer obtained by folding the LDE
+    /// domain in two `first_step` times.
     fn sample_query_indexes(
         number_of_queries: usize,
         domain: &Domain<A::Field>,
+        first_step: usize,
         transcript: &mut impl IsTranscript<A::FieldExtension>,
     ) -> Vec<usize> {
         let domain_size = domain.lde_roots_of_unity_coset.len() as u64;
         (0..number_of_queries)
-            .map(|_| (transcript.sample_u64(domain_size >> 1)) as usize)
+            .map(|_| (transcript.sample_u64(domain_size >> first_step)) as usize)
             .collect::<Vec<usize>>()
     }
 
+    /// Returns the indexes of the pairs of symmetric elements of the LDE domain that are folded
+    /// into the queries `iotas` by the first step of FRI, query by query.
+    fn first_layer_pair_indexes(iotas: &[usize], first_step: usize) -> Vec<usize> {
+        iotas
+            .iter()
+            .flat_map(|iota| fri::first_layer_pair_indexes(*iota, first_step))
+            .collect()
+    }
+
     /// Returns the list of challenges sent to the prover.
     fn step_1_replay_rounds_and_recover_challenges(
         air: &A,
@@ -142,7 +155,8 @@ pub trait IsStarkVerifier<A: AIR> {
         // ===================================
 
         let n_terms_composition_poly = proof.composition_poly_parts_ood_evaluation.len();
-        let n_terms_trace = air.context().transition_offsets.len() * air.context().trace_columns;
+        let n_terms_trace = air.context().transition_offsets.len()
+            * (air.context().trace_columns + air.num_preprocessed_columns());
         let gamma = transcript.sample_field_element();
 
         // <<<< Receive challenges: 𝛾, 𝛾'
@@ -177,8 +191,10 @@ pub trait IsStarkVerifier<A: AIR> {
         // >>>> Send challenge 𝜁ₙ₋₁
         zetas.push(transcript.sample_field_element());
 
-        // <<<< Receive value: pₙ
-        transcript.append_field_element(&proof.fri_last_value);
+        // <<<< Receive coefficients: pₙ
+        for coefficient in proof.fri_last_layer_coefficients.iter() {
+            transcript.append_field_element(coefficient);
+        }
 
         // Receive grinding value
         let security_bits = air.context().proof_options.grinding_factor;
@@ -192,8 +208,11 @@ pub trait IsStarkVerifier<A: AIR> {
 
         // FRI query phase
         // <<<< Send challenges 𝜄ₛ (iota_s)
+        // The proofs with FRI steps that don't fold the DEEP composition polynomial down to the
+        // last layer are rejected by `verify` before sampling the queries.
         let number_of_queries = air.options().fri_number_of_queries;
-        let iotas = Self::sample_query_indexes(number_of_queries, domain, transcript);
+        let first_step = fri::fri_folding_steps(air).map_or(1, |steps| steps[0]);
+        let iotas = Self::sample_query_indexes(number_of_queries, domain, first_step, transcript);
 
         Challenges {
             z,
@@ -233,7 +252,8 @@ pub trait IsStarkVerifier<A: AIR> {
                 let point = &domain.trace_primitive_root.pow(step as u64);
                 let column_idx = boundary_constraints.constraints[index].col;
                 let trace_evaluation = if is_aux {
-                    let column_idx = air.trace_layout().0 + column_idx;
+                    let column_idx =
+                        air.trace_layout().0 + air.num_preprocessed_columns() + column_idx;
                     &proof.trace_ood_evaluations.get_row(0)[column_idx]
                 } else {
                     &proof.trace_ood_evaluations.get_row(0)[column_idx]
@@ -298,8 +318,11 @@ pub trait IsStarkVerifier<A: AIR> {
         let composition_poly_ood_evaluation =
             &boundary_quotient_ood_evaluation + transition_c_i_evaluations_sum;
 
-        let composition_poly_claimed_ood_evaluation = proof
-            .composition_poly_parts_ood_evaluation
+        // In zero-knowledge mode, the last evaluation belongs to the random part, which is not
+        // a part of the composition polynomial.
+        let number_of_parts = air.num_composition_poly_parts();
+        let composition_poly_claimed_ood_evaluation = proof.composition_poly_parts_ood_evaluation
+            [..number_of_parts]
             .iter()
             .rev()
             .fold(FieldElement::zero(), |acc, coeff| {
@@ -309,51 +332,94 @@ pub trait IsStarkVerifier<A: AIR> {
         composition_poly_claimed_ood_evaluation == composition_poly_ood_evaluation
     }
 
-    /// Reconstructs the Deep composition polynomial evaluations at the challenge indices values using the provided
-    /// openings of the trace polynomials and the composition polynomial parts. It then uses these to verify that the
-    /// FRI decommitments are valid and correspond to the Deep composition polynomial.
+    /// Reconstructs the Deep composition polynomial evaluations at the pairs of symmetric elements
+    /// folded into the challenge indices, using the provided openings of the trace polynomials and
+    /// the composition polynomial parts. It then uses these to verify that the FRI decommitments
+    /// are valid and correspond to the Deep composition polynomial. `fri_steps` are the number of
+    /// times each layer of FRI is folded in two.
     fn step_3_verify_fri(
+        air: &A,
         proof: &StarkProof<A::Field, A::FieldExtension>,
         domain: &Domain<A::Field>,
         challenges: &Challenges<A>,
+        fri_steps: &[usize],
     ) -> bool
     where
         FieldElement<A::Field>: AsBytes + Sync + Send,
         FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
     {
+        let pair_indexes = Self::first_layer_pair_indexes(&challenges.iotas, fri_steps[0]);
         let (deep_poly_evaluations, deep_poly_evaluations_sym) =
             Self::reconstruct_deep_composition_poly_evaluations_for_all_queries(
-                challenges, domain, proof,
+                air,
+                challenges,
+                domain,
+                proof,
+                &pair_indexes,
             );
 
         // verify FRI
-        let mut evaluation_point_inverse = challenges
-            .iotas
-            .iter()
-            .map(|iota| Self::query_challenge_to_evaluation_point(*iota, domain))
-            .collect::<Vec<FieldElement<A::Field>>>();
-        FieldElement::inplace_batch_inverse(&mut evaluation_point_inverse).unwrap();
-
+        let pairs_per_query = 1 << (fri_steps[0] - 1);
         proof
             .query_list
             .iter()
             .zip(&challenges.iotas)
-            .zip(evaluation_point_inverse)
             .enumerate()
-            .fold(true, |mut result, (i, ((proof_s, iota_s), eval))| {
+            .fold(true, |mut result, (i, (proof_s, iota_s))| {
+                // The evaluations of p₀ on the coset folded into the query, in bit-reversed order.
+                let pairs = i * pairs_per_query..(i + 1) * pairs_per_query;
+                let deep_composition_coset_evaluations = deep_poly_evaluations[pairs.clone()]
+                    .iter()
+                    .zip(&deep_poly_evaluations_sym[pairs])
+                    .flat_map(|(evaluation, evaluation_sym)| {
+                        [evaluation.clone(), evaluation_sym.clone()]
+                    })
+                    .collect();
                 result &= Self::verify_query_and_sym_openings(
                     proof,
+                    domain,
+                    fri_steps,
                     &challenges.zetas,
                     *iota_s,
                     proof_s,
-                    eval,
-                    &deep_poly_evaluations[i],
-                    &deep_poly_evaluations_sym[i],
+                    deep_composition_coset_evaluations,
                 );
                 result
             })
     }
 
+    /// Returns the element of position `position`, in bit-reversed order, of the domain of the
+    /// layer of FRI obtained by folding the LDE domain `domain` in two `number_of_foldings` times.
+    fn fri_layer_point(
+        domain: &Domain<A::Field>,
+        position: usize,
+        number_of_foldings: usize,
+    ) -> FieldElement<A::Field> {
+        // Folding maps the elements of positions 2i and 2i + 1 to the element of position i.
+        let lde_domain_size = domain.lde_roots_of_unity_coset.len() as u64;
+        domain.lde_roots_of_unity_coset
+            [reverse_index(position << number_of_foldings, lde_domain_size)]
+        .pow(1_u64 << number_of_foldings)
+    }
+
+    /// Returns the inverses of the elements of the even positions of the coset `coset_index`,
+    /// of size `2^step`, of the domain of the layer of FRI obtained by folding the LDE domain
+    /// `domain` in two `number_of_foldings` times.
+    fn fri_coset_points_inv(
+        domain: &Domain<A::Field>,
+        coset_index: usize,
+        step: usize,
+        number_of_foldings: usize,
+    ) -> Vec<FieldElement<A::Field>> {
+        let coset_start = coset_index << step;
+        let mut points: Vec<_> = (coset_start..coset_start + (1 << step))
+            .step_by(2)
+            .map(|position| Self::fri_layer_point(domain, position, number_of_foldings))
+            .collect();
+        FieldElement::inplace_batch_inverse(&mut points).unwrap();
+        points
+    }
+
     /// Returns the field element element of the domain `domain` corresponding to the given FRI query index challenge `iota`.
     fn query_challenge_to_evaluation_point(
         iota: usize,
@@ -391,10 +457,12 @@ pub trait IsStarkVerifier<A: AIR> {
     }
 
     /// Verify opening Open(tⱼ(D_LDE), 𝜐) and Open(tⱼ(D_LDE), -𝜐) for all trace polynomials tⱼ,
-    /// where 𝜐 and -𝜐 are the elements corresponding to the index challenge `iota`.
+    /// where 𝜐 and -𝜐 are the elements corresponding to the index challenge `iota`. The
+    /// openings of the preprocessed columns are checked against `verifying_key`.
     fn verify_trace_openings(
         proof: &StarkProof<A::Field, A::FieldExtension>,
         deep_poly_openings: &DeepPolynomialOpening<A::Field, A::FieldExtension>,
+        verifying_key: Option<&VerifyingKey>,
         iota: usize,
     ) -> bool
     where
@@ -418,6 +486,26 @@ pub trait IsStarkVerifier<A: AIR> {
             &deep_poly_openings.main_trace_polys.evaluations_sym,
         );
 
+        match (verifying_key, &deep_poly_openings.preprocessed_trace_polys) {
+            (None, Some(_)) => result = false,
+            (Some(_), None) => result = false,
+            (Some(verifying_key), Some(preprocessed_trace_polys_opening)) => {
+                result &= Self::verify_opening::<A::Field>(
+                    &preprocessed_trace_polys_opening.proof,
+                    &verifying_key.preprocessed_root,
+                    index,
+                    &preprocessed_trace_polys_opening.evaluations,
+                );
+                result &= Self::verify_opening::<A::Field>(
+                    &preprocessed_trace_polys_opening.proof_sym,
+                    &verifying_key.preprocessed_root,
+                    index_sym,
+                    &preprocessed_trace_polys_opening.evaluations_sym,
+                );
+            }
+            _ => {}
+        }
+
         match (
             proof.lde_trace_aux_merkle_root,
             &deep_poly_openings.aux_trace_polys,
@@ -469,17 +557,20 @@ pub trait IsStarkVerifier<A: AIR> {
     }
 
     /// Verifies the validity of the purported values of the trace polynomials and the composition polynomial
-    /// parts at the domain elements and their symmetric counterparts corresponding to all the FRI query
-    /// index challenges.
+    /// parts at the pairs of symmetric domain elements that the first step of FRI, which folds
+    /// `first_step` times, folds into the query index challenges.
     fn step_4_verify_trace_and_composition_openings(
         proof: &StarkProof<A::Field, A::FieldExtension>,
+        verifying_key: Option<&VerifyingKey>,
         challenges: &Challenges<A>,
+        first_step: usize,
     ) -> bool
     where
         FieldElement<A::Field>: AsBytes + Sync + Send,
         FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
     {
-        challenges.iotas.iter().zip(&proof.deep_poly_openings).fold(
+        let pair_indexes = Self::first_layer_pair_indexes(&challenges.iotas, first_step);
+        pair_indexes.iter().zip(&proof.deep_poly_openings).fold(
             true,
             |mut result, (iota_n, deep_poly_opening)| {
                 result &= Self::verify_composition_poly_opening(
@@ -488,127 +579,99 @@ pub trait IsStarkVerifier<A: AIR> {
                     iota_n,
                 );
 
-                result &= Self::verify_trace_openings(proof, deep_poly_opening, *iota_n);
+                result &=
+                    Self::verify_trace_openings(proof, deep_poly_opening, verifying_key, *iota_n);
                 result
             },
         )
     }
 
-    /// Verifies the openings of a fold polynomial of an inner layer of FRI.
-    fn verify_fri_layer_openings(
-        merkle_root: &Commitment,
-        auth_path_sym: &Proof<Commitment>,
-        evaluation: &FieldElement<A::FieldExtension>,
-        evaluation_sym: &FieldElement<A::FieldExtension>,
-        iota: usize,
-    ) -> bool
-    where
-        FieldElement<A::Field>: AsBytes + Sync + Send,
-        FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
-    {
-        let evaluations = if iota % 2 == 1 {
-            vec![evaluation_sym.clone(), evaluation.clone()]
-        } else {
-            vec![evaluation.clone(), evaluation_sym.clone()]
-        };
-
-        auth_path_sym.verify::<BatchedMerkleTreeBackend<A::FieldExtension>>(
-            merkle_root,
-            iota >> 1,
-            &evaluations,
-        )
-    }
-
     /// Verify a single FRI query
+    /// `fri_steps`: the number of times each layer of FRI is folded in two.
     /// `zetas`: the vector of all challenges sent by the verifier to the prover at the commit
     /// phase to fold polynomials.
-    /// `iota`: the index challenge of this FRI query. This index uniquely determines two elements 𝜐 and -𝜐
-    /// of the evaluation domain of FRI layer 0.
-    /// `evaluation_point_inv`: precomputed value of 𝜐⁻¹.
-    /// `deep_composition_evaluation`: precomputed value of p₀(𝜐), where p₀ is the deep composition polynomial.
-    /// `deep_composition_evaluation_sym`: precomputed value of p₀(-𝜐), where p₀ is the deep composition polynomial.
+    /// `iota`: the index challenge of this FRI query. This index uniquely determines a coset of
+    /// the evaluation domain of FRI layer 0 that the first step folds into one element.
+    /// `deep_composition_coset_evaluations`: precomputed values of p₀, the deep composition
+    /// polynomial, on that coset, in bit-reversed order.
     fn verify_query_and_sym_openings(
         proof: &StarkProof<A::Field, A::FieldExtension>,
+        domain: &Domain<A::Field>,
+        fri_steps: &[usize],
         zetas: &[FieldElement<A::FieldExtension>],
         iota: usize,
         fri_decommitment: &FriDecommitment<A::FieldExtension>,
-        evaluation_point_inv: FieldElement<A::Field>,
-        deep_composition_evaluation: &FieldElement<A::FieldExtension>,
-        deep_composition_evaluation_sym: &FieldElement<A::FieldExtension>,
+        deep_composition_coset_evaluations: Vec<FieldElement<A::FieldExtension>>,
     ) -> bool
     where
         FieldElement<A::Field>: AsBytes + Sync + Send,
         FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
     {
         let fri_layers_merkle_roots = &proof.fri_layers_merkle_roots;
-        let evaluation_point_vec: Vec<FieldElement<A::Field>> =
-            core::iter::successors(Some(evaluation_point_inv.square()), |evaluation_point| {
-                Some(evaluation_point.square())
-            })
-            .take(fri_layers_merkle_roots.len())
-            .collect();
-
-        let p0_eval = deep_composition_evaluation;
-        let p0_eval_sym = deep_composition_evaluation_sym;
+        if fri_decommitment.layers_auth_paths.len() != fri_layers_merkle_roots.len()
+            || fri_decommitment.layers_evaluations_coset.len() != fri_layers_merkle_roots.len()
+        {
+            return false;
+        }
 
-        // Reconstruct p₁(𝜐²)
-        let mut v =
-            (p0_eval + p0_eval_sym) + evaluation_point_inv * &zetas[0] * (p0_eval - p0_eval_sym);
+        // Reconstruct p₁(𝜐), where 𝜐 is the element of the query in the domain of layer 1.
+        let points_inv = Self::fri_coset_points_inv(domain, iota, fri_steps[0], 0);
+        let mut v = fri::fold_coset(deep_composition_coset_evaluations, points_inv, &zetas[0]);
+        let mut number_of_foldings = fri_steps[0];
         let mut index = iota;
 
-        // For each FRI layer, starting from the layer 1: use the proof to verify the validity of values pᵢ(−𝜐^(2ⁱ)) (given by the prover) and
-        // pᵢ(𝜐^(2ⁱ)) (computed on the previous iteration by the verifier). Then use them to obtain pᵢ₊₁(𝜐^(2ⁱ⁺¹)).
-        // Finally, check that the final value coincides with the given by the prover.
-        fri_layers_merkle_roots
+        // For each FRI layer, starting from the layer 1: use the proof to verify the validity of
+        // the values of pₖ on the coset of 𝜐 (given by the prover, except pₖ(𝜐), which was
+        // computed on the previous iteration by the verifier). Then use them to obtain pₖ₊₁(𝜐'),
+        // where 𝜐' is the element the coset is folded into.
+        for (k, ((merkle_root, auth_path), evaluations_coset)) in fri_layers_merkle_roots
             .iter()
-            .enumerate()
             .zip(&fri_decommitment.layers_auth_paths)
-            .zip(&fri_decommitment.layers_evaluations_sym)
-            .zip(evaluation_point_vec)
-            .fold(
-                true,
-                |result,
-                 (
-                    (((i, merkle_root), auth_path_sym), evaluation_sym),
-                    evaluation_point_inv,
-                )| {
-                    // Verify opening Open(pᵢ(Dₖ), −𝜐^(2ⁱ)) and Open(pᵢ(Dₖ), 𝜐^(2ⁱ)).
-                    // `v` is pᵢ(𝜐^(2ⁱ)).
-                    // `evaluation_sym` is pᵢ(−𝜐^(2ⁱ)).
-                    let openings_ok = Self::verify_fri_layer_openings(
-                        merkle_root,
-                        auth_path_sym,
-                        &v,
-                        evaluation_sym,
-                        index,
-                    );
-
-                    // Update `v` with next value pᵢ₊₁(𝜐^(2ⁱ⁺¹)).
-                    v = (&v + evaluation_sym) + evaluation_point_inv * &zetas[i + 1] * (&v - evaluation_sym);
-
-                    // Update index for next iteration. The index of the squares in the next layer
-                    // is obtained by halving the current index. This is due to the bit-reverse
-                    // ordering of the elements in the Merkle tree.
-                    index >>= 1;
-
-                    if i < fri_decommitment.layers_evaluations_sym.len() - 1 {
-                        result & openings_ok
-                    } else {
-                        // Check that final value is the given by the prover
-                        result & (v == proof.fri_last_value) & openings_ok
-                    }
-                },
-            )
+            .zip(&fri_decommitment.layers_evaluations_coset)
+            .enumerate()
+        {
+            let step = fri_steps[k + 1];
+            let coset_size = 1 << step;
+            if evaluations_coset.len() != coset_size - 1 {
+                return false;
+            }
+            let coset_index = index >> step;
+            let mut evaluations = evaluations_coset.clone();
+            evaluations.insert(index & (coset_size - 1), v);
+
+            // Verify opening Open(pₖ(Dₖ), coset of 𝜐).
+            if !auth_path.verify::<BatchedMerkleTreeBackend<A::FieldExtension>>(
+                merkle_root,
+                coset_index,
+                &evaluations,
+            ) {
+                return false;
+            }
+
+            let points_inv =
+                Self::fri_coset_points_inv(domain, coset_index, step, number_of_foldings);
+            v = fri::fold_coset(evaluations, points_inv, &zetas[k + 1]);
+            number_of_foldings += step;
+            index = coset_index;
+        }
+
+        // Check that the final value is the evaluation of the last layer given by the prover.
+        let point = Self::fri_layer_point(domain, index, number_of_foldings);
+        v == Polynomial::new(&proof.fri_last_layer_coefficients).evaluate(&point.to_extension())
     }
 
+    /// Reconstructs the evaluations of the Deep composition polynomial at the pairs of symmetric
+    /// elements `pair_indexes` of the LDE domain, from the openings of the proof in the same order.
     fn reconstruct_deep_composition_poly_evaluations_for_all_queries(
+        air: &A,
         challenges: &Challenges<A>,
         domain: &Domain<A::Field>,
         proof: &StarkProof<A::Field, A::FieldExtension>,
+        pair_indexes: &[usize],
     ) -> DeepPolynomialEvaluations<A::FieldExtension> {
         let mut deep_poly_evaluations = Vec::new();
         let mut deep_poly_evaluations_sym = Vec::new();
-        for (i, iota) in challenges.iotas.iter().enumerate() {
+        for (i, iota) in pair_indexes.iter().enumerate() {
             let primitive_root =
                 &A::Field::get_primitive_root_of_unity(domain.root_order as u64).unwrap();
 
@@ -619,12 +682,23 @@ pub trait IsStarkVerifier<A: AIR> {
                 .into_iter()
                 .map(|x| x.to_extension())
                 .collect();
+            if let Some(preprocessed_trace_polys) =
+                &proof.deep_poly_openings[i].preprocessed_trace_polys
+            {
+                evaluations.extend(
+                    preprocessed_trace_polys
+                        .evaluations
+                        .iter()
+                        .map(|x| x.clone().to_extension()),
+                );
+            }
             if let Some(aux_trace_polys) = &proof.deep_poly_openings[i].aux_trace_polys {
                 evaluations.extend_from_slice(&aux_trace_polys.evaluations);
             }
 
             let evaluation_point = Self::query_challenge_to_evaluation_point(*iota, domain);
             deep_poly_evaluations.push(Self::reconstruct_deep_composition_poly_evaluation(
+                air,
                 proof,
                 &evaluation_point,
                 primitive_root,
@@ -641,12 +715,23 @@ pub trait IsStarkVerifier<A: AIR> {
                 .into_iter()
                 .map(|x| x.to_extension())
                 .collect();
+            if let Some(preprocessed_trace_polys) =
+                &proof.deep_poly_openings[i].preprocessed_trace_polys
+            {
+                evaluations_sym.extend(
+                    preprocessed_trace_polys
+                        .evaluations_sym
+                        .iter()
+                        .map(|x| x.clone().to_extension()),
+                );
+            }
             if let Some(aux_trace_polys) = &proof.deep_poly_openings[i].aux_trace_polys {
                 evaluations_sym.extend_from_slice(&aux_trace_polys.evaluations_sym);
             }
 
             let evaluation_point = Self::query_challenge_to_evaluation_point_sym(*iota, domain);
             deep_poly_evaluations_sym.push(Self::reconstruct_deep_composition_poly_evaluation(
+                air,
                 proof,
                 &evaluation_point,
                 primitive_root,
@@ -659,6 +744,7 @@ pub trait IsStarkVerifier<A: AIR> {
     }
 
     fn reconstruct_deep_composition_poly_evaluation(
+        air: &A,
         proof: &StarkProof<A::Field, A::FieldExtension>,
         evaluation_point: &FieldElement<A::Field>,
         primitive_root: &FieldElement<A::Field>,
@@ -686,7 +772,7 @@ pub trait IsStarkVerifier<A: AIR> {
                 trace_terms + trace_i
             });
 
-        let number_of_parts = lde_composition_poly_parts_evaluation.len();
+        let number_of_parts = air.num_composition_poly_parts();
         let z_pow = &challenges.z.pow(number_of_parts);
 
         let denom_composition = (evaluation_point - z_pow).inv().unwrap();
@@ -701,12 +787,31 @@ pub trait IsStarkVerifier<A: AIR> {
         trace_term + h_terms
     }
 
-    /// Verifies a STARK proof with public inputs `pub_inputs`.
+    /// Verifies a STARK proof with public inputs `pub_inputs`. The AIR must not have
+    /// preprocessed columns; use `verify_with_preprocessed_columns` for those that do.
     /// Warning: the transcript must be safely initializated before passing it to this method.
     fn verify(
         proof: &StarkProof<A::Field, A::FieldExtension>,
         pub_input: &A::PublicInputs,
         proof_options: &ProofOptions,
+        transcript: impl IsTranscript<A::FieldExtension>,
+    ) -> bool
+    where
+        FieldElement<A::Field>: AsBytes + Sync + Send,
+        FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
+    {
+        Self::verify_with_preprocessed_columns(proof, pub_input, proof_options, None, transcript)
+    }
+
+    /// Verifies a STARK proof with public inputs `pub_inputs`. The openings of the preprocessed
+    /// columns of the AIR are checked against `verifying_key`, the commitment computed by
+    /// `crate::preprocessing::setup`, which must be given if and only if the AIR has any.
+    /// Warning: the transcript must be safely initializated before passing it to this method.
+    fn verify_with_preprocessed_columns(
+        proof: &StarkProof<A::Field, A::FieldExtension>,
+        pub_input: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        verifying_key: Option<&VerifyingKey>,
         mut transcript: impl IsTranscript<A::FieldExtension>,
     ) -> bool
     where
@@ -723,9 +828,68 @@ pub trait IsStarkVerifier<A: AIR> {
         #[cfg(feature = "instruments")]
         let timer1 = Instant::now();
 
-        let air = A::new(proof.trace_length, pub_input, proof_options);
+        let air = match A::new(proof.trace_length, pub_input, proof_options) {
+            Ok(air) => air,
+            Err(_) => {
+                error!("The AIR can't be built for the public inputs");
+                return false;
+            }
+        };
         let domain = Domain::new(&air);
 
+        // Verify the number of parts of the composition polynomial, including the random part
+        // in zero-knowledge mode
+        let number_of_parts =
+            air.num_composition_poly_parts() + usize::from(proof_options.zero_knowledge);
+        if proof.composition_poly_parts_ood_evaluation.len() != number_of_parts {
+            error!("Wrong number of composition polynomial parts");
+            return false;
+        }
+
+        // Verify the number of FRI layers and the size of the last one. The DEEP composition
+        // polynomial has degree less than twice the trace length in zero-knowledge mode, which
+        // needs one more folding.
+        let fri_steps = match fri::fri_folding_steps(&air) {
+            Some(fri_steps) => fri_steps,
+            None => {
+                error!("The FRI steps don't fold down to the degree bound of the last layer");
+                return false;
+            }
+        };
+        if proof.fri_layers_merkle_roots.len() + 1 != fri_steps.len() {
+            error!("Wrong number of FRI layers");
+            return false;
+        }
+        if proof.fri_last_layer_coefficients.len() != proof_options.fri_last_layer_degree_bound {
+            error!("Wrong number of coefficients of the last FRI layer");
+            return false;
+        }
+        let pairs_per_query = 1 << (fri_steps[0] - 1);
+        if proof.deep_poly_openings.len() < proof_options.fri_number_of_queries * pairs_per_query {
+            error!("Wrong number of openings of the DEEP composition polynomial");
+            return false;
+        }
+
+        // Verify the verifying key matches the preprocessed columns of the AIR
+        let num_preprocessed_columns = air.num_preprocessed_columns();
+        let verifying_key_is_valid = match verifying_key {
+            None => num_preprocessed_columns == 0,
+            Some(key) => {
+                num_preprocessed_columns > 0
+                    && key.num_preprocessed_columns == num_preprocessed_columns
+                    && key.matches(proof.trace_length, proof_options)
+            }
+        };
+        if !verifying_key_is_valid {
+            error!("Verifying key does not match the preprocessed columns of the AIR");
+            return false;
+        }
+
+        // <<<< Receive commitment: [preprocessed columns], fixed at setup
+        if let Some(key) = verifying_key {
+            transcript.append_bytes(&key.preprocessed_root);
+        }
+
         let challenges = Self::step_1_replay_rounds_and_recover_challenges(
             &air,
             proof,
@@ -771,7 +935,7 @@ pub trait IsStarkVerifier<A: AIR> {
         #[cfg(feature = "instruments")]
         let timer3 = Instant::now();
 
-        if !Self::step_3_verify_fri(proof, &domain, &challenges) {
+        if !Self::step_3_verify_fri(&air, proof, &domain, &challenges, &fri_steps) {
             error!("FRI verification failed");
             return false;
         }
@@ -787,7 +951,12 @@ pub trait IsStarkVerifier<A: AIR> {
         let timer4 = Instant::now();
 
         #[allow(clippy::let_and_return)]
-        if !Self::step_4_verify_trace_and_composition_openings(proof, &challenges) {
+        if !Self::step_4_verify_trace_and_composition_openings(
+            proof,
+            verifying_key,
+            &challenges,
+            fri_steps[0],
+        ) {
             error!("DEEP Composition Polynomial verification failed");
             return false;
         }
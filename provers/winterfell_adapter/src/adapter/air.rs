@@ -7,6 +7,7 @@ use lambdaworks_math::traits::ByteConversion;
 use miden_core::Felt;
 use stark_platinum_prover::{
     constraints::boundary::{BoundaryConstraint, BoundaryConstraints},
+    prover::ProvingError,
     traits::AIR,
 };
 use std::marker::PhantomData;
@@ -100,7 +101,7 @@ where
         _trace_length: usize,
         pub_inputs: &Self::PublicInputs,
         lambda_proof_options: &stark_platinum_prover::proof::options::ProofOptions,
-    ) -> Self {
+    ) -> Result<Self, ProvingError> {
         let winter_proof_options = ProofOptions::new(
             lambda_proof_options.fri_number_of_queries,
             lambda_proof_options.blowup_factor as usize,
@@ -125,13 +126,13 @@ where
             trace_columns: pub_inputs.trace_info.width(),
         };
 
-        Self {
+        Ok(Self {
             winterfell_air,
             public_inputs: pub_inputs.clone(),
             air_context: lambda_context,
             trace: PhantomData,
             extension: PhantomData,
-        }
+        })
     }
 
     fn build_auxiliary_trace(